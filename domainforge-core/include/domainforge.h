@@ -0,0 +1,173 @@
+/* domainforge.h: C ABI of domainforge-core (cargo feature `capi`).
+ * Generated from src/capi/mod.rs by tests/capi_tests.rs; do not edit. */
+
+#ifndef DOMAINFORGE_H
+#define DOMAINFORGE_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Version of this ABI; bumped on any incompatible change to the header. */
+#define DF_ABI_VERSION 1u
+
+/** The call succeeded and its `out_*` results are set. */
+#define DF_OK 0
+
+/** A required pointer argument was NULL. */
+#define DF_ERR_NULL_ARGUMENT 1
+
+/** A string argument was not valid UTF-8. */
+#define DF_ERR_INVALID_UTF8 2
+
+/** A JSON argument did not parse or did not match its schema. */
+#define DF_ERR_INVALID_JSON 3
+
+/** An argument was well-formed but not accepted (unknown format or policy). */
+#define DF_ERR_INVALID_ARGUMENT 4
+
+/** SEA source failed to parse; the error `code` is a `ValidationError` code. */
+#define DF_ERR_PARSE 5
+
+/** The workspace did not resolve; the error carries APP `diagnostics`. */
+#define DF_ERR_RESOLUTION 6
+
+/**
+ * The authority environment rejected the call; the error `code` is an
+ * `AuthorityErrorCode`.
+ */
+#define DF_ERR_AUTHORITY 7
+
+/** A projection failed to render. */
+#define DF_ERR_PROJECTION 8
+
+/**
+ * A handle argument was not NULL but cannot serve the call (a workspace
+ * without a graph).
+ */
+#define DF_ERR_INVALID_HANDLE 9
+
+/**
+ * The core panicked; the call had no effect. Only builds that unwind return
+ * this: the workspace release profile sets `panic = 'abort'`, so in a
+ * release build a panic aborts the host process instead.
+ */
+#define DF_ERR_PANIC 99
+
+/**
+ * A resolved workspace: the graph and semantic envelope of one entry module
+ * and its import closure.
+ */
+typedef struct DfWorkspace DfWorkspace;
+
+/** ABI version of the loaded library (`DF_ABI_VERSION` it was built with). */
+uint32_t df_abi_version(void);
+
+/** Version of domainforge-core, as a static string the caller must not free. */
+const char *df_version(void);
+
+/**
+ * Release a string returned through an `out_json` or `out_error` pointer.
+ * NULL is ignored.
+ *
+ * Safety:
+ * `value` must be NULL or a string returned by this library, freed once.
+ */
+void df_string_free(char *value);
+
+/**
+ * Parse one SEA source into AST v3 JSON (`ast-v3.schema.json`).
+ *
+ * Safety:
+ * `source` must be a NUL-terminated string; `out_json` and `out_error`
+ * must be NULL or valid `char **`.
+ */
+int32_t df_parse(const char *source, char **out_json, char **out_error);
+
+/**
+ * Resolve an entry module and its import closure from `sources_json` (an
+ * object of logical path to SEA source), with an optional
+ * `.sea-registry.toml` in `registry_toml` (NULL for none). On success
+ * `*out_workspace` is a handle to release with `df_workspace_free`.
+ *
+ * Safety:
+ * `entry_logical_path` and `sources_json` must be NUL-terminated strings,
+ * `registry_toml` NULL or one; `out_workspace` must be a valid
+ * `DfWorkspace **` and `out_error` NULL or a valid `char **`.
+ */
+int32_t df_workspace_resolve(const char *entry_logical_path, const char *sources_json, const char *registry_toml, DfWorkspace **out_workspace, char **out_error);
+
+/**
+ * Release a workspace from `df_workspace_resolve`. NULL is ignored.
+ *
+ * Safety:
+ * `workspace` must be NULL or a live handle, released once.
+ */
+void df_workspace_free(DfWorkspace *workspace);
+
+/**
+ * The workspace's canonical semantic envelope document.
+ *
+ * Safety:
+ * `workspace` must be a live handle; `out_json` and `out_error` must be
+ * NULL or valid `char **`.
+ */
+int32_t df_workspace_envelope(const DfWorkspace *workspace, char **out_json, char **out_error);
+
+/**
+ * Validate the workspace as `domainforge validate` does: every policy and
+ * entity-instance check. The result is `{"valid", "total_policies",
+ * "error_count", "violations"}`; violations are not a call failure.
+ *
+ * Safety:
+ * As for `df_workspace_envelope`.
+ */
+int32_t df_workspace_validate(const DfWorkspace *workspace, char **out_json, char **out_error);
+
+/**
+ * Evaluate the workspace's policies, or only `policy_name` when it is not
+ * NULL. The result is `{"policies": [{"name", "namespace", "english",
+ * "satisfied", "violations"}], "violation_count"}`, with `satisfied` null
+ * when a policy evaluates to unknown and `error` in place of the result
+ * when it cannot be evaluated.
+ *
+ * Safety:
+ * `workspace` must be a live handle and `policy_name` NULL or a
+ * NUL-terminated string; `out_json` and `out_error` must be NULL or valid
+ * `char **`.
+ */
+int32_t df_workspace_evaluate_policies(const DfWorkspace *workspace, const char *policy_name, char **out_json, char **out_error);
+
+/**
+ * Decide an authority request: `config_json` is an
+ * `AuthorityEnvironmentConfig`, `request_json` an `AuthorityRequest` and
+ * `facts_json` a `FactEnvelope` array (NULL for none). The result is
+ * `{"decision", "trace"}`, as the MCP `authority_decision` tool returns it.
+ *
+ * Safety:
+ * `config_json` and `request_json` must be NUL-terminated strings and
+ * `facts_json` NULL or one; `out_json` and `out_error` must be NULL or
+ * valid `char **`.
+ */
+int32_t df_authority_decide(const char *config_json, const char *request_json, const char *facts_json, char **out_json, char **out_error);
+
+/**
+ * Render a projection of the workspace into memory: `format` is a
+ * `domainforge project --format` name and `options_json` (NULL for
+ * defaults) an object of `model_ref`, `created_at` and the format's own
+ * options. The result maps each relative artifact path to its contents.
+ *
+ * Safety:
+ * `workspace` must be a live handle, `format` a NUL-terminated string and
+ * `options_json` NULL or one; `out_json` and `out_error` must be NULL or
+ * valid `char **`.
+ */
+int32_t df_workspace_project(const DfWorkspace *workspace, const char *format, const char *options_json, char **out_json, char **out_error);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DOMAINFORGE_H */
@@ -307,6 +307,8 @@ pub enum TypedValue {
     Bool(bool),
     Timestamp(chrono::DateTime<chrono::Utc>),
     Uuid(uuid::Uuid),
+    /// `base_value` is the magnitude in `unit`: resolved defaults are
+    /// normalized to the base unit, payload values keep the field's unit.
     Quantity {
         base_value: rust_decimal::Decimal,
         unit: ConceptId,
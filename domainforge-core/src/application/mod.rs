@@ -4,9 +4,11 @@ pub mod canonical;
 pub mod contract;
 pub mod diagnostic;
 pub mod envelope;
+pub mod payload;
 pub mod policy_context;
 pub mod resolve;
 pub(crate) mod validate;
+pub mod workspace;
 
 pub use canonical::{
     canonical_decimal, canonical_typed_value, document_self_hash, input_fingerprint,
@@ -21,9 +23,13 @@ pub use envelope::{
     validate_application_contract_document_json, validate_semantic_envelope_document_json,
     CanonicalSemanticEnvelope, CanonicalSemanticEnvelopeDocument,
 };
+pub use payload::{
+    validate_payload, PayloadDiagnostic, PayloadDiagnosticKind, PayloadOptions, PayloadReport,
+};
 pub use policy_context::{evaluate_precondition, EvaluationResult};
 pub use resolve::{
     resolve_application_contract, resolve_application_contract_json,
     resolve_application_contract_with_packs, resolve_application_graph, SOURCE_MAP_MAX_BYTES,
     SOURCE_MAP_MAX_MODULES,
 };
+pub use workspace::{resolve_workspace, resolve_workspace_json, WorkspaceResolution};
@@ -0,0 +1,621 @@
+//! JSON payload validation against application-contract records.
+//!
+//! A payload is a JSON object whose members are the record's fields. Each
+//! member is parsed into a [`TypedValue`], checked against its declared type,
+//! optionality, and [`FieldConstraint`]s, and — once the whole record is
+//! well-typed — every precondition policy bound to an operation that takes
+//! the record as input is evaluated with [`evaluate_precondition`].
+//!
+//! Wire forms follow the generated projections: decimals are JSON numbers or
+//! canonical decimal strings, quantities are bare decimals in the field's
+//! declared unit (typed as-is, tagged with that unit, so bounds and policies
+//! compare in the unit they are authored in), enums are their wire value,
+//! entity references are the
+//! referenced entity's key, timestamps are RFC 3339, and `null` is the same
+//! as an absent optional field. Every diagnostic is addressed by the JSON
+//! Pointer of the offending value (`""` for the payload itself).
+
+use crate::application::contract::{
+    AccessMode, ActorRef, ApplicationContract, ApplicationPolicyContext, FieldConstraint,
+    FieldContract, FieldType, OperationContract, RecordContract, ScalarType, TypedValue,
+};
+use crate::application::policy_context::{evaluate_precondition, EvaluationResult};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::policy::Expression;
+use indexmap::IndexMap;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// What a payload diagnostic reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadDiagnosticKind {
+    /// The value does not have the field's declared type.
+    InvalidType,
+    /// A required field is absent or `null`.
+    MissingField,
+    /// The payload carries a member the record does not declare.
+    UnknownField,
+    /// A well-typed value violates one of the field's constraints.
+    ConstraintViolation,
+    /// A bound precondition policy evaluated to false.
+    PolicyViolation,
+    /// A bound precondition policy could not be decided; preconditions fail
+    /// closed, so this rejects the payload as well.
+    PolicyUnknown,
+    /// A bound precondition policy could not be evaluated.
+    PolicyError,
+}
+
+/// One path-addressed finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadDiagnostic {
+    /// JSON Pointer of the offending value; `""` addresses the whole payload.
+    pub path: String,
+    pub kind: PayloadDiagnosticKind,
+    pub message: String,
+    /// Violated constraint slug (`min`, `max_length`, `pattern`, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    /// Operation whose precondition failed (policy diagnostics only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// Failure code the operation raises for this precondition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_code: Option<String>,
+}
+
+impl PayloadDiagnostic {
+    fn new(path: &str, kind: PayloadDiagnosticKind, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            kind,
+            message: message.into(),
+            constraint: None,
+            operation: None,
+            failure_code: None,
+        }
+    }
+}
+
+/// Outcome of validating one payload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadReport {
+    /// Name of the record the payload was checked against.
+    pub record: String,
+    /// The parsed payload; present when every member is well-typed and every
+    /// required field is supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<IndexMap<String, TypedValue>>,
+    pub diagnostics: Vec<PayloadDiagnostic>,
+}
+
+impl PayloadReport {
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+/// Which preconditions run and as whom.
+#[derive(Debug, Clone, Default)]
+pub struct PayloadOptions {
+    /// Only evaluate the preconditions of this operation (by name). By
+    /// default every operation taking the record as input is evaluated.
+    pub operation: Option<String>,
+    /// Evaluate as this role (by name) instead of each operation's declared
+    /// actor.
+    pub actor_role: Option<String>,
+}
+
+/// Validate `payload` against the record named `record` (by name or
+/// application symbol ID).
+///
+/// Returns `Err` when the record, the requested operation, or the requested
+/// actor role does not resolve; payload problems are diagnostics in the
+/// report.
+pub fn validate_payload(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    record: &str,
+    payload: &Value,
+    options: &PayloadOptions,
+) -> Result<PayloadReport, String> {
+    let target = find_record(contract, record)?;
+    let operations: Vec<&OperationContract> = contract
+        .operations
+        .iter()
+        .filter(|op| op.input == target.id)
+        .filter(|op| {
+            options
+                .operation
+                .as_ref()
+                .map_or(true, |name| &op.name == name)
+        })
+        .collect();
+    if let Some(name) = &options.operation {
+        if operations.is_empty() {
+            return Err(format!(
+                "operation '{name}' does not exist or does not take record '{}' as input",
+                target.name
+            ));
+        }
+    }
+    let actor = match &options.actor_role {
+        Some(role) => Some(ActorRef::Role {
+            role: graph
+                .find_role_by_name(role)
+                .ok_or_else(|| format!("role '{role}' does not exist"))?,
+        }),
+        None => None,
+    };
+
+    let checker = Checker { graph, contract };
+    let mut diagnostics = Vec::new();
+    let value = checker.record(target, payload, "", &mut diagnostics);
+
+    if let Some(input) = &value {
+        for op in operations {
+            checker.preconditions(op, input, actor.as_ref(), &mut diagnostics);
+        }
+    }
+
+    Ok(PayloadReport {
+        record: target.name.clone(),
+        value,
+        diagnostics,
+    })
+}
+
+fn find_record<'a>(
+    contract: &'a ApplicationContract,
+    record: &str,
+) -> Result<&'a RecordContract, String> {
+    if let Some(found) = contract.records.iter().find(|r| r.id.0 == record) {
+        return Ok(found);
+    }
+    let mut named = contract.records.iter().filter(|r| r.name == record);
+    match (named.next(), named.next()) {
+        (Some(found), None) => Ok(found),
+        (Some(_), Some(_)) => Err(format!(
+            "record name '{record}' is ambiguous; use its application symbol ID"
+        )),
+        (None, _) => Err(format!("record '{record}' does not exist")),
+    }
+}
+
+/// Escape one JSON Pointer reference token (RFC 6901).
+fn pointer(parent: &str, token: &str) -> String {
+    format!("{parent}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+struct Checker<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+}
+
+impl Checker<'_> {
+    fn record(
+        &self,
+        record: &RecordContract,
+        payload: &Value,
+        path: &str,
+        diagnostics: &mut Vec<PayloadDiagnostic>,
+    ) -> Option<IndexMap<String, TypedValue>> {
+        let Some(members) = payload.as_object() else {
+            diagnostics.push(PayloadDiagnostic::new(
+                path,
+                PayloadDiagnosticKind::InvalidType,
+                format!("payload for record '{}' must be a JSON object", record.name),
+            ));
+            return None;
+        };
+
+        let mut complete = true;
+        let mut typed = IndexMap::new();
+        for field in &record.fields {
+            let field_path = pointer(path, &field.name);
+            match members.get(&field.name).filter(|v| !v.is_null()) {
+                Some(value) => match self.field(field, value, &field_path, diagnostics) {
+                    Some(value) => {
+                        typed.insert(field.name.clone(), value);
+                    }
+                    None => complete = false,
+                },
+                None if !field.optional => {
+                    complete = false;
+                    diagnostics.push(PayloadDiagnostic::new(
+                        &field_path,
+                        PayloadDiagnosticKind::MissingField,
+                        format!("required field '{}' is missing", field.name),
+                    ));
+                }
+                None => {}
+            }
+        }
+
+        let mut unknown: Vec<&String> = members
+            .keys()
+            .filter(|name| !record.fields.iter().any(|f| &f.name == *name))
+            .collect();
+        unknown.sort();
+        for name in unknown {
+            complete = false;
+            diagnostics.push(PayloadDiagnostic::new(
+                &pointer(path, name),
+                PayloadDiagnosticKind::UnknownField,
+                format!("record '{}' has no field '{name}'", record.name),
+            ));
+        }
+
+        complete.then_some(typed)
+    }
+
+    /// Parse and constrain one field value. Constraint violations keep the
+    /// typed value so preconditions can still run.
+    fn field(
+        &self,
+        field: &FieldContract,
+        value: &Value,
+        path: &str,
+        diagnostics: &mut Vec<PayloadDiagnostic>,
+    ) -> Option<TypedValue> {
+        let typed = self.typed(&field.field_type, value, path, diagnostics)?;
+        for constraint in &field.constraints {
+            if let Some(reason) = self.violation(constraint, &typed) {
+                let mut diagnostic = PayloadDiagnostic::new(
+                    path,
+                    PayloadDiagnosticKind::ConstraintViolation,
+                    format!(
+                        "field '{}' violates its {} constraint: {reason}",
+                        field.name,
+                        constraint_slug(constraint)
+                    ),
+                );
+                diagnostic.constraint = Some(constraint_slug(constraint).to_string());
+                diagnostics.push(diagnostic);
+            }
+        }
+        Some(typed)
+    }
+
+    fn typed(
+        &self,
+        field_type: &FieldType,
+        value: &Value,
+        path: &str,
+        diagnostics: &mut Vec<PayloadDiagnostic>,
+    ) -> Option<TypedValue> {
+        let parsed = match field_type {
+            FieldType::Scalar { scalar } => scalar_value(*scalar, value),
+            FieldType::Quantity { unit } => decimal_value(value)
+                .map(|magnitude| TypedValue::Quantity {
+                    base_value: magnitude.normalize(),
+                    unit: unit.clone(),
+                })
+                .ok_or_else(|| "must be a decimal quantity in the field's unit".to_string()),
+            FieldType::Enum { symbol } => {
+                let declared = self.contract.enums.iter().find(|e| &e.id == symbol);
+                match (value.as_str(), declared) {
+                    (Some(wire), Some(declared)) => {
+                        if declared.members.iter().any(|m| m.wire == wire) {
+                            Ok(TypedValue::Enum {
+                                symbol: symbol.clone(),
+                                wire: wire.to_string(),
+                            })
+                        } else {
+                            Err(format!(
+                                "value '{wire}' is not a member of enum '{}'",
+                                declared.name
+                            ))
+                        }
+                    }
+                    (None, _) => Err(format!(
+                        "must be a string wire value of enum '{}'",
+                        symbol.0
+                    )),
+                    (Some(_), None) => {
+                        Err(format!("references missing enum contract '{}'", symbol.0))
+                    }
+                }
+            }
+            FieldType::EntityRef { entity } => {
+                self.entity_key(entity, value)
+                    .map(|key| TypedValue::EntityRef {
+                        entity: entity.clone(),
+                        key: Box::new(key),
+                    })
+            }
+            FieldType::List { element } => {
+                let Some(items) = value.as_array() else {
+                    diagnostics.push(PayloadDiagnostic::new(
+                        path,
+                        PayloadDiagnosticKind::InvalidType,
+                        "must be a list",
+                    ));
+                    return None;
+                };
+                // Keep checking after a bad element so every one is reported.
+                let mut typed = Vec::with_capacity(items.len());
+                for (index, item) in items.iter().enumerate() {
+                    let item_path = pointer(path, &index.to_string());
+                    typed.push(self.typed(element, item, &item_path, diagnostics));
+                }
+                return typed
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .map(TypedValue::List);
+            }
+        };
+        match parsed {
+            Ok(typed) => Some(typed),
+            Err(reason) => {
+                diagnostics.push(PayloadDiagnostic::new(
+                    path,
+                    PayloadDiagnosticKind::InvalidType,
+                    reason,
+                ));
+                None
+            }
+        }
+    }
+
+    /// An entity reference is the referenced entity's key value.
+    fn entity_key(&self, entity: &ConceptId, value: &Value) -> Result<TypedValue, String> {
+        let key_type = self
+            .contract
+            .entities
+            .iter()
+            .find(|e| &e.concept_id == entity)
+            .and_then(|e| e.fields.iter().find(|f| f.name == e.key_field))
+            .map(|f| &f.field_type);
+        match key_type {
+            Some(FieldType::Scalar { scalar }) => scalar_value(*scalar, value)
+                .map_err(|reason| format!("entity reference key {reason}")),
+            _ => value
+                .as_str()
+                .map(|key| TypedValue::String(key.to_string()))
+                .ok_or_else(|| "entity reference must be the referenced key".to_string()),
+        }
+    }
+
+    fn violation(&self, constraint: &FieldConstraint, typed: &TypedValue) -> Option<String> {
+        let magnitude = match typed {
+            TypedValue::Int(i) => Some(Decimal::from(*i)),
+            TypedValue::Decimal(d) => Some(*d),
+            TypedValue::Quantity { base_value, .. } => Some(*base_value),
+            _ => None,
+        };
+        let text = match typed {
+            TypedValue::String(s) => Some(s.as_str()),
+            _ => None,
+        };
+        let items = match typed {
+            TypedValue::List(items) => Some(items.len()),
+            _ => None,
+        };
+        match constraint {
+            FieldConstraint::Min { value: bound } => magnitude
+                .filter(|actual| actual < bound)
+                .map(|actual| format!("{actual} is below {bound}")),
+            FieldConstraint::Max { value: bound } => magnitude
+                .filter(|actual| actual > bound)
+                .map(|actual| format!("{actual} is above {bound}")),
+            FieldConstraint::ExclusiveMin { value: bound } => magnitude
+                .filter(|actual| actual <= bound)
+                .map(|actual| format!("{actual} is not greater than {bound}")),
+            FieldConstraint::ExclusiveMax { value: bound } => magnitude
+                .filter(|actual| actual >= bound)
+                .map(|actual| format!("{actual} is not less than {bound}")),
+            FieldConstraint::MinLength { value: bound } => text.and_then(|actual| {
+                let length = actual.chars().count();
+                (length < *bound as usize).then(|| format!("length {length} is below {bound}"))
+            }),
+            FieldConstraint::MaxLength { value: bound } => text.and_then(|actual| {
+                let length = actual.chars().count();
+                (length > *bound as usize).then(|| format!("length {length} is above {bound}"))
+            }),
+            FieldConstraint::MinItems { value: bound } => items.and_then(|count| {
+                (count < *bound as usize).then(|| format!("item count {count} is below {bound}"))
+            }),
+            FieldConstraint::MaxItems { value: bound } => items.and_then(|count| {
+                (count > *bound as usize).then(|| format!("item count {count} is above {bound}"))
+            }),
+            FieldConstraint::Pattern { pattern } => {
+                let candidate = text?;
+                match self
+                    .graph
+                    .all_patterns()
+                    .into_iter()
+                    .find(|p| p.id() == pattern)
+                {
+                    Some(declared) => match declared.is_match(candidate) {
+                        Ok(true) => None,
+                        Ok(false) => Some(format!(
+                            "value '{candidate}' does not match pattern '{}'",
+                            declared.name()
+                        )),
+                        Err(reason) => Some(format!("pattern evaluation failed: {reason}")),
+                    },
+                    None => Some(format!("pattern {pattern} is missing from the graph")),
+                }
+            }
+        }
+    }
+
+    fn preconditions(
+        &self,
+        op: &OperationContract,
+        input: &IndexMap<String, TypedValue>,
+        actor: Option<&ActorRef>,
+        diagnostics: &mut Vec<PayloadDiagnostic>,
+    ) {
+        let AccessMode::PolicyGoverned { bindings } = &op.access else {
+            return;
+        };
+        let context = ApplicationPolicyContext {
+            operation: op.id.clone(),
+            actor: actor.cloned().unwrap_or_else(|| op.actor.clone()),
+            input: input.clone(),
+            pre_state: None,
+        };
+        for binding in bindings {
+            let (kind, message, path) = match self.graph.get_policy(&binding.policy) {
+                Some(policy) => {
+                    let expression =
+                        resolve_roles(self.graph, &policy.namespace, policy.expression());
+                    let path = single_input_path(&expression, input);
+                    match evaluate_precondition(&expression, &context) {
+                        EvaluationResult::True => continue,
+                        EvaluationResult::False => (
+                            PayloadDiagnosticKind::PolicyViolation,
+                            format!("precondition '{}' is not satisfied", policy.name),
+                            path,
+                        ),
+                        EvaluationResult::Unknown => (
+                            PayloadDiagnosticKind::PolicyUnknown,
+                            format!(
+                                "precondition '{}' cannot be decided from the payload alone",
+                                policy.name
+                            ),
+                            path,
+                        ),
+                        EvaluationResult::Error(reason) => (
+                            PayloadDiagnosticKind::PolicyError,
+                            format!(
+                                "precondition '{}' failed to evaluate: {reason}",
+                                policy.name
+                            ),
+                            path,
+                        ),
+                    }
+                }
+                None => (
+                    PayloadDiagnosticKind::PolicyError,
+                    format!("bound policy {} is missing from the graph", binding.policy),
+                    String::new(),
+                ),
+            };
+            let mut diagnostic = PayloadDiagnostic::new(&path, kind, message);
+            diagnostic.operation = Some(op.name.clone());
+            diagnostic.failure_code = Some(binding.failure_code.clone());
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+fn scalar_value(scalar: ScalarType, value: &Value) -> Result<TypedValue, String> {
+    let parsed = match scalar {
+        ScalarType::String => value.as_str().map(|s| TypedValue::String(s.to_string())),
+        ScalarType::Int => match value {
+            Value::Number(number) => Decimal::from_str(&number.to_string())
+                .ok()
+                .filter(Decimal::is_integer)
+                .and_then(|n| n.to_i64())
+                .map(TypedValue::Int),
+            _ => None,
+        },
+        ScalarType::Decimal => decimal_value(value).map(|d| TypedValue::Decimal(d.normalize())),
+        ScalarType::Bool => value.as_bool().map(TypedValue::Bool),
+        ScalarType::Timestamp => value
+            .as_str()
+            .and_then(|text| chrono::DateTime::parse_from_rfc3339(text).ok())
+            .map(|t| TypedValue::Timestamp(t.with_timezone(&chrono::Utc))),
+        ScalarType::Uuid => value
+            .as_str()
+            .and_then(|text| uuid::Uuid::parse_str(text).ok())
+            .map(TypedValue::Uuid),
+    };
+    parsed.ok_or_else(|| format!("must be a {}", scalar_slug(scalar)))
+}
+
+/// Decimals travel as JSON numbers or canonical decimal strings.
+fn decimal_value(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Number(number) => Decimal::from_str(&number.to_string()).ok(),
+        Value::String(text) => Decimal::from_str(text).ok(),
+        _ => None,
+    }
+}
+
+fn scalar_slug(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::String => "string",
+        ScalarType::Int => "int",
+        ScalarType::Decimal => "decimal",
+        ScalarType::Bool => "bool",
+        ScalarType::Timestamp => "timestamp",
+        ScalarType::Uuid => "uuid",
+    }
+}
+
+fn constraint_slug(constraint: &FieldConstraint) -> &'static str {
+    match constraint {
+        FieldConstraint::Min { .. } => "min",
+        FieldConstraint::Max { .. } => "max",
+        FieldConstraint::ExclusiveMin { .. } => "exclusive_min",
+        FieldConstraint::ExclusiveMax { .. } => "exclusive_max",
+        FieldConstraint::MinLength { .. } => "min_length",
+        FieldConstraint::MaxLength { .. } => "max_length",
+        FieldConstraint::MinItems { .. } => "min_items",
+        FieldConstraint::MaxItems { .. } => "max_items",
+        FieldConstraint::Pattern { .. } => "pattern",
+    }
+}
+
+/// Graph policies keep authored `role<Name>` text; the evaluator expects the
+/// resolved role `ConceptId`. Prefer a role in the policy's own namespace.
+fn resolve_roles(graph: &Graph, namespace: &str, expression: &Expression) -> Expression {
+    match expression {
+        Expression::RoleReference { role } => {
+            let local = ConceptId::from_concept(namespace, role);
+            let resolved = if graph.get_role(&local).is_some() {
+                Some(local)
+            } else {
+                graph.find_role_by_name(role)
+            };
+            Expression::RoleReference {
+                role: resolved.map_or_else(|| role.clone(), |id| id.to_string()),
+            }
+        }
+        Expression::Binary { op, left, right } => Expression::binary(
+            op.clone(),
+            resolve_roles(graph, namespace, left),
+            resolve_roles(graph, namespace, right),
+        ),
+        Expression::Unary { op, operand } => {
+            Expression::unary(op.clone(), resolve_roles(graph, namespace, operand))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Address a policy diagnostic at the one input field its expression reads,
+/// or at the whole payload when it reads several (or none).
+fn single_input_path(expression: &Expression, input: &IndexMap<String, TypedValue>) -> String {
+    fn collect<'e>(expression: &'e Expression, names: &mut Vec<&'e str>) {
+        match expression {
+            Expression::Variable(name) if name != "actor" => names.push(name),
+            Expression::MemberAccess { object, member } if object == "input" => names.push(member),
+            Expression::Binary { left, right, .. } => {
+                collect(left, names);
+                collect(right, names);
+            }
+            Expression::Unary { operand, .. } => collect(operand, names),
+            _ => {}
+        }
+    }
+    let mut names = Vec::new();
+    collect(expression, &mut names);
+    names.sort_unstable();
+    names.dedup();
+    match names.as_slice() {
+        [name] if input.contains_key(*name) => pointer("", name),
+        _ => String::new(),
+    }
+}
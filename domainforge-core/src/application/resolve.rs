@@ -202,17 +202,102 @@ pub(crate) fn resolve_filesystem_graph(
     registry: Option<&crate::registry::NamespaceRegistry>,
     default_namespace: Option<&str>,
 ) -> Result<crate::graph::Graph, Vec<ApplicationDiagnostic>> {
+    let set = resolve_filesystem_set(entry_path, entry_source, registry, default_namespace)?;
+    build_graph_from_set(&set)
+}
+
+/// Full application contract (records and operations included) for the
+/// filesystem entry, for projections that lower it alongside the graph.
+#[cfg(feature = "cli")]
+pub(crate) fn resolve_filesystem_contract(
+    entry_path: &std::path::Path,
+    entry_source: &str,
+    registry: Option<&crate::registry::NamespaceRegistry>,
+    default_namespace: Option<&str>,
+) -> Result<ApplicationContract, Vec<ApplicationDiagnostic>> {
+    let set = resolve_filesystem_set(entry_path, entry_source, registry, default_namespace)?;
+    build_contract(&set)
+}
+
+/// Whether any module of the filesystem entry's import closure declares a
+/// record, enum or operation; a graph-only model has no contract to lower.
+#[cfg(feature = "cli")]
+pub(crate) fn filesystem_declares_contract(
+    entry_path: &std::path::Path,
+    entry_source: &str,
+    registry: Option<&crate::registry::NamespaceRegistry>,
+    default_namespace: Option<&str>,
+) -> Result<bool, Vec<ApplicationDiagnostic>> {
+    use crate::parser::ast::AstNode;
+    let set = resolve_filesystem_set(entry_path, entry_source, registry, default_namespace)?;
+    Ok(set.modules.iter().any(|module| {
+        module.ast.declarations.iter().any(|declaration| {
+            let node = match &declaration.node {
+                AstNode::Export(inner) => &inner.node,
+                other => other,
+            };
+            matches!(
+                node,
+                AstNode::Record(_) | AstNode::Enum(_) | AstNode::Operation(_)
+            )
+        })
+    }))
+}
+
+/// Canonical semantic envelope document for the filesystem entry, exactly
+/// as [`resolve_semantic_envelope`](crate::application::envelope::resolve_semantic_envelope)
+/// produces it for the equivalent source map.
+#[cfg(feature = "cli")]
+pub(crate) fn resolve_filesystem_envelope(
+    entry_path: &std::path::Path,
+    entry_source: &str,
+    registry: Option<&crate::registry::NamespaceRegistry>,
+    default_namespace: Option<&str>,
+) -> Result<
+    crate::application::envelope::CanonicalSemanticEnvelopeDocument,
+    Vec<ApplicationDiagnostic>,
+> {
     let (entry_logical_path, sources) =
         source_map_from_filesystem(entry_path, entry_source, registry, default_namespace)?;
-    let sources_json = serde_json::to_string(&sources.0).map_err(|error| {
+    let sources_json = source_map_json(&sources)?;
+    crate::application::envelope::resolve_semantic_envelope(&entry_logical_path, &sources_json)
+}
+
+/// Source-set hash of the filesystem entry's import closure, as the
+/// resolved document would record it, without building the graph.
+#[cfg(feature = "cli")]
+pub(crate) fn filesystem_source_set_hash(
+    entry_path: &std::path::Path,
+    entry_source: &str,
+    registry: Option<&crate::registry::NamespaceRegistry>,
+    default_namespace: Option<&str>,
+) -> Result<String, Vec<ApplicationDiagnostic>> {
+    let (_, sources) =
+        source_map_from_filesystem(entry_path, entry_source, registry, default_namespace)?;
+    Ok(source_set_hash(&sources))
+}
+
+#[cfg(feature = "cli")]
+fn resolve_filesystem_set(
+    entry_path: &std::path::Path,
+    entry_source: &str,
+    registry: Option<&crate::registry::NamespaceRegistry>,
+    default_namespace: Option<&str>,
+) -> Result<ResolvedModuleSet, Vec<ApplicationDiagnostic>> {
+    let (entry_logical_path, sources) =
+        source_map_from_filesystem(entry_path, entry_source, registry, default_namespace)?;
+    let sources_json = source_map_json(&sources)?;
+    enforce_source_map_budget(&sources_json)?;
+    resolve_source_map(&entry_logical_path, &sources)
+}
+
+pub(crate) fn source_map_json(sources: &SourceMap) -> Result<String, Vec<ApplicationDiagnostic>> {
+    serde_json::to_string(&sources.0).map_err(|error| {
         vec![ApplicationDiagnostic::new(
             ApplicationDiagnosticCode::App015,
-            format!("failed to serialize filesystem source map: {error}"),
+            format!("failed to serialize source map: {error}"),
         )]
-    })?;
-    enforce_source_map_budget(&sources_json)?;
-    let set = resolve_source_map(&entry_logical_path, &sources)?;
-    build_graph_from_set(&set)
+    })
 }
 
 pub(crate) fn build_graph_from_set(
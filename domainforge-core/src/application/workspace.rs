@@ -0,0 +1,134 @@
+//! In-memory workspace resolution for editors and notebooks: a source map
+//! plus an optional namespace registry, resolved through the same closure
+//! machinery the CLI uses for files on disk.
+
+use crate::application::diagnostic::{
+    sort_diagnostics, ApplicationDiagnostic, APP014_UNRESOLVED_SPECIFIER,
+};
+use crate::application::envelope::{resolve_semantic_envelope, CanonicalSemanticEnvelopeDocument};
+use crate::application::resolve::{
+    enforce_source_map_budget, resolve_application_graph, source_map_json,
+};
+use crate::graph::Graph;
+use crate::module::resolver::SourceMap;
+use crate::parser::parse_source;
+use crate::registry::NamespaceRegistry;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Everything one workspace resolution produced. `graph` and
+/// `semantic_envelope` are each present only when their stage succeeded;
+/// `diagnostics` lists every failure, located by logical module and line.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceResolution {
+    pub entry: String,
+    pub graph: Option<Graph>,
+    pub semantic_envelope: Option<CanonicalSemanticEnvelopeDocument>,
+    pub diagnostics: Vec<ApplicationDiagnostic>,
+}
+
+impl WorkspaceResolution {
+    /// True when the workspace resolved without diagnostics.
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+/// Resolve `entry_logical_path` and its import closure from `sources_json`
+/// (an object of logical path → source text, as for
+/// [`resolve_semantic_envelope`]).
+///
+/// With `registry_toml` (the contents of a `.sea-registry.toml`), modules
+/// without an `@namespace` take the namespace the registry assigns to their
+/// logical path, exactly as the filesystem adapter does for files. Reported
+/// lines always refer to the source as given.
+pub fn resolve_workspace(
+    entry_logical_path: &str,
+    sources_json: &str,
+    registry_toml: Option<&str>,
+) -> WorkspaceResolution {
+    let mut resolution = WorkspaceResolution {
+        entry: entry_logical_path.to_string(),
+        graph: None,
+        semantic_envelope: None,
+        diagnostics: Vec::new(),
+    };
+    let (sources_json, injected) = match apply_registry(sources_json, registry_toml) {
+        Ok(applied) => applied,
+        Err(diagnostics) => {
+            resolution.diagnostics = diagnostics;
+            return resolution;
+        }
+    };
+
+    match resolve_application_graph(entry_logical_path, &sources_json) {
+        Ok(graph) => resolution.graph = Some(graph),
+        Err(diagnostics) => resolution.diagnostics = diagnostics,
+    }
+    if resolution.graph.is_some() {
+        match resolve_semantic_envelope(entry_logical_path, &sources_json) {
+            Ok(envelope) => resolution.semantic_envelope = Some(envelope),
+            Err(diagnostics) => resolution.diagnostics = diagnostics,
+        }
+    }
+
+    // Undo the injected `@namespace` header line.
+    for diagnostic in &mut resolution.diagnostics {
+        let context = &mut diagnostic.context;
+        if let (Some(module), Some(line)) = (&context.logical_module_id, context.line) {
+            if injected.contains(module) && line > 1 {
+                context.line = Some(line - 1);
+            }
+        }
+    }
+    sort_diagnostics(&mut resolution.diagnostics);
+    resolution
+}
+
+/// JSON boundary twin of [`resolve_workspace`]: the serialized
+/// [`WorkspaceResolution`].
+pub fn resolve_workspace_json(
+    entry_logical_path: &str,
+    sources_json: &str,
+    registry_toml: Option<&str>,
+) -> String {
+    let resolution = resolve_workspace(entry_logical_path, sources_json, registry_toml);
+    serde_json::to_string(&resolution).expect("graphs, envelopes and diagnostics always serialize")
+}
+
+/// Prefix `@namespace` to each namespace-less module the registry covers.
+/// Returns the rewritten source map and the logical IDs that were rewritten.
+/// Modules that fail to parse are left for the resolver to diagnose.
+fn apply_registry(
+    sources_json: &str,
+    registry_toml: Option<&str>,
+) -> Result<(String, HashSet<String>), Vec<ApplicationDiagnostic>> {
+    let Some(registry_toml) = registry_toml else {
+        return Ok((sources_json.to_string(), HashSet::new()));
+    };
+    let registry =
+        NamespaceRegistry::from_toml_str(registry_toml, PathBuf::new()).map_err(|error| {
+            vec![ApplicationDiagnostic::closure_error(
+                APP014_UNRESOLVED_SPECIFIER,
+                format!("invalid namespace registry: {error}"),
+            )]
+        })?;
+    enforce_source_map_budget(sources_json)?;
+    let mut sources = SourceMap::parse_json(sources_json)?;
+    let mut injected = HashSet::new();
+    for (logical_id, source) in sources.0.iter_mut() {
+        let declares_namespace = match parse_source(source) {
+            Ok(ast) => ast.metadata.namespace.is_some(),
+            Err(_) => continue,
+        };
+        if declares_namespace {
+            continue;
+        }
+        let namespace = serde_json::to_string(registry.namespace_for_logical_path(logical_id))
+            .expect("serializing a Rust string as a SEA string literal cannot fail");
+        *source = format!("@namespace {namespace}\n{source}");
+        injected.insert(logical_id.clone());
+    }
+    Ok((source_map_json(&sources)?, injected))
+}
@@ -0,0 +1,673 @@
+//! Decision-space exploration and policy coverage for loaded authority packs.
+//!
+//! The resolver answers one request at a time; this module answers "what
+//! does the whole policy set do?" by enumerating the dimensions the loaded
+//! [`AuthorityPolicy`]s actually reference and running
+//! [`AuthorityResolver::resolve`] over every combination.
+//!
+//! ## Dimensions
+//!
+//! * **Structural** — every `applies_to` key (`action`, `actor.role`,
+//!   `resource.type`, ..., and metadata keys). Each key takes every value
+//!   some policy names, plus `*`: a value no policy names (or the key
+//!   unset, for optional request fields).
+//! * **Facts** — every `when` path and every `requires_fact` path. Each path
+//!   takes every value some condition compares against (the operand of a
+//!   `__neq` included), plus `*` (a value no condition names), plus
+//!   `unknown` (no fact observed). Paths that are required but never
+//!   compared take only `present` and `unknown`.
+//!
+//! Present facts are synthesized as envelopes that satisfy every presence
+//! check a requirement can make (evidence, signature, confidence). Each
+//! present value is asserted once per source class any requirement on the
+//! path allows (with that requirement's first source id), so two policies
+//! trusting different classes for one path are each satisfiable; such
+//! values display as `value@class`. A requirement
+//! that allows no source class at all — how [`super::SeaPolicyCompiler`]
+//! fails closed on an unannotated fact — can therefore never be satisfied,
+//! which is exactly what the coverage findings should surface. The
+//! synthesized envelopes are handed to the resolver as [`TrustedFacts`]
+//! directly: this is a static analysis of the policy logic, not of the
+//! fact-source registry, so the trust pipeline is deliberately bypassed.
+//!
+//! ## Findings
+//!
+//! * **Unreachable** — the policy's conditions never evaluate to True in
+//!   any scenario (it can at most contribute an unknown-handling default).
+//! * **Shadowed** — the policy fires in some scenario but never decides
+//!   one; a higher-precedence policy always wins. Obligation policies with
+//!   an `obligation_spec` are exempt: their duties surface regardless of
+//!   the winner (A8).
+//! * **Gap** — a structural combination naming at least one concrete value
+//!   for which some fact assignment falls to `NotApplicable`. The all-`*`
+//!   combination is the resolver's documented default and is not reported.
+//! * **Specificity tie** — a scenario in which conflict resolution ended in
+//!   `specificity_incomparable`. The explorer always resolves non-strictly
+//!   so ties are recorded instead of aborting the run; under `strict_mode`
+//!   each of these is a `SpecificityConflict` error at request time.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::environment::AuthorityEnvironmentConfig;
+use super::error::AuthorityError;
+use super::fact_resolver::TrustedFacts;
+use super::pack::AuthorityPack;
+use super::policy::AuthorityPolicy;
+use super::resolver::{AuthorityResolver, ResolverOutput};
+use super::types::*;
+
+/// Display value for "a value no policy names".
+pub const OTHER_VALUE: &str = "*";
+/// Display value for a required fact that no condition compares.
+pub const PRESENT_VALUE: &str = "present";
+/// Display value for a fact that was not observed.
+pub const UNKNOWN_VALUE: &str = "unknown";
+
+/// Scenarios explored before [`DecisionSpaceExplorer::explore`] refuses.
+pub const DEFAULT_MAX_SCENARIOS: usize = 10_000;
+
+const OTHER_SENTINEL: &str = "__explorer_other__";
+const PRESENT_SENTINEL: &str = "__explorer_present__";
+const EXPLORER_SOURCE_ID: &str = "decision-space-explorer";
+
+const DECIDING_STEPS: &[&str] = &[
+    "single_applicable",
+    "modality_resolved",
+    "highest_priority",
+    "specificity_resolved",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageFindingKind {
+    UnreachablePolicy,
+    ShadowedPolicy,
+    NotApplicableGap,
+    SpecificityTie,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageFinding {
+    pub kind: CoverageFindingKind,
+    /// Policies the finding is about (empty for gaps).
+    pub policies: Vec<String>,
+    /// Structural assignment of an example scenario (empty for policy-level
+    /// findings that hold across every scenario).
+    #[serde(default)]
+    pub request: BTreeMap<String, String>,
+    /// Fact assignment of the same example scenario.
+    #[serde(default)]
+    pub facts: BTreeMap<String, String>,
+    pub message: String,
+}
+
+/// One row of the decision matrix: a generated request and fact assignment
+/// and what the resolver made of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRow {
+    pub request: BTreeMap<String, String>,
+    pub facts: BTreeMap<String, String>,
+    pub final_decision: FinalDecision,
+    pub reason_code: String,
+    /// The policy whose evaluation produced `final_decision`, or `None` for
+    /// `NotApplicable` and specificity ties.
+    pub deciding_policy: Option<String>,
+    pub applicable_policies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecisionSpaceReport {
+    /// Every explored dimension and its display values; fact paths are
+    /// prefixed with `fact:`.
+    pub dimensions: BTreeMap<String, Vec<String>>,
+    pub matrix: Vec<DecisionRow>,
+    pub findings: Vec<CoverageFinding>,
+}
+
+impl DecisionSpaceReport {
+    pub fn findings_of(&self, kind: CoverageFindingKind) -> Vec<&CoverageFinding> {
+        self.findings.iter().filter(|f| f.kind == kind).collect()
+    }
+
+    /// Number of matrix rows per final decision.
+    pub fn decision_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for row in &self.matrix {
+            *counts
+                .entry(decision_label(row.final_decision).to_string())
+                .or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Human-readable summary: dimensions, decision counts, the matrix as a
+    /// table, and the findings.
+    pub fn to_report(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "Decision Space ({} scenarios)\n",
+            self.matrix.len()
+        ));
+
+        out.push_str("\nDimensions:\n");
+        for (name, values) in &self.dimensions {
+            out.push_str(&format!("  {}: {}\n", name, values.join(", ")));
+        }
+
+        out.push_str("\nDecisions:\n");
+        for (decision, count) in self.decision_counts() {
+            out.push_str(&format!("  {}: {}\n", decision, count));
+        }
+
+        out.push_str("\nMatrix:\n");
+        for row in &self.matrix {
+            out.push_str(&format!(
+                "  {} | {} => {} ({})\n",
+                format_assignment(&row.request),
+                format_assignment(&row.facts),
+                decision_label(row.final_decision),
+                row.deciding_policy.as_deref().unwrap_or("-")
+            ));
+        }
+
+        if !self.findings.is_empty() {
+            out.push_str(&format!("\nFindings ({}):\n", self.findings.len()));
+            for f in &self.findings {
+                out.push_str(&format!("  - [{:?}] {}\n", f.kind, f.message));
+            }
+        }
+
+        out
+    }
+}
+
+/// Enumerates the decision space of a set of authority packs.
+pub struct DecisionSpaceExplorer {
+    resolver: AuthorityResolver,
+    max_scenarios: usize,
+}
+
+impl DecisionSpaceExplorer {
+    /// Explore with the unknown handling and specificity profile of
+    /// `config`. Strict mode is ignored so specificity ties are reported
+    /// rather than aborting the run.
+    pub fn new(config: &AuthorityEnvironmentConfig) -> Self {
+        Self {
+            resolver: AuthorityResolver::new(
+                config.unknown_handling.clone(),
+                config.specificity_profile.clone(),
+                false,
+                config.resolver_semantics_version.clone(),
+                config.compatibility_lowering_version.clone(),
+            ),
+            max_scenarios: DEFAULT_MAX_SCENARIOS,
+        }
+    }
+
+    pub fn with_max_scenarios(mut self, max_scenarios: usize) -> Self {
+        self.max_scenarios = max_scenarios;
+        self
+    }
+
+    pub fn explore(&self, packs: &[AuthorityPack]) -> Result<DecisionSpaceReport, AuthorityError> {
+        let policies: Vec<&AuthorityPolicy> =
+            packs.iter().flat_map(|p| p.policies.iter()).collect();
+
+        let structural = structural_dimensions(&policies);
+        let facts = fact_dimensions(&policies);
+
+        let scenario_count = structural
+            .iter()
+            .map(|(_, v)| v.len())
+            .chain(facts.iter().map(|d| d.values.len()))
+            .try_fold(1usize, |acc, n| acc.checked_mul(n))
+            .unwrap_or(usize::MAX);
+        if scenario_count > self.max_scenarios {
+            return Err(AuthorityError::invalid_environment(format!(
+                "Decision space has {} scenarios, more than the limit of {}",
+                scenario_count, self.max_scenarios
+            ))
+            .with_hint("raise the scenario limit or split the packs"));
+        }
+
+        let mut report = DecisionSpaceReport::default();
+        for (key, values) in &structural {
+            report
+                .dimensions
+                .insert(key.clone(), values.iter().map(display_structural).collect());
+        }
+        for dim in &facts {
+            report.dimensions.insert(
+                format!("fact:{}", dim.path),
+                dim.values.iter().map(|v| display_fact(dim, v)).collect(),
+            );
+        }
+
+        let now = Utc::now();
+        let mut fired: BTreeSet<String> = BTreeSet::new();
+        let mut decided: BTreeSet<String> = BTreeSet::new();
+        let mut ties: BTreeMap<Vec<String>, usize> = BTreeMap::new();
+        let mut gaps: BTreeMap<Vec<(String, String)>, (usize, usize)> = BTreeMap::new();
+
+        let request_indices = odometer(structural.iter().map(|(_, v)| v.len()).collect());
+        let fact_indices = odometer(facts.iter().map(|d| d.values.len()).collect());
+
+        for request_idx in &request_indices {
+            let assignment: Vec<(&String, &Option<serde_json::Value>)> = structural
+                .iter()
+                .zip(request_idx)
+                .map(|((key, values), i)| (key, &values[*i]))
+                .collect();
+            let request = build_request(&assignment, report.matrix.len(), now);
+            let request_display: BTreeMap<String, String> = assignment
+                .iter()
+                .map(|(k, v)| ((*k).clone(), display_structural(v)))
+                .collect();
+            let concrete = assignment.iter().any(|(_, v)| v.is_some());
+
+            for fact_idx in &fact_indices {
+                let fact_assignment: Vec<(&FactDimension, &FactValue)> = facts
+                    .iter()
+                    .zip(fact_idx)
+                    .map(|(d, i)| (d, &d.values[*i]))
+                    .collect();
+                let envelopes = build_facts(&fact_assignment, now);
+                let output =
+                    self.resolver
+                        .resolve(&request, packs, &TrustedFacts(envelopes), &[], &[])?;
+
+                for ev in &output.evaluations {
+                    if ev.condition_result == ThreeValuedResult::True {
+                        fired.insert(ev.policy.policy_id.clone());
+                    }
+                }
+                let deciding_policy = deciding_policy(&output);
+                if let Some(ref id) = deciding_policy {
+                    decided.insert(id.clone());
+                }
+
+                let row = DecisionRow {
+                    request: request_display.clone(),
+                    facts: fact_assignment
+                        .iter()
+                        .map(|(d, v)| (d.path.clone(), display_fact(d, v)))
+                        .collect(),
+                    final_decision: output.final_decision,
+                    reason_code: output.reason_code.clone(),
+                    deciding_policy,
+                    applicable_policies: output.applicable_policies.clone(),
+                };
+
+                if !output.incomparable_policies.is_empty() {
+                    let mut tied = output.incomparable_policies.clone();
+                    tied.sort();
+                    ties.entry(tied).or_insert(report.matrix.len());
+                }
+                if concrete {
+                    let key: Vec<(String, String)> = request_display
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                    let entry = gaps.entry(key).or_insert((usize::MAX, 0));
+                    if output.final_decision == FinalDecision::NotApplicable {
+                        entry.0 = entry.0.min(report.matrix.len());
+                        entry.1 += 1;
+                    }
+                }
+
+                report.matrix.push(row);
+            }
+        }
+
+        let mut sorted = policies.clone();
+        sorted.sort_by(|a, b| a.policy_id.cmp(&b.policy_id));
+        for policy in &sorted {
+            let id = &policy.policy_id;
+            if !fired.contains(id) {
+                report.findings.push(CoverageFinding {
+                    kind: CoverageFindingKind::UnreachablePolicy,
+                    policies: vec![id.clone()],
+                    request: BTreeMap::new(),
+                    facts: BTreeMap::new(),
+                    message: format!(
+                        "Policy '{}' never evaluates to True; at most its unknown-handling default applies",
+                        id
+                    ),
+                });
+            } else if !decided.contains(id) && !duty_surfaces_anyway(policy) {
+                report.findings.push(CoverageFinding {
+                    kind: CoverageFindingKind::ShadowedPolicy,
+                    policies: vec![id.clone()],
+                    request: BTreeMap::new(),
+                    facts: BTreeMap::new(),
+                    message: format!(
+                        "Policy '{}' fires but never decides; a higher-precedence policy always wins",
+                        id
+                    ),
+                });
+            }
+        }
+
+        let fact_rows = fact_indices.len();
+        for (_, (first_row, count)) in gaps {
+            if count == 0 {
+                continue;
+            }
+            let row = &report.matrix[first_row];
+            report.findings.push(CoverageFinding {
+                kind: CoverageFindingKind::NotApplicableGap,
+                policies: vec![],
+                request: row.request.clone(),
+                facts: row.facts.clone(),
+                message: format!(
+                    "{} falls to NotApplicable for {} of {} fact assignments",
+                    format_assignment(&row.request),
+                    count,
+                    fact_rows
+                ),
+            });
+        }
+
+        for (tied, first_row) in ties {
+            let row = &report.matrix[first_row];
+            report.findings.push(CoverageFinding {
+                kind: CoverageFindingKind::SpecificityTie,
+                message: format!(
+                    "Policies {} tie on specificity for {} | {} and escalate",
+                    tied.join(", "),
+                    format_assignment(&row.request),
+                    format_assignment(&row.facts)
+                ),
+                policies: tied,
+                request: row.request.clone(),
+                facts: row.facts.clone(),
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum FactValue {
+    /// The value, asserted by `sources[source]` of its dimension.
+    Present(serde_json::Value, usize),
+    Unknown,
+}
+
+struct FactDimension {
+    path: String,
+    values: Vec<FactValue>,
+    /// Every `(class, id)` some requirement on `path` accepts, so each
+    /// policy sees a fact it trusts in some scenario.
+    sources: Vec<(SourceClass, String)>,
+}
+
+/// Structural keys, sorted, each with every named value (sorted by its JSON
+/// text) followed by `None` for "unmatched".
+fn structural_dimensions(
+    policies: &[&AuthorityPolicy],
+) -> Vec<(String, Vec<Option<serde_json::Value>>)> {
+    let mut dims: BTreeMap<String, BTreeMap<String, serde_json::Value>> = BTreeMap::new();
+    for policy in policies {
+        for (key, value) in &policy.applies_to.predicates {
+            dims.entry(key.clone())
+                .or_default()
+                .insert(value.to_string(), value.clone());
+        }
+    }
+    dims.into_iter()
+        .map(|(key, values)| {
+            let mut values: Vec<Option<serde_json::Value>> =
+                values.into_values().map(Some).collect();
+            values.push(None);
+            (key, values)
+        })
+        .collect()
+}
+
+fn fact_dimensions(policies: &[&AuthorityPolicy]) -> Vec<FactDimension> {
+    let mut compared: BTreeMap<String, BTreeMap<String, serde_json::Value>> = BTreeMap::new();
+    let mut requirements: BTreeMap<String, Vec<&FactRequirement>> = BTreeMap::new();
+
+    for policy in policies {
+        if let Some(ref when) = policy.when {
+            for (path, expected) in &when.conditions {
+                let operand = expected
+                    .as_object()
+                    .and_then(|o| o.get("__neq"))
+                    .unwrap_or(expected);
+                compared
+                    .entry(path.clone())
+                    .or_default()
+                    .insert(operand.to_string(), operand.clone());
+            }
+        }
+        for req in &policy.requires_fact {
+            requirements
+                .entry(req.fact_path.clone())
+                .or_default()
+                .push(req);
+        }
+    }
+
+    let paths: BTreeSet<&String> = compared.keys().chain(requirements.keys()).collect();
+    paths
+        .into_iter()
+        .map(|path| {
+            let named: Vec<serde_json::Value> = match compared.get(path) {
+                Some(named) => named
+                    .values()
+                    .cloned()
+                    .chain([serde_json::Value::String(OTHER_SENTINEL.to_string())])
+                    .collect(),
+                None => vec![serde_json::Value::String(PRESENT_SENTINEL.to_string())],
+            };
+
+            let mut sources: Vec<(SourceClass, String)> = Vec::new();
+            for req in requirements.get(path).into_iter().flatten() {
+                let id = req
+                    .allowed_source_ids
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| EXPLORER_SOURCE_ID.to_string());
+                for class in &req.allowed_source_classes {
+                    let source = (*class, id.clone());
+                    if !sources.contains(&source) {
+                        sources.push(source);
+                    }
+                }
+            }
+            if sources.is_empty() {
+                sources.push((SourceClass::UnknownSource, EXPLORER_SOURCE_ID.to_string()));
+            }
+
+            let mut values: Vec<FactValue> = named
+                .into_iter()
+                .flat_map(|value| {
+                    (0..sources.len()).map(move |source| FactValue::Present(value.clone(), source))
+                })
+                .collect();
+            values.push(FactValue::Unknown);
+
+            FactDimension {
+                path: path.clone(),
+                values,
+                sources,
+            }
+        })
+        .collect()
+}
+
+/// Every index combination over dimensions of the given sizes, last
+/// dimension varying fastest. No dimensions yields one empty combination.
+fn odometer(sizes: Vec<usize>) -> Vec<Vec<usize>> {
+    let mut out = vec![vec![]];
+    for size in sizes {
+        out = out
+            .into_iter()
+            .flat_map(|prefix| {
+                (0..size).map(move |i| {
+                    let mut next = prefix.clone();
+                    next.push(i);
+                    next
+                })
+            })
+            .collect();
+    }
+    out
+}
+
+fn build_request(
+    assignment: &[(&String, &Option<serde_json::Value>)],
+    seq: usize,
+    now: chrono::DateTime<Utc>,
+) -> AuthorityRequest {
+    let as_string = |v: &Option<serde_json::Value>| -> Option<String> {
+        v.as_ref().map(|v| match v {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    };
+
+    let mut request = AuthorityRequest {
+        request_id: format!("explore-{}", seq),
+        actor: ActorContext {
+            id: OTHER_SENTINEL.to_string(),
+            role: None,
+            groups: vec![],
+            service_account: None,
+            agent_identity: None,
+        },
+        operation: OTHER_SENTINEL.to_string(),
+        resource: ResourceRef {
+            id: None,
+            type_: None,
+            extra: Default::default(),
+        },
+        context: serde_json::json!({}),
+        requested_at: now,
+        correlation_id: None,
+        risk_class: None,
+        metadata: Default::default(),
+    };
+
+    for (key, value) in assignment {
+        match key.as_str() {
+            "action" => {
+                if let Some(s) = as_string(value) {
+                    request.operation = s;
+                }
+            }
+            "actor.id" => {
+                if let Some(s) = as_string(value) {
+                    request.actor.id = s;
+                }
+            }
+            "actor.role" => request.actor.role = as_string(value),
+            "resource.id" => request.resource.id = as_string(value),
+            "resource.type" => request.resource.type_ = as_string(value),
+            _ => {
+                if let Some(v) = value {
+                    request.metadata.insert((*key).clone(), v.clone());
+                }
+            }
+        }
+    }
+
+    if request.resource.id.is_none() && request.resource.type_.is_none() {
+        request.resource.type_ = Some(OTHER_SENTINEL.to_string());
+    }
+    request
+}
+
+fn build_facts(
+    assignment: &[(&FactDimension, &FactValue)],
+    now: chrono::DateTime<Utc>,
+) -> Vec<FactEnvelope> {
+    assignment
+        .iter()
+        .filter_map(|(dim, value)| match value {
+            FactValue::Unknown => None,
+            FactValue::Present(v, source) => Some(FactEnvelope {
+                path: dim.path.clone(),
+                value: v.clone(),
+                source_class: dim.sources[*source].0,
+                source_id: dim.sources[*source].1.clone(),
+                observed_at: now,
+                expires_at: None,
+                evidence_ref: Some(EXPLORER_SOURCE_ID.to_string()),
+                signature: Some(EXPLORER_SOURCE_ID.to_string()),
+                confidence: Some(1.0),
+                lineage: None,
+            }),
+        })
+        .collect()
+}
+
+/// Obligations with an `obligation_spec` surface on every applicable
+/// request whoever wins conflict resolution (A8), so never deciding is not
+/// shadowing for them.
+fn duty_surfaces_anyway(policy: &AuthorityPolicy) -> bool {
+    policy.modality == PolicyModality::Obligation && policy.obligation_spec.is_some()
+}
+
+fn deciding_policy(output: &ResolverOutput) -> Option<String> {
+    output
+        .conflict_resolution_steps
+        .last()
+        .filter(|s| DECIDING_STEPS.contains(&s.step.as_str()))
+        .map(|s| s.policy_id.clone())
+}
+
+fn display_structural(value: &Option<serde_json::Value>) -> String {
+    match value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => OTHER_VALUE.to_string(),
+    }
+}
+
+/// A fact value for display; when the path has several trusted sources the
+/// value is suffixed with the asserting class, e.g. `gold@system_of_record`.
+fn display_fact(dim: &FactDimension, value: &FactValue) -> String {
+    let (value, source) = match value {
+        FactValue::Unknown => return UNKNOWN_VALUE.to_string(),
+        FactValue::Present(value, source) => (value, *source),
+    };
+    let shown = match value {
+        serde_json::Value::String(s) if s == OTHER_SENTINEL => OTHER_VALUE.to_string(),
+        serde_json::Value::String(s) if s == PRESENT_SENTINEL => PRESENT_VALUE.to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if dim.sources.len() > 1 {
+        format!("{}@{}", shown, dim.sources[source].0)
+    } else {
+        shown
+    }
+}
+
+fn format_assignment(assignment: &BTreeMap<String, String>) -> String {
+    if assignment.is_empty() {
+        return "-".to_string();
+    }
+    assignment
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decision_label(decision: FinalDecision) -> &'static str {
+    match decision {
+        FinalDecision::Allow => "allow",
+        FinalDecision::Deny => "deny",
+        FinalDecision::Escalate => "escalate",
+        FinalDecision::NotApplicable => "not_applicable",
+        FinalDecision::Reject => "reject",
+    }
+}
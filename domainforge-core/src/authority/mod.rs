@@ -1,10 +1,12 @@
 pub mod compiler;
 pub mod environment;
 pub mod error;
+pub mod explorer;
 pub mod fact_resolver;
 pub mod pack;
 pub mod policy;
 pub mod resolver;
+pub mod sea_compiler;
 #[cfg(feature = "signing")]
 pub mod signing;
 pub mod trace;
@@ -16,12 +18,18 @@ pub use compiler::{
 };
 pub use environment::{AuthorityEnvironment, AuthorityEnvironmentConfig};
 pub use error::{AuthorityError, AuthorityErrorCode};
+pub use explorer::{
+    CoverageFinding, CoverageFindingKind, DecisionRow, DecisionSpaceExplorer, DecisionSpaceReport,
+};
 pub use fact_resolver::{FactResolver, FactSourceRegistry};
 pub use pack::{compute_pack_hash, AuthorityPack};
 pub use policy::{
     AuthorityPolicy, ConditionPredicates, ObligationSpec, OverrideSpec, StructuralPredicates,
 };
 pub use resolver::AuthorityResolver;
+pub use sea_compiler::{
+    SeaCompilation, SeaCompileFinding, SeaCompileFindingKind, SeaCompileReport, SeaPolicyCompiler,
+};
 pub use trace::{AuthorityTrace, AuthorityTraceEmitter, EvidenceSink};
 pub use transform::{DerivedFactEngine, FactTransformRegistry};
 pub use types::*;
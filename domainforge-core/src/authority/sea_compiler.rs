@@ -0,0 +1,568 @@
+//! Compiles SEA `policy` declarations into authority packs.
+//!
+//! Hand-written `RawPolicy` JSON drifts from the `policy` declarations in the
+//! model it is meant to enforce. This compiler derives the packs from the
+//! model instead: each policy's modality and priority come from its
+//! `per <Kind> <Modality> priority <n>` clause, and its top-level conjunction
+//! is lowered clause by clause:
+//!
+//! - `action = "X"`, `actor.id = "X"`, `actor.role = "X"`, `resource.id = "X"`,
+//!   `resource.type = "X"` → `applies_to`
+//! - `actor has_role "X"` and a bare `role<X>` → `applies_to["actor.role"]`
+//! - any other `path = value` → `when[path]` plus a `requires_fact` entry
+//! - `path != value` / `not path = value` → `when[path] = {"__neq": value}`
+//!
+//! The lowered policy then goes through [`PolicyCompiler`] so the structural
+//! key and fact-path checks are identical to those applied to raw JSON packs.
+//!
+//! # Fact sources
+//! An expression says *which* fact a policy reads, never *who may assert it*.
+//! Trusted source classes come from [`SeaPolicyCompiler::with_fact_source`] or
+//! a policy tag `@tags ["fact_source:<path>=<class>[,<class>]"]` (the tag
+//! wins). A fact with neither is still compiled — with an empty source-class
+//! list, so no envelope can satisfy it and the policy falls to its modality's
+//! unknown-handling default — and is listed in the report as needing a
+//! manual annotation. Fail closed, but visibly.
+//!
+//! Clauses that cannot be decided from a single request (quantifiers over
+//! `flows`, aggregations, ordering comparisons, top-level `or`) are not
+//! partially compiled: the whole policy is skipped and reported, mirroring
+//! [`CompatibilityLoweringAuditor`](super::CompatibilityLoweringAuditor)'s
+//! refusal to drop clauses silently.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::compiler::{PolicyCompiler, RawFactRequirement, RawPolicy};
+use super::error::AuthorityError;
+use super::pack::{compute_pack_hash, AuthorityPack};
+use super::policy::AuthorityPolicy;
+use super::types::{PolicyModality, SourceClass};
+use crate::graph::Graph;
+use crate::policy::{BinaryOp, Expression, Policy, PolicyKind, UnaryOp};
+
+/// Tag prefix carrying a per-policy fact-source annotation.
+pub const FACT_SOURCE_TAG_PREFIX: &str = "fact_source:";
+
+const STRUCTURAL_KEYS: [&str; 5] = [
+    "action",
+    "actor.id",
+    "actor.role",
+    "resource.id",
+    "resource.type",
+];
+
+/// Why a policy (or one of its clauses) was reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeaCompileFindingKind {
+    /// A `when` fact has no trusted source classes; it was compiled
+    /// fail-closed and needs a manual annotation.
+    MissingFactSource,
+    /// The policy could not be lowered and was not emitted.
+    UnsupportedExpression,
+    /// `actor.role` names a role the model does not declare.
+    UnknownRole,
+    /// `resource.type` names a resource no flow in the model moves.
+    UnknownResource,
+    /// The policy has no structural predicate and applies to every request.
+    Unscoped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeaCompileFinding {
+    pub policy_id: String,
+    pub kind: SeaCompileFindingKind,
+    /// The offending (sub-)expression in SEA display form.
+    pub expression: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeaCompileReport {
+    /// Ids of the policies emitted into a pack.
+    pub compiled: Vec<String>,
+    /// Ids of the policies skipped because they could not be lowered.
+    pub skipped: Vec<String>,
+    pub findings: Vec<SeaCompileFinding>,
+}
+
+impl SeaCompileReport {
+    /// Findings that need a `fact_source` annotation before the policy can
+    /// ever be satisfied.
+    pub fn missing_fact_sources(&self) -> Vec<&SeaCompileFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.kind == SeaCompileFindingKind::MissingFactSource)
+            .collect()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SeaCompilation {
+    /// One pack per model namespace, ordered by namespace.
+    pub packs: Vec<AuthorityPack>,
+    pub report: SeaCompileReport,
+}
+
+pub struct SeaPolicyCompiler {
+    compiler: PolicyCompiler,
+    pack_version: String,
+    specificity_profile: String,
+    fact_sources: BTreeMap<String, Vec<SourceClass>>,
+}
+
+impl SeaPolicyCompiler {
+    pub fn new(semantics_version: String, compatibility_version: String) -> Self {
+        Self {
+            compiler: PolicyCompiler::new(semantics_version, compatibility_version),
+            pack_version: "1.0.0".to_string(),
+            specificity_profile: "default".to_string(),
+            fact_sources: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_pack_version(mut self, version: impl Into<String>) -> Self {
+        self.pack_version = version.into();
+        self
+    }
+
+    pub fn with_specificity_profile(mut self, profile_id: impl Into<String>) -> Self {
+        self.specificity_profile = profile_id.into();
+        self
+    }
+
+    /// Trusted source classes for `fact_path` across every policy that does
+    /// not override them with a `fact_source:` tag.
+    pub fn with_fact_source(
+        mut self,
+        fact_path: impl Into<String>,
+        classes: impl IntoIterator<Item = SourceClass>,
+    ) -> Self {
+        self.fact_sources
+            .insert(fact_path.into(), classes.into_iter().collect());
+        self
+    }
+
+    pub fn compile(&self, graph: &Graph) -> Result<SeaCompilation, AuthorityError> {
+        let context = ModelContext::from_graph(graph);
+        let mut report = SeaCompileReport::default();
+        let mut by_namespace: BTreeMap<String, Vec<AuthorityPolicy>> = BTreeMap::new();
+
+        let mut policies = graph.all_policies();
+        policies.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+
+        for policy in policies {
+            match self.lower(policy, &context, &mut report.findings)? {
+                Some(raw) => {
+                    let compiled = self.compiler.compile(vec![raw])?.pop().ok_or_else(|| {
+                        AuthorityError::new(
+                            super::error::AuthorityErrorCode::PolicyParseError,
+                            format!("Policy '{}' compiled to no authority policy", policy.name),
+                        )
+                    })?;
+                    report.compiled.push(compiled.policy_id.clone());
+                    by_namespace
+                        .entry(policy.namespace.clone())
+                        .or_default()
+                        .push(compiled);
+                }
+                None => report.skipped.push(policy.name.clone()),
+            }
+        }
+
+        let mut packs = Vec::new();
+        for (namespace, policies) in by_namespace {
+            let hash = compute_pack_hash(
+                &namespace,
+                &self.pack_version,
+                self.compiler.semantics_version(),
+                &self.specificity_profile,
+                &policies,
+            )?;
+            let pack = AuthorityPack {
+                id: namespace,
+                version: self.pack_version.clone(),
+                semantics_version: self.compiler.semantics_version().to_string(),
+                required_specificity_profile: self.specificity_profile.clone(),
+                policies,
+                hash,
+                signature: None,
+                owner: None,
+                created_at: None,
+                approved_by: None,
+                evidence_ref: None,
+            };
+            pack.validate()?;
+            packs.push(pack);
+        }
+
+        Ok(SeaCompilation { packs, report })
+    }
+
+    /// Lower one SEA policy to a `RawPolicy`, or `None` (with findings) when
+    /// it cannot be decided from a single authority request.
+    fn lower(
+        &self,
+        policy: &Policy,
+        context: &ModelContext,
+        findings: &mut Vec<SeaCompileFinding>,
+    ) -> Result<Option<RawPolicy>, AuthorityError> {
+        let unsupported = |findings: &mut Vec<SeaCompileFinding>, expr: &Expression, why: &str| {
+            findings.push(SeaCompileFinding {
+                policy_id: policy.name.clone(),
+                kind: SeaCompileFindingKind::UnsupportedExpression,
+                expression: expr.to_string(),
+                message: why.to_string(),
+            });
+        };
+
+        if policy.kind == PolicyKind::Derivation {
+            unsupported(
+                findings,
+                policy.expression(),
+                "derivation policies compute values and carry no authority decision",
+            );
+            return Ok(None);
+        }
+
+        let mut applies_to: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut when: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut clauses = Vec::new();
+        conjuncts(policy.expression(), &mut clauses);
+
+        for clause in clauses {
+            let lowered = match lower_clause(clause) {
+                Ok(Some(lowered)) => lowered,
+                Ok(None) => continue,
+                Err(why) => {
+                    unsupported(findings, clause, &why);
+                    return Ok(None);
+                }
+            };
+            let Clause {
+                key,
+                value,
+                negated,
+            } = lowered;
+            let structural = STRUCTURAL_KEYS.contains(&key.as_str());
+            if !structural && (key.starts_with("actor.") || key.starts_with("resource.")) {
+                unsupported(
+                    findings,
+                    clause,
+                    &format!(
+                        "'{key}' uses a prefix reserved for the structural keys {STRUCTURAL_KEYS:?}"
+                    ),
+                );
+                return Ok(None);
+            }
+            if structural && negated {
+                // Spec §14.1.7: negative structural predicates are ambiguous.
+                unsupported(
+                    findings,
+                    clause,
+                    "negated structural predicates are ambiguous and cannot be lowered",
+                );
+                return Ok(None);
+            }
+
+            let (target, value) = if structural {
+                context.check(&policy.name, &key, &value, clause, findings);
+                (&mut applies_to, value)
+            } else if negated {
+                (&mut when, serde_json::json!({ "__neq": value }))
+            } else {
+                (&mut when, value)
+            };
+            if let Some(previous) = target.get(&key) {
+                if *previous != value {
+                    unsupported(
+                        findings,
+                        clause,
+                        &format!("'{key}' is constrained to two different values"),
+                    );
+                    return Ok(None);
+                }
+            }
+            target.insert(key, value);
+        }
+
+        if applies_to.is_empty() {
+            findings.push(SeaCompileFinding {
+                policy_id: policy.name.clone(),
+                kind: SeaCompileFindingKind::Unscoped,
+                expression: policy.expression().to_string(),
+                message: "no action, actor or resource predicate; the policy is a candidate for \
+                          every request"
+                    .to_string(),
+            });
+        }
+
+        let tagged = tagged_fact_sources(policy)?;
+        let mut paths: Vec<&String> = when.keys().collect();
+        paths.sort();
+        let requires_fact = paths
+            .into_iter()
+            .map(|path| {
+                let classes = tagged
+                    .get(path)
+                    .or_else(|| self.fact_sources.get(path))
+                    .cloned()
+                    .unwrap_or_default();
+                if classes.is_empty() {
+                    findings.push(SeaCompileFinding {
+                        policy_id: policy.name.clone(),
+                        kind: SeaCompileFindingKind::MissingFactSource,
+                        expression: path.clone(),
+                        message: format!(
+                            "fact '{path}' has no trusted source classes; annotate it with \
+                             `@tags [\"{FACT_SOURCE_TAG_PREFIX}{path}=system_of_record\"]`"
+                        ),
+                    });
+                }
+                RawFactRequirement {
+                    fact_path: path.clone(),
+                    allowed_source_classes: classes,
+                    allowed_source_ids: None,
+                    max_age: None,
+                    evidence_ref_required: None,
+                    signature_required: None,
+                    minimum_confidence: None,
+                    required_transform: None,
+                    derived_from_source: None,
+                }
+            })
+            .collect();
+
+        Ok(Some(RawPolicy {
+            policy_id: policy.name.clone(),
+            modality: match policy.modality {
+                crate::policy::PolicyModality::Obligation => PolicyModality::Obligation,
+                crate::policy::PolicyModality::Prohibition => PolicyModality::Prohibition,
+                crate::policy::PolicyModality::Permission => PolicyModality::Permission,
+            },
+            priority: policy.priority,
+            applies_to,
+            when,
+            requires_fact,
+            override_spec: None,
+            obligation_spec: None,
+            description: policy.rationale.clone(),
+            evidence_ref: None,
+        }))
+    }
+}
+
+/// Role and flow context used to flag predicates that can never match.
+struct ModelContext {
+    roles: BTreeSet<String>,
+    flow_resources: BTreeSet<String>,
+}
+
+impl ModelContext {
+    fn from_graph(graph: &Graph) -> Self {
+        Self {
+            roles: graph
+                .all_roles()
+                .iter()
+                .map(|r| r.name().to_string())
+                .collect(),
+            flow_resources: graph
+                .all_flows()
+                .iter()
+                .filter_map(|f| graph.get_resource(f.resource_id()))
+                .map(|r| r.name().to_string())
+                .collect(),
+        }
+    }
+
+    fn check(
+        &self,
+        policy_id: &str,
+        key: &str,
+        value: &serde_json::Value,
+        clause: &Expression,
+        findings: &mut Vec<SeaCompileFinding>,
+    ) {
+        let Some(name) = value.as_str() else {
+            return;
+        };
+        let (kind, message) = match key {
+            "actor.role" if !self.roles.contains(name) => (
+                SeaCompileFindingKind::UnknownRole,
+                format!("role '{name}' is not declared in the model"),
+            ),
+            "resource.type" if !self.flow_resources.contains(name) => (
+                SeaCompileFindingKind::UnknownResource,
+                format!("no flow in the model moves a resource named '{name}'"),
+            ),
+            _ => return,
+        };
+        findings.push(SeaCompileFinding {
+            policy_id: policy_id.to_string(),
+            kind,
+            expression: clause.to_string(),
+            message,
+        });
+    }
+}
+
+struct Clause {
+    key: String,
+    value: serde_json::Value,
+    negated: bool,
+}
+
+fn conjuncts<'a>(expr: &'a Expression, out: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::Binary {
+            op: BinaryOp::And,
+            left,
+            right,
+        } => {
+            conjuncts(left, out);
+            conjuncts(right, out);
+        }
+        other => out.push(other),
+    }
+}
+
+/// `Ok(None)` for a clause that constrains nothing (`true`).
+fn lower_clause(expr: &Expression) -> Result<Option<Clause>, String> {
+    match expr {
+        Expression::Literal(serde_json::Value::Bool(true)) => Ok(None),
+        Expression::RoleReference { role } => Ok(Some(Clause {
+            key: "actor.role".to_string(),
+            value: serde_json::Value::String(role.clone()),
+            negated: false,
+        })),
+        Expression::Binary {
+            op: op @ (BinaryOp::Equal | BinaryOp::NotEqual),
+            left,
+            right,
+        } => {
+            let (key, value) = path_and_value(left, right).ok_or_else(|| {
+                "only `<path> = <literal>` comparisons can be decided from a request".to_string()
+            })?;
+            Ok(Some(Clause {
+                key,
+                value,
+                negated: *op == BinaryOp::NotEqual,
+            }))
+        }
+        Expression::Binary {
+            op: BinaryOp::HasRole,
+            left,
+            right,
+        } => {
+            let subject = path_of(left);
+            if subject.as_deref() != Some("actor") {
+                return Err("`has_role` is only decidable for the requesting `actor`".to_string());
+            }
+            let role = match right.as_ref() {
+                Expression::Literal(serde_json::Value::String(s)) => s.clone(),
+                Expression::Variable(s) => s.clone(),
+                Expression::RoleReference { role } => role.clone(),
+                _ => return Err("`has_role` needs a role name".to_string()),
+            };
+            Ok(Some(Clause {
+                key: "actor.role".to_string(),
+                value: serde_json::Value::String(role),
+                negated: false,
+            }))
+        }
+        Expression::Unary {
+            op: UnaryOp::Not,
+            operand,
+        } => match operand.as_ref() {
+            Expression::Binary {
+                op: BinaryOp::Equal,
+                left,
+                right,
+            } => {
+                let (key, value) = path_and_value(left, right)
+                    .ok_or_else(|| "only `not <path> = <literal>` can be lowered".to_string())?;
+                Ok(Some(Clause {
+                    key,
+                    value,
+                    negated: true,
+                }))
+            }
+            _ => Err("only `not <path> = <literal>` can be lowered".to_string()),
+        },
+        Expression::Binary {
+            op: BinaryOp::Or, ..
+        } => Err("top-level `or` is ambiguous; split it into separate policies".to_string()),
+        Expression::Quantifier { .. }
+        | Expression::Aggregation { .. }
+        | Expression::AggregationComprehension { .. }
+        | Expression::GroupBy { .. } => Err(
+            "model-wide quantifiers and aggregations are not request-time decisions".to_string(),
+        ),
+        _ => Err("expression has no authority lowering".to_string()),
+    }
+}
+
+fn path_and_value(left: &Expression, right: &Expression) -> Option<(String, serde_json::Value)> {
+    match (path_of(left), literal_of(right)) {
+        (Some(path), Some(value)) => Some((path, value)),
+        _ => match (path_of(right), literal_of(left)) {
+            (Some(path), Some(value)) => Some((path, value)),
+            _ => None,
+        },
+    }
+}
+
+fn path_of(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Variable(name) => Some(name.clone()),
+        Expression::MemberAccess { object, member } => Some(format!("{object}.{member}")),
+        _ => None,
+    }
+}
+
+fn literal_of(expr: &Expression) -> Option<serde_json::Value> {
+    match expr {
+        Expression::Literal(v) if !v.is_null() => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Parse `fact_source:<path>=<class>[,<class>]` tags.
+fn tagged_fact_sources(
+    policy: &Policy,
+) -> Result<BTreeMap<String, Vec<SourceClass>>, AuthorityError> {
+    let mut out = BTreeMap::new();
+    for tag in &policy.tags {
+        let Some(spec) = tag.strip_prefix(FACT_SOURCE_TAG_PREFIX) else {
+            continue;
+        };
+        let invalid = |detail: String| {
+            AuthorityError::new(
+                super::error::AuthorityErrorCode::PolicyParseError,
+                format!(
+                    "Policy '{}': invalid tag '{}': {}",
+                    policy.name, tag, detail
+                ),
+            )
+        };
+        let (path, classes) = spec
+            .split_once('=')
+            .ok_or_else(|| invalid("expected `<path>=<class>[,<class>]`".to_string()))?;
+        let classes = classes
+            .split(',')
+            .map(|c| {
+                serde_json::from_value::<SourceClass>(serde_json::Value::String(
+                    c.trim().to_string(),
+                ))
+                .map_err(|_| invalid(format!("unknown source class '{}'", c.trim())))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        out.insert(path.trim().to_string(), classes);
+    }
+    Ok(out)
+}
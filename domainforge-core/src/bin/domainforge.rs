@@ -1,7 +1,7 @@
 use clap::Parser;
 use domainforge_core::cli::{
-    authority, format, import, normalize, pack, parse, project, registry, test, validate,
-    validate_kg, Cli, Commands,
+    authority, build, check_payload, format, import, mcp, normalize, pack, package, parse, project,
+    registry, test, validate, validate_kg, Cli, Commands,
 };
 
 fn main() -> anyhow::Result<()> {
@@ -13,7 +13,8 @@ fn main() -> anyhow::Result<()> {
         Commands::Parse(args) => parse::run(args),
         Commands::Validate(args) => validate::run(args),
         Commands::Import(args) => import::run(args),
-        Commands::Project(args) => project::run(args),
+        Commands::Project(args) => project::run(*args),
+        Commands::Build(args) => build::run(args),
         Commands::Format(args) => format::run(args),
         Commands::Test(args) => test::run(args),
         Commands::ValidateKg(args) => validate_kg::run(args),
@@ -21,5 +22,8 @@ fn main() -> anyhow::Result<()> {
         Commands::Registry(args) => registry::run(args),
         Commands::Authority(args) => authority::run(args),
         Commands::Pack(args) => pack::run(args),
+        Commands::Package(args) => package::run(args),
+        Commands::CheckPayload(args) => check_payload::run(args),
+        Commands::Mcp(args) => mcp::run(args),
     }
 }
@@ -0,0 +1,489 @@
+//! Stable C ABI (cargo feature `capi`) for embedding the core in JVM, Go
+//! and .NET services.
+//!
+//! Every call exchanges NUL-terminated UTF-8 JSON. Results and errors are
+//! returned through `out_*` pointers as strings the caller owns and releases
+//! with [`df_string_free`]; workspaces are released with
+//! [`df_workspace_free`]. Each function returns a `DF_*` status code, and on
+//! failure `*out_error` holds `{"code", "message", ...}`: `E…` codes from
+//! `ValidationError`, `APP…` codes with `diagnostics` for workspace
+//! resolution, and `AuthorityErrorCode` names for authority decisions.
+//!
+//! `include/domainforge.h` is generated from this file; regenerate it with
+//! `DOMAINFORGE_BLESS_HEADER=1 cargo test --features capi --test capi_tests`.
+
+mod project;
+
+use crate::application::{resolve_workspace, WorkspaceResolution};
+use crate::authority::{
+    AuthorityEnvironment, AuthorityEnvironmentConfig, AuthorityError, AuthorityRequest,
+    FactEnvelope,
+};
+use crate::parser::ParseError;
+use serde_json::{json, Value};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Version of this ABI; bumped on any incompatible change to the header.
+pub const DF_ABI_VERSION: u32 = 1;
+
+/// The call succeeded and its `out_*` results are set.
+pub const DF_OK: i32 = 0;
+/// A required pointer argument was NULL.
+pub const DF_ERR_NULL_ARGUMENT: i32 = 1;
+/// A string argument was not valid UTF-8.
+pub const DF_ERR_INVALID_UTF8: i32 = 2;
+/// A JSON argument did not parse or did not match its schema.
+pub const DF_ERR_INVALID_JSON: i32 = 3;
+/// An argument was well-formed but not accepted (unknown format or policy).
+pub const DF_ERR_INVALID_ARGUMENT: i32 = 4;
+/// SEA source failed to parse; the error `code` is a `ValidationError` code.
+pub const DF_ERR_PARSE: i32 = 5;
+/// The workspace did not resolve; the error carries APP `diagnostics`.
+pub const DF_ERR_RESOLUTION: i32 = 6;
+/// The authority environment rejected the call; the error `code` is an
+/// `AuthorityErrorCode`.
+pub const DF_ERR_AUTHORITY: i32 = 7;
+/// A projection failed to render.
+pub const DF_ERR_PROJECTION: i32 = 8;
+/// A handle argument was not NULL but cannot serve the call (a workspace
+/// without a graph).
+pub const DF_ERR_INVALID_HANDLE: i32 = 9;
+/// The core panicked; the call had no effect. Only builds that unwind return
+/// this: the workspace release profile sets `panic = 'abort'`, so in a
+/// release build a panic aborts the host process instead.
+pub const DF_ERR_PANIC: i32 = 99;
+
+/// A resolved workspace: the graph and semantic envelope of one entry module
+/// and its import closure.
+pub struct DfWorkspace {
+    resolution: WorkspaceResolution,
+}
+
+/// A failed call: its status and the error document handed to the caller.
+struct Failure {
+    status: i32,
+    error: Value,
+}
+
+impl Failure {
+    fn new(status: i32, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: json!({ "code": code, "message": message.into() }),
+        }
+    }
+
+    fn null_argument(name: &str) -> Self {
+        Self::new(
+            DF_ERR_NULL_ARGUMENT,
+            "null_argument",
+            format!("argument '{name}' is NULL"),
+        )
+    }
+
+    fn invalid_json(name: &str, error: serde_json::Error) -> Self {
+        Self::new(
+            DF_ERR_INVALID_JSON,
+            "invalid_json",
+            format!("argument '{name}' is not valid JSON: {error}"),
+        )
+    }
+
+    fn parse(error: &ParseError) -> Self {
+        let mut failure = Self::new(DF_ERR_PARSE, error.error_code().as_str(), error.to_string());
+        if let Some((line, column)) = error.location() {
+            failure.error["line"] = json!(line);
+            failure.error["column"] = json!(column);
+        }
+        failure
+    }
+
+    fn authority(error: AuthorityError) -> Self {
+        let error = serde_json::to_value(&error).unwrap_or_else(
+            |_| json!({ "code": format!("{:?}", error.code), "message": error.message }),
+        );
+        Self {
+            status: DF_ERR_AUTHORITY,
+            error,
+        }
+    }
+}
+
+type CallResult<T> = Result<T, Failure>;
+
+/// Run `body`, turning its result into a status code and `out_error`.
+fn guard(out_error: *mut *mut c_char, body: impl FnOnce() -> CallResult<()>) -> i32 {
+    let failure = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => return DF_OK,
+        Ok(Err(failure)) => failure,
+        Err(_) => Failure::new(DF_ERR_PANIC, "panic", "domainforge-core panicked"),
+    };
+    // SAFETY: the caller passes NULL or a valid `char **` for `out_error`.
+    unsafe { write_string(out_error, &failure.error.to_string()) };
+    failure.status
+}
+
+/// Borrow a required string argument.
+///
+/// # Safety
+/// `ptr` must be NULL or point to a NUL-terminated string that outlives the call.
+unsafe fn required_str<'a>(ptr: *const c_char, name: &str) -> CallResult<&'a str> {
+    optional_str(ptr, name)?.ok_or_else(|| Failure::null_argument(name))
+}
+
+/// Borrow an optional (NULL-able) string argument.
+///
+/// # Safety
+/// As for [`required_str`].
+unsafe fn optional_str<'a>(ptr: *const c_char, name: &str) -> CallResult<Option<&'a str>> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr).to_str().map(Some).map_err(|_| {
+        Failure::new(
+            DF_ERR_INVALID_UTF8,
+            "invalid_utf8",
+            format!("argument '{name}' is not valid UTF-8"),
+        )
+    })
+}
+
+/// Borrow a required workspace handle.
+///
+/// # Safety
+/// `ptr` must be NULL or a live handle from [`df_workspace_resolve`].
+unsafe fn resolution_of<'a>(ptr: *const DfWorkspace) -> CallResult<&'a WorkspaceResolution> {
+    ptr.as_ref()
+        .map(|workspace| &workspace.resolution)
+        .ok_or_else(|| Failure::null_argument("workspace"))
+}
+
+/// Hand `value` to the caller through `out`, if `out` is not NULL.
+///
+/// # Safety
+/// `out` must be NULL or a valid `char **`.
+unsafe fn write_string(out: *mut *mut c_char, value: &str) {
+    if out.is_null() {
+        return;
+    }
+    // Interior NULs cannot occur in serialized JSON; drop them defensively.
+    let owned = CString::new(value.replace('\0', "")).unwrap_or_default();
+    *out = owned.into_raw();
+}
+
+/// Serialize `value` into `out` as the call's result.
+///
+/// # Safety
+/// As for [`write_string`].
+unsafe fn write_json(out: *mut *mut c_char, value: &Value) -> CallResult<()> {
+    if out.is_null() {
+        return Err(Failure::null_argument("out_json"));
+    }
+    write_string(out, &value.to_string());
+    Ok(())
+}
+
+/// ABI version of the loaded library (`DF_ABI_VERSION` it was built with).
+#[no_mangle]
+pub extern "C" fn df_abi_version() -> u32 {
+    DF_ABI_VERSION
+}
+
+/// Version of domainforge-core, as a static string the caller must not free.
+#[no_mangle]
+pub extern "C" fn df_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Release a string returned through an `out_json` or `out_error` pointer.
+/// NULL is ignored.
+///
+/// # Safety
+/// `value` must be NULL or a string returned by this library, freed once.
+#[no_mangle]
+pub unsafe extern "C" fn df_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// Parse one SEA source into AST v3 JSON (`ast-v3.schema.json`).
+///
+/// # Safety
+/// `source` must be a NUL-terminated string; `out_json` and `out_error`
+/// must be NULL or valid `char **`.
+#[no_mangle]
+pub unsafe extern "C" fn df_parse(
+    source: *const c_char,
+    out_json: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let source = required_str(source, "source")?;
+        let ast = crate::parser::parse(source).map_err(|error| Failure::parse(&error))?;
+        let schema_ast: crate::parser::ast_schema::Ast = ast.into();
+        let value = serde_json::to_value(&schema_ast).expect("AST v3 always serializes");
+        write_json(out_json, &value)
+    })
+}
+
+/// Resolve an entry module and its import closure from `sources_json` (an
+/// object of logical path to SEA source), with an optional
+/// `.sea-registry.toml` in `registry_toml` (NULL for none). On success
+/// `*out_workspace` is a handle to release with `df_workspace_free`.
+///
+/// # Safety
+/// `entry_logical_path` and `sources_json` must be NUL-terminated strings,
+/// `registry_toml` NULL or one; `out_workspace` must be a valid
+/// `DfWorkspace **` and `out_error` NULL or a valid `char **`.
+#[no_mangle]
+pub unsafe extern "C" fn df_workspace_resolve(
+    entry_logical_path: *const c_char,
+    sources_json: *const c_char,
+    registry_toml: *const c_char,
+    out_workspace: *mut *mut DfWorkspace,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let entry = required_str(entry_logical_path, "entry_logical_path")?;
+        let sources = required_str(sources_json, "sources_json")?;
+        let registry = optional_str(registry_toml, "registry_toml")?;
+        if out_workspace.is_null() {
+            return Err(Failure::null_argument("out_workspace"));
+        }
+        let resolution = resolve_workspace(entry, sources, registry);
+        if let Some(first) = resolution.diagnostics.first() {
+            return Err(Failure {
+                status: DF_ERR_RESOLUTION,
+                error: json!({
+                    "code": first.code,
+                    "message": first.message,
+                    "diagnostics": resolution.diagnostics,
+                }),
+            });
+        }
+        *out_workspace = Box::into_raw(Box::new(DfWorkspace { resolution }));
+        Ok(())
+    })
+}
+
+/// Release a workspace from `df_workspace_resolve`. NULL is ignored.
+///
+/// # Safety
+/// `workspace` must be NULL or a live handle, released once.
+#[no_mangle]
+pub unsafe extern "C" fn df_workspace_free(workspace: *mut DfWorkspace) {
+    if !workspace.is_null() {
+        drop(Box::from_raw(workspace));
+    }
+}
+
+/// The workspace's canonical semantic envelope document.
+///
+/// # Safety
+/// `workspace` must be a live handle; `out_json` and `out_error` must be
+/// NULL or valid `char **`.
+#[no_mangle]
+pub unsafe extern "C" fn df_workspace_envelope(
+    workspace: *const DfWorkspace,
+    out_json: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let resolution = resolution_of(workspace)?;
+        let value = serde_json::to_value(&resolution.semantic_envelope)
+            .expect("semantic envelopes always serialize");
+        write_json(out_json, &value)
+    })
+}
+
+/// Validate the workspace as `domainforge validate` does: every policy and
+/// entity-instance check. The result is `{"valid", "total_policies",
+/// "error_count", "violations"}`; violations are not a call failure.
+///
+/// # Safety
+/// As for `df_workspace_envelope`.
+#[no_mangle]
+pub unsafe extern "C" fn df_workspace_validate(
+    workspace: *const DfWorkspace,
+    out_json: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let graph = graph_of(resolution_of(workspace)?)?;
+        let result = graph.validate();
+        write_json(
+            out_json,
+            &json!({
+                "valid": result.error_count == 0,
+                "total_policies": result.total_policies,
+                "error_count": result.error_count,
+                "violations": result.violations,
+            }),
+        )
+    })
+}
+
+/// Evaluate the workspace's policies, or only `policy_name` when it is not
+/// NULL. The result is `{"policies": [{"name", "namespace", "english",
+/// "satisfied", "violations"}], "violation_count"}`, with `satisfied` null
+/// when a policy evaluates to unknown and `error` in place of the result
+/// when it cannot be evaluated.
+///
+/// # Safety
+/// `workspace` must be a live handle and `policy_name` NULL or a
+/// NUL-terminated string; `out_json` and `out_error` must be NULL or valid
+/// `char **`.
+#[no_mangle]
+pub unsafe extern "C" fn df_workspace_evaluate_policies(
+    workspace: *const DfWorkspace,
+    policy_name: *const c_char,
+    out_json: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let graph = graph_of(resolution_of(workspace)?)?;
+        let only = optional_str(policy_name, "policy_name")?;
+        let mut policies = graph.all_policies();
+        policies.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+        if let Some(name) = only {
+            policies.retain(|policy| policy.name == name);
+            if policies.is_empty() {
+                return Err(Failure::new(
+                    DF_ERR_INVALID_ARGUMENT,
+                    "unknown_policy",
+                    format!("No policy named '{name}' in the workspace"),
+                ));
+            }
+        }
+        let mut violation_count = 0;
+        let results: Vec<Value> = policies
+            .into_iter()
+            .map(|policy| match policy.evaluate(graph) {
+                Ok(result) => {
+                    violation_count += result.violations.len();
+                    json!({
+                        "name": policy.name,
+                        "namespace": policy.namespace,
+                        "english": policy.to_english(),
+                        "satisfied": result.is_satisfied_tristate,
+                        "violations": result.violations,
+                    })
+                }
+                Err(error) => json!({
+                    "name": policy.name,
+                    "namespace": policy.namespace,
+                    "english": policy.to_english(),
+                    "error": error,
+                }),
+            })
+            .collect();
+        write_json(
+            out_json,
+            &json!({ "policies": results, "violation_count": violation_count }),
+        )
+    })
+}
+
+/// Decide an authority request: `config_json` is an
+/// `AuthorityEnvironmentConfig`, `request_json` an `AuthorityRequest` and
+/// `facts_json` a `FactEnvelope` array (NULL for none). The result is
+/// `{"decision", "trace"}`, as the MCP `authority_decision` tool returns it.
+///
+/// # Safety
+/// `config_json` and `request_json` must be NUL-terminated strings and
+/// `facts_json` NULL or one; `out_json` and `out_error` must be NULL or
+/// valid `char **`.
+#[no_mangle]
+pub unsafe extern "C" fn df_authority_decide(
+    config_json: *const c_char,
+    request_json: *const c_char,
+    facts_json: *const c_char,
+    out_json: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let config: AuthorityEnvironmentConfig =
+            serde_json::from_str(required_str(config_json, "config_json")?)
+                .map_err(|error| Failure::invalid_json("config_json", error))?;
+        let request: AuthorityRequest =
+            serde_json::from_str(required_str(request_json, "request_json")?)
+                .map_err(|error| Failure::invalid_json("request_json", error))?;
+        let facts: Vec<FactEnvelope> = match optional_str(facts_json, "facts_json")? {
+            Some(facts) => serde_json::from_str(facts)
+                .map_err(|error| Failure::invalid_json("facts_json", error))?,
+            None => Vec::new(),
+        };
+        let mut environment = AuthorityEnvironment::new(config).map_err(Failure::authority)?;
+        environment.validate().map_err(Failure::authority)?;
+        let (trace, decision) = environment
+            .evaluate(&request, &facts)
+            .map_err(Failure::authority)?;
+        write_json(out_json, &json!({ "decision": decision, "trace": trace }))
+    })
+}
+
+/// Render a projection of the workspace into memory: `format` is a
+/// `domainforge project --format` name and `options_json` (NULL for
+/// defaults) an object of `model_ref`, `created_at` and the format's own
+/// options. The result maps each relative artifact path to its contents.
+///
+/// # Safety
+/// `workspace` must be a live handle, `format` a NUL-terminated string and
+/// `options_json` NULL or one; `out_json` and `out_error` must be NULL or
+/// valid `char **`.
+#[no_mangle]
+pub unsafe extern "C" fn df_workspace_project(
+    workspace: *const DfWorkspace,
+    format: *const c_char,
+    options_json: *const c_char,
+    out_json: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let resolution = resolution_of(workspace)?;
+        let format = required_str(format, "format")?;
+        let options: project::ProjectOptions = match optional_str(options_json, "options_json")? {
+            Some(options) => serde_json::from_str(options)
+                .map_err(|error| Failure::invalid_json("options_json", error))?,
+            None => project::ProjectOptions::default(),
+        };
+        let contract = &resolution
+            .semantic_envelope
+            .as_ref()
+            .ok_or_else(|| Failure::null_argument("workspace"))?
+            .envelope
+            .application_contract;
+        let artifacts = project::render(graph_of(resolution)?, contract, format, options)?;
+        write_json(out_json, &json!(artifacts))
+    })
+}
+
+fn graph_of(resolution: &WorkspaceResolution) -> CallResult<&crate::graph::Graph> {
+    resolution.graph.as_ref().ok_or_else(|| {
+        Failure::new(
+            DF_ERR_INVALID_HANDLE,
+            "invalid_handle",
+            "argument 'workspace' holds no resolved graph",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_without_a_graph_is_an_invalid_handle() {
+        let resolution = WorkspaceResolution {
+            entry: "main.sea".to_string(),
+            graph: None,
+            semantic_envelope: None,
+            diagnostics: Vec::new(),
+        };
+        let failure = graph_of(&resolution).unwrap_err();
+        assert_eq!(failure.status, DF_ERR_INVALID_HANDLE);
+        assert_eq!(failure.error["code"], "invalid_handle");
+    }
+}
@@ -0,0 +1,203 @@
+//! `df_workspace_project`: dispatch a `--format` name to the in-memory twin of
+//! its projection. Formats that need files on disk (templates, plugins,
+//! schema history, published schemas) are CLI-only.
+
+use super::{CallResult, Failure, DF_ERR_INVALID_ARGUMENT, DF_ERR_PROJECTION};
+use crate::application::ApplicationContract;
+use crate::graph::Graph;
+use crate::projection;
+use crate::projection::diagram::DiagramFormat;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Formats `df_workspace_project` accepts, by `domainforge project --format` name.
+pub(super) const FORMATS: &[&str] = &[
+    "ai-learning",
+    "alloy",
+    "archimate",
+    "asyncapi",
+    "avro",
+    "baml",
+    "bpmn",
+    "cedar",
+    "cloudevents",
+    "cmmn",
+    "dagger",
+    "devbox",
+    "docs",
+    "domain-python",
+    "domain-rust",
+    "domain-typescript",
+    "dot",
+    "dspy",
+    "gauge",
+    "graphql",
+    "json-schema",
+    "lean",
+    "mermaid",
+    "otel-semconv",
+    "plantuml",
+    "rdf",
+    "sql-postgres",
+    "structurizr",
+    "tla",
+    "zenml",
+];
+
+/// The `options_json` object. Each format reads only the options it knows.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct ProjectOptions {
+    /// Provenance label for the source model.
+    model_ref: Option<String>,
+    /// Fixed RFC3339 timestamp for reproducible output.
+    created_at: Option<String>,
+    /// `archimate`: extra viewpoint names.
+    viewpoints: Vec<String>,
+    /// `rdf`: the IRI the `sea:` prefix expands to.
+    base_iri: Option<String>,
+    /// `mermaid`, `plantuml`, `dot`: limit the diagram to this entity's flows.
+    focus: Option<String>,
+    /// `ai-learning`, `baml`, `dspy`, `zenml`: the recipe, as JSON.
+    recipe: Option<serde_json::Value>,
+    /// `ai-learning`, `baml`, `dspy`, `zenml`: an `AuthorityEnvironmentConfig`.
+    authority_config: Option<serde_json::Value>,
+    /// `ai-learning`, `baml`, `dspy`, `zenml`: split/sampling seed override.
+    seed: Option<u64>,
+}
+
+pub(super) fn render(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    format: &str,
+    options: ProjectOptions,
+) -> CallResult<BTreeMap<String, String>> {
+    let model_ref = options.model_ref.as_deref().unwrap_or("<in-memory>");
+    let created_at = options.created_at.clone();
+    let recipe = options.recipe.as_ref().map(|recipe| recipe.to_string());
+    let authority = options
+        .authority_config
+        .as_ref()
+        .map(|config| config.to_string());
+    let learning = |project: LearningProjection| {
+        project(
+            graph,
+            recipe.as_deref(),
+            authority.as_deref(),
+            model_ref,
+            options.seed,
+            created_at.clone(),
+        )
+    };
+    let diagram = |format: DiagramFormat| {
+        projection::diagram::project_diagram_in_memory(
+            graph,
+            format,
+            model_ref,
+            created_at.clone(),
+            options.focus.as_deref(),
+        )
+    };
+
+    let artifacts = match format {
+        "ai-learning" => learning(projection::ai_learning::project_ai_learning_in_memory),
+        "baml" => learning(projection::baml::project_baml_in_memory),
+        "dspy" => learning(projection::dspy::project_dspy_in_memory),
+        "zenml" => learning(projection::zenml::project_zenml_in_memory),
+        "alloy" => projection::alloy::project_alloy_in_memory(graph, model_ref, created_at),
+        "archimate" => {
+            let viewpoints = projection::archimate::parse_viewpoints(&options.viewpoints).map_err(
+                |message| Failure::new(DF_ERR_INVALID_ARGUMENT, "invalid_argument", message),
+            )?;
+            projection::archimate::project_archimate_in_memory(
+                graph, model_ref, created_at, viewpoints,
+            )
+        }
+        "asyncapi" => {
+            projection::asyncapi::project_asyncapi_in_memory(graph, model_ref, created_at)
+        }
+        "avro" => projection::avro::project_avro_in_memory(graph, contract),
+        "bpmn" => projection::bpmn::project_bpmn_in_memory(graph, model_ref, created_at),
+        "cedar" => projection::cedar::project_cedar_in_memory(graph, model_ref, created_at),
+        "cloudevents" => {
+            projection::cloudevents::project_cloudevents_in_memory(graph, model_ref, created_at)
+        }
+        "cmmn" => projection::cmmn::project_cmmn_in_memory(graph, model_ref, created_at),
+        "dagger" => projection::dagger::project_dagger_in_memory(graph, model_ref, created_at),
+        "devbox" => projection::devbox::project_devbox_in_memory(graph, model_ref, created_at),
+        "docs" => {
+            projection::docs::project_docs_in_memory(graph, contract, None, model_ref, created_at)
+        }
+        "domain-python" => projection::domain::python::project_domain_python_in_memory(
+            graph,
+            model_ref,
+            created_at,
+            Some(contract),
+        ),
+        "domain-rust" => projection::domain::rust::project_domain_rust_in_memory(
+            graph,
+            model_ref,
+            created_at,
+            Some(contract),
+        ),
+        "domain-typescript" => projection::domain::typescript::project_domain_typescript_in_memory(
+            graph,
+            model_ref,
+            created_at,
+            Some(contract),
+        ),
+        "dot" => diagram(DiagramFormat::Dot),
+        "mermaid" => diagram(DiagramFormat::Mermaid),
+        "plantuml" => diagram(DiagramFormat::PlantUml),
+        "gauge" => projection::gauge::project_gauge_in_memory(graph, model_ref, created_at),
+        "graphql" => {
+            projection::graphql::project_graphql_in_memory(graph, contract, model_ref, created_at)
+        }
+        "json-schema" => projection::json_schema::project_json_schema_in_memory(
+            graph, contract, model_ref, created_at,
+        ),
+        "lean" => projection::lean::project_lean_in_memory(graph, model_ref, created_at),
+        "otel-semconv" => {
+            projection::otel::project_otel_semconv_in_memory(graph, model_ref, created_at)
+        }
+        "rdf" => projection::rdf::project_rdf_in_memory(
+            graph,
+            model_ref,
+            created_at,
+            options.base_iri.clone(),
+        ),
+        "sql-postgres" => projection::sql_postgres::project_sql_postgres_in_memory(
+            graph, contract, model_ref, created_at, None,
+        ),
+        "structurizr" => projection::structurizr::project_structurizr_in_memory(
+            graph, None, model_ref, created_at,
+        ),
+        "tla" => projection::tla::project_tla_in_memory(graph, model_ref, created_at),
+        other => {
+            return Err(Failure::new(
+                DF_ERR_INVALID_ARGUMENT,
+                "unknown_format",
+                format!(
+                    "Unknown format '{other}'; expected one of: {}",
+                    FORMATS.join(", ")
+                ),
+            ))
+        }
+    };
+    artifacts.map_err(|message| {
+        Failure::new(
+            DF_ERR_PROJECTION,
+            "projection_failed",
+            format!("{format} projection failed: {message}"),
+        )
+    })
+}
+
+type LearningProjection = fn(
+    &Graph,
+    Option<&str>,
+    Option<&str>,
+    &str,
+    Option<u64>,
+    Option<String>,
+) -> Result<BTreeMap<String, String>, String>;
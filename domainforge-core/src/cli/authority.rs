@@ -3,7 +3,8 @@
 //! Provides authority evaluation from the command line.
 
 use crate::authority::{
-    AuthorityEnvironment, AuthorityEnvironmentConfig, AuthorityRequest, FactEnvelope,
+    AuthorityEnvironment, AuthorityEnvironmentConfig, AuthorityRequest, DecisionSpaceExplorer,
+    FactEnvelope,
 };
 use clap::Args;
 
@@ -15,8 +16,11 @@ pub struct AuthorityArgs {
     pub config: String,
 
     /// Path to authority request JSON file
-    #[arg(help = "Path to authority request JSON")]
-    pub request: String,
+    #[arg(
+        help = "Path to authority request JSON",
+        required_unless_present = "explore"
+    )]
+    pub request: Option<String>,
 
     /// Path to facts JSON file (optional)
     #[arg(long, value_name = "FILE", help = "Path to facts JSON file")]
@@ -25,6 +29,19 @@ pub struct AuthorityArgs {
     /// Output result as JSON
     #[arg(long, help = "Output as JSON object")]
     pub json: bool,
+
+    /// Enumerate the decision space of the loaded packs instead of
+    /// evaluating a single request
+    #[arg(
+        long,
+        conflicts_with_all = ["request", "facts"],
+        help = "Print the decision matrix and coverage findings of the loaded packs"
+    )]
+    pub explore: bool,
+
+    /// Maximum number of scenarios `--explore` will evaluate
+    #[arg(long, value_name = "N", requires = "explore", default_value_t = crate::authority::explorer::DEFAULT_MAX_SCENARIOS)]
+    pub max_scenarios: usize,
 }
 
 /// Result of authority evaluation for JSON output.
@@ -58,11 +75,19 @@ pub fn run_with_writer<W: std::io::Write>(
         ));
     }
 
-    let request_path = std::path::Path::new(&args.request);
+    if args.explore {
+        return run_explore(&args, writer);
+    }
+
+    let request_file = args
+        .request
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("A request file is required unless --explore is set"))?;
+    let request_path = std::path::Path::new(request_file);
     if !request_path.exists() {
         return Err(anyhow::anyhow!(
             "Request file '{}' does not exist",
-            args.request
+            request_file
         ));
     }
 
@@ -73,21 +98,11 @@ pub fn run_with_writer<W: std::io::Write>(
         }
     }
 
-    // Load config
-    let config_str = std::fs::read_to_string(&args.config)
-        .map_err(|e| anyhow::anyhow!("Failed to read config file '{}': {}", args.config, e))?;
-    let config: AuthorityEnvironmentConfig = serde_json::from_str(&config_str)
-        .map_err(|e| anyhow::anyhow!("Failed to parse config JSON: {}", e))?;
-
-    // Create and validate environment
-    let mut env = AuthorityEnvironment::new(config)
-        .map_err(|e| anyhow::anyhow!("Failed to create authority environment: {}", e))?;
-    env.validate()
-        .map_err(|e| anyhow::anyhow!("Environment validation failed: {}", e))?;
+    let env = load_environment(&args.config)?;
 
     // Load request
-    let request_str = std::fs::read_to_string(&args.request)
-        .map_err(|e| anyhow::anyhow!("Failed to read request file '{}': {}", args.request, e))?;
+    let request_str = std::fs::read_to_string(request_file)
+        .map_err(|e| anyhow::anyhow!("Failed to read request file '{}': {}", request_file, e))?;
     let request: AuthorityRequest = serde_json::from_str(&request_str)
         .map_err(|e| anyhow::anyhow!("Failed to parse request JSON: {}", e))?;
 
@@ -147,3 +162,35 @@ pub fn run_with_writer<W: std::io::Write>(
 
     Ok(())
 }
+
+/// Load and validate the authority environment at `config_path`.
+pub(crate) fn load_environment(config_path: &str) -> anyhow::Result<AuthorityEnvironment> {
+    let config_str = std::fs::read_to_string(config_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read config file '{}': {}", config_path, e))?;
+    let config: AuthorityEnvironmentConfig = serde_json::from_str(&config_str)
+        .map_err(|e| anyhow::anyhow!("Failed to parse config JSON: {}", e))?;
+
+    let mut env = AuthorityEnvironment::new(config)
+        .map_err(|e| anyhow::anyhow!("Failed to create authority environment: {}", e))?;
+    env.validate()
+        .map_err(|e| anyhow::anyhow!("Environment validation failed: {}", e))?;
+    Ok(env)
+}
+
+/// `--explore`: enumerate the decision space of the environment's packs.
+fn run_explore<W: std::io::Write>(args: &AuthorityArgs, mut writer: W) -> anyhow::Result<()> {
+    let env = load_environment(&args.config)?;
+    let report = DecisionSpaceExplorer::new(env.config())
+        .with_max_scenarios(args.max_scenarios)
+        .explore(env.packs())
+        .map_err(|e| anyhow::anyhow!("Decision-space exploration failed: {}", e))?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize report: {}", e))?;
+        writeln!(writer, "{}", json)?;
+    } else {
+        write!(writer, "{}", report.to_report())?;
+    }
+    Ok(())
+}
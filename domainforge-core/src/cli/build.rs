@@ -0,0 +1,554 @@
+//! `domainforge build`: run every projection target declared in
+//! `domainforge.toml` against a model resolved once per input.
+//!
+//! ```toml
+//! [build]
+//! input = "domain/model.sea"   # default input for every target
+//! out_dir = "generated"        # target outputs are relative to this
+//!
+//! [[target]]
+//! name = "proto"
+//! format = "protobuf"
+//! output = "proto/orders.proto"
+//! package = "orders.v1"
+//!
+//! [[target]]
+//! format = "ai-learning"
+//! recipe = "recipes/learning.json"
+//! created_at = "2024-01-01T00:00:00Z"
+//! ```
+//!
+//! Target keys mirror the `project` flags with underscores. Each target's
+//! inputs — its options, the files they name, the source closure of its input
+//! and the tool version — are fingerprinted; a target whose fingerprint and
+//! artifacts match the build manifest from the last run is skipped. With
+//! `--check` every target is rendered in memory and compared against its
+//! output instead, and nothing is written.
+
+use super::project::{
+    print_drift, run_cell_projection, run_resolved, CliCompatibilityMode, CliRegistryCompatibility,
+    FormatParser, ProjectArgs, ProjectFormat, ResolvedInput,
+};
+use crate::package::MANIFEST_FILE_NAME;
+use crate::projection::drift::Drift;
+use crate::semantic_pack::canonical_json::{compute_sha256, hash_canonical_json};
+use crate::NamespaceRegistry;
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Build manifest written after every run, relative to the workspace root.
+pub const BUILD_MANIFEST_PATH: &str = ".domainforge/build-manifest.json";
+
+const BUILD_MANIFEST_VERSION: u8 = 1;
+
+#[derive(Debug, Args)]
+pub struct BuildArgs {
+    /// Directory holding domainforge.toml
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Only build the named target; repeatable
+    #[arg(long = "target")]
+    pub targets: Vec<String>,
+
+    /// Rebuild every target even when its inputs are unchanged
+    #[arg(long)]
+    pub force: bool,
+
+    /// Render every target in memory and compare it against its output
+    /// instead of writing; fails if any target has drifted
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspaceFile {
+    #[serde(default)]
+    build: toml::Table,
+    #[serde(default, rename = "target")]
+    targets: Vec<toml::Table>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuildSection {
+    input: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TargetSpec {
+    name: Option<String>,
+    format: String,
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    namespace: Option<String>,
+    package: Option<String>,
+    #[serde(default)]
+    include_governance: bool,
+    #[serde(default)]
+    include_services: bool,
+    compatibility: Option<String>,
+    schema_history: Option<PathBuf>,
+    #[serde(default)]
+    apply_fixes: bool,
+    #[serde(default)]
+    buf_lint: bool,
+    #[serde(default)]
+    buf_breaking: bool,
+    #[serde(default)]
+    buf_generate: bool,
+    #[serde(default)]
+    multi_file: bool,
+    recipe: Option<PathBuf>,
+    seed: Option<u64>,
+    families: Option<String>,
+    authority_config: Option<PathBuf>,
+    created_at: Option<String>,
+    base_iri: Option<String>,
+    focus: Option<String>,
+    overrides: Option<PathBuf>,
+    only: Option<String>,
+    #[serde(default)]
+    viewpoints: Vec<String>,
+    published_schemas: Option<PathBuf>,
+    registry_compatibility: Option<String>,
+    templates: Option<PathBuf>,
+    semantic_pack: Option<PathBuf>,
+    #[serde(default)]
+    plugin_options: BTreeMap<String, String>,
+}
+
+/// One target, with its options turned into `project` arguments.
+struct Target {
+    name: String,
+    args: ProjectArgs,
+    raw: toml::Table,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BuildManifest {
+    version: u8,
+    targets: BTreeMap<String, TargetRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TargetRecord {
+    format: String,
+    input_hash: String,
+    output: String,
+    /// Artifact path (relative to the workspace root) to `sha256:<hex>`.
+    artifacts: BTreeMap<String, String>,
+}
+
+pub fn run(args: BuildArgs) -> Result<()> {
+    let root = args
+        .dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", args.dir.display()))?;
+    let targets = load_targets(&root)?;
+    for name in &args.targets {
+        if !targets.iter().any(|t| &t.name == name) {
+            anyhow::bail!("No target named '{}' in {}", name, MANIFEST_FILE_NAME);
+        }
+    }
+    if args.check {
+        return check(&root, &targets, &args.targets);
+    }
+
+    let manifest_path = root.join(BUILD_MANIFEST_PATH);
+    let previous: BuildManifest = match fs::read_to_string(&manifest_path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+        Err(_) => BuildManifest::default(),
+    };
+    let mut manifest = BuildManifest {
+        version: BUILD_MANIFEST_VERSION,
+        // Carry over the records of targets still declared in the manifest,
+        // including those this run does not select; removed targets drop out.
+        targets: previous
+            .targets
+            .iter()
+            .filter(|(name, _)| targets.iter().any(|t| &t.name == *name))
+            .map(|(name, record)| (name.clone(), record.clone()))
+            .collect(),
+    };
+
+    let mut resolved: HashMap<PathBuf, ResolvedInput> = HashMap::new();
+    let mut source_hashes: HashMap<PathBuf, String> = HashMap::new();
+    let (mut built, mut skipped) = (0usize, 0usize);
+    for target in &targets {
+        if !args.targets.is_empty() && !args.targets.contains(&target.name) {
+            continue;
+        }
+        let format = format_name(&target.args.format);
+        let sources = match source_hashes.get(&target.args.input) {
+            Some(hash) => hash.clone(),
+            None => {
+                let hash = source_set_hash(&target.args.input)?;
+                source_hashes.insert(target.args.input.clone(), hash.clone());
+                hash
+            }
+        };
+        let input_hash = fingerprint(&root, target, &sources)?;
+        let output = display_path(&root, &target.args.output);
+
+        if !args.force {
+            if let Some(record) = previous.targets.get(&target.name) {
+                if record.input_hash == input_hash
+                    && !record.artifacts.is_empty()
+                    && hash_artifacts(&root, &target.args.output)? == record.artifacts
+                {
+                    println!(
+                        "Skipping target '{}' ({}): inputs unchanged",
+                        target.name, format
+                    );
+                    skipped += 1;
+                    continue;
+                }
+            }
+        }
+
+        println!("Building target '{}' ({})", target.name, format);
+        project_target(&target.args, &mut resolved)
+            .with_context(|| format!("Target '{}' failed", target.name))?;
+        manifest.targets.insert(
+            target.name.clone(),
+            TargetRecord {
+                format: format.clone(),
+                input_hash,
+                output,
+                artifacts: hash_artifacts(&root, &target.args.output)?,
+            },
+        );
+        write_manifest(&manifest_path, &manifest)?;
+        built += 1;
+    }
+    write_manifest(&manifest_path, &manifest)?;
+    println!(
+        "Built {} target(s), skipped {}; manifest: {}",
+        built,
+        skipped,
+        manifest_path.display()
+    );
+    Ok(())
+}
+
+/// `build --check`: render every selected target in memory and compare it
+/// against its output. Writes nothing, not even the build manifest.
+fn check(root: &Path, targets: &[Target], selected: &[String]) -> Result<()> {
+    let mut resolved: HashMap<PathBuf, ResolvedInput> = HashMap::new();
+    let (mut checked, mut drifted) = (0usize, 0usize);
+    for target in targets
+        .iter()
+        .filter(|t| selected.is_empty() || selected.contains(&t.name))
+    {
+        println!(
+            "Checking target '{}' ({})",
+            target.name,
+            format_name(&target.args.format)
+        );
+        let args = ProjectArgs {
+            check: true,
+            ..target.args.clone()
+        };
+        let drift = project_target(&args, &mut resolved)
+            .with_context(|| format!("Target '{}' failed", target.name))?
+            .unwrap_or_default();
+        print_drift(Path::new(&display_path(root, &args.output)), &drift);
+        checked += 1;
+        if !drift.is_clean() {
+            drifted += 1;
+        }
+    }
+    if drifted > 0 {
+        anyhow::bail!(
+            "{} of {} target(s) drifted from their projection",
+            drifted,
+            checked
+        );
+    }
+    println!("Checked {} target(s): all up to date", checked);
+    Ok(())
+}
+
+/// Project one target, resolving its input at most once per build.
+fn project_target(
+    args: &ProjectArgs,
+    resolved: &mut HashMap<PathBuf, ResolvedInput>,
+) -> Result<Option<Drift>> {
+    if args.format == ProjectFormat::Cell {
+        return run_cell_projection(args);
+    }
+    let model = match resolved.entry(args.input.clone()) {
+        std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+        std::collections::hash_map::Entry::Vacant(entry) => {
+            entry.insert(ResolvedInput::resolve(&args.input)?)
+        }
+    };
+    run_resolved(args, model)
+}
+
+fn load_targets(root: &Path) -> Result<Vec<Target>> {
+    let path = root.join(MANIFEST_FILE_NAME);
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let file: WorkspaceFile =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let build: BuildSection = toml::Value::Table(file.build)
+        .try_into()
+        .with_context(|| format!("Invalid [build] section in {}", path.display()))?;
+    if file.targets.is_empty() {
+        anyhow::bail!("{} declares no [[target]] entries", path.display());
+    }
+    let out_dir = root.join(build.out_dir.unwrap_or_default());
+
+    let mut targets: Vec<Target> = Vec::new();
+    for (index, raw) in file.targets.into_iter().enumerate() {
+        let spec: TargetSpec = toml::Value::Table(raw.clone())
+            .try_into()
+            .with_context(|| format!("Invalid [[target]] #{} in {}", index + 1, path.display()))?;
+        let format = FormatParser::parse(&spec.format)
+            .map_err(|e| anyhow::anyhow!("{} in target #{}", e, index + 1))?;
+        let name = spec.name.clone().unwrap_or_else(|| match &format {
+            ProjectFormat::Plugin(plugin) => plugin.clone(),
+            format => format_name(format),
+        });
+        if targets.iter().any(|t| t.name == name) {
+            anyhow::bail!(
+                "Duplicate target name '{}' (set `name` to tell them apart)",
+                name
+            );
+        }
+        let input = spec
+            .input
+            .as_ref()
+            .or(build.input.as_ref())
+            .map(|input| root.join(input))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Target '{}' has no `input` and [build] sets no default input",
+                    name
+                )
+            })?;
+        let output = out_dir.join(
+            spec.output
+                .clone()
+                .unwrap_or_else(|| default_output(&name, &format, spec.multi_file)),
+        );
+        let args = project_args(root, spec, format, input, output)
+            .with_context(|| format!("Invalid options for target '{}'", name))?;
+        targets.push(Target { name, args, raw });
+    }
+    Ok(targets)
+}
+
+fn project_args(
+    root: &Path,
+    spec: TargetSpec,
+    format: ProjectFormat,
+    input: PathBuf,
+    output: PathBuf,
+) -> Result<ProjectArgs> {
+    let path = |p: Option<PathBuf>| p.map(|p| root.join(p));
+    let compatibility = match spec.compatibility {
+        Some(mode) => CliCompatibilityMode::from_str(&mode, true)
+            .map_err(|_| anyhow::anyhow!("unknown compatibility mode '{}'", mode))?,
+        None => CliCompatibilityMode::default(),
+    };
+    let registry_compatibility = match spec.registry_compatibility {
+        Some(mode) => CliRegistryCompatibility::from_str(&mode, true)
+            .map_err(|_| anyhow::anyhow!("unknown registry compatibility '{}'", mode))?,
+        None => CliRegistryCompatibility::default(),
+    };
+    Ok(ProjectArgs {
+        format,
+        namespace: spec.namespace,
+        package: spec.package.unwrap_or_else(|| "sea.generated".to_string()),
+        include_governance: spec.include_governance,
+        include_services: spec.include_services,
+        compatibility,
+        schema_history: path(spec.schema_history),
+        apply_fixes: spec.apply_fixes,
+        buf_lint: spec.buf_lint,
+        buf_breaking: spec.buf_breaking,
+        buf_generate: spec.buf_generate,
+        multi_file: spec.multi_file,
+        recipe: path(spec.recipe),
+        seed: spec.seed,
+        families: spec.families,
+        authority_config: path(spec.authority_config),
+        created_at: spec.created_at,
+        base_iri: spec.base_iri,
+        focus: spec.focus,
+        overrides: path(spec.overrides),
+        only: spec.only,
+        viewpoints: spec.viewpoints,
+        published_schemas: path(spec.published_schemas),
+        registry_compatibility,
+        templates: path(spec.templates),
+        semantic_pack: path(spec.semantic_pack),
+        plugin_options: spec
+            .plugin_options
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect(),
+        check: false,
+        input,
+        output,
+    })
+}
+
+fn format_name(format: &ProjectFormat) -> String {
+    match format {
+        ProjectFormat::Plugin(name) => format!("plugin:{name}"),
+        format => format
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Single-file formats get a file name; everything else a directory.
+fn default_output(name: &str, format: &ProjectFormat, multi_file: bool) -> PathBuf {
+    let extension = match format {
+        ProjectFormat::Calm => Some("json"),
+        ProjectFormat::Kg => Some("ttl"),
+        ProjectFormat::Protobuf | ProjectFormat::Proto if !multi_file => Some("proto"),
+        _ => None,
+    };
+    match extension {
+        Some(extension) => PathBuf::from(format!("{name}.{extension}")),
+        None => PathBuf::from(name),
+    }
+}
+
+fn source_set_hash(input: &Path) -> Result<String> {
+    let source = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file {}", input.display()))?;
+    let registry = NamespaceRegistry::discover(input).context("discovering namespace registry")?;
+    let default_namespace = registry
+        .as_ref()
+        .and_then(|reg| reg.namespace_for(input).map(|ns| ns.to_string()));
+    crate::application::resolve::filesystem_source_set_hash(
+        input,
+        &source,
+        registry.as_ref(),
+        default_namespace.as_deref(),
+    )
+    .map_err(|diagnostics| {
+        anyhow::anyhow!(
+            "Parse failed for {}: {}",
+            input.display(),
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        )
+    })
+}
+
+/// Hash of everything a target's output depends on.
+fn fingerprint(root: &Path, target: &Target, sources: &str) -> Result<String> {
+    let args = &target.args;
+    let mut files = serde_json::Map::new();
+    for (key, path) in [
+        ("recipe", &args.recipe),
+        ("authority_config", &args.authority_config),
+        ("overrides", &args.overrides),
+        ("schema_history", &args.schema_history),
+        ("published_schemas", &args.published_schemas),
+        ("templates", &args.templates),
+        ("semantic_pack", &args.semantic_pack),
+    ] {
+        if let Some(path) = path {
+            files.insert(key.to_string(), serde_json::to_value(hash_tree(path)?)?);
+        }
+    }
+    if let ProjectFormat::Plugin(name) = &args.format {
+        let executable = crate::projection::plugin::locate_plugin(name, &args.input)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        files.insert(
+            "plugin".to_string(),
+            serde_json::to_value(hash_tree(&executable)?)?,
+        );
+    }
+    let value = serde_json::json!({
+        "tool_version": env!("CARGO_PKG_VERSION"),
+        "target": serde_json::to_value(&target.raw)?,
+        "input": display_path(root, &args.input),
+        "output": display_path(root, &args.output),
+        "sources": sources,
+        "files": files,
+    });
+    Ok(hash_canonical_json(&value))
+}
+
+/// The artifacts at a target's output, keyed relative to the workspace root.
+fn hash_artifacts(root: &Path, output: &Path) -> Result<BTreeMap<String, String>> {
+    let prefix = display_path(root, output);
+    Ok(hash_tree(output)?
+        .into_iter()
+        .map(|(rel, hash)| {
+            let path = if rel.is_empty() {
+                prefix.clone()
+            } else {
+                format!("{prefix}/{rel}")
+            };
+            (path, hash)
+        })
+        .collect())
+}
+
+/// `sha256:<hex>` of a file (keyed ""), or of every file under a directory
+/// (keyed by `/`-separated relative path). Missing paths hash to nothing.
+fn hash_tree(path: &Path) -> Result<BTreeMap<String, String>> {
+    fn walk(dir: &Path, prefix: &str, out: &mut BTreeMap<String, String>) -> Result<()> {
+        for entry in
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let rel = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+            if entry.file_type()?.is_dir() {
+                walk(&entry.path(), &rel, out)?;
+            } else {
+                out.insert(rel, compute_sha256(&fs::read(entry.path())?));
+            }
+        }
+        Ok(())
+    }
+    let mut out = BTreeMap::new();
+    if path.is_file() {
+        out.insert(String::new(), compute_sha256(&fs::read(path)?));
+    } else if path.is_dir() {
+        walk(path, "", &mut out)?;
+    }
+    Ok(out)
+}
+
+fn display_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn write_manifest(path: &Path, manifest: &BuildManifest) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let json =
+        serde_json::to_string_pretty(manifest).context("Failed to serialize build manifest")?;
+    fs::write(path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))
+}
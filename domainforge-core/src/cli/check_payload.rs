@@ -0,0 +1,123 @@
+//! CLI module for the `domainforge check-payload` command.
+//!
+//! Validates a JSON payload against one application-contract record of a SEA
+//! model and reports path-addressed diagnostics.
+
+use crate::application::{validate_payload, PayloadOptions};
+use crate::NamespaceRegistry;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::fs::read_to_string;
+use std::io::Read;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+pub struct CheckPayloadArgs {
+    /// SEA model declaring the record
+    pub input: PathBuf,
+
+    /// JSON payload file; reads stdin when omitted or `-`
+    pub payload: Option<PathBuf>,
+
+    /// Record to validate against (name or application symbol ID)
+    #[arg(long)]
+    pub record: String,
+
+    /// Only evaluate the preconditions of this operation
+    #[arg(long)]
+    pub operation: Option<String>,
+
+    /// Evaluate preconditions as this role instead of the operation's actor
+    #[arg(long)]
+    pub actor: Option<String>,
+
+    /// Output the report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: CheckPayloadArgs) -> Result<()> {
+    run_with_writer(args, &mut std::io::stdout())
+}
+
+/// Run the command with a specific writer for output capture.
+pub fn run_with_writer<W: std::io::Write>(args: CheckPayloadArgs, mut writer: W) -> Result<()> {
+    let source = read_to_string(&args.input)
+        .with_context(|| format!("Failed to read input file {}", args.input.display()))?;
+    let payload_text = match &args.payload {
+        Some(path) if path.as_os_str() != "-" => read_to_string(path)
+            .with_context(|| format!("Failed to read payload file {}", path.display()))?,
+        _ => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .context("Failed to read payload from stdin")?;
+            text
+        }
+    };
+    let payload: serde_json::Value =
+        serde_json::from_str(&payload_text).context("Payload is not valid JSON")?;
+
+    let registry =
+        NamespaceRegistry::discover(&args.input).context("discovering namespace registry")?;
+    let default_namespace = registry
+        .as_ref()
+        .and_then(|reg| reg.namespace_for(&args.input).map(|ns| ns.to_string()));
+    let failed = |what: &str, diagnostics: Vec<crate::application::ApplicationDiagnostic>| {
+        anyhow::anyhow!(
+            "{what} failed for {}: {}",
+            args.input.display(),
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        )
+    };
+    let graph = crate::application::resolve::resolve_filesystem_graph(
+        &args.input,
+        &source,
+        registry.as_ref(),
+        default_namespace.as_deref(),
+    )
+    .map_err(|diagnostics| failed("Parse", diagnostics))?;
+    let contract = crate::application::resolve::resolve_filesystem_contract(
+        &args.input,
+        &source,
+        registry.as_ref(),
+        default_namespace.as_deref(),
+    )
+    .map_err(|diagnostics| failed("Application contract resolution", diagnostics))?;
+
+    let options = PayloadOptions {
+        operation: args.operation.clone(),
+        actor_role: args.actor.clone(),
+    };
+    let report = validate_payload(&graph, &contract, &args.record, &payload, &options)
+        .map_err(anyhow::Error::msg)?;
+
+    if args.json {
+        writeln!(writer, "{}", serde_json::to_string_pretty(&report)?)?;
+    } else if report.is_valid() {
+        writeln!(writer, "Payload is valid for record '{}'", report.record)?;
+    } else {
+        for diagnostic in &report.diagnostics {
+            let path = if diagnostic.path.is_empty() {
+                "/"
+            } else {
+                diagnostic.path.as_str()
+            };
+            writeln!(writer, "{path}: {}", diagnostic.message)?;
+        }
+    }
+
+    if report.is_valid() {
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "Payload check failed for record '{}': {} diagnostic(s)",
+            report.record,
+            report.diagnostics.len()
+        )
+    }
+}
@@ -1,3 +1,9 @@
+use crate::import::archimate::import_archimate;
+use crate::import::asyncapi::import_asyncapi;
+use crate::import::bpmn::import_bpmn;
+use crate::import::openapi::import_openapi;
+use crate::import::protobuf::{import_protobuf, ProtoImportMode};
+use crate::import::SeaImport;
 use crate::import_kg_turtle;
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
@@ -12,6 +18,31 @@ pub struct ImportArgs {
     #[arg(long, value_enum)]
     pub format: ImportFormat,
 
+    /// Write the imported SEA source here instead of stdout (bpmn, archimate,
+    /// openapi, asyncapi, protobuf)
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+
+    /// Write the mapping report here instead of stderr (bpmn, archimate,
+    /// openapi, asyncapi, protobuf)
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Namespace of the imported declarations (bpmn, archimate, openapi,
+    /// asyncapi, protobuf; default: the model's or document's name)
+    #[arg(long)]
+    pub namespace: Option<String>,
+
+    /// Import messages as entities and resources and rpcs as flows instead of
+    /// an application contract (protobuf)
+    #[arg(long)]
+    pub legacy: bool,
+
+    /// Fail unless re-projecting the imported source reproduces the file
+    /// (protobuf)
+    #[arg(long)]
+    pub verify_round_trip: bool,
+
     pub file: PathBuf,
 }
 
@@ -19,6 +50,19 @@ pub struct ImportArgs {
 pub enum ImportFormat {
     Sbvr,
     Kg,
+    /// BPMN 2.0 XML process/collaboration, written out as SEA source
+    Bpmn,
+    /// ArchiMate Model Exchange File, whose business layer is written out as
+    /// SEA source
+    Archimate,
+    /// OpenAPI 3.x document (JSON or YAML), written out as an application
+    /// contract
+    Openapi,
+    /// AsyncAPI 2.x/3.0 document (JSON or YAML), written out as SEA flows
+    Asyncapi,
+    /// proto3 file, written out as an application contract (or entities,
+    /// resources and flows with --legacy)
+    Protobuf,
 }
 
 /// Helper function to print import success message with graph statistics
@@ -32,11 +76,97 @@ fn print_import_success(graph: &crate::Graph, format_desc: &str) {
     );
 }
 
+/// Write imported SEA to `--out` (or stdout) and the mapping report to
+/// `--report` (or stderr).
+fn write_sea_import(args: &ImportArgs, import: &SeaImport) -> Result<()> {
+    let report = import.report.to_report();
+    match &args.report {
+        Some(path) => std::fs::write(path, &report)
+            .with_context(|| format!("Failed to write report {}", path.display()))?,
+        None => eprint!("{report}"),
+    }
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, &import.source)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "Imported {} to {} ({} element(s) without a SEA counterpart)",
+                import.report.format,
+                path.display(),
+                import.report.unmapped.len()
+            );
+        }
+        None => print!("{}", import.source),
+    }
+    Ok(())
+}
+
 pub fn run(args: ImportArgs) -> Result<()> {
     let source = read_to_string(&args.file)
         .with_context(|| format!("Failed to read file {}", args.file.display()))?;
 
+    if !matches!(
+        args.format,
+        ImportFormat::Bpmn
+            | ImportFormat::Archimate
+            | ImportFormat::Openapi
+            | ImportFormat::Asyncapi
+            | ImportFormat::Protobuf
+    ) && (args.out.is_some() || args.report.is_some() || args.namespace.is_some())
+    {
+        anyhow::bail!(
+            "--out, --report and --namespace apply to --format bpmn, archimate, openapi, asyncapi and protobuf only"
+        );
+    }
+    if !matches!(args.format, ImportFormat::Protobuf) && (args.legacy || args.verify_round_trip) {
+        anyhow::bail!("--legacy and --verify-round-trip apply to --format protobuf only");
+    }
+
     match args.format {
+        ImportFormat::Bpmn => {
+            let import = import_bpmn(&source, args.namespace.as_deref())
+                .map_err(|e| anyhow::anyhow!("Failed to import BPMN: {}", e))?;
+            write_sea_import(&args, &import)
+        }
+        ImportFormat::Archimate => {
+            let import = import_archimate(&source, args.namespace.as_deref())
+                .map_err(|e| anyhow::anyhow!("Failed to import ArchiMate: {}", e))?;
+            write_sea_import(&args, &import)
+        }
+        ImportFormat::Openapi => {
+            let import = import_openapi(&source, args.namespace.as_deref())
+                .map_err(|e| anyhow::anyhow!("Failed to import OpenAPI: {}", e))?;
+            write_sea_import(&args, &import)
+        }
+        ImportFormat::Asyncapi => {
+            let import = import_asyncapi(&source, args.namespace.as_deref())
+                .map_err(|e| anyhow::anyhow!("Failed to import AsyncAPI: {}", e))?;
+            write_sea_import(&args, &import)
+        }
+        ImportFormat::Protobuf => {
+            let mode = if args.legacy {
+                ProtoImportMode::Legacy
+            } else {
+                ProtoImportMode::Contract
+            };
+            let import = import_protobuf(&source, args.namespace.as_deref(), mode)
+                .map_err(|e| anyhow::anyhow!("Failed to import Protobuf: {}", e))?;
+            write_sea_import(&args, &import)?;
+            if args.verify_round_trip {
+                match &import.report.round_trip {
+                    Some(diff) if diff.is_empty() => {}
+                    Some(diff) => anyhow::bail!(
+                        "Round trip failed: the projection differs from {} in {} declaration(s)",
+                        args.file.display(),
+                        diff.len()
+                    ),
+                    None => anyhow::bail!(
+                        "Round trip not checked: the imported source does not resolve (see the report)"
+                    ),
+                }
+            }
+            Ok(())
+        }
         ImportFormat::Sbvr => match crate::SbvrModel::from_xmi(&source) {
             Ok(model) => match model.to_graph() {
                 Ok(graph) => {
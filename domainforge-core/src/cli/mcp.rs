@@ -0,0 +1,825 @@
+//! CLI module for the `domainforge mcp` command.
+//!
+//! Serves one SEA model to AI agents over the Model Context Protocol:
+//! JSON-RPC 2.0 messages, one per line, on stdin and stdout. The model is
+//! re-read from disk for every request, so an agent sees its own edits
+//! without restarting the server.
+//!
+//! Tools: `lookup_concept` (semantic pack resolution), `graph_neighbourhood`,
+//! `evaluate_policies`, `authority_decision` and `validate_snippet`.
+//! Resources: `sea://modules/<logical path>` for every module in the entry's
+//! import closure, and `sea://envelope` for the canonical semantic envelope.
+//! The protocol surface is documented in `docs/reference/mcp-server.md`.
+
+use crate::application::ApplicationDiagnostic;
+use crate::authority::{AuthorityEnvironment, AuthorityRequest, FactEnvelope};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::module::resolver::SourceMap;
+use crate::primitives::Flow;
+use crate::projection::flows::resolve_flow;
+use crate::semantic_pack::{
+    resolve_concept, ConceptKind, ResolveRequest, SemanticPack, SourceRef, ValidationOptions,
+};
+use crate::NamespaceRegistry;
+use anyhow::{Context, Result};
+use clap::Args;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// MCP protocol revisions this server speaks, newest first.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Largest `depth` `graph_neighbourhood` accepts.
+const MAX_NEIGHBOURHOOD_DEPTH: u64 = 3;
+
+const ENVELOPE_URI: &str = "sea://envelope";
+const MODULE_URI_PREFIX: &str = "sea://modules/";
+
+// JSON-RPC 2.0 and MCP error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// Arguments for the `mcp` subcommand.
+#[derive(Args, Debug)]
+pub struct McpArgs {
+    /// Entry SEA file of the model to serve
+    pub input: PathBuf,
+
+    /// Semantic pack JSON (as written by `pack build`) for `lookup_concept`
+    #[arg(long, value_name = "FILE")]
+    pub semantic_pack: Option<PathBuf>,
+
+    /// Authority environment config JSON for `authority_decision`
+    #[arg(long, value_name = "FILE")]
+    pub authority_config: Option<PathBuf>,
+}
+
+/// Run the MCP server on stdin and stdout.
+pub fn run(args: McpArgs) -> Result<()> {
+    let stdin = std::io::stdin();
+    serve(&args, stdin.lock(), std::io::stdout())
+}
+
+/// Serve newline-delimited JSON-RPC messages from `reader` until end of
+/// input, writing one response line per request to `writer`.
+pub fn serve<R: BufRead, W: Write>(args: &McpArgs, reader: R, mut writer: W) -> Result<()> {
+    let server = Server::new(args)?;
+    for line in reader.lines() {
+        let line = line.context("Failed to read an MCP message")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle_message(&line) {
+            writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// A JSON-RPC error object.
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+struct Server {
+    input: PathBuf,
+    pack: Option<SemanticPack>,
+    authority: Option<AuthorityEnvironment>,
+}
+
+impl Server {
+    fn new(args: &McpArgs) -> Result<Self> {
+        if !args.input.is_file() {
+            anyhow::bail!("Input file '{}' does not exist", args.input.display());
+        }
+        let pack = args
+            .semantic_pack
+            .as_deref()
+            .map(super::pack::load_pack_json)
+            .transpose()?;
+        let authority = args
+            .authority_config
+            .as_deref()
+            .map(|path| super::authority::load_environment(&path.to_string_lossy()))
+            .transpose()?;
+        Ok(Self {
+            input: args.input.clone(),
+            pack,
+            authority,
+        })
+    }
+
+    /// Handle one message. Notifications and stray responses get no reply.
+    fn handle_message(&self, line: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(PARSE_ERROR, format!("Parse error: {e}")),
+                ))
+            }
+        };
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses to server requests are never expected: this server sends none.
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "Invalid Request: missing method"),
+            ));
+        };
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+        Some(match self.dispatch(method, &params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    fn dispatch(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_descriptors() })),
+            "tools/call" => self.call_tool(params),
+            "resources/list" => self.list_resources(),
+            "resources/read" => self.read_resource(params),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {method}"),
+            )),
+        }
+    }
+
+    fn call_tool(&self, params: &Value) -> Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "tools/call requires a tool name"))?;
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        let outcome = match name {
+            "lookup_concept" => self.lookup_concept(&arguments),
+            "graph_neighbourhood" => self.graph_neighbourhood(&arguments),
+            "evaluate_policies" => self.evaluate_policies(&arguments),
+            "authority_decision" => self.authority_decision(&arguments),
+            "validate_snippet" => self.validate_snippet(&arguments),
+            _ => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Unknown tool: {name}"),
+                ))
+            }
+        };
+        // Tool failures are results the agent can read, not protocol errors.
+        Ok(match outcome {
+            Ok(value) => json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&value).unwrap_or_default(),
+                }],
+                "isError": false,
+            }),
+            Err(message) => json!({
+                "content": [{ "type": "text", "text": message }],
+                "isError": true,
+            }),
+        })
+    }
+
+    fn model(&self) -> Result<Model, String> {
+        Model::load(&self.input)
+    }
+
+    fn lookup_concept(&self, args: &Value) -> Result<Value, String> {
+        let pack = self
+            .pack
+            .as_ref()
+            .ok_or("No semantic pack is loaded; start the server with --semantic-pack <FILE>")?;
+        let term = string_arg(args, "term")?;
+        let expected_kind = match args.get("kind") {
+            None | Some(Value::Null) => None,
+            Some(kind) => Some(
+                serde_json::from_value::<ConceptKind>(kind.clone())
+                    .map_err(|e| format!("Invalid kind: {e}"))?,
+            ),
+        };
+        let request = ResolveRequest {
+            raw_text: term,
+            expected_kind,
+            source_ref: SourceRef::synthetic("domainforge://mcp/lookup_concept"),
+        };
+        let result = resolve_concept(&request, pack, &ValidationOptions::default());
+        let concept = result
+            .resolved_concept_id
+            .as_ref()
+            .and_then(|id| pack.concepts.iter().find(|c| &c.id == id));
+        let aliases: Vec<Value> = match &result.resolved_concept_id {
+            Some(id) => pack
+                .aliases
+                .iter()
+                .filter(|alias| &alias.target_concept_id == id)
+                .map(|alias| json!({ "alias": alias.alias, "status": alias.status }))
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(json!({
+            "term": term,
+            "resolved_concept_id": result.resolved_concept_id,
+            "semantic_truth": result.semantic_truth,
+            "diagnostic_code": result.diagnostic_code,
+            "message": result.message,
+            "suggestions": result.suggestions,
+            "concept": concept,
+            "aliases": aliases,
+        }))
+    }
+
+    fn graph_neighbourhood(&self, args: &Value) -> Result<Value, String> {
+        let name = string_arg(args, "concept")?;
+        let depth = match args.get("depth") {
+            None | Some(Value::Null) => 1,
+            Some(depth) => depth
+                .as_u64()
+                .filter(|depth| (1..=MAX_NEIGHBOURHOOD_DEPTH).contains(depth))
+                .ok_or_else(|| {
+                    format!("depth must be an integer from 1 to {MAX_NEIGHBOURHOOD_DEPTH}")
+                })?,
+        };
+        let kind = args.get("kind").and_then(Value::as_str);
+        let graph = self.model()?.graph().map_err(|d| diagnostics_message(&d))?;
+        neighbourhood(&graph, name, kind, depth)
+    }
+
+    fn evaluate_policies(&self, args: &Value) -> Result<Value, String> {
+        let only = args.get("policy").and_then(Value::as_str);
+        let graph = self.model()?.graph().map_err(|d| diagnostics_message(&d))?;
+        let mut policies = graph.all_policies();
+        policies.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+        if let Some(name) = only {
+            policies.retain(|policy| policy.name == name);
+            if policies.is_empty() {
+                return Err(format!("No policy named '{name}' in the model"));
+            }
+        }
+        let mut violation_count = 0;
+        let results: Vec<Value> = policies
+            .into_iter()
+            .map(|policy| match policy.evaluate(&graph) {
+                Ok(result) => {
+                    violation_count += result.violations.len();
+                    json!({
+                        "name": policy.name,
+                        "namespace": policy.namespace,
+                        "english": policy.to_english(),
+                        "satisfied": result.is_satisfied_tristate,
+                        "violations": result.violations,
+                    })
+                }
+                Err(error) => json!({
+                    "name": policy.name,
+                    "namespace": policy.namespace,
+                    "english": policy.to_english(),
+                    "error": error,
+                }),
+            })
+            .collect();
+        Ok(json!({ "policies": results, "violation_count": violation_count }))
+    }
+
+    fn authority_decision(&self, args: &Value) -> Result<Value, String> {
+        let environment = self.authority.as_ref().ok_or(
+            "No authority environment is loaded; start the server with --authority-config <FILE>",
+        )?;
+        let request: AuthorityRequest = serde_json::from_value(
+            args.get("request")
+                .cloned()
+                .ok_or("Missing argument 'request'")?,
+        )
+        .map_err(|e| format!("Invalid authority request: {e}"))?;
+        let facts: Vec<FactEnvelope> = match args.get("facts") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(facts) => {
+                serde_json::from_value(facts.clone()).map_err(|e| format!("Invalid facts: {e}"))?
+            }
+        };
+        let (trace, decision) = environment
+            .evaluate(&request, &facts)
+            .map_err(|e| format!("Authority evaluation failed: {e}"))?;
+        Ok(json!({ "decision": decision, "trace": trace }))
+    }
+
+    fn validate_snippet(&self, args: &Value) -> Result<Value, String> {
+        let snippet = string_arg(args, "source")?;
+        let mut model = self.model()?;
+        let module = match args.get("module").and_then(Value::as_str) {
+            Some(module) => module.to_string(),
+            None => model.entry.clone(),
+        };
+        let known: Vec<String> = model.sources.0.keys().cloned().collect();
+        let target = model.sources.0.get_mut(&module).ok_or_else(|| {
+            format!(
+                "No module '{module}' in the model; modules: {}",
+                known.join(", ")
+            )
+        })?;
+        if !target.is_empty() && !target.ends_with('\n') {
+            target.push('\n');
+        }
+        let snippet_line = target.lines().count() + 1;
+        target.push_str(snippet);
+
+        Ok(match model.graph() {
+            Err(diagnostics) => json!({
+                "valid": false,
+                "module": module,
+                "snippet_line": snippet_line,
+                "diagnostics": diagnostics,
+                "violations": [],
+            }),
+            Ok(graph) => {
+                let result = graph.validate();
+                json!({
+                    "valid": result.error_count == 0,
+                    "module": module,
+                    "snippet_line": snippet_line,
+                    "diagnostics": [],
+                    "violations": result.violations,
+                })
+            }
+        })
+    }
+
+    fn list_resources(&self) -> Result<Value, RpcError> {
+        let model = self
+            .model()
+            .map_err(|message| RpcError::new(INTERNAL_ERROR, message))?;
+        let mut resources: Vec<Value> = model
+            .sources
+            .0
+            .keys()
+            .map(|path| {
+                json!({
+                    "uri": format!("{MODULE_URI_PREFIX}{path}"),
+                    "name": path,
+                    "description": "SEA module source",
+                    "mimeType": "text/plain",
+                })
+            })
+            .collect();
+        resources.push(json!({
+            "uri": ENVELOPE_URI,
+            "name": "semantic-envelope",
+            "description": "Canonical semantic envelope of the model",
+            "mimeType": "application/json",
+        }));
+        Ok(json!({ "resources": resources }))
+    }
+
+    fn read_resource(&self, params: &Value) -> Result<Value, RpcError> {
+        let uri = params
+            .get("uri")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "resources/read requires a uri"))?;
+        let model = self
+            .model()
+            .map_err(|message| RpcError::new(INTERNAL_ERROR, message))?;
+        let (mime_type, text) = if uri == ENVELOPE_URI {
+            let envelope = model
+                .envelope()
+                .map_err(|d| RpcError::new(INTERNAL_ERROR, diagnostics_message(&d)))?;
+            let text = serde_json::to_string_pretty(&envelope)
+                .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+            ("application/json", text)
+        } else {
+            let source = uri
+                .strip_prefix(MODULE_URI_PREFIX)
+                .and_then(|path| model.sources.0.get(path))
+                .ok_or_else(|| {
+                    RpcError::new(RESOURCE_NOT_FOUND, format!("Resource not found: {uri}"))
+                })?;
+            ("text/plain", source.clone())
+        };
+        Ok(json!({
+            "contents": [{ "uri": uri, "mimeType": mime_type, "text": text }],
+        }))
+    }
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {}, "resources": {} },
+        "serverInfo": { "name": "domainforge", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Answers questions about one SEA domain model. Look up terms with \
+            lookup_concept, explore entities, resources and roles with graph_neighbourhood, \
+            and check proposed SEA with validate_snippet before editing model files.",
+    })
+}
+
+fn tool_descriptors() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "lookup_concept",
+            "description": "Resolve a business term to its canonical concept in the semantic pack \
+                (exact id, canonical name or alias), with its definition, examples and aliases.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "term": { "type": "string", "description": "Term to resolve" },
+                    "kind": {
+                        "type": "string",
+                        "enum": ["entity", "resource", "role", "flow", "policy", "metric",
+                                 "dimension", "unit", "external"],
+                        "description": "Expected concept kind"
+                    }
+                },
+                "required": ["term"]
+            }
+        }),
+        json!({
+            "name": "graph_neighbourhood",
+            "description": "An entity, resource or role with the entities and flows within \
+                `depth` flow hops, its roles or relations, and the policies that mention it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "concept": {
+                        "type": "string",
+                        "description": "Concept name, or <namespace>::<name>"
+                    },
+                    "kind": { "type": "string", "enum": ["entity", "resource", "role"] },
+                    "depth": { "type": "integer", "minimum": 1, "maximum": MAX_NEIGHBOURHOOD_DEPTH }
+                },
+                "required": ["concept"]
+            }
+        }),
+        json!({
+            "name": "evaluate_policies",
+            "description": "Evaluate the model's policies against the model. Each result has the \
+                policy in structured English, whether it holds (true, false or null for unknown), \
+                and its violations.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "policy": { "type": "string", "description": "Evaluate only this policy" }
+                }
+            }
+        }),
+        json!({
+            "name": "authority_decision",
+            "description": "Decide an authority request (actor, operation, resource) against the \
+                loaded authority environment; returns the decision and its trace.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "request": { "type": "object", "description": "AuthorityRequest JSON" },
+                    "facts": {
+                        "type": "array",
+                        "items": { "type": "object" },
+                        "description": "FactEnvelope JSON values"
+                    }
+                },
+                "required": ["request"]
+            }
+        }),
+        json!({
+            "name": "validate_snippet",
+            "description": "Check proposed SEA source without writing it: the snippet is appended \
+                to a module of the model, which is then resolved and validated. Diagnostics name \
+                the module and line; the snippet starts at snippet_line.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "source": { "type": "string", "description": "SEA declarations" },
+                    "module": {
+                        "type": "string",
+                        "description": "Logical path of the module to append to (default: the entry)"
+                    }
+                },
+                "required": ["source"]
+            }
+        }),
+    ]
+}
+
+fn string_arg<'a>(args: &'a Value, name: &str) -> Result<&'a str, String> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing string argument '{name}'"))
+}
+
+fn diagnostics_message(diagnostics: &[ApplicationDiagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The entry's import closure, read from disk for one request.
+struct Model {
+    entry: String,
+    sources: SourceMap,
+}
+
+impl Model {
+    fn load(input: &Path) -> Result<Self, String> {
+        let source = std::fs::read_to_string(input)
+            .map_err(|e| format!("Failed to read input file {}: {e}", input.display()))?;
+        let registry = NamespaceRegistry::discover(input).map_err(|e| e.to_string())?;
+        let default_namespace = registry
+            .as_ref()
+            .and_then(|reg| reg.namespace_for(input).map(|ns| ns.to_string()));
+        let (entry, sources) = crate::module::resolver::source_map_from_filesystem(
+            input,
+            &source,
+            registry.as_ref(),
+            default_namespace.as_deref(),
+        )
+        .map_err(|d| diagnostics_message(&d))?;
+        Ok(Self { entry, sources })
+    }
+
+    fn graph(&self) -> Result<Graph, Vec<ApplicationDiagnostic>> {
+        let sources_json = crate::application::resolve::source_map_json(&self.sources)?;
+        crate::application::resolve::resolve_application_graph(&self.entry, &sources_json)
+    }
+
+    fn envelope(
+        &self,
+    ) -> Result<crate::application::CanonicalSemanticEnvelopeDocument, Vec<ApplicationDiagnostic>>
+    {
+        let sources_json = crate::application::resolve::source_map_json(&self.sources)?;
+        crate::application::resolve_semantic_envelope(&self.entry, &sources_json)
+    }
+}
+
+/// The concept a neighbourhood is centred on.
+enum Centre {
+    Entity(ConceptId),
+    Resource(ConceptId),
+    Role(ConceptId),
+}
+
+fn find_centre(graph: &Graph, concept: &str, kind: Option<&str>) -> Result<Centre, String> {
+    let (namespace, name) = match concept.rsplit_once("::") {
+        Some((namespace, name)) => (Some(namespace), name),
+        None => (None, concept),
+    };
+    let in_scope = |n: &str, ns: &str| n == name && namespace.map_or(true, |want| ns == want);
+    let mut matches: Vec<(String, Centre)> = Vec::new();
+    if kind.map_or(true, |k| k == "entity") {
+        for e in graph.all_entities() {
+            if in_scope(e.name(), e.namespace()) {
+                matches.push((
+                    format!("entity {}::{}", e.namespace(), e.name()),
+                    Centre::Entity(e.id().clone()),
+                ));
+            }
+        }
+    }
+    if kind.map_or(true, |k| k == "resource") {
+        for r in graph.all_resources() {
+            if in_scope(r.name(), r.namespace()) {
+                matches.push((
+                    format!("resource {}::{}", r.namespace(), r.name()),
+                    Centre::Resource(r.id().clone()),
+                ));
+            }
+        }
+    }
+    if kind.map_or(true, |k| k == "role") {
+        for r in graph.all_roles() {
+            if in_scope(r.name(), r.namespace()) {
+                matches.push((
+                    format!("role {}::{}", r.namespace(), r.name()),
+                    Centre::Role(r.id().clone()),
+                ));
+            }
+        }
+    }
+    match matches.len() {
+        0 => Err(format!(
+            "No entity, resource or role named '{concept}' in the model"
+        )),
+        1 => Ok(matches.remove(0).1),
+        _ => {
+            let mut names: Vec<String> = matches.into_iter().map(|(label, _)| label).collect();
+            names.sort();
+            Err(format!(
+                "'{concept}' is ambiguous ({}); pass kind or use <namespace>::<name>",
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+/// Entities reachable within `depth` flow hops of the centre, the flows
+/// between them, and what the centre's kind adds (roles, unit, relations).
+fn neighbourhood(
+    graph: &Graph,
+    concept: &str,
+    kind: Option<&str>,
+    depth: u64,
+) -> Result<Value, String> {
+    let centre = find_centre(graph, concept, kind)?;
+    let mut hops: IndexMap<ConceptId, u64> = IndexMap::new();
+    let mut flows: Vec<&Flow> = Vec::new();
+    let mut seen_flows: HashSet<ConceptId> = HashSet::new();
+    let mut frontier: Vec<ConceptId> = Vec::new();
+    let mut first_hop = 1;
+
+    let mut result = match &centre {
+        Centre::Entity(id) => {
+            let entity = graph
+                .get_entity(id)
+                .ok_or("entity vanished from the graph")?;
+            hops.insert(id.clone(), 0);
+            frontier.push(id.clone());
+            json!({
+                "kind": "entity",
+                "name": entity.name(),
+                "namespace": entity.namespace(),
+                "roles": sorted(graph.role_names_for_entity(id)),
+            })
+        }
+        Centre::Resource(id) => {
+            let resource = graph
+                .get_resource(id)
+                .ok_or("resource vanished from the graph")?;
+            for flow in graph.all_flows() {
+                if flow.resource_id() == id && seen_flows.insert(flow.id().clone()) {
+                    flows.push(flow);
+                    for end in [flow.from_id(), flow.to_id()] {
+                        if hops.insert(end.clone(), 1).is_none() {
+                            frontier.push(end.clone());
+                        }
+                    }
+                }
+            }
+            first_hop = 2;
+            json!({
+                "kind": "resource",
+                "name": resource.name(),
+                "namespace": resource.namespace(),
+                "unit": resource.unit_symbol(),
+            })
+        }
+        Centre::Role(id) => {
+            let role = graph.get_role(id).ok_or("role vanished from the graph")?;
+            for entity in graph.all_entities() {
+                let plays = graph
+                    .roles_for_entity(entity.id())
+                    .is_some_and(|roles| roles.contains(id));
+                if plays && hops.insert(entity.id().clone(), 1).is_none() {
+                    frontier.push(entity.id().clone());
+                }
+            }
+            first_hop = 2;
+            let role_name = |role: &ConceptId| {
+                graph
+                    .get_role(role)
+                    .map(|r| r.name().to_string())
+                    .unwrap_or_else(|| role.to_string())
+            };
+            let mut relations: Vec<Value> = graph
+                .all_relations()
+                .into_iter()
+                .filter(|r| r.subject_role() == id || r.object_role() == id)
+                .map(|r| {
+                    json!({
+                        "name": r.name(),
+                        "subject": role_name(r.subject_role()),
+                        "predicate": r.predicate(),
+                        "object": role_name(r.object_role()),
+                        "via": r.via_flow().and_then(|f| graph.get_resource(f)).map(|f| f.name()),
+                    })
+                })
+                .collect();
+            relations.sort_by_key(|r| r.to_string());
+            json!({
+                "kind": "role",
+                "name": role.name(),
+                "namespace": role.namespace(),
+                "relations": relations,
+            })
+        }
+    };
+
+    for hop in first_hop..=depth {
+        let mut next = Vec::new();
+        for entity in &frontier {
+            for flow in graph
+                .flows_from(entity)
+                .into_iter()
+                .chain(graph.flows_to(entity))
+            {
+                if !seen_flows.insert(flow.id().clone()) {
+                    continue;
+                }
+                flows.push(flow);
+                for end in [flow.from_id(), flow.to_id()] {
+                    if !hops.contains_key(end) {
+                        hops.insert(end.clone(), hop);
+                        next.push(end.clone());
+                    }
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    let mut entities: Vec<(u64, String, String, Vec<String>)> = hops
+        .iter()
+        .filter(|(_, hop)| **hop > 0)
+        .filter_map(|(id, hop)| {
+            graph.get_entity(id).map(|e| {
+                (
+                    *hop,
+                    e.namespace().to_string(),
+                    e.name().to_string(),
+                    sorted(graph.role_names_for_entity(id)),
+                )
+            })
+        })
+        .collect();
+    entities.sort();
+    let mut flow_rows: Vec<(String, String, String, String, String)> = Vec::new();
+    for flow in flows {
+        let resolved = resolve_flow(graph, flow)?;
+        flow_rows.push((
+            resolved.resource,
+            resolved.from,
+            resolved.to,
+            resolved.quantity,
+            resolved.unit,
+        ));
+    }
+    flow_rows.sort();
+
+    let name = result["name"].as_str().unwrap_or_default().to_string();
+    let mut policies: Vec<Value> = graph
+        .all_policies()
+        .into_iter()
+        .filter(|policy| crate::projection::docs::mentions(policy.expression(), &name))
+        .map(|policy| json!({ "name": policy.name, "english": policy.to_english() }))
+        .collect();
+    policies.sort_by_key(|p| p["name"].to_string());
+
+    result["entities"] = entities
+        .into_iter()
+        .map(|(hops, namespace, name, roles)| {
+            json!({ "name": name, "namespace": namespace, "hops": hops, "roles": roles })
+        })
+        .collect();
+    result["flows"] = flow_rows
+        .into_iter()
+        .map(|(resource, from, to, quantity, unit)| {
+            json!({ "resource": resource, "from": from, "to": to, "quantity": quantity, "unit": unit })
+        })
+        .collect();
+    result["policies"] = Value::Array(policies);
+    Ok(result)
+}
+
+fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names
+}
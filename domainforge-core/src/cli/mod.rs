@@ -1,10 +1,14 @@
 use clap::{Parser, Subcommand, ValueEnum};
 
 pub mod authority;
+pub mod build;
+pub mod check_payload;
 pub mod format;
 pub mod import;
+pub mod mcp;
 pub mod normalize;
 pub mod pack;
+pub mod package;
 pub mod parse;
 pub mod project;
 pub mod registry;
@@ -37,7 +41,9 @@ pub enum Commands {
     /// Import from other formats
     Import(import::ImportArgs),
     /// Project/Export to other formats
-    Project(project::ProjectArgs),
+    Project(Box<project::ProjectArgs>),
+    /// Run every projection target declared in domainforge.toml
+    Build(build::BuildArgs),
     /// Format SEA files
     #[command(name = "format", alias = "fmt")]
     Format(format::FormatArgs),
@@ -54,6 +60,13 @@ pub enum Commands {
     Authority(authority::AuthorityArgs),
     /// Semantic pack management commands
     Pack(pack::PackArgs),
+    /// Model package dependency commands
+    Package(package::PackageArgs),
+    /// Validate a JSON payload against an application record
+    #[command(name = "check-payload")]
+    CheckPayload(check_payload::CheckPayloadArgs),
+    /// Serve the model to AI agents over the Model Context Protocol (stdio)
+    Mcp(mcp::McpArgs),
 }
 
 #[derive(ValueEnum, Clone, Debug, Copy)]
@@ -23,6 +23,10 @@ pub struct NormalizeArgs {
     /// Output result as JSON
     #[arg(long, help = "Output as JSON object")]
     pub json: bool,
+
+    /// Also render the expression in structured English
+    #[arg(long, help = "Also print the expression in structured English")]
+    pub english: bool,
 }
 
 /// Result of normalization for JSON output.
@@ -36,6 +40,8 @@ struct NormalizeResult {
     other_normalized: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     other_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    english: Option<String>,
 }
 
 /// Run the normalize command.
@@ -73,6 +79,8 @@ pub fn run_with_writer<W: std::io::Write>(
         (None, None, None)
     };
 
+    let english = args.english.then(|| expr1.to_english());
+
     if args.json {
         // JSON output
         let result = NormalizeResult {
@@ -81,11 +89,15 @@ pub fn run_with_writer<W: std::io::Write>(
             equivalent,
             other_normalized,
             other_hash,
+            english,
         };
         writeln!(writer, "{}", serde_json::to_string_pretty(&result)?)?;
     } else {
         // Human-readable output
         writeln!(writer, "{}", normalized1)?;
+        if let Some(english) = english {
+            writeln!(writer, "{}", english)?;
+        }
 
         if let (Some(is_equiv), Some(other_norm), _) = (equivalent, other_normalized, other_hash) {
             if is_equiv {
@@ -115,6 +127,7 @@ mod tests {
             expression: "b AND a".to_string(),
             check_equiv: None,
             json: false,
+            english: false,
         };
         let mut buffer = Vec::new();
         run_with_writer(args, &mut buffer).unwrap();
@@ -128,6 +141,7 @@ mod tests {
             expression: "a AND b".to_string(),
             check_equiv: Some("b AND a".to_string()),
             json: false,
+            english: false,
         };
         let mut buffer = Vec::new();
         run_with_writer(args, &mut buffer).unwrap();
@@ -142,6 +156,7 @@ mod tests {
             expression: "a AND b".to_string(),
             check_equiv: Some("a OR b".to_string()),
             json: false,
+            english: false,
         };
         let mut buffer = Vec::new();
         run_with_writer(args, &mut buffer).unwrap();
@@ -155,6 +170,7 @@ mod tests {
             expression: "true AND x".to_string(),
             check_equiv: Some("x".to_string()),
             json: true,
+            english: false,
         };
         let mut buffer = Vec::new();
         run_with_writer(args, &mut buffer).unwrap();
@@ -165,12 +181,32 @@ mod tests {
         assert_eq!(json_val["equivalent"], true);
     }
 
+    #[test]
+    fn test_normalize_english_output() {
+        let args = NormalizeArgs {
+            expression: "forall f in flows: (f.quantity <= 1000)".to_string(),
+            check_equiv: None,
+            json: true,
+            english: true,
+        };
+        let mut buffer = Vec::new();
+        run_with_writer(args, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        let json_val: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+        assert_eq!(
+            json_val["english"],
+            "each flow has a quantity of at most 1000"
+        );
+    }
+
     #[test]
     fn test_invalid_expression() {
         let args = NormalizeArgs {
             expression: "NOT (a".to_string(),
             check_equiv: None,
             json: false,
+            english: false,
         };
         let mut buffer = Vec::new();
         let result = run_with_writer(args, &mut buffer);
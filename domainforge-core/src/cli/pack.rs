@@ -171,7 +171,7 @@ fn exit_with_code(code: i32, message: &str) -> Result<()> {
     }
 }
 
-fn load_pack_json(path: &Path) -> Result<SemanticPack> {
+pub(crate) fn load_pack_json(path: &Path) -> Result<SemanticPack> {
     let data = fs::read_to_string(path)
         .with_context(|| format!("Failed to read pack file: {}", path.display()))?;
     serde_json::from_str(&data)
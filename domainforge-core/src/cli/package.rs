@@ -0,0 +1,65 @@
+use crate::package::install::{fetch, lock, vendor};
+use crate::package::{LOCK_FILE_NAME, VENDOR_DIR};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct PackageArgs {
+    #[command(subcommand)]
+    pub command: PackageCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PackageCommands {
+    /// Resolve domainforge.toml dependencies and write domainforge.lock
+    Lock(WorkspaceArgs),
+    /// Unpack the locked archive packages missing from the package cache
+    Fetch(WorkspaceArgs),
+    /// Copy the locked package modules into vendor/ for offline resolution
+    Vendor(WorkspaceArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct WorkspaceArgs {
+    /// Directory holding domainforge.toml
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+}
+
+pub fn run(args: PackageArgs) -> Result<()> {
+    match args.command {
+        PackageCommands::Lock(args) => {
+            let lockfile = lock(&args.dir)?;
+            for package in &lockfile.packages {
+                println!(
+                    "Locked {} {} ({})",
+                    package.name, package.version, package.source
+                );
+            }
+            println!(
+                "Wrote {} ({} package(s))",
+                args.dir.join(LOCK_FILE_NAME).display(),
+                lockfile.packages.len()
+            );
+        }
+        PackageCommands::Fetch(args) => {
+            let fetched = fetch(&args.dir)?;
+            if fetched.is_empty() {
+                println!("All locked packages are present");
+            }
+            for name in fetched {
+                println!("Fetched {}", name);
+            }
+        }
+        PackageCommands::Vendor(args) => {
+            let vendored = vendor(&args.dir)?;
+            println!(
+                "Vendored {} package(s) into {}",
+                vendored.len(),
+                args.dir.join(VENDOR_DIR).display()
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,15 +1,23 @@
+use crate::application::envelope::CanonicalSemanticEnvelopeDocument;
 use crate::parser::ParseOptions;
+use crate::projection::diagram::DiagramFormat;
+use crate::projection::drift::{self, Drift};
 use crate::projection::protobuf::{CompatibilityMode, SchemaHistory};
+use crate::projection::schema_registry::RegistryCompatibility;
+use crate::projection::sink::ArtifactSink;
 use crate::projection::ProtobufEngine;
 use crate::NamespaceRegistry;
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
+use std::collections::BTreeMap;
 use std::fs::{read_to_string, write};
 use std::path::{Path, PathBuf};
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 pub struct ProjectArgs {
-    #[arg(long, value_enum)]
+    /// Projection format, or `plugin:<name>` to run an external projection
+    /// plugin (see docs/reference/projection-plugins.md)
+    #[arg(long, value_parser = FormatParser)]
     pub format: ProjectFormat,
 
     /// Optional namespace filter (project only entities from this namespace)
@@ -32,7 +40,8 @@ pub struct ProjectArgs {
     #[arg(long, value_enum, default_value = "backward")]
     pub compatibility: CliCompatibilityMode,
 
-    /// Directory to store schema history for compatibility checking (protobuf only)
+    /// Directory to store schema history for compatibility checking (protobuf)
+    /// or migration generation (sql-postgres)
     #[arg(long)]
     pub schema_history: Option<PathBuf>,
 
@@ -78,7 +87,8 @@ pub struct ProjectArgs {
 
     /// Fixed RFC3339 created_at timestamp for byte-identical output (all
     /// directory-output projections: ai-*, rdf, bpmn, cmmn, archimate,
-    /// otel-semconv, baml, dspy, zenml)
+    /// otel-semconv, baml, dspy, zenml, mermaid, plantuml, dot, structurizr, sql-postgres,
+    /// graphql, json-schema, template, docs)
     #[arg(long)]
     pub created_at: Option<String>,
 
@@ -87,6 +97,12 @@ pub struct ProjectArgs {
     #[arg(long)]
     pub base_iri: Option<String>,
 
+    /// Entity to center the diagram on — only its flows and their
+    /// counterparties are drawn (mermaid, plantuml, dot only; `name` or
+    /// `namespace::name`)
+    #[arg(long)]
+    pub focus: Option<String>,
+
     /// `domainforge.cell.toml` override document (cell format only)
     #[arg(long)]
     pub overrides: Option<PathBuf>,
@@ -97,11 +113,47 @@ pub struct ProjectArgs {
     #[arg(long)]
     pub only: Option<String>,
 
+    /// Stakeholder viewpoint to add a view for; repeatable (archimate only):
+    /// organization, business-process-cooperation, information-structure,
+    /// motivation, requirements-realization
+    #[arg(long = "viewpoint")]
+    pub viewpoints: Vec<String>,
+
+    /// Directory of previously published schemas to check the projected ones
+    /// against (avro, json-schema only)
+    #[arg(long)]
+    pub published_schemas: Option<PathBuf>,
+
+    /// Schema-registry compatibility level enforced against
+    /// --published-schemas (avro, json-schema only)
+    #[arg(long, value_enum, default_value = "backward")]
+    pub registry_compatibility: CliRegistryCompatibility,
+
+    /// Directory of `.j2` templates to render (template format only; see
+    /// docs/template-projections.md)
+    #[arg(long)]
+    pub templates: Option<PathBuf>,
+
+    /// Semantic pack JSON whose definitions, examples, counterexamples,
+    /// aliases and owners are shown on the pages (docs format only)
+    #[arg(long)]
+    pub semantic_pack: Option<PathBuf>,
+
+    /// KEY=VALUE option passed to a projection plugin; repeatable
+    /// (plugin:<name> only)
+    #[arg(long = "plugin-option")]
+    pub plugin_options: Vec<String>,
+
+    /// Render in memory and compare against the output instead of writing;
+    /// fails with a diff per drifted file and the missing/extra files
+    #[arg(long)]
+    pub check: bool,
+
     pub input: PathBuf,
     pub output: PathBuf,
 }
 
-#[derive(ValueEnum, Clone, Debug, Copy, PartialEq, Eq)]
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
 pub enum ProjectFormat {
     /// Architecture operator: CALM architecture JSON (single-file output)
     Calm,
@@ -172,6 +224,104 @@ pub enum ProjectFormat {
     /// Code operator: Rust DDD/CQRS domain layer — complete crate up to the ports (directory output)
     #[value(name = "domain-rust")]
     DomainRust,
+    /// Diagram operator: Mermaid flowchart — namespaces as subgraphs, flows as labelled edges (directory output)
+    #[value(name = "mermaid")]
+    Mermaid,
+    /// Diagram operator: PlantUML component diagram — namespaces as packages, flows as labelled edges (directory output)
+    #[value(name = "plantuml")]
+    Plantuml,
+    /// Diagram operator: Graphviz DOT digraph — namespaces as clusters, flows as labelled edges (directory output)
+    #[value(name = "dot")]
+    Dot,
+    /// Architecture operator: Structurizr DSL workspace — namespaces as software systems, entities/services/endpoints as containers, roles as people, with C4 context, container and dynamic views (directory output)
+    #[value(name = "structurizr")]
+    Structurizr,
+    /// Persistence operator: PostgreSQL DDL — entity bodies as tables with keys, foreign keys and CHECK constraints, plus forward migrations from --schema-history (directory output)
+    #[value(name = "sql-postgres")]
+    SqlPostgres,
+    /// API operator: GraphQL SDL schema — entities and records as object/input types, enums, read operations as queries, mutating operations as mutations, constraints as directives (directory output)
+    #[value(name = "graphql")]
+    Graphql,
+    /// Schema operator: Avro schemas — one .avsc per resource flow and record, checked against --published-schemas (directory output)
+    #[value(name = "avro")]
+    Avro,
+    /// Schema operator: JSON Schema 2020-12 — one document per resource flow and record, checked against --published-schemas (directory output)
+    #[value(name = "json-schema")]
+    JsonSchema,
+    /// Custom operator: renders a directory of Jinja-style templates (--templates) against the model — loops over entities, flows, policies, records and operations, with the `projection::ids` helpers as filters (directory output)
+    #[value(name = "template")]
+    Template,
+    /// Documentation operator: static HTML and Markdown site — a page per entity, resource, role, record and operation with semantic-pack definitions, flows, governing policies in plain English and diagrams, plus a searchable glossary (directory output)
+    #[value(name = "docs")]
+    Docs,
+    /// External projection plugin, selected as `plugin:<name>` (directory output)
+    #[value(skip)]
+    Plugin(String),
+}
+
+impl ProjectFormat {
+    /// Whether the projection embeds the time of its run when `--created-at`
+    /// is not given. Templates and plugins only see a pinned timestamp.
+    pub(crate) fn stamps_run_time(&self) -> bool {
+        !matches!(
+            self,
+            ProjectFormat::Calm
+                | ProjectFormat::Kg
+                | ProjectFormat::Protobuf
+                | ProjectFormat::Proto
+                | ProjectFormat::Avro
+                | ProjectFormat::Template
+                | ProjectFormat::Plugin(_)
+        )
+    }
+}
+
+/// Parses `--format`: a [`ProjectFormat`] value, or `plugin:<name>`.
+#[derive(Clone)]
+pub(crate) struct FormatParser;
+
+impl FormatParser {
+    pub(crate) fn parse(value: &str) -> std::result::Result<ProjectFormat, String> {
+        match value.strip_prefix("plugin:") {
+            Some(name) => {
+                crate::projection::plugin::validate_plugin_name(name)?;
+                Ok(ProjectFormat::Plugin(name.to_string()))
+            }
+            None => ProjectFormat::from_str(value, true)
+                .map_err(|_| format!("Unknown format '{value}'")),
+        }
+    }
+}
+
+impl clap::builder::TypedValueParser for FormatParser {
+    type Value = ProjectFormat;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> std::result::Result<ProjectFormat, clap::Error> {
+        match value.to_str().and_then(|v| v.strip_prefix("plugin:")) {
+            Some(_) => Self::parse(value.to_str().unwrap_or_default()).map_err(|message| {
+                clap::Error::raw(clap::error::ErrorKind::InvalidValue, format!("{message}\n"))
+                    .with_cmd(cmd)
+            }),
+            None => {
+                clap::builder::EnumValueParser::<ProjectFormat>::new().parse_ref(cmd, arg, value)
+            }
+        }
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
+        Some(Box::new(
+            ProjectFormat::value_variants()
+                .iter()
+                .filter_map(ProjectFormat::to_possible_value),
+        ))
+    }
 }
 
 #[derive(ValueEnum, Clone, Debug, Copy, Default)]
@@ -195,129 +345,455 @@ impl From<CliCompatibilityMode> for CompatibilityMode {
     }
 }
 
+#[derive(ValueEnum, Clone, Debug, Copy, Default)]
+pub enum CliRegistryCompatibility {
+    /// No checking
+    None,
+    /// New schemas can read data written with the published ones
+    #[default]
+    Backward,
+    /// Published schemas can read data written with the new ones
+    Forward,
+    /// Both backward and forward
+    Full,
+}
+
+impl From<CliRegistryCompatibility> for RegistryCompatibility {
+    fn from(mode: CliRegistryCompatibility) -> Self {
+        match mode {
+            CliRegistryCompatibility::None => RegistryCompatibility::None,
+            CliRegistryCompatibility::Backward => RegistryCompatibility::Backward,
+            CliRegistryCompatibility::Forward => RegistryCompatibility::Forward,
+            CliRegistryCompatibility::Full => RegistryCompatibility::Full,
+        }
+    }
+}
+
 pub fn run(args: ProjectArgs) -> Result<()> {
     // The cell projection parses the Ast directly (ADR-012 deviation from
     // ADR-011) and reads namespace/import context the generic Graph builder
     // discards. Dispatch it before graph construction so the model is parsed
     // exactly once and the Cell declarations are not silently dropped.
-    if args.format == ProjectFormat::Cell {
-        return run_cell(&args);
+    let drift = if args.format == ProjectFormat::Cell {
+        run_cell_projection(&args)?
+    } else {
+        let model = ResolvedInput::resolve(&args.input)?;
+        run_resolved(&args, &model)?
+    };
+
+    if let Some(drift) = drift {
+        print_drift(&args.output, &drift);
+        if !drift.is_clean() {
+            anyhow::bail!(
+                "{} has drifted from the projection: {}",
+                args.output.display(),
+                drift.summary()
+            );
+        }
     }
+    Ok(())
+}
 
-    let source = read_to_string(&args.input)
-        .with_context(|| format!("Failed to read input file {}", args.input.display()))?;
+/// Print a `--check` result: the diffs and missing/extra files, or that the
+/// output is up to date.
+pub(crate) fn print_drift(output: &Path, drift: &Drift) {
+    if drift.is_clean() {
+        println!("{} is up to date", output.display());
+    } else {
+        print!("{drift}");
+    }
+}
 
-    // Parse input
-    let registry =
-        NamespaceRegistry::discover(&args.input).context("discovering namespace registry")?;
-    let default_namespace = registry
-        .as_ref()
-        .and_then(|reg| reg.namespace_for(&args.input).map(|ns| ns.to_string()));
-    let options = ParseOptions {
-        default_namespace: default_namespace.clone(),
-        namespace_registry: registry.clone(),
-        entry_path: Some(args.input.clone()),
-        ..Default::default()
+/// `println!` for progress lines, which `--check` suppresses: nothing is
+/// projected there, only compared.
+macro_rules! report {
+    ($out:expr, $($arg:tt)*) => {
+        if !$out.is_check() {
+            println!($($arg)*);
+        }
     };
-    let graph = crate::application::resolve::resolve_filesystem_graph(
-        &args.input,
-        &source,
-        options.namespace_registry.as_ref(),
-        options.default_namespace.as_deref(),
-    )
-    .map_err(|diagnostics| {
-        anyhow::anyhow!(
-            "Parse failed for {}: {}",
-            args.input.display(),
-            diagnostics
-                .iter()
-                .map(|diagnostic| diagnostic.message.as_str())
-                .collect::<Vec<_>>()
-                .join("; ")
+}
+
+/// Where a projection's artifacts go: the output path, or under `--check` an
+/// in-memory map that is compared against the output path afterwards.
+pub(crate) struct Output<'a> {
+    path: &'a Path,
+    rendered: Option<BTreeMap<String, String>>,
+    single_file: bool,
+}
+
+impl<'a> Output<'a> {
+    pub(crate) fn new(args: &'a ProjectArgs) -> Self {
+        Self {
+            path: &args.output,
+            rendered: args.check.then(BTreeMap::new),
+            single_file: false,
+        }
+    }
+
+    fn is_check(&self) -> bool {
+        self.rendered.is_some()
+    }
+
+    /// Create the output directory; a no-op under `--check`.
+    fn create_dir(&self) -> Result<()> {
+        if !self.is_check() {
+            std::fs::create_dir_all(self.path).with_context(|| {
+                format!("Failed to create output directory {}", self.path.display())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn sink(&mut self) -> ArtifactSink<'_> {
+        match &mut self.rendered {
+            Some(map) => ArtifactSink::Memory {
+                prefix: String::new(),
+                map,
+            },
+            None => ArtifactSink::Dir(self.path),
+        }
+    }
+
+    /// A sink rooted at `dir`, a directory under the output path.
+    fn sink_at<'s>(&'s mut self, dir: &'s Path) -> Result<ArtifactSink<'s>> {
+        match &mut self.rendered {
+            Some(map) => {
+                let prefix = dir
+                    .strip_prefix(self.path)
+                    .map(|rel| rel.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Ok(ArtifactSink::Memory {
+                    prefix: if prefix.is_empty() {
+                        prefix
+                    } else {
+                        format!("{prefix}/")
+                    },
+                    map,
+                })
+            }
+            None => {
+                std::fs::create_dir_all(dir)?;
+                Ok(ArtifactSink::Dir(dir))
+            }
+        }
+    }
+
+    /// Write a single-file projection to the output path.
+    fn write_file(&mut self, content: String) -> Result<()> {
+        match &mut self.rendered {
+            Some(map) => {
+                self.single_file = true;
+                map.insert(String::new(), content);
+                Ok(())
+            }
+            None => write(self.path, content)
+                .with_context(|| format!("Failed to write output to {}", self.path.display())),
+        }
+    }
+
+    /// The drift between what was rendered and the output path under
+    /// `--check`; `None` when the projection was written.
+    pub(crate) fn finish(self) -> Result<Option<Drift>> {
+        let Some(rendered) = self.rendered else {
+            return Ok(None);
+        };
+        let drift = if self.single_file {
+            drift::compare_file(self.path, &rendered[""])
+        } else {
+            drift::compare_dir(self.path, &rendered)
+        }
+        .with_context(|| format!("Failed to compare against {}", self.path.display()))?;
+        Ok(Some(drift))
+    }
+}
+
+/// A projection input resolved once: its source, parse options and graph, and
+/// the application contract and semantic envelope on first use. `domainforge build` shares one
+/// across every target projecting the same input.
+pub(crate) struct ResolvedInput {
+    input: PathBuf,
+    source: String,
+    options: ParseOptions,
+    graph: crate::graph::Graph,
+    contract: once_cell::unsync::OnceCell<crate::application::ApplicationContract>,
+    envelope: once_cell::unsync::OnceCell<CanonicalSemanticEnvelopeDocument>,
+}
+
+impl ResolvedInput {
+    pub(crate) fn resolve(input: &Path) -> Result<Self> {
+        let source = read_to_string(input)
+            .with_context(|| format!("Failed to read input file {}", input.display()))?;
+
+        // Parse input
+        let registry =
+            NamespaceRegistry::discover(input).context("discovering namespace registry")?;
+        let default_namespace = registry
+            .as_ref()
+            .and_then(|reg| reg.namespace_for(input).map(|ns| ns.to_string()));
+        let options = ParseOptions {
+            default_namespace: default_namespace.clone(),
+            namespace_registry: registry.clone(),
+            entry_path: Some(input.to_path_buf()),
+            ..Default::default()
+        };
+        let graph = crate::application::resolve::resolve_filesystem_graph(
+            input,
+            &source,
+            options.namespace_registry.as_ref(),
+            options.default_namespace.as_deref(),
         )
-    })?;
+        .map_err(|diagnostics| {
+            anyhow::anyhow!(
+                "Parse failed for {}: {}",
+                input.display(),
+                diagnostics
+                    .iter()
+                    .map(|diagnostic| diagnostic.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )
+        })?;
+        Ok(Self {
+            input: input.to_path_buf(),
+            source,
+            options,
+            graph,
+            contract: once_cell::unsync::OnceCell::new(),
+            envelope: once_cell::unsync::OnceCell::new(),
+        })
+    }
 
-    match args.format {
+    fn contract(&self) -> Result<&crate::application::ApplicationContract> {
+        self.contract
+            .get_or_try_init(|| resolve_contract(&self.input, &self.source, &self.options))
+    }
+
+    /// The application contract, or `None` for a graph-only model, which
+    /// declares no record, enum or operation and so is not resolved as one.
+    fn declared_contract(&self) -> Result<Option<&crate::application::ApplicationContract>> {
+        let declares = crate::application::resolve::filesystem_declares_contract(
+            &self.input,
+            &self.source,
+            self.options.namespace_registry.as_ref(),
+            self.options.default_namespace.as_deref(),
+        )
+        .map_err(|diagnostics| {
+            anyhow::anyhow!(
+                "Parse failed for {}: {}",
+                self.input.display(),
+                diagnostics
+                    .iter()
+                    .map(|diagnostic| diagnostic.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )
+        })?;
+        if declares {
+            self.contract().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn envelope(&self) -> Result<&CanonicalSemanticEnvelopeDocument> {
+        self.envelope.get_or_try_init(|| {
+            crate::application::resolve::resolve_filesystem_envelope(
+                &self.input,
+                &self.source,
+                self.options.namespace_registry.as_ref(),
+                self.options.default_namespace.as_deref(),
+            )
+            .map_err(|diagnostics| {
+                anyhow::anyhow!(
+                    "Semantic envelope resolution failed for {}: {}",
+                    self.input.display(),
+                    diagnostics
+                        .iter()
+                        .map(|diagnostic| diagnostic.message.as_str())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                )
+            })
+        })
+    }
+}
+
+/// Run one projection against an already-resolved input. Under `--check`
+/// nothing is written and the drift from the output is returned instead.
+pub(crate) fn run_resolved(args: &ProjectArgs, model: &ResolvedInput) -> Result<Option<Drift>> {
+    if args.check && (args.buf_lint || args.buf_breaking || args.buf_generate) {
+        anyhow::bail!(
+            "--check cannot be combined with --buf-lint, --buf-breaking or --buf-generate"
+        );
+    }
+    if args.format == ProjectFormat::Cell {
+        return run_cell_projection(args);
+    }
+    let pinned = pinned_for_check(args)?;
+    let args = pinned.as_ref().unwrap_or(args);
+    let mut out = Output::new(args);
+    project(args, model, &mut out)?;
+    out.finish()
+}
+
+/// Run the cell projection, which parses its input itself (see [`run`]).
+pub(crate) fn run_cell_projection(args: &ProjectArgs) -> Result<Option<Drift>> {
+    let pinned = pinned_for_check(args)?;
+    let args = pinned.as_ref().unwrap_or(args);
+    let mut out = Output::new(args);
+    run_cell(args, &mut out)?;
+    out.finish()
+}
+
+/// Under `--check` without `--created-at`, the arguments with the timestamp
+/// the output's `generated-at:` marker records, so a projection that embeds
+/// the time of its run compares equal to an output it wrote unpinned. An
+/// output without the marker (the JSON-only formats cannot carry one) needs
+/// `--created-at`.
+fn pinned_for_check(args: &ProjectArgs) -> Result<Option<ProjectArgs>> {
+    if !args.check || args.created_at.is_some() || !args.format.stamps_run_time() {
+        return Ok(None);
+    }
+    match drift::recorded_timestamp(&args.output) {
+        Some(created_at) => Ok(Some(ProjectArgs {
+            created_at: Some(created_at),
+            ..args.clone()
+        })),
+        None if drift::has_artifacts(&args.output) => anyhow::bail!(
+            "{} records no `{}` timestamp; pass --created-at with the time it was projected at",
+            args.output.display(),
+            drift::GENERATED_AT
+        ),
+        None => Ok(None),
+    }
+}
+
+fn project(args: &ProjectArgs, model: &ResolvedInput, out: &mut Output) -> Result<()> {
+    let graph = &model.graph;
+
+    match &args.format {
         ProjectFormat::AiLlm
         | ProjectFormat::AiGraphMl
         | ProjectFormat::CepEval
         | ProjectFormat::AiLearning => {
-            run_ai_learning(&args, &graph)?;
+            run_ai_learning(args, out, graph)?;
         }
         ProjectFormat::Lean => {
-            run_lean(&args, &graph)?;
+            run_lean(args, out, graph)?;
         }
         ProjectFormat::Rdf => {
-            run_rdf(&args, &graph)?;
+            run_rdf(args, out, graph)?;
         }
         ProjectFormat::Bpmn => {
-            run_bpmn(&args, &graph)?;
+            run_bpmn(args, out, graph)?;
         }
         ProjectFormat::Cmmn => {
-            run_cmmn(&args, &graph)?;
+            run_cmmn(args, out, graph)?;
         }
         ProjectFormat::Archimate => {
-            run_archimate(&args, &graph)?;
+            run_archimate(args, out, graph)?;
         }
         ProjectFormat::OtelSemconv => {
-            run_otel_semconv(&args, &graph)?;
+            run_otel_semconv(args, out, graph)?;
         }
         ProjectFormat::Baml => {
-            run_baml(&args, &graph)?;
+            run_baml(args, out, graph)?;
         }
         ProjectFormat::Dspy => {
-            run_dspy(&args, &graph)?;
+            run_dspy(args, out, graph)?;
         }
         ProjectFormat::Zenml => {
-            run_zenml(&args, &graph)?;
+            run_zenml(args, out, graph)?;
         }
         ProjectFormat::CloudEvents => {
-            run_cloudevents(&args, &graph)?;
+            run_cloudevents(args, out, graph)?;
         }
         ProjectFormat::AsyncApi => {
-            run_asyncapi(&args, &graph)?;
+            run_asyncapi(args, out, graph)?;
         }
         ProjectFormat::Devbox => {
-            run_devbox(&args, &graph)?;
+            run_devbox(args, out, graph)?;
         }
         // Cell is dispatched before graph construction at the top of `run`;
         // reaching here is a logic error.
         ProjectFormat::Cell => unreachable!("Cell dispatched early in run()"),
         ProjectFormat::Dagger => {
-            run_dagger(&args, &graph)?;
+            run_dagger(args, out, graph)?;
         }
         ProjectFormat::Cedar => {
-            run_cedar(&args, &graph)?;
+            run_cedar(args, out, graph)?;
         }
         ProjectFormat::Gauge => {
-            run_gauge(&args, &graph)?;
+            run_gauge(args, out, graph)?;
         }
         ProjectFormat::Alloy => {
-            run_alloy(&args, &graph)?;
+            run_alloy(args, out, graph)?;
         }
         ProjectFormat::Tla => {
-            run_tla(&args, &graph)?;
+            run_tla(args, out, graph)?;
+        }
+        ProjectFormat::Mermaid => {
+            run_diagram(args, out, graph, DiagramFormat::Mermaid)?;
+        }
+        ProjectFormat::Plantuml => {
+            run_diagram(args, out, graph, DiagramFormat::PlantUml)?;
+        }
+        ProjectFormat::Dot => {
+            run_diagram(args, out, graph, DiagramFormat::Dot)?;
+        }
+        ProjectFormat::Structurizr => {
+            run_structurizr(args, out, graph, &model.source)?;
+        }
+        ProjectFormat::SqlPostgres => {
+            let contract = model.contract()?;
+            run_sql_postgres(args, out, graph, contract)?;
+        }
+        ProjectFormat::Graphql => {
+            let contract = model.contract()?;
+            run_graphql(args, out, graph, contract)?;
+        }
+        ProjectFormat::Avro => {
+            let contract = model.contract()?;
+            run_payload_schemas(args, out, graph, contract, PayloadSchemaFormat::Avro)?;
+        }
+        ProjectFormat::JsonSchema => {
+            let contract = model.contract()?;
+            run_payload_schemas(args, out, graph, contract, PayloadSchemaFormat::JsonSchema)?;
         }
         ProjectFormat::DomainPython => {
-            run_domain_python(&args, &graph)?;
+            let contract = model.contract()?;
+            run_domain_python(args, out, graph, contract)?;
         }
         ProjectFormat::DomainTypescript => {
-            run_domain_typescript(&args, &graph)?;
+            let contract = model.contract()?;
+            run_domain_typescript(args, out, graph, contract)?;
         }
         ProjectFormat::DomainRust => {
-            run_domain_rust(&args, &graph)?;
+            let contract = model.contract()?;
+            run_domain_rust(args, out, graph, contract)?;
+        }
+        ProjectFormat::Template => {
+            let contract = model.contract()?;
+            run_template(args, out, graph, contract)?;
+        }
+        ProjectFormat::Docs => {
+            let contract = model.contract()?;
+            run_docs(args, out, graph, contract)?;
+        }
+        ProjectFormat::Plugin(name) => {
+            run_plugin(args, out, model, name)?;
         }
         ProjectFormat::Calm => {
-            let value = crate::calm::export(&graph)
+            let value = crate::calm::export(graph)
                 .map_err(|e| anyhow::anyhow!("Failed to export to CALM: {}", e))?;
             let json =
                 serde_json::to_string_pretty(&value).context("Failed to serialize CALM JSON")?;
-            write(&args.output, json)
-                .with_context(|| format!("Failed to write output to {}", args.output.display()))?;
-            println!("Projected to CALM: {}", args.output.display());
+            out.write_file(json)?;
+            report!(out, "Projected to CALM: {}", args.output.display());
         }
         ProjectFormat::Kg => {
-            let kg = crate::KnowledgeGraph::from_graph(&graph)
+            let kg = crate::KnowledgeGraph::from_graph(graph)
                 .map_err(|e| anyhow::anyhow!("Failed to convert to Knowledge Graph: {}", e))?;
 
             let output_str = if args
@@ -332,9 +808,8 @@ pub fn run(args: ProjectArgs) -> Result<()> {
                 kg.to_turtle()
             };
 
-            write(&args.output, output_str)
-                .with_context(|| format!("Failed to write output to {}", args.output.display()))?;
-            println!("Projected to KG: {}", args.output.display());
+            out.write_file(output_str)?;
+            report!(out, "Projected to KG: {}", args.output.display());
         }
         ProjectFormat::Protobuf | ProjectFormat::Proto => {
             let namespace_filter = args.namespace.as_deref().unwrap_or("");
@@ -344,6 +819,10 @@ pub fn run(args: ProjectArgs) -> Result<()> {
                 .and_then(|s| s.to_str())
                 .unwrap_or("projection");
 
+            // Records, enums and operations (ADR-013) are lowered next to the
+            // graph-derived messages so schema history covers them too.
+            let contract = model.declared_contract()?;
+
             if args.multi_file {
                 if args.schema_history.is_some() {
                     eprintln!(
@@ -352,62 +831,85 @@ pub fn run(args: ProjectArgs) -> Result<()> {
                 }
 
                 if !args.output.exists() {
-                    std::fs::create_dir_all(&args.output).with_context(|| {
-                        format!(
-                            "Failed to create output directory {}",
-                            args.output.display()
-                        )
-                    })?;
+                    out.create_dir()?;
                 } else if !args.output.is_dir() {
                     return Err(anyhow::anyhow!(
                         "Output path must be a directory for --multi-file projection"
                     ));
                 }
 
-                let files = ProtobufEngine::project_multi_file(
-                    &graph,
+                let mut files = ProtobufEngine::project_multi_file(
+                    graph,
                     &args.package,
                     args.include_governance,
                     args.include_services,
                 );
+                let contract_file = contract
+                    .map(|contract| {
+                        crate::projection::project_application_contract(
+                            graph,
+                            contract,
+                            &args.package,
+                        )
+                    })
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!("{}", e))?
+                    .filter(|file| !file.messages.is_empty() || !file.enums.is_empty());
+                if let Some(contract_file) = contract_file {
+                    let path = PathBuf::from(crate::projection::APPLICATION_CONTRACT_PROTO);
+                    if files.contains_key(&path) {
+                        return Err(anyhow::anyhow!(
+                            "Namespace file {} clashes with the application contract file",
+                            path.display()
+                        ));
+                    }
+                    files.insert(path, contract_file);
+                }
 
+                let mut sink = out.sink();
                 for (rel_path, proto) in &files {
-                    let abs_path = args.output.join(rel_path);
-                    crate::projection::protobuf::validate_output_path(&args.output, &abs_path)
+                    sink.write(&rel_path.to_string_lossy(), &proto.to_proto_string())
                         .map_err(|e| anyhow::anyhow!("{}", e))?;
-                    if let Some(parent) = abs_path.parent() {
-                        std::fs::create_dir_all(parent)?;
-                    }
-
-                    let content = proto.to_proto_string();
-                    write(&abs_path, content)
-                        .with_context(|| format!("Failed to write {}", abs_path.display()))?;
                 }
 
-                println!(
+                report!(
+                    out,
                     "Projected to Protobuf (Multi-file): {}",
                     args.output.display()
                 );
-                println!("  Files: {}", files.len());
-                println!("  Base Package: {}", args.package);
+                report!(out, "  Files: {}", files.len());
+                report!(out, "  Base Package: {}", args.package);
             } else {
                 let mut proto_file = ProtobufEngine::project_with_full_options(
-                    &graph,
+                    graph,
                     namespace_filter,
                     &args.package,
                     projection_name,
                     args.include_governance,
                     args.include_services,
                 );
+                if let Some(contract) = contract {
+                    crate::projection::merge_application_contract(
+                        &mut proto_file,
+                        graph,
+                        contract,
+                        namespace_filter,
+                    )
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+                }
 
                 // Handle compatibility checking if schema history is provided
                 if let Some(ref history_dir) = args.schema_history {
                     let history = SchemaHistory::new(history_dir);
                     let mode: CompatibilityMode = args.compatibility.into();
 
-                    let result = history
-                        .check_and_update(&mut proto_file, mode, args.apply_fixes)
-                        .map_err(|e| anyhow::anyhow!("Compatibility check failed: {}", e))?;
+                    // `--check` leaves the recorded history alone.
+                    let result = if out.is_check() {
+                        history.check(&mut proto_file, mode, args.apply_fixes)
+                    } else {
+                        history.check_and_update(&mut proto_file, mode, args.apply_fixes)
+                    }
+                    .map_err(|e| anyhow::anyhow!("Compatibility check failed: {}", e))?;
 
                     // Print compatibility report
                     if result.has_violations() {
@@ -433,15 +935,13 @@ pub fn run(args: ProjectArgs) -> Result<()> {
                     }
                 }
 
-                let proto_string = proto_file.to_proto_string();
-                write(&args.output, &proto_string).with_context(|| {
-                    format!("Failed to write output to {}", args.output.display())
-                })?;
-                println!("Projected to Protobuf: {}", args.output.display());
-                println!("  Package: {}", args.package);
-                println!("  Messages: {}", proto_file.messages.len());
+                out.write_file(proto_file.to_proto_string())?;
+                report!(out, "Projected to Protobuf: {}", args.output.display());
+                report!(out, "  Package: {}", args.package);
+                report!(out, "  Messages: {}", proto_file.messages.len());
                 if !proto_file.services.is_empty() {
-                    println!(
+                    report!(
+                        out,
                         "  Services: {} ({} methods)",
                         proto_file.services.len(),
                         proto_file
@@ -479,7 +979,7 @@ pub fn run(args: ProjectArgs) -> Result<()> {
                 }
 
                 if args.buf_breaking {
-                    if let Some(history) = args.schema_history {
+                    if let Some(history) = &args.schema_history {
                         print!("  Running buf breaking check... ");
                         println!("(checking against {})", history.display());
                     }
@@ -491,7 +991,7 @@ pub fn run(args: ProjectArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_lean(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_lean(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format lean (the projection is model-driven)"
@@ -500,19 +1000,14 @@ fn run_lean(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, like the ai-* projections.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the lean projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::lean::emit(
         graph,
         &args.input.display().to_string(),
@@ -520,7 +1015,8 @@ fn run_lean(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("lean projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Lean 4 package to {} ({} files); check with `lake build`",
         args.output.display(),
         files.len()
@@ -539,7 +1035,11 @@ fn validate_created_at(args: &ProjectArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_cloudevents(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_cloudevents(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format cloudevents (the projection is model-driven)"
@@ -549,19 +1049,14 @@ fn run_cloudevents(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
 
     // Directory output, like the other model-driven projections.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the cloudevents projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::cloudevents::emit(
         graph,
         &args.input.display().to_string(),
@@ -569,7 +1064,8 @@ fn run_cloudevents(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("cloudevents projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected CloudEvents 1.0 stream to {} ({} files)",
         args.output.display(),
         files.len()
@@ -577,7 +1073,7 @@ fn run_cloudevents(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
     Ok(())
 }
 
-fn run_asyncapi(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_asyncapi(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format asyncapi (the projection is model-driven)"
@@ -586,19 +1082,14 @@ fn run_asyncapi(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the asyncapi projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::asyncapi::emit(
         graph,
         &args.input.display().to_string(),
@@ -606,7 +1097,8 @@ fn run_asyncapi(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("asyncapi projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected AsyncAPI {} document to {} ({} files)",
         crate::projection::asyncapi::ASYNCAPI_VERSION,
         args.output.display(),
@@ -615,7 +1107,7 @@ fn run_asyncapi(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_devbox(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_devbox(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format devbox (the projection is model-driven)"
@@ -624,19 +1116,14 @@ fn run_devbox(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the devbox projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::devbox::emit(
         graph,
         &args.input.display().to_string(),
@@ -644,7 +1131,8 @@ fn run_devbox(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("devbox projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Devbox manifest to {} ({} files)",
         args.output.display(),
         files.len()
@@ -652,7 +1140,7 @@ fn run_devbox(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_cell(args: &ProjectArgs) -> Result<()> {
+fn run_cell(args: &ProjectArgs, out: &mut Output) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format cell (the projection is model-driven)"
@@ -687,19 +1175,14 @@ fn run_cell(args: &ProjectArgs) -> Result<()> {
     };
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the cell projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::cell::emit(
         &ast,
         &source,
@@ -712,7 +1195,8 @@ fn run_cell(args: &ProjectArgs) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("cell projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Cell environment to {} ({} files)",
         args.output.display(),
         files.len()
@@ -720,7 +1204,7 @@ fn run_cell(args: &ProjectArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_dagger(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_dagger(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format dagger (the projection is model-driven)"
@@ -729,19 +1213,14 @@ fn run_dagger(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the dagger projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::dagger::emit(
         graph,
         &args.input.display().to_string(),
@@ -749,7 +1228,8 @@ fn run_dagger(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("dagger projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Dagger module (engine {}) to {} ({} files)",
         crate::projection::dagger::DAGGER_ENGINE_VERSION,
         args.output.display(),
@@ -758,7 +1238,7 @@ fn run_dagger(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_cedar(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_cedar(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format cedar (the projection is model-driven)"
@@ -767,19 +1247,14 @@ fn run_cedar(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the cedar projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::cedar::emit(
         graph,
         &args.input.display().to_string(),
@@ -787,7 +1262,8 @@ fn run_cedar(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("cedar projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Cedar schema + policies to {} ({} files)",
         args.output.display(),
         files.len()
@@ -795,7 +1271,7 @@ fn run_cedar(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_gauge(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_gauge(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format gauge (the projection is model-driven)"
@@ -804,19 +1280,14 @@ fn run_gauge(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the gauge projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::gauge::emit(
         graph,
         &args.input.display().to_string(),
@@ -824,7 +1295,8 @@ fn run_gauge(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("gauge projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Gauge spec to {} ({} files)",
         args.output.display(),
         files.len()
@@ -832,7 +1304,7 @@ fn run_gauge(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_alloy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_alloy(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format alloy (the projection is model-driven)"
@@ -841,19 +1313,14 @@ fn run_alloy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the alloy projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::alloy::emit(
         graph,
         &args.input.display().to_string(),
@@ -861,7 +1328,8 @@ fn run_alloy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("alloy projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected Alloy model to {} ({} files)",
         args.output.display(),
         files.len()
@@ -869,28 +1337,388 @@ fn run_alloy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_tla(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_diagram(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    format: DiagramFormat,
+) -> Result<()> {
+    let name = format!("{:?}", args.format).to_lowercase();
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
-            "--recipe is not used by --format tla (the projection is model-driven)"
+            "--recipe is not used by --format {name} (the projection is model-driven)"
+        ));
+    }
+    validate_created_at(args)?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the {name} projection"
+        ));
+    }
+
+    let mut sink = out.sink();
+    let files = crate::projection::diagram::emit(
+        graph,
+        format,
+        &args.input.display().to_string(),
+        args.created_at.clone(),
+        args.focus.as_deref(),
+        &mut sink,
+    )
+    .map_err(|e| anyhow::anyhow!("{name} projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected {name} diagram to {} ({} files)",
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+fn run_structurizr(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    source: &str,
+) -> Result<()> {
+    if args.recipe.is_some() {
+        return Err(anyhow::anyhow!(
+            "--recipe is not used by --format structurizr (the projection is model-driven)"
+        ));
+    }
+    validate_created_at(args)?;
+    // Service / Endpoint declarations only live in the Ast.
+    let ast = crate::parser::parse(source)
+        .map_err(|e| anyhow::anyhow!("Parse failed for {}: {}", args.input.display(), e))?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the structurizr projection"
+        ));
+    }
+
+    let mut sink = out.sink();
+    let files = crate::projection::structurizr::emit(
+        graph,
+        Some(&ast),
+        &args.input.display().to_string(),
+        args.created_at.clone(),
+        &mut sink,
+    )
+    .map_err(|e| anyhow::anyhow!("structurizr projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected Structurizr workspace to {} ({} files)",
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+fn run_graphql(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
+    if args.recipe.is_some() {
+        return Err(anyhow::anyhow!(
+            "--recipe is not used by --format graphql (the projection is model-driven)"
         ));
     }
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the graphql projection"
+        ));
+    }
+
+    let mut sink = out.sink();
+    let files = crate::projection::graphql::emit(
+        graph,
+        contract,
+        &args.input.display().to_string(),
+        args.created_at.clone(),
+        &mut sink,
+    )
+    .map_err(|e| anyhow::anyhow!("graphql projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected GraphQL schema to {} ({} files)",
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+fn run_template(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
+    let templates = args
+        .templates
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("--templates <DIR> is required for --format template"))?;
+    validate_created_at(args)?;
+    let templates = crate::projection::template::load_templates(templates)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the template projection"
+        ));
+    }
+
+    let model = crate::projection::template::TemplateModel {
+        input: args.input.display().to_string(),
+        namespace: args.namespace.clone(),
+        created_at: args.created_at.clone(),
+    };
+    let mut sink = out.sink();
+    let files = crate::projection::template::emit(graph, contract, &templates, model, &mut sink)
+        .map_err(|e| anyhow::anyhow!("template projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected templates to {} ({} files)",
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+fn run_docs(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
+    if args.recipe.is_some() {
+        return Err(anyhow::anyhow!(
+            "--recipe is not used by --format docs (the projection is model-driven)"
+        ));
+    }
+    validate_created_at(args)?;
+    let pack = args
+        .semantic_pack
+        .as_deref()
+        .map(super::pack::load_pack_json)
+        .transpose()?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the docs projection"
+        ));
+    }
+
+    let mut sink = out.sink();
+    let files = crate::projection::docs::emit(
+        graph,
+        contract,
+        pack.as_ref(),
+        &args.input.display().to_string(),
+        args.created_at.clone(),
+        &mut sink,
+    )
+    .map_err(|e| anyhow::anyhow!("docs projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected documentation site to {} ({} files)",
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum PayloadSchemaFormat {
+    Avro,
+    JsonSchema,
+}
+
+fn run_payload_schemas(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+    format: PayloadSchemaFormat,
+) -> Result<()> {
+    let (name, label) = match format {
+        PayloadSchemaFormat::Avro => ("avro", "Avro schemas"),
+        PayloadSchemaFormat::JsonSchema => ("json-schema", "JSON Schema documents"),
+    };
+    if args.recipe.is_some() {
+        return Err(anyhow::anyhow!(
+            "--recipe is not used by --format {name} (the projection is model-driven)"
+        ));
+    }
+    validate_created_at(args)?;
+
+    if args.output.exists() && !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the {name} projection"
+        ));
+    }
+
+    // Project in memory first so an incompatible schema never reaches disk.
+    let files = match format {
+        PayloadSchemaFormat::Avro => {
+            crate::projection::avro::project_avro_in_memory(graph, contract)
+        }
+        PayloadSchemaFormat::JsonSchema => {
+            crate::projection::json_schema::project_json_schema_in_memory(
+                graph,
+                contract,
+                &args.input.display().to_string(),
+                args.created_at.clone(),
             )
-        })?;
+        }
+    }
+    .map_err(|e| anyhow::anyhow!("{name} projection failed: {e}"))?;
+
+    if let Some(published) = &args.published_schemas {
+        let mode: RegistryCompatibility = args.registry_compatibility.into();
+        let result = match format {
+            PayloadSchemaFormat::Avro => {
+                crate::projection::avro::check_compatibility(published, &files, mode)
+            }
+            PayloadSchemaFormat::JsonSchema => {
+                crate::projection::json_schema::check_compatibility(published, &files, mode)
+            }
+        }
+        .map_err(|e| anyhow::anyhow!("Registry compatibility check failed: {e}"))?;
+        println!("{}", result.to_report());
+        if !result.is_compatible() {
+            return Err(anyhow::anyhow!(
+                "Projected {label} are not {mode} compatible with {}. Use --registry-compatibility none to skip the check.",
+                published.display()
+            ));
+        }
+    }
+
+    out.create_dir()?;
+    let mut sink = out.sink();
+    for (path, content) in &files {
+        sink.write(path, content)
+            .map_err(|e| anyhow::anyhow!("{name} projection failed: {e}"))?;
+    }
+    report!(
+        out,
+        "Projected {label} to {} ({} files)",
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+fn run_sql_postgres(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
+    use crate::projection::sql_postgres::{diff, SqlSchema, SqlSchemaHistory};
+
+    if args.recipe.is_some() {
+        return Err(anyhow::anyhow!(
+            "--recipe is not used by --format sql-postgres (the projection is model-driven)"
+        ));
+    }
+    validate_created_at(args)?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for the sql-postgres projection"
+        ));
+    }
+
+    let history = args.schema_history.as_ref().map(SqlSchemaHistory::new);
+    let previous = history
+        .as_ref()
+        .map(SqlSchemaHistory::load)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Schema history failed: {e}"))?
+        .flatten();
+
+    let mut sink = out.sink();
+    let files = crate::projection::sql_postgres::emit(
+        graph,
+        contract,
+        &args.input.display().to_string(),
+        args.created_at.clone(),
+        previous.as_ref(),
+        &mut sink,
+    )
+    .map_err(|e| anyhow::anyhow!("sql-postgres projection failed: {e}"))?;
+    let schema = SqlSchema::build(graph, contract, previous.as_ref())
+        .map_err(|e| anyhow::anyhow!("sql-postgres projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected PostgreSQL schema v{} to {} ({} files)",
+        schema.version,
+        args.output.display(),
+        files.len()
+    );
+
+    if let Some(previous) = &previous {
+        let migration = diff(previous, &schema);
+        if !migration.is_empty() {
+            let breaking: Vec<_> = migration.breaking_steps().collect();
+            report!(
+                out,
+                "  Migration: v{} -> v{} ({} steps, {} breaking)",
+                migration.from_version,
+                migration.to_version,
+                migration.steps.len(),
+                breaking.len()
+            );
+            for step in breaking {
+                if let Some(reason) = &step.breaking {
+                    eprintln!("  BREAKING: {reason}");
+                }
+            }
+        }
+    }
+    if let Some(history) = history.as_ref().filter(|_| !out.is_check()) {
+        history
+            .save(&schema)
+            .map_err(|e| anyhow::anyhow!("Schema history failed: {e}"))?;
+    }
+    Ok(())
+}
+
+fn run_tla(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
+    if args.recipe.is_some() {
+        return Err(anyhow::anyhow!(
+            "--recipe is not used by --format tla (the projection is model-driven)"
+        ));
+    }
+    validate_created_at(args)?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the tla projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::tla::emit(
         graph,
         &args.input.display().to_string(),
@@ -898,7 +1726,8 @@ fn run_tla(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("tla projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected TLA+ spec to {} ({} files)",
         args.output.display(),
         files.len()
@@ -906,7 +1735,98 @@ fn run_tla(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_domain_python(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+/// Resolve the ADR-013 application contract of the input's source set.
+fn resolve_contract(
+    input: &Path,
+    source: &str,
+    options: &ParseOptions,
+) -> Result<crate::application::ApplicationContract> {
+    crate::application::resolve::resolve_filesystem_contract(
+        input,
+        source,
+        options.namespace_registry.as_ref(),
+        options.default_namespace.as_deref(),
+    )
+    .map_err(|diagnostics| {
+        anyhow::anyhow!(
+            "Application contract resolution failed for {}: {}",
+            input.display(),
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        )
+    })
+}
+
+fn run_plugin(
+    args: &ProjectArgs,
+    out: &mut Output,
+    model: &ResolvedInput,
+    name: &str,
+) -> Result<()> {
+    use crate::projection::plugin::{self, PluginRequest, PLUGIN_PROTOCOL_VERSION};
+
+    let options = args
+        .plugin_options
+        .iter()
+        .map(|option| {
+            option
+                .split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| anyhow::anyhow!("--plugin-option must be KEY=VALUE, got '{option}'"))
+        })
+        .collect::<Result<_>>()?;
+    let executable =
+        plugin::locate_plugin(name, &args.input).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    if !args.output.exists() {
+        out.create_dir()?;
+    } else if !args.output.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Output path must be a directory for plugin projections"
+        ));
+    }
+
+    let request = PluginRequest {
+        protocol: PLUGIN_PROTOCOL_VERSION,
+        plugin: name,
+        producer: crate::application::ProducerIdentity {
+            name: "domainforge-core".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        input: args.input.display().to_string(),
+        options,
+        namespace: args.namespace.as_deref(),
+        created_at: args.created_at.as_deref(),
+        graph: &model.graph,
+        application_contract: model.contract()?,
+        semantic_envelope: model.envelope()?,
+    };
+    let response = plugin::run_plugin(&executable, &request).map_err(|e| anyhow::anyhow!("{e}"))?;
+    for warning in &response.warnings {
+        eprintln!("Warning ({name}): {warning}");
+    }
+    let mut sink = out.sink();
+    let files = plugin::emit(&response, &mut sink)
+        .map_err(|e| anyhow::anyhow!("plugin:{name} projection failed: {e}"))?;
+    report!(
+        out,
+        "Projected with plugin '{}' to {} ({} files)",
+        name,
+        args.output.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+fn run_domain_python(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format domain-python (the projection is model-driven)"
@@ -915,27 +1835,23 @@ fn run_domain_python(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the domain-python projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::domain::python::emit(
         graph,
         &args.input.display().to_string(),
         args.created_at.clone(),
+        Some(contract),
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("domain-python projection failed: {e}"))?;
-    println!(
+    report!(out,
         "Projected Python DDD/CQRS domain layer to {} ({} files); validate with \
          `python -m compileall src tests && mypy --strict src && python -m unittest discover tests`",
         args.output.display(),
@@ -944,7 +1860,12 @@ fn run_domain_python(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<
     Ok(())
 }
 
-fn run_domain_typescript(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_domain_typescript(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format domain-typescript (the projection is model-driven)"
@@ -953,27 +1874,24 @@ fn run_domain_typescript(args: &ProjectArgs, graph: &crate::graph::Graph) -> Res
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the domain-typescript projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::domain::typescript::emit(
         graph,
         &args.input.display().to_string(),
         args.created_at.clone(),
+        Some(contract),
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("domain-typescript projection failed: {e}"))?;
-    println!(
+    report!(
+        out,
         "Projected TypeScript DDD/CQRS domain layer to {} ({} files); validate with `tsc --noEmit`",
         args.output.display(),
         files.len()
@@ -981,7 +1899,12 @@ fn run_domain_typescript(args: &ProjectArgs, graph: &crate::graph::Graph) -> Res
     Ok(())
 }
 
-fn run_domain_rust(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_domain_rust(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+    contract: &crate::application::ApplicationContract,
+) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format domain-rust (the projection is model-driven)"
@@ -990,27 +1913,23 @@ fn run_domain_rust(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
     validate_created_at(args)?;
 
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the domain-rust projection"
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::domain::rust::emit(
         graph,
         &args.input.display().to_string(),
         args.created_at.clone(),
+        Some(contract),
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("domain-rust projection failed: {e}"))?;
-    println!(
+    report!(out,
         "Projected Rust DDD/CQRS domain crate to {} ({} files); validate with `cargo check && cargo test`",
         args.output.display(),
         files.len()
@@ -1018,7 +1937,7 @@ fn run_domain_rust(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
     Ok(())
 }
 
-fn run_rdf(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_rdf(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     use crate::projection::rdf::{self, RdfOptions};
 
     if args.recipe.is_some() {
@@ -1034,12 +1953,7 @@ fn run_rdf(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, identical to the lean projection.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the rdf projection; \
@@ -1050,7 +1964,7 @@ fn run_rdf(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     let opts = RdfOptions {
         base_iri: args.base_iri.clone(),
     };
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = rdf::emit(
         graph,
         &args.input.display().to_string(),
@@ -1059,7 +1973,8 @@ fn run_rdf(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("rdf projection failed: {e}; see docs/rdf-projections.md"))?;
-    println!(
+    report!(
+        out,
         "Projected RDF dataset to {} ({} files); validate with `domainforge validate-kg {}`",
         args.output.display(),
         files.len(),
@@ -1068,7 +1983,7 @@ fn run_rdf(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_bpmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_bpmn(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format bpmn (the projection is model-driven); \
@@ -1082,12 +1997,7 @@ fn run_bpmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, identical to the lean and rdf projections.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the bpmn projection; \
@@ -1095,7 +2005,7 @@ fn run_bpmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::bpmn::emit(
         graph,
         &args.input.display().to_string(),
@@ -1103,7 +2013,8 @@ fn run_bpmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("bpmn projection failed: {e}; see docs/bpmn-projections.md"))?;
-    println!(
+    report!(
+        out,
         "Projected BPMN 2.0 process to {} ({} files); validate with \
          `xmllint --schema schemas/bpmn/BPMN20.xsd {}`",
         args.output.display(),
@@ -1113,7 +2024,7 @@ fn run_bpmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_cmmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_cmmn(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format cmmn (the projection is model-driven); \
@@ -1127,12 +2038,7 @@ fn run_cmmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, identical to the lean, rdf, and bpmn projections.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the cmmn projection; \
@@ -1140,7 +2046,7 @@ fn run_cmmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::cmmn::emit(
         graph,
         &args.input.display().to_string(),
@@ -1148,7 +2054,8 @@ fn run_cmmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
         &mut sink,
     )
     .map_err(|e| anyhow::anyhow!("cmmn projection failed: {e}; see docs/cmmn-projections.md"))?;
-    println!(
+    report!(
+        out,
         "Projected CMMN 1.1 case to {} ({} files); validate with \
          `xmllint --schema schemas/cmmn/CMMN11.xsd {}`",
         args.output.display(),
@@ -1158,7 +2065,7 @@ fn run_cmmn(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_archimate(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_archimate(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format archimate (the projection is model-driven); \
@@ -1172,12 +2079,7 @@ fn run_archimate(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()>
 
     // Directory output, identical to the lean, rdf, bpmn, and cmmn projections.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the archimate projection; \
@@ -1185,17 +2087,23 @@ fn run_archimate(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()>
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let opts = crate::projection::archimate::ArchimateOptions {
+        viewpoints: crate::projection::archimate::parse_viewpoints(&args.viewpoints)
+            .map_err(|e| anyhow::anyhow!("{e}"))?,
+    };
+    let mut sink = out.sink();
     let files = crate::projection::archimate::emit(
         graph,
         &args.input.display().to_string(),
         args.created_at.clone(),
+        &opts,
         &mut sink,
     )
     .map_err(|e| {
         anyhow::anyhow!("archimate projection failed: {e}; see docs/archimate-projections.md")
     })?;
-    println!(
+    report!(
+        out,
         "Projected ArchiMate model to {} ({} files); validate with \
          `xmllint --schema schemas/archimate/archimate3_Diagram.xsd {}`",
         args.output.display(),
@@ -1205,7 +2113,11 @@ fn run_archimate(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()>
     Ok(())
 }
 
-fn run_otel_semconv(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_otel_semconv(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+) -> Result<()> {
     if args.recipe.is_some() {
         return Err(anyhow::anyhow!(
             "--recipe is not used by --format otel-semconv (the projection is model-driven); \
@@ -1219,12 +2131,7 @@ fn run_otel_semconv(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<(
 
     // Directory output, identical to the lean, rdf, bpmn, cmmn, and archimate projections.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the otel-semconv projection; \
@@ -1232,7 +2139,7 @@ fn run_otel_semconv(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<(
         ));
     }
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::otel::emit(
         graph,
         &args.input.display().to_string(),
@@ -1242,7 +2149,8 @@ fn run_otel_semconv(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<(
     .map_err(|e| {
         anyhow::anyhow!("otel-semconv projection failed: {e}; see docs/otel-projections.md")
     })?;
-    println!(
+    report!(
+        out,
         "Projected OTel SemConv registry to {} ({} files); the registry is \
          {}",
         args.output.display(),
@@ -1252,7 +2160,7 @@ fn run_otel_semconv(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<(
     Ok(())
 }
 
-fn run_baml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_baml(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     use crate::projection::ai_learning::{self, recipe::Recipe};
 
     if let Some(ref ts) = args.created_at {
@@ -1309,12 +2217,7 @@ fn run_baml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, identical to the other projection families.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the baml projection; \
@@ -1334,11 +2237,12 @@ fn run_baml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     )
     .map_err(|e| anyhow::anyhow!(e))?;
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::baml::emit(&ctx, &mut sink).map_err(|e| {
         anyhow::anyhow!("baml projection failed: {e}; see docs/baml-projections.md")
     })?;
-    println!(
+    report!(
+        out,
         "Projected BAML capability to {} ({} files); fill in a client in \
          baml_src/clients.baml, then run `baml-cli generate`",
         args.output.display(),
@@ -1347,7 +2251,7 @@ fn run_baml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_dspy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_dspy(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     use crate::projection::ai_learning::{self, recipe::Recipe};
 
     if let Some(ref ts) = args.created_at {
@@ -1404,12 +2308,7 @@ fn run_dspy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, identical to the other projection families.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the dspy projection; \
@@ -1429,11 +2328,12 @@ fn run_dspy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     )
     .map_err(|e| anyhow::anyhow!(e))?;
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::dspy::emit(&ctx, &mut sink).map_err(|e| {
         anyhow::anyhow!("dspy projection failed: {e}; see docs/dspy-projections.md")
     })?;
-    println!(
+    report!(
+        out,
         "Projected DSPy program to {} ({} files); generate the ai-learning dataset it \
          references, then run `python optimize.py`",
         args.output.display(),
@@ -1442,7 +2342,7 @@ fn run_dspy(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_zenml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_zenml(args: &ProjectArgs, out: &mut Output, graph: &crate::graph::Graph) -> Result<()> {
     use crate::projection::ai_learning::{self, recipe::Recipe};
 
     if let Some(ref ts) = args.created_at {
@@ -1499,12 +2399,7 @@ fn run_zenml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
 
     // Directory output, identical to the other projection families.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for the zenml projection; \
@@ -1524,11 +2419,12 @@ fn run_zenml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     )
     .map_err(|e| anyhow::anyhow!(e))?;
 
-    let mut sink = crate::projection::sink::ArtifactSink::Dir(&args.output);
+    let mut sink = out.sink();
     let files = crate::projection::zenml::emit(&ctx, &mut sink).map_err(|e| {
         anyhow::anyhow!("zenml projection failed: {e}; see docs/zenml-projections.md")
     })?;
-    println!(
+    report!(
+        out,
         "Projected ZenML pipeline to {} ({} files); generate the ai-learning dataset it \
          references, then run `python run.py --dry-run`",
         args.output.display(),
@@ -1537,7 +2433,11 @@ fn run_zenml(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
     Ok(())
 }
 
-fn run_ai_learning(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()> {
+fn run_ai_learning(
+    args: &ProjectArgs,
+    out: &mut Output,
+    graph: &crate::graph::Graph,
+) -> Result<()> {
     use crate::projection::ai_learning::{self, recipe::Recipe};
 
     if let Some(ref ts) = args.created_at {
@@ -1613,12 +2513,7 @@ fn run_ai_learning(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
 
     // Directory output, like protobuf --multi-file.
     if !args.output.exists() {
-        std::fs::create_dir_all(&args.output).with_context(|| {
-            format!(
-                "Failed to create output directory {}",
-                args.output.display()
-            )
-        })?;
+        out.create_dir()?;
     } else if !args.output.is_dir() {
         return Err(anyhow::anyhow!(
             "Output path must be a directory for ai-* projections"
@@ -1648,8 +2543,7 @@ fn run_ai_learning(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
         } else {
             args.output.join(subdir)
         };
-        std::fs::create_dir_all(&dir)?;
-        let mut sink = ai_learning::ArtifactSink::Dir(&dir);
+        let mut sink = out.sink_at(&dir)?;
         let artifacts =
             emit(&ctx, &mut sink).map_err(|e| anyhow::anyhow!("{kind} projection failed: {e}"))?;
         emitted.push((kind, artifacts.len()));
@@ -1678,9 +2572,13 @@ fn run_ai_learning(args: &ProjectArgs, graph: &crate::graph::Graph) -> Result<()
         _ => unreachable!("run_ai_learning called for non-ai format"),
     }
 
-    println!("Projected AI learning artifacts: {}", args.output.display());
+    report!(
+        out,
+        "Projected AI learning artifacts: {}",
+        args.output.display()
+    );
     for (kind, count) in emitted {
-        println!("  {kind}: {count} artifacts");
+        report!(out, "  {kind}: {count} artifacts");
     }
     Ok(())
 }
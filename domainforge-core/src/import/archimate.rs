@@ -0,0 +1,554 @@
+//! ArchiMate importer: reads an Open Group ArchiMate Model Exchange File
+//! (Archi, BiZZdesign, or this crate's own `archimate` projection) and writes
+//! the SEA model its business layer describes.
+//!
+//! Mapping:
+//! - `BusinessActor` → `Entity`
+//! - `BusinessRole` → `Role`
+//! - `BusinessObject`, `Contract`, `Representation` → `Resource`, or `Entity`
+//!   when it is the source or target of a flow (the projection writes
+//!   entities as business objects too)
+//! - `BusinessProcess`, `BusinessFunction`, `BusinessInteraction` → `Flow`.
+//!   Its ends, resource and quantity are read from the
+//!   `SEA flow of R from A to B quantity Q` documentation the projection
+//!   writes; otherwise the ends are the object
+//!   it reads and the object it writes (else the `A to B` of its name), and
+//!   the resource is another object it accesses, else [`HANDOFF_RESOURCE`]
+//! - a `Flow` relationship between two entities → `Flow` of the resource it
+//!   is named after, else [`HANDOFF_RESOURCE`]
+//!
+//! `Access` and `Triggering` relationships between imported concepts are
+//! consumed (SEA flow chains are implicit). Requirements and the other
+//! motivation elements, the application, technology, strategy and
+//! implementation layers, role assignments (SEA roles cannot be bound to
+//! entities), properties, and views are listed in the report, and so are the
+//! flows whose quantity the file does not record.
+
+use super::bpmn::HANDOFF_RESOURCE;
+use super::{display_name, print_sea, ImportReport, SeaImport, UnmappedElement};
+use crate::kg_import::ImportError;
+use crate::parser::ast::AstNode;
+use crate::projection::ids::{ident, slug};
+use roxmltree::{Document, Node};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+
+const ARCHIMATE_NS: &str = "http://www.opengroup.org/xsd/archimate/3.0/";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+const OBJECTS: [&str; 3] = ["BusinessObject", "Contract", "Representation"];
+const BEHAVIOURS: [&str; 3] = ["BusinessProcess", "BusinessFunction", "BusinessInteraction"];
+
+fn is_archimate(node: Node, name: &str) -> bool {
+    node.tag_name().namespace() == Some(ARCHIMATE_NS) && node.tag_name().name() == name
+}
+
+fn xsi_type<'a>(node: Node<'a, 'a>) -> &'a str {
+    node.attribute((XSI_NS, "type")).unwrap_or_default()
+}
+
+/// The first `<name>` (or `<documentation>`) child's text.
+fn child_text(node: Node, name: &str) -> Option<String> {
+    node.children()
+        .filter(|c| is_archimate(*c, name))
+        .filter_map(|c| c.text())
+        .map(display_name)
+        .find(|t| !t.is_empty())
+}
+
+fn name_of(node: Node) -> Option<String> {
+    child_text(node, "name")
+}
+
+fn identifier<'a>(node: Node<'a, 'a>) -> &'a str {
+    node.attribute("identifier").unwrap_or_default()
+}
+
+/// Display name, falling back to the identifier.
+fn label(node: Node) -> String {
+    name_of(node).unwrap_or_else(|| identifier(node).to_string())
+}
+
+fn unmapped(node: Node, kind: &str, reason: &str) -> UnmappedElement {
+    UnmappedElement {
+        id: identifier(node).to_string(),
+        kind: kind.to_string(),
+        name: name_of(node),
+        reason: reason.to_string(),
+    }
+}
+
+struct Relationship<'a> {
+    node: Node<'a, 'a>,
+    kind: &'a str,
+    source: &'a str,
+    target: &'a str,
+}
+
+/// Import an ArchiMate Model Exchange File as SEA source under `namespace`
+/// (default: the namespace a projected file records, else the model's name).
+pub fn import_archimate(xml: &str, namespace: Option<&str>) -> Result<SeaImport, ImportError> {
+    let doc = Document::parse(xml)
+        .map_err(|e| ImportError::Other(format!("Failed to parse ArchiMate XML: {e}")))?;
+    let root = doc.root_element();
+    if !is_archimate(root, "model") {
+        return Err(ImportError::Other(format!(
+            "Not an ArchiMate Model Exchange File: root element is `{}`, expected `model` in {ARCHIMATE_NS}",
+            root.tag_name().name()
+        )));
+    }
+    let namespace = namespace
+        .map(str::to_string)
+        .or_else(|| {
+            child_text(root, "documentation")
+                .and_then(|d| d.strip_prefix("SEA namespace ").map(str::to_string))
+        })
+        .or_else(|| name_of(root))
+        .map_or_else(|| "imported".to_string(), |raw| ident(&slug(&raw)));
+
+    let section = |name: &str| -> Vec<Node> {
+        root.children()
+            .filter(|c| is_archimate(*c, name))
+            .flat_map(|s| s.children().filter(Node::is_element))
+            .collect()
+    };
+    let elements = section("elements");
+    let relationships: Vec<Relationship> = section("relationships")
+        .into_iter()
+        .map(|node| Relationship {
+            node,
+            kind: xsi_type(node),
+            source: node.attribute("source").unwrap_or_default(),
+            target: node.attribute("target").unwrap_or_default(),
+        })
+        .collect();
+    let by_id: HashMap<&str, Node> = elements.iter().map(|e| (identifier(*e), *e)).collect();
+    let kind_of = |id: &str| by_id.get(id).map_or("", |e| xsi_type(*e));
+
+    let mut report = ImportReport {
+        format: "ArchiMate Model Exchange File".to_string(),
+        ..ImportReport::default()
+    };
+    let mut entities: BTreeSet<String> = BTreeSet::new();
+    let mut roles: BTreeSet<String> = BTreeSet::new();
+    // Resource name → unit.
+    let mut resources: BTreeMap<String, String> = BTreeMap::new();
+    // (from, to, resource) → quantity, when the file records one.
+    let mut flows: BTreeMap<(String, String, String), Option<Decimal>> = BTreeMap::new();
+    // Elements a flow was imported from, and objects that are flow ends.
+    let mut imported_behaviour: BTreeSet<&str> = BTreeSet::new();
+    let mut endpoint_objects: BTreeSet<&str> = BTreeSet::new();
+    let mut handoff = false;
+
+    for element in &elements {
+        match xsi_type(*element) {
+            "BusinessActor" => {
+                entities.insert(label(*element));
+            }
+            "BusinessRole" => {
+                roles.insert(label(*element));
+            }
+            _ => {}
+        }
+    }
+    // Objects the projection documents as entities are entities even when no
+    // flow connects them.
+    for element in elements.iter().filter(|e| OBJECTS.contains(&xsi_type(**e))) {
+        if child_text(*element, "documentation").as_deref() == Some("SEA entity") {
+            entities.insert(label(*element));
+            endpoint_objects.insert(identifier(*element));
+        }
+    }
+
+    // Behaviour elements → flows.
+    let object_named = |name: &str| -> Option<&str> {
+        elements
+            .iter()
+            .find(|e| {
+                (OBJECTS.contains(&xsi_type(**e)) || xsi_type(**e) == "BusinessActor")
+                    && label(**e) == name
+            })
+            .map(|e| identifier(*e))
+    };
+    for element in elements
+        .iter()
+        .filter(|e| BEHAVIOURS.contains(&xsi_type(**e)))
+    {
+        let id = identifier(*element);
+        let documented = child_text(*element, "documentation").and_then(|d| {
+            let rest = d.strip_prefix("SEA flow of ")?;
+            let (rest, quantity) = match rest.rsplit_once(" quantity ") {
+                Some((rest, quantity)) => (rest, Decimal::from_str(quantity).ok()),
+                None => (rest, None),
+            };
+            let (resource, ends) = rest.split_once(" from ")?;
+            let (from, to) = ends.split_once(" to ")?;
+            Some((
+                from.to_string(),
+                to.to_string(),
+                resource.to_string(),
+                quantity,
+            ))
+        });
+        if let Some((from, to, resource, quantity)) = documented {
+            for end in [&from, &to] {
+                if let Some(object) = object_named(end) {
+                    endpoint_objects.insert(object);
+                }
+                entities.insert(end.clone());
+            }
+            imported_behaviour.insert(id);
+            flows.insert((from, to, resource), quantity);
+            continue;
+        }
+
+        // Objects the behaviour accesses, by access type.
+        let mut reads = Vec::new();
+        let mut writes = Vec::new();
+        let mut accessed = Vec::new();
+        for rel in relationships
+            .iter()
+            .filter(|r| r.kind == "Access" && r.source == id)
+            .filter(|r| OBJECTS.contains(&kind_of(r.target)))
+        {
+            match rel.node.attribute("accessType") {
+                Some("Read") => reads.push(rel.target),
+                Some("Write") => writes.push(rel.target),
+                _ => {}
+            }
+            accessed.push(rel.target);
+        }
+        let name = label(*element);
+        let ends = match (reads.as_slice(), writes.as_slice()) {
+            ([from], [to]) if from != to => Some((*from, *to)),
+            _ => name.split_once(" to ").and_then(|(from, to)| {
+                Some((object_named(from.trim())?, object_named(to.trim())?))
+            }),
+        };
+        let Some((from, to)) = ends else {
+            report.unmapped.push(unmapped(
+                *element,
+                xsi_type(*element),
+                "its ends are unknown: it neither reads one object and writes another nor is named `A to B` after two objects",
+            ));
+            continue;
+        };
+        let resource = accessed
+            .iter()
+            .filter(|o| **o != from && **o != to)
+            .map(|o| label(by_id[o]))
+            .min()
+            .unwrap_or_else(|| {
+                handoff = true;
+                HANDOFF_RESOURCE.to_string()
+            });
+        for end in [from, to] {
+            if OBJECTS.contains(&kind_of(end)) {
+                endpoint_objects.insert(end);
+            }
+            entities.insert(label(by_id[end]));
+        }
+        imported_behaviour.insert(id);
+        flows
+            .entry((label(by_id[from]), label(by_id[to]), resource))
+            .or_insert(None);
+    }
+
+    // Remaining objects are resources.
+    for element in elements.iter().filter(|e| OBJECTS.contains(&xsi_type(**e))) {
+        if endpoint_objects.contains(identifier(*element)) {
+            continue;
+        }
+        let unit = child_text(*element, "documentation")
+            .and_then(|d| {
+                d.strip_prefix("SEA resource measured in ")
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| "units".to_string());
+        resources.insert(label(*element), unit);
+    }
+
+    // Relationships.
+    let is_entity = |id: &str| {
+        kind_of(id) == "BusinessActor"
+            || (OBJECTS.contains(&kind_of(id)) && endpoint_objects.contains(id))
+    };
+    for rel in &relationships {
+        let consumed = match rel.kind {
+            "Access" => imported_behaviour.contains(rel.source),
+            "Triggering" => {
+                imported_behaviour.contains(rel.source) && imported_behaviour.contains(rel.target)
+            }
+            "Flow" if is_entity(rel.source) && is_entity(rel.target) => {
+                let resource = name_of(rel.node).unwrap_or_else(|| {
+                    handoff = true;
+                    HANDOFF_RESOURCE.to_string()
+                });
+                resources
+                    .entry(resource.clone())
+                    .or_insert_with(|| "units".to_string());
+                flows
+                    .entry((label(by_id[rel.source]), label(by_id[rel.target]), resource))
+                    .or_insert(None);
+                if rel.node.children().any(|c| is_archimate(c, "properties")) {
+                    report.unmapped.push(unmapped(
+                        rel.node,
+                        "properties",
+                        "flow properties are not imported",
+                    ));
+                }
+                true
+            }
+            _ => false,
+        };
+        if consumed {
+            continue;
+        }
+        let reason = match rel.kind {
+            "Assignment" if kind_of(rel.source) == "BusinessRole" => {
+                "role assignments are not imported; SEA roles cannot be bound to entities"
+            }
+            "Access" | "Triggering" => "an end was not imported as a flow",
+            _ if kind_of(rel.source) == "Requirement" || kind_of(rel.target) == "Requirement" => {
+                "requirements are not imported"
+            }
+            _ => "no SEA counterpart",
+        };
+        report.unmapped.push(unmapped(rel.node, rel.kind, reason));
+    }
+    if handoff {
+        resources
+            .entry(HANDOFF_RESOURCE.to_string())
+            .or_insert_with(|| "units".to_string());
+    }
+
+    // Elements with no SEA counterpart, then properties and views.
+    for element in &elements {
+        let kind = xsi_type(*element);
+        let mapped = kind == "BusinessActor"
+            || kind == "BusinessRole"
+            || OBJECTS.contains(&kind)
+            || imported_behaviour.contains(identifier(*element));
+        if !mapped && !BEHAVIOURS.contains(&kind) {
+            let reason = if kind == "Requirement" {
+                "policies need a condition expression, which a requirement does not carry"
+            } else {
+                "no SEA counterpart; only the business layer is imported"
+            };
+            report.unmapped.push(unmapped(*element, kind, reason));
+        }
+        if element.children().any(|c| is_archimate(c, "properties")) {
+            report.unmapped.push(unmapped(
+                *element,
+                "properties",
+                "element properties are not imported",
+            ));
+        }
+    }
+    for view in root.descendants().filter(|n| is_archimate(*n, "view")) {
+        report.unmapped.push(unmapped(
+            view,
+            "view",
+            "views are not imported; `project --format archimate --viewpoint` regenerates them",
+        ));
+    }
+
+    report.entities = entities.len();
+    report.roles = roles.len();
+    report.resources = resources.len();
+    report.flows = flows.len();
+
+    let mut declarations = Vec::new();
+    for name in entities {
+        declarations.push(AstNode::Entity {
+            name,
+            version: None,
+            annotations: HashMap::new(),
+            domain: Some(namespace.clone()),
+            body: None,
+        });
+    }
+    for name in roles {
+        declarations.push(AstNode::Role {
+            name,
+            domain: Some(namespace.clone()),
+        });
+    }
+    for (name, unit) in resources {
+        declarations.push(AstNode::Resource {
+            name,
+            annotations: HashMap::new(),
+            unit_name: Some(unit),
+            domain: Some(namespace.clone()),
+        });
+    }
+    for ((from_entity, to_entity, resource_name), quantity) in flows {
+        if quantity.is_none() {
+            report.review.push(format!(
+                "flow {resource_name} from {from_entity} to {to_entity} records no quantity; it is imported without one"
+            ));
+        }
+        declarations.push(AstNode::Flow {
+            resource_name,
+            annotations: HashMap::new(),
+            from_entity,
+            to_entity,
+            quantity,
+        });
+    }
+
+    Ok(SeaImport {
+        source: print_sea(&namespace, declarations),
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_to_graph;
+    use crate::projection::archimate::ir::Viewpoint;
+    use crate::projection::archimate::project_archimate_in_memory;
+
+    const SOURCE: &str = r#"@namespace "demo"
+
+Entity "Invoice"
+    in demo
+
+Entity "Ledger"
+    in demo
+
+Entity "PurchaseOrder"
+    in demo
+
+Role "Approver" in demo
+
+Resource "Budget" kg in demo
+
+Flow "Budget" from "Invoice" to "Ledger"
+
+Flow "Budget" from "PurchaseOrder" to "Invoice"
+"#;
+
+    #[test]
+    fn projected_model_imports_back_to_the_same_source() {
+        let graph = parse_to_graph(SOURCE).unwrap();
+        let files = project_archimate_in_memory(
+            &graph,
+            "demo.sea",
+            Some("2026-07-02T00:00:00+00:00".to_string()),
+            vec![Viewpoint::Organization],
+        )
+        .unwrap();
+        let import = import_archimate(&files["model.xml"], Some("demo")).unwrap();
+        assert_eq!(import.source, SOURCE);
+        let kinds: Vec<&str> = import
+            .report
+            .unmapped
+            .iter()
+            .map(|u| u.kind.as_str())
+            .collect();
+        assert_eq!(kinds, ["view", "view"]);
+    }
+
+    #[test]
+    fn projected_model_keeps_its_namespace_and_flow_quantities() {
+        let source = r#"@namespace "fin"
+
+Entity "Bank"
+    in fin
+
+Entity "Client"
+    in fin
+
+Resource "Cash" units in fin
+
+Flow "Cash" from "Bank" to "Client" quantity 10
+
+Flow "Cash" from "Client" to "Bank"
+"#;
+        let graph = parse_to_graph(source).unwrap();
+        let files = project_archimate_in_memory(&graph, "p.sea", None, vec![]).unwrap();
+        let import = import_archimate(&files["model.xml"], None).unwrap();
+        assert_eq!(import.source, source);
+        assert_eq!(
+            import.report.review,
+            ["flow Cash from Client to Bank records no quantity; it is imported without one"]
+        );
+    }
+
+    #[test]
+    fn maps_business_layer_elements_from_other_tools() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model xmlns="http://www.opengroup.org/xsd/archimate/3.0/"
+       xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" identifier="m">
+  <name xml:lang="en">Order Handling</name>
+  <elements>
+    <element identifier="a1" xsi:type="BusinessActor"><name xml:lang="en">Customer</name></element>
+    <element identifier="a2" xsi:type="BusinessActor"><name xml:lang="en">Warehouse</name></element>
+    <element identifier="r1" xsi:type="BusinessRole"><name xml:lang="en">Picker</name></element>
+    <element identifier="o1" xsi:type="BusinessObject"><name xml:lang="en">Order</name></element>
+    <element identifier="o2" xsi:type="BusinessObject"><name xml:lang="en">Shipment</name></element>
+    <element identifier="o3" xsi:type="Contract"><name xml:lang="en">Parcel</name></element>
+    <element identifier="p1" xsi:type="BusinessProcess"><name xml:lang="en">Ship order</name></element>
+    <element identifier="p2" xsi:type="BusinessProcess"><name xml:lang="en">Audit</name></element>
+    <element identifier="q1" xsi:type="Requirement"><name xml:lang="en">Ship within a day</name></element>
+    <element identifier="x1" xsi:type="ApplicationComponent"><name xml:lang="en">ERP</name>
+      <properties><property propertyDefinitionRef="pd"><value xml:lang="en">v2</value></property></properties>
+    </element>
+  </elements>
+  <relationships>
+    <relationship identifier="f1" source="a1" target="a2" xsi:type="Flow"><name xml:lang="en">Payment</name></relationship>
+    <relationship identifier="c1" source="p1" target="o1" xsi:type="Access" accessType="Read"/>
+    <relationship identifier="c2" source="p1" target="o2" xsi:type="Access" accessType="Write"/>
+    <relationship identifier="c3" source="p1" target="o3" xsi:type="Access"/>
+    <relationship identifier="s1" source="r1" target="p1" xsi:type="Assignment"/>
+    <relationship identifier="s2" source="q1" target="p1" xsi:type="Association"/>
+  </relationships>
+</model>"#;
+        let import = import_archimate(xml, None).unwrap();
+        assert!(import.source.starts_with("@namespace \"order_handling\""));
+        assert!(import
+            .source
+            .contains("Flow \"Parcel\" from \"Order\" to \"Shipment\""));
+        assert!(import
+            .source
+            .contains("Flow \"Payment\" from \"Customer\" to \"Warehouse\""));
+        assert!(import.source.contains("Role \"Picker\" in order_handling"));
+        let graph = parse_to_graph(&import.source).unwrap();
+        assert_eq!(
+            (
+                graph.entity_count(),
+                graph.resource_count(),
+                graph.flow_count()
+            ),
+            (4, 2, 2)
+        );
+        let unmapped: Vec<(&str, &str)> = import
+            .report
+            .unmapped
+            .iter()
+            .map(|u| (u.kind.as_str(), u.id.as_str()))
+            .collect();
+        assert_eq!(
+            unmapped,
+            [
+                ("BusinessProcess", "p2"),
+                ("Assignment", "s1"),
+                ("Association", "s2"),
+                ("Requirement", "q1"),
+                ("ApplicationComponent", "x1"),
+                ("properties", "x1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_other_xml() {
+        let err = import_archimate("<definitions/>", None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Not an ArchiMate Model Exchange File"));
+    }
+}
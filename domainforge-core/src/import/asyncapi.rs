@@ -0,0 +1,451 @@
+//! AsyncAPI importer: reads an AsyncAPI 2.x or 3.0 document (JSON or YAML)
+//! and writes the event flows it describes.
+//!
+//! Mapping:
+//! - the application (`info.title`) → `Entity`
+//! - each channel → `Entity` named after its address, standing for the
+//!   parties on the other side of the broker
+//! - each message → `Resource` named after its `name` (else its key)
+//! - a message the application sends (2.x `subscribe`, 3.0 `send`) → `Flow`
+//!   from the application to the channel; one it receives (2.x `publish`,
+//!   3.0 `receive`) → `Flow` from the channel to the application
+//! - `components.schemas` and inline object payloads → `record`/`enum`
+//!   (see [`super::schema`])
+//!
+//! Servers, bindings, channel parameters, message headers and correlation
+//! ids, non-JSON-Schema payloads, and 3.0 replies have no SEA counterpart and
+//! are listed in the report.
+
+use super::schema::{
+    deref, entries, is_object, key_text, parse_document, pointer_segment, text, SchemaImporter,
+};
+use super::{display_name, print_sea, ImportReport, SeaImport, UnmappedElement};
+use crate::kg_import::ImportError;
+use crate::parser::ast::AstNode;
+use crate::projection::ids::{ident, pascal, slug};
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Payload schema formats read as JSON Schema; anything else (Avro, RAML,
+/// Protobuf) is reported.
+const JSON_SCHEMA_FORMATS: [&str; 3] = [
+    "application/vnd.aai.asyncapi",
+    "application/schema+json",
+    "application/schema+yaml",
+];
+
+struct Importer<'a> {
+    doc: &'a Value,
+    schemas: SchemaImporter<'a>,
+    resources: BTreeSet<String>,
+    /// Message pointer → resource name, so shared messages convert once.
+    messages: HashMap<String, String>,
+    reported: HashSet<String>,
+}
+
+/// Import an AsyncAPI 2.x or 3.0 document as SEA source under `namespace`
+/// (default: the document's `info.title`).
+pub fn import_asyncapi(source: &str, namespace: Option<&str>) -> Result<SeaImport, ImportError> {
+    let doc = parse_document(source, "AsyncAPI")?;
+    let version = doc.get("asyncapi").map(key_text).unwrap_or_default();
+    if !version.starts_with("2.") && !version.starts_with("3.") {
+        return Err(ImportError::Other(format!(
+            "Not an AsyncAPI 2.x or 3.0 document: `asyncapi` is `{version}`"
+        )));
+    }
+    let title = doc
+        .get("info")
+        .and_then(|i| text(i, "title"))
+        .map(display_name)
+        .filter(|t| !t.is_empty());
+    let namespace = namespace
+        .map(str::to_string)
+        .or_else(|| title.clone())
+        .map_or_else(|| "imported".to_string(), |raw| ident(&slug(&raw)));
+    let application = title.unwrap_or_else(|| "Application".to_string());
+
+    let mut importer = Importer {
+        doc: &doc,
+        schemas: SchemaImporter::new(&doc),
+        resources: BTreeSet::new(),
+        messages: HashMap::new(),
+        reported: HashSet::new(),
+    };
+    importer.schemas.import_components("components/schemas");
+    for (name, _) in doc.get("servers").map(entries).unwrap_or_default() {
+        importer.schemas.unmapped.push(UnmappedElement {
+            id: format!("#/servers/{}", pointer_segment(&name)),
+            kind: "server".to_string(),
+            name: Some(name),
+            reason: "brokers are deployment details; not imported".to_string(),
+        });
+    }
+
+    let mut entities = BTreeSet::from([application.clone()]);
+    let mut flows = BTreeSet::new();
+    let mut channels: HashMap<String, String> = HashMap::new();
+    for (key, channel) in doc.get("channels").map(entries).unwrap_or_default() {
+        let pointer = format!("#/channels/{}", pointer_segment(&key));
+        let address = text(channel, "address").unwrap_or(&key).to_string();
+        importer.report_keywords(channel, &pointer, &["parameters", "bindings"]);
+        entities.insert(address.clone());
+        channels.insert(key.clone(), address.clone());
+        if version.starts_with("3.") {
+            continue;
+        }
+        // 2.x operations describe the application: `subscribe` is what it
+        // sends, `publish` what it receives.
+        for (verb, sends) in [("subscribe", true), ("publish", false)] {
+            let Some(operation) = channel.get(verb) else {
+                continue;
+            };
+            let op_pointer = format!("{pointer}/{verb}");
+            importer.report_keywords(operation, &op_pointer, &["bindings"]);
+            let Some(message) = operation.get("message") else {
+                continue;
+            };
+            let message_pointer = format!("{op_pointer}/message");
+            let messages: Vec<(&Value, String)> =
+                match message.get("oneOf").and_then(Value::as_sequence) {
+                    Some(options) => options
+                        .iter()
+                        .enumerate()
+                        .map(|(i, m)| (m, format!("{message_pointer}/oneOf/{i}")))
+                        .collect(),
+                    None => vec![(message, message_pointer)],
+                };
+            for (message, at) in messages {
+                let resource = importer.message(message, &at, &address);
+                flows.insert(flow(&application, &address, resource, sends));
+            }
+        }
+    }
+    if version.starts_with("3.") {
+        for (key, operation) in doc.get("operations").map(entries).unwrap_or_default() {
+            let pointer = format!("#/operations/{}", pointer_segment(&key));
+            importer.report_keywords(operation, &pointer, &["bindings", "reply"]);
+            let sends = match text(operation, "action") {
+                Some("send") => true,
+                Some("receive") => false,
+                _ => {
+                    importer.schemas.report(
+                        &pointer,
+                        "operation",
+                        "an operation without a send or receive action has no flow direction",
+                    );
+                    continue;
+                }
+            };
+            let channel_ref = operation
+                .get("channel")
+                .and_then(|c| text(c, "$ref"))
+                .unwrap_or_default();
+            let channel_key = channel_ref
+                .strip_prefix("#/channels/")
+                .map(|k| k.replace("~1", "/").replace("~0", "~"));
+            let Some(address) = channel_key.and_then(|k| channels.get(&k).cloned()) else {
+                importer.schemas.report(
+                    &pointer,
+                    "operation",
+                    "the operation's channel does not resolve",
+                );
+                continue;
+            };
+            let listed = operation.get("messages").and_then(Value::as_sequence);
+            let messages: Vec<(&Value, String)> = match listed {
+                Some(list) if !list.is_empty() => list
+                    .iter()
+                    .enumerate()
+                    .map(|(i, m)| (m, format!("{pointer}/messages/{i}")))
+                    .collect(),
+                // No list means every message of the channel.
+                _ => deref(&doc, operation.get("channel").unwrap_or(&Value::Null))
+                    .get("messages")
+                    .map(entries)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, m)| {
+                        (
+                            m,
+                            format!("{channel_ref}/messages/{}", pointer_segment(&name)),
+                        )
+                    })
+                    .collect(),
+            };
+            for (message, at) in messages {
+                let resource = importer.message(message, &at, &address);
+                flows.insert(flow(&application, &address, resource, sends));
+            }
+        }
+    }
+
+    let Importer {
+        schemas, resources, ..
+    } = importer;
+    let mut report = ImportReport {
+        format: format!("AsyncAPI {version}"),
+        entities: entities.len(),
+        resources: resources.len(),
+        flows: flows.len(),
+        records: schemas.record_count(),
+        enums: schemas.enum_count(),
+        ..ImportReport::default()
+    };
+    let (schema_declarations, unmapped, review) = schemas.into_declarations();
+    report.unmapped = unmapped;
+    report.review = review;
+
+    let mut declarations: Vec<AstNode> = entities
+        .into_iter()
+        .map(|name| AstNode::Entity {
+            name,
+            version: None,
+            annotations: HashMap::new(),
+            domain: Some(namespace.clone()),
+            body: None,
+        })
+        .collect();
+    declarations.extend(resources.into_iter().map(|name| AstNode::Resource {
+        name,
+        annotations: HashMap::new(),
+        unit_name: Some("units".to_string()),
+        domain: Some(namespace.clone()),
+    }));
+    declarations.extend(
+        flows
+            .into_iter()
+            .map(|(from_entity, to_entity, resource_name)| AstNode::Flow {
+                resource_name,
+                annotations: HashMap::new(),
+                from_entity,
+                to_entity,
+                quantity: None,
+            }),
+    );
+    declarations.extend(schema_declarations);
+
+    Ok(SeaImport {
+        source: print_sea(&namespace, declarations),
+        report,
+    })
+}
+
+fn flow(
+    application: &str,
+    channel: &str,
+    resource: String,
+    sends: bool,
+) -> (String, String, String) {
+    if sends {
+        (application.to_string(), channel.to_string(), resource)
+    } else {
+        (channel.to_string(), application.to_string(), resource)
+    }
+}
+
+impl Importer<'_> {
+    /// Report each of `keywords` present on `node` once.
+    fn report_keywords(&mut self, node: &Value, pointer: &str, keywords: &[&str]) {
+        for keyword in keywords {
+            if node.get(*keyword).is_none() {
+                continue;
+            }
+            let at = format!("{pointer}/{keyword}");
+            if self.reported.insert(at.clone()) {
+                let reason = match *keyword {
+                    "parameters" => "channel address parameters have no SEA counterpart",
+                    "bindings" => "protocol bindings are deployment details; not imported",
+                    "reply" => {
+                        "request/reply has no flow counterpart; only the request is imported"
+                    }
+                    "headers" => "message headers are not imported; only the payload is",
+                    _ => "correlation ids have no SEA counterpart",
+                };
+                self.schemas.report(&at, keyword, reason);
+            }
+        }
+    }
+
+    /// The resource a message maps to, converting its payload on first use.
+    fn message(&mut self, message: &Value, pointer: &str, channel: &str) -> String {
+        let doc = self.doc;
+        let (pointer, key) = match text(message, "$ref") {
+            Some(reference) => (
+                reference.to_string(),
+                reference
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+                    .replace("~1", "/")
+                    .replace("~0", "~"),
+            ),
+            None => (pointer.to_string(), format!("{}Message", pascal(channel))),
+        };
+        if let Some(resource) = self.messages.get(&pointer) {
+            return resource.clone();
+        }
+        let message = deref(doc, message);
+        let resource = text(message, "name")
+            .or_else(|| text(message, "title"))
+            .map(display_name)
+            .filter(|n| !n.is_empty())
+            .unwrap_or(key);
+        self.messages.insert(pointer.clone(), resource.clone());
+        self.resources.insert(resource.clone());
+        self.report_keywords(message, &pointer, &["headers", "correlationId", "bindings"]);
+
+        let Some(payload) = message.get("payload") else {
+            return resource;
+        };
+        let at = format!("{pointer}/payload");
+        let format = text(message, "schemaFormat").unwrap_or("application/schema+json");
+        // 3.0 wraps non-default formats as `{schemaFormat, schema}`.
+        let (format, payload) = match (text(payload, "schemaFormat"), payload.get("schema")) {
+            (Some(inner), Some(schema)) => (inner, schema),
+            _ => (format, payload),
+        };
+        if !JSON_SCHEMA_FORMATS.iter().any(|f| format.starts_with(f)) {
+            self.schemas.report(
+                &at,
+                "payload",
+                format!("{format} payloads are not imported; only JSON Schema is"),
+            );
+            return resource;
+        }
+        match text(payload, "$ref") {
+            Some(reference) => {
+                self.schemas.component(reference);
+            }
+            None if is_object(payload) => {
+                self.schemas
+                    .inline_record(&format!("{}Payload", pascal(&resource)), payload, &at);
+            }
+            None => self.schemas.report(
+                &at,
+                "payload",
+                "a non-object payload has no record counterpart",
+            ),
+        }
+        resource
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_to_graph;
+
+    const SHOP_V3: &str = r#"
+asyncapi: 3.0.0
+info:
+  title: Shop
+  version: 1.0.0
+servers:
+  production: {host: broker.example.com, protocol: kafka}
+channels:
+  orderPlaced:
+    address: orders.placed
+    messages:
+      OrderPlaced: {$ref: '#/components/messages/OrderPlaced'}
+  payments:
+    address: payments.settled
+    messages:
+      PaymentSettled:
+        name: PaymentSettled
+        payload:
+          type: object
+          required: [amount]
+          properties:
+            amount: {type: number, exclusiveMinimum: 0}
+operations:
+  publishOrder:
+    action: send
+    channel: {$ref: '#/channels/orderPlaced'}
+  settle:
+    action: receive
+    channel: {$ref: '#/channels/payments'}
+    messages:
+      - $ref: '#/channels/payments/messages/PaymentSettled'
+    bindings: {kafka: {groupId: shop}}
+components:
+  messages:
+    OrderPlaced:
+      payload: {$ref: '#/components/schemas/Order'}
+      headers: {type: object}
+  schemas:
+    Order:
+      type: object
+      properties:
+        orderId: {type: string, format: uuid}
+"#;
+
+    #[test]
+    fn maps_v3_channels_to_flows_and_payloads_to_records() {
+        let import = import_asyncapi(SHOP_V3, None).unwrap();
+        assert_eq!(
+            import.source,
+            r#"@namespace "shop"
+
+Entity "Shop"
+    in shop
+
+Entity "orders.placed"
+    in shop
+
+Entity "payments.settled"
+    in shop
+
+Resource "OrderPlaced" units in shop
+
+Resource "PaymentSettled" units in shop
+
+Flow "OrderPlaced" from "Shop" to "orders.placed"
+
+Flow "PaymentSettled" from "payments.settled" to "Shop"
+
+record Order {
+    order_id: uuid optional
+}
+
+record PaymentSettledPayload {
+    amount: decimal (exclusive_min 0)
+}
+"#
+        );
+        let graph = parse_to_graph(&import.source).expect("imported SEA parses");
+        assert_eq!(graph.flow_count(), 2);
+        let kinds: Vec<&str> = import
+            .report
+            .unmapped
+            .iter()
+            .map(|u| u.kind.as_str())
+            .collect();
+        assert_eq!(kinds, ["server", "headers", "bindings"]);
+    }
+
+    #[test]
+    fn maps_v2_subscribe_as_send_and_publish_as_receive() {
+        let spec = r#"{
+  "asyncapi": "2.6.0",
+  "info": {"title": "Billing", "version": "1"},
+  "channels": {
+    "invoices": {"subscribe": {"message": {"name": "InvoiceIssued"}}},
+    "orders": {"publish": {"message": {"oneOf": [
+      {"name": "OrderPlaced"}, {"name": "OrderCancelled"}
+    ]}}}
+  }
+}"#;
+        let import = import_asyncapi(spec, None).unwrap();
+        assert!(import
+            .source
+            .contains("Flow \"InvoiceIssued\" from \"Billing\" to \"invoices\""));
+        assert!(import
+            .source
+            .contains("Flow \"OrderCancelled\" from \"orders\" to \"Billing\""));
+        assert!(import
+            .source
+            .contains("Flow \"OrderPlaced\" from \"orders\" to \"Billing\""));
+        assert_eq!(import.report.flows, 3);
+        assert!(import.report.unmapped.is_empty());
+    }
+}
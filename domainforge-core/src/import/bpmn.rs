@@ -0,0 +1,633 @@
+//! BPMN 2.0 importer: reads BPMN 2.0 XML (Camunda, bpmn.io, or this crate's
+//! own `bpmn` projection) and writes the SEA model it describes.
+//!
+//! Mapping:
+//! - `participant` → `Entity`
+//! - activities (`task` and its typed variants, `callActivity`,
+//!   `subProcess`, `transaction`, `adHocSubProcess`) → `Entity` named after
+//!   the activity
+//! - each participant and activity is its own entity: a name another one
+//!   already uses gets a numeric suffix (`Charge 2`), noted for review
+//! - `lane` → `Role`
+//! - `dataObject`, `dataStore`, and their references → `Resource`; a
+//!   reference and the object it references are one resource
+//! - `sequenceFlow` → `Flow` between the activities it connects, followed
+//!   through gateways and events; the flow carries a data object the source
+//!   writes and the target reads, else one the source writes, else the
+//!   synthesized [`HANDOFF_RESOURCE`]
+//! - `messageFlow` → `Flow` of its `message` (a `Resource`) between the
+//!   participants or activities it connects; an event end stands for the
+//!   participant whose pool holds it
+//!
+//! Events, gateways, flow conditions, lane membership (SEA declares roles but
+//! cannot bind them to entities), annotations, extension elements, and the
+//! diagram have no SEA counterpart and are listed in the report.
+
+use super::{display_name, print_sea, ImportReport, SeaImport, UnmappedElement};
+use crate::kg_import::ImportError;
+use crate::parser::ast::AstNode;
+use crate::projection::ids::{ident, slug};
+use roxmltree::{Document, Node};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+const BPMN_NS: &str = "http://www.omg.org/spec/BPMN/20100524/MODEL";
+const BPMNDI_NS: &str = "http://www.omg.org/spec/BPMN/20100524/DI";
+
+/// Resource carried by sequence flows that move no data object.
+pub const HANDOFF_RESOURCE: &str = "Handoff";
+/// Resource carried by message flows that name no message.
+pub const MESSAGE_RESOURCE: &str = "Message";
+
+const ACTIVITIES: [&str; 12] = [
+    "task",
+    "userTask",
+    "serviceTask",
+    "sendTask",
+    "receiveTask",
+    "manualTask",
+    "businessRuleTask",
+    "scriptTask",
+    "callActivity",
+    "subProcess",
+    "transaction",
+    "adHocSubProcess",
+];
+
+const DATA: [&str; 4] = [
+    "dataObject",
+    "dataStore",
+    "dataObjectReference",
+    "dataStoreReference",
+];
+
+/// Elements consumed by the mappings above; never reported.
+const STRUCTURAL: [&str; 14] = [
+    "definitions",
+    "process",
+    "collaboration",
+    "laneSet",
+    "childLaneSet",
+    "flowNodeRef",
+    "incoming",
+    "outgoing",
+    "sourceRef",
+    "targetRef",
+    "dataInputAssociation",
+    "dataOutputAssociation",
+    "sequenceFlow",
+    "message",
+];
+
+fn is_bpmn(node: Node, name: &str) -> bool {
+    node.tag_name().namespace() == Some(BPMN_NS) && node.tag_name().name() == name
+}
+
+fn name_of(node: Node) -> Option<String> {
+    node.attribute("name")
+        .map(display_name)
+        .filter(|n| !n.is_empty())
+}
+
+/// Display name, falling back to the id.
+fn label(node: Node) -> String {
+    name_of(node).unwrap_or_else(|| node.attribute("id").unwrap_or_default().to_string())
+}
+
+fn child_text<'a>(node: Node<'a, 'a>, name: &str) -> Vec<&'a str> {
+    node.children()
+        .filter(|c| is_bpmn(*c, name))
+        .filter_map(|c| c.text())
+        .map(str::trim)
+        .collect()
+}
+
+/// Id of the data object or store `node` stands for: the one a reference
+/// points at, else `node` itself.
+fn data_key<'a>(node: Node<'a, 'a>, by_id: &HashMap<&str, Node>) -> &'a str {
+    node.attribute("dataObjectRef")
+        .or_else(|| node.attribute("dataStoreRef"))
+        .filter(|r| by_id.contains_key(r))
+        .or_else(|| node.attribute("id"))
+        .unwrap_or_default()
+}
+
+/// Import BPMN 2.0 XML as SEA source under `namespace` (default: the
+/// definitions' or first process's name).
+pub fn import_bpmn(xml: &str, namespace: Option<&str>) -> Result<SeaImport, ImportError> {
+    let doc = Document::parse(xml)
+        .map_err(|e| ImportError::Other(format!("Failed to parse BPMN XML: {e}")))?;
+    let root = doc.root_element();
+    if !is_bpmn(root, "definitions") {
+        return Err(ImportError::Other(format!(
+            "Not a BPMN 2.0 document: root element is `{}`, expected `definitions` in {BPMN_NS}",
+            root.tag_name().name()
+        )));
+    }
+    let bpmn: Vec<Node> = root
+        .descendants()
+        .filter(|n| n.tag_name().namespace() == Some(BPMN_NS))
+        .collect();
+    let by_id: HashMap<&str, Node> = bpmn
+        .iter()
+        .filter_map(|n| n.attribute("id").map(|id| (id, *n)))
+        .collect();
+
+    let namespace = namespace
+        .map(str::to_string)
+        .or_else(|| name_of(root))
+        .or_else(|| {
+            bpmn.iter()
+                .find(|n| is_bpmn(**n, "process"))
+                .and_then(|p| name_of(*p))
+        })
+        .map_or_else(|| "imported".to_string(), |raw| ident(&slug(&raw)));
+
+    let mut report = ImportReport {
+        format: "BPMN 2.0".to_string(),
+        ..ImportReport::default()
+    };
+
+    // Each participant and activity is its own entity; a label another one
+    // already uses gets a numeric suffix.
+    let mut entities = BTreeSet::new();
+    let mut entity_of: HashMap<&str, String> = HashMap::new();
+    let mut roles = BTreeSet::new();
+    for node in &bpmn {
+        let kind = node.tag_name().name();
+        let id = node.attribute("id").unwrap_or_default();
+        if kind == "participant" || ACTIVITIES.contains(&kind) {
+            let base = label(*node);
+            let mut name = base.clone();
+            let mut n = 1;
+            while !entities.insert(name.clone()) {
+                n += 1;
+                name = format!("{base} {n}");
+            }
+            if name != base {
+                report.review.push(format!(
+                    "{kind} {id} shares the name \"{base}\" with another element; it is imported as \"{name}\""
+                ));
+            }
+            entity_of.insert(id, name);
+        } else if kind == "lane" {
+            roles.insert(label(*node));
+        }
+    }
+
+    // A data reference stands for the object or store it references, so both
+    // map to one resource: the object's name, else its first named
+    // reference's, else the object's id.
+    let data: Vec<Node> = bpmn
+        .iter()
+        .filter(|n| DATA.contains(&n.tag_name().name()))
+        .copied()
+        .collect();
+    let mut data_name: HashMap<&str, String> = HashMap::new();
+    for node in &data {
+        let key = data_key(*node, &by_id);
+        if !data_name.contains_key(key) {
+            if let Some(name) = by_id
+                .get(key)
+                .and_then(|o| name_of(*o))
+                .or_else(|| name_of(*node))
+            {
+                data_name.insert(key, name);
+            }
+        }
+    }
+    let mut resources = BTreeSet::new();
+    let mut resource_of: HashMap<&str, String> = HashMap::new();
+    for node in &data {
+        let key = data_key(*node, &by_id);
+        let name = data_name
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| key.to_string());
+        resources.insert(name.clone());
+        resource_of.insert(node.attribute("id").unwrap_or_default(), name);
+    }
+    let mut flows: BTreeSet<(String, String, String)> = BTreeSet::new();
+
+    // Sequence flows, followed through every non-activity node.
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in bpmn.iter().filter(|n| is_bpmn(**n, "sequenceFlow")) {
+        if let (Some(source), Some(target)) =
+            (node.attribute("sourceRef"), node.attribute("targetRef"))
+        {
+            successors.entry(source).or_default().push(target);
+        }
+    }
+    let data_refs = |activity: Node, association: &str, end: &str| -> BTreeSet<String> {
+        activity
+            .children()
+            .filter(|c| is_bpmn(*c, association))
+            .flat_map(|a| child_text(a, end))
+            .filter_map(|r| resource_of.get(r).cloned())
+            .collect()
+    };
+    for node in bpmn
+        .iter()
+        .filter(|n| ACTIVITIES.contains(&n.tag_name().name()))
+    {
+        let source = node.attribute("id").unwrap_or_default();
+        let writes = data_refs(*node, "dataOutputAssociation", "targetRef");
+        let mut seen = BTreeSet::from([source]);
+        let mut queue: VecDeque<&str> = successors
+            .get(source)
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect();
+        while let Some(next) = queue.pop_front() {
+            if !seen.insert(next) {
+                continue;
+            }
+            match entity_of.get(next) {
+                Some(target) => {
+                    let reads = by_id
+                        .get(next)
+                        .map(|t| data_refs(*t, "dataInputAssociation", "sourceRef"))
+                        .unwrap_or_default();
+                    let resource = writes
+                        .intersection(&reads)
+                        .next()
+                        .or_else(|| writes.iter().next())
+                        .cloned()
+                        .unwrap_or_else(|| {
+                            resources.insert(HANDOFF_RESOURCE.to_string());
+                            HANDOFF_RESOURCE.to_string()
+                        });
+                    flows.insert((entity_of[source].clone(), target.clone(), resource));
+                }
+                None => queue.extend(successors.get(next).into_iter().flatten().copied()),
+            }
+        }
+    }
+
+    // Message flows: an end that is not itself an entity stands for the
+    // participant whose pool (process) contains it.
+    let pool_entity = |id: &str| -> Option<String> {
+        if let Some(entity) = entity_of.get(id) {
+            return Some(entity.clone());
+        }
+        let process = by_id
+            .get(id)?
+            .ancestors()
+            .find(|a| is_bpmn(*a, "process"))?
+            .attribute("id")?;
+        bpmn.iter()
+            .find(|p| is_bpmn(**p, "participant") && p.attribute("processRef") == Some(process))
+            .and_then(|p| entity_of.get(p.attribute("id")?).cloned())
+    };
+    for node in bpmn.iter().filter(|n| is_bpmn(**n, "messageFlow")) {
+        let ends = (
+            node.attribute("sourceRef").and_then(pool_entity),
+            node.attribute("targetRef").and_then(pool_entity),
+        );
+        let (Some(from), Some(to)) = ends else {
+            report.unmapped.push(unmapped(
+                *node,
+                "an end is neither a participant nor inside a participant's pool",
+            ));
+            continue;
+        };
+        let resource = node
+            .attribute("messageRef")
+            .and_then(|m| by_id.get(m))
+            .and_then(|m| name_of(*m))
+            .or_else(|| name_of(*node))
+            .unwrap_or_else(|| MESSAGE_RESOURCE.to_string());
+        resources.insert(resource.clone());
+        flows.insert((from, to, resource));
+    }
+
+    collect_unmapped(root, &mut report.unmapped);
+
+    report.entities = entities.len();
+    report.roles = roles.len();
+    report.resources = resources.len();
+    report.flows = flows.len();
+
+    let mut declarations = Vec::new();
+    for name in entities {
+        declarations.push(AstNode::Entity {
+            name,
+            version: None,
+            annotations: HashMap::new(),
+            domain: Some(namespace.clone()),
+            body: None,
+        });
+    }
+    for name in roles {
+        declarations.push(AstNode::Role {
+            name,
+            domain: Some(namespace.clone()),
+        });
+    }
+    for name in resources {
+        declarations.push(AstNode::Resource {
+            name,
+            annotations: HashMap::new(),
+            unit_name: Some("units".to_string()),
+            domain: Some(namespace.clone()),
+        });
+    }
+    for (from_entity, to_entity, resource_name) in flows {
+        declarations.push(AstNode::Flow {
+            resource_name,
+            annotations: HashMap::new(),
+            from_entity,
+            to_entity,
+            quantity: None,
+        });
+    }
+
+    Ok(SeaImport {
+        source: print_sea(&namespace, declarations),
+        report,
+    })
+}
+
+fn unmapped(node: Node, reason: &str) -> UnmappedElement {
+    UnmappedElement {
+        id: node.attribute("id").unwrap_or_default().to_string(),
+        kind: node.tag_name().name().to_string(),
+        name: name_of(node),
+        reason: reason.to_string(),
+    }
+}
+
+/// Report every element with no SEA counterpart, in document order; the
+/// children of a reported element are not reported again.
+fn collect_unmapped(node: Node, out: &mut Vec<UnmappedElement>) {
+    for child in node.children().filter(Node::is_element) {
+        let kind = child.tag_name().name();
+        match child.tag_name().namespace() {
+            Some(BPMN_NS) => {}
+            Some(BPMNDI_NS) if kind == "BPMNDiagram" => {
+                let mut element = unmapped(child, "diagram layout is not imported");
+                element.kind = "bpmndi:BPMNDiagram".to_string();
+                out.push(element);
+                continue;
+            }
+            _ => continue,
+        }
+        if kind == "lane" {
+            let members = child
+                .children()
+                .filter(|c| is_bpmn(*c, "flowNodeRef"))
+                .count();
+            if members > 0 {
+                out.push(unmapped(
+                    child,
+                    &format!(
+                        "lane membership ({members} flow node(s)) is not imported; SEA roles cannot be bound to entities"
+                    ),
+                ));
+            }
+        } else if !(kind == "participant"
+            || ACTIVITIES.contains(&kind)
+            || DATA.contains(&kind)
+            || STRUCTURAL.contains(&kind)
+            || kind == "messageFlow")
+        {
+            out.push(unmapped(child, reason(kind)));
+            continue;
+        }
+        collect_unmapped(child, out);
+    }
+}
+
+fn reason(kind: &str) -> &'static str {
+    if kind.ends_with("Event") {
+        "events have no SEA counterpart; flows are connected through them"
+    } else if kind.ends_with("Gateway") {
+        "gateway semantics are not imported; flows are connected through it"
+    } else if kind == "conditionExpression" {
+        "flow conditions have no SEA counterpart"
+    } else if kind == "documentation" {
+        "documentation is not imported"
+    } else if kind == "extensionElements" {
+        "vendor extensions are not imported"
+    } else {
+        "no SEA counterpart"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_to_graph;
+    use crate::projection::bpmn::project_bpmn_in_memory;
+
+    const ORDER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<bpmn:definitions xmlns:bpmn="http://www.omg.org/spec/BPMN/20100524/MODEL"
+    xmlns:bpmndi="http://www.omg.org/spec/BPMN/20100524/DI"
+    xmlns:camunda="http://camunda.org/schema/1.0/bpmn"
+    id="Defs" name="Order Fulfilment" targetNamespace="http://example.com">
+  <bpmn:message id="Msg_Invoice" name="Invoice"/>
+  <bpmn:collaboration id="Collab">
+    <bpmn:participant id="P_Shop" name="Shop" processRef="Proc"/>
+    <bpmn:participant id="P_Customer" name="Customer"/>
+    <bpmn:messageFlow id="MF_1" sourceRef="End" targetRef="P_Customer" messageRef="Msg_Invoice"/>
+  </bpmn:collaboration>
+  <bpmn:process id="Proc" isExecutable="true">
+    <bpmn:extensionElements><camunda:properties/></bpmn:extensionElements>
+    <bpmn:laneSet id="LS">
+      <bpmn:lane id="Lane_Clerk" name="Clerk">
+        <bpmn:flowNodeRef>Pick</bpmn:flowNodeRef>
+      </bpmn:lane>
+      <bpmn:lane id="Lane_Courier" name="Courier"/>
+    </bpmn:laneSet>
+    <bpmn:dataObject id="DO_Parcel" name="Parcel"/>
+    <bpmn:dataObjectReference id="DOR_Parcel" dataObjectRef="DO_Parcel"/>
+    <bpmn:startEvent id="Start"/>
+    <bpmn:userTask id="Pick" name="Pick&#10;items">
+      <bpmn:dataOutputAssociation id="DOA">
+        <bpmn:targetRef>DOR_Parcel</bpmn:targetRef>
+      </bpmn:dataOutputAssociation>
+    </bpmn:userTask>
+    <bpmn:exclusiveGateway id="Gw" name="Express?"/>
+    <bpmn:serviceTask id="Ship" name="Ship">
+      <bpmn:dataInputAssociation id="DIA">
+        <bpmn:sourceRef>DOR_Parcel</bpmn:sourceRef>
+      </bpmn:dataInputAssociation>
+    </bpmn:serviceTask>
+    <bpmn:task id="Notify" name="Notify"/>
+    <bpmn:endEvent id="End"/>
+    <bpmn:sequenceFlow id="F1" sourceRef="Start" targetRef="Pick"/>
+    <bpmn:sequenceFlow id="F2" sourceRef="Pick" targetRef="Gw"/>
+    <bpmn:sequenceFlow id="F3" sourceRef="Gw" targetRef="Ship">
+      <bpmn:conditionExpression>express</bpmn:conditionExpression>
+    </bpmn:sequenceFlow>
+    <bpmn:sequenceFlow id="F4" sourceRef="Gw" targetRef="Notify"/>
+    <bpmn:sequenceFlow id="F5" sourceRef="Ship" targetRef="End"/>
+    <bpmn:sequenceFlow id="F6" sourceRef="Notify" targetRef="End"/>
+  </bpmn:process>
+  <bpmndi:BPMNDiagram id="Diagram"/>
+</bpmn:definitions>
+"#;
+
+    #[test]
+    fn maps_bpmn_elements_to_sea_declarations() {
+        let import = import_bpmn(ORDER, None).unwrap();
+        assert_eq!(
+            import.source,
+            r#"@namespace "order_fulfilment"
+
+Entity "Customer"
+    in order_fulfilment
+
+Entity "Notify"
+    in order_fulfilment
+
+Entity "Pick items"
+    in order_fulfilment
+
+Entity "Ship"
+    in order_fulfilment
+
+Entity "Shop"
+    in order_fulfilment
+
+Role "Clerk" in order_fulfilment
+
+Role "Courier" in order_fulfilment
+
+Resource "Invoice" units in order_fulfilment
+
+Resource "Parcel" units in order_fulfilment
+
+Flow "Parcel" from "Pick items" to "Notify"
+
+Flow "Parcel" from "Pick items" to "Ship"
+
+Flow "Invoice" from "Shop" to "Customer"
+"#
+        );
+        let graph = parse_to_graph(&import.source).expect("imported SEA parses");
+        assert_eq!(graph.entity_count(), 5);
+        assert_eq!(graph.flow_count(), 3);
+    }
+
+    #[test]
+    fn reports_elements_without_sea_counterpart() {
+        let report = import_bpmn(ORDER, None).unwrap().report;
+        let kinds: Vec<_> = report.unmapped.iter().map(|u| u.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            [
+                "extensionElements",
+                "lane",
+                "startEvent",
+                "exclusiveGateway",
+                "endEvent",
+                "conditionExpression",
+                "bpmndi:BPMNDiagram"
+            ]
+        );
+        let text = report.to_report();
+        assert!(text.contains("entities=5 roles=2 resources=2 flows=3"));
+        assert!(text.contains("exclusiveGateway \"Express?\" (Gw): gateway semantics"));
+    }
+
+    #[test]
+    fn unresolvable_message_flows_are_reported() {
+        let xml = ORDER.replace(r#"processRef="Proc""#, "");
+        let import = import_bpmn(&xml, Some("shop")).unwrap();
+        assert!(import.source.starts_with("@namespace \"shop\""));
+        assert!(!import.source.contains("\"Invoice\" from"));
+        assert!(import
+            .report
+            .unmapped
+            .iter()
+            .any(|u| u.kind == "messageFlow" && u.id == "MF_1"));
+    }
+
+    const TWO_POOLS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<definitions xmlns="http://www.omg.org/spec/BPMN/20100524/MODEL" id="Defs" name="Billing">
+  <collaboration id="Collab">
+    <participant id="P_Shop" name="Shop" processRef="Shop_Proc"/>
+    <participant id="P_Bank" name="Bank" processRef="Bank_Proc"/>
+  </collaboration>
+  <process id="Shop_Proc">
+    <task id="Shop_Charge" name="Charge"/>
+    <task id="Ship" name="Ship"/>
+    <sequenceFlow id="F1" sourceRef="Shop_Charge" targetRef="Ship"/>
+  </process>
+  <process id="Bank_Proc">
+    <task id="Bank_Charge" name="Charge"/>
+    <task id="Settle" name="Settle"/>
+    <sequenceFlow id="F2" sourceRef="Bank_Charge" targetRef="Settle"/>
+  </process>
+</definitions>
+"#;
+
+    #[test]
+    fn same_named_activities_stay_distinct_entities() {
+        let import = import_bpmn(TWO_POOLS, None).unwrap();
+        assert!(import.source.contains("Entity \"Charge\""));
+        assert!(import.source.contains("Entity \"Charge 2\""));
+        assert!(import
+            .source
+            .contains("Flow \"Handoff\" from \"Charge\" to \"Ship\""));
+        assert!(import
+            .source
+            .contains("Flow \"Handoff\" from \"Charge 2\" to \"Settle\""));
+        assert_eq!(import.report.entities, 6);
+        assert_eq!(
+            import.report.review,
+            ["task Bank_Charge shares the name \"Charge\" with another element; it is imported as \"Charge 2\""]
+        );
+        parse_to_graph(&import.source).expect("imported SEA parses");
+    }
+
+    #[test]
+    fn a_reference_and_its_unnamed_object_are_one_resource() {
+        let xml = ORDER
+            .replace(r#"<bpmn:dataObject id="DO_Parcel" name="Parcel"/>"#, r#"<bpmn:dataObject id="DO_Parcel"/>"#)
+            .replace(r#"<bpmn:dataObjectReference id="DOR_Parcel" dataObjectRef="DO_Parcel"/>"#, r#"<bpmn:dataObjectReference id="DOR_Parcel" name="Parcel" dataObjectRef="DO_Parcel"/>"#);
+        let import = import_bpmn(&xml, None).unwrap();
+        assert_eq!(import.report.resources, 2);
+        assert!(import.source.contains("Resource \"Parcel\""));
+        assert!(!import.source.contains("DO_Parcel"));
+        assert!(import
+            .source
+            .contains("Flow \"Parcel\" from \"Pick items\" to \"Ship\""));
+    }
+
+    #[test]
+    fn rejects_non_bpmn_documents() {
+        assert!(import_bpmn("<definitions/>", None).is_err());
+        assert!(import_bpmn("not xml", None).is_err());
+    }
+
+    #[test]
+    fn projected_bpmn_imports_back_to_the_same_flows() {
+        let source = r#"@namespace "demo"
+Entity "Warehouse" in demo
+Entity "LineA" in demo
+Entity "LineB" in demo
+Entity "Assembly" in demo
+Role "Operator" in demo
+Resource "CameraUnits" units in demo
+Flow "CameraUnits" from "Warehouse" to "LineA" quantity 40
+Flow "CameraUnits" from "Warehouse" to "LineB" quantity 60
+Flow "CameraUnits" from "LineA" to "Assembly" quantity 40
+Flow "CameraUnits" from "LineB" to "Assembly" quantity 60
+"#;
+        let graph = parse_to_graph(source).unwrap();
+        let files = project_bpmn_in_memory(&graph, "demo.sea", None).unwrap();
+        let import = import_bpmn(&files["model.bpmn"], Some("demo")).unwrap();
+        let imported = parse_to_graph(&import.source).unwrap();
+        assert_eq!(imported.entity_count(), 4);
+        assert_eq!(imported.flow_count(), 4);
+        assert_eq!(imported.all_roles().len(), 1);
+        // Sequence flows carry no data association, so the handoff resource
+        // stands in; the data object survives as a declared resource.
+        assert!(import.source.contains("Resource \"CameraUnits\""));
+        assert!(import
+            .source
+            .contains("Flow \"Handoff\" from \"Warehouse\" to \"LineA\""));
+    }
+}
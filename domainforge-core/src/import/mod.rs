@@ -0,0 +1,138 @@
+//! Importers that turn foreign models into SEA source.
+//!
+//! Each importer builds a parser [`Ast`] and prints it with the
+//! [`PrettyPrinter`], so imported files look like any other formatted SEA
+//! file and parse back to the same graph. Whatever has no SEA counterpart is
+//! listed in an [`ImportReport`] instead of being dropped silently.
+
+pub mod archimate;
+pub mod asyncapi;
+pub mod bpmn;
+pub mod openapi;
+mod proto_parser;
+pub mod protobuf;
+mod schema;
+
+use crate::parser::ast::{Ast, AstNode, FileMetadata, Spanned};
+use crate::parser::PrettyPrinter;
+use std::fmt::Write;
+
+/// The result of an import: SEA source plus the mapping report.
+#[derive(Debug, Clone)]
+pub struct SeaImport {
+    pub source: String,
+    pub report: ImportReport,
+}
+
+/// One source element that has no SEA counterpart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedElement {
+    /// The element's id in the source model (empty when it has none).
+    pub id: String,
+    /// The element kind, e.g. `exclusiveGateway`.
+    pub kind: String,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+/// What an importer mapped and what it could not.
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Source format label, e.g. `BPMN 2.0`.
+    pub format: String,
+    pub entities: usize,
+    pub roles: usize,
+    pub resources: usize,
+    pub flows: usize,
+    pub records: usize,
+    pub enums: usize,
+    pub operations: usize,
+    pub unmapped: Vec<UnmappedElement>,
+    /// Inferred or incomplete parts of the imported source a modeller should
+    /// check before relying on it.
+    pub review: Vec<String>,
+    /// Differences found by re-projecting the imported source and comparing
+    /// it with the input; `None` when the importer does not check, or the
+    /// imported source could not be projected.
+    pub round_trip: Option<Vec<String>>,
+}
+
+impl ImportReport {
+    /// Human-readable mapping report.
+    pub fn to_report(&self) -> String {
+        let mut out = format!(
+            "{} import: entities={} roles={} resources={} flows={}",
+            self.format, self.entities, self.roles, self.resources, self.flows
+        );
+        if self.records + self.enums + self.operations > 0 {
+            let _ = write!(
+                out,
+                " records={} enums={} operations={}",
+                self.records, self.enums, self.operations
+            );
+        }
+        out.push('\n');
+        if self.unmapped.is_empty() {
+            out.push_str("Every element has a SEA counterpart.\n");
+        } else {
+            let _ = writeln!(
+                out,
+                "{} element(s) have no SEA counterpart:",
+                self.unmapped.len()
+            );
+        }
+        for element in &self.unmapped {
+            let label = match &element.name {
+                Some(name) => format!("{} \"{}\"", element.kind, name),
+                None => element.kind.clone(),
+            };
+            let id = if element.id.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", element.id)
+            };
+            let _ = writeln!(out, "  - {label}{id}: {}", element.reason);
+        }
+        if !self.review.is_empty() {
+            let _ = writeln!(out, "{} item(s) need review:", self.review.len());
+            for item in &self.review {
+                let _ = writeln!(out, "  - {item}");
+            }
+        }
+        match &self.round_trip {
+            Some(diff) if diff.is_empty() => out.push_str("Round trip: no differences.\n"),
+            Some(diff) => {
+                let _ = writeln!(out, "Round trip: {} difference(s):", diff.len());
+                for item in diff {
+                    let _ = writeln!(out, "  - {item}");
+                }
+            }
+            None => {}
+        }
+        out
+    }
+}
+
+/// Print `declarations` under `@namespace namespace` as formatted SEA.
+pub(crate) fn print_sea(namespace: &str, declarations: Vec<AstNode>) -> String {
+    let ast = Ast {
+        metadata: FileMetadata {
+            namespace: Some(namespace.to_string()),
+            ..FileMetadata::default()
+        },
+        declarations: declarations
+            .into_iter()
+            .map(|node| Spanned {
+                node,
+                line: 0,
+                column: 0,
+            })
+            .collect(),
+    };
+    PrettyPrinter::new().print(&ast)
+}
+
+/// Collapse runs of whitespace (BPMN names often carry line breaks).
+pub(crate) fn display_name(raw: &str) -> String {
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
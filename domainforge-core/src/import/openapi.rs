@@ -0,0 +1,1087 @@
+//! OpenAPI 3.x importer: reads an OpenAPI 3.0 or 3.1 document (JSON or YAML)
+//! and writes the application contract it describes.
+//!
+//! Mapping:
+//! - `components.schemas` → `record` (objects) and `enum` (string enums),
+//!   with validation keywords as field constraints (see [`super::schema`])
+//! - each path operation → `operation` named after its `operationId`:
+//!   - `summary` (else `description`) is the intent
+//!   - path, query, and `Idempotency-Key` header parameters plus the JSON
+//!     request body form the input record; a body that is a bare component
+//!     reference is used as-is
+//!   - the first 2xx JSON response body is the output record
+//!   - 4xx responses are failures: 400/422 → `input_validation`, 401/403 →
+//!     `policy`, 404/410 → `missing_state`, 409/412 → the operation's
+//!     conflict kinds
+//! - the first path segment (after `api`/`v1`-style prefixes) → a typed
+//!   `Entity` holding the state its operations read and write, keyed by the
+//!   segment's path parameter (else the first required string or uuid input)
+//!   and carrying the fields those operations return
+//! - a security scheme an operation requires → the `Role` acting on it
+//!
+//! OpenAPI says nothing about state, idempotency, or concurrency, so those
+//! clauses are inferred from the HTTP method (`GET` reads, `POST` creates,
+//! `PUT`/`PATCH`/`DELETE` mutate), naming only required scalar inputs (a
+//! strategy with none is left out), and listed for review, together with every
+//! diagnostic the application contract resolver still reports for the
+//! imported source.
+
+use super::schema::{
+    deref, entries, is_object, key_text, parse_document, pointer_segment, snake, text, Component,
+    SchemaImporter,
+};
+use super::{display_name, print_sea, ImportReport, SeaImport, UnmappedElement};
+use crate::application::resolve_application_contract;
+use crate::kg_import::ImportError;
+use crate::parser::ast::{
+    AstNode, EntityBody, FieldDecl, FieldType, FieldTypeRef, OperationClause, OperationDecl,
+    RecordDecl,
+};
+use crate::projection::ids::{ident, pascal, slug};
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// Logical path the imported source is resolved under for the review list.
+const CHECK_ENTRY: &str = "openapi.sea";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Creates,
+    Mutates,
+    Reads,
+}
+
+impl Effect {
+    fn of(method: &str) -> Option<Self> {
+        match method {
+            "get" => Some(Effect::Reads),
+            "post" => Some(Effect::Creates),
+            "put" | "patch" | "delete" => Some(Effect::Mutates),
+            _ => None,
+        }
+    }
+
+    fn keyword(self) -> &'static str {
+        match self {
+            Effect::Creates => "creates",
+            Effect::Mutates => "mutates",
+            Effect::Reads => "reads",
+        }
+    }
+}
+
+/// Path-item methods with no operation counterpart.
+const UNMAPPED_METHODS: [&str; 3] = ["head", "options", "trace"];
+
+/// Failure groups in clause order: code suffix and the kinds it covers.
+const FAILURE_GROUPS: [(&str, &str); 4] = [
+    ("invalid_input", "input_validation"),
+    ("forbidden", "policy"),
+    ("not_found", "missing_state"),
+    ("conflict", "idempotency_conflict"),
+];
+
+/// One path operation of the document.
+struct HttpOperation<'a> {
+    path: String,
+    method: String,
+    effect: Effect,
+    op: &'a Value,
+    pointer: String,
+    /// Path-level and operation-level parameters (the latter win), with
+    /// their pointers.
+    parameters: Vec<(&'a Value, String)>,
+}
+
+/// The aggregate one path segment's operations act on.
+struct State {
+    name: String,
+    segment: String,
+    key: FieldDecl,
+    fields: Vec<FieldDecl>,
+}
+
+struct Importer<'a> {
+    doc: &'a Value,
+    schemas: SchemaImporter<'a>,
+    states: Vec<State>,
+    roles: HashMap<String, String>,
+    role_order: Vec<String>,
+    review: Vec<String>,
+}
+
+/// Import an OpenAPI 3.x document as SEA source under `namespace`
+/// (default: the document's `info.title`).
+pub fn import_openapi(source: &str, namespace: Option<&str>) -> Result<SeaImport, ImportError> {
+    let doc = parse_document(source, "OpenAPI")?;
+    let version = doc.get("openapi").map(key_text).unwrap_or_default();
+    if !version.starts_with("3.") {
+        return Err(ImportError::Other(format!(
+            "Not an OpenAPI 3.x document: `openapi` is `{version}`"
+        )));
+    }
+    let namespace = namespace
+        .map(str::to_string)
+        .or_else(|| {
+            doc.get("info")
+                .and_then(|i| text(i, "title"))
+                .map(str::to_string)
+        })
+        .map_or_else(|| "imported".to_string(), |raw| ident(&slug(&raw)));
+
+    let mut importer = Importer {
+        doc: &doc,
+        schemas: SchemaImporter::new(&doc),
+        states: Vec::new(),
+        roles: HashMap::new(),
+        role_order: Vec::new(),
+        review: Vec::new(),
+    };
+    importer.schemas.import_components("components/schemas");
+    let operations = importer.collect_operations();
+    for operation in &operations {
+        importer.state_for(operation);
+    }
+    let decls: Vec<OperationDecl> = operations
+        .iter()
+        .map(|operation| importer.operation(operation))
+        .collect();
+    for (name, _) in doc.get("webhooks").map(entries).unwrap_or_default() {
+        importer.schemas.unmapped.push(UnmappedElement {
+            id: format!("#/webhooks/{}", pointer_segment(&name)),
+            kind: "webhook".to_string(),
+            name: Some(name),
+            reason: "outbound calls have no operation counterpart".to_string(),
+        });
+    }
+
+    let Importer {
+        schemas,
+        states,
+        roles,
+        role_order,
+        mut review,
+        ..
+    } = importer;
+    let mut report = ImportReport {
+        format: format!("OpenAPI {version}"),
+        entities: states.len(),
+        roles: roles.len(),
+        records: schemas.record_count(),
+        enums: schemas.enum_count(),
+        operations: decls.len(),
+        ..ImportReport::default()
+    };
+    let (mut declarations, unmapped, renames) = schemas.into_declarations();
+    review.extend(renames);
+    declarations.extend(
+        role_order
+            .into_iter()
+            .map(|name| AstNode::Role { name, domain: None }),
+    );
+    for state in states {
+        review.push(format!(
+            "entity {} is inferred from the /{} paths and keyed by {}",
+            state.name, state.segment, state.key.name
+        ));
+        let mut fields = vec![state.key];
+        fields.extend(state.fields);
+        declarations.push(AstNode::Entity {
+            name: state.name,
+            version: None,
+            annotations: HashMap::new(),
+            domain: None,
+            body: Some(EntityBody { fields }),
+        });
+    }
+    declarations.extend(decls.into_iter().map(AstNode::Operation));
+    let source = print_sea(&namespace, declarations);
+
+    // The resolver's remaining diagnostics are what a modeller still has to
+    // decide; list them rather than failing the import.
+    let sources = serde_json::json!({ CHECK_ENTRY: &source }).to_string();
+    if let Err(diagnostics) = resolve_application_contract(CHECK_ENTRY, &sources) {
+        review.extend(
+            diagnostics
+                .iter()
+                .map(|d| format!("{} {}", d.code.code(), d.message)),
+        );
+    }
+    report.unmapped = unmapped;
+    report.review = review;
+    Ok(SeaImport { source, report })
+}
+
+/// The JSON schema of a request body or response, with its media type.
+fn json_schema(body: &Value) -> Option<(&Value, String)> {
+    let content = entries(body.get("content")?);
+    let (media, media_type) = content
+        .iter()
+        .find(|(m, _)| m == "application/json" || m.ends_with("+json"))
+        .or_else(|| content.first())?;
+    Some((media_type.get("schema")?, media.clone()))
+}
+
+/// The path segment naming the aggregate: the first literal segment that is
+/// not an `api` or version prefix.
+fn resource_segment(path: &str) -> String {
+    let is_prefix = |s: &str| {
+        s == "api"
+            || (s.len() > 1 && s.starts_with('v') && s[1..].bytes().all(|b| b.is_ascii_digit()))
+    };
+    path.split('/')
+        .find(|s| !s.is_empty() && !s.starts_with('{') && !is_prefix(s))
+        .unwrap_or("root")
+        .to_string()
+}
+
+fn path_parameter(path: &str) -> Option<&str> {
+    path.split('/')
+        .find_map(|s| s.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+}
+
+/// Whether `field` is required and of scalar type `symbol` (any scalar when
+/// `symbol` is empty).
+fn is_required_scalar(field: &FieldDecl, symbol: &str) -> bool {
+    !field.is_optional
+        && matches!(&field.field_type, FieldType::Scalar(r) if symbol.is_empty() || r.symbol == symbol)
+}
+
+fn scalar(symbol: &str) -> FieldType {
+    FieldType::Scalar(FieldTypeRef {
+        alias: None,
+        symbol: symbol.to_string(),
+    })
+}
+
+impl<'a> Importer<'a> {
+    fn collect_operations(&mut self) -> Vec<HttpOperation<'a>> {
+        let doc = self.doc;
+        let mut operations = Vec::new();
+        for (path, item) in doc.get("paths").map(entries).unwrap_or_default() {
+            let item_pointer = format!("#/paths/{}", pointer_segment(&path));
+            let item = deref(doc, item);
+            for (method, op) in entries(item) {
+                let pointer = format!("{item_pointer}/{method}");
+                let Some(effect) = Effect::of(&method) else {
+                    if UNMAPPED_METHODS.contains(&method.as_str()) {
+                        self.schemas.report(
+                            &pointer,
+                            &method,
+                            "HEAD, OPTIONS, and TRACE have no operation counterpart",
+                        );
+                    }
+                    continue;
+                };
+                let mut parameters: Vec<(&'a Value, String)> = Vec::new();
+                let lists = [
+                    (item.get("parameters"), format!("{item_pointer}/parameters")),
+                    (op.get("parameters"), format!("{pointer}/parameters")),
+                ];
+                for (list, list_pointer) in lists {
+                    let Some(list) = list.and_then(Value::as_sequence) else {
+                        continue;
+                    };
+                    for (i, parameter) in list.iter().enumerate() {
+                        let resolved = deref(doc, parameter);
+                        let id = (text(resolved, "name"), text(resolved, "in"));
+                        parameters.retain(|(p, _)| (text(p, "name"), text(p, "in")) != id);
+                        parameters.push((resolved, format!("{list_pointer}/{i}")));
+                    }
+                }
+                if let Some(callbacks) = op.get("callbacks") {
+                    for (name, _) in entries(callbacks) {
+                        self.schemas.unmapped.push(UnmappedElement {
+                            id: format!("{pointer}/callbacks/{}", pointer_segment(&name)),
+                            kind: "callback".to_string(),
+                            name: Some(name),
+                            reason: "outbound calls have no operation counterpart".to_string(),
+                        });
+                    }
+                }
+                operations.push(HttpOperation {
+                    path: path.clone(),
+                    method,
+                    effect,
+                    op,
+                    pointer,
+                    parameters,
+                });
+            }
+        }
+        operations
+    }
+
+    /// The state entity of `operation`'s path segment, created on first use
+    /// and keyed by the first path parameter any of its paths declares.
+    fn state_for(&mut self, operation: &HttpOperation) -> usize {
+        let segment = resource_segment(&operation.path);
+        let index = match self.states.iter().position(|s| s.segment == segment) {
+            Some(index) => index,
+            None => {
+                let name = self.schemas.declare(&pascal(&segment));
+                self.states.push(State {
+                    name,
+                    segment: segment.clone(),
+                    key: FieldDecl {
+                        is_key: true,
+                        name: String::new(),
+                        field_type: scalar("uuid"),
+                        is_optional: false,
+                        constraints: Vec::new(),
+                        default: None,
+                    },
+                    fields: Vec::new(),
+                });
+                self.states.len() - 1
+            }
+        };
+        if !self.states[index].key.name.is_empty() {
+            return index;
+        }
+        let key_parameter = path_parameter(&operation.path).and_then(|name| {
+            operation
+                .parameters
+                .iter()
+                .map(|(p, _)| *p)
+                .find(|p| text(p, "name") == Some(name) && text(p, "in") == Some("path"))
+        });
+        if let Some(parameter) = key_parameter {
+            let name = text(parameter, "name").unwrap_or_default();
+            let schema = parameter.get("schema").unwrap_or(&Value::Null);
+            let symbol = match (text(schema, "type"), text(schema, "format")) {
+                (_, Some("uuid")) => "uuid",
+                (Some("string") | None, _) => "string",
+                (Some(other), _) => {
+                    self.review.push(format!(
+                        "path parameter {name} of /{segment} is {other} in the API; SEA keys are uuid or string"
+                    ));
+                    "string"
+                }
+            };
+            let key = &mut self.states[index].key;
+            key.name = snake(name);
+            key.field_type = scalar(symbol);
+        }
+        index
+    }
+
+    fn operation(&mut self, operation: &HttpOperation) -> OperationDecl {
+        let op = operation.op;
+        let raw_name = text(op, "operationId")
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{} {}", operation.method, operation.path));
+        let name = self.schemas.declare(&snake(&raw_name));
+        let type_prefix = pascal(&name);
+        let state = self.state_for(operation);
+        let input = self.input(operation, &type_prefix);
+        let output = self.output(operation, &type_prefix);
+        let input_fields = self
+            .schemas
+            .record(&input)
+            .map(|r| r.fields.clone())
+            .unwrap_or_default();
+        if self.states[state].key.name.is_empty() {
+            // No path of this segment names its aggregate: key it by the
+            // first required string or uuid input, else `id`.
+            let key = &mut self.states[state].key;
+            match input_fields.iter().find(|f| {
+                f.name != "idempotency_key"
+                    && (is_required_scalar(f, "string") || is_required_scalar(f, "uuid"))
+            }) {
+                Some(field) => {
+                    key.name = field.name.clone();
+                    key.field_type = field.field_type.clone();
+                    key.constraints = field.constraints.clone();
+                }
+                None => key.name = "id".to_string(),
+            }
+        }
+        let key = self.states[state].key.name.clone();
+        let output_fields = self
+            .schemas
+            .record(&output)
+            .map(|r| r.fields.clone())
+            .unwrap_or_default();
+
+        // Strategy clauses may only name required scalar inputs; a clause
+        // with no such field is left out for the modeller to decide.
+        let effect = operation.effect;
+        let required = |name: &str| {
+            input_fields
+                .iter()
+                .any(|f| f.name == name && is_required_scalar(f, ""))
+        };
+        let idempotency_field = ["idempotency_key", key.as_str()]
+            .into_iter()
+            .find(|name| required(name))
+            .map(str::to_string);
+        let concurrency_field = match effect {
+            Effect::Reads => None,
+            Effect::Creates => Some(key.clone())
+                .filter(|key| required(key))
+                .or_else(|| idempotency_field.clone()),
+            Effect::Mutates => input_fields
+                .iter()
+                .any(|f| f.name == "version" && is_required_scalar(f, "int"))
+                .then(|| "version".to_string()),
+        };
+        let (idempotency, concurrency) = match effect {
+            Effect::Reads => (
+                Some(OperationClause::IdempotencyInherent),
+                Some(OperationClause::ConcurrencyReadSnapshot),
+            ),
+            Effect::Creates => (
+                idempotency_field
+                    .clone()
+                    .map(|field| OperationClause::IdempotencyKeyed { field }),
+                concurrency_field
+                    .clone()
+                    .map(|field| OperationClause::ConcurrencyUnique { field }),
+            ),
+            Effect::Mutates => (
+                idempotency_field
+                    .clone()
+                    .map(|field| OperationClause::IdempotencyKeyed { field }),
+                concurrency_field
+                    .clone()
+                    .map(|field| OperationClause::ConcurrencyOptimistic { field }),
+            ),
+        };
+        if effect != Effect::Reads {
+            let method = operation.method.to_uppercase();
+            let strategy = if effect == Effect::Creates {
+                "unique_key"
+            } else {
+                "optimistic_version"
+            };
+            let inferred: Vec<String> = [
+                idempotency_field.map(|f| format!("idempotency keyed_by {f}")),
+                concurrency_field.map(|f| format!("concurrency {strategy} {f}")),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            match inferred.as_slice() {
+                [] => {}
+                [one] => self
+                    .review
+                    .push(format!("operation {name}: {one} is inferred from {method}")),
+                [first, second] => self.review.push(format!(
+                    "operation {name}: {first} and {second} are inferred from {method}"
+                )),
+                _ => unreachable!("at most two strategy clauses"),
+            }
+            if idempotency.is_none() {
+                self.review.push(format!(
+                    "operation {name}: no required scalar input can key idempotency; the idempotency clause is left out"
+                ));
+            }
+            if concurrency.is_none() {
+                let needs = if effect == Effect::Creates {
+                    "required scalar input can be its unique_key"
+                } else {
+                    "required int version input exists"
+                };
+                self.review.push(format!(
+                    "operation {name}: no {needs}; the concurrency clause is left out"
+                ));
+            }
+        }
+
+        // The state carries what the operations return and write.
+        let state_name = self.states[state].name.clone();
+        let written: &[FieldDecl] = if effect == Effect::Reads {
+            &[]
+        } else {
+            &input_fields
+        };
+        for field in output_fields.iter().chain(written) {
+            if field.name == key || field.name == "idempotency_key" {
+                continue;
+            }
+            let fields = &mut self.states[state].fields;
+            match fields.iter().find(|f| f.name == field.name) {
+                None => fields.push(FieldDecl {
+                    is_key: false,
+                    default: None,
+                    ..field.clone()
+                }),
+                Some(existing)
+                    if existing.field_type != field.field_type
+                        || existing.is_optional != field.is_optional
+                        || existing.constraints != field.constraints =>
+                {
+                    let message = format!(
+                        "field {} of entity {state_name} differs between operations; the first declaration is kept",
+                        field.name
+                    );
+                    if !self.review.contains(&message) {
+                        self.review.push(message);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut clauses = vec![
+            OperationClause::Intent(
+                text(op, "summary")
+                    .or_else(|| text(op, "description"))
+                    .map(display_name)
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or_else(|| {
+                        format!("{} {}", operation.method.to_uppercase(), operation.path)
+                    }),
+            ),
+            OperationClause::Direction {
+                kind: "inbound".to_string(),
+            },
+            OperationClause::Actor {
+                actor: self.actor(op),
+            },
+            OperationClause::AccessPublic,
+            OperationClause::Input { reference: input },
+            OperationClause::Output { reference: output },
+            OperationClause::State {
+                reference: state_name.clone(),
+            },
+            OperationClause::Effect {
+                kind: effect.keyword().to_string(),
+                reference: state_name,
+            },
+            OperationClause::Transaction {
+                kind: if effect == Effect::Reads {
+                    "read_only"
+                } else {
+                    "single_aggregate"
+                }
+                .to_string(),
+            },
+        ];
+        let constrained = input_fields.iter().any(|f| !f.constraints.is_empty());
+        clauses.extend(self.failures(operation, &name, constrained));
+        clauses.extend(idempotency);
+        clauses.extend(concurrency);
+        clauses.extend([
+            OperationClause::EvidenceOperationTrace,
+            OperationClause::LifecycleSynchronousRequestResponse,
+        ]);
+        OperationDecl { name, clauses }
+    }
+
+    /// `anonymous`, or the role named after the first security scheme the
+    /// operation (else the document) requires.
+    fn actor(&mut self, op: &Value) -> String {
+        let requirements = op.get("security").or_else(|| self.doc.get("security"));
+        let scheme = requirements.and_then(Value::as_sequence).and_then(|reqs| {
+            reqs.iter()
+                .find_map(|req| entries(req).first().map(|(n, _)| n.clone()))
+        });
+        let Some(scheme) = scheme else {
+            return "anonymous".to_string();
+        };
+        if let Some(role) = self.roles.get(&scheme) {
+            return role.clone();
+        }
+        let role = self.schemas.declare(&pascal(&scheme));
+        self.review.push(format!(
+            "security scheme {scheme} is imported as role {role}; its operations keep public access until a policy is bound"
+        ));
+        self.roles.insert(scheme, role.clone());
+        self.role_order.push(role.clone());
+        role
+    }
+
+    /// The input record: the body's component record when the operation has
+    /// no parameters, else a synthesized `<Operation>Input`.
+    fn input(&mut self, operation: &HttpOperation, type_prefix: &str) -> String {
+        let owner = format!("{type_prefix}Input");
+        let mut taken = BTreeSet::new();
+        let mut fields: Vec<FieldDecl> = Vec::new();
+        for (parameter, pointer) in &operation.parameters {
+            let name = text(parameter, "name").unwrap_or_default();
+            let location = text(parameter, "in").unwrap_or_default();
+            let mapped = match location {
+                "path" | "query" => true,
+                "header" => name.eq_ignore_ascii_case("idempotency-key"),
+                _ => false,
+            };
+            if !mapped {
+                self.schemas.unmapped.push(UnmappedElement {
+                    id: pointer.clone(),
+                    kind: format!("{location} parameter"),
+                    name: Some(name.to_string()),
+                    reason: "only path, query, and Idempotency-Key header parameters are imported"
+                        .to_string(),
+                });
+                continue;
+            }
+            let Some(schema) = parameter.get("schema") else {
+                self.schemas.report(
+                    pointer,
+                    "parameter",
+                    "a parameter without a schema has no field type",
+                );
+                continue;
+            };
+            let hint = format!("{type_prefix}{}", pascal(name));
+            let Some((field_type, constraints)) =
+                self.schemas
+                    .field_type(&hint, schema, &format!("{pointer}/schema"))
+            else {
+                continue;
+            };
+            let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+            fields.push(FieldDecl {
+                is_key: false,
+                name: self.schemas.field_name(&owner, name, &mut taken),
+                field_type,
+                is_optional: location != "path" && !required,
+                constraints,
+                default: None,
+            });
+        }
+
+        if let Some(body) = operation.op.get("requestBody") {
+            let pointer = format!("{}/requestBody", operation.pointer);
+            let body = deref(self.doc, body);
+            match json_schema(body) {
+                Some((schema, media)) => {
+                    let at = format!("{pointer}/content/{}/schema", pointer_segment(&media));
+                    let body_fields = match text(schema, "$ref").map(str::to_string) {
+                        Some(reference) => match self.schemas.component(&reference) {
+                            Component::Record(record) if fields.is_empty() => return record,
+                            Component::Record(record) => self
+                                .schemas
+                                .record(&record)
+                                .map(|r| r.fields.clone())
+                                .unwrap_or_default(),
+                            _ => {
+                                self.schemas.report(
+                                    &at,
+                                    "requestBody",
+                                    "a non-object request body has no input record counterpart",
+                                );
+                                Vec::new()
+                            }
+                        },
+                        None if is_object(schema) => self.schemas.fields(&owner, schema, &at),
+                        None => {
+                            self.schemas.report(
+                                &at,
+                                "requestBody",
+                                "a non-object request body has no input record counterpart",
+                            );
+                            Vec::new()
+                        }
+                    };
+                    for field in body_fields {
+                        if fields.iter().any(|f| f.name == field.name) {
+                            self.review.push(format!(
+                                "body field {} of {type_prefix}Input shadows a parameter and is dropped",
+                                field.name
+                            ));
+                        } else {
+                            fields.push(field);
+                        }
+                    }
+                }
+                None => self.schemas.report(
+                    &pointer,
+                    "requestBody",
+                    "only JSON request bodies with a schema are imported",
+                ),
+            }
+        }
+        let name = self.schemas.declare(&format!("{type_prefix}Input"));
+        self.schemas.push_record(RecordDecl {
+            name: name.clone(),
+            fields,
+        });
+        name
+    }
+
+    /// The output record: the first 2xx body's component record, else a
+    /// synthesized `<Operation>Output` (empty for bodiless responses).
+    fn output(&mut self, operation: &HttpOperation, type_prefix: &str) -> String {
+        let responses = operation
+            .op
+            .get("responses")
+            .map(entries)
+            .unwrap_or_default();
+        let success = responses.iter().find(|(status, _)| status.starts_with('2'));
+        if let Some((status, response)) = success {
+            let pointer = format!("{}/responses/{status}", operation.pointer);
+            if let Some((schema, media)) = json_schema(deref(self.doc, response)) {
+                let at = format!("{pointer}/content/{}/schema", pointer_segment(&media));
+                match text(schema, "$ref").map(str::to_string) {
+                    Some(reference) => {
+                        if let Component::Record(record) = self.schemas.component(&reference) {
+                            return record;
+                        }
+                        self.schemas.report(
+                            &at,
+                            "response",
+                            "a non-object response body has no output record counterpart",
+                        );
+                    }
+                    None if is_object(schema) => {
+                        return self.schemas.inline_record(
+                            &format!("{type_prefix}Output"),
+                            schema,
+                            &at,
+                        );
+                    }
+                    None => self.schemas.report(
+                        &at,
+                        "response",
+                        "a non-object response body has no output record counterpart",
+                    ),
+                }
+            }
+        }
+        let name = self.schemas.declare(&format!("{type_prefix}Output"));
+        self.schemas.push_record(RecordDecl {
+            name: name.clone(),
+            fields: Vec::new(),
+        });
+        name
+    }
+
+    /// Failure clauses from the 4xx responses, one per kind group, plus the
+    /// groups the contract requires but the document does not declare.
+    fn failures(
+        &mut self,
+        operation: &HttpOperation,
+        name: &str,
+        constrained_input: bool,
+    ) -> Vec<OperationClause> {
+        let writes = operation.effect != Effect::Reads;
+        let mut messages: Vec<Vec<String>> = vec![Vec::new(); FAILURE_GROUPS.len()];
+        let responses = operation
+            .op
+            .get("responses")
+            .map(entries)
+            .unwrap_or_default();
+        for (status, response) in responses {
+            if status.starts_with('2') {
+                continue;
+            }
+            let group = match status.as_str() {
+                "400" | "422" => Some(0),
+                "401" | "403" => Some(1),
+                "404" | "410" => Some(2),
+                "409" | "412" if writes => Some(3),
+                _ => None,
+            };
+            let Some(group) = group else {
+                self.schemas.unmapped.push(UnmappedElement {
+                    id: format!(
+                        "{}/responses/{}",
+                        operation.pointer,
+                        pointer_segment(&status)
+                    ),
+                    kind: "response".to_string(),
+                    name: Some(status),
+                    reason: "no failure kind covers this status".to_string(),
+                });
+                continue;
+            };
+            let message = text(deref(self.doc, response), "description")
+                .map(display_name)
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| format!("HTTP {status}"));
+            if !messages[group].contains(&message) {
+                messages[group].push(message);
+            }
+        }
+
+        let required = [
+            constrained_input,
+            false,
+            operation.effect != Effect::Creates,
+            writes,
+        ];
+        let mut clauses = Vec::new();
+        for (i, (suffix, kind)) in FAILURE_GROUPS.iter().enumerate() {
+            let code = format!("{name}_{suffix}");
+            let message = if !messages[i].is_empty() {
+                messages[i].join("; ")
+            } else if required[i] {
+                self.review.push(format!(
+                    "operation {name}: failure {code} is synthesized; the document declares no {kind} response"
+                ));
+                format!("{kind} failure (no response declared)")
+            } else {
+                continue;
+            };
+            let kinds = if i == 3 {
+                vec![
+                    "idempotency_conflict".to_string(),
+                    "concurrency_conflict".to_string(),
+                ]
+            } else {
+                vec![kind.to_string()]
+            };
+            clauses.push(OperationClause::Failure {
+                code,
+                kinds,
+                message,
+            });
+        }
+        clauses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::resolve_application_contract;
+
+    const ORDERS: &str = r#"
+openapi: 3.0.3
+info:
+  title: Order Service
+  version: 1.0.0
+paths:
+  /orders/{orderId}:
+    parameters:
+      - name: orderId
+        in: path
+        required: true
+        schema: {type: string, format: uuid}
+    get:
+      operationId: getOrder
+      summary: Return one order
+      responses:
+        '200':
+          description: The order
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Order'}
+        '404':
+          description: No order has that id
+        '500':
+          description: Server error
+    head:
+      responses:
+        '200': {description: ok}
+components:
+  schemas:
+    Order:
+      type: object
+      required: [status, total]
+      properties:
+        status: {$ref: '#/components/schemas/OrderStatus'}
+        total: {type: number, minimum: 0}
+        note:
+          type: string
+          maxLength: 140
+          nullable: true
+    OrderStatus:
+      type: string
+      enum: [placed, shipped]
+"#;
+
+    #[test]
+    fn maps_paths_and_schemas_to_a_contract() {
+        let import = import_openapi(ORDERS, None).unwrap();
+        assert_eq!(
+            import.source,
+            r#"@namespace "order_service"
+
+enum OrderStatus {
+    placed = "placed",
+    shipped = "shipped"
+}
+
+record Order {
+    status: OrderStatus
+    total: decimal (min 0)
+    note: string optional (max_length 140)
+}
+
+record GetOrderInput {
+    order_id: uuid
+}
+
+Entity "Orders" {
+    key order_id: uuid
+    status: OrderStatus
+    total: decimal (min 0)
+    note: string optional (max_length 140)
+}
+
+operation get_order {
+    intent "Return one order"
+    direction inbound
+    actor anonymous
+    access public
+    input GetOrderInput
+    output Order
+    state Orders
+    effect reads Orders
+    transaction read_only
+    failure get_order_not_found for missing_state "No order has that id"
+    idempotency inherent
+    concurrency read_snapshot
+    evidence operation_trace
+    lifecycle synchronous_request_response
+}
+"#
+        );
+        let sources = serde_json::json!({ "main.sea": import.source }).to_string();
+        resolve_application_contract("main.sea", &sources).expect("imported contract resolves");
+    }
+
+    #[test]
+    fn reports_unsupported_constructs_and_inferences() {
+        let report = import_openapi(ORDERS, None).unwrap().report;
+        let kinds: Vec<&str> = report.unmapped.iter().map(|u| u.kind.as_str()).collect();
+        assert_eq!(kinds, ["nullable", "head", "response"]);
+        assert_eq!(
+            report.unmapped[2].id,
+            "#/paths/~1orders~1{orderId}/get/responses/500"
+        );
+        assert_eq!(
+            report.review,
+            [
+                "GetOrderInput.orderId is imported as field order_id; its wire name changes",
+                "entity Orders is inferred from the /orders paths and keyed by order_id"
+            ]
+        );
+        assert_eq!((report.records, report.enums, report.operations), (2, 1, 1));
+    }
+
+    #[test]
+    fn writes_get_inferred_strategies_and_conflict_failures() {
+        let spec = r#"{
+  "openapi": "3.1.0",
+  "info": {"title": "Orders", "version": "1"},
+  "security": [{"bearer": []}],
+  "paths": {
+    "/v1/orders": {
+      "post": {
+        "operationId": "placeOrder",
+        "parameters": [
+          {"name": "Idempotency-Key", "in": "header", "required": true, "schema": {"type": "string"}},
+          {"name": "X-Trace", "in": "header", "schema": {"type": "string"}}
+        ],
+        "requestBody": {"content": {"application/json": {"schema": {
+          "type": "object", "required": ["sku"],
+          "properties": {"sku": {"type": "string", "minLength": 1}}
+        }}}},
+        "responses": {
+          "201": {"description": "Created"},
+          "409": {"description": "Key reused"}
+        }
+      }
+    }
+  }
+}"#;
+        let import = import_openapi(spec, Some("shop")).unwrap();
+        assert!(import.source.starts_with("@namespace \"shop\""));
+        assert!(import.source.contains("Role \"Bearer\""));
+        assert!(import
+            .source
+            .contains("    actor Bearer\n    access public\n"));
+        assert!(import.source.contains(
+            "failure place_order_invalid_input for input_validation \"input_validation failure (no response declared)\""
+        ));
+        assert!(import.source.contains(
+            "failure place_order_conflict for idempotency_conflict, concurrency_conflict \"Key reused\""
+        ));
+        assert!(import
+            .source
+            .contains("idempotency keyed_by idempotency_key"));
+        // No path names the order, so the first required string input keys it.
+        assert!(import
+            .source
+            .contains("Entity \"Orders\" {\n    key sku: string"));
+        assert!(import.source.contains("concurrency unique_key sku"));
+        let report = import.report;
+        assert_eq!(report.unmapped[0].kind, "header parameter");
+        assert!(report
+            .review
+            .iter()
+            .any(|r| r.starts_with("security scheme bearer is imported as role Bearer")));
+        assert!(report.review.contains(
+            &"operation place_order: idempotency keyed_by idempotency_key and concurrency unique_key sku are inferred from POST".to_string()
+        ));
+        // Every strategy names a required scalar input, so the contract resolves.
+        assert!(
+            !report.review.iter().any(|r| r.starts_with("APP")),
+            "{:?}",
+            report.review
+        );
+    }
+
+    #[test]
+    fn strategies_without_a_required_scalar_input_are_left_out() {
+        let spec = r#"{
+  "openapi": "3.1.0",
+  "info": {"title": "Orders", "version": "1"},
+  "paths": {
+    "/orders/{orderId}": {
+      "patch": {
+        "operationId": "renameOrder",
+        "parameters": [{"name": "orderId", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}}],
+        "requestBody": {"content": {"application/json": {"schema": {
+          "type": "object", "properties": {"label": {"type": "string"}}
+        }}}},
+        "responses": {"200": {"description": "Renamed"}}
+      }
+    }
+  }
+}"#;
+        let import = import_openapi(spec, None).unwrap();
+        assert!(import.source.contains("idempotency keyed_by order_id"));
+        assert!(!import.source.contains("\n    concurrency "));
+        assert!(import.report.review.contains(
+            &"operation rename_order: no required int version input exists; the concurrency clause is left out".to_string()
+        ));
+    }
+
+    #[test]
+    fn colliding_fields_and_members_are_suffixed_and_reported() {
+        let spec = r#"{
+  "openapi": "3.1.0",
+  "info": {"title": "Orders", "version": "1"},
+  "paths": {},
+  "components": {"schemas": {
+    "Order": {
+      "type": "object", "required": ["fooBar", "foo_bar", "status"],
+      "properties": {
+        "fooBar": {"type": "string"},
+        "foo_bar": {"type": "string"},
+        "status": {"type": "string", "enum": ["placed", "placed "]}
+      }
+    }
+  }}
+}"#;
+        let import = import_openapi(spec, None).unwrap();
+        assert!(import
+            .source
+            .contains("    placed = \"placed\",\n    placed_2 = \"placed \"\n"));
+        assert!(import
+            .source
+            .contains("    foo_bar: string\n    foo_bar_2: string\n"));
+        assert_eq!(
+            import.report.review,
+            [
+                "Order.fooBar is imported as field foo_bar; its wire name changes",
+                "Order.foo_bar is imported as field foo_bar_2 because foo_bar is taken; its wire name changes",
+                "enum OrderStatus: wire value \"placed \" is member placed_2 because placed is taken"
+            ]
+        );
+        let sources = serde_json::json!({ "main.sea": import.source }).to_string();
+        resolve_application_contract("main.sea", &sources).expect("imported contract resolves");
+    }
+
+    #[test]
+    fn rejects_swagger_documents() {
+        let err = import_openapi("swagger: '2.0'\ninfo: {title: x}\n", None).unwrap_err();
+        assert!(err.to_string().contains("Not an OpenAPI 3.x document"));
+    }
+}
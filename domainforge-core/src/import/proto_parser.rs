@@ -0,0 +1,1205 @@
+//! proto3 text → the Protobuf projection's [`ProtoFile`] IR.
+//!
+//! Reading `.proto` files into the same IR [`ProtobufEngine`] writes lets the
+//! importer compare its re-projection against the input declaration by
+//! declaration. The IR has no slot for oneofs, extensions, or message,
+//! enum, and service options, so the parser lists those instead of keeping
+//! them; oneof members are kept as `optional` fields.
+//!
+//! [`ProtobufEngine`]: crate::projection::ProtobufEngine
+
+use super::UnmappedElement;
+use crate::kg_import::ImportError;
+use crate::projection::protobuf::{
+    ProtoCustomOption, ProtoEnum, ProtoEnumValue, ProtoField, ProtoFile, ProtoMessage,
+    ProtoOptionValue, ProtoRpcMethod, ProtoService, ProtoType, ScalarType, StreamingMode,
+};
+use std::collections::BTreeSet;
+
+/// Largest `reserved a to b` range expanded into field numbers.
+const MAX_RESERVED_RANGE: u32 = 256;
+
+/// A parsed file plus the constructs the IR cannot hold.
+#[derive(Debug, Clone)]
+pub(crate) struct ParsedProto {
+    pub(crate) file: ProtoFile,
+    pub(crate) unmapped: Vec<UnmappedElement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Sym(char),
+    /// Comment text; `trailing` when code precedes it on the same line.
+    Comment {
+        text: String,
+        trailing: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    line: usize,
+}
+
+fn error(line: usize, message: impl std::fmt::Display) -> ImportError {
+    ImportError::Other(format!("Invalid proto3 at line {line}: {message}"))
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ImportError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut line = 1;
+    let mut code_on_line = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            code_on_line = false;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let start = i + 2;
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token {
+                tok: Tok::Comment {
+                    text: text.trim().to_string(),
+                    trailing: code_on_line,
+                },
+                line,
+            });
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let start_line = line;
+            let start = i + 2;
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(error(start_line, "unterminated block comment"));
+            }
+            let body: String = chars[start..i].iter().collect();
+            i += 2;
+            let text = body
+                .lines()
+                .map(|l| l.trim().trim_start_matches('*').trim())
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            tokens.push(Token {
+                tok: Tok::Comment {
+                    text,
+                    trailing: code_on_line,
+                },
+                line: start_line,
+            });
+        } else if c == '"' || c == '\'' {
+            let (text, next, lines) = string_literal(&chars, i)
+                .ok_or_else(|| error(line, "unterminated string literal"))?;
+            // Adjacent literals concatenate.
+            match tokens.last_mut() {
+                Some(Token {
+                    tok: Tok::Str(prev),
+                    ..
+                }) => prev.push_str(&text),
+                _ => tokens.push(Token {
+                    tok: Tok::Str(text),
+                    line,
+                }),
+            }
+            line += lines;
+            code_on_line = true;
+            i = next;
+        } else if c.is_ascii_alphabetic() || c == '_' || (c == '.' && is_ident_start(&chars, i + 1))
+        {
+            let start = i;
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric()
+                    || chars[i] == '_'
+                    || (chars[i] == '.' && is_ident_start(&chars, i + 1)))
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                tok: Tok::Ident(chars[start..i].iter().collect()),
+                line,
+            });
+            code_on_line = true;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = i;
+            let hex = c == '0' && matches!(chars.get(i + 1), Some('x' | 'X'));
+            i += 1;
+            while i < chars.len() {
+                let ch = chars[i];
+                let exponent_sign =
+                    !hex && (ch == '+' || ch == '-') && matches!(chars[i - 1], 'e' | 'E');
+                if ch.is_ascii_alphanumeric() || ch == '.' || exponent_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                tok: Tok::Number(chars[start..i].iter().collect()),
+                line,
+            });
+            code_on_line = true;
+        } else if "{}()[]<>=;,:-+".contains(c) {
+            tokens.push(Token {
+                tok: Tok::Sym(c),
+                line,
+            });
+            code_on_line = true;
+            i += 1;
+        } else {
+            return Err(error(line, format!("unexpected character `{c}`")));
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_ident_start(chars: &[char], i: usize) -> bool {
+    chars
+        .get(i)
+        .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_')
+}
+
+/// The unescaped literal starting at `start`, the index after it, and the
+/// number of line breaks it spans.
+fn string_literal(chars: &[char], start: usize) -> Option<(String, usize, usize)> {
+    let quote = chars[start];
+    let mut out = String::new();
+    let mut lines = 0;
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            c if c == quote => return Some((out, i + 1, lines)),
+            '\n' => return None,
+            '\\' => {
+                let escaped = *chars.get(i + 1)?;
+                i += 2;
+                match escaped {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    '0'..='7' => {
+                        let mut value = escaped.to_digit(8)?;
+                        while i < chars.len() && value < 32 && chars[i].is_digit(8) {
+                            value = value * 8 + chars[i].to_digit(8)?;
+                            i += 1;
+                        }
+                        out.push(char::from_u32(value)?);
+                    }
+                    'x' | 'X' => {
+                        let mut value = 0;
+                        while i < chars.len() && chars[i].is_ascii_hexdigit() && value < 16 {
+                            value = value * 16 + chars[i].to_digit(16)?;
+                            i += 1;
+                        }
+                        out.push(char::from_u32(value)?);
+                    }
+                    '\n' => lines += 1,
+                    other => out.push(other),
+                }
+                continue;
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    None
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    unmapped: Vec<UnmappedElement>,
+}
+
+impl Parser {
+    /// Consume the comments before the next declaration, returning the ones
+    /// that lead it (trailing comments of the previous line are dropped).
+    fn comments(&mut self) -> Vec<String> {
+        let mut leading = Vec::new();
+        while let Some(Token {
+            tok: Tok::Comment { text, trailing },
+            ..
+        }) = self.tokens.get(self.pos)
+        {
+            if !trailing && !text.is_empty() {
+                leading.push(text.clone());
+            }
+            self.pos += 1;
+        }
+        leading
+    }
+
+    /// Consume the comment trailing the line of the token just read.
+    fn trailing(&mut self) -> Option<String> {
+        let line = self.tokens.get(self.pos.checked_sub(1)?)?.line;
+        match self.tokens.get(self.pos) {
+            Some(Token {
+                tok:
+                    Tok::Comment {
+                        text,
+                        trailing: true,
+                    },
+                line: l,
+            }) if *l == line => {
+                let text = text.clone();
+                self.pos += 1;
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
+    fn skip_comments(&mut self) {
+        while matches!(
+            self.tokens.get(self.pos),
+            Some(Token {
+                tok: Tok::Comment { .. },
+                ..
+            })
+        ) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&Tok> {
+        self.skip_comments();
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |t| t.line)
+    }
+
+    fn next(&mut self) -> Result<Tok, ImportError> {
+        self.skip_comments();
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| error(self.line(), "unexpected end of file"))?;
+        self.pos += 1;
+        Ok(token.tok.clone())
+    }
+
+    fn peek_is(&mut self, word: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Ident(w)) if w == word)
+    }
+
+    fn peek_sym(&mut self, sym: char) -> bool {
+        matches!(self.peek(), Some(Tok::Sym(s)) if *s == sym)
+    }
+
+    fn expect_sym(&mut self, sym: char) -> Result<(), ImportError> {
+        let line = self.line();
+        match self.next()? {
+            Tok::Sym(s) if s == sym => Ok(()),
+            other => Err(error(
+                line,
+                format!("expected `{sym}`, found {}", describe(&other)),
+            )),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ImportError> {
+        let line = self.line();
+        match self.next()? {
+            Tok::Ident(name) => Ok(name),
+            other => Err(error(
+                line,
+                format!("expected an identifier, found {}", describe(&other)),
+            )),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ImportError> {
+        let line = self.line();
+        match self.next()? {
+            Tok::Str(text) => Ok(text),
+            other => Err(error(
+                line,
+                format!("expected a string, found {}", describe(&other)),
+            )),
+        }
+    }
+
+    fn integer(&mut self) -> Result<i64, ImportError> {
+        let line = self.line();
+        let negative = self.peek_sym('-');
+        if negative {
+            self.next()?;
+        }
+        let value = match self.next()? {
+            Tok::Number(raw) => parse_int(&raw),
+            other => {
+                return Err(error(
+                    line,
+                    format!("expected a number, found {}", describe(&other)),
+                ))
+            }
+        }
+        .ok_or_else(|| error(line, "expected an integer"))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn field_number(&mut self) -> Result<u32, ImportError> {
+        let line = self.line();
+        let value = self.integer()?;
+        u32::try_from(value)
+            .ok()
+            .filter(|n| (1..=536_870_911).contains(n))
+            .ok_or_else(|| error(line, format!("{value} is not a valid field number")))
+    }
+
+    /// Skip a balanced `{ ... }` block whose opening brace is next.
+    fn skip_block(&mut self) -> Result<(), ImportError> {
+        self.expect_sym('{')?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Tok::Sym('{') => depth += 1,
+                Tok::Sym('}') => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip to the end of the current statement (`;` or a block).
+    fn skip_statement(&mut self) -> Result<(), ImportError> {
+        loop {
+            match self.next()? {
+                Tok::Sym(';') => return Ok(()),
+                Tok::Sym('{') => {
+                    self.pos -= 1;
+                    return self.skip_block();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn report(&mut self, id: String, kind: &str, reason: &str) {
+        self.unmapped.push(UnmappedElement {
+            id,
+            kind: kind.to_string(),
+            name: None,
+            reason: reason.to_string(),
+        });
+    }
+
+    /// `name`, `a.b`, or `(ext.name).sub.path`.
+    fn option_name(&mut self) -> Result<String, ImportError> {
+        let mut name = String::new();
+        if self.peek_sym('(') {
+            self.next()?;
+            name.push('(');
+            name.push_str(self.ident()?.trim_start_matches('.'));
+            self.expect_sym(')')?;
+            name.push(')');
+            if let Some(Tok::Ident(rest)) = self.peek() {
+                if rest.starts_with('.') {
+                    name.push_str(&self.ident()?);
+                }
+            }
+        } else {
+            name = self.ident()?;
+        }
+        Ok(name)
+    }
+
+    /// An option constant; `None` for aggregate (`{ ... }`) values.
+    fn constant(&mut self) -> Result<Option<ProtoOptionValue>, ImportError> {
+        if self.peek_sym('{') {
+            self.skip_block()?;
+            return Ok(None);
+        }
+        let line = self.line();
+        let sign = match self.peek() {
+            Some(Tok::Sym('-')) => "-",
+            Some(Tok::Sym('+')) => "+",
+            _ => "",
+        };
+        if !sign.is_empty() {
+            self.next()?;
+        }
+        Ok(Some(match self.next()? {
+            Tok::Str(text) => ProtoOptionValue::String(text),
+            Tok::Number(raw) => match parse_int(&raw) {
+                Some(value) if sign == "-" => ProtoOptionValue::Int(-value),
+                Some(value) => ProtoOptionValue::Int(value),
+                None => {
+                    let value: f64 = raw
+                        .parse()
+                        .map_err(|_| error(line, format!("invalid number `{raw}`")))?;
+                    ProtoOptionValue::Float(if sign == "-" { -value } else { value })
+                }
+            },
+            Tok::Ident(word) if word == "true" => ProtoOptionValue::Bool(true),
+            Tok::Ident(word) if word == "false" => ProtoOptionValue::Bool(false),
+            Tok::Ident(word) => ProtoOptionValue::Identifier(format!("{sign}{word}")),
+            other => {
+                return Err(error(
+                    line,
+                    format!("expected an option value, found {}", describe(&other)),
+                ))
+            }
+        }))
+    }
+
+    /// `option name = value;` after the `option` keyword.
+    fn option_statement(&mut self) -> Result<(String, Option<ProtoOptionValue>), ImportError> {
+        let name = self.option_name()?;
+        self.expect_sym('=')?;
+        let value = self.constant()?;
+        self.expect_sym(';')?;
+        Ok((name, value))
+    }
+
+    /// `[a = 1, (b).c = true]` after a field number, if present.
+    fn field_options(&mut self, owner: &str) -> Result<Vec<ProtoCustomOption>, ImportError> {
+        let mut options = Vec::new();
+        if !self.peek_sym('[') {
+            return Ok(options);
+        }
+        self.next()?;
+        loop {
+            let name = self.option_name()?;
+            self.expect_sym('=')?;
+            match self.constant()? {
+                Some(value) => options.push(ProtoCustomOption::new(name, value)),
+                None => self.report(
+                    format!("{owner} [{name}]"),
+                    "field option",
+                    "aggregate option values have no SEA counterpart",
+                ),
+            }
+            if self.peek_sym(',') {
+                self.next()?;
+            } else {
+                break;
+            }
+        }
+        self.expect_sym(']')?;
+        Ok(options)
+    }
+
+    fn parse_type(&mut self) -> Result<ProtoType, ImportError> {
+        if self.peek_is("map") {
+            self.next()?;
+            self.expect_sym('<')?;
+            let key = self.parse_type()?;
+            self.expect_sym(',')?;
+            let value = self.parse_type()?;
+            self.expect_sym('>')?;
+            return Ok(ProtoType::Map {
+                key: Box::new(key),
+                value: Box::new(value),
+            });
+        }
+        let name = self.ident()?;
+        Ok(match scalar(&name) {
+            Some(scalar) => ProtoType::Scalar(scalar),
+            None => ProtoType::Message(name.trim_start_matches('.').to_string()),
+        })
+    }
+
+    fn field(
+        &mut self,
+        path: &str,
+        mut comments: Vec<String>,
+        in_oneof: bool,
+    ) -> Result<ProtoField, ImportError> {
+        let line = self.line();
+        let mut repeated = false;
+        let mut optional = in_oneof;
+        if self.peek_is("repeated") {
+            self.next()?;
+            repeated = true;
+        } else if self.peek_is("optional") {
+            self.next()?;
+            optional = true;
+        } else if self.peek_is("required") || self.peek_is("group") {
+            return Err(error(line, "`required` and `group` are proto2 syntax"));
+        }
+        let proto_type = self.parse_type()?;
+        let name = self.ident()?;
+        self.expect_sym('=')?;
+        let number = self.field_number()?;
+        let options = self.field_options(&format!("{path}.{name}"))?;
+        self.expect_sym(';')?;
+        if let Some(trailing) = self.trailing() {
+            comments.extend(trailing.split("; ").map(str::to_string));
+        }
+        Ok(ProtoField {
+            name,
+            number,
+            proto_type,
+            repeated,
+            optional,
+            comments,
+            options,
+        })
+    }
+
+    fn reserved(&mut self, message: &mut ProtoMessage, path: &str) -> Result<(), ImportError> {
+        loop {
+            let line = self.line();
+            match self.peek() {
+                Some(Tok::Str(_)) => {
+                    let name = self.string()?;
+                    message.reserved_names.push(name);
+                }
+                _ => {
+                    let start = self.integer()?;
+                    let mut end = start;
+                    if self.peek_is("to") {
+                        self.next()?;
+                        if self.peek_is("max") {
+                            self.next()?;
+                            end = -1;
+                        } else {
+                            end = self.integer()?;
+                        }
+                    }
+                    let range = u32::try_from(start)
+                        .ok()
+                        .zip(u32::try_from(end).ok())
+                        .filter(|(s, e)| s <= e && e - s < MAX_RESERVED_RANGE);
+                    match range {
+                        Some((s, e)) => message.reserved_numbers.extend(s..=e),
+                        None if end < start && end != -1 => {
+                            return Err(error(line, "reserved range ends before it starts"))
+                        }
+                        None => self.report(
+                            format!(
+                                "{path} reserved {start} to {}",
+                                if end == -1 {
+                                    "max".to_string()
+                                } else {
+                                    end.to_string()
+                                }
+                            ),
+                            "reserved range",
+                            "only reserved ranges of up to 256 numbers are kept",
+                        ),
+                    }
+                }
+            }
+            if self.peek_sym(',') {
+                self.next()?;
+            } else {
+                break;
+            }
+        }
+        self.expect_sym(';')
+    }
+
+    fn message(&mut self, comments: Vec<String>, scope: &str) -> Result<ProtoMessage, ImportError> {
+        let name = self.ident()?;
+        let path = qualified(scope, &name);
+        let mut message = ProtoMessage::new(&name);
+        message.comments = comments;
+        self.expect_sym('{')?;
+        loop {
+            let comments = self.comments();
+            let line = self.line();
+            match self.peek() {
+                None => return Err(error(line, format!("message {path} is not closed"))),
+                Some(Tok::Sym('}')) => {
+                    self.next()?;
+                    break;
+                }
+                Some(Tok::Sym(';')) => {
+                    self.next()?;
+                }
+                Some(Tok::Ident(word)) => match word.as_str() {
+                    "message" => {
+                        self.next()?;
+                        let nested = self.message(comments, &path)?;
+                        message.nested_messages.push(nested);
+                    }
+                    "enum" => {
+                        self.next()?;
+                        let nested = self.enumeration(comments, &path)?;
+                        message.nested_enums.push(nested);
+                    }
+                    "oneof" => {
+                        self.next()?;
+                        let oneof = self.ident()?;
+                        self.report(
+                            format!("{path}.{oneof}"),
+                            "oneof",
+                            "SEA has no one-of constraint; the members are imported as optional fields",
+                        );
+                        self.expect_sym('{')?;
+                        loop {
+                            let comments = self.comments();
+                            if self.peek_sym('}') {
+                                self.next()?;
+                                break;
+                            }
+                            if self.peek_sym(';') {
+                                self.next()?;
+                            } else if self.peek_is("option") {
+                                self.next()?;
+                                self.skip_statement()?;
+                            } else {
+                                let field = self.field(&path, comments, true)?;
+                                message.fields.push(field);
+                            }
+                        }
+                    }
+                    "reserved" => {
+                        self.next()?;
+                        self.reserved(&mut message, &path)?;
+                    }
+                    "option" => {
+                        self.next()?;
+                        let (option, _) = self.option_statement()?;
+                        self.report(
+                            format!("{path} option {option}"),
+                            "message option",
+                            "message options have no SEA counterpart",
+                        );
+                    }
+                    "extensions" | "extend" => {
+                        let kind = word.clone();
+                        self.next()?;
+                        self.skip_statement()?;
+                        self.report(path.clone(), &kind, "extensions are proto2 features");
+                    }
+                    _ => {
+                        let field = self.field(&path, comments, false)?;
+                        message.fields.push(field);
+                    }
+                },
+                Some(other) => {
+                    let found = describe(other);
+                    return Err(error(line, format!("unexpected {found} in message {path}")));
+                }
+            }
+        }
+        Ok(message)
+    }
+
+    fn enumeration(
+        &mut self,
+        comments: Vec<String>,
+        scope: &str,
+    ) -> Result<ProtoEnum, ImportError> {
+        let name = self.ident()?;
+        let path = qualified(scope, &name);
+        let mut proto = ProtoEnum {
+            name,
+            values: Vec::new(),
+            comments,
+        };
+        self.expect_sym('{')?;
+        loop {
+            self.comments();
+            let line = self.line();
+            if self.peek().is_none() {
+                return Err(error(line, format!("enum {path} is not closed")));
+            }
+            if self.peek_sym('}') {
+                self.next()?;
+                break;
+            }
+            if self.peek_sym(';') {
+                self.next()?;
+            } else if self.peek_is("option") || self.peek_is("reserved") {
+                let kind = if self.peek_is("option") {
+                    "enum option"
+                } else {
+                    "enum reserved"
+                };
+                self.next()?;
+                self.skip_statement()?;
+                self.report(path.clone(), kind, "SEA enums carry only their members");
+            } else {
+                let value = self.ident()?;
+                self.expect_sym('=')?;
+                let number = self.integer()?;
+                let number = i32::try_from(number)
+                    .map_err(|_| error(line, format!("{number} is out of the enum range")))?;
+                if !self.field_options(&format!("{path}.{value}"))?.is_empty() {
+                    self.report(
+                        format!("{path}.{value}"),
+                        "enum value option",
+                        "SEA enum members carry no options",
+                    );
+                }
+                self.expect_sym(';')?;
+                self.trailing();
+                proto.values.push(ProtoEnumValue {
+                    name: value,
+                    number,
+                });
+            }
+        }
+        Ok(proto)
+    }
+
+    fn service(&mut self, comments: Vec<String>) -> Result<ProtoService, ImportError> {
+        let name = self.ident()?;
+        let mut service = ProtoService::new(&name);
+        service.comments = comments;
+        self.expect_sym('{')?;
+        loop {
+            let comments = self.comments();
+            let line = self.line();
+            if self.peek().is_none() {
+                return Err(error(line, format!("service {name} is not closed")));
+            }
+            if self.peek_sym('}') {
+                self.next()?;
+                break;
+            }
+            if self.peek_sym(';') {
+                self.next()?;
+            } else if self.peek_is("option") {
+                self.next()?;
+                let (option, _) = self.option_statement()?;
+                self.report(
+                    format!("{name} option {option}"),
+                    "service option",
+                    "service options have no SEA counterpart",
+                );
+            } else if self.peek_is("rpc") {
+                self.next()?;
+                let method = self.rpc(&name, comments)?;
+                service.methods.push(method);
+            } else {
+                let found = self.next().map(|t| describe(&t))?;
+                return Err(error(line, format!("unexpected {found} in service {name}")));
+            }
+        }
+        Ok(service)
+    }
+
+    /// `( [stream] Type )` of an rpc signature.
+    fn rpc_type(&mut self) -> Result<(bool, String), ImportError> {
+        self.expect_sym('(')?;
+        let stream = self.peek_is("stream");
+        if stream {
+            self.next()?;
+        }
+        let ty = self.ident()?.trim_start_matches('.').to_string();
+        self.expect_sym(')')?;
+        Ok((stream, ty))
+    }
+
+    fn rpc(&mut self, service: &str, comments: Vec<String>) -> Result<ProtoRpcMethod, ImportError> {
+        let name = self.ident()?;
+        let (client_stream, request) = self.rpc_type()?;
+        let line = self.line();
+        if self.ident()? != "returns" {
+            return Err(error(line, "expected `returns`"));
+        }
+        let (server_stream, response) = self.rpc_type()?;
+        let mut method = ProtoRpcMethod::new(&name, request, response);
+        method.comments = comments;
+        method.streaming = match (client_stream, server_stream) {
+            (false, false) => StreamingMode::Unary,
+            (false, true) => StreamingMode::ServerStreaming,
+            (true, false) => StreamingMode::ClientStreaming,
+            (true, true) => StreamingMode::Bidirectional,
+        };
+        if self.peek_sym('{') {
+            self.next()?;
+            loop {
+                self.comments();
+                if self.peek_sym('}') {
+                    self.next()?;
+                    break;
+                }
+                if self.peek_sym(';') {
+                    self.next()?;
+                    continue;
+                }
+                let line = self.line();
+                if self.ident()? != "option" {
+                    return Err(error(line, "expected `option` in an rpc body"));
+                }
+                let (option, value) = self.option_statement()?;
+                match value {
+                    Some(value) => method.options.push(ProtoCustomOption::new(option, value)),
+                    None => self.report(
+                        format!("{service}.{name} option {option}"),
+                        "rpc option",
+                        "aggregate option values have no SEA counterpart",
+                    ),
+                }
+            }
+        } else {
+            self.expect_sym(';')?;
+        }
+        Ok(method)
+    }
+}
+
+fn describe(tok: &Tok) -> String {
+    match tok {
+        Tok::Ident(word) => format!("`{word}`"),
+        Tok::Number(raw) => format!("`{raw}`"),
+        Tok::Str(text) => format!("\"{text}\""),
+        Tok::Sym(sym) => format!("`{sym}`"),
+        Tok::Comment { .. } => "a comment".to_string(),
+    }
+}
+
+fn parse_int(raw: &str) -> Option<i64> {
+    if let Some(hex) = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()
+    } else if raw.len() > 1 && raw.starts_with('0') {
+        i64::from_str_radix(&raw[1..], 8).ok()
+    } else {
+        raw.parse().ok()
+    }
+}
+
+fn qualified(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+fn scalar(name: &str) -> Option<ScalarType> {
+    Some(match name {
+        "double" => ScalarType::Double,
+        "float" => ScalarType::Float,
+        "int32" => ScalarType::Int32,
+        "int64" => ScalarType::Int64,
+        "uint32" => ScalarType::Uint32,
+        "uint64" => ScalarType::Uint64,
+        "sint32" => ScalarType::Sint32,
+        "sint64" => ScalarType::Sint64,
+        "fixed32" => ScalarType::Fixed32,
+        "fixed64" => ScalarType::Fixed64,
+        "sfixed32" => ScalarType::Sfixed32,
+        "sfixed64" => ScalarType::Sfixed64,
+        "bool" => ScalarType::Bool,
+        "string" => ScalarType::String,
+        "bytes" => ScalarType::Bytes,
+        _ => return None,
+    })
+}
+
+/// Resolve a type reference written inside `scope` (a dotted message path)
+/// against the file's declared full names, innermost scope first. A prefix
+/// naming the file's own package is dropped first.
+pub(crate) fn resolve_type(
+    declared: &BTreeSet<String>,
+    package: &str,
+    scope: &str,
+    name: &str,
+) -> Option<String> {
+    let name = match name.strip_prefix(package).and_then(|n| n.strip_prefix('.')) {
+        Some(local) if !package.is_empty() => local,
+        _ => name,
+    };
+    let mut scope = scope.to_string();
+    loop {
+        let candidate = qualified(&scope, name);
+        if declared.contains(&candidate) {
+            return Some(candidate);
+        }
+        if scope.is_empty() {
+            return None;
+        }
+        scope = scope
+            .rsplit_once('.')
+            .map(|(outer, _)| outer.to_string())
+            .unwrap_or_default();
+    }
+}
+
+/// Full names of every message and enum declared in `file`.
+pub(crate) fn declared_names(file: &ProtoFile) -> (BTreeSet<String>, BTreeSet<String>) {
+    fn walk(
+        message: &ProtoMessage,
+        scope: &str,
+        messages: &mut BTreeSet<String>,
+        enums: &mut BTreeSet<String>,
+    ) {
+        let path = qualified(scope, &message.name);
+        for nested in &message.nested_enums {
+            enums.insert(qualified(&path, &nested.name));
+        }
+        for nested in &message.nested_messages {
+            walk(nested, &path, messages, enums);
+        }
+        messages.insert(path);
+    }
+    let mut messages = BTreeSet::new();
+    let mut enums: BTreeSet<String> = file.enums.iter().map(|e| e.name.clone()).collect();
+    for message in &file.messages {
+        walk(message, "", &mut messages, &mut enums);
+    }
+    (messages, enums)
+}
+
+/// Mark enum-typed fields as [`ProtoType::Enum`] and drop the file's own
+/// package from type references, matching what the projection writes.
+fn link_types(file: &mut ProtoFile) {
+    fn link(ty: &mut ProtoType, enums: &BTreeSet<String>, package: &str, scope: &str) {
+        match ty {
+            ProtoType::Message(name) => {
+                if let Some(local) = name
+                    .strip_prefix(package)
+                    .and_then(|n| n.strip_prefix('.'))
+                    .filter(|_| !package.is_empty())
+                {
+                    *name = local.to_string();
+                }
+                if resolve_type(enums, package, scope, name).is_some() {
+                    *ty = ProtoType::Enum(name.clone());
+                }
+            }
+            ProtoType::Map { value, .. } => link(value, enums, package, scope),
+            _ => {}
+        }
+    }
+    fn walk(message: &mut ProtoMessage, scope: &str, enums: &BTreeSet<String>, package: &str) {
+        let path = qualified(scope, &message.name);
+        for field in &mut message.fields {
+            link(&mut field.proto_type, enums, package, &path);
+        }
+        for nested in &mut message.nested_messages {
+            walk(nested, &path, enums, package);
+        }
+    }
+    let (_, enums) = declared_names(file);
+    let package = file.package.clone();
+    for message in &mut file.messages {
+        walk(message, "", &enums, &package);
+    }
+    for service in &mut file.services {
+        for method in &mut service.methods {
+            for ty in [&mut method.request_type, &mut method.response_type] {
+                if let Some(local) = ty
+                    .strip_prefix(package.as_str())
+                    .and_then(|n| n.strip_prefix('.'))
+                    .filter(|_| !package.is_empty())
+                {
+                    *ty = local.to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Parse a proto3 file. proto2 and editions files are rejected.
+pub(crate) fn parse_proto(source: &str) -> Result<ParsedProto, ImportError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        unmapped: Vec::new(),
+    };
+    let mut file = ProtoFile::new("");
+    let mut syntax = None;
+    loop {
+        let comments = parser.comments();
+        let line = parser.line();
+        let Some(Tok::Ident(word)) = parser.peek().cloned() else {
+            match parser.peek() {
+                None => break,
+                Some(Tok::Sym(';')) => {
+                    parser.next()?;
+                    continue;
+                }
+                Some(other) => {
+                    let found = describe(other);
+                    return Err(error(line, format!("unexpected {found}")));
+                }
+            }
+        };
+        parser.next()?;
+        match word.as_str() {
+            "syntax" => {
+                parser.expect_sym('=')?;
+                let declared = parser.string()?;
+                if declared != "proto3" {
+                    return Err(ImportError::Other(format!(
+                        "Only proto3 files are imported; this file declares syntax \"{declared}\""
+                    )));
+                }
+                syntax = Some(declared);
+                parser.expect_sym(';')?;
+            }
+            "edition" => return Err(error(line, "editions files are not supported; use proto3")),
+            "package" => {
+                file.package = parser.ident()?;
+                parser.expect_sym(';')?;
+            }
+            "import" => {
+                if parser.peek_is("public") || parser.peek_is("weak") {
+                    parser.next()?;
+                }
+                file.imports.push(parser.string()?);
+                parser.expect_sym(';')?;
+            }
+            "option" => {
+                let (name, value) = parser.option_statement()?;
+                match value {
+                    Some(value) => file.options.set_option(&name, value),
+                    None => parser.report(
+                        format!("option {name}"),
+                        "file option",
+                        "aggregate option values have no SEA counterpart",
+                    ),
+                }
+            }
+            "message" => {
+                let message = parser.message(comments, "")?;
+                file.messages.push(message);
+            }
+            "enum" => {
+                let proto = parser.enumeration(comments, "")?;
+                file.enums.push(proto);
+            }
+            "service" => {
+                let service = parser.service(comments)?;
+                file.services.push(service);
+            }
+            "extend" => {
+                let target = parser.ident()?;
+                parser.skip_block()?;
+                parser.report(
+                    format!("extend {target}"),
+                    "extend",
+                    "extensions are proto2 features",
+                );
+            }
+            other => return Err(error(line, format!("unexpected `{other}`"))),
+        }
+        if syntax.is_none() {
+            return Err(error(
+                line,
+                "the file must start with `syntax = \"proto3\";`",
+            ));
+        }
+    }
+    if syntax.is_none() {
+        return Err(ImportError::Other(
+            "Only proto3 files are imported; this file declares no syntax".to_string(),
+        ));
+    }
+    link_types(&mut file);
+    Ok(ParsedProto {
+        file,
+        unmapped: parser.unmapped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_messages_enums_services_and_comments() {
+        let parsed = parse_proto(
+            r#"
+// Orders API
+syntax = "proto3";
+package acme.orders.v1;
+
+import "google/protobuf/timestamp.proto";
+option go_package = "acme/orders";
+
+/* Order lifecycle */
+enum Status {
+  STATUS_UNSPECIFIED = 0;
+  STATUS_OPEN = 1; // still editable
+}
+
+message Order {
+  reserved 4, 8 to 9;
+  reserved "legacy";
+  string id = 1 [(buf.validate.field).string.uuid = true];
+  optional .acme.orders.v1.Status status = 2; // min 0; decimal: canonical decimal string
+  repeated google.protobuf.Timestamp events = 3;
+  map<string, int64> counts = 5;
+  oneof choice {
+    string note = 6;
+  }
+}
+
+service OrderService {
+  rpc Watch(Order) returns (stream Order) {
+    option idempotency_level = NO_SIDE_EFFECTS;
+  }
+}
+"#,
+        )
+        .unwrap();
+        let file = parsed.file;
+        assert_eq!(file.package, "acme.orders.v1");
+        assert_eq!(file.options.go_package.as_deref(), Some("acme/orders"));
+        assert_eq!(file.enums[0].comments, ["Order lifecycle"]);
+        assert_eq!(file.enums[0].values[1].name, "STATUS_OPEN");
+
+        let order = &file.messages[0];
+        assert_eq!(order.reserved_numbers, [4, 8, 9]);
+        assert_eq!(order.reserved_names, ["legacy"]);
+        assert_eq!(
+            order.fields[0].options[0].name,
+            "(buf.validate.field).string.uuid"
+        );
+        assert_eq!(order.fields[1].proto_type, ProtoType::Enum("Status".into()));
+        assert!(order.fields[1].optional);
+        assert_eq!(
+            order.fields[1].comments,
+            ["min 0", "decimal: canonical decimal string"]
+        );
+        assert!(order.fields[2].repeated);
+        assert!(matches!(order.fields[3].proto_type, ProtoType::Map { .. }));
+        assert!(order.fields[4].optional, "oneof members become optional");
+
+        let watch = &file.services[0].methods[0];
+        assert_eq!(watch.streaming, StreamingMode::ServerStreaming);
+        assert_eq!(
+            watch.options[0].value,
+            ProtoOptionValue::Identifier("NO_SIDE_EFFECTS".into())
+        );
+        let kinds: Vec<&str> = parsed.unmapped.iter().map(|u| u.kind.as_str()).collect();
+        assert_eq!(kinds, ["oneof"]);
+    }
+
+    #[test]
+    fn rejects_proto2_and_reports_the_line_of_syntax_errors() {
+        let err =
+            parse_proto("syntax = \"proto2\";\nmessage A { required string a = 1; }").unwrap_err();
+        assert!(err.to_string().contains("syntax \"proto2\""), "{err}");
+        let err = parse_proto("message A {}").unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{err}");
+        let err = parse_proto("syntax = \"proto3\";\nmessage A { string a = ; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid proto3 at line 2: expected a number, found `;`"
+        );
+    }
+}
@@ -0,0 +1,1669 @@
+//! Protobuf importer: reads a proto3 file and writes the SEA model it
+//! describes, then re-projects that model through [`ProtobufEngine`] and
+//! compares the result with the input.
+//!
+//! Mapping ([`ProtoImportMode::Contract`], the default):
+//! - `enum` → `enum`; the zero value is dropped (the projection writes its
+//!   own `<ENUM>_UNSPECIFIED = 0`) and the `<ENUM>_` prefix is stripped from
+//!   member names
+//! - `message` → `record`; nested messages and enums are hoisted and named
+//!   after their path (`Order.Line` → `OrderLine`)
+//! - scalar fields: `string` → `string` (`uuid` with a `string.uuid` rule),
+//!   integer types → `int`, `double`/`float` → `decimal`, `bool` → `bool`;
+//!   `google.protobuf.Timestamp` → `timestamp`, wrapper types → an optional
+//!   scalar; `repeated` → `list<T>`, proto3 `optional` → `optional`
+//! - protovalidate `(buf.validate.field)` rules → field constraints (the
+//!   inverse of the contract projection's mapping)
+//! - each `service` → the `operation`s of the entity it is named after
+//!   (`OrderOperationsService` and `OrderService` both act on `Order`); one
+//!   unary `rpc` per operation, its request and response messages being the
+//!   input and output records
+//!
+//! [`ProtoImportMode::Legacy`] reads the graph projection's shape instead:
+//! messages become bodyless entities (or resources, for rpc request types),
+//! and each rpc becomes a flow of its request into the service's entity.
+//!
+//! Declarations the projections themselves write are recognised in either
+//! mode by their comments (`SEA Entity:`, `SEA Resource:`, `Flow:`, the
+//! operation clause comments, `<Op>FailureCode`/`<Op>Error` failure details,
+//! and the governance messages), so a file projected from SEA imports back
+//! to the model it came from. Clauses the file does not state are inferred
+//! from rpc names and listed for review.
+//!
+//! The imported source is then re-projected and compared with the input
+//! declaration by declaration, ignoring comments; the differences (or their
+//! absence) are the report's round-trip section.
+
+use super::proto_parser::{declared_names, parse_proto, resolve_type};
+use super::schema::snake;
+use super::{print_sea, ImportReport, SeaImport, UnmappedElement};
+use crate::application::{resolve_application_contract, resolve_application_graph};
+use crate::kg_import::ImportError;
+use crate::parser::ast::{
+    AstNode, EntityBody, EnumDecl, EnumMember, FieldConstraintDecl, FieldDecl, FieldType,
+    FieldTypeRef, OperationClause, OperationDecl, RecordDecl,
+};
+use crate::policy::Expression;
+use crate::projection::ids::{ident, pascal, slug};
+use crate::projection::protobuf::{
+    to_screaming_snake_case, ProtoEnum, ProtoField, ProtoFile, ProtoMessage, ProtoOptionValue,
+    ProtoRpcMethod, ProtoService, ProtoType, ProtobufEngine, ScalarType, StreamingMode,
+    WellKnownType,
+};
+use crate::projection::protobuf_contract::{merge_application_contract, FAILURE_KIND_ENUM};
+use rust_decimal::Decimal;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+
+/// Logical path the imported source is resolved under.
+const CHECK_ENTRY: &str = "protobuf.sea";
+
+/// Prefix of protovalidate field rules.
+const FIELD_RULES: &str = "(buf.validate.field).";
+
+/// Comment the graph projection puts on flow rpcs.
+const FLOW_COMMENT: &str = "Flow: ";
+
+/// What the messages and services of the file become.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProtoImportMode {
+    /// Records, enums, and operations (the application contract).
+    #[default]
+    Contract,
+    /// Entities, resources, and flows (the graph projection's shape).
+    Legacy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Creates,
+    Mutates,
+    Reads,
+}
+
+impl Effect {
+    fn parse(keyword: &str) -> Option<Self> {
+        match keyword {
+            "creates" => Some(Effect::Creates),
+            "mutates" => Some(Effect::Mutates),
+            "reads" => Some(Effect::Reads),
+            _ => None,
+        }
+    }
+
+    /// Inferred from the leading verb of an rpc name.
+    fn of_rpc(snake_name: &str) -> Self {
+        let verb = snake_name.split('_').next().unwrap_or_default();
+        match verb {
+            "get" | "list" | "read" | "find" | "search" | "fetch" | "lookup" | "query"
+            | "describe" | "watch" | "count" | "check" => Effect::Reads,
+            "create" | "add" | "register" | "place" | "submit" | "insert" | "new" | "open"
+            | "start" => Effect::Creates,
+            _ => Effect::Mutates,
+        }
+    }
+
+    fn keyword(self) -> &'static str {
+        match self {
+            Effect::Creates => "creates",
+            Effect::Mutates => "mutates",
+            Effect::Reads => "reads",
+        }
+    }
+}
+
+/// Synthesized failure groups in clause order: code suffix and kinds.
+const FAILURE_GROUPS: [(&str, &[&str]); 3] = [
+    ("invalid_input", &["input_validation"]),
+    ("not_found", &["missing_state"]),
+    (
+        "conflict",
+        &["idempotency_conflict", "concurrency_conflict"],
+    ),
+];
+
+/// A typed or bodyless entity being imported.
+struct EntityDraft {
+    name: String,
+    key: Option<FieldDecl>,
+    fields: Vec<FieldDecl>,
+    /// Whether an operations service acts on it (it gets a typed body).
+    state: bool,
+    /// Declared from a `SEA Entity:` message, the graph projection's
+    /// `id`/`name` shell rather than a contract entity.
+    shell: bool,
+}
+
+struct Importer<'a> {
+    file: &'a ProtoFile,
+    mode: ProtoImportMode,
+    /// Full names of every message and enum in the file.
+    declared: BTreeSet<String>,
+    messages: BTreeMap<String, &'a ProtoMessage>,
+    enum_defs: BTreeMap<String, &'a ProtoEnum>,
+    /// Full proto name → SEA record or enum name.
+    types: HashMap<String, String>,
+    names: BTreeSet<String>,
+    patterns: Vec<(String, String)>,
+    enums: Vec<EnumDecl>,
+    records: Vec<RecordDecl>,
+    entities: Vec<EntityDraft>,
+    resources: Vec<(String, String)>,
+    flows: Vec<AstNode>,
+    operations: Vec<OperationDecl>,
+    unmapped: Vec<UnmappedElement>,
+    review: Vec<String>,
+    include_governance: bool,
+    include_services: bool,
+}
+
+/// Import a proto3 file as SEA source under `namespace` (default: the
+/// namespace a projected file records, else the package).
+pub fn import_protobuf(
+    source: &str,
+    namespace: Option<&str>,
+    mode: ProtoImportMode,
+) -> Result<SeaImport, ImportError> {
+    let parsed = parse_proto(source)?;
+    let file = &parsed.file;
+    let namespace = namespace
+        .map(str::to_string)
+        .or_else(|| {
+            file.messages
+                .iter()
+                .find_map(|m| comment_value(&m.comments, "Namespace: "))
+                .filter(|ns| !ns.is_empty())
+                .map(str::to_string)
+        })
+        .or_else(|| Some(file.package.clone()).filter(|p| !p.is_empty()))
+        .map_or_else(|| "imported".to_string(), |raw| ident(&slug(&raw)));
+
+    let (message_names, enum_names) = declared_names(file);
+    let mut importer = Importer {
+        file,
+        mode,
+        declared: message_names.union(&enum_names).cloned().collect(),
+        messages: BTreeMap::new(),
+        enum_defs: BTreeMap::new(),
+        types: HashMap::new(),
+        names: BTreeSet::new(),
+        patterns: Vec::new(),
+        enums: Vec::new(),
+        records: Vec::new(),
+        entities: Vec::new(),
+        resources: Vec::new(),
+        flows: Vec::new(),
+        operations: Vec::new(),
+        unmapped: parsed.unmapped.clone(),
+        review: Vec::new(),
+        include_governance: false,
+        include_services: false,
+    };
+    importer.collect();
+    for import in &file.imports {
+        let known = import.starts_with("google/protobuf/") || import.starts_with("buf/validate/");
+        if !known {
+            importer.unmapped.push(UnmappedElement {
+                id: format!("import \"{import}\""),
+                kind: "import".to_string(),
+                name: None,
+                reason: "types from other files are not imported; import that file first"
+                    .to_string(),
+            });
+        }
+    }
+    importer.run();
+
+    let Importer {
+        patterns,
+        enums,
+        records,
+        entities,
+        resources,
+        flows,
+        operations,
+        unmapped,
+        mut review,
+        include_governance,
+        include_services,
+        ..
+    } = importer;
+    let mut report = ImportReport {
+        format: "Protobuf (proto3)".to_string(),
+        entities: entities.len(),
+        resources: resources.len(),
+        flows: flows.len(),
+        records: records.len(),
+        enums: enums.len(),
+        operations: operations.len(),
+        unmapped,
+        ..ImportReport::default()
+    };
+    let mut declarations: Vec<AstNode> = patterns
+        .into_iter()
+        .map(|(name, regex)| AstNode::Pattern { name, regex })
+        .collect();
+    declarations.extend(enums.into_iter().map(AstNode::Enum));
+    declarations.extend(records.into_iter().map(AstNode::Record));
+    for entity in entities {
+        let body = entity.state.then(|| {
+            let mut fields: Vec<FieldDecl> = entity.key.into_iter().collect();
+            fields.extend(entity.fields);
+            EntityBody { fields }
+        });
+        declarations.push(AstNode::Entity {
+            name: entity.name,
+            version: None,
+            annotations: HashMap::new(),
+            domain: None,
+            body,
+        });
+    }
+    declarations.extend(resources.into_iter().map(|(name, unit)| AstNode::Resource {
+        name,
+        annotations: HashMap::new(),
+        unit_name: Some(unit),
+        domain: None,
+    }));
+    declarations.extend(flows);
+    declarations.extend(operations.into_iter().map(AstNode::Operation));
+    let source = print_sea(&namespace, declarations);
+
+    // Same as the OpenAPI importer: what the resolver still reports is left
+    // for the modeller, and blocks the round trip.
+    let sources = serde_json::json!({ CHECK_ENTRY: &source }).to_string();
+    match resolve_application_contract(CHECK_ENTRY, &sources) {
+        Err(diagnostics) => review.extend(
+            diagnostics
+                .iter()
+                .map(|d| format!("{} {}", d.code.code(), d.message)),
+        ),
+        Ok(doc) => {
+            let projected = resolve_application_graph(CHECK_ENTRY, &sources)
+                .map_err(|d| {
+                    d.iter()
+                        .map(|d| d.message.clone())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+                .and_then(|graph| {
+                    let mut projected = ProtobufEngine::project_with_full_options(
+                        &graph,
+                        "",
+                        &file.package,
+                        "",
+                        include_governance,
+                        include_services,
+                    );
+                    merge_application_contract(&mut projected, &graph, &doc.contract, "")
+                        .map(|()| projected)
+                });
+            match projected {
+                Ok(projected) => report.round_trip = Some(declaration_diff(file, &projected)),
+                Err(reason) => {
+                    review.push(format!("the imported source does not project: {reason}"))
+                }
+            }
+        }
+    }
+    report.review = review;
+    Ok(SeaImport { source, report })
+}
+
+/// Differences between the declarations of `original` and `projected`,
+/// ignoring comments, declaration order, and file-level statements.
+pub fn declaration_diff(original: &ProtoFile, projected: &ProtoFile) -> Vec<String> {
+    let rendered = |file: &ProtoFile| -> BTreeMap<String, String> {
+        let mut out = BTreeMap::new();
+        for proto in &file.enums {
+            let mut proto = proto.clone();
+            proto.comments.clear();
+            out.insert(format!("enum {}", proto.name), proto.to_proto_string());
+        }
+        for message in &file.messages {
+            let message = strip_message_comments(message);
+            out.insert(
+                format!("message {}", message.name),
+                message.to_proto_string(),
+            );
+        }
+        for service in &file.services {
+            let mut service = service.clone();
+            service.comments.clear();
+            for method in &mut service.methods {
+                method.comments.clear();
+            }
+            out.insert(
+                format!("service {}", service.name),
+                service.to_proto_string(),
+            );
+        }
+        out
+    };
+    let before = rendered(original);
+    let after = rendered(projected);
+    let mut diff = Vec::new();
+    for (name, text) in &before {
+        match after.get(name) {
+            None => diff.push(format!("{name} is not reproduced")),
+            Some(other) if other != text => {
+                let mut old_lines = text.lines();
+                let mut new_lines = other.lines();
+                let (old, new) = loop {
+                    match (old_lines.next(), new_lines.next()) {
+                        (Some(a), Some(b)) if a == b => continue,
+                        (a, b) => break (a.unwrap_or("").trim(), b.unwrap_or("").trim()),
+                    }
+                };
+                diff.push(format!("{name} differs: `{old}` becomes `{new}`"));
+            }
+            Some(_) => {}
+        }
+    }
+    for name in after.keys().filter(|name| !before.contains_key(*name)) {
+        diff.push(format!("{name} is added by the projection"));
+    }
+    diff
+}
+
+fn strip_message_comments(message: &ProtoMessage) -> ProtoMessage {
+    let mut message = message.clone();
+    message.comments.clear();
+    for field in &mut message.fields {
+        field.comments.clear();
+    }
+    for nested in &mut message.nested_enums {
+        nested.comments.clear();
+    }
+    message.nested_messages = message
+        .nested_messages
+        .iter()
+        .map(strip_message_comments)
+        .collect();
+    message
+}
+
+/// The text after `prefix` in the first comment starting with it.
+fn comment_value<'c>(comments: &'c [String], prefix: &str) -> Option<&'c str> {
+    comments.iter().find_map(|c| c.strip_prefix(prefix))
+}
+
+fn scalar(symbol: &str) -> FieldType {
+    FieldType::Scalar(FieldTypeRef {
+        alias: None,
+        symbol: symbol.to_string(),
+    })
+}
+
+fn named(symbol: &str) -> FieldType {
+    FieldType::Named(FieldTypeRef {
+        alias: None,
+        symbol: symbol.to_string(),
+    })
+}
+
+fn option_decimal(value: &ProtoOptionValue) -> Option<Decimal> {
+    match value {
+        ProtoOptionValue::Int(v) => Some(Decimal::from(*v)),
+        ProtoOptionValue::Float(v) => Decimal::from_str(&v.to_string())
+            .ok()
+            .map(|d| d.normalize()),
+        _ => None,
+    }
+}
+
+fn option_count(value: &ProtoOptionValue) -> Option<u64> {
+    match value {
+        ProtoOptionValue::Int(v) => u64::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+/// The name an entity or service gets without its projection suffix.
+fn service_subject(service: &ProtoService) -> String {
+    comment_value(&service.comments, "Application operations on ")
+        .or_else(|| comment_value(&service.comments, "gRPC service for "))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let name = &service.name;
+            name.strip_suffix("OperationsService")
+                .or_else(|| name.strip_suffix("Service"))
+                .filter(|n| !n.is_empty())
+                .unwrap_or(name)
+                .to_string()
+        })
+}
+
+impl<'a> Importer<'a> {
+    /// Index every message and enum by full name.
+    fn collect(&mut self) {
+        fn walk<'m>(
+            message: &'m ProtoMessage,
+            scope: &str,
+            messages: &mut BTreeMap<String, &'m ProtoMessage>,
+            enums: &mut BTreeMap<String, &'m ProtoEnum>,
+        ) {
+            let path = if scope.is_empty() {
+                message.name.clone()
+            } else {
+                format!("{scope}.{}", message.name)
+            };
+            for nested in &message.nested_enums {
+                enums.insert(format!("{path}.{}", nested.name), nested);
+            }
+            for nested in &message.nested_messages {
+                walk(nested, &path, messages, enums);
+            }
+            messages.insert(path, message);
+        }
+        for proto in &self.file.enums {
+            self.enum_defs.insert(proto.name.clone(), proto);
+        }
+        for message in &self.file.messages {
+            walk(message, "", &mut self.messages, &mut self.enum_defs);
+        }
+    }
+
+    fn declare(&mut self, raw: &str) -> String {
+        let base = ident(raw);
+        let mut name = base.clone();
+        let mut n = 1;
+        while !self.names.insert(name.clone()) {
+            n += 1;
+            name = format!("{base}{n}");
+        }
+        name
+    }
+
+    fn report(&mut self, id: String, kind: &str, reason: impl Into<String>) {
+        self.unmapped.push(UnmappedElement {
+            id,
+            kind: kind.to_string(),
+            name: None,
+            reason: reason.into(),
+        });
+    }
+
+    fn resolve(&self, scope: &str, name: &str) -> Option<String> {
+        resolve_type(&self.declared, &self.file.package, scope, name)
+    }
+
+    fn is_flow_service(&self, service: &ProtoService) -> bool {
+        self.mode == ProtoImportMode::Legacy
+            || (!service.methods.is_empty()
+                && service
+                    .methods
+                    .iter()
+                    .all(|m| comment_value(&m.comments, FLOW_COMMENT).is_some()))
+    }
+
+    fn run(&mut self) {
+        let file = self.file;
+        let operation_rpcs: BTreeSet<&str> = file
+            .services
+            .iter()
+            .filter(|s| !self.is_flow_service(s))
+            .flat_map(|s| s.methods.iter().map(|m| m.name.as_str()))
+            .collect();
+        let flow_services: Vec<&ProtoService> = file
+            .services
+            .iter()
+            .filter(|s| self.is_flow_service(s))
+            .collect();
+        self.include_services = !flow_services.is_empty();
+
+        // Messages and enums the projections derive rather than declare.
+        let mut derived: BTreeSet<String> = BTreeSet::new();
+        for name in &operation_rpcs {
+            derived.insert(format!("{name}FailureCode"));
+            derived.insert(format!("{name}Error"));
+        }
+        if !operation_rpcs.is_empty() {
+            derived.insert(FAILURE_KIND_ENUM.to_string());
+        }
+        for (name, message) in &self.messages {
+            let response = comment_value(&message.comments, "Response message for ").is_some()
+                && flow_services
+                    .iter()
+                    .flat_map(|s| s.methods.iter())
+                    .any(|m| &m.response_type == name);
+            let governance = matches!(
+                (name.as_str(), message.comments.first().map(String::as_str)),
+                (
+                    "PolicyViolation",
+                    Some("Represents a policy violation event")
+                ) | ("MetricEvent", Some("Represents a metric measurement event"))
+            );
+            if governance {
+                self.include_governance = true;
+            }
+            if response || governance {
+                derived.insert(name.clone());
+            }
+        }
+        let flow_requests: BTreeSet<String> = flow_services
+            .iter()
+            .flat_map(|s| s.methods.iter())
+            .filter_map(|m| self.resolve("", &m.request_type))
+            .collect();
+
+        let enum_defs: Vec<(String, &ProtoEnum)> = self
+            .enum_defs
+            .iter()
+            .filter(|(name, _)| !derived.contains(*name))
+            .map(|(name, proto)| (name.clone(), *proto))
+            .collect();
+        for (full, _) in &enum_defs {
+            self.enum_name(full);
+        }
+        let messages: Vec<(String, &ProtoMessage)> = self
+            .messages
+            .iter()
+            .filter(|(name, _)| !derived.contains(*name))
+            .map(|(name, message)| (name.clone(), *message))
+            .collect();
+        // Names first so records can reference records declared later.
+        let mut records = Vec::new();
+        for (full, message) in &messages {
+            let resource = comment_value(&message.comments, "SEA Resource: ").is_some()
+                || (self.mode == ProtoImportMode::Legacy && flow_requests.contains(full));
+            if resource {
+                self.resource(full, message);
+            } else if comment_value(&message.comments, "SEA Entity: ").is_some()
+                || self.mode == ProtoImportMode::Legacy
+            {
+                self.legacy_entity(full, message);
+            } else {
+                let name = self.declare(&pascal(full));
+                if full.contains('.') {
+                    self.review.push(format!(
+                        "nested message {full} is imported as record {name}"
+                    ));
+                }
+                self.types.insert(full.clone(), name.clone());
+                records.push((full.clone(), *message, name));
+            }
+        }
+        for (full, proto) in &enum_defs {
+            self.enumeration(full, proto);
+        }
+        for (full, message, name) in records {
+            self.record(&full, message, name);
+        }
+
+        for service in &file.services {
+            if self.is_flow_service(service) {
+                self.flow_service(service);
+            } else {
+                self.operation_service(service);
+            }
+        }
+    }
+
+    fn enum_name(&mut self, full: &str) -> String {
+        let name = self.declare(&pascal(full));
+        if full.contains('.') {
+            self.review
+                .push(format!("nested enum {full} is imported as enum {name}"));
+        }
+        self.types.insert(full.to_string(), name.clone());
+        name
+    }
+
+    fn enumeration(&mut self, full: &str, proto: &ProtoEnum) {
+        let name = self.types[full].clone();
+        let prefix = format!("{}_", to_screaming_snake_case(&proto.name));
+        let mut members: Vec<EnumMember> = Vec::new();
+        for value in &proto.values {
+            if value.number == 0 {
+                continue;
+            }
+            let member = ident(
+                &value
+                    .name
+                    .strip_prefix(&prefix)
+                    .unwrap_or(&value.name)
+                    .to_lowercase(),
+            );
+            if members.iter().any(|m| m.name == member) {
+                self.report(
+                    format!("{full}.{}", value.name),
+                    "enum value",
+                    "an alias of an earlier member has no SEA counterpart",
+                );
+                continue;
+            }
+            let wire_prefix = format!("{}: wire \"", value.name);
+            let wire = comment_value(&proto.comments, &wire_prefix)
+                .and_then(|w| w.strip_suffix('"'))
+                .map_or_else(|| member.clone(), str::to_string);
+            members.push(EnumMember { name: member, wire });
+        }
+        if members.is_empty() {
+            self.report(
+                full.to_string(),
+                "enum",
+                "an enum with only its zero value has no SEA counterpart",
+            );
+            self.types.remove(full);
+            return;
+        }
+        self.enums.push(EnumDecl { name, members });
+    }
+
+    fn legacy_entity(&mut self, full: &str, message: &ProtoMessage) {
+        let raw = comment_value(&message.comments, "SEA Entity: ").unwrap_or(&message.name);
+        let name = self.declare(raw);
+        self.standard_fields(full, message, &["id", "name"]);
+        self.entities.push(EntityDraft {
+            name,
+            key: None,
+            fields: Vec::new(),
+            state: false,
+            shell: true,
+        });
+    }
+
+    fn resource(&mut self, full: &str, message: &ProtoMessage) {
+        let raw = comment_value(&message.comments, "SEA Resource: ").unwrap_or(&message.name);
+        let name = self.declare(raw);
+        let unit = comment_value(&message.comments, "Unit: ")
+            .filter(|u| !u.is_empty())
+            .unwrap_or("units")
+            .to_string();
+        self.standard_fields(full, message, &["id", "name", "quantity", "unit"]);
+        self.resources.push((name, unit));
+    }
+
+    /// Report the fields an entity or resource message carries beyond the
+    /// ones the graph projection writes for every such message.
+    fn standard_fields(&mut self, full: &str, message: &ProtoMessage, standard: &[&str]) {
+        for field in &message.fields {
+            if !standard.contains(&field.name.as_str()) {
+                self.report(
+                    format!("{full}.{}", field.name),
+                    "field",
+                    "entities and resources carry no typed fields in the graph projection",
+                );
+            }
+        }
+    }
+
+    fn record(&mut self, full: &str, message: &ProtoMessage, name: String) {
+        if !message.reserved_numbers.is_empty() || !message.reserved_names.is_empty() {
+            self.report(
+                full.to_string(),
+                "reserved",
+                "records do not reserve field numbers or names",
+            );
+        }
+        let mut fields = Vec::new();
+        for field in &message.fields {
+            if let Some(decl) = self.field(full, &name, field) {
+                fields.push(decl);
+            }
+        }
+        self.records.push(RecordDecl { name, fields });
+    }
+
+    /// The SEA type of a field's element, whether it is optional by
+    /// construction (wrapper types), and the rule family its options use.
+    fn element_type(
+        &mut self,
+        owner: &str,
+        field: &ProtoField,
+        at: &str,
+    ) -> Option<(FieldType, bool)> {
+        let rules = |name: &str| {
+            field
+                .options
+                .iter()
+                .any(|o| o.name.starts_with(FIELD_RULES) && o.name.ends_with(name))
+        };
+        let has_comment = |text: &str| field.comments.iter().any(|c| c == text);
+        let lossy = |this: &mut Self, from: &str, to: &str| {
+            this.review.push(format!(
+                "field {at} is {from} in the file and {to} in SEA; the projection changes its wire type"
+            ));
+        };
+        let ty = match &field.proto_type {
+            ProtoType::Scalar(ScalarType::String) => {
+                if rules("string.uuid") {
+                    scalar("uuid")
+                } else if has_comment("decimal: canonical decimal string") {
+                    scalar("decimal")
+                } else if has_comment("quantity: canonical decimal in the base unit") {
+                    self.review.push(format!(
+                        "field {at} carries a quantity; it is imported as decimal because the unit is not recorded"
+                    ));
+                    scalar("decimal")
+                } else {
+                    scalar("string")
+                }
+            }
+            ProtoType::Scalar(ScalarType::Int64) => scalar("int"),
+            ProtoType::Scalar(ScalarType::Bool) => scalar("bool"),
+            ProtoType::Scalar(ScalarType::Double | ScalarType::Float) => {
+                lossy(self, &field.proto_type.to_proto_string(), "decimal");
+                scalar("decimal")
+            }
+            ProtoType::Scalar(ScalarType::Bytes) => {
+                self.report(at.to_string(), "bytes field", "SEA has no binary type");
+                return None;
+            }
+            ProtoType::Scalar(other) => {
+                lossy(self, &other.to_proto_string(), "int");
+                scalar("int")
+            }
+            ProtoType::Map { .. } => {
+                self.report(at.to_string(), "map field", "records have no map type");
+                return None;
+            }
+            ProtoType::Enum(name) | ProtoType::Message(name) => {
+                if let Some(wkt) = WellKnownType::from_type_name(name) {
+                    let wrapped = match wkt {
+                        WellKnownType::Timestamp => return Some((scalar("timestamp"), false)),
+                        WellKnownType::StringValue => "string",
+                        WellKnownType::BoolValue => "bool",
+                        WellKnownType::Int64Value => "int",
+                        WellKnownType::Int32Value
+                        | WellKnownType::UInt32Value
+                        | WellKnownType::UInt64Value => {
+                            lossy(self, name, "int");
+                            "int"
+                        }
+                        WellKnownType::DoubleValue | WellKnownType::FloatValue => {
+                            lossy(self, name, "decimal");
+                            "decimal"
+                        }
+                        _ => {
+                            self.report(
+                                at.to_string(),
+                                "well-known type",
+                                format!("{name} has no SEA field type"),
+                            );
+                            return None;
+                        }
+                    };
+                    return Some((scalar(wrapped), true));
+                }
+                let resolved = self.resolve(owner, name);
+                match resolved.as_ref().and_then(|full| self.types.get(full)) {
+                    Some(sea)
+                        if resolved
+                            .as_ref()
+                            .is_some_and(|f| self.enum_defs.contains_key(f)) =>
+                    {
+                        named(sea)
+                    }
+                    Some(_) => {
+                        self.report(
+                            at.to_string(),
+                            "message field",
+                            "records cannot nest records; reference the entity by key instead",
+                        );
+                        return None;
+                    }
+                    None => {
+                        self.report(
+                            at.to_string(),
+                            "field",
+                            format!("type {name} is not a record or enum of this file"),
+                        );
+                        return None;
+                    }
+                }
+            }
+        };
+        Some((ty, false))
+    }
+
+    fn field(&mut self, owner: &str, record: &str, field: &ProtoField) -> Option<FieldDecl> {
+        let at = format!("{owner}.{}", field.name);
+        let (element, wrapped) = self.element_type(owner, field, &at)?;
+        let is_reference =
+            matches!(field.proto_type, ProtoType::Enum(_)) || element == scalar("timestamp");
+        let mut required_rule = false;
+        let mut constraints = Vec::new();
+        for option in &field.options {
+            let Some(rule) = option.name.strip_prefix(FIELD_RULES) else {
+                self.report(
+                    format!("{at} [{}]", option.name),
+                    "field option",
+                    "only protovalidate field rules are imported",
+                );
+                continue;
+            };
+            let rule = rule.strip_prefix("repeated.items.").unwrap_or(rule);
+            let (family, check) = rule.split_once('.').unwrap_or(("", rule));
+            let numeric = matches!(
+                family,
+                "int32"
+                    | "int64"
+                    | "uint32"
+                    | "uint64"
+                    | "sint32"
+                    | "sint64"
+                    | "fixed32"
+                    | "fixed64"
+                    | "sfixed32"
+                    | "sfixed64"
+                    | "double"
+                    | "float"
+            );
+            let mapped = match (family, check) {
+                ("", "required") => {
+                    required_rule = true;
+                    Some(None)
+                }
+                ("enum", "defined_only") | ("string", "uuid") => Some(None),
+                (_, "gte") if numeric => {
+                    option_decimal(&option.value).map(|v| Some(FieldConstraintDecl::Min(v)))
+                }
+                (_, "lte") if numeric => {
+                    option_decimal(&option.value).map(|v| Some(FieldConstraintDecl::Max(v)))
+                }
+                (_, "gt") if numeric => option_decimal(&option.value)
+                    .map(|v| Some(FieldConstraintDecl::ExclusiveMin(v))),
+                (_, "lt") if numeric => option_decimal(&option.value)
+                    .map(|v| Some(FieldConstraintDecl::ExclusiveMax(v))),
+                ("string", "min_len") => {
+                    option_count(&option.value).map(|v| Some(FieldConstraintDecl::MinLength(v)))
+                }
+                ("string", "max_len") => {
+                    option_count(&option.value).map(|v| Some(FieldConstraintDecl::MaxLength(v)))
+                }
+                ("repeated", "min_items") => {
+                    option_count(&option.value).map(|v| Some(FieldConstraintDecl::MinItems(v)))
+                }
+                ("repeated", "max_items") => {
+                    option_count(&option.value).map(|v| Some(FieldConstraintDecl::MaxItems(v)))
+                }
+                ("string", "pattern") => match &option.value {
+                    ProtoOptionValue::String(regex) => Some(Some(FieldConstraintDecl::Pattern(
+                        self.pattern(record, &field.name, regex),
+                    ))),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match mapped {
+                Some(Some(constraint)) => constraints.push(constraint),
+                Some(None) => {}
+                None => self.report(
+                    format!("{at} [{}]", option.name),
+                    "field rule",
+                    "this protovalidate rule has no SEA constraint",
+                ),
+            }
+        }
+        // Numeric bounds the contract projection keeps as comments.
+        for comment in &field.comments {
+            let Some((keyword, value)) = comment.split_once(' ') else {
+                continue;
+            };
+            let Ok(value) = Decimal::from_str(value) else {
+                continue;
+            };
+            let constraint = match keyword {
+                "min" => FieldConstraintDecl::Min(value),
+                "max" => FieldConstraintDecl::Max(value),
+                "exclusive_min" => FieldConstraintDecl::ExclusiveMin(value),
+                "exclusive_max" => FieldConstraintDecl::ExclusiveMax(value),
+                _ => continue,
+            };
+            constraints.push(constraint);
+        }
+
+        let (field_type, is_optional) = if field.repeated {
+            (FieldType::List(Box::new(element)), false)
+        } else {
+            let optional = field.optional || wrapped || (is_reference && !required_rule);
+            (element, optional)
+        };
+        Some(FieldDecl {
+            is_key: false,
+            name: snake(&field.name),
+            field_type,
+            is_optional,
+            constraints,
+            default: None,
+        })
+    }
+
+    /// A `Pattern` declaration for `regex`, shared by identical rules.
+    fn pattern(&mut self, record: &str, field: &str, regex: &str) -> String {
+        if let Some((name, _)) = self.patterns.iter().find(|(_, r)| r == regex) {
+            return name.clone();
+        }
+        let name = self.declare(&format!("{record}{}Pattern", pascal(field)));
+        self.patterns.push((name.clone(), regex.to_string()));
+        name
+    }
+
+    /// The entity named `raw`, declared bodyless on first use; `reason`
+    /// is the review note for a declaration the file does not make.
+    fn entity(&mut self, raw: &str, reason: Option<String>) -> usize {
+        if let Some(index) = self.entities.iter().position(|e| e.name == raw) {
+            return index;
+        }
+        let name = self.declare(raw);
+        if let Some(reason) = reason {
+            self.review
+                .push(format!("entity {name} is declared because {reason}"));
+        }
+        self.entities.push(EntityDraft {
+            name,
+            key: None,
+            fields: Vec::new(),
+            state: false,
+            shell: false,
+        });
+        self.entities.len() - 1
+    }
+
+    fn flow_service(&mut self, service: &ProtoService) {
+        let subject = service_subject(service);
+        let to = self.entity(
+            &subject,
+            Some(format!("service {} is named after it", service.name)),
+        );
+        let to = self.entities[to].name.clone();
+        for method in &service.methods {
+            let at = format!("{}.{}", service.name, method.name);
+            let resource = self
+                .resolve("", &method.request_type)
+                .and_then(|full| self.messages.get(&full).copied())
+                .map(|m| {
+                    comment_value(&m.comments, "SEA Resource: ")
+                        .unwrap_or(&m.name)
+                        .to_string()
+                })
+                .filter(|name| self.resources.iter().any(|(r, _)| r == name));
+            let Some(resource) = resource else {
+                self.report(
+                    at,
+                    "rpc",
+                    format!(
+                        "request type {} is not a resource of this file",
+                        method.request_type
+                    ),
+                );
+                continue;
+            };
+            let from = match comment_value(&method.comments, FLOW_COMMENT)
+                .and_then(|flow| flow.split_once(" -> "))
+            {
+                Some((from, _)) => {
+                    let index = self.entity(from, Some(format!("rpc {at} flows from it")));
+                    self.entities[index].name.clone()
+                }
+                None => {
+                    let index = self.entity(
+                        "Client",
+                        Some(
+                            "rpcs do not say who calls them; it stands for the callers".to_string(),
+                        ),
+                    );
+                    self.entities[index].name.clone()
+                }
+            };
+            let mut annotations = HashMap::new();
+            if method.streaming != StreamingMode::Unary {
+                annotations.insert(
+                    "streaming".to_string(),
+                    JsonValue::String(method.streaming.to_string()),
+                );
+            }
+            self.flows.push(AstNode::Flow {
+                resource_name: resource,
+                annotations,
+                from_entity: from,
+                to_entity: to.clone(),
+                quantity: None,
+            });
+        }
+    }
+
+    fn operation_service(&mut self, service: &ProtoService) {
+        let subject = service_subject(service);
+        // Always reviewed below, with the key it is given.
+        let state = self.entity(&subject, None);
+        self.entities[state].state = true;
+        let state_name = self.entities[state].name.clone();
+
+        // Resolve the records first: the key is read off the inputs.
+        let mut resolved = Vec::new();
+        for method in &service.methods {
+            let op_name = comment_value_pair(&method.comments)
+                .filter(|(name, _)| pascal(name) == method.name)
+                .map_or_else(|| snake(&method.name), |(name, _)| name.to_string());
+            let name = self.declare(&op_name);
+            let prefix = pascal(&name);
+            let input = self.record_ref(
+                &method.request_type,
+                &format!("{prefix}Input"),
+                &service.name,
+                method,
+            );
+            let output = self.record_ref(
+                &method.response_type,
+                &format!("{prefix}Output"),
+                &service.name,
+                method,
+            );
+            let effect = self.effect(&name, method);
+            resolved.push((method, name, input, output, effect));
+        }
+
+        // A `SEA Entity:` message is the graph projection of the entity
+        // the service acts on; its `id` and `name` are not contract fields.
+        let shell = std::mem::take(&mut self.entities[state].shell);
+        if self.entities[state].key.is_none() {
+            let own = format!("{}_id", snake(&state_name));
+            let candidates: Vec<FieldDecl> = resolved
+                .iter()
+                .flat_map(|(_, _, input, _, _)| self.record_fields(input))
+                .filter(|f| f.field_type == scalar("uuid") || f.field_type == scalar("string"))
+                .filter(|f| !f.is_optional)
+                .collect();
+            let key = candidates
+                .iter()
+                .find(|f| f.name == own)
+                .or_else(|| candidates.iter().find(|f| f.name == "id" && !shell))
+                .or_else(|| candidates.iter().find(|f| f.name.ends_with("_id")))
+                .cloned();
+            let key = match key {
+                Some(field) => FieldDecl {
+                    is_key: true,
+                    constraints: Vec::new(),
+                    ..field
+                },
+                None => FieldDecl {
+                    is_key: true,
+                    name: if shell { own } else { "id".to_string() },
+                    field_type: scalar("uuid"),
+                    is_optional: false,
+                    constraints: Vec::new(),
+                    default: None,
+                },
+            };
+            self.review.push(format!(
+                "entity {state_name} is inferred from service {} and keyed by {}",
+                service.name, key.name
+            ));
+            self.entities[state].key = Some(key);
+        }
+
+        for (method, name, input, output, effect) in resolved {
+            let decl = self.operation(service, method, name, input, output, effect, state);
+            self.operations.push(decl);
+        }
+    }
+
+    /// The wire value of the first member of an enum-typed field.
+    fn first_member(&self, field_type: &FieldType) -> Option<String> {
+        let FieldType::Named(named) = field_type else {
+            return None;
+        };
+        self.enums
+            .iter()
+            .find(|e| e.name == named.symbol)
+            .and_then(|e| e.members.first())
+            .map(|m| m.wire.clone())
+    }
+
+    fn record_fields(&self, record: &str) -> Vec<FieldDecl> {
+        self.records
+            .iter()
+            .find(|r| r.name == record)
+            .map(|r| r.fields.clone())
+            .unwrap_or_default()
+    }
+
+    /// The record an rpc type names, else a synthesized empty record.
+    fn record_ref(
+        &mut self,
+        type_name: &str,
+        fallback: &str,
+        service: &str,
+        method: &ProtoRpcMethod,
+    ) -> String {
+        let record = self
+            .resolve("", type_name)
+            .and_then(|full| self.types.get(&full).cloned())
+            .filter(|name| self.records.iter().any(|r| &r.name == name));
+        if let Some(record) = record {
+            return record;
+        }
+        if type_name != WellKnownType::Empty.type_name() {
+            self.report(
+                format!("{service}.{}", method.name),
+                "rpc type",
+                format!("{type_name} is not a record of this file; an empty record stands in"),
+            );
+        }
+        let name = self.declare(fallback);
+        self.records.push(RecordDecl {
+            name: name.clone(),
+            fields: Vec::new(),
+        });
+        name
+    }
+
+    fn effect(&mut self, name: &str, method: &ProtoRpcMethod) -> Effect {
+        if let Some(effect) = comment_value(&method.comments, "effect: ")
+            .and_then(|e| e.split_whitespace().next())
+            .and_then(Effect::parse)
+        {
+            return effect;
+        }
+        let side_effect_free = method.options.iter().any(|o| {
+            o.name == "idempotency_level"
+                && o.value == ProtoOptionValue::Identifier("NO_SIDE_EFFECTS".to_string())
+        });
+        let effect = if side_effect_free {
+            Effect::Reads
+        } else {
+            Effect::of_rpc(name)
+        };
+        self.review.push(format!(
+            "operation {name}: effect {} is inferred from the rpc {}",
+            effect.keyword(),
+            if side_effect_free {
+                "idempotency level"
+            } else {
+                "name"
+            }
+        ));
+        effect
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn operation(
+        &mut self,
+        service: &ProtoService,
+        method: &ProtoRpcMethod,
+        name: String,
+        input: String,
+        output: String,
+        effect: Effect,
+        state: usize,
+    ) -> OperationDecl {
+        if method.streaming != StreamingMode::Unary {
+            self.report(
+                format!("{}.{}", service.name, method.name),
+                "streaming rpc",
+                format!(
+                    "operations are request/response; the {} rpc is imported as unary",
+                    method.streaming
+                ),
+            );
+        }
+        for option in method
+            .options
+            .iter()
+            .filter(|o| o.name != "idempotency_level")
+        {
+            self.report(
+                format!("{}.{} [{}]", service.name, method.name, option.name),
+                "method option",
+                "only idempotency_level is read from rpc options",
+            );
+        }
+        let state_name = self.entities[state].name.clone();
+        let key = self.entities[state]
+            .key
+            .as_ref()
+            .map_or_else(|| "id".to_string(), |k| k.name.clone());
+        let input_fields = self.record_fields(&input);
+        let output_fields = self.record_fields(&output);
+
+        let intent = comment_value_pair(&method.comments)
+            .filter(|(n, _)| *n == name)
+            .map(|(_, intent)| intent.to_string())
+            .or_else(|| method.comments.iter().find(|c| !c.contains(": ")).cloned())
+            .unwrap_or_else(|| format!("{} on {}", method.name, service.name));
+        let transaction = comment_value(&method.comments, "effect: ")
+            .and_then(|e| e.rsplit_once('('))
+            .and_then(|(_, tx)| tx.strip_suffix(')'))
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                if effect == Effect::Reads {
+                    "read_only"
+                } else {
+                    "single_aggregate"
+                }
+                .to_string()
+            });
+
+        let idempotency_field = if input_fields.iter().any(|f| f.name == "idempotency_key") {
+            "idempotency_key".to_string()
+        } else {
+            key.clone()
+        };
+        let idempotency = match comment_value(&method.comments, "idempotency: ") {
+            Some("inherent") => OperationClause::IdempotencyInherent,
+            Some("not_applicable") => {
+                self.review.push(format!(
+                    "operation {name}: the not_applicable idempotency explanation is not recorded in the file"
+                ));
+                OperationClause::IdempotencyNotApplicable {
+                    reason: "read_only".to_string(),
+                    explanation: "Declared not applicable in the Protobuf source".to_string(),
+                }
+            }
+            Some(keyed) if keyed.starts_with("keyed_by ") => OperationClause::IdempotencyKeyed {
+                field: keyed["keyed_by ".len()..].to_string(),
+            },
+            _ => {
+                let clause = if effect == Effect::Reads {
+                    OperationClause::IdempotencyInherent
+                } else {
+                    OperationClause::IdempotencyKeyed {
+                        field: idempotency_field.clone(),
+                    }
+                };
+                if effect != Effect::Reads {
+                    self.review.push(format!(
+                        "operation {name}: idempotency keyed_by {idempotency_field} is inferred"
+                    ));
+                }
+                clause
+            }
+        };
+        let concurrency = match comment_value(&method.comments, "concurrency: ") {
+            Some("read_snapshot") => OperationClause::ConcurrencyReadSnapshot,
+            Some(unique) if unique.starts_with("unique_key ") => {
+                OperationClause::ConcurrencyUnique {
+                    field: unique["unique_key ".len()..].to_string(),
+                }
+            }
+            Some(version) if version.starts_with("optimistic_version ") => {
+                OperationClause::ConcurrencyOptimistic {
+                    field: version["optimistic_version ".len()..].to_string(),
+                }
+            }
+            _ => {
+                let clause = match effect {
+                    Effect::Reads => OperationClause::ConcurrencyReadSnapshot,
+                    Effect::Creates => OperationClause::ConcurrencyUnique { field: key.clone() },
+                    Effect::Mutates => OperationClause::ConcurrencyOptimistic {
+                        field: "version".to_string(),
+                    },
+                };
+                if effect != Effect::Reads {
+                    self.review.push(format!(
+                        "operation {name}: concurrency {} is inferred",
+                        match &clause {
+                            OperationClause::ConcurrencyUnique { field } =>
+                                format!("unique_key {field}"),
+                            _ => "optimistic_version version".to_string(),
+                        }
+                    ));
+                }
+                clause
+            }
+        };
+
+        // The state carries what the operations return and write.
+        let written: &[FieldDecl] = if effect == Effect::Reads {
+            &[]
+        } else {
+            &input_fields
+        };
+        for field in output_fields.iter().chain(written) {
+            if field.name == key || field.name == idempotency_field {
+                continue;
+            }
+            // A create constructs every required state field from its input;
+            // one it only returns was defaulted, which the file does not say.
+            let defaulted = effect == Effect::Creates
+                && !field.is_optional
+                && !input_fields.iter().any(|f| f.name == field.name);
+            let default = defaulted.then(|| self.first_member(&field.field_type));
+            let fields = &mut self.entities[state].fields;
+            match fields.iter().find(|f| f.name == field.name) {
+                None => {
+                    fields.push(FieldDecl {
+                        is_key: false,
+                        default: default
+                            .clone()
+                            .flatten()
+                            .map(|wire| Expression::Literal(JsonValue::String(wire))),
+                        ..field.clone()
+                    });
+                    match default {
+                        Some(Some(wire)) => self.review.push(format!(
+                            "field {} of entity {state_name} defaults to \"{wire}\", the first member of its enum, because operation {name} returns it without an input",
+                            field.name
+                        )),
+                        Some(None) => self.review.push(format!(
+                            "field {} of entity {state_name} needs a default: operation {name} returns it without an input",
+                            field.name
+                        )),
+                        None => {}
+                    }
+                }
+                Some(existing)
+                    if existing.field_type != field.field_type
+                        || existing.is_optional != field.is_optional
+                        || existing.constraints != field.constraints =>
+                {
+                    let message = format!(
+                        "field {} of entity {state_name} differs between operations; the first declaration is kept",
+                        field.name
+                    );
+                    if !self.review.contains(&message) {
+                        self.review.push(message);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        self.review.push(format!(
+            "operation {name}: actor anonymous and access public are assumed because the file does not record them"
+        ));
+        let mut clauses = vec![
+            OperationClause::Intent(intent),
+            OperationClause::Direction {
+                kind: "inbound".to_string(),
+            },
+            OperationClause::Actor {
+                actor: "anonymous".to_string(),
+            },
+            OperationClause::AccessPublic,
+            OperationClause::Input { reference: input },
+            OperationClause::Output { reference: output },
+            OperationClause::State {
+                reference: state_name.clone(),
+            },
+            OperationClause::Effect {
+                kind: effect.keyword().to_string(),
+                reference: state_name,
+            },
+            OperationClause::Transaction { kind: transaction },
+        ];
+        let constrained = input_fields.iter().any(|f| !f.constraints.is_empty());
+        clauses.extend(self.failures(method, &name, effect, constrained));
+        clauses.extend([
+            idempotency,
+            concurrency,
+            OperationClause::EvidenceOperationTrace,
+            OperationClause::LifecycleSynchronousRequestResponse,
+        ]);
+        OperationDecl { name, clauses }
+    }
+
+    /// Failures from the `<Op>FailureCode` enum and the rpc's failure
+    /// comments, else the ones the contract requires, synthesized.
+    fn failures(
+        &mut self,
+        method: &ProtoRpcMethod,
+        name: &str,
+        effect: Effect,
+        constrained_input: bool,
+    ) -> Vec<OperationClause> {
+        let declared: Vec<(String, Vec<String>, String)> = method
+            .comments
+            .iter()
+            .filter_map(|c| c.strip_prefix("failure "))
+            .filter_map(|c| {
+                let (code, rest) = c.split_once(" (")?;
+                let (kinds, meaning) = rest.split_once("): ")?;
+                let kinds = kinds
+                    .split(", ")
+                    .filter_map(|k| k.split(" -> ").next())
+                    .map(str::to_string)
+                    .collect();
+                Some((code.to_string(), kinds, meaning.to_string()))
+            })
+            .collect();
+        let codes = self
+            .enum_defs
+            .get(&format!("{}FailureCode", method.name))
+            .copied();
+        if let Some(codes) = codes {
+            let prefix = format!("{}_", to_screaming_snake_case(&codes.name));
+            let mut clauses = Vec::new();
+            for value in codes.values.iter().filter(|v| v.number != 0) {
+                let code = value
+                    .name
+                    .strip_prefix(&prefix)
+                    .unwrap_or(&value.name)
+                    .to_lowercase();
+                let Some((_, kinds, meaning)) = declared.iter().find(|(c, _, _)| *c == code) else {
+                    self.report(
+                        format!("{}.{}", codes.name, value.name),
+                        "failure code",
+                        "no rpc comment records the failure kinds of this code",
+                    );
+                    continue;
+                };
+                clauses.push(OperationClause::Failure {
+                    code,
+                    kinds: kinds.clone(),
+                    message: meaning.clone(),
+                });
+            }
+            return clauses;
+        }
+
+        let required = [
+            constrained_input,
+            effect != Effect::Creates,
+            effect != Effect::Reads,
+        ];
+        let mut clauses = Vec::new();
+        for ((suffix, kinds), required) in FAILURE_GROUPS.iter().zip(required) {
+            if !required {
+                continue;
+            }
+            let code = format!("{name}_{suffix}");
+            self.review.push(format!(
+                "operation {name}: failure {code} is synthesized; the file declares no failure details"
+            ));
+            clauses.push(OperationClause::Failure {
+                code,
+                kinds: kinds.iter().map(|k| k.to_string()).collect(),
+                message: format!("{} failure (not declared in the Protobuf source)", kinds[0]),
+            });
+        }
+        clauses
+    }
+}
+
+/// `("<name>", "<intent>")` from the `<name>: <intent>` comment the contract
+/// projection writes first on every rpc.
+fn comment_value_pair(comments: &[String]) -> Option<(&str, &str)> {
+    let (name, intent) = comments.first()?.split_once(": ")?;
+    let is_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    let clause = matches!(name, "effect" | "idempotency" | "concurrency" | "errors");
+    (is_name && !clause).then_some((name, intent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::resolve_application_contract;
+
+    const CONTRACT: &str = r#"@namespace "shop"
+
+enum OrderStatus {
+    placed = "placed",
+    shipped = "shipped"
+}
+
+Pattern "Sku" matches "^[A-Z]{3}-[0-9]+$"
+
+record GetOrderInput {
+    order_id: uuid
+}
+
+record OrderView {
+    status: OrderStatus
+    total: decimal (min 0)
+    sku: string (pattern Sku)
+    tags: list<string> (max_items 5)
+}
+
+Entity "Order" {
+    key order_id: uuid
+    status: OrderStatus
+    total: decimal (min 0)
+    sku: string (pattern Sku)
+    tags: list<string> (max_items 5)
+}
+
+operation get_order {
+    intent "Return one order"
+    direction inbound
+    actor anonymous
+    access public
+    input GetOrderInput
+    output OrderView
+    state Order
+    effect reads Order
+    transaction read_only
+    failure order_not_found for missing_state "No order has that id"
+    idempotency inherent
+    concurrency read_snapshot
+    evidence operation_trace
+    lifecycle synchronous_request_response
+}
+"#;
+
+    /// What `project --format protobuf` writes for `source`.
+    fn project(source: &str) -> String {
+        let sources = serde_json::json!({ "main.sea": source }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).unwrap();
+        let doc = resolve_application_contract("main.sea", &sources).unwrap();
+        let mut proto =
+            ProtobufEngine::project_with_full_options(&graph, "", "acme.shop", "shop", false, true);
+        merge_application_contract(&mut proto, &graph, &doc.contract, "").unwrap();
+        proto.to_proto_string()
+    }
+
+    #[test]
+    fn contract_projection_round_trips_without_differences() {
+        let proto = project(CONTRACT);
+        let import = import_protobuf(&proto, None, ProtoImportMode::Contract).unwrap();
+        assert_eq!(import.report.round_trip, Some(vec![]), "{}", import.source);
+        assert!(import.source.starts_with("@namespace \"shop\""));
+        assert!(import
+            .source
+            .contains("    failure order_not_found for missing_state \"No order has that id\"\n"));
+        assert!(import.source.contains("    total: decimal (min 0)\n"));
+        assert_eq!(
+            import.report.review,
+            [
+                "entity Order is inferred from service OrderOperationsService and keyed by order_id",
+                "operation get_order: actor anonymous and access public are assumed because the file does not record them",
+            ]
+        );
+        // Projecting the import again gives the same file.
+        assert_eq!(project(&import.source), proto);
+    }
+
+    #[test]
+    fn legacy_mode_maps_messages_and_services_to_the_graph() {
+        let source = r#"@namespace "logistics"
+
+Entity "Factory"
+
+Entity "Warehouse"
+
+Resource "Widget" kg
+
+Flow "Widget"
+    @streaming "server_streaming"
+    from "Warehouse" to "Factory"
+"#;
+        let proto = project(source);
+        let import = import_protobuf(&proto, None, ProtoImportMode::Legacy).unwrap();
+        assert_eq!(import.report.round_trip, Some(vec![]), "{}", import.source);
+        assert_eq!(import.source, source);
+        assert_eq!(
+            (
+                import.report.entities,
+                import.report.resources,
+                import.report.flows
+            ),
+            (2, 1, 1)
+        );
+    }
+
+    #[test]
+    fn foreign_files_get_inferred_clauses_and_a_round_trip_diff() {
+        let proto = r#"
+syntax = "proto3";
+package billing.v1;
+
+import "google/protobuf/timestamp.proto";
+import "google/protobuf/wrappers.proto";
+
+enum InvoiceState {
+  INVOICE_STATE_UNSPECIFIED = 0;
+  INVOICE_STATE_OPEN = 1;
+  INVOICE_STATE_PAID = 2;
+}
+
+message Invoice {
+  string invoice_id = 1;
+  InvoiceState state = 2;
+  double amount = 3;
+  google.protobuf.Timestamp issued_at = 4;
+  google.protobuf.StringValue memo = 5;
+  bytes pdf = 6;
+}
+
+message GetInvoiceRequest {
+  string invoice_id = 1;
+}
+
+service InvoiceService {
+  // Fetch one invoice.
+  rpc GetInvoice(GetInvoiceRequest) returns (Invoice);
+  rpc WatchInvoice(GetInvoiceRequest) returns (stream Invoice);
+}
+"#;
+        let import = import_protobuf(proto, None, ProtoImportMode::Contract).unwrap();
+        assert!(import.source.starts_with("@namespace \"billing_v1\""));
+        assert!(import.source.contains("    open = \"open\",\n"));
+        assert!(import.source.contains("    state: InvoiceState optional\n"));
+        assert!(import.source.contains("    memo: string optional\n"));
+        assert!(import
+            .source
+            .contains("    intent \"Fetch one invoice.\"\n"));
+        assert!(import
+            .source
+            .contains("    failure get_invoice_not_found for missing_state"));
+        let kinds: Vec<&str> = import
+            .report
+            .unmapped
+            .iter()
+            .map(|u| u.kind.as_str())
+            .collect();
+        assert_eq!(kinds, ["bytes field", "streaming rpc"]);
+        let review = &import.report.review;
+        assert!(review.iter().any(|r| r.contains("amount is double")));
+        assert!(review.iter().any(|r| r
+            == "entity Invoice2 is inferred from service InvoiceService and keyed by invoice_id"));
+        let diff = import.report.round_trip.expect("the import resolves");
+        assert_eq!(
+            diff[..2],
+            [
+                "message Invoice differs: `InvoiceState state = 2;` becomes \
+                 `optional InvoiceState state = 2 [(buf.validate.field).enum.defined_only = true];`",
+                "service InvoiceService is not reproduced",
+            ]
+        );
+        assert!(diff
+            .contains(&"service Invoice2OperationsService is added by the projection".to_string()));
+    }
+}
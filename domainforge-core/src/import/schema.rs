@@ -0,0 +1,719 @@
+//! JSON Schema → SEA `record`/`enum` conversion shared by the OpenAPI and
+//! AsyncAPI importers.
+//!
+//! SEA records are closed and flat: a field is a scalar, an enum, or a list
+//! of either. Object schemas become records, string `enum`s become enums,
+//! and `pattern` becomes a `Pattern` declaration the field constraint names.
+//! Nested objects, composition (`oneOf`, `anyOf`, `allOf`, `not`), open maps
+//! (`additionalProperties`), and keywords the field model has no slot for
+//! are reported instead of guessed at.
+//!
+//! Property names become snake_case fields and enum values snake_case
+//! members; a name already taken in its record or enum is suffixed, and
+//! every field whose wire name changes is noted for review.
+//!
+//! Documents are read as `serde_yaml` values (JSON is valid YAML), which
+//! keep mappings in document order, so fields come out in authored order.
+
+use super::UnmappedElement;
+use crate::kg_import::ImportError;
+use crate::parser::ast::{
+    AstNode, EnumDecl, EnumMember, FieldConstraintDecl, FieldDecl, FieldType, FieldTypeRef,
+    RecordDecl,
+};
+use crate::projection::ids::{ident, pascal};
+use rust_decimal::Decimal;
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+
+/// Composition keywords; a schema using one has no single record shape.
+const COMPOSITION: [&str; 4] = ["oneOf", "anyOf", "allOf", "not"];
+
+/// Field keywords the SEA field model has no slot for, with the reason.
+const FIELD_KEYWORDS: [(&str, &str); 7] = [
+    (
+        "nullable",
+        "null is not a SEA value; the field keeps its optionality",
+    ),
+    ("default", "record fields carry no defaults"),
+    ("multipleOf", "SEA has no multiple-of constraint"),
+    ("uniqueItems", "SEA lists have no uniqueness constraint"),
+    ("const", "SEA has no constant fields"),
+    (
+        "readOnly",
+        "field direction is expressed by input/output records",
+    ),
+    (
+        "writeOnly",
+        "field direction is expressed by input/output records",
+    ),
+];
+
+/// Parse a JSON or YAML document whose top level is a mapping.
+pub(crate) fn parse_document(text: &str, label: &str) -> Result<Value, ImportError> {
+    let doc: Value = serde_yaml::from_str(text)
+        .map_err(|e| ImportError::Other(format!("Failed to parse {label} document: {e}")))?;
+    if !doc.is_mapping() {
+        return Err(ImportError::Other(format!(
+            "Not a {label} document: the top level is not an object"
+        )));
+    }
+    Ok(doc)
+}
+
+/// The text of a mapping key (YAML allows bare numeric keys such as `200:`).
+pub(crate) fn key_text(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// The entries of a mapping, in document order (empty for anything else).
+pub(crate) fn entries(value: &Value) -> Vec<(String, &Value)> {
+    value
+        .as_mapping()
+        .map(|m| m.iter().map(|(k, v)| (key_text(k), v)).collect())
+        .unwrap_or_default()
+}
+
+pub(crate) fn text<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Escape a mapping key as a JSON Pointer segment.
+pub(crate) fn pointer_segment(raw: &str) -> String {
+    raw.replace('~', "~0").replace('/', "~1")
+}
+
+/// Look up a local `#/...` reference.
+pub(crate) fn resolve<'a>(doc: &'a Value, reference: &str) -> Option<&'a Value> {
+    let path = reference.strip_prefix("#/")?;
+    path.split('/').try_fold(doc, |node, segment| {
+        let key = segment.replace("~1", "/").replace("~0", "~");
+        node.as_mapping()?
+            .iter()
+            .find(|(k, _)| key_text(k) == key)
+            .map(|(_, v)| v)
+    })
+}
+
+/// Follow `$ref` until a concrete object (unresolvable references are
+/// returned as-is, so callers see the `$ref` and report it).
+pub(crate) fn deref<'a>(doc: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..32 {
+        match text(current, "$ref").and_then(|r| resolve(doc, r)) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Convert a camelCase, kebab-case, or spaced name to a lower_snake_case
+/// identifier (`orderId` → `order_id`, `HTTPStatus` → `http_status`).
+pub(crate) fn snake(raw: &str) -> String {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = String::with_capacity(raw.len() + 4);
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase() {
+                let prev = i.checked_sub(1).map(|p| chars[p]);
+                let after_lower =
+                    prev.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit());
+                let acronym_end = prev.is_some_and(|p| p.is_ascii_uppercase())
+                    && chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+                if (after_lower || acronym_end) && !out.is_empty() && !out.ends_with('_') {
+                    out.push('_');
+                }
+                out.push(ch.to_ascii_lowercase());
+            } else {
+                out.push(ch);
+            }
+        } else if !out.is_empty() && !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    while out.ends_with('_') {
+        out.pop();
+    }
+    ident(&out)
+}
+
+fn decimal(value: &Value) -> Option<Decimal> {
+    let raw = match value {
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    Decimal::from_str(&raw)
+        .or_else(|_| Decimal::from_scientific(&raw))
+        .ok()
+        .map(|d| d.normalize())
+}
+
+fn scalar(symbol: &str) -> FieldType {
+    FieldType::Scalar(FieldTypeRef {
+        alias: None,
+        symbol: symbol.to_string(),
+    })
+}
+
+fn named(symbol: &str) -> FieldType {
+    FieldType::Named(FieldTypeRef {
+        alias: None,
+        symbol: symbol.to_string(),
+    })
+}
+
+fn unmapped(pointer: &str, kind: &str, reason: impl Into<String>) -> UnmappedElement {
+    UnmappedElement {
+        id: pointer.to_string(),
+        kind: kind.to_string(),
+        name: None,
+        reason: reason.into(),
+    }
+}
+
+/// What a component schema became.
+#[derive(Debug, Clone)]
+pub(crate) enum Component {
+    Record(String),
+    Enum(String),
+    /// A scalar or list schema, inlined wherever it is referenced.
+    Alias(FieldType, Vec<FieldConstraintDecl>),
+    Unsupported,
+}
+
+/// Converts the schemas of one document, owning the declaration names so
+/// every importer-synthesized declaration stays collision-free.
+pub(crate) struct SchemaImporter<'a> {
+    doc: &'a Value,
+    names: BTreeSet<String>,
+    components: HashMap<String, Component>,
+    patterns: Vec<(String, String)>,
+    enums: Vec<EnumDecl>,
+    records: Vec<RecordDecl>,
+    pub(crate) unmapped: Vec<UnmappedElement>,
+    /// Renamed wire names and disambiguated members, for review.
+    review: Vec<String>,
+}
+
+impl<'a> SchemaImporter<'a> {
+    pub(crate) fn new(doc: &'a Value) -> Self {
+        Self {
+            doc,
+            names: BTreeSet::new(),
+            components: HashMap::new(),
+            patterns: Vec::new(),
+            enums: Vec::new(),
+            records: Vec::new(),
+            unmapped: Vec::new(),
+            review: Vec::new(),
+        }
+    }
+
+    /// Claim a declaration name; a name already taken (even by the same
+    /// source name, e.g. a component and a synthesized record) gets a
+    /// numeric suffix.
+    pub(crate) fn declare(&mut self, raw: &str) -> String {
+        let base = ident(raw);
+        let mut name = base.clone();
+        let mut n = 1;
+        while !self.names.insert(name.clone()) {
+            n += 1;
+            name = format!("{base}{n}");
+        }
+        name
+    }
+
+    /// Claim the field name for the wire name `raw` of `owner` among the
+    /// names in `taken`; a name already taken gets a numeric suffix. SEA
+    /// fields carry no separate wire name, so any change is noted for review.
+    pub(crate) fn field_name(
+        &mut self,
+        owner: &str,
+        raw: &str,
+        taken: &mut BTreeSet<String>,
+    ) -> String {
+        let (name, base) = unique_snake(raw, taken);
+        if name != base {
+            self.review.push(format!(
+                "{owner}.{raw} is imported as field {name} because {base} is taken; its wire name changes"
+            ));
+        } else if name != raw {
+            self.review.push(format!(
+                "{owner}.{raw} is imported as field {name}; its wire name changes"
+            ));
+        }
+        name
+    }
+
+    pub(crate) fn report(&mut self, pointer: &str, kind: &str, reason: impl Into<String>) {
+        self.unmapped.push(unmapped(pointer, kind, reason));
+    }
+
+    pub(crate) fn record(&self, name: &str) -> Option<&RecordDecl> {
+        self.records.iter().find(|r| r.name == name)
+    }
+
+    pub(crate) fn push_record(&mut self, record: RecordDecl) {
+        self.records.push(record);
+    }
+
+    pub(crate) fn record_count(&self) -> usize {
+        self.records.len()
+    }
+
+    pub(crate) fn enum_count(&self) -> usize {
+        self.enums.len()
+    }
+
+    /// Convert every schema under the mapping at `#/<path>`.
+    pub(crate) fn import_components(&mut self, path: &str) {
+        let Some(schemas) = resolve(self.doc, &format!("#/{path}")) else {
+            return;
+        };
+        for (name, _) in entries(schemas) {
+            self.component(&format!("#/{path}/{}", pointer_segment(&name)));
+        }
+    }
+
+    /// Convert the schema at `reference` once; later calls reuse the result.
+    pub(crate) fn component(&mut self, reference: &str) -> Component {
+        if let Some(done) = self.components.get(reference) {
+            return done.clone();
+        }
+        let doc = self.doc;
+        let Some(schema) = resolve(doc, reference) else {
+            self.report(
+                reference,
+                "$ref",
+                "only local references into this document are imported",
+            );
+            self.components
+                .insert(reference.to_string(), Component::Unsupported);
+            return Component::Unsupported;
+        };
+        let raw_name = reference
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .replace("~1", "/")
+            .replace("~0", "~");
+        let name = pascal(&raw_name);
+        if let Some(keyword) = COMPOSITION.iter().find(|k| schema.get(**k).is_some()) {
+            self.report(
+                reference,
+                keyword,
+                "schema composition has no SEA counterpart",
+            );
+            self.components
+                .insert(reference.to_string(), Component::Unsupported);
+            return Component::Unsupported;
+        }
+        let component = if is_object(schema) {
+            let name = self.declare(&name);
+            // Registered before the fields so self-references resolve.
+            self.components
+                .insert(reference.to_string(), Component::Record(name.clone()));
+            let fields = self.fields(&name, schema, reference);
+            self.records.push(RecordDecl {
+                name: name.clone(),
+                fields,
+            });
+            Component::Record(name)
+        } else if schema.get("enum").is_some() {
+            match self.enumeration(&name, schema, reference) {
+                Some(name) => Component::Enum(name),
+                None => Component::Unsupported,
+            }
+        } else {
+            match self.field_type(&name, schema, reference) {
+                Some((ty, constraints)) => Component::Alias(ty, constraints),
+                None => Component::Unsupported,
+            }
+        };
+        self.components
+            .insert(reference.to_string(), component.clone());
+        component
+    }
+
+    /// Convert an inline object schema into a record named `name`.
+    pub(crate) fn inline_record(&mut self, name: &str, schema: &Value, pointer: &str) -> String {
+        let name = self.declare(name);
+        let fields = self.fields(&name, schema, pointer);
+        self.records.push(RecordDecl {
+            name: name.clone(),
+            fields,
+        });
+        name
+    }
+
+    /// The fields of an object schema; `owner` names synthesized enums and
+    /// patterns.
+    pub(crate) fn fields(&mut self, owner: &str, schema: &Value, pointer: &str) -> Vec<FieldDecl> {
+        match schema.get("additionalProperties") {
+            None | Some(Value::Bool(false)) => {}
+            Some(_) => self.report(
+                &format!("{pointer}/additionalProperties"),
+                "additionalProperties",
+                "SEA records are closed; open maps have no counterpart",
+            ),
+        }
+        for keyword in ["patternProperties", "discriminator"] {
+            if schema.get(keyword).is_some() {
+                self.report(
+                    &format!("{pointer}/{keyword}"),
+                    keyword,
+                    "SEA records are closed and untagged",
+                );
+            }
+        }
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_sequence)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let mut taken = BTreeSet::new();
+        let mut fields = Vec::new();
+        let properties = schema.get("properties").map(entries).unwrap_or_default();
+        for (property, property_schema) in properties {
+            let at = format!("{pointer}/properties/{}", pointer_segment(&property));
+            let hint = format!("{owner}{}", pascal(&property));
+            let Some((field_type, constraints)) = self.field_type(&hint, property_schema, &at)
+            else {
+                continue;
+            };
+            let name = self.field_name(owner, &property, &mut taken);
+            fields.push(FieldDecl {
+                is_key: false,
+                name,
+                field_type,
+                is_optional: !required.contains(&property.as_str()),
+                constraints,
+                default: None,
+            });
+        }
+        fields
+    }
+
+    /// The SEA type and constraints of a property schema, or `None` (after
+    /// reporting why) when it has no field counterpart. `hint` names any
+    /// enum or pattern the property needs.
+    pub(crate) fn field_type(
+        &mut self,
+        hint: &str,
+        schema: &Value,
+        pointer: &str,
+    ) -> Option<(FieldType, Vec<FieldConstraintDecl>)> {
+        if let Some(reference) = text(schema, "$ref") {
+            let reference = reference.to_string();
+            return match self.component(&reference) {
+                Component::Enum(name) => Some((named(&name), Vec::new())),
+                Component::Alias(ty, constraints) => Some((ty, constraints)),
+                Component::Record(name) => {
+                    self.report(
+                        pointer,
+                        "$ref",
+                        format!("nests record {name}; the closed type model has no nested records"),
+                    );
+                    None
+                }
+                Component::Unsupported => {
+                    self.report(pointer, "$ref", format!("{reference} was not imported"));
+                    None
+                }
+            };
+        }
+        if let Some(keyword) = COMPOSITION.iter().find(|k| schema.get(**k).is_some()) {
+            self.report(
+                pointer,
+                keyword,
+                "schema composition has no SEA counterpart",
+            );
+            return None;
+        }
+        for (keyword, reason) in FIELD_KEYWORDS {
+            if schema.get(keyword).is_some() {
+                self.report(&format!("{pointer}/{keyword}"), keyword, reason);
+            }
+        }
+        let kind = match schema.get("type") {
+            Some(Value::String(t)) => Some(t.as_str()),
+            Some(Value::Sequence(types)) => {
+                let mut non_null = types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|t| *t != "null");
+                let first = non_null.next();
+                if non_null.next().is_some() {
+                    self.report(
+                        &format!("{pointer}/type"),
+                        "type",
+                        "a union of types has no SEA counterpart",
+                    );
+                    return None;
+                }
+                if types.iter().any(|t| t.as_str() == Some("null")) {
+                    self.report(
+                        &format!("{pointer}/type"),
+                        "type",
+                        "null is not a SEA value; the field keeps its optionality",
+                    );
+                }
+                first
+            }
+            _ => None,
+        };
+        if schema.get("enum").is_some() && matches!(kind, None | Some("string")) {
+            return self
+                .enumeration(hint, schema, pointer)
+                .map(|name| (named(&name), Vec::new()));
+        }
+        let mut constraints = Vec::new();
+        let field_type = match kind {
+            Some("string") => {
+                if let Some(n) = schema.get("minLength").and_then(Value::as_u64) {
+                    constraints.push(FieldConstraintDecl::MinLength(n));
+                }
+                if let Some(n) = schema.get("maxLength").and_then(Value::as_u64) {
+                    constraints.push(FieldConstraintDecl::MaxLength(n));
+                }
+                if let Some(regex) = text(schema, "pattern") {
+                    let name = self.pattern(hint, regex);
+                    constraints.push(FieldConstraintDecl::Pattern(name));
+                }
+                match text(schema, "format") {
+                    Some("uuid") => scalar("uuid"),
+                    Some("date-time") => scalar("timestamp"),
+                    Some(other) => {
+                        self.report(
+                            &format!("{pointer}/format"),
+                            "format",
+                            format!("format {other} has no SEA scalar; imported as string"),
+                        );
+                        scalar("string")
+                    }
+                    None => scalar("string"),
+                }
+            }
+            Some(kind @ ("integer" | "number")) => {
+                self.numeric_constraints(schema, &mut constraints);
+                scalar(if kind == "integer" { "int" } else { "decimal" })
+            }
+            Some("boolean") => scalar("bool"),
+            Some("array") => {
+                let items = schema.get("items").cloned().unwrap_or(Value::Null);
+                let (element, item_constraints) =
+                    self.field_type(hint, &items, &format!("{pointer}/items"))?;
+                if matches!(element, FieldType::List(_)) {
+                    self.report(pointer, "array", "SEA lists do not nest");
+                    return None;
+                }
+                if !item_constraints.is_empty() {
+                    self.report(
+                        &format!("{pointer}/items"),
+                        "items",
+                        "SEA constrains the list, not its items; item constraints are dropped",
+                    );
+                }
+                if let Some(n) = schema.get("minItems").and_then(Value::as_u64) {
+                    constraints.push(FieldConstraintDecl::MinItems(n));
+                }
+                if let Some(n) = schema.get("maxItems").and_then(Value::as_u64) {
+                    constraints.push(FieldConstraintDecl::MaxItems(n));
+                }
+                FieldType::List(Box::new(element))
+            }
+            _ if is_object(schema) => {
+                self.report(
+                    pointer,
+                    "object",
+                    "inline object; the closed type model has no nested records",
+                );
+                return None;
+            }
+            _ => {
+                self.report(pointer, "schema", "schema without a supported type");
+                return None;
+            }
+        };
+        Some((field_type, constraints))
+    }
+
+    fn numeric_constraints(&self, schema: &Value, constraints: &mut Vec<FieldConstraintDecl>) {
+        // OpenAPI 3.0 marks `minimum`/`maximum` exclusive with a boolean;
+        // JSON Schema 2020-12 (OpenAPI 3.1, AsyncAPI 3) gives the bound itself.
+        let exclusive = |key: &str| matches!(schema.get(key), Some(Value::Bool(true)));
+        if let Some(min) = schema.get("minimum").and_then(decimal) {
+            constraints.push(if exclusive("exclusiveMinimum") {
+                FieldConstraintDecl::ExclusiveMin(min)
+            } else {
+                FieldConstraintDecl::Min(min)
+            });
+        }
+        if let Some(min) = schema.get("exclusiveMinimum").and_then(decimal) {
+            constraints.push(FieldConstraintDecl::ExclusiveMin(min));
+        }
+        if let Some(max) = schema.get("maximum").and_then(decimal) {
+            constraints.push(if exclusive("exclusiveMaximum") {
+                FieldConstraintDecl::ExclusiveMax(max)
+            } else {
+                FieldConstraintDecl::Max(max)
+            });
+        }
+        if let Some(max) = schema.get("exclusiveMaximum").and_then(decimal) {
+            constraints.push(FieldConstraintDecl::ExclusiveMax(max));
+        }
+    }
+
+    /// Declare a string enum; members are named after their wire values.
+    fn enumeration(&mut self, name: &str, schema: &Value, pointer: &str) -> Option<String> {
+        let values = schema
+            .get("enum")
+            .and_then(Value::as_sequence)
+            .cloned()
+            .unwrap_or_default();
+        let wires: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+        if wires.is_empty() || wires.len() != values.len() {
+            self.report(
+                &format!("{pointer}/enum"),
+                "enum",
+                "SEA enums carry string wire values only",
+            );
+            return None;
+        }
+        let name = self.declare(name);
+        let mut taken = BTreeSet::new();
+        let mut members = Vec::new();
+        for wire in wires {
+            let (member, base) = unique_snake(wire, &mut taken);
+            if member != base {
+                self.review.push(format!(
+                    "enum {name}: wire value {wire:?} is member {member} because {base} is taken"
+                ));
+            }
+            members.push(EnumMember {
+                name: member,
+                wire: wire.to_string(),
+            });
+        }
+        self.enums.push(EnumDecl {
+            name: name.clone(),
+            members,
+        });
+        Some(name)
+    }
+
+    /// The `Pattern` declaration for `regex`, shared by every field using it.
+    fn pattern(&mut self, hint: &str, regex: &str) -> String {
+        if let Some((name, _)) = self.patterns.iter().find(|(_, r)| r == regex) {
+            return name.clone();
+        }
+        let name = self.declare(&format!("{hint}Pattern"));
+        self.patterns.push((name.clone(), regex.to_string()));
+        name
+    }
+
+    /// Patterns, enums, then records, in conversion order, with the
+    /// unmapped elements and review notes.
+    pub(crate) fn into_declarations(self) -> (Vec<AstNode>, Vec<UnmappedElement>, Vec<String>) {
+        let mut declarations: Vec<AstNode> = self
+            .patterns
+            .into_iter()
+            .map(|(name, regex)| AstNode::Pattern { name, regex })
+            .collect();
+        declarations.extend(self.enums.into_iter().map(AstNode::Enum));
+        declarations.extend(self.records.into_iter().map(AstNode::Record));
+        (declarations, self.unmapped, self.review)
+    }
+}
+
+/// `snake(raw)` made unique among `taken` with a `_<n>` suffix, and the
+/// unsuffixed name.
+fn unique_snake(raw: &str, taken: &mut BTreeSet<String>) -> (String, String) {
+    let base = snake(raw);
+    let mut name = base.clone();
+    let mut n = 1;
+    while !taken.insert(name.clone()) {
+        n += 1;
+        name = format!("{base}_{n}");
+    }
+    (name, base)
+}
+
+pub(crate) fn is_object(schema: &Value) -> bool {
+    text(schema, "type") == Some("object") || schema.get("properties").is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_splits_camel_case_and_acronyms() {
+        assert_eq!(snake("orderId"), "order_id");
+        assert_eq!(snake("HTTPStatus"), "http_status");
+        assert_eq!(snake("Idempotency-Key"), "idempotency_key");
+        assert_eq!(snake("get /orders/{orderId}"), "get_orders_order_id");
+        assert_eq!(snake("2fa"), "_2fa");
+    }
+
+    #[test]
+    fn converts_object_schema_with_constraints() {
+        let doc = parse_document(
+            r#"
+components:
+  schemas:
+    Order:
+      type: object
+      required: [orderId, total]
+      additionalProperties: false
+      properties:
+        orderId: {type: string, format: uuid}
+        total: {type: number, minimum: 0, exclusiveMinimum: true}
+        sku: {type: string, pattern: "^[A-Z]{3}$", maxLength: 3}
+        status: {type: string, enum: [placed, in-transit]}
+        tags: {type: array, items: {type: string}, maxItems: 5}
+        extra: {oneOf: [{type: string}, {type: integer}]}
+"#,
+            "test",
+        )
+        .unwrap();
+        let mut schemas = SchemaImporter::new(&doc);
+        schemas.import_components("components/schemas");
+        let order = schemas.record("Order").unwrap();
+        let names: Vec<&str> = order.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["order_id", "total", "sku", "status", "tags"]);
+        assert!(!order.fields[1].is_optional);
+        assert_eq!(
+            order.fields[1].constraints,
+            [FieldConstraintDecl::ExclusiveMin(Decimal::ZERO)]
+        );
+        assert_eq!(
+            order.fields[2].constraints,
+            [
+                FieldConstraintDecl::MaxLength(3),
+                FieldConstraintDecl::Pattern("OrderSkuPattern".to_string())
+            ]
+        );
+        assert_eq!(order.fields[3].field_type, named("OrderStatus"));
+        let (declarations, unmapped, review) = schemas.into_declarations();
+        assert_eq!(
+            review,
+            ["Order.orderId is imported as field order_id; its wire name changes"]
+        );
+        assert!(matches!(&declarations[1], AstNode::Enum(e)
+            if e.members[1].name == "in_transit" && e.members[1].wire == "in-transit"));
+        assert_eq!(unmapped.len(), 1);
+        assert_eq!(unmapped[0].kind, "oneOf");
+        assert_eq!(
+            unmapped[0].id,
+            "#/components/schemas/Order/properties/extra"
+        );
+    }
+}
@@ -40,9 +40,11 @@ pub mod concept_id;
 pub mod error;
 pub mod formatter;
 pub mod graph;
+pub mod import;
 pub mod kg;
 pub mod kg_import;
 pub mod module;
+pub mod package;
 pub mod parser;
 pub mod patterns;
 pub mod policy;
@@ -57,6 +59,9 @@ pub mod uuid_module;
 pub mod validation_error;
 pub mod validation_result;
 
+#[cfg(feature = "capi")]
+pub mod capi;
+
 #[cfg(feature = "cli")]
 pub mod cli;
 
@@ -129,6 +134,7 @@ fn domainforge(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<python::primitives::Relation>()?;
     m.add_class::<python::registry::NamespaceRegistry>()?;
     m.add_class::<python::registry::NamespaceBinding>()?;
+    m.add_class::<python::workspace::WorkspaceResolution>()?;
     m.add_class::<python::policy::Severity>()?;
     m.add_class::<python::policy::Violation>()?;
     m.add_class::<python::policy::EvaluationResult>()?;
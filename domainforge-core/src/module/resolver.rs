@@ -4,6 +4,7 @@ use crate::application::diagnostic::{
 };
 use crate::application::ApplicationDiagnosticCode;
 use crate::error::fuzzy::levenshtein_distance;
+use crate::package::{PackageSet, PACKAGE_SCHEME};
 use crate::parser::ast::{Ast, AstNode, ImportDecl, ImportSpecifier};
 use crate::parser::{parse_source, ParseError, ParseOptions, ParseResult};
 use crate::registry::{NamespaceBinding, NamespaceRegistry};
@@ -30,6 +31,7 @@ pub struct ModuleInfo {
 pub struct ModuleResolver<'a> {
     registry: &'a NamespaceRegistry,
     bindings: Vec<NamespaceBinding>,
+    packages: Option<PackageSet>,
     loaded_modules: HashMap<PathBuf, ModuleInfo>,
     visiting: HashSet<PathBuf>,
 }
@@ -42,6 +44,7 @@ impl<'a> ModuleResolver<'a> {
         Ok(Self {
             registry,
             bindings,
+            packages: None,
             loaded_modules: HashMap::new(),
             visiting: HashSet::new(),
         })
@@ -57,6 +60,7 @@ impl<'a> ModuleResolver<'a> {
             .canonicalize()
             .unwrap_or_else(|_| entry_path.as_ref().to_path_buf());
         let ast = parse_source(source)?;
+        self.discover_packages(&path)?;
         self.visit(&path, &ast)?;
         Ok(ast)
     }
@@ -70,9 +74,20 @@ impl<'a> ModuleResolver<'a> {
             .as_ref()
             .canonicalize()
             .unwrap_or_else(|_| entry_path.as_ref().to_path_buf());
+        self.discover_packages(&path)?;
         self.visit(&path, ast)
     }
 
+    /// Load the locked packages of the entry's workspace, once, so `pkg:`
+    /// imports resolve.
+    fn discover_packages(&mut self, entry_path: &Path) -> ParseResult<()> {
+        if self.packages.is_none() {
+            self.packages = PackageSet::discover(entry_path)
+                .map_err(|e| ParseError::GrammarError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
     fn visit(&mut self, path: &Path, ast: &Ast) -> ParseResult<()> {
         let canonical = if path.to_string_lossy().starts_with("__std__") {
             path.to_path_buf()
@@ -150,6 +165,18 @@ impl<'a> ModuleResolver<'a> {
         if namespace == "std" || namespace.starts_with("std:") {
             return Ok(PathBuf::from(format!("__std__{}", namespace)));
         }
+        if namespace.starts_with(PACKAGE_SCHEME) {
+            let packages = self.packages.as_ref().ok_or_else(|| {
+                ParseError::GrammarError(format!(
+                    "Package import '{}' needs a {} workspace",
+                    namespace,
+                    crate::package::MANIFEST_FILE_NAME
+                ))
+            })?;
+            return packages
+                .resolve_specifier(namespace)
+                .map_err(|e| ParseError::GrammarError(e.to_string()));
+        }
 
         self.bindings
             .iter()
@@ -417,6 +444,12 @@ pub(crate) fn source_map_from_filesystem(
         })?,
         None => Vec::new(),
     };
+    let packages = PackageSet::discover(&entry_path).map_err(|error| {
+        vec![ApplicationDiagnostic::closure_error(
+            APP014_UNRESOLVED_SPECIFIER,
+            format!("failed to load package workspace: {error}"),
+        )]
+    })?;
     let mut sources: IndexMap<PathBuf, String> = IndexMap::new();
 
     #[allow(clippy::too_many_arguments)]
@@ -426,6 +459,7 @@ pub(crate) fn source_map_from_filesystem(
         registry: Option<&NamespaceRegistry>,
         default_namespace: Option<&str>,
         bindings: &[NamespaceBinding],
+        packages: Option<&PackageSet>,
         sources: &mut IndexMap<PathBuf, String>,
         is_entry: bool,
         depth: usize,
@@ -513,6 +547,25 @@ pub(crate) fn source_map_from_filesystem(
                 path.parent()
                     .unwrap_or_else(|| Path::new("."))
                     .join(specifier)
+            } else if specifier.starts_with(PACKAGE_SCHEME) {
+                let resolved = match packages {
+                    Some(packages) => packages
+                        .resolve_specifier(specifier)
+                        .map_err(|e| e.to_string()),
+                    None => Err(format!(
+                        "no {} workspace encloses the entry",
+                        crate::package::MANIFEST_FILE_NAME
+                    )),
+                };
+                resolved.map_err(|reason| {
+                    vec![ApplicationDiagnostic::closure_error(
+                        APP014_UNRESOLVED_SPECIFIER,
+                        format!(
+                            "package import '{specifier}' in '{}' is unresolved: {reason}",
+                            path.display()
+                        ),
+                    )]
+                })?
             } else {
                 let mut matches = bindings
                     .iter()
@@ -537,6 +590,7 @@ pub(crate) fn source_map_from_filesystem(
                 registry,
                 default_namespace,
                 bindings,
+                packages,
                 sources,
                 false,
                 depth + 1,
@@ -551,16 +605,29 @@ pub(crate) fn source_map_from_filesystem(
         registry,
         default_namespace,
         &bindings,
+        packages.as_ref(),
         &mut sources,
         true,
         0,
     )?;
 
+    // Package modules are keyed `pkg:<name>/<module>` wherever they live on
+    // disk, so only the workspace's own modules share a common root.
+    let package_ids: HashMap<PathBuf, String> = sources
+        .keys()
+        .filter_map(|path| {
+            let id = packages.as_ref()?.logical_id(path)?;
+            Some((path.clone(), id))
+        })
+        .collect();
     let mut root = entry_path
         .parent()
         .unwrap_or(entry_path.as_path())
         .to_path_buf();
-    while sources.keys().any(|path| !path.starts_with(&root)) {
+    while sources
+        .keys()
+        .any(|path| !package_ids.contains_key(path) && !path.starts_with(&root))
+    {
         if !root.pop() {
             return Err(vec![ApplicationDiagnostic::closure_error(
                 APP014_UNRESOLVED_SPECIFIER,
@@ -572,6 +639,9 @@ pub(crate) fn source_map_from_filesystem(
     let mut ordered: Vec<(String, String)> = sources
         .into_iter()
         .map(|(path, source)| {
+            if let Some(id) = package_ids.get(&path) {
+                return (id.clone(), source);
+            }
             let logical = path
                 .strip_prefix(&root)
                 .expect("common root contains every collected module")
@@ -758,6 +828,9 @@ pub(crate) fn resolve_source_map(
                 )
                 .with_document_kind("authored_source");
                 d.context.logical_module_id = Some(logical_id.clone());
+                if let Some((line, column)) = e.location() {
+                    d = d.at(logical_id, line, column);
+                }
                 diags.push(d);
             }
         }
@@ -848,6 +921,31 @@ pub(crate) fn resolve_source_map(
                         None
                     }
                 }
+            } else if spec.starts_with(PACKAGE_SCHEME) {
+                // Package modules are keyed by their own specifier.
+                match crate::package::parse_specifier(spec) {
+                    Ok((name, module)) => {
+                        let id = format!("{PACKAGE_SCHEME}{name}/{module}");
+                        if parsed.contains_key(&id) {
+                            Some(id)
+                        } else {
+                            diags.push(ApplicationDiagnostic::closure_error(
+                                APP014_UNRESOLVED_SPECIFIER,
+                                format!(
+                                    "package import '{spec}' in '{logical_id}' resolves to '{id}', which is not in sources_json"
+                                ),
+                            ));
+                            None
+                        }
+                    }
+                    Err(error) => {
+                        diags.push(ApplicationDiagnostic::closure_error(
+                            APP014_UNRESOLVED_SPECIFIER,
+                            format!("{error} (imported by '{logical_id}')"),
+                        ));
+                        None
+                    }
+                }
             } else if spec == "std" || spec.starts_with("std:") {
                 match builtin_std_source(spec) {
                     Some(source) => {
@@ -1136,6 +1234,31 @@ mod source_map_tests {
             .all(|m| m.source_hash.starts_with("sha256:")));
     }
 
+    #[test]
+    fn package_import_resolves_to_its_pkg_logical_id() {
+        let sources = serde_json::json!({
+            "app.sea": "@namespace \"app\"\nimport { Payment } from \"pkg:payments/core\"\n",
+            "pkg:payments/core.sea": "@namespace \"payments\"\nexport Entity \"Payment\"\n",
+        })
+        .to_string();
+        let set = resolve("app.sea", &sources).unwrap();
+        assert_eq!(
+            set.import_graph,
+            [ImportEdge {
+                importer: "app.sea".to_string(),
+                imported: "pkg:payments/core.sea".to_string(),
+            }]
+        );
+
+        let sources = serde_json::json!({
+            "app.sea": "@namespace \"app\"\nimport { Payment } from \"pkg:payments/ledger\"\n",
+        })
+        .to_string();
+        let err = resolve("app.sea", &sources).unwrap_err();
+        assert_eq!(reason(&err), "unresolved_specifier");
+        assert!(err[0].message.contains("pkg:payments/ledger.sea"));
+    }
+
     #[test]
     fn relative_import_escaping_root_is_unresolved_specifier() {
         let sources = serde_json::json!({
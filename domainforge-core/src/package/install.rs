@@ -0,0 +1,318 @@
+//! Locking, fetching and vendoring packages (the `domainforge package`
+//! commands). Resolution itself lives in the parent module and never touches
+//! archives; these functions put package modules where the lockfile says they
+//! are.
+
+use super::{
+    content_hash, sea_files, DependencySource, DependencySpec, LockedPackage, Lockfile,
+    PackageError, PackageManifest, CACHE_DIR, LOCK_FILE_NAME, MANIFEST_FILE_NAME, VENDOR_DIR,
+};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const PATH_SOURCE: &str = "path+";
+const ARCHIVE_SOURCE: &str = "archive+";
+
+/// Resolve the dependency closure of the workspace in `root`, unpack archive
+/// packages into the cache, and write `domainforge.lock`.
+pub fn lock(root: &Path) -> Result<Lockfile, PackageError> {
+    let root = root.canonicalize()?;
+    let manifest_path = root.join(MANIFEST_FILE_NAME);
+    if !manifest_path.is_file() {
+        return Err(PackageError::MissingManifest(root));
+    }
+    let manifest = PackageManifest::from_file(&manifest_path)?;
+
+    let mut queue: VecDeque<(String, DependencySpec, PathBuf)> = manifest
+        .dependencies
+        .into_iter()
+        .map(|(name, spec)| (name, spec, root.clone()))
+        .collect();
+    let mut packages: Vec<LockedPackage> = Vec::new();
+
+    while let Some((name, spec, base)) = queue.pop_front() {
+        let source = spec.source(&name)?;
+        let declared = match &source {
+            DependencySource::Path(path) => path,
+            DependencySource::Archive { archive, .. } => archive,
+        };
+        let location =
+            base.join(declared)
+                .canonicalize()
+                .map_err(|_| PackageError::InvalidDependency {
+                    name: name.clone(),
+                    message: format!("{} does not exist", base.join(declared).display()),
+                })?;
+        let source_id = match &source {
+            DependencySource::Path(_) => {
+                format!("{PATH_SOURCE}{}", relative_path(&root, &location))
+            }
+            DependencySource::Archive { .. } => {
+                format!("{ARCHIVE_SOURCE}{}", relative_path(&root, &location))
+            }
+        };
+        if let Some(existing) = packages.iter().find(|p| p.name == name) {
+            if existing.source != source_id {
+                return Err(PackageError::Conflict {
+                    name,
+                    existing: existing.source.clone(),
+                    requested: source_id,
+                });
+            }
+            continue;
+        }
+
+        let (dir, pinned_version, archive_sha256) = match source {
+            DependencySource::Path(_) => (location, None, None),
+            DependencySource::Archive {
+                version, sha256, ..
+            } => {
+                let sha256 = sha256.to_ascii_lowercase();
+                verify_archive(&name, &location, &sha256)?;
+                let dest = contained(&name, &root.join(CACHE_DIR), &format!("{name}-{version}"))?;
+                let dir = unpack(&name, &location, &dest)?;
+                (dir, Some(version.to_string()), Some(sha256))
+            }
+        };
+
+        let dependency_manifest = if dir.join(MANIFEST_FILE_NAME).is_file() {
+            PackageManifest::from_file(dir.join(MANIFEST_FILE_NAME))?
+        } else {
+            PackageManifest::default()
+        };
+        let info = dependency_manifest.package.as_ref();
+        if let Some(info) = info.filter(|info| info.name != name) {
+            return Err(PackageError::InvalidDependency {
+                name,
+                message: format!("its manifest names the package '{}'", info.name),
+            });
+        }
+        let version = match (pinned_version, info.map(|info| info.version.clone())) {
+            (Some(pinned), Some(found)) if pinned != found => {
+                return Err(PackageError::InvalidDependency {
+                    name,
+                    message: format!("pinned to version {pinned} but the archive holds {found}"),
+                })
+            }
+            (Some(pinned), _) => pinned,
+            (None, Some(found)) => found,
+            (None, None) => "0.0.0".to_string(),
+        };
+        let module_root = dependency_manifest.module_root(&dir).canonicalize()?;
+        for (dependency, spec) in &dependency_manifest.dependencies {
+            queue.push_back((dependency.clone(), spec.clone(), dir.clone()));
+        }
+        packages.push(LockedPackage {
+            name,
+            version,
+            source: source_id,
+            path: relative_path(&root, &module_root),
+            archive_sha256,
+            checksum: content_hash(&module_root)?,
+            dependencies: dependency_manifest.dependencies.into_keys().collect(),
+        });
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    let lock = Lockfile::new(packages);
+    fs::write(root.join(LOCK_FILE_NAME), lock.to_toml())?;
+    Ok(lock)
+}
+
+/// Unpack locked archive packages that are missing from (or stale in) the
+/// cache. Returns the names of the packages unpacked.
+pub fn fetch(root: &Path) -> Result<Vec<String>, PackageError> {
+    let root = root.canonicalize()?;
+    let lock_path = root.join(LOCK_FILE_NAME);
+    if !lock_path.is_file() {
+        return Err(PackageError::MissingLock(root));
+    }
+    let lock = Lockfile::from_file(lock_path)?;
+    let mut fetched = Vec::new();
+    // The lock is name-sorted; an archive shipped inside another archive
+    // package needs its parent unpacked first, so retry until no progress.
+    let mut pending: Vec<&LockedPackage> = lock
+        .packages
+        .iter()
+        .filter(|p| p.source.starts_with(ARCHIVE_SOURCE))
+        .collect();
+    while !pending.is_empty() {
+        let before = pending.len();
+        let mut waiting = Vec::new();
+        for locked in pending {
+            let module_root = root.join(&locked.path);
+            if module_root.is_dir() && content_hash(&module_root)? == locked.checksum {
+                continue;
+            }
+            let archive = root.join(&locked.source[ARCHIVE_SOURCE.len()..]);
+            if !archive.is_file() {
+                waiting.push(locked);
+                continue;
+            }
+            let sha256 = locked.archive_sha256.as_deref().unwrap_or_default();
+            verify_archive(&locked.name, &archive, sha256)?;
+            let dest = contained(
+                &locked.name,
+                &root.join(CACHE_DIR),
+                &format!("{}-{}", locked.name, locked.version),
+            )?;
+            unpack(&locked.name, &archive, &dest)?;
+            let actual = content_hash(&module_root)?;
+            if actual != locked.checksum {
+                return Err(PackageError::ChecksumMismatch {
+                    name: locked.name.clone(),
+                    expected: locked.checksum.clone(),
+                    actual,
+                });
+            }
+            fetched.push(locked.name.clone());
+        }
+        if waiting.len() == before {
+            let locked = waiting[0];
+            return Err(PackageError::NotMaterialized {
+                name: locked.name.clone(),
+                path: root.join(&locked.source[ARCHIVE_SOURCE.len()..]),
+            });
+        }
+        pending = waiting;
+    }
+    Ok(fetched)
+}
+
+/// Copy every locked package's modules into `vendor/<name>/`, so the
+/// workspace resolves without the original paths or archives. Returns the
+/// names of the packages vendored.
+pub fn vendor(root: &Path) -> Result<Vec<String>, PackageError> {
+    let root = root.canonicalize()?;
+    let lock_path = root.join(LOCK_FILE_NAME);
+    if !lock_path.is_file() {
+        return Err(PackageError::MissingLock(root));
+    }
+    let lock = Lockfile::from_file(lock_path)?;
+    let mut vendored = Vec::new();
+    for locked in &lock.packages {
+        let source = root.join(&locked.path);
+        let dest = contained(&locked.name, &root.join(VENDOR_DIR), &locked.name)?;
+        if !source.is_dir() {
+            // Already vendored from a source that is no longer around.
+            if dest.is_dir() && content_hash(&dest)? == locked.checksum {
+                vendored.push(locked.name.clone());
+                continue;
+            }
+            return Err(PackageError::NotMaterialized {
+                name: locked.name.clone(),
+                path: source,
+            });
+        }
+        let actual = content_hash(&source)?;
+        if actual != locked.checksum {
+            return Err(PackageError::ChecksumMismatch {
+                name: locked.name.clone(),
+                expected: locked.checksum.clone(),
+                actual,
+            });
+        }
+        if dest.exists() {
+            fs::remove_dir_all(&dest)?;
+        }
+        for (relative, path) in sea_files(&source)? {
+            let target = dest.join(&relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&path, &target)?;
+        }
+        vendored.push(locked.name.clone());
+    }
+    Ok(vendored)
+}
+
+/// `base/<entry>`, refused unless it is a directory directly inside `base`:
+/// it is about to be removed and rewritten.
+fn contained(name: &str, base: &Path, entry: &str) -> Result<PathBuf, PackageError> {
+    let dest = base.join(entry);
+    let inside = dest.starts_with(base)
+        && dest.parent() == Some(base)
+        && matches!(dest.components().next_back(), Some(Component::Normal(_)));
+    if !inside {
+        return Err(PackageError::InvalidDependency {
+            name: name.to_string(),
+            message: format!("{} is not inside {}", dest.display(), base.display()),
+        });
+    }
+    Ok(dest)
+}
+
+fn verify_archive(name: &str, archive: &Path, sha256: &str) -> Result<(), PackageError> {
+    let actual = format!("{:x}", Sha256::digest(fs::read(archive)?));
+    if actual != sha256 {
+        return Err(PackageError::ChecksumMismatch {
+            name: name.to_string(),
+            expected: sha256.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Unpack `archive` into a fresh `dest` and return the package directory: the
+/// archive root, or its single top-level directory when it has one.
+fn unpack(name: &str, archive: &Path, dest: &Path) -> Result<PathBuf, PackageError> {
+    let failed = |message: String| PackageError::Archive {
+        name: name.to_string(),
+        message,
+    };
+    if dest.exists() {
+        fs::remove_dir_all(dest)?;
+    }
+    fs::create_dir_all(dest)?;
+    let file_name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let file = fs::File::open(archive)?;
+    let result = if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+        tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(dest)
+    } else if file_name.ends_with(".tar") {
+        tar::Archive::new(file).unpack(dest)
+    } else {
+        return Err(failed(format!(
+            "{} is not a .tar, .tar.gz or .tgz archive",
+            archive.display()
+        )));
+    };
+    result.map_err(|e| failed(e.to_string()))?;
+
+    if dest.join(MANIFEST_FILE_NAME).is_file() {
+        return Ok(dest.canonicalize()?);
+    }
+    let entries: Vec<PathBuf> = fs::read_dir(dest)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    match entries.as_slice() {
+        [only] if only.is_dir() => Ok(only.canonicalize()?),
+        _ => Ok(dest.canonicalize()?),
+    }
+}
+
+/// `to` relative to `from`, with `/` separators (both must be absolute).
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let parts: Vec<String> = std::iter::repeat("..".to_string())
+        .take(from.len() - common)
+        .chain(
+            to[common..]
+                .iter()
+                .map(|c| c.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect();
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
@@ -0,0 +1,660 @@
+//! Versioned model packages.
+//!
+//! A workspace declares the packages it depends on in `domainforge.toml`:
+//!
+//! ```toml
+//! [package]
+//! name = "orders"
+//! version = "1.2.0"
+//! source = "domain"          # module root, relative to the manifest
+//!
+//! [dependencies]
+//! payments = { path = "../payments" }
+//! billing = { archive = "archives/billing-2.0.0.tar.gz", version = "2.0.0", sha256 = "…" }
+//! ```
+//!
+//! `domainforge package lock` resolves the dependency closure into
+//! `domainforge.lock`, recording where each package's modules live and a
+//! content hash over them. Imports then name modules by package rather than by
+//! path (`import { Payment } from "pkg:payments/core"`), and resolution refuses
+//! any package whose modules no longer match the locked hash. `domainforge
+//! package vendor` copies the locked modules into `vendor/<name>/`, which takes
+//! precedence over the locked location so a vendored workspace resolves
+//! offline.
+
+#[cfg(feature = "cli")]
+pub mod install;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub const MANIFEST_FILE_NAME: &str = "domainforge.toml";
+pub const LOCK_FILE_NAME: &str = "domainforge.lock";
+/// Vendored package modules, one directory per package, under the workspace root.
+pub const VENDOR_DIR: &str = "vendor";
+/// Extracted archive packages, under the workspace root.
+pub const CACHE_DIR: &str = ".domainforge/packages";
+/// Import specifier scheme for package modules.
+pub const PACKAGE_SCHEME: &str = "pkg:";
+
+const LOCK_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PackageManifest {
+    #[serde(default)]
+    pub package: Option<PackageInfo>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, DependencySpec>,
+    /// Projection plugin name to executable, relative to the manifest (see
+    /// [`crate::projection::plugin`]).
+    #[serde(default)]
+    pub plugins: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: String,
+    /// Module root relative to the manifest; defaults to the manifest's directory.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// One `[dependencies]` entry: either a local `path`, or an `archive`
+/// (`.tar`, `.tar.gz` or `.tgz`) pinned by `version` and `sha256`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DependencySpec {
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub archive: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySource<'a> {
+    Path(&'a str),
+    Archive {
+        archive: &'a str,
+        version: &'a str,
+        sha256: &'a str,
+    },
+}
+
+impl DependencySpec {
+    pub fn source(&self, name: &str) -> Result<DependencySource<'_>, PackageError> {
+        let invalid = |message: &str| PackageError::InvalidDependency {
+            name: name.to_string(),
+            message: message.to_string(),
+        };
+        match (&self.path, &self.archive) {
+            (Some(path), None) => {
+                if self.sha256.is_some() {
+                    return Err(invalid("`sha256` applies to archive dependencies only"));
+                }
+                Ok(DependencySource::Path(path))
+            }
+            (None, Some(archive)) => {
+                let version = self
+                    .version
+                    .as_deref()
+                    .ok_or_else(|| invalid("archive dependencies must pin a `version`"))?;
+                let sha256 = self
+                    .sha256
+                    .as_deref()
+                    .ok_or_else(|| invalid("archive dependencies must pin a `sha256`"))?;
+                if sha256.len() != 64 || !sha256.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(invalid("`sha256` must be 64 hexadecimal characters"));
+                }
+                if !is_semver(version) {
+                    return Err(invalid(
+                        "`version` must be a semantic version such as `1.2.0`",
+                    ));
+                }
+                Ok(DependencySource::Archive {
+                    archive,
+                    version,
+                    sha256,
+                })
+            }
+            (Some(_), Some(_)) => Err(invalid("declare either `path` or `archive`, not both")),
+            (None, None) => Err(invalid("declare a `path` or an `archive`")),
+        }
+    }
+}
+
+impl PackageManifest {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PackageError> {
+        let contents = fs::read_to_string(path.as_ref())?;
+        let manifest: Self = toml::from_str(&contents)?;
+        if let Some(package) = &manifest.package {
+            validate_package_name(&package.name)?;
+        }
+        for (name, spec) in &manifest.dependencies {
+            validate_package_name(name)?;
+            spec.source(name)?;
+        }
+        Ok(manifest)
+    }
+
+    /// Module root of the package whose manifest sits in `dir`.
+    pub fn module_root(&self, dir: &Path) -> PathBuf {
+        match self.package.as_ref().and_then(|p| p.source.as_deref()) {
+            Some(source) => dir.join(source),
+            None => dir.to_path_buf(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Lockfile {
+    pub version: u8,
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    /// Where the package came from: `path+<dir>` or `archive+<file>`,
+    /// relative to the workspace root.
+    pub source: String,
+    /// Module root relative to the workspace root.
+    pub path: String,
+    /// SHA-256 of the archive file, for archive packages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_sha256: Option<String>,
+    /// Content hash over the package's `.sea` modules (see [`content_hash`]).
+    pub checksum: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+impl Lockfile {
+    pub fn new(packages: Vec<LockedPackage>) -> Self {
+        Self {
+            version: LOCK_VERSION,
+            packages,
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PackageError> {
+        let contents = fs::read_to_string(path.as_ref())?;
+        let lock: Self = toml::from_str(&contents)?;
+        if lock.version != LOCK_VERSION {
+            return Err(PackageError::InvalidLockVersion(lock.version));
+        }
+        for locked in &lock.packages {
+            locked.validate()?;
+        }
+        Ok(lock)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let body = toml::to_string(self).expect("lockfile fields serialize as TOML");
+        format!("# Generated by `domainforge package lock`. Do not edit.\n{body}")
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+}
+
+impl LockedPackage {
+    /// Refuse entries that would place files outside the workspace: the
+    /// name and version name cache and vendor directories, so each must be a
+    /// single path component, and the module root must be relative. Archive
+    /// packages live in the cache, so their module root may not climb out of
+    /// the workspace either.
+    fn validate(&self) -> Result<(), PackageError> {
+        let invalid = |message: String| PackageError::InvalidLockEntry {
+            name: self.name.clone(),
+            message,
+        };
+        validate_package_name(&self.name)?;
+        if !is_single_component(&self.version) {
+            return Err(invalid(format!(
+                "version '{}' is not a single path component",
+                self.version
+            )));
+        }
+        let path = Path::new(&self.path);
+        if !path.components().all(|c| {
+            matches!(
+                c,
+                Component::Normal(_) | Component::CurDir | Component::ParentDir
+            )
+        }) {
+            return Err(invalid(format!("path '{}' is not relative", self.path)));
+        }
+        if self.source.starts_with("archive+")
+            && path.components().any(|c| c == Component::ParentDir)
+        {
+            return Err(invalid(format!(
+                "archive package path '{}' leaves the workspace",
+                self.path
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum PackageError {
+    Io(std::io::Error),
+    ParseToml(toml::de::Error),
+    InvalidLockVersion(u8),
+    InvalidName(String),
+    InvalidDependency {
+        name: String,
+        message: String,
+    },
+    InvalidSpecifier {
+        specifier: String,
+        message: String,
+    },
+    InvalidLockEntry {
+        name: String,
+        message: String,
+    },
+    MissingManifest(PathBuf),
+    MissingLock(PathBuf),
+    NotLocked(String),
+    NotMaterialized {
+        name: String,
+        path: PathBuf,
+    },
+    ChecksumMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+    Conflict {
+        name: String,
+        existing: String,
+        requested: String,
+    },
+    ModuleNotFound {
+        specifier: String,
+        path: PathBuf,
+    },
+    Archive {
+        name: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for PackageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageError::Io(err) => write!(f, "IO error: {}", err),
+            PackageError::ParseToml(err) => write!(f, "Failed to parse package file: {}", err),
+            PackageError::InvalidLockVersion(version) => {
+                write!(f, "Unsupported {} version {}", LOCK_FILE_NAME, version)
+            }
+            PackageError::InvalidName(name) => write!(
+                f,
+                "Invalid package name '{}': use lowercase letters, digits, '-' and '_'",
+                name
+            ),
+            PackageError::InvalidDependency { name, message } => {
+                write!(f, "Invalid dependency '{}': {}", name, message)
+            }
+            PackageError::InvalidSpecifier { specifier, message } => {
+                write!(f, "Invalid package import '{}': {}", specifier, message)
+            }
+            PackageError::InvalidLockEntry { name, message } => {
+                write!(
+                    f,
+                    "Invalid {} entry '{}': {}",
+                    LOCK_FILE_NAME, name, message
+                )
+            }
+            PackageError::MissingManifest(dir) => {
+                write!(f, "No {} found in {}", MANIFEST_FILE_NAME, dir.display())
+            }
+            PackageError::MissingLock(dir) => write!(
+                f,
+                "No {} found in {}; run `domainforge package lock`",
+                LOCK_FILE_NAME,
+                dir.display()
+            ),
+            PackageError::NotLocked(name) => write!(
+                f,
+                "Package '{}' is not in {}; add it to {} and run `domainforge package lock`",
+                name, LOCK_FILE_NAME, MANIFEST_FILE_NAME
+            ),
+            PackageError::NotMaterialized { name, path } => write!(
+                f,
+                "Package '{}' is locked but {} does not exist; run `domainforge package fetch`",
+                name,
+                path.display()
+            ),
+            PackageError::ChecksumMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Package '{}' does not match its locked checksum (expected {}, found {})",
+                name, expected, actual
+            ),
+            PackageError::Conflict {
+                name,
+                existing,
+                requested,
+            } => write!(
+                f,
+                "Package '{}' is required from both '{}' and '{}'",
+                name, existing, requested
+            ),
+            PackageError::ModuleNotFound { specifier, path } => write!(
+                f,
+                "Package import '{}' names no module ({} does not exist)",
+                specifier,
+                path.display()
+            ),
+            PackageError::Archive { name, message } => {
+                write!(f, "Failed to unpack package '{}': {}", name, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PackageError {}
+
+impl From<std::io::Error> for PackageError {
+    fn from(value: std::io::Error) -> Self {
+        PackageError::Io(value)
+    }
+}
+
+impl From<toml::de::Error> for PackageError {
+    fn from(value: toml::de::Error) -> Self {
+        PackageError::ParseToml(value)
+    }
+}
+
+/// The nearest directory at or above `start` holding a `domainforge.toml`.
+pub fn find_workspace_root(start: impl AsRef<Path>) -> Result<Option<PathBuf>, PackageError> {
+    let start = start.as_ref();
+    let start = if start.is_file() {
+        start.parent().unwrap_or_else(|| Path::new("."))
+    } else {
+        start
+    };
+    let start = if start.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        start
+    };
+    let mut dir = start.canonicalize()?;
+    loop {
+        if dir.join(MANIFEST_FILE_NAME).is_file() {
+            return Ok(Some(dir));
+        }
+        if !dir.pop() {
+            return Ok(None);
+        }
+    }
+}
+
+pub fn validate_package_name(name: &str) -> Result<(), PackageError> {
+    let mut chars = name.chars();
+    let valid = matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c.is_ascii_digit())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(PackageError::InvalidName(name.to_string()))
+    }
+}
+
+/// `MAJOR.MINOR.PATCH`, optionally followed by `-<pre-release>` and
+/// `+<build>` identifiers of ASCII alphanumerics and `-`.
+fn is_semver(version: &str) -> bool {
+    let identifiers = |part: &str| {
+        part.split('.')
+            .all(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-'))
+    };
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let numbers: Vec<&str> = core.split('.').collect();
+    numbers.len() == 3
+        && numbers
+            .iter()
+            .all(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        && pre.map_or(true, identifiers)
+        && build.map_or(true, identifiers)
+}
+
+/// Whether `value` names exactly one ordinary path component.
+fn is_single_component(value: &str) -> bool {
+    !value.contains(['/', '\\'])
+        && matches!(
+            Path::new(value).components().collect::<Vec<_>>().as_slice(),
+            [Component::Normal(_)]
+        )
+}
+
+/// Split `pkg:<name>/<module>` into the package name and the module's path
+/// within the package, with the `.sea` extension added when omitted.
+pub fn parse_specifier(specifier: &str) -> Result<(&str, String), PackageError> {
+    let invalid = |message: &str| PackageError::InvalidSpecifier {
+        specifier: specifier.to_string(),
+        message: message.to_string(),
+    };
+    let rest = specifier
+        .strip_prefix(PACKAGE_SCHEME)
+        .ok_or_else(|| invalid("expected the `pkg:` scheme"))?;
+    let (name, module) = rest
+        .split_once('/')
+        .ok_or_else(|| invalid("expected `pkg:<package>/<module>`"))?;
+    validate_package_name(name).map_err(|_| invalid("invalid package name"))?;
+    if module
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+        || module.contains('\\')
+    {
+        return Err(invalid("module paths use plain `/`-separated segments"));
+    }
+    let module = if module.ends_with(".sea") {
+        module.to_string()
+    } else {
+        format!("{module}.sea")
+    };
+    Ok((name, module))
+}
+
+/// Content hash over every `.sea` file under `root`: SHA-256 of each file's
+/// `/`-separated relative path and bytes, in path order, as `sha256:<hex>`.
+pub fn content_hash(root: &Path) -> Result<String, PackageError> {
+    let mut hasher = Sha256::new();
+    for (relative, path) in sea_files(root)? {
+        let bytes = fs::read(&path)?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update((bytes.len() as u64).to_be_bytes());
+        hasher.update(&bytes);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+/// `.sea` files under `root` keyed by `/`-separated relative path, sorted.
+/// Hidden directories are skipped.
+pub(crate) fn sea_files(root: &Path) -> Result<Vec<(String, PathBuf)>, PackageError> {
+    fn walk(
+        dir: &Path,
+        prefix: &str,
+        out: &mut Vec<(String, PathBuf)>,
+    ) -> Result<(), PackageError> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let relative = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{prefix}/{name}")
+            };
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                walk(&entry.path(), &relative, out)?;
+            } else if file_type.is_file() && name.ends_with(".sea") {
+                out.push((relative, entry.path()));
+            }
+        }
+        Ok(())
+    }
+    let mut out = Vec::new();
+    walk(root, "", &mut out)?;
+    out.sort();
+    Ok(out)
+}
+
+/// A resolved package: where its modules live on disk right now.
+#[derive(Debug, Clone)]
+pub struct ResolvedPackage {
+    pub name: String,
+    pub version: String,
+    pub root: PathBuf,
+    pub checksum: String,
+}
+
+/// The locked packages of one workspace, used to resolve `pkg:` imports.
+#[derive(Debug, Clone)]
+pub struct PackageSet {
+    root: PathBuf,
+    lock: Lockfile,
+}
+
+impl PackageSet {
+    /// Load the packages of the workspace whose `domainforge.toml` sits in
+    /// `root`.
+    pub fn load(root: impl AsRef<Path>) -> Result<Self, PackageError> {
+        let root = root.as_ref().canonicalize()?;
+        if !root.join(MANIFEST_FILE_NAME).is_file() {
+            return Err(PackageError::MissingManifest(root));
+        }
+        let lock_path = root.join(LOCK_FILE_NAME);
+        if !lock_path.is_file() {
+            return Err(PackageError::MissingLock(root));
+        }
+        let lock = Lockfile::from_file(lock_path)?;
+        Ok(Self { root, lock })
+    }
+
+    /// Find the nearest `domainforge.toml` at or above `start` and load its
+    /// workspace. A manifest without dependencies and no lockfile yields an
+    /// empty set; no manifest yields `None`.
+    pub fn discover(start: impl AsRef<Path>) -> Result<Option<Self>, PackageError> {
+        let Some(dir) = find_workspace_root(start)? else {
+            return Ok(None);
+        };
+        if !dir.join(LOCK_FILE_NAME).is_file() {
+            let manifest = PackageManifest::from_file(dir.join(MANIFEST_FILE_NAME))?;
+            if manifest.dependencies.is_empty() {
+                return Ok(Some(Self {
+                    root: dir,
+                    lock: Lockfile::new(Vec::new()),
+                }));
+            }
+        }
+        Self::load(dir).map(Some)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn lock(&self) -> &Lockfile {
+        &self.lock
+    }
+
+    /// Locate a locked package's modules, preferring `vendor/<name>`, and
+    /// check them against the locked checksum.
+    pub fn package(&self, name: &str) -> Result<ResolvedPackage, PackageError> {
+        let locked = self
+            .lock
+            .get(name)
+            .ok_or_else(|| PackageError::NotLocked(name.to_string()))?;
+        let vendored = self.root.join(VENDOR_DIR).join(name);
+        let root = if vendored.is_dir() {
+            vendored
+        } else {
+            self.root.join(&locked.path)
+        };
+        if !root.is_dir() {
+            return Err(PackageError::NotMaterialized {
+                name: name.to_string(),
+                path: root,
+            });
+        }
+        let actual = content_hash(&root)?;
+        if actual != locked.checksum {
+            return Err(PackageError::ChecksumMismatch {
+                name: name.to_string(),
+                expected: locked.checksum.clone(),
+                actual,
+            });
+        }
+        Ok(ResolvedPackage {
+            name: locked.name.clone(),
+            version: locked.version.clone(),
+            root: root.canonicalize()?,
+            checksum: locked.checksum.clone(),
+        })
+    }
+
+    /// Resolve a `pkg:<name>/<module>` import to the module's file.
+    pub fn resolve_specifier(&self, specifier: &str) -> Result<PathBuf, PackageError> {
+        let (name, module) = parse_specifier(specifier)?;
+        let package = self.package(name)?;
+        let path = package.root.join(&module);
+        if !path.is_file() {
+            return Err(PackageError::ModuleNotFound {
+                specifier: specifier.to_string(),
+                path,
+            });
+        }
+        Ok(path)
+    }
+
+    /// The `pkg:<name>/<module>` logical ID of a file inside a locked
+    /// package, if it is one.
+    pub fn logical_id(&self, path: &Path) -> Option<String> {
+        self.lock.packages.iter().find_map(|locked| {
+            let vendored = self.root.join(VENDOR_DIR).join(&locked.name);
+            [vendored, self.root.join(&locked.path)]
+                .into_iter()
+                .filter_map(|root| root.canonicalize().ok())
+                .find_map(|root| {
+                    let relative = path.strip_prefix(root).ok()?;
+                    Some(format!(
+                        "{PACKAGE_SCHEME}{}/{}",
+                        locked.name,
+                        relative.to_string_lossy().replace('\\', "/")
+                    ))
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
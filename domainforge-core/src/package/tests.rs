@@ -0,0 +1,309 @@
+use super::*;
+use tempfile::TempDir;
+
+fn write(dir: &Path, path: &str, content: &str) {
+    let full_path = dir.join(path);
+    fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+    fs::write(full_path, content).unwrap();
+}
+
+fn locked(name: &str, path: &str, checksum: String) -> LockedPackage {
+    LockedPackage {
+        name: name.to_string(),
+        version: "1.0.0".to_string(),
+        source: format!("path+{path}"),
+        path: path.to_string(),
+        archive_sha256: None,
+        checksum,
+        dependencies: Vec::new(),
+    }
+}
+
+#[test]
+fn test_parse_specifier() {
+    assert_eq!(
+        parse_specifier("pkg:payments/core").unwrap(),
+        ("payments", "core.sea".to_string())
+    );
+    assert_eq!(
+        parse_specifier("pkg:payments/ledger/entries.sea").unwrap(),
+        ("payments", "ledger/entries.sea".to_string())
+    );
+    for bad in [
+        "pkg:payments",
+        "pkg:Payments/core",
+        "pkg:payments/../x",
+        "payments/core",
+    ] {
+        assert!(parse_specifier(bad).is_err(), "{bad} should be rejected");
+    }
+}
+
+#[test]
+fn test_archive_dependencies_must_pin_version_and_sha256() {
+    let temp = TempDir::new().unwrap();
+    write(
+        temp.path(),
+        MANIFEST_FILE_NAME,
+        r#"
+        [dependencies]
+        billing = { archive = "billing.tar.gz", version = "2.0.0" }
+    "#,
+    );
+    let err = PackageManifest::from_file(temp.path().join(MANIFEST_FILE_NAME)).unwrap_err();
+    assert!(err.to_string().contains("must pin a `sha256`"), "{err}");
+}
+
+#[test]
+fn test_archive_dependency_versions_must_be_semantic_versions() {
+    let temp = TempDir::new().unwrap();
+    write(
+        temp.path(),
+        MANIFEST_FILE_NAME,
+        &format!(
+            "[dependencies]\nbilling = {{ archive = \"billing.tar.gz\", version = \"../../victim\", sha256 = \"{}\" }}\n",
+            "0".repeat(64)
+        ),
+    );
+    let err = PackageManifest::from_file(temp.path().join(MANIFEST_FILE_NAME)).unwrap_err();
+    assert!(
+        err.to_string().contains("must be a semantic version"),
+        "{err}"
+    );
+    for good in ["1.2.0", "0.1.0-rc.1", "2.0.0+build-7"] {
+        assert!(is_semver(good), "{good} should be accepted");
+    }
+    for bad in ["1.2", "1.2.0/..", "1.2.0-", "v1.2.0", "1.2.0-a..b"] {
+        assert!(!is_semver(bad), "{bad} should be rejected");
+    }
+}
+
+#[test]
+fn test_resolves_locked_package_modules_and_rejects_drift() {
+    let temp = TempDir::new().unwrap();
+    write(temp.path(), "payments/core.sea", "Entity \"Payment\"\n");
+    write(
+        temp.path(),
+        "app/domainforge.toml",
+        "[dependencies]\npayments = { path = \"../payments\" }\n",
+    );
+    let checksum = content_hash(&temp.path().join("payments")).unwrap();
+    write(
+        temp.path(),
+        "app/domainforge.lock",
+        &Lockfile::new(vec![locked("payments", "../payments", checksum)]).to_toml(),
+    );
+    write(temp.path(), "app/model.sea", "");
+
+    let set = PackageSet::discover(temp.path().join("app/model.sea"))
+        .unwrap()
+        .unwrap();
+    let core = set.resolve_specifier("pkg:payments/core").unwrap();
+    assert!(core.ends_with("payments/core.sea"));
+    assert_eq!(
+        set.logical_id(&core).as_deref(),
+        Some("pkg:payments/core.sea")
+    );
+    assert!(matches!(
+        set.resolve_specifier("pkg:payments/missing"),
+        Err(PackageError::ModuleNotFound { .. })
+    ));
+    assert!(matches!(
+        set.resolve_specifier("pkg:ledger/core"),
+        Err(PackageError::NotLocked(_))
+    ));
+
+    write(temp.path(), "payments/core.sea", "Entity \"Refund\"\n");
+    assert!(matches!(
+        set.resolve_specifier("pkg:payments/core"),
+        Err(PackageError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_manifest_with_dependencies_requires_a_lock() {
+    let temp = TempDir::new().unwrap();
+    write(
+        temp.path(),
+        MANIFEST_FILE_NAME,
+        "[dependencies]\npayments = { path = \"../payments\" }\n",
+    );
+    assert!(matches!(
+        PackageSet::discover(temp.path()),
+        Err(PackageError::MissingLock(_))
+    ));
+}
+
+#[cfg(feature = "cli")]
+mod install_tests {
+    use super::*;
+    use crate::package::install::{fetch, lock, vendor};
+
+    fn tar_gz(files: &[(&str, &str)]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn test_lock_resolves_path_and_archive_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let archive = tar_gz(&[
+            (
+                "billing-2.0.0/domainforge.toml",
+                "[package]\nname = \"billing\"\nversion = \"2.0.0\"\nsource = \"sea\"\n",
+            ),
+            ("billing-2.0.0/sea/invoice.sea", "Entity \"Invoice\"\n"),
+        ]);
+        let sha256 = format!("{:x}", Sha256::digest(&archive));
+        fs::create_dir_all(temp.path().join("archives")).unwrap();
+        fs::write(temp.path().join("archives/billing-2.0.0.tar.gz"), &archive).unwrap();
+        write(
+            temp.path(),
+            "payments/domainforge.toml",
+            &format!(
+                "[package]\nname = \"payments\"\nversion = \"0.3.0\"\n\n[dependencies]\nbilling = {{ archive = \"../archives/billing-2.0.0.tar.gz\", version = \"2.0.0\", sha256 = \"{sha256}\" }}\n"
+            ),
+        );
+        write(temp.path(), "payments/core.sea", "Entity \"Payment\"\n");
+        write(
+            temp.path(),
+            "app/domainforge.toml",
+            "[package]\nname = \"app\"\nversion = \"1.0.0\"\n\n[dependencies]\npayments = { path = \"../payments\" }\n",
+        );
+        let app = temp.path().join("app");
+
+        let lockfile = lock(&app).unwrap();
+        let names: Vec<_> = lockfile.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["billing", "payments"]);
+        let billing = lockfile.get("billing").unwrap();
+        assert_eq!(billing.version, "2.0.0");
+        assert_eq!(billing.source, "archive+../archives/billing-2.0.0.tar.gz");
+        assert_eq!(
+            billing.path,
+            ".domainforge/packages/billing-2.0.0/billing-2.0.0/sea"
+        );
+        assert_eq!(billing.archive_sha256.as_deref(), Some(sha256.as_str()));
+        let payments = lockfile.get("payments").unwrap();
+        assert_eq!(payments.source, "path+../payments");
+        assert_eq!(payments.dependencies, ["billing"]);
+        assert_eq!(
+            Lockfile::from_file(app.join(LOCK_FILE_NAME)).unwrap(),
+            lockfile
+        );
+
+        // A fresh clone fetches archives from the lock alone.
+        fs::remove_dir_all(app.join(CACHE_DIR)).unwrap();
+        assert_eq!(fetch(&app).unwrap(), ["billing"]);
+        assert!(fetch(&app).unwrap().is_empty());
+
+        // Vendored modules resolve once the original sources are gone.
+        assert_eq!(vendor(&app).unwrap(), ["billing", "payments"]);
+        fs::remove_dir_all(temp.path().join("payments")).unwrap();
+        fs::remove_dir_all(app.join(CACHE_DIR)).unwrap();
+        let set = PackageSet::discover(&app).unwrap().unwrap();
+        let invoice = set.resolve_specifier("pkg:billing/invoice").unwrap();
+        assert!(invoice.ends_with("vendor/billing/invoice.sea"));
+        assert_eq!(
+            set.logical_id(&invoice).as_deref(),
+            Some("pkg:billing/invoice.sea")
+        );
+    }
+
+    #[test]
+    fn test_malicious_lockfile_is_refused() {
+        let temp = TempDir::new().unwrap();
+        let workspace = temp.path().join("app");
+        write(&workspace, MANIFEST_FILE_NAME, "");
+        write(temp.path(), "victim/keep.sea", "Entity \"Keep\"\n");
+        let entries = [
+            (
+                "billing",
+                "../../../victim",
+                ".domainforge/packages/billing",
+            ),
+            ("../victim", "1.0.0", "payments"),
+            ("billing", "1.0.0", ".domainforge/../../victim"),
+            ("billing", "1.0.0", "/victim"),
+        ];
+        for (name, version, path) in entries {
+            let lock = Lockfile {
+                version: LOCK_VERSION,
+                packages: vec![LockedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    source: "archive+billing.tar.gz".to_string(),
+                    path: path.to_string(),
+                    archive_sha256: Some("0".repeat(64)),
+                    checksum: "sha256:0".to_string(),
+                    dependencies: Vec::new(),
+                }],
+            };
+            fs::write(workspace.join(LOCK_FILE_NAME), lock.to_toml()).unwrap();
+            let fetched = fetch(&workspace).unwrap_err();
+            assert!(
+                matches!(
+                    fetched,
+                    PackageError::InvalidLockEntry { .. } | PackageError::InvalidName(_)
+                ),
+                "{name} {version} {path}: {fetched}"
+            );
+            assert!(vendor(&workspace).is_err());
+        }
+        assert!(temp.path().join("victim/keep.sea").is_file());
+    }
+
+    #[test]
+    fn test_lock_rejects_tampered_archive() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("billing.tar.gz"),
+            tar_gz(&[("invoice.sea", "Entity \"Invoice\"\n")]),
+        )
+        .unwrap();
+        write(
+            temp.path(),
+            MANIFEST_FILE_NAME,
+            &format!(
+                "[dependencies]\nbilling = {{ archive = \"billing.tar.gz\", version = \"1.0.0\", sha256 = \"{}\" }}\n",
+                "0".repeat(64)
+            ),
+        );
+        assert!(matches!(
+            lock(temp.path()),
+            Err(PackageError::ChecksumMismatch { .. })
+        ));
+        assert!(!temp.path().join(LOCK_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn test_lock_rejects_conflicting_sources() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "a/core.sea", "");
+        write(temp.path(), "b/core.sea", "");
+        write(
+            temp.path(),
+            "b/domainforge.toml",
+            "[dependencies]\nshared = { path = \"../a\" }\n",
+        );
+        write(temp.path(), "shared/core.sea", "");
+        write(
+            temp.path(),
+            MANIFEST_FILE_NAME,
+            "[dependencies]\nb = { path = \"b\" }\nshared = { path = \"shared\" }\n",
+        );
+        assert!(matches!(
+            lock(temp.path()),
+            Err(PackageError::Conflict { .. })
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::parser::Rule;
+use crate::validation_error::ErrorCode;
 use pest::error::Error as PestError;
 use std::fmt;
 
@@ -193,6 +194,46 @@ impl ParseError {
     pub fn circular_dependency(cycle: Vec<String>) -> Self {
         ParseError::CircularDependency { cycle }
     }
+
+    /// Source `(line, column)` of the error, when the variant carries a real
+    /// one. The `_no_loc` constructors record 0:0, which is not a location.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        let (line, column) = match self {
+            ParseError::SyntaxError { line, column, .. }
+            | ParseError::UndefinedEntity { line, column, .. }
+            | ParseError::UndefinedResource { line, column, .. }
+            | ParseError::UndefinedVariable { line, column, .. }
+            | ParseError::DuplicateDeclaration { line, column, .. }
+            | ParseError::NamespaceNotFound { line, column, .. }
+            | ParseError::ModuleNotFound { line, column, .. }
+            | ParseError::SymbolNotExported { line, column, .. } => (*line, *column),
+            _ => return None,
+        };
+        (line > 0).then_some((line, column))
+    }
+
+    /// The [`ErrorCode`] a validation diagnostic for this error would carry.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            ParseError::SyntaxError { .. } | ParseError::GrammarError(_) => {
+                ErrorCode::E005_SyntaxError
+            }
+            ParseError::UnsupportedExpression { .. } | ParseError::InvalidExpression(_) => {
+                ErrorCode::E006_InvalidExpression
+            }
+            ParseError::UndefinedEntity { .. } => ErrorCode::E001_UndefinedEntity,
+            ParseError::UndefinedResource { .. } => ErrorCode::E002_UndefinedResource,
+            ParseError::UndefinedVariable { .. } => ErrorCode::E008_UndefinedVariable,
+            ParseError::DuplicateDeclaration { .. } => ErrorCode::E007_DuplicateDeclaration,
+            ParseError::TypeError { .. } => ErrorCode::E004_TypeMismatch,
+            ParseError::InvalidQuantity(_) => ErrorCode::E009_InvalidQuantity,
+            ParseError::Validation(_) => ErrorCode::E303_InvalidReference,
+            ParseError::NamespaceNotFound { .. } => ErrorCode::E500_NamespaceNotFound,
+            ParseError::ModuleNotFound { .. } => ErrorCode::E503_ModuleNotFound,
+            ParseError::SymbolNotExported { .. } => ErrorCode::E504_SymbolNotExported,
+            ParseError::CircularDependency { .. } => ErrorCode::E505_CircularDependency,
+        }
+    }
 }
 
 impl fmt::Display for ParseError {
@@ -123,6 +123,10 @@ impl PrettyPrinter {
     fn format_node(&self, node: &AstNode, indent_level: usize) -> String {
         match node {
             AstNode::Export(inner) => self.format_export(&inner.node, indent_level),
+            // Typed entity bodies share the record field syntax.
+            AstNode::Entity { body: Some(_), .. } => {
+                crate::formatter::printer::Formatter::node_to_string(node, indent_level)
+            }
             AstNode::Entity {
                 name,
                 version,
@@ -466,6 +470,16 @@ impl PrettyPrinter {
                 .join(", ");
             lines.push(format!("{}@changes [{}]", self.indent(1), rendered));
         }
+        // Other annotations (e.g. `@cqrs { "kind": "command" }`), sorted for
+        // deterministic output; compact JSON is valid annotation syntax.
+        let mut others: Vec<(&String, &JsonValue)> = annotations
+            .iter()
+            .filter(|(key, _)| key.as_str() != "replaces" && key.as_str() != "changes")
+            .collect();
+        others.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in others {
+            lines.push(format!("{}@{} {}", self.indent(1), key, value));
+        }
 
         // Add from/to/quantity
         let mut suffix = format!("from {} to {}", self.quote(from), self.quote(to));
@@ -209,14 +209,18 @@ impl Policy {
         } else if is_satisfied_tristate == Some(false) {
             vec![Violation::new(
                 &self.name,
-                format!("Policy '{}' was violated", self.name),
+                format!("Policy '{}' was violated: {}", self.name, self.to_english()),
                 self.modality.to_severity(),
             )]
         } else {
             // Unknown (NULL) evaluation: severity follows the policy modality.
             vec![Violation::new(
                 &self.name,
-                format!("Policy '{}' evaluation is UNKNOWN (NULL)", self.name),
+                format!(
+                    "Policy '{}' evaluation is UNKNOWN (NULL): {}",
+                    self.name,
+                    self.to_english()
+                ),
                 self.modality.to_severity(),
             )]
         };
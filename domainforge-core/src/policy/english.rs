@@ -0,0 +1,690 @@
+//! SBVR-style structured English for policy expressions.
+//!
+//! [`Expression::to_english`] renders the proposition and
+//! [`Policy::to_english`] prefixes the modality, so
+//! `forall f in flows: (f.resource != "Money" or f.quantity <= 1000 "USD")`
+//! under an obligation reads "It is obligatory that each flow of Money has a
+//! quantity of at most 1000 USD."
+//!
+//! - Quantifiers introduce a noun from their collection ("each flow", "at
+//!   least one entity", "exactly one resource"). Comparisons on the bound
+//!   variable's members become its predicate ("has a quantity of at most
+//!   …"); a guard (`f.resource != X or …` under `forall`, `f.resource = X and
+//!   …` under `exists`) becomes a restriction ("each flow of Money").
+//! - Aggregations read "the number of flows", "the total quantity in USD of
+//!   all flows of Money over the last 30 days"; `group_by` reads "for each
+//!   group of flows that share the same …".
+//! - `not` is pushed through comparisons, connectives and quantifiers where
+//!   English has a direct form, else "it is not the case that …".
+//! - A policy's expression states what must hold under every modality (it is
+//!   what evaluation checks), so a prohibition is rendered over its negation:
+//!   `Prohibition: total <= 10000` reads "It is prohibited that the total is
+//!   more than 10000."
+
+use super::core::{Policy, PolicyModality};
+use super::expression::{AggregateFunction, BinaryOp, Expression, Quantifier, UnaryOp};
+use serde_json::Value;
+
+impl Expression {
+    /// Renders the expression as a structured-English proposition, without
+    /// modality.
+    #[must_use]
+    pub fn to_english(&self) -> String {
+        Scope::default().proposition(self)
+    }
+}
+
+impl Policy {
+    /// Renders the policy as a structured-English rule sentence: "It is
+    /// obligatory / prohibited / permitted that …".
+    #[must_use]
+    pub fn to_english(&self) -> String {
+        let scope = Scope::default();
+        let (modality, proposition) = match self.modality {
+            PolicyModality::Obligation => ("obligatory", scope.proposition(self.expression())),
+            PolicyModality::Prohibition => {
+                ("prohibited", scope.proposition(&negate(self.expression())))
+            }
+            PolicyModality::Permission => ("permitted", scope.proposition(self.expression())),
+        };
+        format!("It is {modality} that {proposition}.")
+    }
+}
+
+/// A variable bound by a quantifier, aggregation or `group_by`.
+#[derive(Clone)]
+struct Binding {
+    variable: String,
+    /// Singular noun phrase, e.g. "flow".
+    noun: String,
+    /// Plural of the collection, e.g. "flows".
+    plural: String,
+    /// Bound by `group_by`: aggregations over it range over the group.
+    group: bool,
+}
+
+#[derive(Clone, Default)]
+struct Scope {
+    bindings: Vec<Binding>,
+}
+
+impl Scope {
+    fn bind(&self, variable: &str, collection: &Expression, group: bool) -> Scope {
+        let (mut noun, plural) = nouns(collection);
+        if self.bindings.iter().any(|b| b.noun == noun) {
+            noun = format!("{noun} {variable}");
+        }
+        let mut scope = self.clone();
+        scope.bindings.push(Binding {
+            variable: variable.to_string(),
+            noun,
+            plural,
+            group,
+        });
+        scope
+    }
+
+    fn lookup(&self, variable: &str) -> Option<&Binding> {
+        self.bindings.iter().rev().find(|b| b.variable == variable)
+    }
+
+    fn proposition(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Binary { op, left, right } => match op {
+                BinaryOp::And | BinaryOp::Or => {
+                    let word = if *op == BinaryOp::And { "and" } else { "or" };
+                    format!(
+                        "{} {word} {}",
+                        self.operand(op, left),
+                        self.operand(op, right)
+                    )
+                }
+                BinaryOp::HasRole => {
+                    format!("{} has the role {}", self.term(left), self.name(right))
+                }
+                op if is_comparison(op) => format!(
+                    "{} {} {}",
+                    self.term(left),
+                    comparison_phrase(op),
+                    self.term(right)
+                ),
+                _ => self.term(expression),
+            },
+            Expression::Unary {
+                op: UnaryOp::Not,
+                operand,
+            } => match negate(operand) {
+                Expression::Unary {
+                    op: UnaryOp::Not, ..
+                } => format!("it is not the case that {}", self.proposition(operand)),
+                pushed => self.proposition(&pushed),
+            },
+            Expression::Quantifier {
+                quantifier,
+                variable,
+                collection,
+                condition,
+            } => self.quantified(quantifier, variable, collection, condition),
+            Expression::GroupBy {
+                variable,
+                collection,
+                filter,
+                key,
+                condition,
+            } => {
+                let inner = self.bind(variable, collection, true);
+                let plural = &inner.bindings[inner.bindings.len() - 1].plural;
+                let restriction = filter
+                    .as_deref()
+                    .map(|filter| inner.restriction_or_clause(variable, filter))
+                    .unwrap_or_default();
+                let key = match key.as_ref() {
+                    Expression::MemberAccess { object, member } if object == variable => {
+                        humanize(member)
+                    }
+                    other => inner.term(other),
+                };
+                format!(
+                    "for each group of {plural}{restriction} that share the same {key}, {}",
+                    inner.proposition(condition)
+                )
+            }
+            Expression::Variable(_) | Expression::MemberAccess { .. } => {
+                format!("{} is true", self.term(expression))
+            }
+            _ => self.term(expression),
+        }
+    }
+
+    /// An operand of `and` / `or`; a nested connective of the other kind is
+    /// bracketed with "either … or" / "both … and".
+    fn operand(&self, outer: &BinaryOp, operand: &Expression) -> String {
+        match operand {
+            Expression::Binary { op, .. }
+                if (*op == BinaryOp::And || *op == BinaryOp::Or) && op != outer =>
+            {
+                let lead = if *op == BinaryOp::And {
+                    "both"
+                } else {
+                    "either"
+                };
+                format!("{lead} {}", self.proposition(operand))
+            }
+            _ => self.proposition(operand),
+        }
+    }
+
+    fn quantified(
+        &self,
+        quantifier: &Quantifier,
+        variable: &str,
+        collection: &Expression,
+        condition: &Expression,
+    ) -> String {
+        let inner = self.bind(variable, collection, false);
+        let noun = inner.bindings[inner.bindings.len() - 1].noun.clone();
+
+        // A guard on the bound variable becomes a restriction of the noun.
+        let mut restriction = String::new();
+        let mut body = condition;
+        if let Expression::Binary { op, left, right } = condition {
+            let guard = match (quantifier, op) {
+                (Quantifier::ForAll, BinaryOp::Or) => inner.restriction(variable, &negate(left)),
+                (Quantifier::Exists | Quantifier::ExistsUnique, BinaryOp::And) => {
+                    inner.restriction(variable, left)
+                }
+                _ => None,
+            };
+            if let Some(guard) = guard {
+                restriction = guard;
+                body = right;
+            }
+        }
+
+        let determiner = match quantifier {
+            Quantifier::ForAll => "each",
+            Quantifier::Exists => "at least one",
+            Quantifier::ExistsUnique => "exactly one",
+        };
+        match inner.predicate(variable, body) {
+            Some(predicate) => format!("{determiner} {noun}{restriction} {predicate}"),
+            None => format!(
+                "{determiner} {noun}{restriction} is such that {}",
+                inner.proposition(body)
+            ),
+        }
+    }
+
+    /// Verb phrase for `expression` about the bound `variable`, when it only
+    /// compares the variable or its members.
+    fn predicate(&self, variable: &str, expression: &Expression) -> Option<String> {
+        match expression {
+            Expression::Binary { op, left, right } => match (op, left.as_ref()) {
+                (BinaryOp::And | BinaryOp::Or, _) => {
+                    let word = if *op == BinaryOp::And { "and" } else { "or" };
+                    Some(format!(
+                        "{} {word} {}",
+                        self.predicate(variable, left)?,
+                        self.predicate(variable, right)?
+                    ))
+                }
+                (BinaryOp::HasRole, Expression::Variable(v)) if v == variable => {
+                    Some(format!("has the role {}", self.name(right)))
+                }
+                (op, Expression::MemberAccess { object, member })
+                    if object == variable && is_comparison(op) =>
+                {
+                    Some(member_phrase(member, op, &self.term(right)))
+                }
+                (op, Expression::Variable(v)) if v == variable && is_comparison(op) => {
+                    Some(format!("{} {}", comparison_phrase(op), self.term(right)))
+                }
+                _ => None,
+            },
+            Expression::Unary {
+                op: UnaryOp::Not,
+                operand,
+            } => match negate(operand) {
+                Expression::Unary {
+                    op: UnaryOp::Not, ..
+                } => None,
+                pushed => self.predicate(variable, &pushed),
+            },
+            _ => None,
+        }
+    }
+
+    /// " of Money", " from Buyer", " whose status is …" for an equality
+    /// guard on the bound variable (or a conjunction of them).
+    fn restriction(&self, variable: &str, expression: &Expression) -> Option<String> {
+        match expression {
+            Expression::Binary {
+                op: BinaryOp::Equal,
+                left,
+                right,
+            } => match left.as_ref() {
+                Expression::MemberAccess { object, member } if object == variable => {
+                    Some(match member.as_str() {
+                        "resource" => format!(" of {}", self.name(right)),
+                        "from" => format!(" from {}", self.name(right)),
+                        "to" => format!(" to {}", self.name(right)),
+                        _ => format!(" whose {} is {}", humanize(member), self.term(right)),
+                    })
+                }
+                _ => None,
+            },
+            Expression::Binary {
+                op: BinaryOp::And,
+                left,
+                right,
+            } => {
+                let first = self.restriction(variable, left)?;
+                let second = self.restriction(variable, right)?;
+                let joint = if first.contains(" whose ") && second.starts_with(" whose ") {
+                    " and"
+                } else {
+                    ""
+                };
+                Some(format!("{first}{joint}{second}"))
+            }
+            _ => None,
+        }
+    }
+
+    fn restriction_or_clause(&self, variable: &str, filter: &Expression) -> String {
+        self.restriction(variable, filter)
+            .unwrap_or_else(|| format!(" for which {}", self.proposition(filter)))
+    }
+
+    fn term(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Literal(value) => literal(value),
+            Expression::QuantityLiteral { value, unit } => format!("{} {unit}", value.normalize()),
+            Expression::TimeLiteral(timestamp) => timestamp.clone(),
+            Expression::IntervalLiteral { start, end } => {
+                format!("the interval from {start} to {end}")
+            }
+            Expression::Variable(name) => match self.lookup(name) {
+                Some(binding) if binding.group => "the group".to_string(),
+                Some(binding) => format!("the {}", binding.noun),
+                None => format!("the {}", humanize(name)),
+            },
+            Expression::MemberAccess { object, member } => match self.lookup(object) {
+                Some(binding) => format!("the {} of the {}", humanize(member), binding.noun),
+                None => format!("the {} of {object}", humanize(member)),
+            },
+            Expression::Binary { op, left, right } => match op {
+                BinaryOp::Plus => format!("{} plus {}", self.term(left), self.term(right)),
+                BinaryOp::Minus => format!("{} minus {}", self.term(left), self.term(right)),
+                BinaryOp::Multiply => format!("{} times {}", self.term(left), self.term(right)),
+                BinaryOp::Divide => {
+                    format!("{} divided by {}", self.term(left), self.term(right))
+                }
+                _ => self.proposition(expression),
+            },
+            Expression::Unary {
+                op: UnaryOp::Negate,
+                operand,
+            } => format!("minus {}", self.term(operand)),
+            Expression::Cast {
+                operand,
+                target_type,
+            } => format!("{} in {target_type}", self.term(operand)),
+            Expression::Aggregation {
+                function,
+                collection,
+                field,
+                filter,
+            } => {
+                let items = match collection.as_ref() {
+                    Expression::Variable(name) => match self.lookup(name) {
+                        Some(binding) if binding.group => {
+                            format!("the {} in the group", binding.plural)
+                        }
+                        _ => format!("all {}", nouns(collection).1),
+                    },
+                    other => self.term(other),
+                };
+                let filter = filter
+                    .as_deref()
+                    .map(|filter| format!(" for which {}", self.proposition(filter)))
+                    .unwrap_or_default();
+                match (function, field) {
+                    (AggregateFunction::Count, _) => {
+                        let items = items.strip_prefix("all ").unwrap_or(&items);
+                        format!("the number of {items}{filter}")
+                    }
+                    (function, Some(field)) => format!(
+                        "the {} {} of {items}{filter}",
+                        aggregate_adjective(function),
+                        humanize(field)
+                    ),
+                    (function, None) => {
+                        format!("the {} of {items}{filter}", aggregate_adjective(function))
+                    }
+                }
+            }
+            Expression::AggregationComprehension {
+                function,
+                variable,
+                collection,
+                window,
+                predicate,
+                projection,
+                target_unit,
+            } => {
+                let inner = self.bind(variable, collection, false);
+                let plural = inner.bindings[inner.bindings.len() - 1].plural.clone();
+                let restriction = match predicate.as_ref() {
+                    Expression::Literal(Value::Bool(true)) => String::new(),
+                    predicate => inner.restriction_or_clause(variable, predicate),
+                };
+                let window = window
+                    .as_ref()
+                    .map(|w| format!(" over the last {} {}", w.duration, w.unit))
+                    .unwrap_or_default();
+                // `f.quantity as "USD"` converts each projected value.
+                let (projection, cast) = match projection.as_ref() {
+                    Expression::Cast {
+                        operand,
+                        target_type,
+                    } => (operand.as_ref(), Some(target_type)),
+                    projection => (projection, None),
+                };
+                let unit = target_unit
+                    .as_ref()
+                    .or(cast)
+                    .map(|unit| format!(" in {unit}"))
+                    .unwrap_or_default();
+                if *function == AggregateFunction::Count {
+                    return format!("the number of {plural}{restriction}{window}");
+                }
+                let adjective = aggregate_adjective(function);
+                match projection {
+                    Expression::MemberAccess { object, member } if object == variable => format!(
+                        "the {adjective} {}{unit} of all {plural}{restriction}{window}",
+                        humanize(member)
+                    ),
+                    projection => format!(
+                        "the {adjective}{unit} of {} across all {plural}{restriction}{window}",
+                        inner.term(projection)
+                    ),
+                }
+            }
+            Expression::RoleReference { role } => format!("the {role} role"),
+            Expression::GroupBy { .. }
+            | Expression::Quantifier { .. }
+            | Expression::Unary {
+                op: UnaryOp::Not, ..
+            } => self.proposition(expression),
+        }
+    }
+
+    /// A concept named by a string literal, unquoted.
+    fn name(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Literal(Value::String(name)) => name.clone(),
+            other => self.term(other),
+        }
+    }
+}
+
+/// Structural negation: comparisons are inverted, De Morgan is applied to
+/// connectives and quantifiers are swapped; anything else is wrapped in
+/// `not`.
+fn negate(expression: &Expression) -> Expression {
+    match expression {
+        Expression::Binary { op, left, right } => {
+            let inverted = match op {
+                BinaryOp::Equal => Some(BinaryOp::NotEqual),
+                BinaryOp::NotEqual => Some(BinaryOp::Equal),
+                BinaryOp::GreaterThan => Some(BinaryOp::LessThanOrEqual),
+                BinaryOp::LessThanOrEqual => Some(BinaryOp::GreaterThan),
+                BinaryOp::LessThan => Some(BinaryOp::GreaterThanOrEqual),
+                BinaryOp::GreaterThanOrEqual => Some(BinaryOp::LessThan),
+                _ => None,
+            };
+            match (op, inverted) {
+                (_, Some(op)) => Expression::binary(op, *left.clone(), *right.clone()),
+                (BinaryOp::And, None) => {
+                    Expression::binary(BinaryOp::Or, negate(left), negate(right))
+                }
+                (BinaryOp::Or, None) => {
+                    Expression::binary(BinaryOp::And, negate(left), negate(right))
+                }
+                _ => Expression::unary(UnaryOp::Not, expression.clone()),
+            }
+        }
+        Expression::Unary {
+            op: UnaryOp::Not,
+            operand,
+        } => *operand.clone(),
+        Expression::Quantifier {
+            quantifier: quantifier @ (Quantifier::ForAll | Quantifier::Exists),
+            variable,
+            collection,
+            condition,
+        } => Expression::Quantifier {
+            quantifier: match quantifier {
+                Quantifier::ForAll => Quantifier::Exists,
+                _ => Quantifier::ForAll,
+            },
+            variable: variable.clone(),
+            collection: collection.clone(),
+            condition: Box::new(negate(condition)),
+        },
+        _ => Expression::unary(UnaryOp::Not, expression.clone()),
+    }
+}
+
+fn is_comparison(op: &BinaryOp) -> bool {
+    !matches!(
+        op,
+        BinaryOp::And
+            | BinaryOp::Or
+            | BinaryOp::Plus
+            | BinaryOp::Minus
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+    )
+}
+
+/// "is at most", "contains", … between a subject and an object.
+fn comparison_phrase(op: &BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Equal => "is",
+        BinaryOp::NotEqual => "is not",
+        BinaryOp::GreaterThan => "is more than",
+        BinaryOp::LessThan => "is less than",
+        BinaryOp::GreaterThanOrEqual => "is at least",
+        BinaryOp::LessThanOrEqual => "is at most",
+        BinaryOp::Contains => "contains",
+        BinaryOp::StartsWith => "starts with",
+        BinaryOp::EndsWith => "ends with",
+        BinaryOp::Matches => "matches",
+        BinaryOp::HasRole => "has the role",
+        BinaryOp::Before => "is before",
+        BinaryOp::After => "is after",
+        BinaryOp::During => "is during",
+        BinaryOp::And | BinaryOp::Or => "and",
+        BinaryOp::Plus | BinaryOp::Minus | BinaryOp::Multiply | BinaryOp::Divide => "is",
+    }
+}
+
+/// "has a quantity of at most 1000 USD" for `v.quantity <= 1000 "USD"`.
+fn member_phrase(member: &str, op: &BinaryOp, value: &str) -> String {
+    let member = humanize(member);
+    let lower = member.to_lowercase();
+    let article = if lower.starts_with(['a', 'e', 'i', 'o', 'u'])
+        && !["uni", "use", "usu", "uti", "eu", "one"]
+            .iter()
+            .any(|prefix| lower.starts_with(prefix))
+    {
+        "an"
+    } else {
+        "a"
+    };
+    let relation = match op {
+        BinaryOp::Equal => "of",
+        BinaryOp::NotEqual => "other than",
+        BinaryOp::GreaterThan => "of more than",
+        BinaryOp::LessThan => "of less than",
+        BinaryOp::GreaterThanOrEqual => "of at least",
+        BinaryOp::LessThanOrEqual => "of at most",
+        BinaryOp::Before => "before",
+        BinaryOp::After => "after",
+        BinaryOp::During => "during",
+        other => {
+            return format!(
+                "has {article} {member} that {} {value}",
+                comparison_phrase(other)
+            )
+        }
+    };
+    format!("has {article} {member} {relation} {value}")
+}
+
+fn aggregate_adjective(function: &AggregateFunction) -> &'static str {
+    match function {
+        AggregateFunction::Count => "number",
+        AggregateFunction::Sum => "total",
+        AggregateFunction::Min => "smallest",
+        AggregateFunction::Max => "largest",
+        AggregateFunction::Avg => "average",
+    }
+}
+
+/// (singular, plural) nouns for a quantified collection.
+fn nouns(collection: &Expression) -> (String, String) {
+    let Expression::Variable(name) = collection else {
+        return ("item".to_string(), "items".to_string());
+    };
+    let singular = match name.as_str() {
+        "flows" => "flow".to_string(),
+        "entities" => "entity".to_string(),
+        "resources" => "resource".to_string(),
+        "instances" => "instance".to_string(),
+        "entity_instances" => "entity instance".to_string(),
+        "relations" => "relation".to_string(),
+        other => humanize(other.strip_suffix('s').unwrap_or(other)),
+    };
+    (singular, humanize(name))
+}
+
+fn humanize(identifier: &str) -> String {
+    identifier.replace('_', " ")
+}
+
+fn literal(value: &Value) -> String {
+    match value {
+        Value::String(text) => format!("\"{text}\""),
+        Value::Number(number) => match number.as_f64() {
+            Some(f) if number.is_f64() && f.fract() == 0.0 && f.abs() < 1e15 => {
+                format!("{}", f as i64)
+            }
+            _ => number.to_string(),
+        },
+        Value::Bool(flag) => flag.to_string(),
+        Value::Null => "nothing".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_expression_from_str;
+
+    fn english(source: &str) -> String {
+        parse_expression_from_str(source)
+            .expect("expression parses")
+            .to_english()
+    }
+
+    #[test]
+    fn test_quantifiers_read_as_subject_and_predicate() {
+        assert_eq!(
+            english(r#"forall f in flows: (f.resource != "Money" or f.quantity <= 1000 "USD")"#),
+            "each flow of Money has a quantity of at most 1000 USD"
+        );
+        assert_eq!(
+            english(r#"exists e in entities: (e.name = "Warehouse" and e has_role "Storage")"#),
+            "at least one entity whose name is \"Warehouse\" has the role Storage"
+        );
+        assert_eq!(
+            english("exists_unique r in resources: (r.unit = \"USD\")"),
+            "exactly one resource has a unit of \"USD\""
+        );
+        assert_eq!(
+            english("forall f in flows: (exists e in entities: (e.name = f.to))"),
+            "each flow is such that at least one entity has a name of the to of the flow"
+        );
+    }
+
+    #[test]
+    fn test_aggregations_windows_and_groups() {
+        assert_eq!(
+            english("count(flows) > 0"),
+            "the number of flows is more than 0"
+        );
+        assert_eq!(
+            english("sum(flows.quantity) <= 500"),
+            "the total quantity of all flows is at most 500"
+        );
+        assert_eq!(
+            english(
+                r#"sum(f in flows over last 30 "days" where f.resource = "Money": f.quantity as "USD") < 10000 "USD""#
+            ),
+            "the total quantity in USD of all flows of Money over the last 30 days is less than 10000 USD"
+        );
+        assert_eq!(
+            english("group_by(f in flows: f.to) { sum(f.quantity) > 10 }"),
+            "for each group of flows that share the same to, \
+             the total quantity of the flows in the group is more than 10"
+        );
+    }
+
+    #[test]
+    fn test_literals_casts_and_negation() {
+        assert_eq!(
+            english(r#"Shipment.arrival before "2026-01-01T00:00:00Z""#),
+            "the arrival of Shipment is before 2026-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            english(r#"Shift.start during interval("09:00", "17:00")"#),
+            "the start of Shift is during the interval from 09:00 to 17:00"
+        );
+        assert_eq!(
+            english(r#"Payment.amount as "EUR" >= 5 "EUR""#),
+            "the amount of Payment in EUR is at least 5 EUR"
+        );
+        assert_eq!(
+            english("not (total <= 10000 or total > 20000)"),
+            "the total is more than 10000 and the total is at most 20000"
+        );
+        assert_eq!(
+            english(r#"not (Supplier.name contains "Acme")"#),
+            "it is not the case that the name of Supplier contains \"Acme\""
+        );
+    }
+
+    #[test]
+    fn test_policy_modality_prefixes_the_sentence() {
+        let expression = parse_expression_from_str("total <= 10000").unwrap();
+        let policy = Policy::new("limit", expression);
+        assert_eq!(
+            policy.to_english(),
+            "It is obligatory that the total is at most 10000."
+        );
+        let prohibition = policy.clone().with_modality(PolicyModality::Prohibition);
+        assert_eq!(
+            prohibition.to_english(),
+            "It is prohibited that the total is more than 10000."
+        );
+        let permission = policy.with_modality(PolicyModality::Permission);
+        assert_eq!(
+            permission.to_english(),
+            "It is permitted that the total is at most 10000."
+        );
+    }
+}
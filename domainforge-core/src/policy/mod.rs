@@ -1,6 +1,7 @@
 pub mod type_inference;
 
 mod core;
+mod english;
 mod expression;
 mod normalize;
 mod quantifier;
@@ -16,6 +16,7 @@
 //! [Alloy]: https://alloytools.org/
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::ids::{sanitize_filename, NameRegistrar};
 use crate::projection::sink::ArtifactSink;
@@ -105,8 +106,10 @@ fn build_model(
     };
 
     let mut s = String::new();
+    let marker = generated_at(created_at);
     s.push_str(&format!(
-        "// Alloy model projected by DomainForge from {model_ref} at {created_at}.\n\
+        "// Alloy model projected by DomainForge from {model_ref}.\n\
+         // {marker}\n\
          // Analyze with the Alloy Analyzer (https://alloytools.org/).\n\
          module {ns_ident}\n\n"
     ));
@@ -24,12 +24,25 @@
 //! - **Association** Requirement → BusinessObject (a policy is associated with
 //!   every object whose name its condition expression references).
 //!
+//! Views: one business-layer view (every business-layer element) plus one view
+//! per requested [`Viewpoint`]. A viewpoint names the element kinds it shows;
+//! the relation kinds it shows are derived from [`ALLOWED_RELATIONS`] (every
+//! legal triple between two of those kinds), and each view draws a connection
+//! for every relation whose triple qualifies and whose endpoints it shows.
+//!
+//! Objects and processes carry a `documentation` line recording the SEA
+//! concept they came from (`SEA entity`, `SEA resource measured in kg`,
+//! `SEA flow of Widget from Warehouse to Factory quantity 10`), and the model
+//! one recording its namespace (`SEA namespace fin`), which the ArchiMate
+//! importer reads back.
+//!
 //! Every id is minted through [`crate::projection::ids::element_id`] under the
 //! `archimate` family and prefixed with an alpha tag so it is a legal XML
 //! `NCName` / `xsd:ID`. All collections are built and emitted in sorted
 //! (`BTreeMap`/`Vec::sort`) order, so output is byte-identical run-to-run.
 
 use crate::graph::Graph;
+use crate::projection::flows::model_namespace;
 use crate::projection::ids::element_id;
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -273,12 +286,93 @@ pub fn relation_allowed(source: ElemKind, rel: RelKind, target: ElemKind) -> boo
         .any(|&(s, r, t)| s == source && r == rel && t == target)
 }
 
+/// A stakeholder viewpoint from the ArchiMate 3.1 viewpoint catalogue, limited
+/// to the element kinds this projection mints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Viewpoint {
+    Organization,
+    BusinessProcessCooperation,
+    InformationStructure,
+    Motivation,
+    RequirementsRealization,
+}
+
+impl Viewpoint {
+    pub const ALL: [Viewpoint; 5] = [
+        Viewpoint::Organization,
+        Viewpoint::BusinessProcessCooperation,
+        Viewpoint::InformationStructure,
+        Viewpoint::Motivation,
+        Viewpoint::RequirementsRealization,
+    ];
+
+    /// The view's `viewpoint` attribute (a `ViewpointTypeType` value).
+    pub fn archimate_name(self) -> &'static str {
+        match self {
+            Viewpoint::Organization => "Organization",
+            Viewpoint::BusinessProcessCooperation => "Business Process Cooperation",
+            Viewpoint::InformationStructure => "Information Structure",
+            Viewpoint::Motivation => "Motivation",
+            Viewpoint::RequirementsRealization => "Requirements Realization",
+        }
+    }
+
+    /// Kebab-case name, as accepted by [`Viewpoint::parse`].
+    pub fn key(self) -> &'static str {
+        match self {
+            Viewpoint::Organization => "organization",
+            Viewpoint::BusinessProcessCooperation => "business-process-cooperation",
+            Viewpoint::InformationStructure => "information-structure",
+            Viewpoint::Motivation => "motivation",
+            Viewpoint::RequirementsRealization => "requirements-realization",
+        }
+    }
+
+    /// Parse a kebab-case viewpoint name.
+    pub fn parse(s: &str) -> Option<Self> {
+        Viewpoint::ALL.into_iter().find(|v| v.key() == s)
+    }
+
+    /// The element kinds the viewpoint shows.
+    pub fn element_kinds(self) -> &'static [ElemKind] {
+        match self {
+            Viewpoint::Organization => &[ElemKind::BusinessRole],
+            Viewpoint::BusinessProcessCooperation => &[
+                ElemKind::BusinessRole,
+                ElemKind::BusinessObject,
+                ElemKind::BusinessProcess,
+                ElemKind::BusinessService,
+            ],
+            Viewpoint::InformationStructure => &[ElemKind::BusinessObject],
+            Viewpoint::Motivation => &[ElemKind::Requirement],
+            Viewpoint::RequirementsRealization => &[
+                ElemKind::BusinessObject,
+                ElemKind::BusinessProcess,
+                ElemKind::BusinessService,
+                ElemKind::Requirement,
+            ],
+        }
+    }
+
+    /// The legal triples between two of the viewpoint's element kinds.
+    pub fn relations(self) -> Vec<(ElemKind, RelKind, ElemKind)> {
+        let kinds = self.element_kinds();
+        ALLOWED_RELATIONS
+            .iter()
+            .copied()
+            .filter(|(s, _, t)| kinds.contains(s) && kinds.contains(t))
+            .collect()
+    }
+}
+
 /// An ArchiMate element (a node in the model).
 #[derive(Debug, Clone)]
 pub struct Element {
     pub id: String,
     pub kind: ElemKind,
     pub name: String,
+    /// The SEA concept the element was projected from.
+    pub documentation: Option<String>,
 }
 
 /// An ArchiMate relationship (a typed, directed edge). Constructed only through
@@ -338,12 +432,26 @@ pub struct ViewNode {
     pub h: i32,
 }
 
+/// A connection inside a view: it draws a [`Relation`] between the nodes of
+/// the relation's endpoints.
+#[derive(Debug, Clone)]
+pub struct ViewConnection {
+    pub id: String,
+    pub relation_ref: String,
+    pub source: String,
+    pub target: String,
+}
+
 /// A single ArchiMate view (`xsi:type="Diagram"`) listing element references.
 #[derive(Debug, Clone)]
 pub struct View {
     pub id: String,
     pub name: String,
+    /// `None` for the business-layer view, which follows no catalogue
+    /// viewpoint.
+    pub viewpoint: Option<Viewpoint>,
     pub nodes: Vec<ViewNode>,
+    pub connections: Vec<ViewConnection>,
 }
 
 /// The complete architecture, ready to render. Every collection is sorted so
@@ -352,6 +460,8 @@ pub struct View {
 pub struct ArchitectureIR {
     pub model_id: String,
     pub model_name: String,
+    /// The SEA namespace of the model; `None` when it spans several.
+    pub namespace: Option<String>,
     pub elements: Vec<Element>,
     pub relations: Vec<Relation>,
     pub views: Vec<View>,
@@ -381,12 +491,79 @@ fn identifier_tokens(expr: &str) -> BTreeSet<String> {
     out
 }
 
+/// A view of `shown`, laid out on a deterministic grid, drawing every relation
+/// of `relations` whose triple is in `triples` and whose endpoints are shown.
+/// `scope` keeps node and connection ids unique across views.
+fn view(
+    view_id: String,
+    name: String,
+    viewpoint: Option<Viewpoint>,
+    scope: &str,
+    shown: &[&Element],
+    relations: &[Relation],
+    triples: &[(ElemKind, RelKind, ElemKind)],
+) -> View {
+    let cols = 5;
+    let (cell_w, cell_h, node_w, node_h) = (185, 100, 160, 80);
+    let nodes: Vec<ViewNode> = shown
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let col = (i % cols) as i32;
+            let row = (i / cols) as i32;
+            ViewNode {
+                id: id("Node", &[scope, &e.id]),
+                element_ref: e.id.clone(),
+                x: 12 + col * cell_w,
+                y: 12 + row * cell_h,
+                w: node_w,
+                h: node_h,
+            }
+        })
+        .collect();
+    let node_of: BTreeMap<&str, &str> = nodes
+        .iter()
+        .map(|n| (n.element_ref.as_str(), n.id.as_str()))
+        .collect();
+    let connections = relations
+        .iter()
+        .filter(|r| triples.contains(&(r.source_kind, r.kind, r.target_kind)))
+        .filter_map(|r| {
+            let source = node_of.get(r.source.as_str())?;
+            let target = node_of.get(r.target.as_str())?;
+            Some(ViewConnection {
+                id: id("Connection", &[scope, &r.id]),
+                relation_ref: r.id.clone(),
+                source: source.to_string(),
+                target: target.to_string(),
+            })
+        })
+        .collect();
+    View {
+        id: view_id,
+        name,
+        viewpoint,
+        nodes,
+        connections,
+    }
+}
+
 impl ArchitectureIR {
     /// Build the architecture IR from `graph`. `model_ref` is a provenance label
     /// used for the model display name and its id. Returns `Err` only if a
     /// relation would violate the ArchiMate matrix — which, by construction of
     /// this builder, it never does; the fallibility is the guarantee.
     pub fn from_graph(graph: &Graph, model_ref: &str) -> Result<Self, String> {
+        Self::from_graph_with_viewpoints(graph, model_ref, &[])
+    }
+
+    /// [`ArchitectureIR::from_graph`], plus one view per entry of `viewpoints`
+    /// after the business-layer view.
+    pub fn from_graph_with_viewpoints(
+        graph: &Graph,
+        model_ref: &str,
+        viewpoints: &[Viewpoint],
+    ) -> Result<Self, String> {
         let model_id = id("Model", &["model", model_ref]);
 
         // --- Elements -------------------------------------------------------
@@ -402,6 +579,7 @@ impl ArchitectureIR {
                 id: eid,
                 kind: ElemKind::BusinessRole,
                 name: r.name().to_string(),
+                documentation: None,
             });
         }
 
@@ -420,6 +598,7 @@ impl ArchitectureIR {
                 id: eid,
                 kind: ElemKind::BusinessObject,
                 name: e.name().to_string(),
+                documentation: Some("SEA entity".to_string()),
             });
         }
 
@@ -442,6 +621,7 @@ impl ArchitectureIR {
                 id: eid,
                 kind: ElemKind::BusinessObject,
                 name: r.name().to_string(),
+                documentation: Some(format!("SEA resource measured in {}", r.unit().symbol())),
             });
         }
 
@@ -459,10 +639,28 @@ impl ArchitectureIR {
         }
         flow_keys.sort();
         flow_keys.dedup();
+        // The quantity the flows of a process share; none when they differ
+        // or are all zero (a flow declared without one).
+        let quantity_of = |(from, to, res): &(String, String, String)| {
+            let quantities: BTreeSet<_> = graph
+                .all_flows()
+                .into_iter()
+                .filter(|f| {
+                    f.from_id().to_string() == *from
+                        && f.to_id().to_string() == *to
+                        && f.resource_id().to_string() == *res
+                })
+                .map(|f| f.quantity())
+                .collect();
+            match quantities.into_iter().collect::<Vec<_>>().as_slice() {
+                [quantity] if !quantity.is_zero() => Some(quantity.normalize()),
+                _ => None,
+            }
+        };
         // (from, to) → process element id, for triggering chains.
         let mut process_by_key: BTreeMap<(String, String, String), String> = BTreeMap::new();
         let mut processes_from_entity: BTreeMap<String, Vec<String>> = BTreeMap::new();
-        for (from, to, res) in &flow_keys {
+        for key @ (from, to, res) in &flow_keys {
             let pid = id("Element", &["process", from, to, res]);
             let from_name = entities
                 .iter()
@@ -478,6 +676,15 @@ impl ArchitectureIR {
                 id: pid.clone(),
                 kind: ElemKind::BusinessProcess,
                 name: format!("{from_name} to {to_name}"),
+                documentation: Some(format!(
+                    "SEA flow of {} from {from_name} to {to_name}{}",
+                    resources
+                        .iter()
+                        .find(|r| r.id().to_string() == *res)
+                        .map(|r| r.name().to_string())
+                        .unwrap_or_default(),
+                    quantity_of(key).map_or_else(String::new, |q| format!(" quantity {q}"))
+                )),
             });
             process_by_key.insert((from.clone(), to.clone(), res.clone()), pid.clone());
             processes_from_entity
@@ -496,6 +703,7 @@ impl ArchitectureIR {
                 id: rid.clone(),
                 kind: ElemKind::Requirement,
                 name: p.name.clone(),
+                documentation: None,
             });
             requirement_of_policy.push((rid, p.expression().to_string()));
         }
@@ -607,41 +815,51 @@ impl ArchitectureIR {
 
         let relations: Vec<Relation> = relations.into_values().collect();
 
-        // --- View: one auto-generated business-layer view -------------------
-        // Lists every business-layer element (roles, objects, processes) via a
-        // node with a deterministic grid position. Motivation requirements are a
-        // different layer and are excluded (per-stakeholder views are v2).
+        // --- Views -----------------------------------------------------------
+        // The auto-generated business-layer view lists every business-layer
+        // element (roles, objects, processes); motivation requirements are a
+        // different layer and are left to the viewpoint views that show them.
         let business: Vec<&Element> = elements
             .iter()
             .filter(|e| e.kind.is_business_layer())
             .collect();
-        let cols = 5;
-        let (cell_w, cell_h, node_w, node_h) = (185, 100, 160, 80);
-        let nodes: Vec<ViewNode> = business
+        let business_triples: Vec<(ElemKind, RelKind, ElemKind)> = ALLOWED_RELATIONS
             .iter()
-            .enumerate()
-            .map(|(i, e)| {
-                let col = (i % cols) as i32;
-                let row = (i / cols) as i32;
-                ViewNode {
-                    id: id("Node", &["node", &e.id]),
-                    element_ref: e.id.clone(),
-                    x: 12 + col * cell_w,
-                    y: 12 + row * cell_h,
-                    w: node_w,
-                    h: node_h,
-                }
-            })
+            .copied()
+            .filter(|(s, _, t)| s.is_business_layer() && t.is_business_layer())
             .collect();
-        let views = vec![View {
-            id: id("View", &["view", "business", model_ref]),
-            name: "Business Layer".to_string(),
-            nodes,
-        }];
+        let mut views = vec![view(
+            id("View", &["view", "business", model_ref]),
+            "Business Layer".to_string(),
+            None,
+            "node",
+            &business,
+            &relations,
+            &business_triples,
+        )];
+        let mut viewpoints = viewpoints.to_vec();
+        viewpoints.sort();
+        viewpoints.dedup();
+        for viewpoint in viewpoints {
+            let shown: Vec<&Element> = elements
+                .iter()
+                .filter(|e| viewpoint.element_kinds().contains(&e.kind))
+                .collect();
+            views.push(view(
+                id("View", &["view", viewpoint.key(), model_ref]),
+                viewpoint.archimate_name().to_string(),
+                Some(viewpoint),
+                viewpoint.key(),
+                &shown,
+                &relations,
+                &viewpoint.relations(),
+            ));
+        }
 
         Ok(ArchitectureIR {
             model_id,
             model_name: model_ref.to_string(),
+            namespace: model_namespace(graph).ok(),
             elements,
             relations,
             views,
@@ -697,6 +915,8 @@ impl ArchitectureIR {
                 ));
             }
         }
+        let relation_by_id: BTreeMap<&str, &Relation> =
+            self.relations.iter().map(|r| (r.id.as_str(), r)).collect();
         for v in &self.views {
             for n in &v.nodes {
                 if !elem_ids.contains(n.element_ref.as_str()) {
@@ -706,6 +926,28 @@ impl ArchitectureIR {
                     ));
                 }
             }
+            let element_of: BTreeMap<&str, &str> = v
+                .nodes
+                .iter()
+                .map(|n| (n.id.as_str(), n.element_ref.as_str()))
+                .collect();
+            for c in &v.connections {
+                let Some(r) = relation_by_id.get(c.relation_ref.as_str()) else {
+                    return Err(format!(
+                        "view {} connection {} references relationship {} which does not exist",
+                        v.id, c.id, c.relation_ref
+                    ));
+                };
+                // The connection must join the nodes of the relation's endpoints.
+                if element_of.get(c.source.as_str()) != Some(&r.source.as_str())
+                    || element_of.get(c.target.as_str()) != Some(&r.target.as_str())
+                {
+                    return Err(format!(
+                        "view {} connection {} does not join the nodes of relationship {}",
+                        v.id, c.id, r.id
+                    ));
+                }
+            }
         }
         Ok(())
     }
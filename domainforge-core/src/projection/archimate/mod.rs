@@ -9,8 +9,10 @@
 //! relations**. Legality is enforced *by construction*: the IR builder consults a
 //! static allowed-relations table ([`ir::ALLOWED_RELATIONS`]) and cannot emit a
 //! triple absent from it (see [`ir::Relation::build`]). One auto-generated
-//! business-layer view lists the business-layer elements; per-stakeholder views
-//! are a **Non-goal v1**.
+//! business-layer view lists the business-layer elements; [`ArchimateOptions`]
+//! adds one stakeholder view per requested [`ir::Viewpoint`], each showing the
+//! viewpoint's element kinds and the relations the allowed-relations table
+//! permits between them.
 //!
 //! One IR module ([`ir`]) + one renderer ([`xml`]) + one [`emit`] + one
 //! [`project_archimate_in_memory`] binding surface, per the shared projection
@@ -24,22 +26,31 @@ pub mod ir;
 pub mod xml;
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
-use ir::ArchitectureIR;
+use ir::{ArchitectureIR, Viewpoint};
 use std::collections::BTreeMap;
 
 /// The single emitted artifact's relative path.
 pub const OUTPUT_FILE: &str = "model.xml";
 
+/// Projection options. `viewpoints` adds one view per entry after the
+/// business-layer view.
+#[derive(Debug, Clone, Default)]
+pub struct ArchimateOptions {
+    pub viewpoints: Vec<Viewpoint>,
+}
+
 /// Emit the ArchiMate model into `sink`; returns the emitted relative paths.
 pub fn emit(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    opts: &ArchimateOptions,
     sink: &mut ArtifactSink,
 ) -> Result<Vec<String>, String> {
     let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
-    let model = ArchitectureIR::from_graph(graph, model_ref)?;
+    let model = ArchitectureIR::from_graph_with_viewpoints(graph, model_ref, &opts.viewpoints)?;
     // Self-check: never write a model with dangling IDREFs or an illegal relation
     // (the XSD resolves neither reliably — see validate_references).
     model.validate_references()?;
@@ -51,18 +62,42 @@ pub fn emit(
     Ok(vec![OUTPUT_FILE.to_string()])
 }
 
+/// Parse kebab-case viewpoint names (see [`Viewpoint::parse`]).
+pub fn parse_viewpoints<S: AsRef<str>>(names: &[S]) -> Result<Vec<Viewpoint>, String> {
+    names
+        .iter()
+        .map(|name| {
+            let name = name.as_ref();
+            Viewpoint::parse(name).ok_or_else(|| {
+                let known: Vec<&str> = Viewpoint::ALL.iter().map(|v| v.key()).collect();
+                format!(
+                    "unknown ArchiMate viewpoint `{name}` (expected one of: {})",
+                    known.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
 /// Binding surface: string in, path→content map out, no filesystem.
 pub fn project_archimate_in_memory(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    viewpoints: Vec<Viewpoint>,
 ) -> Result<BTreeMap<String, String>, String> {
     let mut map = BTreeMap::new();
     let mut sink = ArtifactSink::Memory {
         prefix: String::new(),
         map: &mut map,
     };
-    emit(graph, model_ref, created_at, &mut sink)?;
+    emit(
+        graph,
+        model_ref,
+        created_at,
+        &ArchimateOptions { viewpoints },
+        &mut sink,
+    )?;
     Ok(map)
 }
 
@@ -71,9 +106,9 @@ pub fn project_archimate_in_memory(
 fn with_provenance(body: &str, model_ref: &str, created_at: &str) -> String {
     let safe = |s: &str| s.replace("--", "- -").replace(['\n', '\r'], " ");
     let comment = format!(
-        "<!-- Generated by DomainForge (format archimate) from {}. Created: {}. Do not edit by hand. -->\n",
+        "<!-- Generated by DomainForge (format archimate) from {}. Do not edit by hand. -->\n<!-- {} -->\n",
         safe(model_ref),
-        safe(created_at)
+        generated_at(&safe(created_at))
     );
     match body.split_once('\n') {
         Some((decl, rest)) => format!("{decl}\n{comment}{rest}"),
@@ -109,7 +144,7 @@ Policy within_limit as: Budget.quantity < 500
 
     fn project(source: &str) -> BTreeMap<String, String> {
         let graph = parse_to_graph(source).expect("fixture parses");
-        project_archimate_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()))
+        project_archimate_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()), vec![])
             .expect("projection succeeds")
     }
 
@@ -179,6 +214,45 @@ Policy within_limit as: Budget.quantity < 500
         assert!(!xml.contains("<relationships"));
     }
 
+    // Each viewpoint view shows only its element kinds and connects them with
+    // the relations the allowed-relations table permits between those kinds.
+    #[test]
+    fn viewpoints_add_views_of_their_element_kinds() {
+        let graph = parse_to_graph(SOURCE).expect("fixture parses");
+        let ir = ArchitectureIR::from_graph_with_viewpoints(
+            &graph,
+            "test.sea",
+            &[Viewpoint::Motivation, Viewpoint::RequirementsRealization],
+        )
+        .expect("builds");
+        ir.validate_references().expect("views are consistent");
+        let names: Vec<&str> = ir.views.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Business Layer", "Motivation", "Requirements Realization"]
+        );
+        let kind_of = |id: &str| ir.elements.iter().find(|e| e.id == id).unwrap().kind;
+        assert!(ir.views[1]
+            .nodes
+            .iter()
+            .all(|n| kind_of(&n.element_ref) == ElemKind::Requirement));
+        assert!(ir.views[1].connections.is_empty());
+        // Requirement → object associations are drawn where both ends show.
+        assert_eq!(ir.views[2].connections.len(), 2);
+
+        let xml = &project_archimate_in_memory(
+            &graph,
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+            vec![Viewpoint::Motivation],
+        )
+        .unwrap()["model.xml"];
+        assert!(xml.contains("viewpoint=\"Motivation\""));
+        assert!(parse_viewpoints(&["organization", "org"])
+            .unwrap_err()
+            .contains("unknown ArchiMate viewpoint `org`"));
+    }
+
     #[test]
     fn output_is_deterministic() {
         assert_eq!(project(SOURCE), project(SOURCE));
@@ -10,14 +10,15 @@
 //! Element ordering follows the ArchiMate 3.0 `ModelType` content model
 //! (base in `archimate3_Model.xsd`, extended by `archimate3_View.xsd` which
 //! adds `views`, and `archimate3_Diagram.xsd` which redefines `ViewsType` to
-//! add `diagrams` → `view`): `name` → `elements` → `relationships` →
-//! `views`/`diagrams`/`view`. `archimate3_Diagram.xsd` is the validation entry
-//! point because it sits at the top of the `xs:redefine` chain (View redefines
-//! Model; Diagram redefines View) and therefore carries the full content model.
+//! add `diagrams` → `view`): `name` → `documentation` → `elements` →
+//! `relationships` → `views`/`diagrams`/`view`. `archimate3_Diagram.xsd` is
+//! the validation entry point because it sits at the top of the
+//! `xs:redefine` chain (View redefines Model; Diagram redefines View) and
+//! therefore carries the full content model.
 //! Each concrete element/relationship/view carries its ArchiMate type as an
 //! `xsi:type` attribute.
 
-use super::ir::{ArchitectureIR, Element, Relation, View, ViewNode};
+use super::ir::{ArchitectureIR, Element, Relation, View, ViewConnection, ViewNode};
 
 /// ArchiMate 3.0 Model Exchange File model namespace.
 const ARCHIMATE_NS: &str = "http://www.opengroup.org/xsd/archimate/3.0/";
@@ -42,6 +43,13 @@ pub fn render(ir: &ArchitectureIR) -> String {
 
     // ModelType content order: name first.
     x.leaf_attrs("name", &[("xml:lang", "en")], &ir.model_name);
+    if let Some(namespace) = &ir.namespace {
+        x.leaf_attrs(
+            "documentation",
+            &[("xml:lang", "en")],
+            &format!("SEA namespace {namespace}"),
+        );
+    }
 
     // elements.
     if !ir.elements.is_empty() {
@@ -82,6 +90,9 @@ fn render_element(x: &mut crate::projection::bpmn::xml::Xml, e: &Element) {
         &[("identifier", &e.id), ("xsi:type", e.kind.archimate_type())],
     );
     x.leaf_attrs("name", &[("xml:lang", "en")], &e.name);
+    if let Some(doc) = &e.documentation {
+        x.leaf_attrs("documentation", &[("xml:lang", "en")], doc);
+    }
     x.close("element");
 }
 
@@ -98,14 +109,34 @@ fn render_relation(x: &mut crate::projection::bpmn::xml::Xml, r: &Relation) {
 }
 
 fn render_view(x: &mut crate::projection::bpmn::xml::Xml, v: &View) {
-    x.open("view", &[("identifier", &v.id), ("xsi:type", "Diagram")]);
+    let mut attrs = vec![("identifier", v.id.as_str()), ("xsi:type", "Diagram")];
+    if let Some(viewpoint) = v.viewpoint {
+        attrs.push(("viewpoint", viewpoint.archimate_name()));
+    }
+    x.open("view", &attrs);
     x.leaf_attrs("name", &[("xml:lang", "en")], &v.name);
     for n in &v.nodes {
         render_node(x, n);
     }
+    for c in &v.connections {
+        render_connection(x, c);
+    }
     x.close("view");
 }
 
+fn render_connection(x: &mut crate::projection::bpmn::xml::Xml, c: &ViewConnection) {
+    x.empty(
+        "connection",
+        &[
+            ("identifier", &c.id),
+            ("relationshipRef", &c.relation_ref),
+            ("xsi:type", "Relationship"),
+            ("source", &c.source),
+            ("target", &c.target),
+        ],
+    );
+}
+
 fn render_node(x: &mut crate::projection::bpmn::xml::Xml, n: &ViewNode) {
     let (xs, ys, ws, hs) = (
         n.x.to_string(),
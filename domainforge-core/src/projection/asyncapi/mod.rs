@@ -27,6 +27,7 @@
 //! [AsyncAPI]: https://www.asyncapi.com/
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::ids::{slug, NameRegistrar};
 use crate::projection::sink::ArtifactSink;
@@ -51,8 +52,10 @@ pub fn emit(
     let yaml = serde_yaml::to_string(&spec)
         .map_err(|e| format!("failed to serialize AsyncAPI document: {e}"))?;
     // YAML has no leading-comment affordance in serde_yaml; prepend a header.
+    let marker = generated_at(&created_at);
     let body = format!(
-        "# AsyncAPI {ASYNCAPI_VERSION} document projected by DomainForge from {model_ref} at {created_at}.\n\
+        "# AsyncAPI {ASYNCAPI_VERSION} document projected by DomainForge from {model_ref}.\n\
+         # {marker}\n\
          # Concept mapping: Entity -> producer/consumer, Flow -> channel+event, Resource -> payload schema.\n{yaml}"
     );
     sink.write(OUTPUT_FILE, &body)?;
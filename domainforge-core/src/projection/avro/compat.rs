@@ -0,0 +1,488 @@
+//! Avro schema resolution (Avro 1.11 specification, "Schema Resolution"):
+//! whether data written with one schema can be read with another.
+//!
+//! Rules applied, writer → reader:
+//! - primitives must match, or promote (`int` → `long`/`float`/`double`,
+//!   `long` → `float`/`double`, `float` → `double`, `string` ↔ `bytes`);
+//!   differing logical types are reported as well
+//! - records, enums, and fixed types must have the same unqualified name
+//! - a reader record field missing from the writer needs a `default`; writer
+//!   fields missing from the reader are skipped
+//! - writer enum symbols must all exist in the reader unless the reader
+//!   declares a `default` symbol
+//! - a writer union must have every branch readable; a reader union must
+//!   accept the writer type in at least one branch
+//! - arrays and maps compare their items and values; fixed types their size
+
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Every reason data written with `writer` cannot be read with `reader`.
+pub(crate) fn reader_accepts(reader: &Value, writer: &Value) -> Vec<String> {
+    let mut reader_names = HashMap::new();
+    collect_names(reader, "", &mut reader_names);
+    let mut writer_names = HashMap::new();
+    collect_names(writer, "", &mut writer_names);
+    let mut resolver = Resolver {
+        reader_names,
+        writer_names,
+        visiting: HashSet::new(),
+    };
+    let mut issues = Vec::new();
+    resolver.read(reader, "", writer, "", "$", &mut issues);
+    issues
+}
+
+const PRIMITIVES: [&str; 8] = [
+    "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+];
+
+/// Full name of a named type declared or referenced as `name` inside
+/// `namespace`.
+fn full_name(name: &str, namespace: &str) -> String {
+    if name.contains('.') || namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{namespace}.{name}")
+    }
+}
+
+fn short_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Named type definitions by full name, with the namespace each one encloses.
+fn collect_names<'v>(
+    schema: &'v Value,
+    namespace: &str,
+    names: &mut HashMap<String, (&'v Value, String)>,
+) {
+    match schema {
+        Value::Array(branches) => {
+            for branch in branches {
+                collect_names(branch, namespace, names);
+            }
+        }
+        Value::Object(obj) => {
+            let kind = obj.get("type").and_then(Value::as_str).unwrap_or("");
+            let mut inner = namespace.to_string();
+            if matches!(kind, "record" | "error" | "enum" | "fixed") {
+                if let Some(name) = obj.get("name").and_then(Value::as_str) {
+                    let ns = obj
+                        .get("namespace")
+                        .and_then(Value::as_str)
+                        .unwrap_or(namespace);
+                    let full = full_name(name, ns);
+                    inner = full
+                        .rsplit_once('.')
+                        .map_or(String::new(), |(ns, _)| ns.to_string());
+                    names.insert(full, (schema, inner.clone()));
+                }
+            }
+            match kind {
+                "record" | "error" => {
+                    for field in obj
+                        .get("fields")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                    {
+                        if let Some(ty) = field.get("type") {
+                            collect_names(ty, &inner, names);
+                        }
+                    }
+                }
+                "array" => {
+                    if let Some(items) = obj.get("items") {
+                        collect_names(items, &inner, names);
+                    }
+                }
+                "map" => {
+                    if let Some(values) = obj.get("values") {
+                        collect_names(values, &inner, names);
+                    }
+                }
+                _ => {
+                    if let Some(nested @ (Value::Object(_) | Value::Array(_))) = obj.get("type") {
+                        collect_names(nested, &inner, names);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+struct Resolver<'v> {
+    reader_names: HashMap<String, (&'v Value, String)>,
+    writer_names: HashMap<String, (&'v Value, String)>,
+    /// (reader, writer) named-type pairs under comparison, so recursive
+    /// records terminate.
+    visiting: HashSet<(String, String)>,
+}
+
+/// A schema with references resolved: its kind plus the definition and the
+/// namespace its own references resolve against.
+struct Resolved<'v> {
+    kind: String,
+    schema: &'v Value,
+    namespace: String,
+}
+
+impl<'v> Resolver<'v> {
+    fn resolve(
+        names: &HashMap<String, (&'v Value, String)>,
+        schema: &'v Value,
+        namespace: &str,
+    ) -> Resolved<'v> {
+        match schema {
+            Value::String(name) if !PRIMITIVES.contains(&name.as_str()) => {
+                let full = full_name(name, namespace);
+                let found = names.get(&full).or_else(|| names.get(name.as_str()));
+                match found {
+                    Some((definition, inner)) => Resolved {
+                        kind: definition["type"].as_str().unwrap_or("").to_string(),
+                        schema: definition,
+                        namespace: inner.clone(),
+                    },
+                    None => Resolved {
+                        kind: format!("unknown type {name}"),
+                        schema,
+                        namespace: namespace.to_string(),
+                    },
+                }
+            }
+            Value::String(name) => Resolved {
+                kind: name.clone(),
+                schema,
+                namespace: namespace.to_string(),
+            },
+            Value::Array(_) => Resolved {
+                kind: "union".to_string(),
+                schema,
+                namespace: namespace.to_string(),
+            },
+            Value::Object(obj) => match obj.get("type") {
+                Some(Value::String(kind)) => match kind.as_str() {
+                    "record" | "error" | "enum" | "fixed" => {
+                        let name = obj.get("name").and_then(Value::as_str).unwrap_or("");
+                        let ns = obj
+                            .get("namespace")
+                            .and_then(Value::as_str)
+                            .unwrap_or(namespace);
+                        Resolved {
+                            kind: kind.clone(),
+                            schema,
+                            namespace: full_name(name, ns)
+                                .rsplit_once('.')
+                                .map_or(String::new(), |(ns, _)| ns.to_string()),
+                        }
+                    }
+                    "array" | "map" => Resolved {
+                        kind: kind.clone(),
+                        schema,
+                        namespace: namespace.to_string(),
+                    },
+                    // A primitive with attributes, or `{"type": "Name"}`
+                    // wrapping a reference.
+                    _ if PRIMITIVES.contains(&kind.as_str()) => Resolved {
+                        kind: kind.clone(),
+                        schema,
+                        namespace: namespace.to_string(),
+                    },
+                    _ => Self::resolve(names, &obj["type"], namespace),
+                },
+                Some(nested) => Self::resolve(names, nested, namespace),
+                None => Resolved {
+                    kind: "invalid schema".to_string(),
+                    schema,
+                    namespace: namespace.to_string(),
+                },
+            },
+            _ => Resolved {
+                kind: "invalid schema".to_string(),
+                schema,
+                namespace: namespace.to_string(),
+            },
+        }
+    }
+
+    fn read(
+        &mut self,
+        reader: &'v Value,
+        reader_ns: &str,
+        writer: &'v Value,
+        writer_ns: &str,
+        path: &str,
+        issues: &mut Vec<String>,
+    ) {
+        let r = Self::resolve(&self.reader_names, reader, reader_ns);
+        let w = Self::resolve(&self.writer_names, writer, writer_ns);
+
+        if w.kind == "union" {
+            for branch in w.schema.as_array().into_iter().flatten() {
+                self.read(r.schema, &r.namespace, branch, &w.namespace, path, issues);
+            }
+            return;
+        }
+        if r.kind == "union" {
+            let branches = r.schema.as_array().into_iter().flatten();
+            let readable = branches.into_iter().any(|branch| {
+                let mut scratch = Vec::new();
+                self.read(
+                    branch,
+                    &r.namespace,
+                    w.schema,
+                    &w.namespace,
+                    path,
+                    &mut scratch,
+                );
+                scratch.is_empty()
+            });
+            if !readable {
+                issues.push(format!(
+                    "{path}: writer type {} matches no branch of the reader union",
+                    describe(&w)
+                ));
+            }
+            return;
+        }
+
+        match (r.kind.as_str(), w.kind.as_str()) {
+            ("record" | "error", "record" | "error") => self.read_record(&r, &w, path, issues),
+            ("enum", "enum") => read_enum(&r, &w, path, issues),
+            ("array", "array") => self.read(
+                &r.schema["items"],
+                &r.namespace,
+                &w.schema["items"],
+                &w.namespace,
+                &format!("{path}[]"),
+                issues,
+            ),
+            ("map", "map") => self.read(
+                &r.schema["values"],
+                &r.namespace,
+                &w.schema["values"],
+                &w.namespace,
+                &format!("{path}{{}}"),
+                issues,
+            ),
+            ("fixed", "fixed") => {
+                check_names(&r, &w, path, issues);
+                if r.schema["size"] != w.schema["size"] {
+                    issues.push(format!(
+                        "{path}: fixed size changed from {} to {}",
+                        w.schema["size"], r.schema["size"]
+                    ));
+                }
+            }
+            (reader_kind, writer_kind) if promotes(writer_kind, reader_kind) => {
+                let (rl, wl) = (logical_type(r.schema), logical_type(w.schema));
+                if rl.is_some() && wl.is_some() && rl != wl {
+                    issues.push(format!(
+                        "{path}: logical type changed from {} to {}",
+                        wl.unwrap_or_default(),
+                        rl.unwrap_or_default()
+                    ));
+                }
+            }
+            _ => issues.push(format!(
+                "{path}: writer type {} cannot be read as {}",
+                describe(&w),
+                describe(&r)
+            )),
+        }
+    }
+
+    fn read_record(
+        &mut self,
+        r: &Resolved<'v>,
+        w: &Resolved<'v>,
+        path: &str,
+        issues: &mut Vec<String>,
+    ) {
+        if !check_names(r, w, path, issues) {
+            return;
+        }
+        let key = (
+            r.schema["name"].as_str().unwrap_or("").to_string(),
+            w.schema["name"].as_str().unwrap_or("").to_string(),
+        );
+        if !self.visiting.insert(key.clone()) {
+            return;
+        }
+        let writer_fields: Vec<&Value> = w.schema["fields"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .collect();
+        for field in r.schema["fields"].as_array().into_iter().flatten() {
+            let name = field["name"].as_str().unwrap_or("");
+            let aliases: Vec<&str> = field["aliases"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            let written = writer_fields.iter().find(|f| {
+                let n = f["name"].as_str().unwrap_or("");
+                n == name || aliases.contains(&n)
+            });
+            let field_path = if path == "$" {
+                format!("$.{name}")
+            } else {
+                format!("{path}.{name}")
+            };
+            match written {
+                Some(written) => self.read(
+                    &field["type"],
+                    &r.namespace,
+                    &written["type"],
+                    &w.namespace,
+                    &field_path,
+                    issues,
+                ),
+                None if field.get("default").is_none() => issues.push(format!(
+                    "{field_path}: reader field has no default and is missing from the writer"
+                )),
+                None => {}
+            }
+        }
+        self.visiting.remove(&key);
+    }
+}
+
+fn read_enum(r: &Resolved, w: &Resolved, path: &str, issues: &mut Vec<String>) {
+    if !check_names(r, w, path, issues) || r.schema.get("default").is_some() {
+        return;
+    }
+    let symbols = |s: &Value| -> Vec<String> {
+        s["symbols"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    let reader_symbols = symbols(r.schema);
+    for symbol in symbols(w.schema) {
+        if !reader_symbols.contains(&symbol) {
+            issues.push(format!(
+                "{path}: writer symbol `{symbol}` is not in reader enum {}",
+                r.schema["name"].as_str().unwrap_or("")
+            ));
+        }
+    }
+}
+
+/// Named types match on their unqualified name.
+fn check_names(r: &Resolved, w: &Resolved, path: &str, issues: &mut Vec<String>) -> bool {
+    let name = |s: &Value| short_name(s["name"].as_str().unwrap_or("")).to_string();
+    let (rn, wn) = (name(r.schema), name(w.schema));
+    if rn != wn {
+        issues.push(format!("{path}: {} name changed from {wn} to {rn}", r.kind));
+        return false;
+    }
+    true
+}
+
+fn promotes(writer: &str, reader: &str) -> bool {
+    (writer == reader && PRIMITIVES.contains(&writer))
+        || matches!(
+            (writer, reader),
+            ("int", "long" | "float" | "double")
+                | ("long", "float" | "double")
+                | ("float", "double")
+                | ("string", "bytes")
+                | ("bytes", "string")
+        )
+}
+
+fn logical_type(schema: &Value) -> Option<&str> {
+    schema.get("logicalType").and_then(Value::as_str)
+}
+
+fn describe(resolved: &Resolved) -> String {
+    match resolved.schema.get("name").and_then(Value::as_str) {
+        Some(name) => format!("{} {name}", resolved.kind),
+        None => resolved.kind.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(fields: Value) -> Value {
+        json!({ "type": "record", "name": "Order", "namespace": "shop", "fields": fields })
+    }
+
+    #[test]
+    fn missing_reader_field_needs_default() {
+        let old = record(json!([{ "name": "id", "type": "string" }]));
+        let with_default = record(json!([
+            { "name": "id", "type": "string" },
+            { "name": "note", "type": ["null", "string"], "default": null }
+        ]));
+        let without = record(json!([
+            { "name": "id", "type": "string" },
+            { "name": "note", "type": "string" }
+        ]));
+        assert!(reader_accepts(&with_default, &old).is_empty());
+        assert_eq!(
+            reader_accepts(&without, &old),
+            ["$.note: reader field has no default and is missing from the writer"]
+        );
+        // Old readers skip the extra writer field.
+        assert!(reader_accepts(&old, &without).is_empty());
+    }
+
+    #[test]
+    fn primitives_promote_but_do_not_narrow() {
+        let int = record(json!([{ "name": "n", "type": "int" }]));
+        let long = record(json!([{ "name": "n", "type": "long" }]));
+        assert!(reader_accepts(&long, &int).is_empty());
+        assert_eq!(
+            reader_accepts(&int, &long),
+            ["$.n: writer type long cannot be read as int"]
+        );
+    }
+
+    #[test]
+    fn enum_symbols_and_named_references_resolve() {
+        let status = |symbols: Value| {
+            record(json!([
+                { "name": "status", "type": { "type": "enum", "name": "Status", "symbols": symbols } },
+                { "name": "previous", "type": ["null", "Status"], "default": null }
+            ]))
+        };
+        let narrow = status(json!(["placed"]));
+        let wide = status(json!(["placed", "shipped"]));
+        assert!(reader_accepts(&wide, &narrow).is_empty());
+        let issues = reader_accepts(&narrow, &wide);
+        assert_eq!(issues.len(), 2, "{issues:?}");
+        assert_eq!(
+            issues[0],
+            "$.status: writer symbol `shipped` is not in reader enum Status"
+        );
+    }
+
+    #[test]
+    fn unions_and_record_names() {
+        let optional =
+            record(json!([{ "name": "n", "type": ["null", "string"], "default": null }]));
+        let required = record(json!([{ "name": "n", "type": "string" }]));
+        assert!(reader_accepts(&optional, &required).is_empty());
+        assert_eq!(
+            reader_accepts(&required, &optional),
+            ["$.n: writer type null cannot be read as string"]
+        );
+        let renamed = json!({ "type": "record", "name": "Purchase", "fields": [] });
+        assert_eq!(
+            reader_accepts(&renamed, &required),
+            ["$: record name changed from Order to Purchase"]
+        );
+    }
+}
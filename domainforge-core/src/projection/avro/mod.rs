@@ -0,0 +1,269 @@
+//! Avro projection: one Avro schema (`<namespace>.<Name>.avsc`) per resource
+//! flow and application record, built from the shared
+//! [`schema_registry`](crate::projection::schema_registry) payload schemas.
+//!
+//! Mapping:
+//! - payload schema → `record` with its namespace, name, and doc
+//! - `string` → `string`; `int` → `long`; `bool` → `boolean`
+//! - `uuid` → `string` with logical type `uuid`; `timestamp` → `long` with
+//!   logical type `timestamp-millis`
+//! - `decimal` and quantities → `string` (SEA decimals carry no fixed
+//!   precision/scale, which Avro's `decimal` logical type requires)
+//! - `enum` → named `enum` whose symbols are the members' wire values made
+//!   valid Avro names; defined inline at first use, referenced by full name
+//!   afterwards
+//! - `list<T>` → `array`
+//! - `optional` field → `["null", T]` union defaulting to `null`
+//!
+//! Field constraints have no Avro equivalent and are not emitted; use the
+//! `json-schema` projection where validation matters. The schemas carry no
+//! timestamp, so output is byte-identical run-to-run.
+
+pub mod compat;
+
+use crate::application::{ApplicationContract, TypedValue};
+use crate::graph::Graph;
+use crate::projection::ids::ident;
+use crate::projection::schema_registry::{
+    check_published, payload_schemas, PayloadField, PayloadSchema, PayloadType,
+    RegistryCheckResult, RegistryCompatibility,
+};
+use crate::projection::sink::ArtifactSink;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Emit one `.avsc` per payload schema into `sink`; returns the emitted
+/// relative paths.
+pub fn emit(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    for schema in payload_schemas(graph, contract)? {
+        let file = format!("{}.avsc", schema.full_name());
+        let body = serde_json::to_string_pretty(&avro_schema(&schema))
+            .map_err(|e| format!("failed to serialize Avro schema: {e}"))?;
+        sink.write(&file, &format!("{body}\n"))?;
+        files.push(file);
+    }
+    Ok(files)
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_avro_in_memory(
+    graph: &Graph,
+    contract: &ApplicationContract,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, contract, &mut sink)?;
+    Ok(map)
+}
+
+/// Check projected `.avsc` files against the published ones in `published`
+/// using Avro schema-resolution rules.
+pub fn check_compatibility(
+    published: &Path,
+    files: &BTreeMap<String, String>,
+    mode: RegistryCompatibility,
+) -> Result<RegistryCheckResult, String> {
+    check_published(published, files, mode, compat::reader_accepts)
+}
+
+fn avro_schema(schema: &PayloadSchema) -> Value {
+    let mut defined = HashSet::new();
+    let fields: Vec<Value> = schema
+        .fields
+        .iter()
+        .map(|field| avro_field(field, &schema.namespace, &mut defined))
+        .collect();
+    json!({
+        "type": "record",
+        "name": schema.name,
+        "namespace": schema.namespace,
+        "doc": schema.doc,
+        "fields": fields,
+    })
+}
+
+fn avro_field(field: &PayloadField, namespace: &str, defined: &mut HashSet<String>) -> Value {
+    let ty = avro_type(&field.ty, namespace, defined);
+    let mut out = Map::new();
+    out.insert("name".into(), Value::String(field.name.clone()));
+    if let Some(doc) = &field.doc {
+        out.insert("doc".into(), Value::String(doc.clone()));
+    }
+    if field.optional {
+        out.insert("type".into(), json!(["null", ty]));
+        out.insert("default".into(), Value::Null);
+    } else {
+        out.insert("type".into(), ty);
+        if let Some(default) = &field.default {
+            out.insert("default".into(), avro_default(default));
+        }
+    }
+    Value::Object(out)
+}
+
+fn avro_type(ty: &PayloadType, namespace: &str, defined: &mut HashSet<String>) -> Value {
+    match ty {
+        PayloadType::String | PayloadType::Decimal => json!("string"),
+        PayloadType::Long => json!("long"),
+        PayloadType::Boolean => json!("boolean"),
+        PayloadType::Timestamp => json!({ "type": "long", "logicalType": "timestamp-millis" }),
+        PayloadType::Uuid => json!({ "type": "string", "logicalType": "uuid" }),
+        PayloadType::Enum(e) => {
+            let full_name = e.full_name();
+            if !defined.insert(full_name.clone()) {
+                // Avro names resolve against the enclosing namespace.
+                return if e.namespace == namespace {
+                    json!(e.name)
+                } else {
+                    json!(full_name)
+                };
+            }
+            json!({
+                "type": "enum",
+                "name": e.name,
+                "namespace": e.namespace,
+                "symbols": e.symbols.iter().map(|s| ident(s)).collect::<Vec<_>>(),
+            })
+        }
+        PayloadType::List(element) => {
+            json!({ "type": "array", "items": avro_type(element, namespace, defined) })
+        }
+    }
+}
+
+/// JSON encoding of a default value in its Avro type.
+fn avro_default(value: &TypedValue) -> Value {
+    match value {
+        TypedValue::String(s) => json!(s),
+        TypedValue::Int(i) => json!(i),
+        TypedValue::Decimal(d) => json!(d.normalize().to_string()),
+        TypedValue::Bool(b) => json!(b),
+        TypedValue::Timestamp(t) => json!(t.timestamp_millis()),
+        TypedValue::Uuid(u) => json!(u.to_string()),
+        TypedValue::Quantity { base_value, .. } => json!(base_value.normalize().to_string()),
+        TypedValue::EntityRef { key, .. } => avro_default(key),
+        TypedValue::Enum { wire, .. } => json!(ident(wire)),
+        TypedValue::List(items) => Value::Array(items.iter().map(avro_default).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{resolve_application_contract, resolve_application_graph};
+
+    fn project(source: &str) -> BTreeMap<String, String> {
+        let sources = serde_json::json!({ "main.sea": source }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).expect("graph");
+        let contract = resolve_application_contract("main.sea", &sources)
+            .expect("contract")
+            .contract;
+        project_avro_in_memory(&graph, &contract).unwrap()
+    }
+
+    const SOURCE: &str = r#"@namespace "shop"
+Resource "Pallet" units in shop
+enum Tier { gold = "gold", silver = "silver-plus" }
+entity "Customer" {
+    key customer_id: uuid
+}
+record Signup {
+    customer: ref<Customer>
+    tier: Tier
+    previous: Tier optional
+    tags: list<string> optional
+    at: timestamp
+}
+"#;
+
+    fn schema(files: &BTreeMap<String, String>, file: &str) -> Value {
+        serde_json::from_str(&files[file]).unwrap()
+    }
+
+    #[test]
+    fn records_become_avro_records() {
+        let files = project(SOURCE);
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            ["shop.PalletFlow.avsc", "shop.Signup.avsc"]
+        );
+        let signup = schema(&files, "shop.Signup.avsc");
+        assert_eq!(signup["type"], "record");
+        assert_eq!(signup["namespace"], "shop");
+        let fields = signup["fields"].as_array().unwrap();
+        assert_eq!(
+            fields[0]["type"],
+            json!({ "type": "string", "logicalType": "uuid" })
+        );
+        assert_eq!(fields[0]["doc"], "Key `customer_id` of Customer.");
+        assert_eq!(fields[1]["type"]["type"], "enum");
+        assert_eq!(fields[1]["type"]["symbols"], json!(["gold", "silver_plus"]));
+        // Second use refers to the enum by name inside a nullable union.
+        assert_eq!(fields[2]["type"], json!(["null", "Tier"]));
+        assert_eq!(fields[2]["default"], Value::Null);
+        assert_eq!(
+            fields[3]["type"],
+            json!(["null", { "type": "array", "items": "string" }])
+        );
+        assert_eq!(fields[4]["type"]["logicalType"], "timestamp-millis");
+        assert_eq!(files, project(SOURCE));
+    }
+
+    #[test]
+    fn resource_flows_default_their_unit() {
+        let files = project(SOURCE);
+        let flow = schema(&files, "shop.PalletFlow.avsc");
+        assert_eq!(flow["doc"], "A flow of the Pallet resource.");
+        let unit = &flow["fields"][1];
+        assert_eq!(unit["name"], "unit");
+        assert_eq!(unit["type"], "string");
+        assert!(unit["default"].is_string());
+    }
+
+    #[test]
+    fn projected_schemas_check_against_published_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, content) in project(SOURCE) {
+            std::fs::write(dir.path().join(file), content).unwrap();
+        }
+        // Adding an optional field passes FULL; adding a required one only
+        // FORWARD.
+        let optional = project(&SOURCE.replace(
+            "    at: timestamp\n",
+            "    at: timestamp\n    note: string optional\n",
+        ));
+        assert!(
+            check_compatibility(dir.path(), &optional, RegistryCompatibility::Full)
+                .unwrap()
+                .is_compatible()
+        );
+        let required = project(&SOURCE.replace(
+            "    at: timestamp\n",
+            "    at: timestamp\n    note: string\n",
+        ));
+        let backward =
+            check_compatibility(dir.path(), &required, RegistryCompatibility::Backward).unwrap();
+        assert_eq!(backward.checked.len(), 2);
+        assert_eq!(
+            backward.incompatibilities.len(),
+            1,
+            "{}",
+            backward.to_report()
+        );
+        assert_eq!(backward.incompatibilities[0].subject, "shop.Signup.avsc");
+        assert!(
+            check_compatibility(dir.path(), &required, RegistryCompatibility::Forward)
+                .unwrap()
+                .is_compatible()
+        );
+    }
+}
@@ -6,6 +6,7 @@
 //! random data enters file contents (only the caller-supplied `created_at`).
 
 use super::ir::{AICapabilityIR, ClassDef, EnumDef};
+use crate::projection::drift::generated_at;
 
 /// Escape a raw model string for a double-quoted BAML string literal.
 fn quote(raw: &str) -> String {
@@ -22,10 +23,11 @@ fn header(ir: &AICapabilityIR, what: &str) -> String {
     format!(
         "// Generated by DomainForge (`--format baml`) from {}.\n\
          // {}\n\
-         // Created: {}. Target BAML syntax: {}. Do not edit by hand.\n\n",
+         // {}\n\
+         // Target BAML syntax: {}. Do not edit by hand.\n\n",
         comment_safe(&ir.model_ref),
         comment_safe(what),
-        comment_safe(&ir.created_at),
+        generated_at(&comment_safe(&ir.created_at)),
         super::ir::BAML_TARGET_VERSION,
     )
 }
@@ -0,0 +1,483 @@
+//! Deterministic BPMNDI layout for [`ProcessIR`]: a layered, left-to-right
+//! diagram that BPMN tools open as-is, without auto-layout.
+//!
+//! Layout rules:
+//! - **ranks** — every flow node gets a column from the longest path to it
+//!   from the start event over the sequence flows. Cycles are broken at the
+//!   back edges of a depth-first walk (start event first, then nodes by id),
+//!   and the end event always takes the last column.
+//! - **lanes** — each lane is a horizontal band, in lane order; a task sits in
+//!   the band of the lane whose `flowNodeRef`s name it. Events and gateways
+//!   join the lane of the task they are wired to (a start event or converging
+//!   gateway follows its outgoing task, an end event or diverging gateway its
+//!   incoming one). Nodes in no lane share an unlaned band above the lanes.
+//! - **rows** — nodes sharing a band and a column stack top-to-bottom by id.
+//! - **edges** — orthogonal: out of the source's right side, vertical at the
+//!   column boundary after the source, into the target's left side. Edges
+//!   that run backwards (cycles) detour below the whole diagram.
+//!
+//! Data objects carry no shape (BPMN tools draw data object *references*,
+//! which this projection does not emit). Every coordinate is an integer
+//! derived from sorted ids, so the layout is byte-identical run-to-run.
+
+use super::ir::{NodeKind, ProcessIR};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Width of the lane label strip on the left of every band.
+const LABEL_WIDTH: i32 = 30;
+/// Width of one rank column.
+const COLUMN_WIDTH: i32 = 150;
+/// Height of one row within a band.
+const ROW_HEIGHT: i32 = 120;
+/// Clearance between the diagram and the detour of a backwards edge.
+const DETOUR_GAP: i32 = 20;
+
+/// An axis-aligned rectangle (`dc:Bounds`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Bounds {
+    fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    fn middle(&self) -> i32 {
+        self.y + self.height / 2
+    }
+}
+
+/// A diagram point (`di:waypoint`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A `bpmndi:BPMNShape` for one flow node or lane.
+#[derive(Debug, Clone)]
+pub struct Shape {
+    pub id: String,
+    pub element: String,
+    pub bounds: Bounds,
+    /// `Some(true)` for lanes, `None` for flow nodes.
+    pub is_horizontal: Option<bool>,
+}
+
+/// A `bpmndi:BPMNEdge` for one sequence flow.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub id: String,
+    pub element: String,
+    pub waypoints: Vec<Point>,
+}
+
+/// The complete diagram: one plane over the process, lanes first, then flow
+/// nodes (by id), then edges (by sequence flow id).
+#[derive(Debug, Clone)]
+pub struct DiagramLayout {
+    pub diagram_id: String,
+    pub plane_id: String,
+    pub shapes: Vec<Shape>,
+    pub edges: Vec<Edge>,
+}
+
+/// Default BPMN tool sizes per node kind.
+fn size(kind: NodeKind) -> (i32, i32) {
+    match kind {
+        NodeKind::StartEvent | NodeKind::EndEvent => (36, 36),
+        NodeKind::Task => (100, 80),
+        NodeKind::GatewayDiverging | NodeKind::GatewayConverging => (50, 50),
+    }
+}
+
+fn di_id(element: &str) -> String {
+    format!("{element}_di")
+}
+
+impl DiagramLayout {
+    /// Lay out every flow node, lane, and sequence flow of `ir`.
+    pub fn from_ir(ir: &ProcessIR) -> Self {
+        let ranks = ranks(ir);
+        let columns = ranks.values().copied().max().map_or(1, |r| r + 1);
+        let bands = bands(ir);
+
+        // Rows: nodes sharing (band, rank) stack in id order (`ir.nodes` is
+        // sorted by id).
+        let mut rows: BTreeMap<String, i32> = BTreeMap::new();
+        let mut band_rows: BTreeMap<usize, i32> = BTreeMap::new();
+        let mut cells: BTreeMap<(usize, usize), i32> = BTreeMap::new();
+        for node in &ir.nodes {
+            let cell = (bands[&node.id], ranks[&node.id]);
+            let row = cells.entry(cell).or_default();
+            rows.insert(node.id.clone(), *row);
+            *row += 1;
+            let used = band_rows.entry(cell.0).or_default();
+            *used = (*used).max(*row);
+        }
+
+        // Band 0 is the unlaned band; lane `i` is band `i + 1`. Every lane
+        // keeps at least one row so empty lanes stay visible.
+        let mut band_top = BTreeMap::new();
+        let mut y = 0;
+        for band in 0..=ir.lanes.len() {
+            band_top.insert(band, y);
+            let min_rows = if band == 0 { 0 } else { 1 };
+            y += band_rows.get(&band).copied().unwrap_or(0).max(min_rows) * ROW_HEIGHT;
+        }
+        let height = y;
+        let width = LABEL_WIDTH + columns as i32 * COLUMN_WIDTH;
+
+        let mut shapes = Vec::new();
+        for (i, lane) in ir.lanes.iter().enumerate() {
+            let top = band_top[&(i + 1)];
+            let bottom = band_top.get(&(i + 2)).copied().unwrap_or(height);
+            shapes.push(Shape {
+                id: di_id(&lane.id),
+                element: lane.id.clone(),
+                bounds: Bounds {
+                    x: 0,
+                    y: top,
+                    width,
+                    height: bottom - top,
+                },
+                is_horizontal: Some(true),
+            });
+        }
+        let mut bounds: BTreeMap<&str, Bounds> = BTreeMap::new();
+        for node in &ir.nodes {
+            let (w, h) = size(node.kind);
+            let center_x = LABEL_WIDTH + ranks[&node.id] as i32 * COLUMN_WIDTH + COLUMN_WIDTH / 2;
+            let center_y =
+                band_top[&bands[&node.id]] + rows[&node.id] * ROW_HEIGHT + ROW_HEIGHT / 2;
+            let b = Bounds {
+                x: center_x - w / 2,
+                y: center_y - h / 2,
+                width: w,
+                height: h,
+            };
+            bounds.insert(&node.id, b);
+            shapes.push(Shape {
+                id: di_id(&node.id),
+                element: node.id.clone(),
+                bounds: b,
+                is_horizontal: None,
+            });
+        }
+
+        let edges = ir
+            .sequence_flows
+            .iter()
+            .map(|flow| {
+                let (s, t) = (bounds[flow.source.as_str()], bounds[flow.target.as_str()]);
+                let start = Point {
+                    x: s.right(),
+                    y: s.middle(),
+                };
+                let end = Point {
+                    x: t.x,
+                    y: t.middle(),
+                };
+                let after_source = LABEL_WIDTH + (ranks[&flow.source] as i32 + 1) * COLUMN_WIDTH;
+                let waypoints = if ranks[&flow.source] < ranks[&flow.target] {
+                    if start.y == end.y {
+                        vec![start, end]
+                    } else {
+                        vec![
+                            start,
+                            Point {
+                                x: after_source,
+                                y: start.y,
+                            },
+                            Point {
+                                x: after_source,
+                                y: end.y,
+                            },
+                            end,
+                        ]
+                    }
+                } else {
+                    let before_target = LABEL_WIDTH + ranks[&flow.target] as i32 * COLUMN_WIDTH;
+                    let below = height + DETOUR_GAP;
+                    vec![
+                        start,
+                        Point {
+                            x: after_source,
+                            y: start.y,
+                        },
+                        Point {
+                            x: after_source,
+                            y: below,
+                        },
+                        Point {
+                            x: before_target,
+                            y: below,
+                        },
+                        Point {
+                            x: before_target,
+                            y: end.y,
+                        },
+                        end,
+                    ]
+                };
+                Edge {
+                    id: di_id(&flow.id),
+                    element: flow.id.clone(),
+                    waypoints,
+                }
+            })
+            .collect();
+
+        DiagramLayout {
+            diagram_id: format!("{}_diagram", ir.process_id),
+            plane_id: format!("{}_plane", ir.process_id),
+            shapes,
+            edges,
+        }
+    }
+}
+
+/// Column of every node: longest path from the roots over the forward edges
+/// of a depth-first walk; the end event goes last.
+fn ranks(ir: &ProcessIR) -> BTreeMap<String, usize> {
+    let mut successors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for flow in &ir.sequence_flows {
+        successors
+            .entry(flow.source.as_str())
+            .or_default()
+            .insert(flow.target.as_str());
+    }
+    let start = ir.nodes.iter().find(|n| n.kind == NodeKind::StartEvent);
+    let roots = start
+        .into_iter()
+        .chain(&ir.nodes)
+        .map(|n| n.id.as_str())
+        .collect::<Vec<_>>();
+
+    // Iterative DFS; an edge into a node still on the stack is a back edge.
+    let mut visited: BTreeSet<&str> = BTreeSet::new();
+    let mut on_stack: BTreeSet<&str> = BTreeSet::new();
+    let mut back_edges: BTreeSet<(&str, &str)> = BTreeSet::new();
+    let mut finished: Vec<&str> = Vec::new();
+    for root in roots {
+        if !visited.insert(root) {
+            continue;
+        }
+        on_stack.insert(root);
+        let mut stack = vec![(root, successors.get(root).into_iter().flatten())];
+        while let Some((node, children)) = stack.last_mut() {
+            let node = *node;
+            match children.next() {
+                Some(&child) if on_stack.contains(child) => {
+                    back_edges.insert((node, child));
+                }
+                Some(&child) if visited.insert(child) => {
+                    on_stack.insert(child);
+                    stack.push((child, successors.get(child).into_iter().flatten()));
+                }
+                Some(_) => {}
+                None => {
+                    on_stack.remove(node);
+                    finished.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    // Reverse post-order is a topological order of the forward edges.
+    let mut ranks: BTreeMap<String, usize> = BTreeMap::new();
+    for &node in finished.iter().rev() {
+        let rank = ranks.get(node).copied().unwrap_or(0);
+        ranks.insert(node.to_string(), rank);
+        for &child in successors.get(node).into_iter().flatten() {
+            if back_edges.contains(&(node, child)) {
+                continue;
+            }
+            let entry = ranks.entry(child.to_string()).or_default();
+            *entry = (*entry).max(rank + 1);
+        }
+    }
+    if let Some(end) = ir.nodes.iter().find(|n| n.kind == NodeKind::EndEvent) {
+        let last = ir
+            .nodes
+            .iter()
+            .filter(|n| n.kind != NodeKind::EndEvent)
+            .map(|n| ranks[&n.id] + 1)
+            .max()
+            .unwrap_or(0);
+        ranks.insert(end.id.clone(), last);
+    }
+    ranks
+}
+
+/// Band of every node: `0` when unlaned, else its lane's index plus one.
+fn bands(ir: &ProcessIR) -> BTreeMap<String, usize> {
+    let mut task_band: BTreeMap<&str, usize> = BTreeMap::new();
+    for (i, lane) in ir.lanes.iter().enumerate() {
+        for node in &lane.flow_node_refs {
+            task_band.entry(node.as_str()).or_insert(i + 1);
+        }
+    }
+    let flows: BTreeMap<&str, (&str, &str)> = ir
+        .sequence_flows
+        .iter()
+        .map(|f| (f.id.as_str(), (f.source.as_str(), f.target.as_str())))
+        .collect();
+    ir.nodes
+        .iter()
+        .map(|node| {
+            let band = match node.kind {
+                NodeKind::Task => task_band.get(node.id.as_str()).copied(),
+                NodeKind::StartEvent | NodeKind::GatewayConverging => node
+                    .outgoing
+                    .iter()
+                    .find_map(|f| flows.get(f.as_str()).and_then(|(_, t)| task_band.get(t)))
+                    .copied(),
+                NodeKind::EndEvent | NodeKind::GatewayDiverging => node
+                    .incoming
+                    .iter()
+                    .find_map(|f| flows.get(f.as_str()).and_then(|(s, _)| task_band.get(s)))
+                    .copied(),
+            };
+            (node.id.clone(), band.unwrap_or(0))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_to_graph;
+
+    const SOURCE: &str = r#"
+@namespace "demo"
+Entity "Warehouse" in demo
+Entity "LineA" in demo
+Entity "LineB" in demo
+Entity "Assembly" in demo
+Role "Operator" in demo
+Resource "CameraUnits" units in demo
+Flow "CameraUnits" from "Warehouse" to "LineA" quantity 40
+Flow "CameraUnits" from "Warehouse" to "LineB" quantity 60
+Flow "CameraUnits" from "LineA" to "Assembly" quantity 40
+Flow "CameraUnits" from "LineB" to "Assembly" quantity 60
+"#;
+
+    fn ir(source: &str) -> ProcessIR {
+        ProcessIR::from_graph(&parse_to_graph(source).expect("parses"), "test.sea")
+    }
+
+    fn shape<'a>(layout: &'a DiagramLayout, ir: &ProcessIR, name: &str) -> &'a Shape {
+        let node = ir
+            .nodes
+            .iter()
+            .find(|n| n.name.as_deref() == Some(name))
+            .expect("node exists");
+        layout
+            .shapes
+            .iter()
+            .find(|s| s.element == node.id)
+            .expect("shape exists")
+    }
+
+    #[test]
+    fn every_node_lane_and_flow_is_laid_out_once() {
+        let ir = ir(SOURCE);
+        let layout = DiagramLayout::from_ir(&ir);
+        let shaped: BTreeSet<&str> = layout.shapes.iter().map(|s| s.element.as_str()).collect();
+        let expected: BTreeSet<&str> = ir
+            .nodes
+            .iter()
+            .map(|n| n.id.as_str())
+            .chain(ir.lanes.iter().map(|l| l.id.as_str()))
+            .collect();
+        assert_eq!(shaped, expected);
+        assert_eq!(layout.shapes.len(), expected.len());
+        assert_eq!(layout.edges.len(), ir.sequence_flows.len());
+    }
+
+    #[test]
+    fn tasks_rank_left_to_right_in_flow_order() {
+        let ir = ir(SOURCE);
+        let layout = DiagramLayout::from_ir(&ir);
+        let x = |name| shape(&layout, &ir, name).bounds.x;
+        assert!(x("Warehouse") < x("LineA"));
+        assert_eq!(x("LineA"), x("LineB"));
+        assert!(x("LineB") < x("Assembly"));
+        // Parallel branches stack in one column instead of overlapping.
+        assert_ne!(
+            shape(&layout, &ir, "LineA").bounds.y,
+            shape(&layout, &ir, "LineB").bounds.y
+        );
+    }
+
+    #[test]
+    fn edges_are_orthogonal_and_forward() {
+        let ir = ir(SOURCE);
+        for edge in DiagramLayout::from_ir(&ir).edges {
+            assert!(edge.waypoints.len() >= 2);
+            for pair in edge.waypoints.windows(2) {
+                assert!(
+                    pair[0].x == pair[1].x || pair[0].y == pair[1].y,
+                    "{} has a diagonal segment",
+                    edge.element
+                );
+            }
+            assert!(edge.waypoints[0].x < edge.waypoints.last().unwrap().x);
+        }
+    }
+
+    #[test]
+    fn bound_tasks_sit_inside_their_lane() {
+        let mut graph = parse_to_graph(SOURCE).expect("parses");
+        let role = graph.find_role_by_name("Operator").expect("role exists");
+        let entity = graph.find_entity_by_name("LineB").expect("entity exists");
+        graph
+            .assign_role_to_entity(entity, role)
+            .expect("assignment succeeds");
+        let ir = ProcessIR::from_graph(&graph, "test.sea");
+        let layout = DiagramLayout::from_ir(&ir);
+        let lane = layout
+            .shapes
+            .iter()
+            .find(|s| s.is_horizontal == Some(true))
+            .expect("lane shape");
+        let task = shape(&layout, &ir, "LineB").bounds;
+        assert!(
+            task.y >= lane.bounds.y && task.y + task.height <= lane.bounds.y + lane.bounds.height
+        );
+        let warehouse = shape(&layout, &ir, "Warehouse").bounds;
+        assert!(warehouse.y + warehouse.height <= lane.bounds.y);
+    }
+
+    #[test]
+    fn cycles_route_backwards_below_the_diagram() {
+        let ir = ir(r#"
+@namespace "demo"
+Entity "A" in demo
+Entity "B" in demo
+Resource "R" units in demo
+Flow "R" from "A" to "B" quantity 1
+Flow "R" from "B" to "A" quantity 1
+"#);
+        let layout = DiagramLayout::from_ir(&ir);
+        let bottom = layout
+            .shapes
+            .iter()
+            .map(|s| s.bounds.y + s.bounds.height)
+            .max()
+            .unwrap();
+        let back: Vec<_> = layout
+            .edges
+            .iter()
+            .filter(|e| e.waypoints[0].x > e.waypoints.last().unwrap().x)
+            .collect();
+        assert_eq!(back.len(), 1);
+        assert!(back[0].waypoints.iter().any(|p| p.y > bottom));
+    }
+}
@@ -4,20 +4,22 @@
 //! Declared v1 subset (see `docs/bpmn-projections.md`): a non-executable
 //! process with lanes (from roles), start/end events, tasks (from the entities
 //! that participate in flows), parallel gateways (from flow fan-out/fan-in),
-//! sequence flows (from flow edges), and data objects (from resources). BPMNDI
-//! visual layout is **out of scope v1** — import tools (bpmn.io, Camunda)
-//! auto-layout on open.
+//! sequence flows (from flow edges), and data objects (from resources), plus a
+//! BPMNDI diagram from the deterministic layered [`layout`] so tools that do
+//! not auto-layout (Camunda, Signavio) open a readable diagram.
 //!
-//! One IR module ([`ir`]) + one renderer ([`xml`]) + one [`emit`] + one
-//! [`project_bpmn_in_memory`] binding surface, per the shared projection pattern
-//! (`projection/lean/mod.rs`). Output is byte-deterministic for a fixed
+//! One IR module ([`ir`]) + one layout ([`layout`]) + one renderer ([`xml`]) +
+//! one [`emit`] + one [`project_bpmn_in_memory`] binding surface, per the
+//! shared projection pattern (`projection/lean/mod.rs`). Output is byte-deterministic for a fixed
 //! `created_at`: every collection is sorted and no wall-clock or random data
 //! enters file contents (the timestamp is caller-supplied).
 
 pub mod ir;
+pub mod layout;
 pub mod xml;
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
 use ir::ProcessIR;
 use std::collections::BTreeMap;
@@ -65,9 +67,9 @@ pub fn project_bpmn_in_memory(
 fn with_provenance(body: &str, model_ref: &str, created_at: &str) -> String {
     let safe = |s: &str| s.replace("--", "- -").replace(['\n', '\r'], " ");
     let comment = format!(
-        "<!-- Generated by DomainForge (format bpmn) from {}. Created: {}. Do not edit by hand. -->\n",
+        "<!-- Generated by DomainForge (format bpmn) from {}. Do not edit by hand. -->\n<!-- {} -->\n",
         safe(model_ref),
-        safe(created_at)
+        generated_at(&safe(created_at))
     );
     match body.split_once('\n') {
         Some((decl, rest)) => format!("{decl}\n{comment}{rest}"),
@@ -172,6 +174,29 @@ Flow "R" from "A" to "B" quantity 1
         assert!(!bpmn.contains("<task"));
     }
 
+    #[test]
+    fn diagram_shapes_every_node_and_edges_every_flow() {
+        let bpmn = &project(SOURCE)["model.bpmn"];
+        let graph = parse_to_graph(SOURCE).expect("parses");
+        let ir = ProcessIR::from_graph(&graph, "test.sea");
+        assert!(bpmn.contains("xmlns:bpmndi=\"http://www.omg.org/spec/BPMN/20100524/DI\""));
+        // The diagram follows the process inside `definitions`.
+        assert!(bpmn.find("</process>") < bpmn.find("<bpmndi:BPMNDiagram"));
+        for node in &ir.nodes {
+            assert!(bpmn.contains(&format!(
+                "<bpmndi:BPMNShape id=\"{0}_di\" bpmnElement=\"{0}\">",
+                node.id
+            )));
+        }
+        for flow in &ir.sequence_flows {
+            assert!(bpmn.contains(&format!("bpmnElement=\"{}\"", flow.id)));
+        }
+        assert_eq!(
+            bpmn.matches("isHorizontal=\"true\"").count(),
+            ir.lanes.len()
+        );
+    }
+
     #[test]
     fn output_is_deterministic() {
         assert_eq!(project(SOURCE), project(SOURCE));
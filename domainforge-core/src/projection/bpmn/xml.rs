@@ -9,14 +9,22 @@
 //!
 //! Element ordering follows the BPMN 2.0 `Semantic.xsd` content models exactly:
 //! `laneSet` precedes flow elements inside `tProcess`; `incoming` precedes
-//! `outgoing` inside every `tFlowNode`.
+//! `outgoing` inside every `tFlowNode`. The `bpmndi:BPMNDiagram` follows the
+//! process, as `BPMN20.xsd` requires of `tDefinitions`.
 
 use super::ir::{DataObject, Lane, Node, NodeKind, ProcessIR, SequenceFlow};
+use super::layout::{Bounds, DiagramLayout, Edge, Shape};
 
 /// BPMN model namespace (element namespace; `elementFormDefault="qualified"`).
 const BPMN_NS: &str = "http://www.omg.org/spec/BPMN/20100524/MODEL";
 /// Target namespace stamped on generated definitions.
 const TARGET_NS: &str = "http://domainforge.ai/bpmn";
+/// BPMN diagram-interchange namespace (`BPMNDiagram`, `BPMNShape`, `BPMNEdge`).
+const BPMNDI_NS: &str = "http://www.omg.org/spec/BPMN/20100524/DI";
+/// Diagram-common namespace (`Bounds`).
+const DC_NS: &str = "http://www.omg.org/spec/DD/20100524/DC";
+/// Diagram-interchange base namespace (`waypoint`).
+const DI_NS: &str = "http://www.omg.org/spec/DD/20100524/DI";
 
 /// Minimal, dependency-free XML writer. Generic across XML-family projections.
 pub(crate) struct Xml {
@@ -114,6 +122,9 @@ pub fn render(ir: &ProcessIR) -> String {
         "definitions",
         &[
             ("xmlns", BPMN_NS),
+            ("xmlns:bpmndi", BPMNDI_NS),
+            ("xmlns:dc", DC_NS),
+            ("xmlns:di", DI_NS),
             ("id", &ir.definitions_id),
             ("targetNamespace", TARGET_NS),
             ("exporter", "DomainForge"),
@@ -153,6 +164,9 @@ pub fn render(ir: &ProcessIR) -> String {
     }
 
     x.close("process");
+
+    render_diagram(&mut x, ir, &DiagramLayout::from_ir(ir));
+
     x.close("definitions");
     x.finish()
 }
@@ -215,3 +229,54 @@ fn render_sequence_flow(x: &mut Xml, flow: &SequenceFlow) {
         ],
     );
 }
+
+fn render_diagram(x: &mut Xml, ir: &ProcessIR, layout: &DiagramLayout) {
+    x.open("bpmndi:BPMNDiagram", &[("id", &layout.diagram_id)]);
+    x.open(
+        "bpmndi:BPMNPlane",
+        &[("id", &layout.plane_id), ("bpmnElement", &ir.process_id)],
+    );
+    for shape in &layout.shapes {
+        render_shape(x, shape);
+    }
+    for edge in &layout.edges {
+        render_edge(x, edge);
+    }
+    x.close("bpmndi:BPMNPlane");
+    x.close("bpmndi:BPMNDiagram");
+}
+
+fn render_shape(x: &mut Xml, shape: &Shape) {
+    let mut attrs: Vec<(&str, &str)> = vec![("id", &shape.id), ("bpmnElement", &shape.element)];
+    if let Some(horizontal) = shape.is_horizontal {
+        attrs.push(("isHorizontal", if horizontal { "true" } else { "false" }));
+    }
+    x.open("bpmndi:BPMNShape", &attrs);
+    render_bounds(x, &shape.bounds);
+    x.close("bpmndi:BPMNShape");
+}
+
+fn render_bounds(x: &mut Xml, b: &Bounds) {
+    let (xs, ys, ws, hs) = (
+        b.x.to_string(),
+        b.y.to_string(),
+        b.width.to_string(),
+        b.height.to_string(),
+    );
+    x.empty(
+        "dc:Bounds",
+        &[("x", &xs), ("y", &ys), ("width", &ws), ("height", &hs)],
+    );
+}
+
+fn render_edge(x: &mut Xml, edge: &Edge) {
+    x.open(
+        "bpmndi:BPMNEdge",
+        &[("id", &edge.id), ("bpmnElement", &edge.element)],
+    );
+    for p in &edge.waypoints {
+        let (xs, ys) = (p.x.to_string(), p.y.to_string());
+        x.empty("di:waypoint", &[("x", &xs), ("y", &ys)]);
+    }
+    x.close("bpmndi:BPMNEdge");
+}
@@ -29,6 +29,7 @@
 //! [Cedar]: https://docs.cedarpolicy.com/schema/json-schema.html
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::ids::{ident, NameRegistrar};
 use crate::projection::sink::ArtifactSink;
@@ -174,8 +175,10 @@ fn build_policies(
 
     let mut s = String::new();
     // Provenance + scope note live here (Cedar policy files allow `//` comments).
+    let marker = generated_at(created_at);
     s.push_str(&format!(
-        "// Cedar policies projected by DomainForge from {model_ref} at {created_at}.\n\
+        "// Cedar policies projected by DomainForge from {model_ref}.\n\
+         // {marker}\n\
          // PERMISSIVE BASELINE: one scoped `permit` per Action — the authority grant\n\
          // that the flow exists in the `{ns_ident}` architecture. Each permit is scoped\n\
          // to the flow's source entity type (`principal is <From>`) and resource type\n\
@@ -19,6 +19,7 @@ pub mod ir;
 pub mod xml;
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
 use ir::CaseIR;
 use std::collections::BTreeMap;
@@ -66,9 +67,9 @@ pub fn project_cmmn_in_memory(
 fn with_provenance(body: &str, model_ref: &str, created_at: &str) -> String {
     let safe = |s: &str| s.replace("--", "- -").replace(['\n', '\r'], " ");
     let comment = format!(
-        "<!-- Generated by DomainForge (format cmmn) from {}. Created: {}. Do not edit by hand. -->\n",
+        "<!-- Generated by DomainForge (format cmmn) from {}. Do not edit by hand. -->\n<!-- {} -->\n",
         safe(model_ref),
-        safe(created_at)
+        generated_at(&safe(created_at))
     );
     match body.split_once('\n') {
         Some((decl, rest)) => format!("{decl}\n{comment}{rest}"),
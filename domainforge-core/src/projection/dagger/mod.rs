@@ -16,6 +16,7 @@
 //! [`dagger.json`]: https://docs.dagger.io/reference/dagger.schema.json
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::ids::{slug, NameRegistrar};
 use crate::projection::sink::ArtifactSink;
@@ -105,8 +106,10 @@ fn build_module_src(
     let mut reg = NameRegistrar::new();
 
     let mut s = String::new();
+    let marker = generated_at(created_at);
     s.push_str(&format!(
-        "\"\"\"Dagger module projected by DomainForge from {model_ref} at {created_at}.\n\
+        "\"\"\"Dagger module projected by DomainForge from {model_ref}.\n\
+         {marker}\n\
          \nEach SEA Flow becomes a callable Dagger function that activates that\n\
          step of the `{ns}` architecture. Regenerate the SDK layer with\n\
          `dagger develop --sdk=python`.\n\"\"\"\n\n\
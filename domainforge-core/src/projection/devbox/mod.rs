@@ -17,6 +17,7 @@
 //! [`devbox.json`]: https://www.jetify.com/devbox/docs/configuration/
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::sink::ArtifactSink;
 use serde_json::{json, Map, Value};
@@ -34,8 +35,10 @@ pub fn emit(
 ) -> Result<Vec<String>, String> {
     let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
     let spec = build_spec(graph, model_ref, &created_at)?;
+    let marker = generated_at(&created_at);
     let mut body = format!(
-        "// devbox.json projected by DomainForge from {model_ref} at {created_at}.\n\
+        "// devbox.json projected by DomainForge from {model_ref}.\n\
+         // {marker}\n\
          // Activation projection: a reproducible shell pre-loaded with the domain\n\
          // manifest as env vars (namespace, entities, resources, flows) plus a\n\
          // banner on entry. `packages` is intentionally empty — the SEA model\n\
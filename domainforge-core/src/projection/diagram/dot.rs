@@ -0,0 +1,93 @@
+//! Graphviz DOT renderer. Translates the diagram IR only.
+
+use super::{DiagramIr, EdgeKind, NodeKind};
+
+/// Render the IR as a Graphviz digraph with one cluster per namespace, each
+/// `header` line as a comment.
+pub fn render(ir: &DiagramIr, header: &str) -> String {
+    let mut s: String = header.lines().map(|line| format!("// {line}\n")).collect();
+    s.push_str(&format!(
+        "digraph model {{\n  label=\"{}\";\n  labelloc=t;\n  rankdir=LR;\n  node [shape=box];\n",
+        escape(&ir.title)
+    ));
+    for cluster in &ir.clusters {
+        s.push_str(&format!(
+            "  subgraph cluster_{} {{\n    label=\"{}\";\n",
+            cluster.id,
+            escape(&cluster.namespace)
+        ));
+        for node in &cluster.nodes {
+            let style = match node.kind {
+                NodeKind::Entity => "",
+                NodeKind::Role => ", style=rounded",
+            };
+            s.push_str(&format!(
+                "    {} [label=\"{}\"{style}];\n",
+                node.id,
+                escape(&node.label)
+            ));
+        }
+        s.push_str("  }\n");
+    }
+    for edge in &ir.edges {
+        let style = match edge.kind {
+            EdgeKind::Flow => "",
+            EdgeKind::Assignment => ", style=dashed",
+            EdgeKind::Relation => ", style=bold",
+        };
+        s.push_str(&format!(
+            "  {} -> {} [label=\"{}\"{style}];\n",
+            edge.from,
+            edge.to,
+            escape(&edge.label)
+        ));
+    }
+    s.push_str("}\n");
+    s
+}
+
+/// DOT double-quoted strings escape `"`; escape `\` first so it stays literal.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{graph, FIXED_TS};
+    use super::super::{project_diagram_in_memory, DiagramFormat};
+    use super::escape;
+
+    #[test]
+    fn renders_clusters_and_styled_edges() {
+        let files = project_diagram_in_memory(
+            &graph(),
+            DiagramFormat::Dot,
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+            None,
+        )
+        .unwrap();
+        let text = &files["model.dot"];
+        assert!(text.starts_with("// Diagram projected by DomainForge"));
+        assert!(text.contains("digraph model {\n"));
+        assert!(text.contains(
+            "  subgraph cluster_ns_logistics {\n    label=\"logistics\";\n    logistics_Carrier [label=\"Carrier\"];\n  }\n"
+        ));
+        assert!(text.contains("    procurement_Requester [label=\"Requester\", style=rounded];\n"));
+        assert!(text.contains(
+            "  procurement_Buyer -> procurement_Supplier [label=\"PurchaseOrder: 1 units\"];\n"
+        ));
+        assert!(text.contains(
+            "  procurement_Buyer -> procurement_Requester [label=\"plays\", style=dashed];\n"
+        ));
+        assert!(text.contains(
+            "  procurement_Requester -> procurement_Authorizer [label=\"orders\", style=bold];\n"
+        ));
+        assert!(text.ends_with("}\n"));
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        assert_eq!(escape(r#"a "b" \c"#), r#"a \"b\" \\c"#);
+    }
+}
@@ -0,0 +1,82 @@
+//! Mermaid flowchart renderer. Translates the diagram IR only.
+
+use super::{DiagramIr, EdgeKind, NodeKind};
+
+/// Render the IR as a left-to-right Mermaid flowchart, each `header` line
+/// as a comment.
+pub fn render(ir: &DiagramIr, header: &str) -> String {
+    let mut s: String = header.lines().map(|line| format!("%% {line}\n")).collect();
+    s.push_str(&format!("%% {}\nflowchart LR\n", ir.title));
+    for cluster in &ir.clusters {
+        s.push_str(&format!(
+            "    subgraph {}[\"{}\"]\n",
+            cluster.id,
+            escape(&cluster.namespace)
+        ));
+        for node in &cluster.nodes {
+            let label = escape(&node.label);
+            match node.kind {
+                NodeKind::Entity => s.push_str(&format!("        {}[\"{label}\"]\n", node.id)),
+                NodeKind::Role => s.push_str(&format!("        {}([\"{label}\"])\n", node.id)),
+            }
+        }
+        s.push_str("    end\n");
+    }
+    for edge in &ir.edges {
+        let arrow = match edge.kind {
+            EdgeKind::Flow => "-->",
+            EdgeKind::Assignment => "-.->",
+            EdgeKind::Relation => "==>",
+        };
+        s.push_str(&format!(
+            "    {} {arrow}|\"{}\"| {}\n",
+            edge.from,
+            escape(&edge.label),
+            edge.to
+        ));
+    }
+    s
+}
+
+/// Mermaid quoted labels take HTML entity codes for quotes.
+fn escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{graph, FIXED_TS};
+    use super::super::{project_diagram_in_memory, DiagramFormat};
+
+    fn render(focus: Option<&str>) -> String {
+        let files = project_diagram_in_memory(
+            &graph(),
+            DiagramFormat::Mermaid,
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+            focus,
+        )
+        .unwrap();
+        files.into_values().next().unwrap()
+    }
+
+    #[test]
+    fn renders_clusters_nodes_and_labelled_edges() {
+        let text = render(None);
+        assert!(text.starts_with("%% Diagram projected by DomainForge from test.sea"));
+        assert!(text.contains("flowchart LR\n"));
+        assert!(text.contains("    subgraph ns_logistics[\"logistics\"]\n        logistics_Carrier[\"Carrier\"]\n    end\n"));
+        assert!(text.contains("        procurement_Requester([\"Requester\"])\n"));
+        assert!(
+            text.contains("    procurement_Buyer -->|\"Payment: 100 USD\"| procurement_Supplier\n")
+        );
+        assert!(text.contains("    procurement_Buyer -.->|\"plays\"| procurement_Requester\n"));
+        assert!(text.contains("    procurement_Requester ==>|\"orders\"| procurement_Authorizer\n"));
+    }
+
+    #[test]
+    fn output_is_deterministic() {
+        assert_eq!(render(None), render(None));
+        assert_eq!(render(Some("Supplier")), render(Some("Supplier")));
+    }
+}
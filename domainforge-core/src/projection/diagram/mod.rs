@@ -0,0 +1,440 @@
+//! Diagram projection family: one diagram IR (this module) rendered as a
+//! Mermaid flowchart (`mermaid.rs`), a PlantUML component diagram
+//! (`plantuml.rs`), or a Graphviz DOT digraph (`dot.rs`). `svg.rs` renders
+//! the same IR as inline SVG for the documentation site.
+//!
+//! Mapping:
+//! - namespace → cluster (Mermaid `subgraph`, PlantUML `package`, DOT
+//!   `subgraph cluster_*`); clusters and the nodes inside them are sorted
+//! - `Entity` → box node; `Role` → rounded node
+//! - `Flow` (`<R> from <E1> to <E2> quantity <q>`) → solid edge `E1 → E2`
+//!   labelled `<R>: <q> <unit>`
+//! - role assignment (entity plays role) → dashed edge labelled `plays`
+//! - `Relation` → bold edge between the subject and object roles labelled
+//!   with the predicate (and `via <R>` when bound to a flow)
+//!
+//! Focus mode (`focus = Some(entity)`) keeps only the flows returned by
+//! `Graph::flows_from` / `flows_to` for that entity, the entities on either
+//! end, their roles, and relations between those roles.
+//!
+//! Every identifier goes through [`NameRegistrar`]; edges are sorted by
+//! (kind, from, to, label), so output is byte-identical run-to-run for a
+//! fixed `created_at`.
+
+pub mod dot;
+pub mod mermaid;
+pub mod plantuml;
+pub mod svg;
+
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::primitives::Flow;
+use crate::projection::drift::generated_at;
+use crate::projection::flows::resolve_flow;
+use crate::projection::ids::NameRegistrar;
+use crate::projection::sink::ArtifactSink;
+use indexmap::IndexSet;
+use std::collections::{BTreeMap, HashMap};
+
+/// Which diagram language to render.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagramFormat {
+    Mermaid,
+    PlantUml,
+    Dot,
+}
+
+impl DiagramFormat {
+    /// File extension of the emitted diagram.
+    pub fn extension(self) -> &'static str {
+        match self {
+            DiagramFormat::Mermaid => "mmd",
+            DiagramFormat::PlantUml => "puml",
+            DiagramFormat::Dot => "dot",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeKind {
+    Entity,
+    Role,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeKind {
+    Flow,
+    Assignment,
+    Relation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagramNode {
+    pub id: String,
+    pub label: String,
+    pub kind: NodeKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagramCluster {
+    pub id: String,
+    pub namespace: String,
+    pub nodes: Vec<DiagramNode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagramEdge {
+    pub from: String,
+    pub to: String,
+    pub label: String,
+    pub kind: EdgeKind,
+}
+
+/// Language-neutral diagram: namespace clusters of nodes plus labelled edges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagramIr {
+    pub title: String,
+    pub clusters: Vec<DiagramCluster>,
+    pub edges: Vec<DiagramEdge>,
+}
+
+/// Emit the diagram into `sink`; returns the emitted relative path
+/// (`model.<ext>`, or `focus_<entity>.<ext>` in focus mode).
+pub fn emit(
+    graph: &Graph,
+    format: DiagramFormat,
+    model_ref: &str,
+    created_at: Option<String>,
+    focus: Option<&str>,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let ir = DiagramIr::from_graph(graph, focus)?;
+    let header = format!(
+        "Diagram projected by DomainForge from {model_ref}.\n{}",
+        generated_at(&created_at)
+    );
+    let body = match format {
+        DiagramFormat::Mermaid => mermaid::render(&ir, &header),
+        DiagramFormat::PlantUml => plantuml::render(&ir, &header),
+        DiagramFormat::Dot => dot::render(&ir, &header),
+    };
+    let stem = match focus {
+        Some(entity) => format!("focus_{}", crate::projection::ids::slug(entity)),
+        None => "model".to_string(),
+    };
+    let file = format!("{stem}.{}", format.extension());
+    sink.write(&file, &body)?;
+    Ok(vec![file])
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_diagram_in_memory(
+    graph: &Graph,
+    format: DiagramFormat,
+    model_ref: &str,
+    created_at: Option<String>,
+    focus: Option<&str>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, format, model_ref, created_at, focus, &mut sink)?;
+    Ok(map)
+}
+
+impl DiagramIr {
+    /// Build the IR from a graph, optionally focused on one entity (by name,
+    /// or `<namespace>::<name>` when the name is declared in several
+    /// namespaces).
+    pub fn from_graph(graph: &Graph, focus: Option<&str>) -> Result<Self, String> {
+        let (title, flows): (String, Vec<&Flow>) = match focus {
+            Some(name) => {
+                let id = find_focus(graph, name)?;
+                let mut flows = graph.flows_from(&id);
+                flows.extend(graph.flows_to(&id));
+                (format!("Flows of {name}"), flows)
+            }
+            None => ("Model".to_string(), graph.all_flows()),
+        };
+
+        // Which entities appear: every entity, or the focus and its counterparties.
+        let entities: IndexSet<ConceptId> = match focus {
+            Some(name) => {
+                let mut set = IndexSet::new();
+                set.insert(find_focus(graph, name)?);
+                for f in &flows {
+                    set.insert(f.from_id().clone());
+                    set.insert(f.to_id().clone());
+                }
+                set
+            }
+            None => graph
+                .all_entities()
+                .iter()
+                .map(|e| e.id().clone())
+                .collect(),
+        };
+        let mut assignments: Vec<(ConceptId, ConceptId)> = Vec::new();
+        for entity in &entities {
+            for role in graph.roles_for_entity(entity).into_iter().flatten() {
+                assignments.push((entity.clone(), role.clone()));
+            }
+        }
+        let roles: IndexSet<ConceptId> = match focus {
+            Some(_) => assignments.iter().map(|(_, r)| r.clone()).collect(),
+            None => graph.all_roles().iter().map(|r| r.id().clone()).collect(),
+        };
+
+        // (namespace, kind, name, concept) sorted, then registered in order so
+        // collision suffixes are deterministic.
+        let mut members: Vec<(String, NodeKind, String, ConceptId)> = Vec::new();
+        for id in &entities {
+            let entity = graph
+                .get_entity(id)
+                .ok_or_else(|| format!("flow references unknown entity {id}"))?;
+            members.push((
+                entity.namespace().to_string(),
+                NodeKind::Entity,
+                entity.name().to_string(),
+                id.clone(),
+            ));
+        }
+        for id in &roles {
+            if let Some(role) = graph.get_role(id) {
+                members.push((
+                    role.namespace().to_string(),
+                    NodeKind::Role,
+                    role.name().to_string(),
+                    id.clone(),
+                ));
+            }
+        }
+        members.sort_by(|a, b| (&a.0, a.1, &a.2).cmp(&(&b.0, b.1, &b.2)));
+
+        let mut reg = NameRegistrar::new();
+        let mut node_ids: HashMap<ConceptId, String> = HashMap::new();
+        let mut clusters: Vec<DiagramCluster> = Vec::new();
+        for (namespace, kind, name, concept) in members {
+            let id = reg.register("ident", &format!("{namespace}.{name}"));
+            node_ids.insert(concept, id.clone());
+            if clusters.last().map_or(true, |c| c.namespace != namespace) {
+                clusters.push(DiagramCluster {
+                    id: reg.register("ident", &format!("ns {namespace}")),
+                    namespace: namespace.clone(),
+                    nodes: Vec::new(),
+                });
+            }
+            if let Some(cluster) = clusters.last_mut() {
+                cluster.nodes.push(DiagramNode {
+                    id,
+                    label: name,
+                    kind,
+                });
+            }
+        }
+
+        let mut edges: Vec<DiagramEdge> = Vec::new();
+        for f in flows {
+            let resolved = resolve_flow(graph, f)?; // M4: loud dangling-ref policy
+            edges.push(DiagramEdge {
+                from: node_ids[f.from_id()].clone(),
+                to: node_ids[f.to_id()].clone(),
+                label: format!(
+                    "{}: {} {}",
+                    resolved.resource,
+                    f.quantity().normalize(),
+                    resolved.unit
+                ),
+                kind: EdgeKind::Flow,
+            });
+        }
+        for (entity, role) in &assignments {
+            if let (Some(from), Some(to)) = (node_ids.get(entity), node_ids.get(role)) {
+                edges.push(DiagramEdge {
+                    from: from.clone(),
+                    to: to.clone(),
+                    label: "plays".to_string(),
+                    kind: EdgeKind::Assignment,
+                });
+            }
+        }
+        for relation in graph.all_relations() {
+            let (Some(from), Some(to)) = (
+                node_ids.get(relation.subject_role()),
+                node_ids.get(relation.object_role()),
+            ) else {
+                continue;
+            };
+            let via = relation
+                .via_flow()
+                .and_then(|id| graph.get_resource(id))
+                .map(|r| format!(" via {}", r.name()))
+                .unwrap_or_default();
+            edges.push(DiagramEdge {
+                from: from.clone(),
+                to: to.clone(),
+                label: format!("{}{via}", relation.predicate()),
+                kind: EdgeKind::Relation,
+            });
+        }
+        edges.sort_by(|a, b| {
+            (a.kind, &a.from, &a.to, &a.label).cmp(&(b.kind, &b.from, &b.to, &b.label))
+        });
+        edges.dedup();
+
+        Ok(DiagramIr {
+            title,
+            clusters,
+            edges,
+        })
+    }
+}
+
+fn find_focus(graph: &Graph, focus: &str) -> Result<ConceptId, String> {
+    let (namespace, name) = match focus.rsplit_once("::") {
+        Some((namespace, name)) => (Some(namespace), name),
+        None => (None, focus),
+    };
+    let matches: Vec<&crate::primitives::Entity> = graph
+        .all_entities()
+        .into_iter()
+        .filter(|e| e.name() == name && namespace.map_or(true, |ns| e.namespace() == ns))
+        .collect();
+    match matches.as_slice() {
+        [entity] => Ok(entity.id().clone()),
+        [] => Err(format!("focus entity '{focus}' does not exist")),
+        _ => Err(format!(
+            "focus entity '{focus}' is declared in several namespaces; use <namespace>::{name}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_to_graph;
+
+    pub(super) const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+    pub(super) const SOURCE: &str = r#"
+@namespace "procurement"
+Entity "Buyer" in procurement
+Entity "Supplier" in procurement
+Entity "Carrier" in logistics
+Resource "PurchaseOrder" units in procurement
+Resource "Payment" USD in procurement
+Resource "Parcel" units in logistics
+Role "Requester" in procurement
+Role "Authorizer" in procurement
+Flow "PurchaseOrder" from "Buyer" to "Supplier" quantity 1
+Flow "Payment" from "Buyer" to "Supplier" quantity 100
+Flow "Parcel" from "Supplier" to "Carrier" quantity 2
+Relation "OrderFulfillment"
+  subject: "Requester"
+  predicate: "orders"
+  object: "Authorizer"
+"#;
+
+    pub(super) fn graph() -> Graph {
+        let mut graph = parse_to_graph(SOURCE).expect("fixture parses");
+        let role = graph.find_role_by_name("Requester").expect("role");
+        let buyer = graph.find_entity_by_name("Buyer").expect("entity");
+        graph.assign_role_to_entity(buyer, role).expect("assigns");
+        graph
+    }
+
+    #[test]
+    fn namespaces_become_sorted_clusters() {
+        let ir = DiagramIr::from_graph(&graph(), None).unwrap();
+        let clusters: Vec<(&str, Vec<&str>)> = ir
+            .clusters
+            .iter()
+            .map(|c| {
+                (
+                    c.namespace.as_str(),
+                    c.nodes.iter().map(|n| n.label.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            clusters,
+            vec![
+                ("logistics", vec!["Carrier"]),
+                (
+                    "procurement",
+                    vec!["Buyer", "Supplier", "Authorizer", "Requester"]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn edges_carry_resource_quantity_and_kind() {
+        let ir = DiagramIr::from_graph(&graph(), None).unwrap();
+        let edges: Vec<(EdgeKind, &str)> = ir
+            .edges
+            .iter()
+            .map(|e| (e.kind, e.label.as_str()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                (EdgeKind::Flow, "Payment: 100 USD"),
+                (EdgeKind::Flow, "PurchaseOrder: 1 units"),
+                (EdgeKind::Flow, "Parcel: 2 units"),
+                (EdgeKind::Assignment, "plays"),
+                (EdgeKind::Relation, "orders"),
+            ]
+        );
+    }
+
+    #[test]
+    fn focus_keeps_only_adjacent_flows() {
+        let ir = DiagramIr::from_graph(&graph(), Some("Carrier")).unwrap();
+        let labels: Vec<&str> = ir
+            .clusters
+            .iter()
+            .flat_map(|c| c.nodes.iter().map(|n| n.label.as_str()))
+            .collect();
+        assert_eq!(labels, vec!["Carrier", "Supplier"]);
+        assert_eq!(ir.edges.len(), 1);
+        assert_eq!(ir.edges[0].label, "Parcel: 2 units");
+
+        // The focus brings its roles and the relations between them along.
+        let ir = DiagramIr::from_graph(&graph(), Some("procurement::Buyer")).unwrap();
+        assert!(ir.edges.iter().any(|e| e.kind == EdgeKind::Assignment));
+        assert!(!ir.edges.iter().any(|e| e.kind == EdgeKind::Relation));
+    }
+
+    #[test]
+    fn unknown_focus_is_an_error() {
+        let err = DiagramIr::from_graph(&graph(), Some("Nobody")).unwrap_err();
+        assert!(err.contains("does not exist"), "{err}");
+    }
+
+    #[test]
+    fn file_names_follow_format_and_focus() {
+        let graph = graph();
+        let files = project_diagram_in_memory(
+            &graph,
+            DiagramFormat::PlantUml,
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+            Some("Buyer"),
+        )
+        .unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["focus_buyer.puml"]);
+        let files = project_diagram_in_memory(
+            &graph,
+            DiagramFormat::Dot,
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["model.dot"]);
+    }
+}
@@ -0,0 +1,89 @@
+//! PlantUML component-diagram renderer. Translates the diagram IR only.
+
+use super::{DiagramIr, EdgeKind, NodeKind};
+
+/// Render the IR as a PlantUML component diagram, each `header` line as a
+/// comment.
+pub fn render(ir: &DiagramIr, header: &str) -> String {
+    let mut s = "@startuml\n".to_string();
+    for line in header.lines() {
+        s.push_str(&format!("' {line}\n"));
+    }
+    s.push_str(&format!("title {}\nleft to right direction\n", ir.title));
+    for cluster in &ir.clusters {
+        s.push_str(&format!(
+            "package \"{}\" as {} {{\n",
+            escape(&cluster.namespace),
+            cluster.id
+        ));
+        for node in &cluster.nodes {
+            let keyword = match node.kind {
+                NodeKind::Entity => "component",
+                NodeKind::Role => "actor",
+            };
+            s.push_str(&format!(
+                "  {keyword} \"{}\" as {}\n",
+                escape(&node.label),
+                node.id
+            ));
+        }
+        s.push_str("}\n");
+    }
+    for edge in &ir.edges {
+        let arrow = match edge.kind {
+            EdgeKind::Flow => "-->",
+            EdgeKind::Assignment => "..>",
+            EdgeKind::Relation => "-[bold]->",
+        };
+        s.push_str(&format!(
+            "{} {arrow} {} : {}\n",
+            edge.from, edge.to, edge.label
+        ));
+    }
+    s.push_str("@enduml\n");
+    s
+}
+
+/// PlantUML quoted names have no escape for `"`; fall back to `'`.
+fn escape(text: &str) -> String {
+    text.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{graph, FIXED_TS};
+    use super::super::{project_diagram_in_memory, DiagramFormat};
+
+    fn render(focus: Option<&str>) -> String {
+        let files = project_diagram_in_memory(
+            &graph(),
+            DiagramFormat::PlantUml,
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+            focus,
+        )
+        .unwrap();
+        files.into_values().next().unwrap()
+    }
+
+    #[test]
+    fn renders_packages_components_and_labelled_edges() {
+        let text = render(None);
+        assert!(text.starts_with("@startuml\n' Diagram projected by DomainForge"));
+        assert!(text.ends_with("@enduml\n"));
+        assert!(text.contains(
+            "package \"logistics\" as ns_logistics {\n  component \"Carrier\" as logistics_Carrier\n}\n"
+        ));
+        assert!(text.contains("  actor \"Authorizer\" as procurement_Authorizer\n"));
+        assert!(text.contains("procurement_Supplier --> logistics_Carrier : Parcel: 2 units\n"));
+        assert!(text.contains("procurement_Buyer ..> procurement_Requester : plays\n"));
+        assert!(text.contains("procurement_Requester -[bold]-> procurement_Authorizer : orders\n"));
+    }
+
+    #[test]
+    fn focus_titles_the_diagram() {
+        let text = render(Some("Carrier"));
+        assert!(text.contains("title Flows of Carrier\n"));
+        assert!(!text.contains("procurement_Buyer"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Inline SVG renderer for documentation pages. Translates the diagram IR
+//! only. The layout is a fixed grid — one column per namespace cluster,
+//! nodes stacked in IR order — so output is deterministic and needs neither
+//! a layout engine nor a script to display.
+
+use super::{DiagramIr, EdgeKind, NodeKind};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+const NODE_H: f64 = 36.0;
+const ROW_GAP: f64 = 28.0;
+const COLUMN_GAP: f64 = 140.0;
+const HEADER: f64 = 24.0;
+const PAD: f64 = 16.0;
+/// Perpendicular offset between parallel edges of the same node pair.
+const BEND: f64 = 36.0;
+
+/// Render the IR as a standalone `<svg>` element.
+pub fn render(ir: &DiagramIr) -> String {
+    let mut boxes: HashMap<&str, (f64, f64, f64)> = HashMap::new(); // centre x, centre y, width
+    let mut body = String::new();
+    let mut x = PAD;
+    let mut height: f64 = 0.0;
+    for cluster in &ir.clusters {
+        let widest = cluster
+            .nodes
+            .iter()
+            .map(|n| n.label.chars().count())
+            .chain([cluster.namespace.chars().count()])
+            .max()
+            .unwrap_or(0);
+        let node_w = (widest as f64 * 7.0 + 24.0).max(120.0);
+        let cluster_h =
+            HEADER + PAD + cluster.nodes.len() as f64 * (NODE_H + ROW_GAP) - ROW_GAP + PAD;
+        let _ = writeln!(
+            body,
+            r##"<rect x="{x:.1}" y="{PAD:.1}" width="{:.1}" height="{cluster_h:.1}" rx="6" fill="#f6f8fa" stroke="#d0d7de"/>"##,
+            node_w + 2.0 * PAD
+        );
+        let _ = writeln!(
+            body,
+            r##"<text x="{:.1}" y="{:.1}" fill="#57606a">{}</text>"##,
+            x + PAD,
+            PAD + 17.0,
+            escape(&cluster.namespace)
+        );
+        for (row, node) in cluster.nodes.iter().enumerate() {
+            let top = PAD + HEADER + PAD + row as f64 * (NODE_H + ROW_GAP);
+            let rx = match node.kind {
+                NodeKind::Entity => 4.0,
+                NodeKind::Role => NODE_H / 2.0,
+            };
+            let _ = writeln!(
+                body,
+                r##"<rect x="{:.1}" y="{top:.1}" width="{node_w:.1}" height="{NODE_H:.1}" rx="{rx:.1}" fill="#ffffff" stroke="#0969da"/>"##,
+                x + PAD
+            );
+            let _ = writeln!(
+                body,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+                x + PAD + node_w / 2.0,
+                top + NODE_H / 2.0 + 4.0,
+                escape(&node.label)
+            );
+            boxes.insert(
+                node.id.as_str(),
+                (x + PAD + node_w / 2.0, top + NODE_H / 2.0, node_w),
+            );
+        }
+        x += node_w + 2.0 * PAD + COLUMN_GAP;
+        height = height.max(PAD + cluster_h + PAD);
+    }
+    let width = (x - COLUMN_GAP + PAD).max(2.0 * PAD);
+
+    let mut seen: HashMap<(&str, &str), usize> = HashMap::new();
+    for edge in &ir.edges {
+        let (Some(&from), Some(&to)) = (boxes.get(edge.from.as_str()), boxes.get(edge.to.as_str()))
+        else {
+            continue;
+        };
+        let pair = if edge.from <= edge.to {
+            (edge.from.as_str(), edge.to.as_str())
+        } else {
+            (edge.to.as_str(), edge.from.as_str())
+        };
+        let nth = seen.entry(pair).or_insert(0);
+        let offset = bend_offset(*nth);
+        *nth += 1;
+        let style = match edge.kind {
+            EdgeKind::Flow => r#"stroke-width="1.5""#,
+            EdgeKind::Assignment => r#"stroke-width="1.5" stroke-dasharray="5 4""#,
+            EdgeKind::Relation => r#"stroke-width="3""#,
+        };
+        let (path, label_x, label_y) = if edge.from == edge.to {
+            self_loop(from, offset)
+        } else {
+            curve(from, to, offset)
+        };
+        let _ = writeln!(
+            body,
+            r##"<path d="{path}" fill="none" stroke="#57606a" {style} marker-end="url(#arrow)"/>"##
+        );
+        let _ = writeln!(
+            body,
+            r##"<text x="{label_x:.1}" y="{label_y:.1}" text-anchor="middle" font-size="11" fill="#24292f" stroke="#ffffff" stroke-width="3" paint-order="stroke">{}</text>"##,
+            escape(&edge.label)
+        );
+    }
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.0} {height:.0}" role="img" aria-label="{}" font-family="sans-serif" font-size="12">
+<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="#57606a"/></marker></defs>
+{body}</svg>
+"##,
+        escape(&ir.title)
+    )
+}
+
+/// 0, +1, -1, +2, -2, … bends for the parallel edges of one node pair.
+fn bend_offset(nth: usize) -> f64 {
+    let step = nth.div_ceil(2) as f64;
+    if nth % 2 == 1 {
+        step * BEND
+    } else {
+        -step * BEND
+    }
+}
+
+/// Quadratic edge between two node boxes, clipped to their borders; returns
+/// the path and the label position (the curve's midpoint).
+fn curve(from: (f64, f64, f64), to: (f64, f64, f64), offset: f64) -> (String, f64, f64) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let len = (dx * dx + dy * dy).sqrt().max(1.0);
+    let control = (
+        (from.0 + to.0) / 2.0 - dy / len * offset,
+        (from.1 + to.1) / 2.0 + dx / len * offset,
+    );
+    let start = clip(from, control);
+    let end = clip(to, control);
+    let path = format!(
+        "M {:.1} {:.1} Q {:.1} {:.1} {:.1} {:.1}",
+        start.0, start.1, control.0, control.1, end.0, end.1
+    );
+    let label = (
+        0.25 * start.0 + 0.5 * control.0 + 0.25 * end.0,
+        0.25 * start.1 + 0.5 * control.1 + 0.25 * end.1 - 4.0,
+    );
+    (path, label.0, label.1)
+}
+
+/// Loop over the top edge of a node, for flows from an entity to itself.
+fn self_loop(node: (f64, f64, f64), offset: f64) -> (String, f64, f64) {
+    let (cx, top) = (node.0, node.1 - NODE_H / 2.0);
+    let rise = 40.0 + offset.abs();
+    let path = format!(
+        "M {:.1} {top:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {top:.1}",
+        cx - 12.0,
+        cx - 40.0,
+        top - rise,
+        cx + 40.0,
+        top - rise,
+        cx + 12.0
+    );
+    (path, cx, top - rise * 0.75 - 4.0)
+}
+
+/// Point where the segment from a node's centre towards `toward` leaves its
+/// box.
+fn clip(node: (f64, f64, f64), toward: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (toward.0 - node.0, toward.1 - node.1);
+    if dx == 0.0 && dy == 0.0 {
+        return (node.0, node.1);
+    }
+    let tx = if dx == 0.0 {
+        f64::INFINITY
+    } else {
+        node.2 / 2.0 / dx.abs()
+    };
+    let ty = if dy == 0.0 {
+        f64::INFINITY
+    } else {
+        NODE_H / 2.0 / dy.abs()
+    };
+    let t = tx.min(ty).min(1.0);
+    (node.0 + dx * t, node.1 + dy * t)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::graph;
+    use super::super::DiagramIr;
+    use super::*;
+
+    #[test]
+    fn renders_clusters_nodes_and_edges() {
+        let svg = render(&DiagramIr::from_graph(&graph(), None).unwrap());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(">logistics</text>"));
+        assert!(svg.contains(">Carrier</text>"));
+        assert!(svg.contains(">Payment: 100 USD</text>"));
+        assert!(svg.contains("stroke-dasharray"));
+        assert_eq!(svg.matches("marker-end=").count(), 5);
+        assert_eq!(svg, render(&DiagramIr::from_graph(&graph(), None).unwrap()));
+    }
+
+    #[test]
+    fn parallel_edges_bend_apart() {
+        assert_eq!(bend_offset(0), 0.0);
+        assert_eq!(bend_offset(1), BEND);
+        assert_eq!(bend_offset(2), -BEND);
+        let straight = curve((0.0, 0.0, 100.0), (300.0, 0.0, 100.0), 0.0);
+        assert_eq!(straight.0, "M 50.0 0.0 Q 150.0 0.0 250.0 0.0");
+    }
+}
@@ -0,0 +1,147 @@
+//! HTML renderer for the page model: one self-contained document per page,
+//! linked to the shared `assets/style.css`. The only script is the glossary
+//! filter, inlined on pages with a searchable table, so the site works from
+//! `file://` with no network access.
+
+use super::page::{split_target, Block, Line, Page, Site, Span};
+use crate::projection::drift::generated_at;
+use std::fmt::Write as _;
+
+/// Filters the rows of `table.searchable` by the search box text.
+const SEARCH_SCRIPT: &str = r#"<script>
+document.getElementById('search').addEventListener('input', function (event) {
+  var query = event.target.value.toLowerCase();
+  document.querySelectorAll('table.searchable tbody tr').forEach(function (row) {
+    row.hidden = query !== '' && row.textContent.toLowerCase().indexOf(query) < 0;
+  });
+});
+</script>
+"#;
+
+pub fn render(page: &Page, site: &Site) -> String {
+    let prefix = page.root_prefix();
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="stylesheet" href="{prefix}assets/style.css">
+</head>
+<body>
+<nav><a href="{prefix}index.html">Model</a> <a href="{prefix}glossary.html">Glossary</a> <a href="{prefix}policies.html">Policies</a></nav>
+<main>
+<h1>{title}</h1>
+"#,
+        title = escape(&page.title),
+    );
+    if !page.subtitle.is_empty() {
+        let _ = writeln!(
+            out,
+            r#"<p class="subtitle">{}</p>"#,
+            line(&page.subtitle, &prefix)
+        );
+    }
+    let mut searchable = false;
+    for block in &page.blocks {
+        match block {
+            Block::Heading { text, anchor } => match anchor {
+                Some(anchor) => {
+                    let _ = writeln!(out, r#"<h2 id="{}">{}</h2>"#, escape(anchor), escape(text));
+                }
+                None => {
+                    let _ = writeln!(out, "<h2>{}</h2>", escape(text));
+                }
+            },
+            Block::Paragraph(spans) => {
+                let _ = writeln!(out, "<p>{}</p>", line(spans, &prefix));
+            }
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for item in items {
+                    let _ = writeln!(out, "<li>{}</li>", line(item, &prefix));
+                }
+                out.push_str("</ul>\n");
+            }
+            Block::Table {
+                headers,
+                rows,
+                searchable: filter,
+            } => {
+                if *filter {
+                    searchable = true;
+                    out.push_str(
+                        r#"<input type="search" id="search" placeholder="Filter terms" aria-label="Filter terms">"#,
+                    );
+                    out.push('\n');
+                }
+                out.push_str(if *filter {
+                    "<table class=\"searchable\">\n<thead><tr>"
+                } else {
+                    "<table>\n<thead><tr>"
+                });
+                for header in headers {
+                    let _ = write!(out, "<th>{}</th>", escape(header));
+                }
+                out.push_str("</tr></thead>\n<tbody>\n");
+                for row in rows {
+                    out.push_str("<tr>");
+                    for cell in row {
+                        let _ = write!(out, "<td>{}</td>", line(cell, &prefix));
+                    }
+                    out.push_str("</tr>\n");
+                }
+                out.push_str("</tbody>\n</table>\n");
+            }
+            Block::Diagram { svg, .. } => {
+                let _ = writeln!(out, "<figure class=\"diagram\">\n{svg}</figure>");
+            }
+        }
+    }
+    let _ = write!(
+        out,
+        "</main>\n<footer>Generated by DomainForge from {}.</footer>\n<!-- {} -->\n",
+        escape(site.model_ref),
+        generated_at(&escape(site.created_at).replace("--", "- -"))
+    );
+    if searchable {
+        out.push_str(SEARCH_SCRIPT);
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn line(spans: &Line, prefix: &str) -> String {
+    let mut out = String::new();
+    for span in spans {
+        match span {
+            Span::Text(text) => out.push_str(&escape(text)),
+            Span::Code(text) => {
+                let _ = write!(out, "<code>{}</code>", escape(text));
+            }
+            Span::Strong(text) => {
+                let _ = write!(out, "<strong>{}</strong>", escape(text));
+            }
+            Span::Link { text, target } => {
+                let (path, anchor) = split_target(target);
+                let _ = write!(
+                    out,
+                    r#"<a href="{}">{}</a>"#,
+                    escape(&format!("{prefix}{path}.html{anchor}")),
+                    escape(text)
+                );
+            }
+        }
+    }
+    out
+}
+
+fn escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,80 @@
+//! Markdown renderer for the page model (CommonMark plus GitHub tables).
+//! Diagrams become fenced `mermaid` blocks, which GitHub, GitLab and most
+//! documentation hosts draw natively; links point at the sibling `.md` pages.
+
+use super::page::{split_target, Block, Line, Page, Site, Span};
+use crate::projection::drift::generated_at;
+use std::fmt::Write as _;
+
+pub fn render(page: &Page, site: &Site) -> String {
+    let prefix = page.root_prefix();
+    let mut out = format!(
+        "[Model]({prefix}index.md) · [Glossary]({prefix}glossary.md) · [Policies]({prefix}policies.md)\n\n# {}\n",
+        page.title
+    );
+    if !page.subtitle.is_empty() {
+        let _ = write!(out, "\n_{}_\n", line(&page.subtitle, &prefix));
+    }
+    for block in &page.blocks {
+        out.push('\n');
+        match block {
+            Block::Heading { text, anchor } => match anchor {
+                Some(anchor) => {
+                    let _ = writeln!(out, "<a id=\"{anchor}\"></a>\n\n## {text}");
+                }
+                None => {
+                    let _ = writeln!(out, "## {text}");
+                }
+            },
+            Block::Paragraph(spans) => {
+                let _ = writeln!(out, "{}", line(spans, &prefix));
+            }
+            Block::List(items) => {
+                for item in items {
+                    let _ = writeln!(out, "- {}", line(item, &prefix));
+                }
+            }
+            Block::Table { headers, rows, .. } => {
+                let _ = writeln!(out, "| {} |", headers.join(" | "));
+                let _ = writeln!(out, "|{}", " --- |".repeat(headers.len()));
+                for row in rows {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|cell| line(cell, &prefix).replace('|', "\\|"))
+                        .collect();
+                    let _ = writeln!(out, "| {} |", cells.join(" | "));
+                }
+            }
+            Block::Diagram { mermaid, .. } => {
+                let _ = write!(out, "```mermaid\n{mermaid}```\n");
+            }
+        }
+    }
+    let _ = write!(
+        out,
+        "\n---\n\nGenerated by DomainForge from {}.\n\n{}\n",
+        site.model_ref,
+        generated_at(site.created_at)
+    );
+    out
+}
+
+fn line(spans: &Line, prefix: &str) -> String {
+    let mut out = String::new();
+    for span in spans {
+        match span {
+            Span::Text(text) => out.push_str(&text.replace('\n', " ")),
+            Span::Code(text) => {
+                let _ = write!(out, "`{}`", text.replace('`', "'"));
+            }
+            Span::Strong(text) => {
+                let _ = write!(out, "**{text}**");
+            }
+            Span::Link { text, target } => {
+                let (path, anchor) = split_target(target);
+                let _ = write!(out, "[{text}]({prefix}{path}.md{anchor})");
+            }
+        }
+    }
+    out
+}
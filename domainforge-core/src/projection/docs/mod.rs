@@ -0,0 +1,1095 @@
+//! Documentation site projection (`project --format docs`).
+//!
+//! Generates a static, fully offline site for domain experts who do not read
+//! `.sea`: every page is written as `<path>.html` and `<path>.md` from one
+//! page model ([`page`]), plus `assets/style.css`.
+//!
+//! Pages:
+//! - `index` — the model diagram, every concept grouped by namespace, and
+//!   the application records, operations and enumerations
+//! - `glossary` — every term and semantic-pack alias, filterable in HTML
+//! - `policies` — every policy as a plain-English sentence with its metadata
+//! - `entities/<slug>`, `resources/<slug>`, `roles/<slug>`,
+//!   `records/<slug>`, `operations/<slug>` — one page per concept
+//!
+//! Definitions, examples, counterexamples, aliases and owners come from an
+//! optional semantic pack: a `ConceptDef` matches a concept by id, or by
+//! normalized `canonical_name` and kind. Concept pages list incoming and
+//! outgoing flows (resolved through [`resolve_flow`]) and the policies that
+//! govern the concept — those whose expression mentions it by name, plus
+//! those bound to operations on it. Entity pages embed a focus diagram;
+//! HTML gets inline SVG ([`diagram::svg`]), Markdown a Mermaid block.
+//!
+//! Collections are sorted and slugs go through [`NameRegistrar`], so output
+//! is byte-identical for a fixed model, pack and `created_at`.
+
+pub mod html;
+pub mod markdown;
+pub mod page;
+
+use crate::application::{
+    AccessMode, ActorRef, ApplicationContract, ApplicationSymbolId, FieldContract, FieldType,
+    OperationContract, ScalarType,
+};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::policy::{Expression, Policy, PolicyKind};
+use crate::primitives::Flow;
+use crate::projection::diagram::{mermaid, svg, DiagramIr};
+use crate::projection::flows::resolve_flow;
+use crate::projection::ids::NameRegistrar;
+use crate::projection::sink::ArtifactSink;
+use crate::semantic_pack::resolver::normalize_lookup_key;
+use crate::semantic_pack::schema::{AliasStatus, ConceptDef, ConceptKind, SemanticPack};
+use page::{code, heading, link, strong, table, text, Block, Line, Page, Site};
+use std::collections::{BTreeMap, HashMap};
+
+const STYLE: &str = r#"body { margin: 0; font: 15px/1.5 system-ui, -apple-system, "Segoe UI", sans-serif; color: #1f2328; }
+nav { padding: 10px 24px; background: #f6f8fa; border-bottom: 1px solid #d0d7de; }
+nav a { margin-right: 16px; }
+main { max-width: 960px; margin: 0 auto; padding: 8px 24px 32px; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+h1 { margin-bottom: 4px; }
+.subtitle { margin-top: 0; color: #57606a; }
+code { padding: 1px 4px; background: #eff1f3; border-radius: 4px; font-size: 90%; }
+table { border-collapse: collapse; width: 100%; margin: 8px 0 16px; }
+th, td { padding: 6px 10px; border: 1px solid #d0d7de; text-align: left; vertical-align: top; }
+th { background: #f6f8fa; }
+input[type=search] { width: 100%; padding: 6px 10px; margin: 8px 0; box-sizing: border-box; }
+figure.diagram { margin: 16px 0; overflow-x: auto; }
+footer { max-width: 960px; margin: 0 auto; padding: 16px 24px; color: #57606a; border-top: 1px solid #d0d7de; font-size: 13px; }
+"#;
+
+/// Emit the site into `sink`; returns the emitted relative paths, sorted.
+pub fn emit(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    pack: Option<&SemanticPack>,
+    model_ref: &str,
+    created_at: Option<String>,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let site = Site {
+        model_ref,
+        created_at: &created_at,
+    };
+    let docs = Docs::new(graph, contract, pack, model_ref);
+    let mut files = vec!["assets/style.css".to_string()];
+    sink.write("assets/style.css", STYLE)?;
+    for page in docs.pages()? {
+        let html_path = format!("{}.html", page.path);
+        let markdown_path = format!("{}.md", page.path);
+        sink.write(&html_path, &html::render(&page, &site))?;
+        sink.write(&markdown_path, &markdown::render(&page, &site))?;
+        files.push(html_path);
+        files.push(markdown_path);
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_docs_in_memory(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    pack: Option<&SemanticPack>,
+    model_ref: &str,
+    created_at: Option<String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, contract, pack, model_ref, created_at, &mut sink)?;
+    Ok(map)
+}
+
+/// Whether `expression` names `name` — as a string literal, variable,
+/// member, aggregated field or role reference.
+pub(crate) fn mentions(expression: &Expression, name: &str) -> bool {
+    match expression {
+        Expression::Literal(value) => value.as_str() == Some(name),
+        Expression::Variable(variable) => variable == name,
+        Expression::MemberAccess { object, member } => object == name || member == name,
+        Expression::RoleReference { role } => role == name,
+        Expression::QuantityLiteral { .. }
+        | Expression::TimeLiteral(_)
+        | Expression::IntervalLiteral { .. } => false,
+        Expression::GroupBy {
+            collection,
+            filter,
+            key,
+            condition,
+            ..
+        } => {
+            mentions(collection, name)
+                || filter.as_deref().is_some_and(|f| mentions(f, name))
+                || mentions(key, name)
+                || mentions(condition, name)
+        }
+        Expression::Binary { left, right, .. } => mentions(left, name) || mentions(right, name),
+        Expression::Unary { operand, .. } | Expression::Cast { operand, .. } => {
+            mentions(operand, name)
+        }
+        Expression::Quantifier {
+            collection,
+            condition,
+            ..
+        } => mentions(collection, name) || mentions(condition, name),
+        Expression::Aggregation {
+            collection,
+            field,
+            filter,
+            ..
+        } => {
+            mentions(collection, name)
+                || field.as_deref() == Some(name)
+                || filter.as_deref().is_some_and(|f| mentions(f, name))
+        }
+        Expression::AggregationComprehension {
+            collection,
+            predicate,
+            projection,
+            ..
+        } => mentions(collection, name) || mentions(predicate, name) || mentions(projection, name),
+    }
+}
+
+fn sort_key(namespace: &str, name: &str) -> (String, String) {
+    (namespace.to_string(), name.to_string())
+}
+
+/// Cross-reference tables shared by every page.
+struct Docs<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+    pack: Option<&'a SemanticPack>,
+    model_ref: &'a str,
+    /// Entity, resource and role pages.
+    pages: HashMap<ConceptId, String>,
+    /// Display names of entities, resources, roles and policies.
+    names: HashMap<ConceptId, String>,
+    policy_anchors: HashMap<ConceptId, String>,
+    records: HashMap<String, (String, String)>,
+    enums: HashMap<String, String>,
+    operations: Vec<(&'a OperationContract, String)>,
+}
+
+impl<'a> Docs<'a> {
+    fn new(
+        graph: &'a Graph,
+        contract: &'a ApplicationContract,
+        pack: Option<&'a SemanticPack>,
+        model_ref: &'a str,
+    ) -> Self {
+        let mut pages = HashMap::new();
+        let mut names = HashMap::new();
+
+        let mut registrar = NameRegistrar::new();
+        for entity in sorted_entities(graph) {
+            let slug = registrar.register("slug", entity.name());
+            pages.insert(entity.id().clone(), format!("entities/{slug}"));
+            names.insert(entity.id().clone(), entity.name().to_string());
+        }
+        let mut resources = graph.all_resources();
+        resources.sort_by_key(|r| sort_key(r.namespace(), r.name()));
+        let mut registrar = NameRegistrar::new();
+        for resource in resources {
+            let slug = registrar.register("slug", resource.name());
+            pages.insert(resource.id().clone(), format!("resources/{slug}"));
+            names.insert(resource.id().clone(), resource.name().to_string());
+        }
+        let mut roles = graph.all_roles();
+        roles.sort_by_key(|r| sort_key(r.namespace(), r.name()));
+        let mut registrar = NameRegistrar::new();
+        for role in roles {
+            let slug = registrar.register("slug", role.name());
+            pages.insert(role.id().clone(), format!("roles/{slug}"));
+            names.insert(role.id().clone(), role.name().to_string());
+        }
+        let mut registrar = NameRegistrar::new();
+        let mut policy_anchors = HashMap::new();
+        for policy in sorted_policies(graph) {
+            let slug = registrar.register("slug", &policy.name);
+            policy_anchors.insert(policy.id.clone(), slug);
+            names.insert(policy.id.clone(), policy.name.clone());
+        }
+
+        let mut registrar = NameRegistrar::new();
+        let records = contract
+            .records
+            .iter()
+            .map(|record| {
+                let slug = registrar.register("slug", &record.name);
+                (
+                    record.id.0.clone(),
+                    (record.name.clone(), format!("records/{slug}")),
+                )
+            })
+            .collect();
+        let enums = contract
+            .enums
+            .iter()
+            .map(|e| (e.id.0.clone(), e.name.clone()))
+            .collect();
+        let mut registrar = NameRegistrar::new();
+        let operations = contract
+            .operations
+            .iter()
+            .map(|operation| {
+                let slug = registrar.register("slug", &operation.name);
+                (operation, format!("operations/{slug}"))
+            })
+            .collect();
+
+        Self {
+            graph,
+            contract,
+            pack,
+            model_ref,
+            pages,
+            names,
+            policy_anchors,
+            records,
+            enums,
+            operations,
+        }
+    }
+
+    fn pages(&self) -> Result<Vec<Page>, String> {
+        let mut pages = vec![self.index()?, self.glossary(), self.policies()];
+        for entity in sorted_entities(self.graph) {
+            pages.push(self.entity_page(entity)?);
+        }
+        let mut resources = self.graph.all_resources();
+        resources.sort_by_key(|r| sort_key(r.namespace(), r.name()));
+        for resource in resources {
+            pages.push(self.resource_page(resource)?);
+        }
+        let mut roles = self.graph.all_roles();
+        roles.sort_by_key(|r| sort_key(r.namespace(), r.name()));
+        for role in roles {
+            pages.push(self.role_page(role));
+        }
+        for record in &self.contract.records {
+            pages.push(self.record_page(record));
+        }
+        for (operation, path) in &self.operations {
+            pages.push(self.operation_page(operation, path));
+        }
+        Ok(pages)
+    }
+
+    // ----------------------------------------------------------------------
+    // Site-wide pages
+    // ----------------------------------------------------------------------
+
+    fn index(&self) -> Result<Page, String> {
+        let mut subtitle = vec![text(format!(
+            "{} entities, {} resources, {} roles, {} policies",
+            self.graph.all_entities().len(),
+            self.graph.all_resources().len(),
+            self.graph.all_roles().len(),
+            self.graph.all_policies().len()
+        ))];
+        if let Some(pack) = self.pack {
+            subtitle.push(text(". Definitions from semantic pack "));
+            subtitle.push(code(&pack.pack_id));
+            subtitle.push(text(format!(" (meaning version {})", pack.meaning_version)));
+        }
+        let mut blocks = Vec::new();
+        if !self.graph.all_entities().is_empty() {
+            blocks.push(heading("Diagram"));
+            blocks.push(self.diagram(None)?);
+        }
+
+        let mut concepts: BTreeMap<String, Vec<(String, &'static str, ConceptId)>> =
+            BTreeMap::new();
+        for entity in self.graph.all_entities() {
+            concepts
+                .entry(entity.namespace().to_string())
+                .or_default()
+                .push((entity.name().to_string(), "Entity", entity.id().clone()));
+        }
+        for resource in self.graph.all_resources() {
+            concepts
+                .entry(resource.namespace().to_string())
+                .or_default()
+                .push((
+                    resource.name().to_string(),
+                    "Resource",
+                    resource.id().clone(),
+                ));
+        }
+        for role in self.graph.all_roles() {
+            concepts
+                .entry(role.namespace().to_string())
+                .or_default()
+                .push((role.name().to_string(), "Role", role.id().clone()));
+        }
+        for (namespace, mut members) in concepts {
+            members.sort_by(|a, b| (a.1, &a.0).cmp(&(b.1, &b.0)));
+            blocks.push(heading(format!("Namespace {namespace}")));
+            let rows = members
+                .into_iter()
+                .map(|(name, kind, id)| {
+                    let def = self.definition(&id, &name, Some(concept_kind(kind)));
+                    vec![vec![self.concept_link(&id)], vec![text(kind)], summary(def)]
+                })
+                .collect();
+            blocks.push(table(vec!["Concept", "Kind", "Summary"], rows));
+        }
+
+        if !self.contract.records.is_empty() {
+            blocks.push(heading("Records"));
+            let rows = self
+                .contract
+                .records
+                .iter()
+                .map(|record| {
+                    vec![
+                        vec![self.record_link(&record.id)],
+                        vec![text(record.fields.len().to_string())],
+                        summary(self.definition_by_name(&record.name, None)),
+                    ]
+                })
+                .collect();
+            blocks.push(table(vec!["Record", "Fields", "Summary"], rows));
+        }
+        if !self.operations.is_empty() {
+            blocks.push(heading("Operations"));
+            let rows = self
+                .operations
+                .iter()
+                .map(|(operation, path)| {
+                    vec![
+                        vec![link(&operation.name, path)],
+                        vec![self.actor(&operation.actor)],
+                        vec![text(&operation.intent)],
+                    ]
+                })
+                .collect();
+            blocks.push(table(vec!["Operation", "Actor", "Intent"], rows));
+        }
+        if !self.contract.enums.is_empty() {
+            blocks.push(heading("Enumerations"));
+            let rows = self
+                .contract
+                .enums
+                .iter()
+                .map(|enumeration| {
+                    let members: Vec<&str> = enumeration
+                        .members
+                        .iter()
+                        .map(|member| member.name.as_str())
+                        .collect();
+                    vec![
+                        vec![text(&enumeration.name)],
+                        vec![code(members.join(", "))],
+                    ]
+                })
+                .collect();
+            blocks.push(table(vec!["Enumeration", "Members"], rows));
+        }
+        Ok(Page {
+            path: "index".to_string(),
+            title: "Model".to_string(),
+            subtitle,
+            blocks,
+        })
+    }
+
+    fn glossary(&self) -> Page {
+        // (sort key, term, kind, definition)
+        let mut terms: Vec<(String, Line, String, Line)> = Vec::new();
+        let mut concepts: Vec<(ConceptId, &str, &'static str)> = Vec::new();
+        for entity in self.graph.all_entities() {
+            concepts.push((entity.id().clone(), entity.name(), "Entity"));
+        }
+        for resource in self.graph.all_resources() {
+            concepts.push((resource.id().clone(), resource.name(), "Resource"));
+        }
+        for role in self.graph.all_roles() {
+            concepts.push((role.id().clone(), role.name(), "Role"));
+        }
+        for (id, name, kind) in concepts {
+            let def = self.definition(&id, name, Some(concept_kind(kind)));
+            terms.push((
+                normalize_lookup_key(name),
+                vec![self.concept_link(&id)],
+                kind.to_string(),
+                summary(def),
+            ));
+            for alias in self.aliases(def) {
+                terms.push((
+                    normalize_lookup_key(alias),
+                    vec![text(alias)],
+                    "Alias".to_string(),
+                    vec![text("See "), self.concept_link(&id)],
+                ));
+            }
+        }
+        for policy in self.graph.all_policies() {
+            let def = self.definition(&policy.id, &policy.name, Some(ConceptKind::Policy));
+            let mut definition = summary(def);
+            if definition.is_empty() {
+                definition.push(text(policy.to_english()));
+            }
+            terms.push((
+                normalize_lookup_key(&policy.name),
+                vec![self.concept_link(&policy.id)],
+                "Policy".to_string(),
+                definition,
+            ));
+        }
+        for record in &self.contract.records {
+            terms.push((
+                normalize_lookup_key(&record.name),
+                vec![self.record_link(&record.id)],
+                "Record".to_string(),
+                summary(self.definition_by_name(&record.name, None)),
+            ));
+        }
+        for (operation, path) in &self.operations {
+            terms.push((
+                normalize_lookup_key(&operation.name),
+                vec![link(&operation.name, path)],
+                "Operation".to_string(),
+                vec![text(&operation.intent)],
+            ));
+        }
+        terms.sort_by(|a, b| (&a.0, &a.2).cmp(&(&b.0, &b.2)));
+        let rows = terms
+            .into_iter()
+            .map(|(_, term, kind, definition)| vec![term, vec![text(kind)], definition])
+            .collect();
+        Page {
+            path: "glossary".to_string(),
+            title: "Glossary".to_string(),
+            subtitle: vec![text("Every term in the model, with its aliases.")],
+            blocks: vec![Block::Table {
+                headers: vec!["Term", "Kind", "Definition"],
+                rows,
+                searchable: true,
+            }],
+        }
+    }
+
+    fn policies(&self) -> Page {
+        let mut blocks = Vec::new();
+        for policy in sorted_policies(self.graph) {
+            blocks.push(Block::Heading {
+                text: policy.name.clone(),
+                anchor: Some(self.policy_anchors[&policy.id].clone()),
+            });
+            blocks.push(Block::Paragraph(vec![strong(policy.to_english())]));
+            let mut facts = vec![
+                vec![text("Namespace: "), code(&policy.namespace)],
+                vec![
+                    text("Kind: "),
+                    text(match policy.kind {
+                        PolicyKind::Constraint => "constraint",
+                        PolicyKind::Derivation => "derivation",
+                        PolicyKind::Obligation => "obligation",
+                    }),
+                ],
+                vec![text(format!("Priority: {}", policy.priority))],
+            ];
+            if let Some(rationale) = &policy.rationale {
+                facts.push(vec![text(format!("Rationale: {rationale}"))]);
+            }
+            if !policy.tags.is_empty() {
+                facts.push(vec![text("Tags: "), code(policy.tags.join(", "))]);
+            }
+            facts.push(vec![
+                text("Expression: "),
+                code(policy.expression().to_string()),
+            ]);
+            blocks.push(Block::List(facts));
+            let def = self.definition(&policy.id, &policy.name, Some(ConceptKind::Policy));
+            blocks.extend(self.definition_blocks(def));
+        }
+        if blocks.is_empty() {
+            blocks.push(Block::Paragraph(vec![text(
+                "The model declares no policies.",
+            )]));
+        }
+        Page {
+            path: "policies".to_string(),
+            title: "Policies".to_string(),
+            subtitle: vec![text("Every policy in plain English.")],
+            blocks,
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // Concept pages
+    // ----------------------------------------------------------------------
+
+    fn entity_page(&self, entity: &crate::primitives::Entity) -> Result<Page, String> {
+        let id = entity.id();
+        let def = self.definition(id, entity.name(), Some(ConceptKind::Entity));
+        let mut blocks = self.definition_blocks(def);
+
+        let roles: Vec<Line> = self
+            .graph
+            .roles_for_entity(id)
+            .into_iter()
+            .flatten()
+            .map(|role| vec![self.concept_link(role)])
+            .collect();
+        let has_roles = !roles.is_empty();
+        if has_roles {
+            blocks.push(heading("Roles"));
+            blocks.push(Block::List(roles));
+        }
+        if let Some(body) = self.contract.entities.iter().find(|e| &e.concept_id == id) {
+            blocks.push(heading("Fields"));
+            blocks.push(self.fields_table(&body.fields, Some(&body.key_field)));
+        }
+        let outgoing = self.graph.flows_from(id);
+        if !outgoing.is_empty() {
+            blocks.push(heading("Outgoing flows"));
+            blocks.push(self.flows_table(outgoing, "To", |f| f.to_id())?);
+        }
+        let incoming = self.graph.flows_to(id);
+        if !incoming.is_empty() {
+            blocks.push(heading("Incoming flows"));
+            blocks.push(self.flows_table(incoming, "From", |f| f.from_id())?);
+        }
+        let operations: Vec<&OperationContract> = self
+            .operations
+            .iter()
+            .map(|(operation, _)| *operation)
+            .filter(|operation| &operation.state == id)
+            .collect();
+        if !operations.is_empty() {
+            blocks.push(heading("Operations"));
+            blocks.push(self.operations_list(&operations));
+        }
+        let bound = operations.iter().flat_map(|operation| bindings(operation));
+        blocks.extend(self.governing(entity.name(), bound));
+
+        let focus = format!("{}::{}", entity.namespace(), entity.name());
+        if !self.graph.flows_from(id).is_empty() || !self.graph.flows_to(id).is_empty() || has_roles
+        {
+            blocks.push(heading("Diagram"));
+            blocks.push(self.diagram(Some(&focus))?);
+        }
+        Ok(Page {
+            path: self.pages[id].clone(),
+            title: entity.name().to_string(),
+            subtitle: vec![text("Entity in "), code(entity.namespace())],
+            blocks,
+        })
+    }
+
+    fn resource_page(&self, resource: &crate::primitives::Resource) -> Result<Page, String> {
+        let id = resource.id();
+        let def = self.definition(id, resource.name(), Some(ConceptKind::Resource));
+        let mut blocks = self.definition_blocks(def);
+        let mut flows: Vec<&Flow> = self
+            .graph
+            .all_flows()
+            .into_iter()
+            .filter(|flow| flow.resource_id() == id)
+            .collect();
+        if !flows.is_empty() {
+            flows.sort_by_key(|flow| (self.name(flow.from_id()), self.name(flow.to_id())));
+            let mut rows = Vec::new();
+            for flow in flows {
+                let resolved = resolve_flow(self.graph, flow)?;
+                rows.push(vec![
+                    vec![self.concept_link(flow.from_id())],
+                    vec![self.concept_link(flow.to_id())],
+                    vec![text(format!("{} {}", resolved.quantity, resolved.unit))],
+                ]);
+            }
+            blocks.push(heading("Flows"));
+            blocks.push(table(vec!["From", "To", "Quantity"], rows));
+        }
+        blocks.extend(self.governing(resource.name(), std::iter::empty()));
+        Ok(Page {
+            path: self.pages[id].clone(),
+            title: resource.name().to_string(),
+            subtitle: vec![
+                text("Resource in "),
+                code(resource.namespace()),
+                text(", measured in "),
+                code(resource.unit_symbol()),
+            ],
+            blocks,
+        })
+    }
+
+    fn role_page(&self, role: &crate::primitives::Role) -> Page {
+        let id = role.id();
+        let def = self.definition(id, role.name(), Some(ConceptKind::Role));
+        let mut blocks = self.definition_blocks(def);
+        let players: Vec<Line> = sorted_entities(self.graph)
+            .into_iter()
+            .filter(|entity| {
+                self.graph
+                    .roles_for_entity(entity.id())
+                    .is_some_and(|roles| roles.contains(id))
+            })
+            .map(|entity| vec![self.concept_link(entity.id())])
+            .collect();
+        if !players.is_empty() {
+            blocks.push(heading("Played by"));
+            blocks.push(Block::List(players));
+        }
+        let mut relations: Vec<Line> = self
+            .graph
+            .all_relations()
+            .into_iter()
+            .filter(|r| r.subject_role() == id || r.object_role() == id)
+            .map(|relation| {
+                vec![
+                    self.concept_link(relation.subject_role()),
+                    text(format!(" {} ", relation.predicate())),
+                    self.concept_link(relation.object_role()),
+                ]
+            })
+            .collect();
+        relations.sort();
+        if !relations.is_empty() {
+            blocks.push(heading("Relations"));
+            blocks.push(Block::List(relations));
+        }
+        let operations: Vec<&OperationContract> = self
+            .operations
+            .iter()
+            .map(|(operation, _)| *operation)
+            .filter(|operation| matches!(&operation.actor, ActorRef::Role { role } if role == id))
+            .collect();
+        if !operations.is_empty() {
+            blocks.push(heading("Operations"));
+            blocks.push(self.operations_list(&operations));
+        }
+        blocks.extend(self.governing(role.name(), std::iter::empty()));
+        Page {
+            path: self.pages[id].clone(),
+            title: role.name().to_string(),
+            subtitle: vec![text("Role in "), code(role.namespace())],
+            blocks,
+        }
+    }
+
+    fn record_page(&self, record: &crate::application::RecordContract) -> Page {
+        let def = self.definition_by_name(&record.name, None);
+        let mut blocks = self.definition_blocks(def);
+        blocks.push(heading("Fields"));
+        blocks.push(self.fields_table(&record.fields, None));
+        let users: Vec<&OperationContract> = self
+            .operations
+            .iter()
+            .map(|(operation, _)| *operation)
+            .filter(|operation| operation.input == record.id || operation.output == record.id)
+            .collect();
+        if !users.is_empty() {
+            blocks.push(heading("Used by"));
+            blocks.push(self.operations_list(&users));
+        }
+        Page {
+            path: self.records[&record.id.0].1.clone(),
+            title: record.name.clone(),
+            subtitle: vec![text("Application record")],
+            blocks,
+        }
+    }
+
+    fn operation_page(&self, operation: &OperationContract, path: &str) -> Page {
+        let def = self.definition_by_name(&operation.name, None);
+        let mut blocks = vec![Block::Paragraph(vec![text(&operation.intent)])];
+        blocks.extend(self.definition_blocks(def));
+        blocks.push(heading("Contract"));
+        blocks.push(Block::List(vec![
+            vec![text("Actor: "), self.actor(&operation.actor)],
+            vec![text("Input: "), self.record_link(&operation.input)],
+            vec![text("Output: "), self.record_link(&operation.output)],
+            vec![text("State: "), self.concept_link(&operation.state)],
+            vec![text("Direction: "), code(label(&operation.direction))],
+            vec![text("Effect: "), code(label(&operation.effect))],
+        ]));
+        if let AccessMode::PolicyGoverned { bindings } = &operation.access {
+            blocks.push(heading("Governing policies"));
+            let items = bindings
+                .iter()
+                .map(|binding| {
+                    let mut item = vec![self.concept_link(&binding.policy)];
+                    if let Some(policy) = self.graph.get_policy(&binding.policy) {
+                        item.push(text(format!(": {}", policy.to_english())));
+                    }
+                    item.push(text(format!(
+                        " Enforced at {}; fails with ",
+                        label(&binding.enforcement_point)
+                    )));
+                    item.push(code(&binding.failure_code));
+                    item.push(text("."));
+                    item
+                })
+                .collect();
+            blocks.push(Block::List(items));
+        }
+        if !operation.failures.is_empty() {
+            blocks.push(heading("Failures"));
+            let rows = operation
+                .failures
+                .iter()
+                .map(|failure| vec![vec![code(&failure.code)], vec![text(&failure.meaning)]])
+                .collect();
+            blocks.push(table(vec!["Code", "Meaning"], rows));
+        }
+        Page {
+            path: path.to_string(),
+            title: operation.name.clone(),
+            subtitle: vec![text("Application operation")],
+            blocks,
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // Sections
+    // ----------------------------------------------------------------------
+
+    fn definition_blocks(&self, def: Option<&ConceptDef>) -> Vec<Block> {
+        let Some(def) = def else {
+            return match self.pack {
+                Some(_) => vec![Block::Paragraph(vec![text(
+                    "The semantic pack does not define this concept.",
+                )])],
+                None => Vec::new(),
+            };
+        };
+        let mut blocks = vec![Block::Paragraph(vec![text(&def.definition.text)])];
+        let mut facts = vec![
+            vec![text("Status: "), code(label(&def.status))],
+            vec![text(format!("Owner: {}", def.owner))],
+        ];
+        if !def.definition.decision_ref.is_empty() {
+            facts.push(vec![text("Decision: "), code(&def.definition.decision_ref)]);
+        }
+        let aliases = self.aliases(Some(def));
+        if !aliases.is_empty() {
+            facts.push(vec![text(format!("Also known as: {}", aliases.join(", ")))]);
+        }
+        blocks.push(Block::List(facts));
+        if !def.examples.is_empty() {
+            blocks.push(heading("Examples"));
+            blocks.push(Block::List(
+                def.examples.iter().map(|e| vec![text(e)]).collect(),
+            ));
+        }
+        if !def.counterexamples.is_empty() {
+            blocks.push(heading("Counterexamples"));
+            blocks.push(Block::List(
+                def.counterexamples.iter().map(|e| vec![text(e)]).collect(),
+            ));
+        }
+        blocks
+    }
+
+    /// Policies mentioning `name`, plus `bound` ones, in policy-page order.
+    fn governing(&self, name: &str, bound: impl Iterator<Item = &'a ConceptId>) -> Vec<Block> {
+        let bound: Vec<&ConceptId> = bound.collect();
+        let items: Vec<Line> = sorted_policies(self.graph)
+            .into_iter()
+            .filter(|policy| bound.contains(&&policy.id) || mentions(policy.expression(), name))
+            .map(|policy| {
+                vec![
+                    self.concept_link(&policy.id),
+                    text(format!(": {}", policy.to_english())),
+                ]
+            })
+            .collect();
+        if items.is_empty() {
+            return Vec::new();
+        }
+        vec![heading("Governing policies"), Block::List(items)]
+    }
+
+    fn flows_table(
+        &self,
+        mut flows: Vec<&Flow>,
+        counterpart: &'static str,
+        other: impl Fn(&Flow) -> &ConceptId,
+    ) -> Result<Block, String> {
+        flows.sort_by_key(|flow| (self.name(flow.resource_id()), self.name(other(flow))));
+        let mut rows = Vec::new();
+        for flow in flows {
+            let resolved = resolve_flow(self.graph, flow)?;
+            rows.push(vec![
+                vec![self.concept_link(flow.resource_id())],
+                vec![self.concept_link(other(flow))],
+                vec![text(format!("{} {}", resolved.quantity, resolved.unit))],
+            ]);
+        }
+        Ok(table(vec!["Resource", counterpart, "Quantity"], rows))
+    }
+
+    fn fields_table(&self, fields: &[FieldContract], key_field: Option<&str>) -> Block {
+        let rows = fields
+            .iter()
+            .map(|field| {
+                let mut name = vec![code(&field.name)];
+                if key_field == Some(field.name.as_str()) {
+                    name.push(text(" (key)"));
+                }
+                vec![
+                    name,
+                    self.field_type(&field.field_type),
+                    vec![text(if field.optional { "no" } else { "yes" })],
+                ]
+            })
+            .collect();
+        table(vec!["Field", "Type", "Required"], rows)
+    }
+
+    fn operations_list(&self, operations: &[&OperationContract]) -> Block {
+        Block::List(
+            operations
+                .iter()
+                .map(|operation| {
+                    vec![
+                        self.operation_link(operation),
+                        text(format!(": {}", operation.intent)),
+                    ]
+                })
+                .collect(),
+        )
+    }
+
+    fn diagram(&self, focus: Option<&str>) -> Result<Block, String> {
+        let ir = DiagramIr::from_graph(self.graph, focus)?;
+        let header = format!("Diagram projected by DomainForge from {}.", self.model_ref);
+        Ok(Block::Diagram {
+            svg: svg::render(&ir),
+            mermaid: mermaid::render(&ir, &header),
+        })
+    }
+
+    // ----------------------------------------------------------------------
+    // Lookups
+    // ----------------------------------------------------------------------
+
+    /// The pack definition for a graph concept: by id, then by name and kind.
+    fn definition(
+        &self,
+        id: &ConceptId,
+        name: &str,
+        kind: Option<ConceptKind>,
+    ) -> Option<&'a ConceptDef> {
+        let pack = self.pack?;
+        let id = id.to_string();
+        pack.concepts
+            .iter()
+            .find(|def| def.id == id)
+            .or_else(|| self.definition_by_name(name, kind))
+    }
+
+    fn definition_by_name(&self, name: &str, kind: Option<ConceptKind>) -> Option<&'a ConceptDef> {
+        let key = normalize_lookup_key(name);
+        self.pack?.concepts.iter().find(|def| {
+            normalize_lookup_key(&def.canonical_name) == key && kind.map_or(true, |k| def.kind == k)
+        })
+    }
+
+    fn aliases(&self, def: Option<&ConceptDef>) -> Vec<&'a str> {
+        let (Some(pack), Some(def)) = (self.pack, def) else {
+            return Vec::new();
+        };
+        let mut aliases: Vec<&str> = pack
+            .aliases
+            .iter()
+            .filter(|alias| {
+                alias.target_concept_id == def.id && alias.status != AliasStatus::Blocked
+            })
+            .map(|alias| alias.alias.as_str())
+            .collect();
+        aliases.sort();
+        aliases.dedup();
+        aliases
+    }
+
+    fn name(&self, id: &ConceptId) -> String {
+        self.names
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn concept_link(&self, id: &ConceptId) -> page::Span {
+        if let Some(anchor) = self.policy_anchors.get(id) {
+            return link(self.name(id), format!("policies#{anchor}"));
+        }
+        match self.pages.get(id) {
+            Some(path) => link(self.name(id), path),
+            None => text(self.name(id)),
+        }
+    }
+
+    fn record_link(&self, id: &ApplicationSymbolId) -> page::Span {
+        match self.records.get(&id.0) {
+            Some((name, path)) => link(name, path),
+            None => code(self.enums.get(&id.0).unwrap_or(&id.0)),
+        }
+    }
+
+    fn operation_link(&self, operation: &OperationContract) -> page::Span {
+        let path = self
+            .operations
+            .iter()
+            .find(|(candidate, _)| candidate.id == operation.id)
+            .map(|(_, path)| path.as_str())
+            .unwrap_or("index");
+        link(&operation.name, path)
+    }
+
+    fn actor(&self, actor: &ActorRef) -> page::Span {
+        match actor {
+            ActorRef::Anonymous => text("anonymous"),
+            ActorRef::Role { role } => self.concept_link(role),
+        }
+    }
+
+    fn field_type(&self, field_type: &FieldType) -> Line {
+        match field_type {
+            FieldType::Scalar { scalar } => vec![code(match scalar {
+                ScalarType::String => "string",
+                ScalarType::Int => "int",
+                ScalarType::Decimal => "decimal",
+                ScalarType::Bool => "bool",
+                ScalarType::Timestamp => "timestamp",
+                ScalarType::Uuid => "uuid",
+            })],
+            FieldType::Quantity { .. } => vec![code("quantity")],
+            FieldType::EntityRef { entity } => {
+                vec![text("reference to "), self.concept_link(entity)]
+            }
+            FieldType::Enum { symbol } => vec![
+                text("one of "),
+                code(self.enums.get(&symbol.0).unwrap_or(&symbol.0)),
+            ],
+            FieldType::List { element } => {
+                let mut line = vec![text("list of ")];
+                line.extend(self.field_type(element));
+                line
+            }
+        }
+    }
+}
+
+fn sorted_entities(graph: &Graph) -> Vec<&crate::primitives::Entity> {
+    let mut entities = graph.all_entities();
+    entities.sort_by_key(|e| sort_key(e.namespace(), e.name()));
+    entities
+}
+
+fn sorted_policies(graph: &Graph) -> Vec<&Policy> {
+    let mut policies = graph.all_policies();
+    policies.sort_by_key(|p| sort_key(&p.namespace, &p.name));
+    policies
+}
+
+fn bindings(operation: &OperationContract) -> Vec<&ConceptId> {
+    match &operation.access {
+        AccessMode::Public => Vec::new(),
+        AccessMode::PolicyGoverned { bindings } => bindings.iter().map(|b| &b.policy).collect(),
+    }
+}
+
+fn concept_kind(kind: &str) -> ConceptKind {
+    match kind {
+        "Resource" => ConceptKind::Resource,
+        "Role" => ConceptKind::Role,
+        _ => ConceptKind::Entity,
+    }
+}
+
+/// The first sentence of a definition, for tables.
+fn summary(def: Option<&ConceptDef>) -> Line {
+    let Some(def) = def else {
+        return Vec::new();
+    };
+    let text_ = def.definition.text.trim();
+    let first = match text_.find(". ") {
+        Some(end) => &text_[..=end],
+        None => text_,
+    };
+    vec![text(first)]
+}
+
+/// snake_case serde label of a contract enum.
+fn label(value: &impl serde::Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+    fn graph() -> Graph {
+        crate::parser::parse_to_graph(
+            r#"Entity "Warehouse" in logistics
+Entity "Cold Store" in logistics
+Resource "Widget" units in logistics
+Flow "Widget" from "Cold Store" to "Warehouse" quantity 10
+Policy widgets_move as: Widget.quantity > 0
+"#,
+        )
+        .unwrap()
+    }
+
+    fn site() -> BTreeMap<String, String> {
+        project_docs_in_memory(
+            &graph(),
+            &ApplicationContract::default(),
+            None,
+            "model.sea",
+            Some(FIXED_TS.to_string()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_every_page_is_written_as_html_and_markdown() {
+        let files: Vec<String> = site().into_keys().collect();
+        assert_eq!(
+            files,
+            vec![
+                "assets/style.css",
+                "entities/cold_store.html",
+                "entities/cold_store.md",
+                "entities/warehouse.html",
+                "entities/warehouse.md",
+                "glossary.html",
+                "glossary.md",
+                "index.html",
+                "index.md",
+                "policies.html",
+                "policies.md",
+                "resources/widget.html",
+                "resources/widget.md",
+            ]
+        );
+        assert_eq!(site(), site());
+    }
+
+    #[test]
+    fn test_policies_govern_the_concepts_they_mention() {
+        let site = site();
+        let widget = &site["resources/widget.md"];
+        assert!(widget.contains("## Governing policies"));
+        assert!(widget.contains("[widgets_move](../policies.md#widgets_move): It is obligatory"));
+        assert!(!site["entities/warehouse.md"].contains("Governing policies"));
+    }
+}
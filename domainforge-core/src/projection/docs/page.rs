@@ -0,0 +1,105 @@
+//! Renderer-neutral page model. Every documentation page is built once as
+//! blocks of inline spans, then rendered to HTML (`html.rs`) and Markdown
+//! (`markdown.rs`), so the two outputs never disagree on content.
+
+/// Inline content.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Span {
+    Text(String),
+    Code(String),
+    Strong(String),
+    /// `target` is a site-relative page path without extension, optionally
+    /// followed by `#anchor`.
+    Link {
+        text: String,
+        target: String,
+    },
+}
+
+/// One line of inline content.
+pub type Line = Vec<Span>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Block {
+    Heading {
+        text: String,
+        anchor: Option<String>,
+    },
+    Paragraph(Line),
+    List(Vec<Line>),
+    Table {
+        headers: Vec<&'static str>,
+        rows: Vec<Vec<Line>>,
+        /// Filterable from a search box (HTML only).
+        searchable: bool,
+    },
+    /// The same diagram as inline SVG (HTML) and Mermaid (Markdown).
+    Diagram {
+        svg: String,
+        mermaid: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page {
+    /// Site-relative path without extension, e.g. `entities/order`.
+    pub path: String,
+    pub title: String,
+    pub subtitle: Line,
+    pub blocks: Vec<Block>,
+}
+
+/// Footer and navigation shared by every page.
+pub struct Site<'a> {
+    pub model_ref: &'a str,
+    pub created_at: &'a str,
+}
+
+impl Page {
+    /// `../` once per directory level, so links resolve from this page.
+    pub fn root_prefix(&self) -> String {
+        "../".repeat(self.path.matches('/').count())
+    }
+}
+
+pub fn text(s: impl Into<String>) -> Span {
+    Span::Text(s.into())
+}
+
+pub fn code(s: impl Into<String>) -> Span {
+    Span::Code(s.into())
+}
+
+pub fn strong(s: impl Into<String>) -> Span {
+    Span::Strong(s.into())
+}
+
+pub fn link(text: impl Into<String>, target: impl Into<String>) -> Span {
+    Span::Link {
+        text: text.into(),
+        target: target.into(),
+    }
+}
+
+pub fn heading(text: impl Into<String>) -> Block {
+    Block::Heading {
+        text: text.into(),
+        anchor: None,
+    }
+}
+
+pub fn table(headers: Vec<&'static str>, rows: Vec<Vec<Line>>) -> Block {
+    Block::Table {
+        headers,
+        rows,
+        searchable: false,
+    }
+}
+
+/// Split a link target into its page path and `#anchor` suffix.
+pub fn split_target(target: &str) -> (&str, &str) {
+    match target.find('#') {
+        Some(i) => (&target[..i], &target[i..]),
+        None => (target, ""),
+    }
+}
@@ -17,7 +17,29 @@
 //! | `Pattern`          | Value object validating against the regex                       |
 //! | `Role`             | Enum `Role { <Pascal>, ... }`; commands carry `issued_by: Role` |
 //! | `Relation`         | Doc-comment line on the Role enum                               |
+//!
+//! With an ADR-013 [`ApplicationContract`] ([`DomainIr::from_graph_and_contract`])
+//! the IR also carries the application slice:
+//!
+//! | Contract element   | Domain construct                                                |
+//! | ------------------ | --------------------------------------------------------------- |
+//! | `enum`             | Enum with wire values                                           |
+//! | `record`           | Record type + `validate()` over its field constraints           |
+//! | `operation`        | `<Op>Handler` + `<Op>Port` (+ policy and idempotency ports)     |
+//! | `failure`          | Typed error `<Op><Code>Error` (Rust: `<Op>Error::<Code>`)       |
+//!
+//! A handler runs, in order: input validation, precondition policies, the
+//! idempotency replay check (`keyed_by`), then the state port, mapping every
+//! detected failure kind to the declared failure that covers it. Concurrency
+//! is the port's contract: `unique_key` creates return nothing when the key
+//! is taken, `optimistic_version` mutates compare the caller's version first.
 
+use crate::application::{
+    ApplicationContract, ApplicationSymbolId, ConcurrencyStrategy, EffectKind, FailureKind,
+    FieldConstraint, FieldContract, FieldType, IdempotencyStrategy, OperationContract,
+    RecordContract, ScalarType, TypedValue,
+};
+use crate::concept_id::ConceptId;
 use crate::graph::Graph;
 use crate::projection::flows::ResolvedFlow;
 use crate::projection::flows::{collect_flows, model_namespace};
@@ -147,6 +169,299 @@ pub struct DomainIr {
     /// `subject —predicate→ object` doc-comment lines.
     pub relations_doc: Vec<String>,
     pub instances: Vec<InstanceIr>,
+    /// Application enums (empty without a contract).
+    pub app_enums: Vec<AppEnumIr>,
+    /// Application records (empty without a contract).
+    pub records: Vec<RecordIr>,
+    /// Application operations (empty without a contract).
+    pub operations: Vec<OperationIr>,
+}
+
+impl DomainIr {
+    /// Whether the application slice (records, enums, operations) is present;
+    /// renderers emit the application files only then.
+    pub fn has_application(&self) -> bool {
+        !(self.app_enums.is_empty() && self.records.is_empty() && self.operations.is_empty())
+    }
+
+    /// Whether a `keyed_by` idempotent operation takes `record` as input;
+    /// renderers then emit its canonical input fingerprint.
+    pub fn fingerprints(&self, record: &str) -> bool {
+        self.operations
+            .iter()
+            .any(|op| op.idempotency_port.is_some() && op.input == record)
+    }
+}
+
+/// Language-neutral type of an application record field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeIr {
+    Str,
+    Int,
+    /// Canonical decimal. Quantities lower here too (the base-unit amount).
+    Decimal,
+    Bool,
+    /// RFC 3339 text.
+    Timestamp,
+    Uuid,
+    /// `<Pascal(enum)>`.
+    Enum(String),
+    List(Box<TypeIr>),
+}
+
+/// One field constraint. On a list field, `MinItems`/`MaxItems` constrain
+/// the list and every other check applies to each element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckIr {
+    /// Lower bound as a canonical decimal string.
+    Min {
+        bound: String,
+        exclusive: bool,
+    },
+    /// Upper bound as a canonical decimal string.
+    Max {
+        bound: String,
+        exclusive: bool,
+    },
+    MinLength(u32),
+    MaxLength(u32),
+    MinItems(u32),
+    MaxItems(u32),
+    /// The SEA Pattern's regex.
+    Pattern(String),
+}
+
+impl CheckIr {
+    /// Whether the check constrains the list rather than its elements.
+    pub fn is_collection(&self) -> bool {
+        matches!(self, CheckIr::MinItems(_) | CheckIr::MaxItems(_))
+    }
+
+    /// Violation text every renderer reports after `<path>: `.
+    pub fn message(&self) -> String {
+        match self {
+            CheckIr::Min { bound, exclusive } => {
+                format!(
+                    "{} {bound}",
+                    if *exclusive { "exclusive_min" } else { "min" }
+                )
+            }
+            CheckIr::Max { bound, exclusive } => {
+                format!(
+                    "{} {bound}",
+                    if *exclusive { "exclusive_max" } else { "max" }
+                )
+            }
+            CheckIr::MinLength(n) => format!("min_length {n}"),
+            CheckIr::MaxLength(n) => format!("max_length {n}"),
+            CheckIr::MinItems(n) => format!("min_items {n}"),
+            CheckIr::MaxItems(n) => format!("max_items {n}"),
+            CheckIr::Pattern(regex) => format!("pattern {regex}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldIr {
+    /// `ident(field)`.
+    pub name: String,
+    pub ty: TypeIr,
+    pub optional: bool,
+    pub checks: Vec<CheckIr>,
+    /// What the plain type loses (quantity unit, referenced entity).
+    pub doc: Option<String>,
+    /// Contract field name: the payload member and fingerprint key.
+    pub wire_name: String,
+    pub canonical: CanonicalIr,
+}
+
+/// How a field value enters the canonical input fingerprint: the
+/// `application::canonical_typed_value` tag its contract type lowers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CanonicalIr {
+    /// `string`, `int`, `decimal`, `bool`, `timestamp` or `uuid`.
+    Scalar(&'static str),
+    /// Base-unit amount tagged with the unit concept id.
+    Quantity {
+        unit: String,
+    },
+    /// Key tagged with the referenced entity's concept id.
+    EntityRef {
+        entity: String,
+        key: Box<CanonicalIr>,
+    },
+    /// Wire value tagged with the enum symbol.
+    Enum {
+        symbol: String,
+    },
+    List(Box<CanonicalIr>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordIr {
+    /// `<Pascal(record)>`.
+    pub name: String,
+    pub fields: Vec<FieldIr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumMemberIr {
+    /// `<Pascal(member)>` (TypeScript/Rust variant).
+    pub variant: String,
+    /// `<UPPER_SNAKE(member)>` (Python member).
+    pub constant: String,
+    pub wire: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppEnumIr {
+    /// `<Pascal(enum)>`.
+    pub name: String,
+    pub members: Vec<EnumMemberIr>,
+}
+
+/// A constraint-satisfying (or deliberately violating) literal for generated
+/// tests; renderers only translate it into their literal syntax.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SampleIr {
+    Str(String),
+    Int(i64),
+    Decimal(String),
+    Bool(bool),
+    Enum {
+        name: String,
+        variant: String,
+        constant: String,
+    },
+    List(Vec<SampleIr>),
+}
+
+/// A full record literal: `(field, optional, value)` in declaration order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordSampleIr {
+    pub record: String,
+    pub fields: Vec<(String, bool, SampleIr)>,
+}
+
+impl RecordSampleIr {
+    /// The same literal with one field replaced.
+    pub fn with(&self, field: &str, value: SampleIr) -> RecordSampleIr {
+        let mut out = self.clone();
+        for (name, _, v) in &mut out.fields {
+            if name == field {
+                *v = value.clone();
+            }
+        }
+        out
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EffectIr {
+    Creates,
+    Mutates,
+    Reads,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdempotencyIr {
+    /// Replays are detected by this input field.
+    KeyedBy {
+        field: String,
+    },
+    Inherent,
+    NotApplicable,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConcurrencyIr {
+    UniqueKey {
+        field: String,
+    },
+    /// The input field carrying the caller's expected version.
+    OptimisticVersion {
+        field: String,
+    },
+    ReadSnapshot,
+}
+
+impl ConcurrencyIr {
+    /// Detail text a handler reports with a concurrency conflict.
+    pub fn conflict_detail(&self) -> String {
+        match self {
+            ConcurrencyIr::UniqueKey { field } => format!("{field} already taken"),
+            _ => "version changed concurrently".to_string(),
+        }
+    }
+}
+
+/// One declared failure → one typed error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailureIr {
+    pub code: String,
+    /// `<Op><Pascal(code)>Error` (Python/TypeScript class).
+    pub class_name: String,
+    /// `<Pascal(code)>` (Rust `<Op>Error` variant).
+    pub variant: String,
+    /// Canonical failure kinds (`input_validation`, `policy`, ...).
+    pub kinds: Vec<String>,
+    pub meaning: String,
+}
+
+/// The declared failure a handler raises for one detected failure kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RaiseIr {
+    pub class_name: String,
+    pub variant: String,
+    pub kind: String,
+}
+
+/// A `policy_governed` binding checked before the state port runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreconditionIr {
+    pub policy_name: String,
+    /// `allows_<slug(policy)>` on the policies port.
+    pub method: String,
+    pub raise: RaiseIr,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationIr {
+    /// `slug(operation)`.
+    pub name: String,
+    pub intent: String,
+    /// `<Pascal(state entity)>`.
+    pub state: String,
+    pub input: String,
+    pub output: String,
+    pub effect: EffectIr,
+    pub idempotency: IdempotencyIr,
+    pub concurrency: ConcurrencyIr,
+    /// `<Op>Error`: base class (Python/TypeScript) or error enum (Rust).
+    pub error: String,
+    /// `<Op>Handler`.
+    pub handler: String,
+    /// `<Op>Port`: the state port the handler delegates the effect to.
+    pub port: String,
+    /// `<Op>Policies`, when the operation is policy governed.
+    pub policies_port: Option<String>,
+    /// `<Op>IdempotencyStore`, for `keyed_by` idempotency.
+    pub idempotency_port: Option<String>,
+    pub failures: Vec<FailureIr>,
+    pub preconditions: Vec<PreconditionIr>,
+    pub on_invalid_input: Option<RaiseIr>,
+    pub on_missing_state: Option<RaiseIr>,
+    pub on_idempotency_conflict: Option<RaiseIr>,
+    pub on_concurrency_conflict: Option<RaiseIr>,
+    /// Valid input/output literals for generated tests. `None` when a field
+    /// carries a pattern no sample can be derived for.
+    pub sample_input: Option<RecordSampleIr>,
+    pub sample_output: Option<RecordSampleIr>,
+    /// `(field, value)` violating exactly one input constraint.
+    pub invalid_input: Option<(String, SampleIr)>,
+    /// `application::input_fingerprint` of `sample_input`, for `keyed_by`
+    /// operations; generated tests pin `fingerprint()` to it.
+    pub sample_fingerprint: Option<String>,
 }
 
 /// Read the fixture file relative to the crate root (CARGO_MANIFEST_DIR).
@@ -158,6 +473,22 @@ fn fixture_source() -> String {
         .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()))
 }
 
+/// The flagship command operation (ADR-013) as a graph + application contract.
+#[cfg(test)]
+pub(crate) fn flagship_fixture() -> (Graph, ApplicationContract) {
+    let entry = "flagship/command-write.sea";
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../fixtures/application_generation")
+        .join(entry);
+    let source = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+    let sources = serde_json::json!({ entry: source }).to_string();
+    let graph = crate::application::resolve_application_graph(entry, &sources).expect("graph");
+    let document =
+        crate::application::resolve_application_contract(entry, &sources).expect("contract");
+    (graph, document.contract)
+}
+
 impl DomainIr {
     /// Build the IR from a graph. The single place DDD semantics are decided.
     pub fn from_graph(graph: &Graph) -> Result<DomainIr, String> {
@@ -373,8 +704,36 @@ impl DomainIr {
             roles,
             relations_doc,
             instances,
+            app_enums: Vec::new(),
+            records: Vec::new(),
+            operations: Vec::new(),
         })
     }
+
+    /// [`DomainIr::from_graph`] plus the application slice of `contract`.
+    pub fn from_graph_and_contract(
+        graph: &Graph,
+        contract: Option<&ApplicationContract>,
+    ) -> Result<DomainIr, String> {
+        let mut ir = Self::from_graph(graph)?;
+        if let Some(contract) = contract {
+            let app = ApplicationLowering::new(graph, contract);
+            ir.app_enums = app.enums();
+            ir.records = contract
+                .records
+                .iter()
+                .map(|r| app.record(r))
+                .collect::<Result<_, _>>()?;
+            ir.records.sort_by(|a, b| a.name.cmp(&b.name));
+            ir.operations = contract
+                .operations
+                .iter()
+                .map(|op| app.operation(op))
+                .collect::<Result<_, _>>()?;
+            ir.operations.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        Ok(ir)
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -444,6 +803,635 @@ fn value_to_string(v: &serde_json::Value) -> String {
     }
 }
 
+/// `text` as a doc-comment sentence: first letter upper-cased, trailing period.
+pub(crate) fn sentence(text: &str) -> String {
+    let mut chars = text.trim().chars();
+    let mut out = match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => return String::new(),
+    };
+    if !out.ends_with('.') {
+        out.push('.');
+    }
+    out
+}
+
+/// Lowers the application slice of an [`ApplicationContract`].
+struct ApplicationLowering<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+    /// Symbol ID → `<Pascal>` type name for records and enums.
+    types: BTreeMap<String, String>,
+    /// Enum type name → members (for enum samples).
+    members: BTreeMap<String, Vec<EnumMemberIr>>,
+}
+
+impl<'a> ApplicationLowering<'a> {
+    fn new(graph: &'a Graph, contract: &'a ApplicationContract) -> Self {
+        let mut reg = NameRegistrar::new();
+        let mut types = BTreeMap::new();
+        let mut members = BTreeMap::new();
+        for e in &contract.enums {
+            let name = reg.register("ident", &pascal(&e.name));
+            let lowered = e
+                .members
+                .iter()
+                .map(|m| EnumMemberIr {
+                    variant: pascal(&m.name),
+                    constant: slug(&m.name).to_uppercase(),
+                    wire: m.wire.clone(),
+                })
+                .collect();
+            members.insert(name.clone(), lowered);
+            types.insert(e.id.0.clone(), name);
+        }
+        for r in &contract.records {
+            types.insert(r.id.0.clone(), reg.register("ident", &pascal(&r.name)));
+        }
+        Self {
+            graph,
+            contract,
+            types,
+            members,
+        }
+    }
+
+    fn type_name(&self, symbol: &str) -> Result<String, String> {
+        self.types
+            .get(symbol)
+            .cloned()
+            .ok_or_else(|| format!("application contract references unknown type '{symbol}'"))
+    }
+
+    fn enums(&self) -> Vec<AppEnumIr> {
+        // `members` is keyed by type name, so this is already sorted.
+        self.members
+            .iter()
+            .map(|(name, members)| AppEnumIr {
+                name: name.clone(),
+                members: members.clone(),
+            })
+            .collect()
+    }
+
+    fn record(&self, record: &RecordContract) -> Result<RecordIr, String> {
+        Ok(RecordIr {
+            name: self.type_name(&record.id.0)?,
+            fields: record
+                .fields
+                .iter()
+                .map(|f| self.field(f))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn field(&self, field: &FieldContract) -> Result<FieldIr, String> {
+        let (ty, doc, canonical) = self.field_type(&field.field_type)?;
+        let checks = field
+            .constraints
+            .iter()
+            .map(|c| match c {
+                FieldConstraint::Min { value } => Ok(CheckIr::Min {
+                    bound: value.normalize().to_string(),
+                    exclusive: false,
+                }),
+                FieldConstraint::ExclusiveMin { value } => Ok(CheckIr::Min {
+                    bound: value.normalize().to_string(),
+                    exclusive: true,
+                }),
+                FieldConstraint::Max { value } => Ok(CheckIr::Max {
+                    bound: value.normalize().to_string(),
+                    exclusive: false,
+                }),
+                FieldConstraint::ExclusiveMax { value } => Ok(CheckIr::Max {
+                    bound: value.normalize().to_string(),
+                    exclusive: true,
+                }),
+                FieldConstraint::MinLength { value } => Ok(CheckIr::MinLength(*value)),
+                FieldConstraint::MaxLength { value } => Ok(CheckIr::MaxLength(*value)),
+                FieldConstraint::MinItems { value } => Ok(CheckIr::MinItems(*value)),
+                FieldConstraint::MaxItems { value } => Ok(CheckIr::MaxItems(*value)),
+                FieldConstraint::Pattern { pattern } => self
+                    .graph
+                    .all_patterns()
+                    .into_iter()
+                    .find(|p| p.id() == pattern)
+                    .map(|p| CheckIr::Pattern(p.regex().to_string()))
+                    .ok_or_else(|| {
+                        format!(
+                            "field '{}' references unknown pattern '{pattern}'",
+                            field.name
+                        )
+                    }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(FieldIr {
+            name: crate::projection::ids::ident(&field.name),
+            ty,
+            optional: field.optional,
+            checks,
+            doc,
+            wire_name: field.name.clone(),
+            canonical,
+        })
+    }
+
+    fn field_type(
+        &self,
+        field_type: &FieldType,
+    ) -> Result<(TypeIr, Option<String>, CanonicalIr), String> {
+        Ok(match field_type {
+            FieldType::Scalar { scalar } => {
+                let (ty, tag) = match scalar {
+                    ScalarType::String => (TypeIr::Str, "string"),
+                    ScalarType::Int => (TypeIr::Int, "int"),
+                    ScalarType::Decimal => (TypeIr::Decimal, "decimal"),
+                    ScalarType::Bool => (TypeIr::Bool, "bool"),
+                    ScalarType::Timestamp => (TypeIr::Timestamp, "timestamp"),
+                    ScalarType::Uuid => (TypeIr::Uuid, "uuid"),
+                };
+                (ty, None, CanonicalIr::Scalar(tag))
+            }
+            FieldType::Quantity { unit } => (
+                TypeIr::Decimal,
+                Some("quantity as a canonical decimal in the declared unit".to_string()),
+                CanonicalIr::Quantity {
+                    unit: unit.to_string(),
+                },
+            ),
+            FieldType::EntityRef { entity } => {
+                let target = self
+                    .contract
+                    .entities
+                    .iter()
+                    .find(|e| &e.concept_id == entity)
+                    .or_else(|| self.graph.entity_contract(entity))
+                    .ok_or_else(|| format!("entity reference '{entity}' has no entity contract"))?;
+                let key = target
+                    .fields
+                    .iter()
+                    .find(|f| f.name == target.key_field)
+                    .ok_or_else(|| {
+                        format!(
+                            "entity '{}' key field '{}' is not declared",
+                            target.name, target.key_field
+                        )
+                    })?;
+                let (ty, _, key) = self.field_type(&key.field_type)?;
+                (
+                    ty,
+                    Some(format!("key `{}` of {}", target.key_field, target.name)),
+                    CanonicalIr::EntityRef {
+                        entity: entity.to_string(),
+                        key: Box::new(key),
+                    },
+                )
+            }
+            FieldType::Enum { symbol } => (
+                TypeIr::Enum(self.type_name(&symbol.0)?),
+                None,
+                CanonicalIr::Enum {
+                    symbol: symbol.0.clone(),
+                },
+            ),
+            FieldType::List { element } => {
+                let (ty, doc, canonical) = self.field_type(element)?;
+                (
+                    TypeIr::List(Box::new(ty)),
+                    doc,
+                    CanonicalIr::List(Box::new(canonical)),
+                )
+            }
+        })
+    }
+
+    fn record_by_symbol(&self, symbol: &str) -> Result<RecordIr, String> {
+        let record = self
+            .contract
+            .records
+            .iter()
+            .find(|r| r.id.0 == symbol)
+            .ok_or_else(|| format!("operation references unknown record '{symbol}'"))?;
+        self.record(record)
+    }
+
+    fn operation(&self, op: &OperationContract) -> Result<OperationIr, String> {
+        let op_pascal = pascal(&op.name);
+        let input = self.record_by_symbol(&op.input.0)?;
+        let output = self.record_by_symbol(&op.output.0)?;
+        let state = self
+            .contract
+            .entities
+            .iter()
+            .find(|e| e.concept_id == op.state)
+            .map(|e| pascal(&e.name))
+            .or_else(|| self.graph.get_entity(&op.state).map(|e| pascal(e.name())))
+            .ok_or_else(|| format!("operation '{}' state does not resolve", op.name))?;
+
+        let failures: Vec<FailureIr> = op
+            .failures
+            .iter()
+            .map(|f| FailureIr {
+                code: f.code.clone(),
+                class_name: format!("{op_pascal}{}Error", pascal(&f.code)),
+                variant: pascal(&f.code),
+                kinds: f
+                    .kinds
+                    .iter()
+                    .map(|k| failure_kind_slug(*k).to_string())
+                    .collect(),
+                meaning: f.meaning.clone(),
+            })
+            .collect();
+        let raise_for = |kind: FailureKind| -> Result<RaiseIr, String> {
+            let slug = failure_kind_slug(kind);
+            failures
+                .iter()
+                .find(|f| f.kinds.iter().any(|k| k == slug))
+                .map(|f| RaiseIr {
+                    class_name: f.class_name.clone(),
+                    variant: f.variant.clone(),
+                    kind: slug.to_string(),
+                })
+                .ok_or_else(|| format!("operation '{}' declares no {slug} failure", op.name))
+        };
+
+        let mut preconditions = Vec::new();
+        if let crate::application::AccessMode::PolicyGoverned { bindings } = &op.access {
+            for binding in bindings {
+                let policy_name = self
+                    .graph
+                    .get_policy(&binding.policy)
+                    .map(|p| p.name.clone())
+                    .ok_or_else(|| format!("operation '{}' binds an unknown policy", op.name))?;
+                let failure = failures
+                    .iter()
+                    .find(|f| f.code == binding.failure_code)
+                    .ok_or_else(|| {
+                        format!(
+                            "operation '{}' binds undeclared failure '{}'",
+                            op.name, binding.failure_code
+                        )
+                    })?;
+                preconditions.push(PreconditionIr {
+                    method: format!("allows_{}", slug(&policy_name)),
+                    policy_name,
+                    raise: RaiseIr {
+                        class_name: failure.class_name.clone(),
+                        variant: failure.variant.clone(),
+                        kind: failure_kind_slug(FailureKind::Policy).to_string(),
+                    },
+                });
+            }
+        }
+
+        let effect = match op.effect {
+            EffectKind::Creates => EffectIr::Creates,
+            EffectKind::Mutates => EffectIr::Mutates,
+            EffectKind::Reads => EffectIr::Reads,
+        };
+        let idempotency = match &op.idempotency {
+            IdempotencyStrategy::KeyedBy { field } => IdempotencyIr::KeyedBy {
+                field: crate::projection::ids::ident(field),
+            },
+            IdempotencyStrategy::Inherent => IdempotencyIr::Inherent,
+            IdempotencyStrategy::NotApplicable(_) => IdempotencyIr::NotApplicable,
+        };
+        let concurrency = match &op.concurrency {
+            ConcurrencyStrategy::UniqueKey { field } => ConcurrencyIr::UniqueKey {
+                field: crate::projection::ids::ident(field),
+            },
+            ConcurrencyStrategy::OptimisticVersion { field } => ConcurrencyIr::OptimisticVersion {
+                field: crate::projection::ids::ident(field),
+            },
+            ConcurrencyStrategy::ReadSnapshot => ConcurrencyIr::ReadSnapshot,
+        };
+
+        let validates = input.fields.iter().any(|f| !f.checks.is_empty());
+        let on_invalid_input = if validates {
+            Some(raise_for(FailureKind::InputValidation)?)
+        } else {
+            None
+        };
+        let on_missing_state = match effect {
+            EffectIr::Reads | EffectIr::Mutates => Some(raise_for(FailureKind::MissingState)?),
+            EffectIr::Creates => None,
+        };
+        let on_idempotency_conflict = match idempotency {
+            IdempotencyIr::KeyedBy { .. } => Some(raise_for(FailureKind::IdempotencyConflict)?),
+            _ => None,
+        };
+        let on_concurrency_conflict = match concurrency {
+            ConcurrencyIr::ReadSnapshot => None,
+            _ => Some(raise_for(FailureKind::ConcurrencyConflict)?),
+        };
+
+        let sample_input = self.record_sample(&input);
+        let sample_fingerprint = match (&idempotency, &sample_input) {
+            (IdempotencyIr::KeyedBy { .. }, Some(sample)) => {
+                self.sample_fingerprint(&input, sample)
+            }
+            _ => None,
+        };
+        let invalid_input = match (&on_invalid_input, &sample_input) {
+            (Some(_), Some(valid)) => {
+                input
+                    .fields
+                    .iter()
+                    .zip(&valid.fields)
+                    .find_map(|(field, (_, _, value))| {
+                        violation(field, value).map(|v| (field.name.clone(), v))
+                    })
+            }
+            _ => None,
+        };
+
+        Ok(OperationIr {
+            name: slug(&op.name),
+            intent: op.intent.clone(),
+            state,
+            sample_output: self.record_sample(&output),
+            input: input.name,
+            output: output.name,
+            effect,
+            idempotency,
+            concurrency,
+            error: format!("{op_pascal}Error"),
+            handler: format!("{op_pascal}Handler"),
+            port: format!("{op_pascal}Port"),
+            policies_port: (!preconditions.is_empty()).then(|| format!("{op_pascal}Policies")),
+            idempotency_port: on_idempotency_conflict
+                .as_ref()
+                .map(|_| format!("{op_pascal}IdempotencyStore")),
+            failures,
+            preconditions,
+            on_invalid_input,
+            on_missing_state,
+            on_idempotency_conflict,
+            on_concurrency_conflict,
+            sample_input,
+            invalid_input,
+            sample_fingerprint,
+        })
+    }
+
+    /// The reference fingerprint of a record sample: the sample as the
+    /// payload checker would type it, hashed by `input_fingerprint`.
+    fn sample_fingerprint(&self, record: &RecordIr, sample: &RecordSampleIr) -> Option<String> {
+        let input = record
+            .fields
+            .iter()
+            .zip(&sample.fields)
+            .map(|(field, (_, _, value))| {
+                Some((
+                    field.wire_name.clone(),
+                    self.sample_value(&field.canonical, value)?,
+                ))
+            })
+            .collect::<Option<indexmap::IndexMap<_, _>>>()?;
+        Some(crate::application::input_fingerprint(&input))
+    }
+
+    fn sample_value(&self, canonical: &CanonicalIr, sample: &SampleIr) -> Option<TypedValue> {
+        Some(match (canonical, sample) {
+            (CanonicalIr::Scalar("string"), SampleIr::Str(v)) => TypedValue::String(v.clone()),
+            (CanonicalIr::Scalar("timestamp"), SampleIr::Str(v)) => {
+                TypedValue::Timestamp(chrono::DateTime::parse_from_rfc3339(v).ok()?.into())
+            }
+            (CanonicalIr::Scalar("uuid"), SampleIr::Str(v)) => {
+                TypedValue::Uuid(uuid::Uuid::parse_str(v).ok()?)
+            }
+            (CanonicalIr::Scalar("int"), SampleIr::Int(v)) => TypedValue::Int(*v),
+            (CanonicalIr::Scalar("bool"), SampleIr::Bool(v)) => TypedValue::Bool(*v),
+            (CanonicalIr::Scalar("decimal"), SampleIr::Decimal(v)) => {
+                TypedValue::Decimal(v.parse().ok()?)
+            }
+            (CanonicalIr::Quantity { unit }, SampleIr::Decimal(v)) => TypedValue::Quantity {
+                base_value: v.parse().ok()?,
+                unit: ConceptId::from_uuid(uuid::Uuid::parse_str(unit).ok()?),
+            },
+            (CanonicalIr::EntityRef { entity, key }, sample) => TypedValue::EntityRef {
+                entity: ConceptId::from_uuid(uuid::Uuid::parse_str(entity).ok()?),
+                key: Box::new(self.sample_value(key, sample)?),
+            },
+            (CanonicalIr::Enum { symbol }, SampleIr::Enum { name, variant, .. }) => {
+                let member = self
+                    .members
+                    .get(name)?
+                    .iter()
+                    .find(|m| &m.variant == variant)?;
+                TypedValue::Enum {
+                    symbol: ApplicationSymbolId(symbol.clone()),
+                    wire: member.wire.clone(),
+                }
+            }
+            (CanonicalIr::List(element), SampleIr::List(items)) => TypedValue::List(
+                items
+                    .iter()
+                    .map(|item| self.sample_value(element, item))
+                    .collect::<Option<_>>()?,
+            ),
+            _ => return None,
+        })
+    }
+
+    fn record_sample(&self, record: &RecordIr) -> Option<RecordSampleIr> {
+        let fields = record
+            .fields
+            .iter()
+            .map(|f| Some((f.name.clone(), f.optional, self.field_sample(f)?)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(RecordSampleIr {
+            record: record.name.clone(),
+            fields,
+        })
+    }
+
+    fn field_sample(&self, field: &FieldIr) -> Option<SampleIr> {
+        match &field.ty {
+            TypeIr::List(element) => {
+                let min = field.checks.iter().find_map(|c| match c {
+                    CheckIr::MinItems(n) => Some(*n),
+                    _ => None,
+                });
+                let max = field.checks.iter().find_map(|c| match c {
+                    CheckIr::MaxItems(n) => Some(*n),
+                    _ => None,
+                });
+                let count = min.unwrap_or(1).max(1).min(max.unwrap_or(u32::MAX));
+                let item = self.element_sample(element, &field.checks)?;
+                Some(SampleIr::List(vec![item; count as usize]))
+            }
+            ty => self.element_sample(ty, &field.checks),
+        }
+    }
+
+    fn element_sample(&self, ty: &TypeIr, checks: &[CheckIr]) -> Option<SampleIr> {
+        if checks.iter().any(|c| matches!(c, CheckIr::Pattern(_))) {
+            return None;
+        }
+        Some(match ty {
+            TypeIr::Str => {
+                let min = checks.iter().find_map(|c| match c {
+                    CheckIr::MinLength(n) => Some(*n as usize),
+                    _ => None,
+                });
+                let max = checks.iter().find_map(|c| match c {
+                    CheckIr::MaxLength(n) => Some(*n as usize),
+                    _ => None,
+                });
+                let len = SAMPLE_TEXT
+                    .len()
+                    .max(min.unwrap_or(0))
+                    .min(max.unwrap_or(usize::MAX));
+                SampleIr::Str(SAMPLE_TEXT.chars().cycle().take(len).collect())
+            }
+            TypeIr::Int => {
+                let value = numeric_sample(checks, true)?;
+                SampleIr::Int(rust_decimal::prelude::ToPrimitive::to_i64(&value)?)
+            }
+            TypeIr::Decimal => SampleIr::Decimal(numeric_sample(checks, false)?.to_string()),
+            TypeIr::Bool => SampleIr::Bool(true),
+            TypeIr::Timestamp => SampleIr::Str(SAMPLE_TIMESTAMP.to_string()),
+            TypeIr::Uuid => SampleIr::Str(SAMPLE_UUID.to_string()),
+            TypeIr::Enum(name) => {
+                let member = self.members.get(name)?.first()?;
+                SampleIr::Enum {
+                    name: name.clone(),
+                    variant: member.variant.clone(),
+                    constant: member.constant.clone(),
+                }
+            }
+            TypeIr::List(_) => return None,
+        })
+    }
+}
+
+const SAMPLE_TEXT: &str = "sample";
+const SAMPLE_TIMESTAMP: &str = "2026-01-01T00:00:00Z";
+const SAMPLE_UUID: &str = "00000000-0000-4000-8000-000000000001";
+
+fn failure_kind_slug(kind: FailureKind) -> &'static str {
+    match kind {
+        FailureKind::InputValidation => "input_validation",
+        FailureKind::Policy => "policy",
+        FailureKind::MissingState => "missing_state",
+        FailureKind::IdempotencyConflict => "idempotency_conflict",
+        FailureKind::ConcurrencyConflict => "concurrency_conflict",
+    }
+}
+
+fn parse_bound(bound: &str) -> Option<rust_decimal::Decimal> {
+    bound.parse().ok()
+}
+
+/// A value inside the numeric bounds: the lower bound (nudged inside when
+/// exclusive), else 1, pulled under the upper bound. Decimals fall back to the
+/// midpoint of an open interval. `None` if the bounds admit no value.
+fn numeric_sample(checks: &[CheckIr], integral: bool) -> Option<rust_decimal::Decimal> {
+    use rust_decimal::Decimal;
+    let mut lower: Option<(Decimal, bool)> = None;
+    let mut upper: Option<(Decimal, bool)> = None;
+    for check in checks {
+        match check {
+            CheckIr::Min { bound, exclusive } => lower = Some((parse_bound(bound)?, *exclusive)),
+            CheckIr::Max { bound, exclusive } => upper = Some((parse_bound(bound)?, *exclusive)),
+            _ => {}
+        }
+    }
+    let inside = |v: Decimal| {
+        lower.map_or(true, |(b, excl)| if excl { v > b } else { v >= b })
+            && upper.map_or(true, |(b, excl)| if excl { v < b } else { v <= b })
+    };
+    let candidates: Vec<Decimal> = if integral {
+        let lo = lower.map(|(b, excl)| {
+            if excl {
+                b.floor() + Decimal::ONE
+            } else {
+                b.ceil()
+            }
+        });
+        let hi = upper.map(|(b, excl)| {
+            if excl {
+                b.ceil() - Decimal::ONE
+            } else {
+                b.floor()
+            }
+        });
+        vec![lo.unwrap_or(Decimal::ONE), hi.unwrap_or(Decimal::ONE)]
+    } else {
+        let lo = lower.map(|(b, excl)| if excl { b + Decimal::ONE } else { b });
+        let hi = upper.map(|(b, excl)| if excl { b - Decimal::ONE } else { b });
+        let mid = lower
+            .zip(upper)
+            .map(|((l, _), (h, _))| (l + h) / Decimal::TWO);
+        [lo, Some(Decimal::ONE), hi, mid]
+            .into_iter()
+            .flatten()
+            .collect()
+    };
+    candidates
+        .into_iter()
+        .find(|v| inside(*v))
+        .map(|v| v.normalize())
+}
+
+/// A value violating the first violable check of `field`.
+fn violation(field: &FieldIr, valid: &SampleIr) -> Option<SampleIr> {
+    use rust_decimal::Decimal;
+    field
+        .checks
+        .iter()
+        .find_map(|check| match (&field.ty, check, valid) {
+            (TypeIr::Str, CheckIr::MinLength(n), _) if *n > 0 => Some(SampleIr::Str(
+                SAMPLE_TEXT.chars().cycle().take(*n as usize - 1).collect(),
+            )),
+            (TypeIr::Str, CheckIr::MaxLength(n), _) => {
+                Some(SampleIr::Str("x".repeat(*n as usize + 1)))
+            }
+            (TypeIr::Int, CheckIr::Min { bound, exclusive }, _) => {
+                let b = parse_bound(bound)?;
+                let v = if *exclusive {
+                    b.floor()
+                } else {
+                    b.ceil() - Decimal::ONE
+                };
+                Some(SampleIr::Int(rust_decimal::prelude::ToPrimitive::to_i64(
+                    &v,
+                )?))
+            }
+            (TypeIr::Int, CheckIr::Max { bound, exclusive }, _) => {
+                let b = parse_bound(bound)?;
+                let v = if *exclusive {
+                    b.ceil()
+                } else {
+                    b.floor() + Decimal::ONE
+                };
+                Some(SampleIr::Int(rust_decimal::prelude::ToPrimitive::to_i64(
+                    &v,
+                )?))
+            }
+            (TypeIr::Decimal, CheckIr::Min { bound, exclusive }, _) => {
+                let b = parse_bound(bound)?;
+                let v = if *exclusive { b } else { b - Decimal::ONE };
+                Some(SampleIr::Decimal(v.normalize().to_string()))
+            }
+            (TypeIr::Decimal, CheckIr::Max { bound, exclusive }, _) => {
+                let b = parse_bound(bound)?;
+                let v = if *exclusive { b } else { b + Decimal::ONE };
+                Some(SampleIr::Decimal(v.normalize().to_string()))
+            }
+            (TypeIr::List(_), CheckIr::MinItems(n), SampleIr::List(items)) if *n > 0 => Some(
+                SampleIr::List(items.iter().take(*n as usize - 1).cloned().collect()),
+            ),
+            (TypeIr::List(_), CheckIr::MaxItems(n), SampleIr::List(items)) => {
+                let item = items.first()?.clone();
+                Some(SampleIr::List(vec![item; *n as usize + 1]))
+            }
+            _ => None,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -618,4 +1606,193 @@ Flow "R" @cqrs { "kind": "event" } from "A" to "B" quantity 1
         let agg = &ir.aggregates[0];
         assert!(agg.methods.is_empty(), "event-kind must mint no method");
     }
+
+    fn flagship_ir() -> DomainIr {
+        let (graph, contract) = flagship_fixture();
+        DomainIr::from_graph_and_contract(&graph, Some(&contract)).expect("IR builds")
+    }
+
+    #[test]
+    fn without_contract_has_no_application_slice() {
+        let g = parse_to_graph(&fixture_source()).expect("fixture parses");
+        let ir = DomainIr::from_graph_and_contract(&g, None).unwrap();
+        assert!(!ir.has_application());
+        assert_eq!(ir, ir_from_fixture());
+    }
+
+    #[test]
+    fn contract_records_and_enums() {
+        let ir = flagship_ir();
+        assert_eq!(ir.app_enums[0].name, "OrderStatus");
+        assert_eq!(ir.app_enums[0].members[0].constant, "PLACED");
+        assert_eq!(ir.app_enums[0].members[0].wire, "placed");
+        let names: Vec<&str> = ir.records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["PlaceOrderInput", "PlaceOrderOutput"]);
+        let input = &ir.records[0];
+        let total = input.fields.iter().find(|f| f.name == "total").unwrap();
+        assert_eq!(total.ty, TypeIr::Decimal);
+        assert_eq!(
+            total.checks,
+            [CheckIr::Min {
+                bound: "0".to_string(),
+                exclusive: true
+            }]
+        );
+        let client = input
+            .fields
+            .iter()
+            .find(|f| f.name == "client_order_id")
+            .unwrap();
+        assert_eq!(
+            client.checks,
+            [CheckIr::MinLength(1), CheckIr::MaxLength(64)]
+        );
+        assert_eq!(
+            ir.records[1].fields[1].ty,
+            TypeIr::Enum("OrderStatus".to_string())
+        );
+    }
+
+    #[test]
+    fn unresolved_pattern_constraint_is_an_error() {
+        let (graph, mut contract) = flagship_fixture();
+        contract.records[0].fields[0]
+            .constraints
+            .push(FieldConstraint::Pattern {
+                pattern: ConceptId::from_concept("p", "missing"),
+            });
+        let err = DomainIr::from_graph_and_contract(&graph, Some(&contract)).unwrap_err();
+        assert!(err.contains("unknown pattern"), "{err}");
+    }
+
+    #[test]
+    fn contract_operation_handler_ports_and_failures() {
+        let ir = flagship_ir();
+        let op = &ir.operations[0];
+        assert_eq!(op.name, "place_order");
+        assert_eq!(op.handler, "PlaceOrderHandler");
+        assert_eq!(op.port, "PlaceOrderPort");
+        assert_eq!(op.policies_port.as_deref(), Some("PlaceOrderPolicies"));
+        assert_eq!(
+            op.idempotency_port.as_deref(),
+            Some("PlaceOrderIdempotencyStore")
+        );
+        assert_eq!(op.state, "Order");
+        assert_eq!(op.effect, EffectIr::Creates);
+        let classes: Vec<&str> = op.failures.iter().map(|f| f.class_name.as_str()).collect();
+        assert_eq!(
+            classes,
+            [
+                "PlaceOrderInvalidOrderError",
+                "PlaceOrderOrderLimitExceededError",
+                "PlaceOrderIdempotencyConflictError"
+            ]
+        );
+        assert_eq!(
+            op.preconditions[0].method,
+            "allows_order_total_within_limit"
+        );
+        assert_eq!(op.preconditions[0].raise.variant, "OrderLimitExceeded");
+        assert_eq!(
+            op.on_invalid_input.as_ref().unwrap().variant,
+            "InvalidOrder"
+        );
+        // One declared failure covers both conflict kinds.
+        let idem = op.on_idempotency_conflict.as_ref().unwrap();
+        let conc = op.on_concurrency_conflict.as_ref().unwrap();
+        assert_eq!(idem.variant, conc.variant);
+        assert_eq!(
+            (idem.kind.as_str(), conc.kind.as_str()),
+            ("idempotency_conflict", "concurrency_conflict")
+        );
+        // Creates never looks state up.
+        assert!(op.on_missing_state.is_none());
+    }
+
+    #[test]
+    fn contract_samples_satisfy_and_violate_constraints() {
+        let ir = flagship_ir();
+        let op = &ir.operations[0];
+        let sample = op.sample_input.as_ref().unwrap();
+        let value = |name: &str| {
+            sample
+                .fields
+                .iter()
+                .find(|(n, _, _)| n == name)
+                .map(|(_, _, v)| v.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            value("client_order_id"),
+            SampleIr::Str("sample".to_string())
+        );
+        assert_eq!(value("total"), SampleIr::Decimal("1".to_string()));
+        assert_eq!(value("item_count"), SampleIr::Int(1));
+        // The first violable check: client_order_id min_length 1.
+        assert_eq!(
+            op.invalid_input,
+            Some(("client_order_id".to_string(), SampleIr::Str(String::new())))
+        );
+    }
+
+    #[test]
+    fn sample_fingerprint_matches_the_payload_checker() {
+        let (graph, contract) = flagship_fixture();
+        let ir = DomainIr::from_graph_and_contract(&graph, Some(&contract)).unwrap();
+        let total = ir.records[0]
+            .fields
+            .iter()
+            .find(|f| f.name == "total")
+            .unwrap();
+        assert!(matches!(total.canonical, CanonicalIr::Quantity { .. }));
+        // The sample as a caller would send it, scale and all.
+        let payload = serde_json::json!({
+            "order_id": "00000000-0000-4000-8000-000000000001",
+            "client_order_id": "sample",
+            "total": "1.00",
+            "item_count": 1,
+        });
+        let report = crate::application::validate_payload(
+            &graph,
+            &contract,
+            "PlaceOrderInput",
+            &payload,
+            &Default::default(),
+        )
+        .unwrap();
+        let typed = report.value.expect("payload types");
+        assert_eq!(
+            ir.operations[0].sample_fingerprint,
+            Some(crate::application::input_fingerprint(&typed))
+        );
+    }
+
+    #[test]
+    fn numeric_samples_respect_bounds() {
+        let bounds = |min: &str, min_excl: bool, max: &str, max_excl: bool| {
+            vec![
+                CheckIr::Min {
+                    bound: min.to_string(),
+                    exclusive: min_excl,
+                },
+                CheckIr::Max {
+                    bound: max.to_string(),
+                    exclusive: max_excl,
+                },
+            ]
+        };
+        let int = |checks: &[CheckIr]| numeric_sample(checks, true).map(|d| d.to_string());
+        let dec = |checks: &[CheckIr]| numeric_sample(checks, false).map(|d| d.to_string());
+        assert_eq!(int(&bounds("0", true, "10", false)).as_deref(), Some("1"));
+        assert_eq!(int(&bounds("5", false, "5", false)).as_deref(), Some("5"));
+        assert_eq!(int(&bounds("0.5", false, "0.9", false)), None);
+        assert_eq!(
+            dec(&bounds("0", true, "0.5", true)).as_deref(),
+            Some("0.25")
+        );
+        assert_eq!(
+            dec(&bounds("-3", false, "-1", false)).as_deref(),
+            Some("-3")
+        );
+    }
 }
@@ -1,11 +1,19 @@
 //! Python DDD/CQRS renderer. Consumes [`crate::projection::domain::ir::DomainIr`]
 //! and emits a complete, stdlib-only Python package up to the port boundary.
 //!
-//! `emit` = `DomainIr::from_graph` + pure render functions IR → Python source.
-//! This renderer never touches [`crate::graph::Graph`].
+//! `emit` = `DomainIr::from_graph_and_contract` + pure render functions IR →
+//! Python source. This renderer never touches [`crate::graph::Graph`].
+//!
+//! With an application contract it adds `application/records.py`,
+//! `application/operations.py` and `tests/test_operations.py`.
 
+use crate::application::ApplicationContract;
 use crate::graph::Graph;
-use crate::projection::domain::ir::DomainIr;
+use crate::projection::domain::ir::{
+    sentence, CanonicalIr, CheckIr, ConcurrencyIr, DomainIr, EffectIr, FieldIr, IdempotencyIr,
+    OperationIr, RaiseIr, RecordIr, RecordSampleIr, SampleIr, TypeIr,
+};
+use crate::projection::drift::generated_at;
 use crate::projection::ids::slug;
 use crate::projection::sink::ArtifactSink;
 use std::collections::BTreeMap;
@@ -15,13 +23,14 @@ pub fn emit(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    contract: Option<&ApplicationContract>,
     sink: &mut ArtifactSink,
 ) -> Result<Vec<String>, String> {
-    let ir = DomainIr::from_graph(graph)?;
+    let ir = DomainIr::from_graph_and_contract(graph, contract)?;
     let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
     let pkg = &ir.package_slug;
     let pkg_dir = format!("src/{pkg}_domain");
-    let files: Vec<(String, String)> = vec![
+    let mut files: Vec<(String, String)> = vec![
         ("pyproject.toml".to_string(), pyproject(&ir)),
         ("README.md".to_string(), readme(&ir, model_ref, &created_at)),
         (format!("{pkg_dir}/__init__.py"), pkg_init(&ir)),
@@ -46,6 +55,20 @@ pub fn emit(
         (format!("{pkg_dir}/ports/read_model.py"), read_model(&ir)),
         ("tests/test_domain_smoke.py".to_string(), smoke_test(&ir)),
     ];
+    if ir.has_application() {
+        files.extend([
+            (
+                format!("{pkg_dir}/application/__init__.py"),
+                "# Application layer: records, operation handlers and their ports.\n".to_string(),
+            ),
+            (format!("{pkg_dir}/application/records.py"), records(&ir)),
+            (
+                format!("{pkg_dir}/application/operations.py"),
+                operations(&ir),
+            ),
+            ("tests/test_operations.py".to_string(), operations_test(&ir)),
+        ]);
+    }
     let mut emitted = Vec::new();
     for (path, body) in &files {
         sink.write(path, body)?;
@@ -59,13 +82,14 @@ pub fn project_domain_python_in_memory(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    contract: Option<&ApplicationContract>,
 ) -> Result<BTreeMap<String, String>, String> {
     let mut map = BTreeMap::new();
     let mut sink = ArtifactSink::Memory {
         prefix: String::new(),
         map: &mut map,
     };
-    emit(graph, model_ref, created_at, &mut sink)?;
+    emit(graph, model_ref, created_at, contract, &mut sink)?;
     Ok(map)
 }
 
@@ -85,13 +109,13 @@ requires-python = \">=3.10\"\ndependencies = []\n\n\
 
 fn readme(ir: &DomainIr, model_ref: &str, created_at: &str) -> String {
     format!(
-        "# {ns} domain package\n\nGenerated by DomainForge from `{model_ref}` at {created_at}.\n\n\
+        "# {ns} domain package\n\nGenerated by DomainForge from `{model_ref}`.\n\n{created_at}\n\n\
 A complete, stdlib-only DDD/CQRS domain layer up to the port boundary (aggregates,\n\
 commands, events, value objects, repository/event/command bus ports). No infrastructure\n\
 adapters are included — implement the ports to wire the domain to persistence and messaging.\n",
         ns = ir.namespace,
         model_ref = model_ref,
-        created_at = created_at,
+        created_at = generated_at(created_at),
     )
 }
 
@@ -494,6 +518,661 @@ fn aggregate_unit(ir: &DomainIr, aggregate: &str) -> String {
         .unwrap_or_else(|| "units".to_string())
 }
 
+// ---------- application layer (records, operations, generated tests) ----------
+
+fn py_type(ty: &TypeIr) -> String {
+    match ty {
+        TypeIr::Str | TypeIr::Timestamp | TypeIr::Uuid => "str".to_string(),
+        TypeIr::Int => "int".to_string(),
+        TypeIr::Decimal => "Decimal".to_string(),
+        TypeIr::Bool => "bool".to_string(),
+        TypeIr::Enum(name) => name.clone(),
+        TypeIr::List(element) => format!("list[{}]", py_type(element)),
+    }
+}
+
+/// Python literal for a numeric bound compared against a value of `ty`.
+fn py_bound(ty: &TypeIr, bound: &str) -> String {
+    match ty {
+        TypeIr::Int if !bound.contains('.') => bound.to_string(),
+        _ => format!("Decimal(\"{bound}\")"),
+    }
+}
+
+/// Condition that holds when `value` violates `check`.
+fn py_violates(ty: &TypeIr, check: &CheckIr, value: &str) -> String {
+    let numeric = |op: &str, bound: &str| match ty {
+        TypeIr::Int if bound.contains('.') => {
+            format!("Decimal({value}) {op} {}", py_bound(ty, bound))
+        }
+        _ => format!("{value} {op} {}", py_bound(ty, bound)),
+    };
+    match check {
+        CheckIr::Min { bound, exclusive } => numeric(if *exclusive { "<=" } else { "<" }, bound),
+        CheckIr::Max { bound, exclusive } => numeric(if *exclusive { ">=" } else { ">" }, bound),
+        CheckIr::MinLength(n) | CheckIr::MinItems(n) => format!("len({value}) < {n}"),
+        CheckIr::MaxLength(n) | CheckIr::MaxItems(n) => format!("len({value}) > {n}"),
+        CheckIr::Pattern(regex) => format!("re.fullmatch({}, {value}) is None", py_str_lit(regex)),
+    }
+}
+
+/// `validate()` statements for one field, indented for a method body.
+fn py_field_checks(field: &FieldIr) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let value = format!("self.{}", field.name);
+    for check in field.checks.iter().filter(|c| c.is_collection()) {
+        lines.push(format!("if {}:", py_violates(&field.ty, check, &value)));
+        lines.push(format!(
+            "    errors.append(\"{}: {}\")",
+            field.name,
+            check.message()
+        ));
+    }
+    let element_checks: Vec<&CheckIr> =
+        field.checks.iter().filter(|c| !c.is_collection()).collect();
+    if !element_checks.is_empty() {
+        match &field.ty {
+            TypeIr::List(element) => {
+                lines.push(format!("for i, item in enumerate({value}):"));
+                for check in element_checks {
+                    lines.push(format!("    if {}:", py_violates(element, check, "item")));
+                    lines.push(format!(
+                        "        errors.append(f\"{}[{{i}}]: {}\")",
+                        field.name,
+                        check.message().replace('{', "{{").replace('}', "}}")
+                    ));
+                }
+            }
+            ty => {
+                for check in element_checks {
+                    lines.push(format!("if {}:", py_violates(ty, check, &value)));
+                    lines.push(format!(
+                        "    errors.append({})",
+                        py_str_lit(&format!("{}: {}", field.name, check.message()))
+                    ));
+                }
+            }
+        }
+    }
+    let (prefix, indent) = if field.optional && !lines.is_empty() {
+        (format!("        if {value} is not None:\n"), "            ")
+    } else {
+        (String::new(), "        ")
+    };
+    let mut s = prefix;
+    for line in lines {
+        s.push_str(indent);
+        s.push_str(&line);
+        s.push('\n');
+    }
+    s
+}
+
+fn records(ir: &DomainIr) -> String {
+    let uses_re = ir
+        .records
+        .iter()
+        .flat_map(|r| &r.fields)
+        .flat_map(|f| &f.checks)
+        .any(|c| matches!(c, CheckIr::Pattern(_)));
+    let fingerprints = ir.records.iter().any(|r| ir.fingerprints(&r.name));
+    let mut s = String::from("from __future__ import annotations\n\n");
+    if fingerprints {
+        s.push_str("import hashlib\nimport json\n");
+    }
+    if uses_re {
+        s.push_str("import re\n");
+    }
+    if fingerprints {
+        s.push_str("import unicodedata\n");
+    }
+    s.push_str(
+        "from dataclasses import dataclass\nfrom decimal import Decimal\nfrom enum import Enum\n",
+    );
+    for e in &ir.app_enums {
+        s.push_str(&format!("\n\nclass {}(Enum):\n", e.name));
+        for m in &e.members {
+            s.push_str(&format!("    {} = {}\n", m.constant, py_str_lit(&m.wire)));
+        }
+    }
+    for r in &ir.records {
+        s.push_str(&format!(
+            "\n\n@dataclass(frozen=True, kw_only=True)\nclass {}:\n    \"\"\"Application record.\"\"\"\n",
+            r.name
+        ));
+        for f in &r.fields {
+            if f.optional {
+                s.push_str(&format!(
+                    "    {}: {} | None = None\n",
+                    f.name,
+                    py_type(&f.ty)
+                ));
+            } else {
+                s.push_str(&format!("    {}: {}\n", f.name, py_type(&f.ty)));
+            }
+            if let Some(doc) = &f.doc {
+                s.push_str(&format!("    \"\"\"{}\"\"\"\n", sentence(doc)));
+            }
+        }
+        s.push_str("\n    def validate(self) -> list[str]:\n        \"\"\"Constraint violations as `<field>: <constraint>` (empty when valid).\"\"\"\n        errors: list[str] = []\n");
+        for f in &r.fields {
+            s.push_str(&py_field_checks(f));
+        }
+        s.push_str("        return errors\n");
+        if ir.fingerprints(&r.name) {
+            s.push_str(&py_fingerprint(r));
+        }
+    }
+    if fingerprints {
+        s.push_str(FINGERPRINT_HELPERS);
+    }
+    s
+}
+
+/// `fingerprint()` of an idempotent operation's input: the Reference §6
+/// canonical input fingerprint `application::input_fingerprint` computes.
+fn py_fingerprint(record: &RecordIr) -> String {
+    let mut fields: Vec<&FieldIr> = record.fields.iter().collect();
+    fields.sort_by(|a, b| a.wire_name.cmp(&b.wire_name));
+    let mut s = String::from("\n    def fingerprint(self) -> str:\n        \"\"\"Canonical input fingerprint: `sha256:` over the canonical JSON of the present fields.\"\"\"\n        fields: dict[str, object] = {}\n");
+    for f in fields {
+        let key = py_str_lit(&f.wire_name);
+        let value = format!("self.{}", f.name);
+        if f.optional {
+            s.push_str(&format!(
+                "        if {value} is not None:\n            fields[{key}] = {}\n",
+                py_canonical(&f.canonical, &value)
+            ));
+        } else {
+            s.push_str(&format!(
+                "        fields[{key}] = {}\n",
+                py_canonical(&f.canonical, &value)
+            ));
+        }
+    }
+    s.push_str("        return _fingerprint(fields)\n");
+    s
+}
+
+/// Expression building the canonical tagged value of `value`.
+fn py_canonical(canonical: &CanonicalIr, value: &str) -> String {
+    match canonical {
+        CanonicalIr::Scalar("string") => {
+            format!("_tagged(\"string\", unicodedata.normalize(\"NFC\", {value}))")
+        }
+        CanonicalIr::Scalar("uuid") => format!("_tagged(\"uuid\", {value}.lower())"),
+        CanonicalIr::Scalar("decimal") => {
+            format!("_tagged(\"decimal\", _canonical_decimal({value}))")
+        }
+        CanonicalIr::Scalar(kind) => format!("_tagged({}, {value})", py_str_lit(kind)),
+        CanonicalIr::Quantity { unit } => format!(
+            "_tagged(\"quantity\", {{\"base_value\": _canonical_decimal({value}), \"unit\": {}}})",
+            py_str_lit(unit)
+        ),
+        CanonicalIr::EntityRef { entity, key } => format!(
+            "_tagged(\"entity_ref\", {{\"entity\": {}, \"key\": {}}})",
+            py_str_lit(entity),
+            py_canonical(key, value)
+        ),
+        CanonicalIr::Enum { symbol } => format!(
+            "_tagged(\"enum\", {{\"symbol\": {}, \"wire\": {value}.value}})",
+            py_str_lit(symbol)
+        ),
+        CanonicalIr::List(element) => format!(
+            "_tagged(\"list\", [{} for item in {value}])",
+            py_canonical(element, "item")
+        ),
+    }
+}
+
+/// Canonical JSON and SHA-256 for `fingerprint()`.
+const FINGERPRINT_HELPERS: &str = r#"
+
+def _tagged(kind: str, data: object) -> dict[str, object]:
+    return {"kind": kind, "data": data}
+
+
+def _canonical_decimal(value: Decimal) -> str:
+    """No exponent, no trailing fractional zeros, "0" for zero."""
+    return "0" if value.is_zero() else format(value.normalize(), "f")
+
+
+def _fingerprint(fields: dict[str, object]) -> str:
+    text = json.dumps(fields, sort_keys=True, separators=(",", ":"), ensure_ascii=False)
+    return "sha256:" + hashlib.sha256(text.encode()).hexdigest()
+"#;
+
+/// Whether the state port may return nothing (missing state or a conflict).
+fn port_optional(op: &OperationIr) -> bool {
+    op.effect == EffectIr::Reads || op.on_concurrency_conflict.is_some()
+}
+
+fn port_method(op: &OperationIr) -> &'static str {
+    match op.effect {
+        EffectIr::Creates => "create",
+        EffectIr::Mutates => "mutate",
+        EffectIr::Reads => "read",
+    }
+}
+
+fn py_raise(raise: &RaiseIr, detail: &str) -> String {
+    format!(
+        "raise {}(FailureKind.{}, {detail})",
+        raise.class_name,
+        raise.kind.to_uppercase()
+    )
+}
+
+fn operations(ir: &DomainIr) -> String {
+    let mut s = String::from(
+        "from __future__ import annotations\n\nfrom abc import ABC, abstractmethod\nfrom enum import Enum\nfrom typing import Optional\n\nfrom ..domain.errors import DomainError\n",
+    );
+    let mut used: Vec<&str> = ir
+        .operations
+        .iter()
+        .flat_map(|op| [op.input.as_str(), op.output.as_str()])
+        .collect();
+    used.sort();
+    used.dedup();
+    if !used.is_empty() {
+        s.push_str(&format!("from .records import {}\n", used.join(", ")));
+    }
+    s.push_str("\n\nclass FailureKind(Enum):\n");
+    for kind in [
+        "input_validation",
+        "policy",
+        "missing_state",
+        "idempotency_conflict",
+        "concurrency_conflict",
+    ] {
+        s.push_str(&format!("    {} = \"{kind}\"\n", kind.to_uppercase()));
+    }
+    for op in &ir.operations {
+        s.push_str(&py_operation(op));
+    }
+    s
+}
+
+fn py_operation(op: &OperationIr) -> String {
+    let (input, output) = (&op.input, &op.output);
+    let mut s = format!(
+        "\n\nclass {err}(DomainError):\n    \"\"\"Declared failures of operation '{name}'.\"\"\"\n\n    code: str = \"\"\n    kinds: tuple[FailureKind, ...] = ()\n\n    def __init__(self, kind: FailureKind, detail: str) -> None:\n        super().__init__(f\"{{self.code}} ({{kind.value}}): {{detail}}\")\n        self.kind = kind\n        self.detail = detail\n",
+        err = op.error,
+        name = op.name,
+    );
+    for f in &op.failures {
+        let kinds: Vec<String> = f
+            .kinds
+            .iter()
+            .map(|k| format!("FailureKind.{}", k.to_uppercase()))
+            .collect();
+        let kinds = match kinds.as_slice() {
+            [single] => format!("{single},"),
+            _ => kinds.join(", "),
+        };
+        s.push_str(&format!(
+            "\n\nclass {cls}({err}):\n    \"\"\"{meaning}\"\"\"\n\n    code = \"{code}\"\n    kinds = ({kinds})\n",
+            cls = f.class_name,
+            err = op.error,
+            meaning = sentence(&f.meaning),
+            code = f.code,
+        ));
+    }
+
+    // State port.
+    let ret = if port_optional(op) {
+        format!("Optional[{output}]")
+    } else {
+        output.clone()
+    };
+    s.push_str(&format!(
+        "\n\nclass {port}(ABC):\n    \"\"\"State port for {state}.\"\"\"\n",
+        port = op.port,
+        state = op.state
+    ));
+    if op.effect == EffectIr::Mutates {
+        if let ConcurrencyIr::OptimisticVersion { .. } = op.concurrency {
+            s.push_str(&format!(
+                "\n    @abstractmethod\n    def current_version(self, command: {input}) -> Optional[int]:\n        \"\"\"Stored version of the {state}, or None when it does not exist.\"\"\"\n",
+                state = op.state
+            ));
+        } else {
+            s.push_str(&format!(
+                "\n    @abstractmethod\n    def exists(self, command: {input}) -> bool:\n        \"\"\"Whether the {state} addressed by the command exists.\"\"\"\n",
+                state = op.state
+            ));
+        }
+    }
+    let none_means = match (op.effect, &op.concurrency) {
+        (EffectIr::Reads, _) => format!(" Returns None when the {} does not exist.", op.state),
+        (_, ConcurrencyIr::UniqueKey { field }) => {
+            format!(" Returns None when `{field}` is already taken.")
+        }
+        (_, ConcurrencyIr::OptimisticVersion { .. }) => {
+            " Returns None when the version changed concurrently.".to_string()
+        }
+        _ => String::new(),
+    };
+    s.push_str(&format!(
+        "\n    @abstractmethod\n    def {method}(self, command: {input}) -> {ret}:\n        \"\"\"{verb} the {state}.{none_means}\"\"\"\n",
+        method = port_method(op),
+        verb = match op.effect {
+            EffectIr::Creates => "Create",
+            EffectIr::Mutates => "Mutate",
+            EffectIr::Reads => "Read",
+        },
+        state = op.state,
+    ));
+
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!(
+            "\n\nclass {policies}(ABC):\n    \"\"\"Precondition policies of operation '{}'.\"\"\"\n",
+            op.name
+        ));
+        for pre in &op.preconditions {
+            s.push_str(&format!(
+                "\n    @abstractmethod\n    def {method}(self, command: {input}) -> bool:\n        \"\"\"Whether policy '{policy}' allows the command.\"\"\"\n",
+                method = pre.method,
+                policy = pre.policy_name,
+            ));
+        }
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!(
+            "\n\nclass {store}(ABC):\n    \"\"\"Outcomes of completed '{name}' calls by idempotency key.\"\"\"\n\n    @abstractmethod\n    def lookup(self, key: str) -> Optional[tuple[str, {output}]]:\n        \"\"\"The (fingerprint, output) stored for key, if any.\"\"\"\n\n    @abstractmethod\n    def remember(self, key: str, fingerprint: str, output: {output}) -> None:\n        \"\"\"Store the outcome of a completed call.\"\"\"\n",
+            name = op.name,
+        ));
+    }
+
+    // Handler.
+    let mut params = vec![format!("port: {}", op.port)];
+    let mut assigns = vec!["        self._port = port\n".to_string()];
+    if let Some(policies) = &op.policies_port {
+        params.push(format!("policies: {policies}"));
+        assigns.push("        self._policies = policies\n".to_string());
+    }
+    if let Some(store) = &op.idempotency_port {
+        params.push(format!("idempotency: {store}"));
+        assigns.push("        self._idempotency = idempotency\n".to_string());
+    }
+    s.push_str(&format!(
+        "\n\nclass {handler}:\n    \"\"\"Operation '{name}' ({state}).\"\"\"\n\n    def __init__(self, {params}) -> None:\n{assigns}\n    def handle(self, command: {input}) -> {output}:\n        \"\"\"{intent}\"\"\"\n",
+        handler = op.handler,
+        name = op.name,
+        state = op.state,
+        params = params.join(", "),
+        assigns = assigns.concat(),
+        intent = sentence(&op.intent),
+    ));
+    if let Some(raise) = &op.on_invalid_input {
+        s.push_str(&format!(
+            "        violations = command.validate()\n        if violations:\n            {}\n",
+            py_raise(raise, "\"; \".join(violations)")
+        ));
+    }
+    for pre in &op.preconditions {
+        s.push_str(&format!(
+            "        if not self._policies.{}(command):\n            {}\n",
+            pre.method,
+            py_raise(&pre.raise, &py_str_lit(&pre.policy_name))
+        ));
+    }
+    if let (IdempotencyIr::KeyedBy { field }, Some(raise)) =
+        (&op.idempotency, &op.on_idempotency_conflict)
+    {
+        s.push_str(&format!(
+            "        key = str(command.{field})\n        fingerprint = command.fingerprint()\n        replay = self._idempotency.lookup(key)\n        if replay is not None:\n            if replay[0] != fingerprint:\n                {}\n            return replay[1]\n",
+            py_raise(raise, "key")
+        ));
+    }
+    let missing = op
+        .on_missing_state
+        .as_ref()
+        .map(|r| py_raise(r, &format!("\"{} not found\"", op.state)));
+    let conflict = op
+        .on_concurrency_conflict
+        .as_ref()
+        .map(|r| py_raise(r, &py_str_lit(&op.concurrency.conflict_detail())));
+    if op.effect == EffectIr::Mutates {
+        if let (ConcurrencyIr::OptimisticVersion { field }, Some(missing), Some(conflict)) =
+            (&op.concurrency, &missing, &conflict)
+        {
+            s.push_str(&format!(
+                "        version = self._port.current_version(command)\n        if version is None:\n            {missing}\n        if version != command.{field}:\n            {conflict}\n"
+            ));
+        } else if let Some(missing) = &missing {
+            s.push_str(&format!(
+                "        if not self._port.exists(command):\n            {missing}\n"
+            ));
+        }
+    }
+    s.push_str(&format!(
+        "        output = self._port.{}(command)\n",
+        port_method(op)
+    ));
+    let on_none = match op.effect {
+        EffectIr::Reads => missing,
+        _ => conflict,
+    };
+    if let Some(on_none) = on_none {
+        s.push_str(&format!(
+            "        if output is None:\n            {on_none}\n"
+        ));
+    }
+    if op.idempotency_port.is_some() {
+        s.push_str("        self._idempotency.remember(key, fingerprint, output)\n");
+    }
+    s.push_str("        return output\n");
+    s
+}
+
+fn py_sample(sample: &SampleIr) -> String {
+    match sample {
+        SampleIr::Str(v) => py_str_lit(v),
+        SampleIr::Int(v) => v.to_string(),
+        SampleIr::Decimal(v) => format!("Decimal(\"{v}\")"),
+        SampleIr::Bool(v) => if *v { "True" } else { "False" }.to_string(),
+        SampleIr::Enum { name, constant, .. } => format!("{name}.{constant}"),
+        SampleIr::List(items) => format!(
+            "[{}]",
+            items.iter().map(py_sample).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn py_record_sample(sample: &RecordSampleIr) -> String {
+    let args: Vec<String> = sample
+        .fields
+        .iter()
+        .map(|(name, _, value)| format!("{name}={}", py_sample(value)))
+        .collect();
+    format!("{}({})", sample.record, args.join(", "))
+}
+
+fn operations_test(ir: &DomainIr) -> String {
+    let pkg = &ir.package_slug;
+    let mut record_names: Vec<&str> = ir
+        .app_enums
+        .iter()
+        .map(|e| e.name.as_str())
+        .chain(ir.records.iter().map(|r| r.name.as_str()))
+        .collect();
+    record_names.sort();
+    let mut op_names: Vec<String> = vec!["FailureKind".to_string()];
+    let mut fakes = String::new();
+    let mut tests = String::new();
+    for op in &ir.operations {
+        let (Some(input), Some(output)) = (&op.sample_input, &op.sample_output) else {
+            continue;
+        };
+        op_names.push(op.handler.clone());
+        op_names.push(op.port.clone());
+        op_names.push(op.error.clone());
+        op_names.extend(op.failures.iter().map(|f| f.class_name.clone()));
+        op_names.extend(op.policies_port.clone());
+        op_names.extend(op.idempotency_port.clone());
+        fakes.push_str(&py_fakes(op, input, output));
+        tests.push_str(&py_tests(op));
+    }
+    op_names.sort();
+    op_names.dedup();
+    let body = if tests.is_empty() {
+        "    pass\n".to_string()
+    } else {
+        tests
+    };
+    format!(
+        "from __future__ import annotations\n\nimport unittest\nfrom dataclasses import replace\nfrom decimal import Decimal\nfrom typing import Optional\n\nfrom {pkg}_domain.application.operations import {ops}\nfrom {pkg}_domain.application.records import {records}\n{fakes}\n\nclass OperationsTest(unittest.TestCase):\n{body}\n\nif __name__ == \"__main__\":\n    unittest.main()\n",
+        ops = op_names.join(", "),
+        records = record_names.join(", "),
+    )
+}
+
+fn py_fakes(op: &OperationIr, input: &RecordSampleIr, output: &RecordSampleIr) -> String {
+    let upper = op.name.to_uppercase();
+    let (in_ty, out_ty) = (&op.input, &op.output);
+    let ret = if port_optional(op) {
+        format!("Optional[{out_ty}]")
+    } else {
+        out_ty.clone()
+    };
+    let mut s = format!(
+        "\n\n{upper}_INPUT = {}\n{upper}_OUTPUT = {}\n",
+        py_record_sample(input),
+        py_record_sample(output)
+    );
+    s.push_str(&format!(
+        "\n\nclass Fake{port}({port}):\n    def __init__(self) -> None:\n        self.output: {ret} = {upper}_OUTPUT\n        self.calls = 0\n",
+        port = op.port
+    ));
+    let optimistic = match &op.concurrency {
+        ConcurrencyIr::OptimisticVersion { field } if op.effect == EffectIr::Mutates => Some(field),
+        _ => None,
+    };
+    if op.effect == EffectIr::Mutates {
+        match optimistic {
+            Some(field) => s.push_str(&format!(
+                "        self.version: Optional[int] = {upper}_INPUT.{field}\n\n    def current_version(self, command: {in_ty}) -> Optional[int]:\n        return self.version\n"
+            )),
+            None => s.push_str(&format!(
+                "        self.present = True\n\n    def exists(self, command: {in_ty}) -> bool:\n        return self.present\n"
+            )),
+        }
+    }
+    s.push_str(&format!(
+        "\n    def {method}(self, command: {in_ty}) -> {ret}:\n        self.calls += 1\n        return self.output\n",
+        method = port_method(op)
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!(
+            "\n\nclass Fake{policies}({policies}):\n    def __init__(self) -> None:\n        self.denied: set[str] = set()\n"
+        ));
+        for pre in &op.preconditions {
+            s.push_str(&format!(
+                "\n    def {method}(self, command: {in_ty}) -> bool:\n        return {policy} not in self.denied\n",
+                method = pre.method,
+                policy = py_str_lit(&pre.policy_name)
+            ));
+        }
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!(
+            "\n\nclass Fake{store}({store}):\n    def __init__(self) -> None:\n        self.entries: dict[str, tuple[str, {out_ty}]] = {{}}\n\n    def lookup(self, key: str) -> Optional[tuple[str, {out_ty}]]:\n        return self.entries.get(key)\n\n    def remember(self, key: str, fingerprint: str, output: {out_ty}) -> None:\n        self.entries[key] = (fingerprint, output)\n"
+        ));
+    }
+    let mut args = vec!["self.port".to_string()];
+    s.push_str(&format!(
+        "\n\nclass {handler}Fixture:\n    def __init__(self) -> None:\n        self.port = Fake{port}()\n",
+        handler = op.handler,
+        port = op.port
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!("        self.policies = Fake{policies}()\n"));
+        args.push("self.policies".to_string());
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!("        self.store = Fake{store}()\n"));
+        args.push("self.store".to_string());
+    }
+    s.push_str(&format!(
+        "        self.handler = {}({})\n",
+        op.handler,
+        args.join(", ")
+    ));
+    s
+}
+
+fn py_expect(op: &OperationIr, raise: &RaiseIr, command: &str) -> String {
+    format!(
+        "        with self.assertRaises({cls}) as ctx:\n            f.handler.handle({command})\n        self.assertEqual(ctx.exception.kind, FailureKind.{kind})\n        self.assertIsInstance(ctx.exception, {err})\n",
+        cls = raise.class_name,
+        kind = raise.kind.to_uppercase(),
+        err = op.error,
+    )
+}
+
+fn py_tests(op: &OperationIr) -> String {
+    let upper = op.name.to_uppercase();
+    let name = &op.name;
+    let fixture = format!("        f = {}Fixture()\n", op.handler);
+    let mut s = format!(
+        "    def test_{name}_succeeds(self) -> None:\n{fixture}        self.assertEqual(f.handler.handle({upper}_INPUT), {upper}_OUTPUT)\n        self.assertEqual(f.port.calls, 1)\n"
+    );
+    if let (Some(raise), Some((field, value))) = (&op.on_invalid_input, &op.invalid_input) {
+        s.push_str(&format!(
+            "\n    def test_{name}_rejects_invalid_input(self) -> None:\n{fixture}        bad = replace({upper}_INPUT, {field}={value})\n        self.assertNotEqual(bad.validate(), [])\n{expect}        self.assertEqual(f.port.calls, 0)\n",
+            value = py_sample(value),
+            expect = py_expect(op, raise, "bad"),
+        ));
+    }
+    for pre in &op.preconditions {
+        s.push_str(&format!(
+            "\n    def test_{name}_denied_by_{policy}(self) -> None:\n{fixture}        f.policies.denied.add({lit})\n{expect}        self.assertEqual(f.port.calls, 0)\n",
+            policy = slug(&pre.policy_name),
+            lit = py_str_lit(&pre.policy_name),
+            expect = py_expect(op, &pre.raise, &format!("{upper}_INPUT")),
+        ));
+    }
+    if let (IdempotencyIr::KeyedBy { field }, Some(raise)) =
+        (&op.idempotency, &op.on_idempotency_conflict)
+    {
+        s.push_str(&format!(
+            "\n    def test_{name}_replays_idempotently(self) -> None:\n{fixture}        first = f.handler.handle({upper}_INPUT)\n        self.assertEqual(f.handler.handle({upper}_INPUT), first)\n        self.assertEqual(f.port.calls, 1)\n\n    def test_{name}_rejects_conflicting_replay(self) -> None:\n{fixture}        f.store.remember(str({upper}_INPUT.{field}), \"other\", {upper}_OUTPUT)\n{expect}        self.assertEqual(f.port.calls, 0)\n",
+            expect = py_expect(op, raise, &format!("{upper}_INPUT")),
+        ));
+    }
+    if let Some(raise) = &op.on_concurrency_conflict {
+        let setup = match &op.concurrency {
+            ConcurrencyIr::OptimisticVersion { field } if op.effect == EffectIr::Mutates => {
+                format!("        f.port.version = {upper}_INPUT.{field} + 1\n")
+            }
+            _ => "        f.port.output = None\n".to_string(),
+        };
+        s.push_str(&format!(
+            "\n    def test_{name}_concurrency_conflict(self) -> None:\n{fixture}{setup}{expect}",
+            expect = py_expect(op, raise, &format!("{upper}_INPUT")),
+        ));
+    }
+    if let Some(raise) = &op.on_missing_state {
+        let setup = match (&op.effect, &op.concurrency) {
+            (EffectIr::Reads, _) => "        f.port.output = None\n",
+            (_, ConcurrencyIr::OptimisticVersion { .. }) => "        f.port.version = None\n",
+            _ => "        f.port.present = False\n",
+        };
+        s.push_str(&format!(
+            "\n    def test_{name}_missing_state(self) -> None:\n{fixture}{setup}{expect}",
+            expect = py_expect(op, raise, &format!("{upper}_INPUT")),
+        ));
+    }
+    if let Some(fingerprint) = &op.sample_fingerprint {
+        s.push_str(&format!(
+            "\n    def test_{name}_fingerprints_canonical_input(self) -> None:\n        self.assertEqual({upper}_INPUT.fingerprint(), {})\n",
+            py_str_lit(fingerprint)
+        ));
+    }
+    s
+}
+
 /// Quote a string literal for Python source.
 fn py_str_lit(s: &str) -> String {
     format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
@@ -508,7 +1187,7 @@ mod tests {
 
     fn project(source: &str) -> BTreeMap<String, String> {
         let graph = parse_to_graph(source).expect("parses");
-        project_domain_python_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()))
+        project_domain_python_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()), None)
             .expect("projects")
     }
 
@@ -568,4 +1247,35 @@ mod tests {
         let pp = &files["pyproject.toml"];
         assert!(pp.contains("dependencies = []"), "must declare zero deps");
     }
+
+    #[test]
+    fn contract_operations_emit_handlers_ports_and_tests() {
+        let (graph, contract) = crate::projection::domain::ir::flagship_fixture();
+        let files = project_domain_python_in_memory(
+            &graph,
+            "command-write.sea",
+            Some(FIXED_TS.to_string()),
+            Some(&contract),
+        )
+        .expect("projects");
+        let ops = &files["src/flagship_orders_domain/application/operations.py"];
+        assert!(ops.contains("class PlaceOrderError(DomainError):"));
+        assert!(ops.contains("class PlaceOrderOrderLimitExceededError(PlaceOrderError):"));
+        assert!(ops
+            .contains("def create(self, command: PlaceOrderInput) -> Optional[PlaceOrderOutput]:"));
+        assert!(ops.contains("if not self._policies.allows_order_total_within_limit(command):"));
+        assert!(ops.contains("replay = self._idempotency.lookup(key)"));
+        assert!(ops.contains("fingerprint = command.fingerprint()"));
+        let records = &files["src/flagship_orders_domain/application/records.py"];
+        assert!(records.contains("if self.total <= Decimal(\"0\"):"));
+        assert!(records.contains("errors.append(\"item_count: min 1\")"));
+        assert!(records.contains(
+            "fields[\"total\"] = _tagged(\"quantity\", {\"base_value\": _canonical_decimal(self.total), "
+        ));
+        let tests = &files["tests/test_operations.py"];
+        assert!(tests.contains("def test_place_order_rejects_invalid_input(self) -> None:"));
+        assert!(tests.contains("def test_place_order_rejects_conflicting_replay(self) -> None:"));
+        assert!(tests.contains("def test_place_order_concurrency_conflict(self) -> None:"));
+        assert!(tests.contains("def test_place_order_fingerprints_canonical_input(self) -> None:"));
+    }
 }
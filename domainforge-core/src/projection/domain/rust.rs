@@ -1,10 +1,18 @@
 //! Rust DDD/CQRS renderer. Consumes [`crate::projection::domain::ir::DomainIr`]
 //! and emits a complete, zero-dependency Rust crate up to the port boundary.
-//! `emit` = `DomainIr::from_graph` + pure render functions IR → Rust source.
-//! This renderer never touches [`crate::graph::Graph`].
+//! `emit` = `DomainIr::from_graph_and_contract` + pure render functions IR →
+//! Rust source. This renderer never touches [`crate::graph::Graph`].
+//!
+//! With an application contract it adds `src/records.rs`, `src/operations.rs`
+//! and `tests/operations.rs`.
 
+use crate::application::ApplicationContract;
 use crate::graph::Graph;
-use crate::projection::domain::ir::DomainIr;
+use crate::projection::domain::ir::{
+    sentence, CanonicalIr, CheckIr, ConcurrencyIr, DomainIr, EffectIr, FieldIr, IdempotencyIr,
+    OperationIr, RaiseIr, RecordIr, RecordSampleIr, SampleIr, TypeIr,
+};
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
 use std::collections::BTreeMap;
 
@@ -12,11 +20,12 @@ pub fn emit(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    contract: Option<&ApplicationContract>,
     sink: &mut ArtifactSink,
 ) -> Result<Vec<String>, String> {
-    let ir = DomainIr::from_graph(graph)?;
+    let ir = DomainIr::from_graph_and_contract(graph, contract)?;
     let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
-    let files: Vec<(String, String)> = vec![
+    let mut files: Vec<(String, String)> = vec![
         ("Cargo.toml".to_string(), cargo_toml(&ir)),
         ("README.md".to_string(), readme(&ir, model_ref, &created_at)),
         ("src/lib.rs".to_string(), lib_rs(&ir)),
@@ -31,6 +40,13 @@ pub fn emit(
         ("src/container.rs".to_string(), container(&ir)),
         ("tests/smoke.rs".to_string(), smoke(&ir)),
     ];
+    if ir.has_application() {
+        files.extend([
+            ("src/records.rs".to_string(), records(&ir)),
+            ("src/operations.rs".to_string(), operations(&ir)),
+            ("tests/operations.rs".to_string(), operations_test(&ir)),
+        ]);
+    }
     let mut emitted = Vec::new();
     for (path, body) in &files {
         sink.write(path, body)?;
@@ -43,13 +59,14 @@ pub fn project_domain_rust_in_memory(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    contract: Option<&ApplicationContract>,
 ) -> Result<BTreeMap<String, String>, String> {
     let mut map = BTreeMap::new();
     let mut sink = ArtifactSink::Memory {
         prefix: String::new(),
         map: &mut map,
     };
-    emit(graph, model_ref, created_at, &mut sink)?;
+    emit(graph, model_ref, created_at, contract, &mut sink)?;
     Ok(map)
 }
 
@@ -65,12 +82,12 @@ fn cargo_toml(ir: &DomainIr) -> String {
 
 fn readme(ir: &DomainIr, model_ref: &str, created_at: &str) -> String {
     format!(
-        "# {ns} domain crate\n\nGenerated by DomainForge from `{model_ref}` at {created_at}.\n\n\
+        "# {ns} domain crate\n\nGenerated by DomainForge from `{model_ref}`.\n\n{created_at}\n\n\
 A complete, zero-dependency DDD/CQRS domain layer (Rust) up to the port boundary.\n\
 Validate with `cargo check` and `cargo test`.\n",
         ns = ir.namespace,
         model_ref = model_ref,
-        created_at = created_at,
+        created_at = generated_at(created_at),
     )
 }
 
@@ -86,7 +103,11 @@ pub mod value_objects;\npub mod roles;\npub mod entities;\npub mod errors;\n",
     if !ir.events.is_empty() {
         s.push_str("pub mod events;\n");
     }
-    s.push_str("pub mod aggregates;\npub mod ports;\npub mod container;\n\n");
+    s.push_str("pub mod aggregates;\npub mod ports;\npub mod container;\n");
+    if ir.has_application() {
+        s.push_str("pub mod records;\npub mod operations;\n");
+    }
+    s.push('\n');
     s.push_str("pub use value_objects::Quantity;\npub use roles::Role;\npub use errors::DomainError;\npub use container::Container;\n");
     s
 }
@@ -435,6 +456,936 @@ fn smoke(ir: &DomainIr) -> String {
     s
 }
 
+// ---------- application layer (records, operations, generated tests) ----------
+
+fn rs_type(ty: &TypeIr) -> String {
+    match ty {
+        // Decimals travel as canonical strings (zero-dep: no decimal crate).
+        TypeIr::Str | TypeIr::Timestamp | TypeIr::Uuid | TypeIr::Decimal => "String".to_string(),
+        TypeIr::Int => "i64".to_string(),
+        TypeIr::Bool => "bool".to_string(),
+        TypeIr::Enum(name) => name.clone(),
+        TypeIr::List(element) => format!("Vec<{}>", rs_type(element)),
+    }
+}
+
+/// Rust string literal (Debug escaping is valid Rust syntax).
+fn rs_str_lit(s: &str) -> String {
+    format!("{s:?}")
+}
+
+/// Condition that holds when `value` (a place expression) violates `check`.
+fn rs_violates(ty: &TypeIr, check: &CheckIr, value: &str) -> String {
+    let numeric = |op: &str, bound: &str| match ty {
+        TypeIr::Decimal => format!("decimal(&{value}).map_or(true, |v| !(v {op} {bound}_f64))"),
+        TypeIr::Int if bound.contains('.') => format!("!(({value} as f64) {op} {bound}_f64)"),
+        _ => format!("!({value} {op} {bound})"),
+    };
+    match check {
+        CheckIr::Min { bound, exclusive } => numeric(if *exclusive { ">" } else { ">=" }, bound),
+        CheckIr::Max { bound, exclusive } => numeric(if *exclusive { "<" } else { "<=" }, bound),
+        CheckIr::MinLength(n) => format!("{value}.chars().count() < {n}"),
+        CheckIr::MaxLength(n) => format!("{value}.chars().count() > {n}"),
+        CheckIr::MinItems(n) => format!("{value}.len() < {n}"),
+        CheckIr::MaxItems(n) => format!("{value}.len() > {n}"),
+        // Zero-dep: like the pattern value objects, only empty text is rejected.
+        CheckIr::Pattern(_) => format!("{value}.is_empty()"),
+    }
+}
+
+fn rs_field_checks(field: &FieldIr) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let value = if field.optional {
+        "*value".to_string()
+    } else {
+        format!("self.{}", field.name)
+    };
+    let push = |lines: &mut Vec<String>, cond: String, message: String| {
+        lines.push(format!("if {cond} {{"));
+        lines.push(format!("    errors.push({message});"));
+        lines.push("}".to_string());
+    };
+    for check in field.checks.iter().filter(|c| c.is_collection()) {
+        push(
+            &mut lines,
+            rs_violates(&field.ty, check, &value),
+            format!(
+                "{}.to_string()",
+                rs_str_lit(&format!("{}: {}", field.name, check.message()))
+            ),
+        );
+    }
+    let element_checks: Vec<&CheckIr> =
+        field.checks.iter().filter(|c| !c.is_collection()).collect();
+    if !element_checks.is_empty() {
+        match &field.ty {
+            TypeIr::List(element) => {
+                lines.push(format!("for (i, item) in {value}.iter().enumerate() {{"));
+                let mut inner = Vec::new();
+                for check in element_checks {
+                    push(
+                        &mut inner,
+                        rs_violates(element, check, "*item"),
+                        format!(
+                            "format!(\"{}[{{i}}]: {{}}\", {})",
+                            field.name,
+                            rs_str_lit(&check.message())
+                        ),
+                    );
+                }
+                lines.extend(inner.into_iter().map(|l| format!("    {l}")));
+                lines.push("}".to_string());
+            }
+            ty => {
+                for check in element_checks {
+                    push(
+                        &mut lines,
+                        rs_violates(ty, check, &value),
+                        format!(
+                            "{}.to_string()",
+                            rs_str_lit(&format!("{}: {}", field.name, check.message()))
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    let mut s = String::new();
+    if field.optional && !lines.is_empty() {
+        s.push_str(&format!(
+            "        if let Some(value) = &self.{} {{\n",
+            field.name
+        ));
+        for line in lines {
+            s.push_str(&format!("            {line}\n"));
+        }
+        s.push_str("        }\n");
+    } else {
+        for line in lines {
+            s.push_str(&format!("        {line}\n"));
+        }
+    }
+    s
+}
+
+fn records(ir: &DomainIr) -> String {
+    let mut s = String::from("//! Application records and enums.\n");
+    let uses_decimal = ir.records.iter().flat_map(|r| &r.fields).any(|f| {
+        let element = match &f.ty {
+            TypeIr::List(element) => element.as_ref(),
+            ty => ty,
+        };
+        *element == TypeIr::Decimal
+            && f.checks
+                .iter()
+                .any(|c| matches!(c, CheckIr::Min { .. } | CheckIr::Max { .. }))
+    });
+    for e in &ir.app_enums {
+        s.push_str(&format!(
+            "\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum {} {{\n",
+            e.name
+        ));
+        for m in &e.members {
+            s.push_str(&format!("    {},\n", m.variant));
+        }
+        s.push_str(&format!(
+            "}}\n\nimpl {} {{\n    /// Wire value.\n    pub fn wire(&self) -> &'static str {{\n        match self {{\n",
+            e.name
+        ));
+        for m in &e.members {
+            s.push_str(&format!(
+                "            Self::{} => {},\n",
+                m.variant,
+                rs_str_lit(&m.wire)
+            ));
+        }
+        s.push_str("        }\n    }\n}\n");
+    }
+    for r in &ir.records {
+        s.push_str(&format!(
+            "\n#[derive(Debug, Clone, PartialEq)]\npub struct {} {{\n",
+            r.name
+        ));
+        for f in &r.fields {
+            if let Some(doc) = &f.doc {
+                s.push_str(&format!("    /// {}\n", sentence(doc)));
+            }
+            let ty = rs_type(&f.ty);
+            if f.optional {
+                s.push_str(&format!("    pub {}: Option<{ty}>,\n", f.name));
+            } else {
+                s.push_str(&format!("    pub {}: {ty},\n", f.name));
+            }
+        }
+        let checks: String = r.fields.iter().map(rs_field_checks).collect();
+        if checks.is_empty() {
+            s.push_str(&format!(
+                "}}\n\nimpl {} {{\n    /// Constraint violations as `<field>: <constraint>` (empty when valid).\n    pub fn validate(&self) -> Vec<String> {{\n        Vec::new()\n    }}\n}}\n",
+                r.name
+            ));
+        } else {
+            s.push_str(&format!(
+                "}}\n\nimpl {} {{\n    /// Constraint violations as `<field>: <constraint>` (empty when valid).\n    pub fn validate(&self) -> Vec<String> {{\n        let mut errors = Vec::new();\n{checks}        errors\n    }}\n}}\n",
+                r.name
+            ));
+        }
+        if ir.fingerprints(&r.name) {
+            s.push_str(&rs_fingerprint(r));
+        }
+    }
+    if ir.records.iter().any(|r| ir.fingerprints(&r.name)) {
+        s.push_str(FINGERPRINT_HELPERS);
+    }
+    if uses_decimal {
+        s.push_str("\n/// Numeric value of a canonical decimal string (`None` when malformed).\nfn decimal(value: &str) -> Option<f64> {\n    value.parse().ok()\n}\n");
+    }
+    s
+}
+
+/// `fingerprint()` of an idempotent operation's input: the Reference §6
+/// canonical input fingerprint `application::input_fingerprint` computes.
+fn rs_fingerprint(record: &RecordIr) -> String {
+    let mut fields: Vec<&FieldIr> = record.fields.iter().collect();
+    fields.sort_by(|a, b| a.wire_name.cmp(&b.wire_name));
+    let mut s = format!(
+        "\nimpl {} {{\n    /// Canonical input fingerprint: `sha256:` over the canonical JSON of the\n    /// present fields. Strings are hashed as given, so pass NFC text.\n    pub fn fingerprint(&self) -> String {{\n        let mut fields = Vec::new();\n",
+        record.name
+    );
+    for f in fields {
+        let key = rs_str_lit(&f.wire_name);
+        if f.optional {
+            s.push_str(&format!(
+                "        if let Some(value) = &self.{} {{\n            fields.push(({key}, {}));\n        }}\n",
+                f.name,
+                rs_canonical(&f.canonical, "value")
+            ));
+        } else {
+            s.push_str(&format!(
+                "        fields.push(({key}, {}));\n",
+                rs_canonical(&f.canonical, &format!("self.{}", f.name))
+            ));
+        }
+    }
+    s.push_str("        sha256(object(&fields).as_bytes())\n    }\n}\n");
+    s
+}
+
+/// Expression rendering the canonical tagged JSON of the place `value`.
+fn rs_canonical(canonical: &CanonicalIr, value: &str) -> String {
+    match canonical {
+        CanonicalIr::Scalar(kind @ ("int" | "bool")) => {
+            format!("tagged({kind:?}, {value}.to_string())")
+        }
+        CanonicalIr::Scalar("decimal") => {
+            format!("tagged(\"decimal\", json_string(&canonical_decimal(&{value})))")
+        }
+        CanonicalIr::Scalar("uuid") => {
+            format!("tagged(\"uuid\", json_string(&{value}.to_lowercase()))")
+        }
+        CanonicalIr::Scalar(kind) => format!("tagged({kind:?}, json_string(&{value}))"),
+        CanonicalIr::Quantity { unit } => format!(
+            "tagged(\"quantity\", object(&[(\"base_value\", json_string(&canonical_decimal(&{value}))), (\"unit\", json_string({}))]))",
+            rs_str_lit(unit)
+        ),
+        CanonicalIr::EntityRef { entity, key } => format!(
+            "tagged(\"entity_ref\", object(&[(\"entity\", json_string({})), (\"key\", {})]))",
+            rs_str_lit(entity),
+            rs_canonical(key, value)
+        ),
+        CanonicalIr::Enum { symbol } => format!(
+            "tagged(\"enum\", object(&[(\"symbol\", json_string({})), (\"wire\", json_string({value}.wire()))]))",
+            rs_str_lit(symbol)
+        ),
+        CanonicalIr::List(element) => format!(
+            "tagged(\"list\", format!(\"[{{}}]\", {value}.iter().map(|item| {}).collect::<Vec<_>>().join(\",\")))",
+            rs_canonical(element, "item")
+        ),
+    }
+}
+
+/// Canonical JSON and SHA-256 for `fingerprint()`, kept in the crate so it
+/// stays zero-dependency.
+const FINGERPRINT_HELPERS: &str = r#"
+/// Canonical JSON of a typed value: `{"data":<data>,"kind":"<kind>"}`.
+fn tagged(kind: &str, data: String) -> String {
+    object(&[("data", data), ("kind", json_string(kind))])
+}
+
+/// Canonical JSON object of members already in bytewise key order.
+fn object(members: &[(&str, String)]) -> String {
+    let members: Vec<String> = members
+        .iter()
+        .map(|(key, value)| format!("{}:{value}", json_string(key)))
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// JSON string literal, escaped as canonical JSON escapes it.
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Canonical decimal: no exponent, no trailing fractional zeros, `0` for zero.
+fn canonical_decimal(value: &str) -> String {
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    let int = match int.trim_start_matches('0') {
+        "" => "0",
+        int => int,
+    };
+    match frac.trim_end_matches('0') {
+        "" if int == "0" => "0".to_string(),
+        "" => format!("{sign}{int}"),
+        frac => format!("{sign}{int}.{frac}"),
+    }
+}
+
+/// `sha256:<hex>` of `data` (FIPS 180-4).
+fn sha256(data: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for (k, w) in K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (state, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *state = state.wrapping_add(v);
+        }
+    }
+    let hex: String = h.iter().map(|word| format!("{word:08x}")).collect();
+    format!("sha256:{hex}")
+}
+"#;
+
+fn port_optional(op: &OperationIr) -> bool {
+    op.effect == EffectIr::Reads || op.on_concurrency_conflict.is_some()
+}
+
+fn port_method(op: &OperationIr) -> &'static str {
+    match op.effect {
+        EffectIr::Creates => "create",
+        EffectIr::Mutates => "mutate",
+        EffectIr::Reads => "read",
+    }
+}
+
+const FAILURE_KINDS: [(&str, &str); 5] = [
+    ("InputValidation", "input_validation"),
+    ("Policy", "policy"),
+    ("MissingState", "missing_state"),
+    ("IdempotencyConflict", "idempotency_conflict"),
+    ("ConcurrencyConflict", "concurrency_conflict"),
+];
+
+fn rs_kind(kind: &str) -> String {
+    let variant = FAILURE_KINDS
+        .iter()
+        .find(|(_, wire)| *wire == kind)
+        .map(|(variant, _)| *variant)
+        .unwrap_or("InputValidation");
+    format!("FailureKind::{variant}")
+}
+
+fn rs_raise(op: &OperationIr, raise: &RaiseIr, detail: &str) -> String {
+    format!(
+        "{}::{} {{ kind: {}, detail: {detail} }}",
+        op.error,
+        raise.variant,
+        rs_kind(&raise.kind)
+    )
+}
+
+fn operations(ir: &DomainIr) -> String {
+    let mut s = String::from(
+        "//! Application operations: handlers, ports and typed failures.\n\nuse std::fmt;\n\n",
+    );
+    let mut used: Vec<&str> = ir
+        .operations
+        .iter()
+        .flat_map(|op| [op.input.as_str(), op.output.as_str()])
+        .collect();
+    used.sort();
+    used.dedup();
+    if !used.is_empty() {
+        s.push_str(&format!("use crate::records::{{{}}};\n\n", used.join(", ")));
+    }
+    s.push_str("/// Canonical failure kinds.\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum FailureKind {\n");
+    for (variant, _) in FAILURE_KINDS {
+        s.push_str(&format!("    {variant},\n"));
+    }
+    s.push_str("}\n\nimpl FailureKind {\n    pub fn as_str(&self) -> &'static str {\n        match self {\n");
+    for (variant, wire) in FAILURE_KINDS {
+        s.push_str(&format!("            Self::{variant} => \"{wire}\",\n"));
+    }
+    s.push_str("        }\n    }\n}\n");
+    for op in &ir.operations {
+        s.push_str(&rs_operation(op));
+    }
+    s
+}
+
+fn rs_operation(op: &OperationIr) -> String {
+    let (input, output, err) = (&op.input, &op.output, &op.error);
+    let mut s = format!(
+        "\n/// Declared failures of operation `{}`.\n#[derive(Debug, Clone, PartialEq, Eq)]\npub enum {err} {{\n",
+        op.name
+    );
+    for f in &op.failures {
+        s.push_str(&format!(
+            "    /// {}\n    {} {{ kind: FailureKind, detail: String }},\n",
+            sentence(&f.meaning),
+            f.variant
+        ));
+    }
+    let arms = |body: &dyn Fn(&str, &str) -> String| -> String {
+        op.failures
+            .iter()
+            .map(|f| body(&f.variant, &f.code))
+            .collect()
+    };
+    s.push_str(&format!(
+        "}}\n\nimpl {err} {{\n    /// Declared failure code.\n    pub fn code(&self) -> &'static str {{\n        match self {{\n{}        }}\n    }}\n\n    pub fn kind(&self) -> FailureKind {{\n        match self {{\n{}        }}\n    }}\n\n    pub fn detail(&self) -> &str {{\n        match self {{\n{}        }}\n    }}\n}}\n\nimpl fmt::Display for {err} {{\n    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {{\n        write!(f, \"{{}} ({{}}): {{}}\", self.code(), self.kind().as_str(), self.detail())\n    }}\n}}\n\nimpl std::error::Error for {err} {{}}\n",
+        arms(&|v, code| format!("            Self::{v} {{ .. }} => \"{code}\",\n")),
+        arms(&|v, _| format!("            Self::{v} {{ kind, .. }} => *kind,\n")),
+        arms(&|v, _| format!("            Self::{v} {{ detail, .. }} => detail,\n")),
+    ));
+
+    let ret = if port_optional(op) {
+        format!("Option<{output}>")
+    } else {
+        output.clone()
+    };
+    s.push_str(&format!(
+        "\n/// State port for {state}.\npub trait {port} {{\n",
+        state = op.state,
+        port = op.port
+    ));
+    if op.effect == EffectIr::Mutates {
+        if let ConcurrencyIr::OptimisticVersion { .. } = op.concurrency {
+            s.push_str(&format!(
+                "    /// Stored version of the {state}, or `None` when it does not exist.\n    fn current_version(&self, command: &{input}) -> Option<i64>;\n",
+                state = op.state
+            ));
+        } else {
+            s.push_str(&format!(
+                "    /// Whether the {state} addressed by the command exists.\n    fn exists(&self, command: &{input}) -> bool;\n",
+                state = op.state
+            ));
+        }
+    }
+    let none_means = match (op.effect, &op.concurrency) {
+        (EffectIr::Reads, _) => format!(" Returns `None` when the {} does not exist.", op.state),
+        (_, ConcurrencyIr::UniqueKey { field }) => {
+            format!(" Returns `None` when `{field}` is already taken.")
+        }
+        (_, ConcurrencyIr::OptimisticVersion { .. }) => {
+            " Returns `None` when the version changed concurrently.".to_string()
+        }
+        _ => String::new(),
+    };
+    s.push_str(&format!(
+        "    /// {verb} the {state}.{none_means}\n    fn {method}(&self, command: &{input}) -> {ret};\n}}\n",
+        verb = match op.effect {
+            EffectIr::Creates => "Create",
+            EffectIr::Mutates => "Mutate",
+            EffectIr::Reads => "Read",
+        },
+        state = op.state,
+        method = port_method(op),
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!(
+            "\n/// Precondition policies of operation `{}`.\npub trait {policies} {{\n",
+            op.name
+        ));
+        for pre in &op.preconditions {
+            s.push_str(&format!(
+                "    /// Whether policy `{policy}` allows the command.\n    fn {method}(&self, command: &{input}) -> bool;\n",
+                policy = pre.policy_name,
+                method = pre.method,
+            ));
+        }
+        s.push_str("}\n");
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!(
+            "\n/// Outcomes of completed `{name}` calls by idempotency key.\npub trait {store} {{\n    /// The `(fingerprint, output)` stored for `key`, if any.\n    fn lookup(&self, key: &str) -> Option<(String, {output})>;\n    /// Store the outcome of a completed call.\n    fn remember(&self, key: &str, fingerprint: &str, output: &{output});\n}}\n",
+            name = op.name,
+        ));
+    }
+
+    let mut fields = vec![format!("    pub port: &'a dyn {},\n", op.port)];
+    if let Some(policies) = &op.policies_port {
+        fields.push(format!("    pub policies: &'a dyn {policies},\n"));
+    }
+    if let Some(store) = &op.idempotency_port {
+        fields.push(format!("    pub idempotency: &'a dyn {store},\n"));
+    }
+    s.push_str(&format!(
+        "\n/// Operation `{name}` ({state}).\npub struct {handler}<'a> {{\n{fields}}}\n\nimpl {handler}<'_> {{\n    /// {intent}\n    pub fn handle(&self, command: &{input}) -> Result<{output}, {err}> {{\n",
+        name = op.name,
+        state = op.state,
+        handler = op.handler,
+        fields = fields.concat(),
+        intent = sentence(&op.intent),
+    ));
+    if let Some(raise) = &op.on_invalid_input {
+        s.push_str(&format!(
+            "        let violations = command.validate();\n        if !violations.is_empty() {{\n            return Err({});\n        }}\n",
+            rs_raise(op, raise, "violations.join(\"; \")")
+        ));
+    }
+    for pre in &op.preconditions {
+        s.push_str(&format!(
+            "        if !self.policies.{}(command) {{\n            return Err({});\n        }}\n",
+            pre.method,
+            rs_raise(
+                op,
+                &pre.raise,
+                &format!("{}.to_string()", rs_str_lit(&pre.policy_name))
+            )
+        ));
+    }
+    if let (IdempotencyIr::KeyedBy { field }, Some(raise)) =
+        (&op.idempotency, &op.on_idempotency_conflict)
+    {
+        s.push_str(&format!(
+            "        let key = command.{field}.to_string();\n        let fingerprint = command.fingerprint();\n        if let Some((stored, output)) = self.idempotency.lookup(&key) {{\n            if stored != fingerprint {{\n                return Err({});\n            }}\n            return Ok(output);\n        }}\n",
+            rs_raise(op, raise, "key")
+        ));
+    }
+    let missing = op.on_missing_state.as_ref().map(|r| {
+        rs_raise(
+            op,
+            r,
+            &format!(
+                "{}.to_string()",
+                rs_str_lit(&format!("{} not found", op.state))
+            ),
+        )
+    });
+    let conflict = op.on_concurrency_conflict.as_ref().map(|r| {
+        rs_raise(
+            op,
+            r,
+            &format!(
+                "{}.to_string()",
+                rs_str_lit(&op.concurrency.conflict_detail())
+            ),
+        )
+    });
+    if op.effect == EffectIr::Mutates {
+        if let (ConcurrencyIr::OptimisticVersion { field }, Some(missing), Some(conflict)) =
+            (&op.concurrency, &missing, &conflict)
+        {
+            s.push_str(&format!(
+                "        match self.port.current_version(command) {{\n            None => return Err({missing}),\n            Some(version) if version != command.{field} => return Err({conflict}),\n            Some(_) => {{}}\n        }}\n"
+            ));
+        } else if let Some(missing) = &missing {
+            s.push_str(&format!(
+                "        if !self.port.exists(command) {{\n            return Err({missing});\n        }}\n"
+            ));
+        }
+    }
+    let on_none = match op.effect {
+        EffectIr::Reads => missing,
+        _ => conflict,
+    };
+    match on_none {
+        Some(on_none) => s.push_str(&format!(
+            "        let output = self\n            .port\n            .{}(command)\n            .ok_or_else(|| {on_none})?;\n",
+            port_method(op)
+        )),
+        None => s.push_str(&format!(
+            "        let output = self.port.{}(command);\n",
+            port_method(op)
+        )),
+    }
+    if op.idempotency_port.is_some() {
+        s.push_str("        self.idempotency.remember(&key, &fingerprint, &output);\n");
+    }
+    s.push_str("        Ok(output)\n    }\n}\n");
+    s
+}
+
+fn rs_sample(sample: &SampleIr) -> String {
+    match sample {
+        SampleIr::Str(v) | SampleIr::Decimal(v) => format!("{}.to_string()", rs_str_lit(v)),
+        SampleIr::Int(v) => v.to_string(),
+        SampleIr::Bool(v) => v.to_string(),
+        SampleIr::Enum { name, variant, .. } => format!("{name}::{variant}"),
+        SampleIr::List(items) => format!(
+            "vec![{}]",
+            items.iter().map(rs_sample).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn rs_record_sample(sample: &RecordSampleIr) -> String {
+    let fields: Vec<String> = sample
+        .fields
+        .iter()
+        .map(|(name, optional, value)| {
+            if *optional {
+                format!("{name}: Some({})", rs_sample(value))
+            } else {
+                format!("{name}: {}", rs_sample(value))
+            }
+        })
+        .collect();
+    format!("{} {{ {} }}", sample.record, fields.join(", "))
+}
+
+fn operations_test(ir: &DomainIr) -> String {
+    let crate_name = format!("{}_domain", ir.package_slug);
+    let mut op_names: Vec<String> = vec!["FailureKind".to_string()];
+    let mut record_names: Vec<String> = Vec::new();
+    let mut body = String::new();
+    for op in &ir.operations {
+        let (Some(input), Some(output)) = (&op.sample_input, &op.sample_output) else {
+            continue;
+        };
+        op_names.extend([op.handler.clone(), op.port.clone(), op.error.clone()]);
+        op_names.extend(op.policies_port.clone());
+        op_names.extend(op.idempotency_port.clone());
+        record_names.extend([op.input.clone(), op.output.clone()]);
+        let mut samples = Vec::new();
+        collect_enums(input, &mut samples);
+        collect_enums(output, &mut samples);
+        record_names.extend(samples);
+        body.push_str(&rs_fakes(op, input, output));
+        body.push_str(&rs_tests(op));
+    }
+    op_names.sort();
+    op_names.dedup();
+    record_names.sort();
+    record_names.dedup();
+    let mut s = format!(
+        "use {crate_name}::operations::{{{}}};\n",
+        op_names.join(", ")
+    );
+    if !record_names.is_empty() {
+        s.push_str(&format!(
+            "use {crate_name}::records::{{{}}};\n",
+            record_names.join(", ")
+        ));
+    }
+    if body.is_empty() {
+        return s;
+    }
+    s.push_str("use std::cell::{Cell, RefCell};\n");
+    if ir.operations.iter().any(|op| op.idempotency_port.is_some()) {
+        s.push_str("use std::collections::HashMap;\n");
+    }
+    s.push_str(&body);
+    s
+}
+
+fn collect_enums(sample: &RecordSampleIr, out: &mut Vec<String>) {
+    fn walk(value: &SampleIr, out: &mut Vec<String>) {
+        match value {
+            SampleIr::Enum { name, .. } => out.push(name.clone()),
+            SampleIr::List(items) => items.iter().for_each(|i| walk(i, out)),
+            _ => {}
+        }
+    }
+    for (_, _, value) in &sample.fields {
+        walk(value, out);
+    }
+}
+
+fn rs_fakes(op: &OperationIr, input: &RecordSampleIr, output: &RecordSampleIr) -> String {
+    let name = &op.name;
+    let (in_ty, out_ty) = (&op.input, &op.output);
+    let mut s = format!(
+        "\nfn {name}_input() -> {in_ty} {{\n    {}\n}}\n\nfn {name}_output() -> {out_ty} {{\n    {}\n}}\n",
+        rs_record_sample(input),
+        rs_record_sample(output)
+    );
+    let optimistic = match &op.concurrency {
+        ConcurrencyIr::OptimisticVersion { field } if op.effect == EffectIr::Mutates => Some(field),
+        _ => None,
+    };
+    let mut fields = vec![
+        format!("    output: RefCell<Option<{out_ty}>>,\n"),
+        "    calls: Cell<u32>,\n".to_string(),
+    ];
+    let mut inits = vec![
+        format!("            output: RefCell::new(Some({name}_output())),\n"),
+        "            calls: Cell::new(0),\n".to_string(),
+    ];
+    if op.effect == EffectIr::Mutates {
+        match optimistic {
+            Some(field) => {
+                fields.push("    version: Cell<Option<i64>>,\n".to_string());
+                inits.push(format!(
+                    "            version: Cell::new(Some({name}_input().{field})),\n"
+                ));
+            }
+            None => {
+                fields.push("    present: Cell<bool>,\n".to_string());
+                inits.push("            present: Cell::new(true),\n".to_string());
+            }
+        }
+    }
+    s.push_str(&format!(
+        "\nstruct Fake{port} {{\n{fields}}}\n\nimpl Fake{port} {{\n    fn new() -> Self {{\n        Self {{\n{inits}        }}\n    }}\n}}\n\nimpl {port} for Fake{port} {{\n",
+        port = op.port,
+        fields = fields.concat(),
+        inits = inits.concat(),
+    ));
+    if op.effect == EffectIr::Mutates {
+        match optimistic {
+            Some(_) => s.push_str(&format!(
+                "    fn current_version(&self, _command: &{in_ty}) -> Option<i64> {{\n        self.version.get()\n    }}\n\n"
+            )),
+            None => s.push_str(&format!(
+                "    fn exists(&self, _command: &{in_ty}) -> bool {{\n        self.present.get()\n    }}\n\n"
+            )),
+        }
+    }
+    let (ret, result) = if port_optional(op) {
+        (format!("Option<{out_ty}>"), "self.output.borrow().clone()")
+    } else {
+        (
+            out_ty.clone(),
+            "self.output.borrow().clone().expect(\"output\")",
+        )
+    };
+    s.push_str(&format!(
+        "    fn {method}(&self, _command: &{in_ty}) -> {ret} {{\n        self.calls.set(self.calls.get() + 1);\n        {result}\n    }}\n}}\n",
+        method = port_method(op)
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!(
+            "\n#[derive(Default)]\nstruct Fake{policies} {{\n    denied: RefCell<Vec<&'static str>>,\n}}\n\nimpl {policies} for Fake{policies} {{\n"
+        ));
+        let methods: Vec<String> = op
+            .preconditions
+            .iter()
+            .map(|pre| {
+                format!(
+                    "    fn {method}(&self, _command: &{in_ty}) -> bool {{\n        !self.denied.borrow().contains(&{policy})\n    }}\n",
+                    method = pre.method,
+                    policy = rs_str_lit(&pre.policy_name)
+                )
+            })
+            .collect();
+        s.push_str(&methods.join("\n"));
+        s.push_str("}\n");
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!(
+            "\n#[derive(Default)]\nstruct Fake{store} {{\n    entries: RefCell<HashMap<String, (String, {out_ty})>>,\n}}\n\nimpl {store} for Fake{store} {{\n    fn lookup(&self, key: &str) -> Option<(String, {out_ty})> {{\n        self.entries.borrow().get(key).cloned()\n    }}\n\n    fn remember(&self, key: &str, fingerprint: &str, output: &{out_ty}) {{\n        self.entries\n            .borrow_mut()\n            .insert(key.to_string(), (fingerprint.to_string(), output.clone()));\n    }}\n}}\n"
+        ));
+    }
+    s
+}
+
+/// `let` bindings that wire fresh fakes into a handler named `handler`.
+fn rs_fixture(op: &OperationIr) -> String {
+    let mut s = format!("    let port = Fake{}::new();\n", op.port);
+    let mut fields = vec!["port: &port".to_string()];
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!("    let policies = Fake{policies}::default();\n"));
+        fields.push("policies: &policies".to_string());
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!("    let store = Fake{store}::default();\n"));
+        fields.push("idempotency: &store".to_string());
+    }
+    s.push_str(&format!(
+        "    let handler = {} {{ {} }};\n",
+        op.handler,
+        fields.join(", ")
+    ));
+    s
+}
+
+fn rs_expect(op: &OperationIr, raise: &RaiseIr, command: &str) -> String {
+    format!(
+        "    let error = handler.handle(&{command}).unwrap_err();\n    assert!(matches!(error, {err}::{variant} {{ .. }}));\n    assert_eq!(error.kind(), {kind});\n",
+        err = op.error,
+        variant = raise.variant,
+        kind = rs_kind(&raise.kind),
+    )
+}
+
+fn rs_tests(op: &OperationIr) -> String {
+    let name = &op.name;
+    let fixture = rs_fixture(op);
+    let mut s = format!(
+        "\n#[test]\nfn {name}_succeeds() {{\n{fixture}    assert_eq!(handler.handle(&{name}_input()), Ok({name}_output()));\n    assert_eq!(port.calls.get(), 1);\n}}\n"
+    );
+    let mut case = |title: &str, setup: String, expect: String, untouched: bool| {
+        s.push_str(&format!(
+            "\n#[test]\nfn {name}_{title}() {{\n{fixture}{setup}{expect}{tail}}}\n",
+            tail = if untouched {
+                "    assert_eq!(port.calls.get(), 0);\n"
+            } else {
+                ""
+            }
+        ));
+    };
+    if let (Some(raise), Some((field, value))) = (&op.on_invalid_input, &op.invalid_input) {
+        let optional = op
+            .sample_input
+            .as_ref()
+            .and_then(|i| i.fields.iter().find(|(n, _, _)| n == field))
+            .is_some_and(|(_, optional, _)| *optional);
+        let value = if optional {
+            format!("Some({})", rs_sample(value))
+        } else {
+            rs_sample(value)
+        };
+        case(
+            "rejects_invalid_input",
+            format!(
+                "    let bad = {} {{ {field}: {value}, ..{name}_input() }};\n    assert!(!bad.validate().is_empty());\n",
+                op.input
+            ),
+            rs_expect(op, raise, "bad"),
+            true,
+        );
+    }
+    for pre in &op.preconditions {
+        case(
+            &format!(
+                "denied_by_{}",
+                crate::projection::ids::slug(&pre.policy_name)
+            ),
+            format!(
+                "    policies.denied.borrow_mut().push({});\n",
+                rs_str_lit(&pre.policy_name)
+            ),
+            rs_expect(op, &pre.raise, &format!("{name}_input()")),
+            true,
+        );
+    }
+    if let (IdempotencyIr::KeyedBy { field }, Some(raise)) =
+        (&op.idempotency, &op.on_idempotency_conflict)
+    {
+        case(
+            "replays_idempotently",
+            format!("    let first = handler.handle(&{name}_input()).unwrap();\n"),
+            format!(
+                "    assert_eq!(handler.handle(&{name}_input()), Ok(first));\n    assert_eq!(port.calls.get(), 1);\n"
+            ),
+            false,
+        );
+        case(
+            "rejects_conflicting_replay",
+            format!(
+                "    store.remember(&{name}_input().{field}.to_string(), \"other\", &{name}_output());\n"
+            ),
+            rs_expect(op, raise, &format!("{name}_input()")),
+            true,
+        );
+    }
+    if let Some(raise) = &op.on_concurrency_conflict {
+        let setup = match &op.concurrency {
+            ConcurrencyIr::OptimisticVersion { field } if op.effect == EffectIr::Mutates => {
+                format!("    port.version.set(Some({name}_input().{field} + 1));\n")
+            }
+            _ => "    port.output.replace(None);\n".to_string(),
+        };
+        case(
+            "concurrency_conflict",
+            setup,
+            rs_expect(op, raise, &format!("{name}_input()")),
+            false,
+        );
+    }
+    if let Some(raise) = &op.on_missing_state {
+        let setup = match (&op.effect, &op.concurrency) {
+            (EffectIr::Reads, _) => "    port.output.replace(None);\n",
+            (_, ConcurrencyIr::OptimisticVersion { .. }) => "    port.version.set(None);\n",
+            _ => "    port.present.set(false);\n",
+        };
+        case(
+            "missing_state",
+            setup.to_string(),
+            rs_expect(op, raise, &format!("{name}_input()")),
+            false,
+        );
+    }
+    if let Some(fingerprint) = &op.sample_fingerprint {
+        s.push_str(&format!(
+            "\n#[test]\nfn {name}_fingerprints_canonical_input() {{\n    assert_eq!({name}_input().fingerprint(), {});\n}}\n",
+            rs_str_lit(fingerprint)
+        ));
+    }
+    s
+}
+
 // ---------- helpers ----------
 
 /// `PurchaseOrder` -> `purchase_order` (lower snake_case field prefix).
@@ -478,7 +1429,7 @@ mod tests {
         )
         .expect("fixture");
         let graph = parse_to_graph(&src).expect("parses");
-        project_domain_rust_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()))
+        project_domain_rust_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()), None)
             .expect("projects")
     }
 
@@ -522,4 +1473,29 @@ mod tests {
             "must declare zero deps + workspace opt-out"
         );
     }
+
+    #[test]
+    fn contract_operations_emit_handlers_ports_and_tests() {
+        let (graph, contract) = crate::projection::domain::ir::flagship_fixture();
+        let files = project_domain_rust_in_memory(
+            &graph,
+            "command-write.sea",
+            Some(FIXED_TS.to_string()),
+            Some(&contract),
+        )
+        .expect("projects");
+        assert!(files["src/lib.rs"].contains("pub mod records;\npub mod operations;\n"));
+        let ops = &files["src/operations.rs"];
+        assert!(ops.contains("pub enum PlaceOrderError {"));
+        assert!(ops.contains("IdempotencyConflict { kind: FailureKind, detail: String },"));
+        assert!(ops
+            .contains("fn create(&self, command: &PlaceOrderInput) -> Option<PlaceOrderOutput>;"));
+        assert!(ops.contains("pub struct PlaceOrderHandler<'a> {"));
+        assert!(ops.contains("let fingerprint = command.fingerprint();"));
+        assert!(files["src/records.rs"].contains("pub fn fingerprint(&self) -> String {"));
+        let tests = &files["tests/operations.rs"];
+        assert!(tests.contains("fn place_order_replays_idempotently() {"));
+        assert!(tests.contains("fn place_order_denied_by_order_total_within_limit() {"));
+        assert!(tests.contains("fn place_order_fingerprints_canonical_input() {"));
+    }
 }
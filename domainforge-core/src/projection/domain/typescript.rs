@@ -1,10 +1,18 @@
 //! TypeScript DDD/CQRS renderer. Consumes [`crate::projection::domain::ir::DomainIr`]
 //! and emits a complete, zero-runtime-dependency TypeScript package up to the port
-//! boundary. `emit` = `DomainIr::from_graph` + pure render functions IR → TS source.
-//! This renderer never touches [`crate::graph::Graph`].
+//! boundary. `emit` = `DomainIr::from_graph_and_contract` + pure render functions
+//! IR → TS source. This renderer never touches [`crate::graph::Graph`].
+//!
+//! With an application contract it adds `src/application/records.ts`,
+//! `src/application/operations.ts` and `src/operationsSmoke.ts`.
 
+use crate::application::ApplicationContract;
 use crate::graph::Graph;
-use crate::projection::domain::ir::DomainIr;
+use crate::projection::domain::ir::{
+    sentence, CanonicalIr, CheckIr, ConcurrencyIr, DomainIr, EffectIr, FieldIr, IdempotencyIr,
+    OperationIr, RaiseIr, RecordIr, RecordSampleIr, SampleIr, TypeIr,
+};
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
 use std::collections::BTreeMap;
 
@@ -12,11 +20,12 @@ pub fn emit(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    contract: Option<&ApplicationContract>,
     sink: &mut ArtifactSink,
 ) -> Result<Vec<String>, String> {
-    let ir = DomainIr::from_graph(graph)?;
+    let ir = DomainIr::from_graph_and_contract(graph, contract)?;
     let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
-    let files: Vec<(String, String)> = vec![
+    let mut files: Vec<(String, String)> = vec![
         ("package.json".to_string(), package_json(&ir)),
         ("tsconfig.json".to_string(), tsconfig()),
         ("README.md".to_string(), readme(&ir, model_ref, &created_at)),
@@ -34,6 +43,13 @@ pub fn emit(
         ("src/container.ts".to_string(), container(&ir)),
         ("src/smoke.ts".to_string(), smoke(&ir)),
     ];
+    if ir.has_application() {
+        files.extend([
+            ("src/application/records.ts".to_string(), records(&ir)),
+            ("src/application/operations.ts".to_string(), operations(&ir)),
+            ("src/operationsSmoke.ts".to_string(), operations_smoke(&ir)),
+        ]);
+    }
     let mut emitted = Vec::new();
     for (path, body) in &files {
         sink.write(path, body)?;
@@ -46,13 +62,14 @@ pub fn project_domain_typescript_in_memory(
     graph: &Graph,
     model_ref: &str,
     created_at: Option<String>,
+    contract: Option<&ApplicationContract>,
 ) -> Result<BTreeMap<String, String>, String> {
     let mut map = BTreeMap::new();
     let mut sink = ArtifactSink::Memory {
         prefix: String::new(),
         map: &mut map,
     };
-    emit(graph, model_ref, created_at, &mut sink)?;
+    emit(graph, model_ref, created_at, contract, &mut sink)?;
     Ok(map)
 }
 
@@ -93,12 +110,12 @@ fn tsconfig() -> String {
 
 fn readme(ir: &DomainIr, model_ref: &str, created_at: &str) -> String {
     format!(
-        "# {ns} domain package\n\nGenerated by DomainForge from `{model_ref}` at {created_at}.\n\n\
+        "# {ns} domain package\n\nGenerated by DomainForge from `{model_ref}`.\n\n{created_at}\n\n\
 A complete, zero-runtime-dependency DDD/CQRS domain layer (TypeScript) up to the port\n\
 boundary. Validate with `tsc --noEmit`.\n",
         ns = ir.namespace,
         model_ref = model_ref,
-        created_at = created_at,
+        created_at = generated_at(created_at),
     )
 }
 
@@ -442,6 +459,724 @@ fn smoke(ir: &DomainIr) -> String {
     s
 }
 
+// ---------- application layer (records, operations, generated smoke) ----------
+
+fn ts_type(ty: &TypeIr) -> String {
+    match ty {
+        // Decimals travel as canonical strings; `number` would lose precision.
+        TypeIr::Str | TypeIr::Timestamp | TypeIr::Uuid | TypeIr::Decimal => "string".to_string(),
+        TypeIr::Int => "number".to_string(),
+        TypeIr::Bool => "boolean".to_string(),
+        TypeIr::Enum(name) => name.clone(),
+        TypeIr::List(element) => format!("readonly {}[]", ts_type(element)),
+    }
+}
+
+/// JSON (= TypeScript) string literal.
+fn ts_str_lit(s: &str) -> String {
+    serde_json::Value::String(s.to_string()).to_string()
+}
+
+/// Condition that holds when `value` violates `check`.
+fn ts_violates(ty: &TypeIr, check: &CheckIr, value: &str) -> String {
+    let number = match ty {
+        TypeIr::Decimal => format!("Number({value})"),
+        _ => value.to_string(),
+    };
+    match check {
+        CheckIr::Min { bound, exclusive } => {
+            format!(
+                "!({number} {} {bound})",
+                if *exclusive { ">" } else { ">=" }
+            )
+        }
+        CheckIr::Max { bound, exclusive } => {
+            format!(
+                "!({number} {} {bound})",
+                if *exclusive { "<" } else { "<=" }
+            )
+        }
+        CheckIr::MinLength(n) | CheckIr::MinItems(n) => format!("{value}.length < {n}"),
+        CheckIr::MaxLength(n) | CheckIr::MaxItems(n) => format!("{value}.length > {n}"),
+        CheckIr::Pattern(regex) => format!(
+            "!new RegExp({}).test({value})",
+            ts_str_lit(&format!("^(?:{regex})$"))
+        ),
+    }
+}
+
+fn ts_field_checks(field: &FieldIr) -> String {
+    let value = format!("value.{}", field.name);
+    let mut lines: Vec<String> = Vec::new();
+    for check in field.checks.iter().filter(|c| c.is_collection()) {
+        lines.push(format!(
+            "if ({}) errors.push({});",
+            ts_violates(&field.ty, check, &value),
+            ts_str_lit(&format!("{}: {}", field.name, check.message()))
+        ));
+    }
+    let element_checks: Vec<&CheckIr> =
+        field.checks.iter().filter(|c| !c.is_collection()).collect();
+    if !element_checks.is_empty() {
+        match &field.ty {
+            TypeIr::List(element) => {
+                lines.push(format!("{value}.forEach((item, i) => {{"));
+                for check in element_checks {
+                    let message = check
+                        .message()
+                        .replace('\\', "\\\\")
+                        .replace('`', "\\`")
+                        .replace("${", "\\${");
+                    lines.push(format!(
+                        "  if ({}) errors.push(`{}[${{i}}]: {message}`);",
+                        ts_violates(element, check, "item"),
+                        field.name,
+                    ));
+                }
+                lines.push("});".to_string());
+            }
+            ty => {
+                for check in element_checks {
+                    lines.push(format!(
+                        "if ({}) errors.push({});",
+                        ts_violates(ty, check, &value),
+                        ts_str_lit(&format!("{}: {}", field.name, check.message()))
+                    ));
+                }
+            }
+        }
+    }
+    let mut s = String::new();
+    if field.optional && !lines.is_empty() {
+        s.push_str(&format!("  if ({value} !== undefined) {{\n"));
+        for line in lines {
+            s.push_str(&format!("    {line}\n"));
+        }
+        s.push_str("  }\n");
+    } else {
+        for line in lines {
+            s.push_str(&format!("  {line}\n"));
+        }
+    }
+    s
+}
+
+fn records(ir: &DomainIr) -> String {
+    let mut s = String::new();
+    for e in &ir.app_enums {
+        s.push_str(&format!("export enum {} {{\n", e.name));
+        for m in &e.members {
+            s.push_str(&format!("  {} = {},\n", m.variant, ts_str_lit(&m.wire)));
+        }
+        s.push_str("}\n\n");
+    }
+    for r in &ir.records {
+        s.push_str(&format!("export interface {} {{\n", r.name));
+        for f in &r.fields {
+            if let Some(doc) = &f.doc {
+                s.push_str(&format!("  /** {} */\n", sentence(doc)));
+            }
+            s.push_str(&format!(
+                "  readonly {}{}: {};\n",
+                f.name,
+                if f.optional { "?" } else { "" },
+                ts_type(&f.ty)
+            ));
+        }
+        s.push_str(&format!(
+            "}}\n\n/** Constraint violations as `<field>: <constraint>` (empty when valid). */\nexport function validate{name}(value: {name}): string[] {{\n  const errors: string[] = [];\n",
+            name = r.name
+        ));
+        for f in &r.fields {
+            s.push_str(&ts_field_checks(f));
+        }
+        s.push_str("  return errors;\n}\n\n");
+        if ir.fingerprints(&r.name) {
+            s.push_str(&ts_fingerprint(r));
+        }
+    }
+    if ir.records.iter().any(|r| ir.fingerprints(&r.name)) {
+        s.push_str(FINGERPRINT_HELPERS);
+    }
+    s.truncate(s.trim_end().len());
+    s.push('\n');
+    s
+}
+
+/// `fingerprint<Record>()` of an idempotent operation's input: the
+/// Reference §6 canonical input fingerprint `application::input_fingerprint`
+/// computes.
+fn ts_fingerprint(record: &RecordIr) -> String {
+    let mut fields: Vec<&FieldIr> = record.fields.iter().collect();
+    fields.sort_by(|a, b| a.wire_name.cmp(&b.wire_name));
+    let mut s = format!(
+        "/** Canonical input fingerprint: `sha256:` over the canonical JSON of the present fields. */\nexport async function fingerprint{name}(value: {name}): Promise<string> {{\n  const fields: Record<string, unknown> = {{}};\n",
+        name = record.name
+    );
+    for f in fields {
+        let key = ts_str_lit(&f.wire_name);
+        let value = format!("value.{}", f.name);
+        if f.optional {
+            s.push_str(&format!(
+                "  if ({value} !== undefined) {{\n    fields[{key}] = {};\n  }}\n",
+                ts_canonical(&f.canonical, &value)
+            ));
+        } else {
+            s.push_str(&format!(
+                "  fields[{key}] = {};\n",
+                ts_canonical(&f.canonical, &value)
+            ));
+        }
+    }
+    s.push_str("  return sha256Json(fields);\n}\n\n");
+    s
+}
+
+/// Expression building the canonical tagged value of `value`. Object keys are
+/// written in bytewise order, which `JSON.stringify` preserves.
+fn ts_canonical(canonical: &CanonicalIr, value: &str) -> String {
+    match canonical {
+        CanonicalIr::Scalar("string") => format!("tagged(\"string\", {value}.normalize(\"NFC\"))"),
+        CanonicalIr::Scalar("uuid") => format!("tagged(\"uuid\", {value}.toLowerCase())"),
+        CanonicalIr::Scalar("decimal") => {
+            format!("tagged(\"decimal\", canonicalDecimal({value}))")
+        }
+        CanonicalIr::Scalar(kind) => format!("tagged({}, {value})", ts_str_lit(kind)),
+        CanonicalIr::Quantity { unit } => format!(
+            "tagged(\"quantity\", {{ base_value: canonicalDecimal({value}), unit: {} }})",
+            ts_str_lit(unit)
+        ),
+        CanonicalIr::EntityRef { entity, key } => format!(
+            "tagged(\"entity_ref\", {{ entity: {}, key: {} }})",
+            ts_str_lit(entity),
+            ts_canonical(key, value)
+        ),
+        CanonicalIr::Enum { symbol } => format!(
+            "tagged(\"enum\", {{ symbol: {}, wire: {value} }})",
+            ts_str_lit(symbol)
+        ),
+        CanonicalIr::List(element) => format!(
+            "tagged(\"list\", {value}.map((item) => {}))",
+            ts_canonical(element, "item")
+        ),
+    }
+}
+
+/// Canonical JSON and SHA-256 (Web Crypto) for `fingerprint<Record>()`.
+const FINGERPRINT_HELPERS: &str = r#"function tagged(kind: string, data: unknown): { data: unknown; kind: string } {
+  return { data, kind };
+}
+
+/** Canonical decimal: no exponent, no trailing fractional zeros, "0" for zero. */
+function canonicalDecimal(value: string): string {
+  const sign = value.startsWith("-") ? "-" : "";
+  const [whole, fraction = ""] = value.replace(/^[-+]/, "").split(".");
+  const int = whole.replace(/^0+/, "") || "0";
+  const frac = fraction.replace(/0+$/, "");
+  if (frac === "") {
+    return int === "0" ? "0" : `${sign}${int}`;
+  }
+  return `${sign}${int}.${frac}`;
+}
+
+/** `sha256:<hex>` of the JSON of `fields`. */
+async function sha256Json(fields: Record<string, unknown>): Promise<string> {
+  const bytes = new TextEncoder().encode(JSON.stringify(fields));
+  const digest = new Uint8Array(await crypto.subtle.digest("SHA-256", bytes));
+  return `sha256:${Array.from(digest, (byte) => byte.toString(16).padStart(2, "0")).join("")}`;
+}
+"#;
+
+fn port_optional(op: &OperationIr) -> bool {
+    op.effect == EffectIr::Reads || op.on_concurrency_conflict.is_some()
+}
+
+fn port_method(op: &OperationIr) -> &'static str {
+    match op.effect {
+        EffectIr::Creates => "create",
+        EffectIr::Mutates => "mutate",
+        EffectIr::Reads => "read",
+    }
+}
+
+fn ts_kind(kind: &str) -> String {
+    format!("FailureKind.{}", pascal_case(kind))
+}
+
+fn pascal_case(snake: &str) -> String {
+    let c = camel(snake);
+    let mut chars = c.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn ts_raise(raise: &RaiseIr, detail: &str) -> String {
+    format!(
+        "throw new {}({}, {detail});",
+        raise.class_name,
+        ts_kind(&raise.kind)
+    )
+}
+
+fn operations(ir: &DomainIr) -> String {
+    let mut used: Vec<String> = ir
+        .operations
+        .iter()
+        .flat_map(|op| {
+            let mut names = vec![format!("type {}", op.input), format!("type {}", op.output)];
+            if op.on_invalid_input.is_some() {
+                names.push(format!("validate{}", op.input));
+            }
+            if op.idempotency_port.is_some() {
+                names.push(format!("fingerprint{}", op.input));
+            }
+            names
+        })
+        .collect();
+    used.sort();
+    used.dedup();
+    let mut s = String::from("import { DomainError } from \"../domain/errors\";\n");
+    if !used.is_empty() {
+        s.push_str(&format!(
+            "import {{ {} }} from \"./records\";\n",
+            used.join(", ")
+        ));
+    }
+    s.push_str("\nexport enum FailureKind {\n");
+    for kind in [
+        "input_validation",
+        "policy",
+        "missing_state",
+        "idempotency_conflict",
+        "concurrency_conflict",
+    ] {
+        s.push_str(&format!("  {} = \"{kind}\",\n", pascal_case(kind)));
+    }
+    s.push_str("}\n");
+    for op in &ir.operations {
+        s.push_str(&ts_operation(op));
+    }
+    s
+}
+
+fn ts_operation(op: &OperationIr) -> String {
+    let (input, output) = (&op.input, &op.output);
+    let mut s = format!(
+        "\n/** Declared failures of operation '{name}'. */\nexport class {err} extends DomainError {{\n  readonly code: string;\n  readonly kind: FailureKind;\n  readonly detail: string;\n  constructor(code: string, kind: FailureKind, detail: string) {{\n    super(`${{code}} (${{kind}}): ${{detail}}`);\n    this.name = \"{err}\";\n    this.code = code;\n    this.kind = kind;\n    this.detail = detail;\n  }}\n}}\n",
+        name = op.name,
+        err = op.error,
+    );
+    for f in &op.failures {
+        let kinds: Vec<String> = f.kinds.iter().map(|k| ts_kind(k)).collect();
+        s.push_str(&format!(
+            "\n/** {meaning} */\nexport class {cls} extends {err} {{\n  static readonly kinds: readonly FailureKind[] = [{kinds}];\n  constructor(kind: FailureKind, detail: string) {{\n    super(\"{code}\", kind, detail);\n    this.name = \"{cls}\";\n  }}\n}}\n",
+            meaning = sentence(&f.meaning),
+            cls = f.class_name,
+            err = op.error,
+            kinds = kinds.join(", "),
+            code = f.code,
+        ));
+    }
+
+    let ret = if port_optional(op) {
+        format!("{output} | null")
+    } else {
+        output.clone()
+    };
+    s.push_str(&format!(
+        "\n/** State port for {state}. */\nexport interface {port} {{\n",
+        state = op.state,
+        port = op.port
+    ));
+    if op.effect == EffectIr::Mutates {
+        if let ConcurrencyIr::OptimisticVersion { .. } = op.concurrency {
+            s.push_str(&format!(
+                "  /** Stored version of the {state}, or null when it does not exist. */\n  currentVersion(command: {input}): Promise<number | null>;\n",
+                state = op.state
+            ));
+        } else {
+            s.push_str(&format!(
+                "  /** Whether the {state} addressed by the command exists. */\n  exists(command: {input}): Promise<boolean>;\n",
+                state = op.state
+            ));
+        }
+    }
+    let none_means = match (op.effect, &op.concurrency) {
+        (EffectIr::Reads, _) => format!(" Returns null when the {} does not exist.", op.state),
+        (_, ConcurrencyIr::UniqueKey { field }) => {
+            format!(" Returns null when `{field}` is already taken.")
+        }
+        (_, ConcurrencyIr::OptimisticVersion { .. }) => {
+            " Returns null when the version changed concurrently.".to_string()
+        }
+        _ => String::new(),
+    };
+    s.push_str(&format!(
+        "  /** {verb} the {state}.{none_means} */\n  {method}(command: {input}): Promise<{ret}>;\n}}\n",
+        verb = match op.effect {
+            EffectIr::Creates => "Create",
+            EffectIr::Mutates => "Mutate",
+            EffectIr::Reads => "Read",
+        },
+        state = op.state,
+        method = port_method(op),
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!(
+            "\n/** Precondition policies of operation '{}'. */\nexport interface {policies} {{\n",
+            op.name
+        ));
+        for pre in &op.preconditions {
+            s.push_str(&format!(
+                "  /** Whether policy '{policy}' allows the command. */\n  {method}(command: {input}): Promise<boolean>;\n",
+                policy = pre.policy_name,
+                method = camel(&pre.method),
+            ));
+        }
+        s.push_str("}\n");
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!(
+            "\n/** Outcomes of completed '{name}' calls by idempotency key. */\nexport interface {store} {{\n  lookup(key: string): Promise<{{ fingerprint: string; output: {output} }} | null>;\n  remember(key: string, fingerprint: string, output: {output}): Promise<void>;\n}}\n",
+            name = op.name,
+        ));
+    }
+
+    // Handler.
+    let mut fields = vec![format!("  private readonly port: {};\n", op.port)];
+    let mut params = vec![format!("port: {}", op.port)];
+    let mut assigns = vec!["    this.port = port;\n".to_string()];
+    if let Some(policies) = &op.policies_port {
+        fields.push(format!("  private readonly policies: {policies};\n"));
+        params.push(format!("policies: {policies}"));
+        assigns.push("    this.policies = policies;\n".to_string());
+    }
+    if let Some(store) = &op.idempotency_port {
+        fields.push(format!("  private readonly idempotency: {store};\n"));
+        params.push(format!("idempotency: {store}"));
+        assigns.push("    this.idempotency = idempotency;\n".to_string());
+    }
+    s.push_str(&format!(
+        "\n/** Operation '{name}' ({state}). */\nexport class {handler} {{\n{fields}\n  constructor({params}) {{\n{assigns}  }}\n\n  /** {intent} */\n  async handle(command: {input}): Promise<{output}> {{\n",
+        name = op.name,
+        state = op.state,
+        handler = op.handler,
+        fields = fields.concat(),
+        params = params.join(", "),
+        assigns = assigns.concat(),
+        intent = sentence(&op.intent),
+    ));
+    if let Some(raise) = &op.on_invalid_input {
+        s.push_str(&format!(
+            "    const violations = validate{input}(command);\n    if (violations.length > 0) {{\n      {}\n    }}\n",
+            ts_raise(raise, "violations.join(\"; \")")
+        ));
+    }
+    for pre in &op.preconditions {
+        s.push_str(&format!(
+            "    if (!(await this.policies.{}(command))) {{\n      {}\n    }}\n",
+            camel(&pre.method),
+            ts_raise(&pre.raise, &ts_str_lit(&pre.policy_name))
+        ));
+    }
+    if let (IdempotencyIr::KeyedBy { field }, Some(raise)) =
+        (&op.idempotency, &op.on_idempotency_conflict)
+    {
+        s.push_str(&format!(
+            "    const key = String(command.{field});\n    const fingerprint = await fingerprint{input}(command);\n    const replay = await this.idempotency.lookup(key);\n    if (replay !== null) {{\n      if (replay.fingerprint !== fingerprint) {{\n        {}\n      }}\n      return replay.output;\n    }}\n",
+            ts_raise(raise, "key")
+        ));
+    }
+    let missing = op
+        .on_missing_state
+        .as_ref()
+        .map(|r| ts_raise(r, &ts_str_lit(&format!("{} not found", op.state))));
+    let conflict = op
+        .on_concurrency_conflict
+        .as_ref()
+        .map(|r| ts_raise(r, &ts_str_lit(&op.concurrency.conflict_detail())));
+    if op.effect == EffectIr::Mutates {
+        if let (ConcurrencyIr::OptimisticVersion { field }, Some(missing), Some(conflict)) =
+            (&op.concurrency, &missing, &conflict)
+        {
+            s.push_str(&format!(
+                "    const version = await this.port.currentVersion(command);\n    if (version === null) {{\n      {missing}\n    }}\n    if (version !== command.{field}) {{\n      {conflict}\n    }}\n"
+            ));
+        } else if let Some(missing) = &missing {
+            s.push_str(&format!(
+                "    if (!(await this.port.exists(command))) {{\n      {missing}\n    }}\n"
+            ));
+        }
+    }
+    s.push_str(&format!(
+        "    const output = await this.port.{}(command);\n",
+        port_method(op)
+    ));
+    let on_none = match op.effect {
+        EffectIr::Reads => missing,
+        _ => conflict,
+    };
+    if let Some(on_none) = on_none {
+        s.push_str(&format!(
+            "    if (output === null) {{\n      {on_none}\n    }}\n"
+        ));
+    }
+    if op.idempotency_port.is_some() {
+        s.push_str("    await this.idempotency.remember(key, fingerprint, output);\n");
+    }
+    s.push_str("    return output;\n  }\n}\n");
+    s
+}
+
+fn ts_sample(sample: &SampleIr) -> String {
+    match sample {
+        SampleIr::Str(v) | SampleIr::Decimal(v) => ts_str_lit(v),
+        SampleIr::Int(v) => v.to_string(),
+        SampleIr::Bool(v) => v.to_string(),
+        SampleIr::Enum { name, variant, .. } => format!("{name}.{variant}"),
+        SampleIr::List(items) => format!(
+            "[{}]",
+            items.iter().map(ts_sample).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn ts_record_sample(sample: &RecordSampleIr) -> String {
+    let fields: Vec<String> = sample
+        .fields
+        .iter()
+        .map(|(name, _, value)| format!("{name}: {}", ts_sample(value)))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+fn operations_smoke(ir: &DomainIr) -> String {
+    let mut op_names: Vec<String> = vec!["FailureKind".to_string()];
+    let mut record_names: Vec<String> = ir.app_enums.iter().map(|e| e.name.clone()).collect();
+    let mut body = String::new();
+    for op in &ir.operations {
+        let (Some(input), Some(output)) = (&op.sample_input, &op.sample_output) else {
+            continue;
+        };
+        op_names.push(op.handler.clone());
+        op_names.push(format!("type {}", op.port));
+        op_names.extend(op.failures.iter().map(|f| f.class_name.clone()));
+        op_names.extend(op.policies_port.iter().map(|p| format!("type {p}")));
+        op_names.extend(op.idempotency_port.iter().map(|p| format!("type {p}")));
+        record_names.push(format!("type {}", op.input));
+        if op.sample_fingerprint.is_some() {
+            record_names.push(format!("fingerprint{}", op.input));
+        }
+        record_names.push(format!("type {}", op.output));
+        body.push_str(&ts_fakes(op, input, output));
+    }
+    op_names.sort();
+    op_names.dedup();
+    record_names.sort();
+    record_names.dedup();
+    let mut s = format!(
+        "import {{ {} }} from \"./application/operations\";\n",
+        op_names.join(", ")
+    );
+    if !record_names.is_empty() {
+        s.push_str(&format!(
+            "import {{ {} }} from \"./application/records\";\n",
+            record_names.join(", ")
+        ));
+    }
+    s.push_str("\nfunction assert(condition: unknown): asserts condition {\n  if (!condition) {\n    throw new Error(\"assertion failed\");\n  }\n}\n\n");
+    s.push_str("async function failure(run: () => Promise<unknown>): Promise<unknown> {\n  try {\n    await run();\n  } catch (error) {\n    return error;\n  }\n  throw new Error(\"expected a failure\");\n}\n");
+    s.push_str(&body);
+    s.push_str("\n// Runtime exercise of every operation handler against in-memory ports.\nasync function run(): Promise<void> {\n");
+    for op in &ir.operations {
+        if op.sample_input.is_some() && op.sample_output.is_some() {
+            s.push_str(&ts_checks(op));
+        }
+    }
+    s.push_str("}\n\nvoid run();\n");
+    s
+}
+
+fn ts_fakes(op: &OperationIr, input: &RecordSampleIr, output: &RecordSampleIr) -> String {
+    let upper = op.name.to_uppercase();
+    let (in_ty, out_ty) = (&op.input, &op.output);
+    let ret = if port_optional(op) {
+        format!("{out_ty} | null")
+    } else {
+        out_ty.clone()
+    };
+    let mut s = format!(
+        "\nconst {upper}_INPUT: {in_ty} = {};\nconst {upper}_OUTPUT: {out_ty} = {};\n",
+        ts_record_sample(input),
+        ts_record_sample(output)
+    );
+    s.push_str(&format!(
+        "\nclass Fake{port} implements {port} {{\n  output: {ret} = {upper}_OUTPUT;\n  calls = 0;\n",
+        port = op.port
+    ));
+    if op.effect == EffectIr::Mutates {
+        match &op.concurrency {
+            ConcurrencyIr::OptimisticVersion { field } => s.push_str(&format!(
+                "  version: number | null = {upper}_INPUT.{field};\n\n  async currentVersion(_command: {in_ty}): Promise<number | null> {{\n    return this.version;\n  }}\n"
+            )),
+            _ => s.push_str(&format!(
+                "  present = true;\n\n  async exists(_command: {in_ty}): Promise<boolean> {{\n    return this.present;\n  }}\n"
+            )),
+        }
+    }
+    s.push_str(&format!(
+        "\n  async {method}(_command: {in_ty}): Promise<{ret}> {{\n    this.calls += 1;\n    return this.output;\n  }}\n}}\n",
+        method = port_method(op)
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!(
+            "\nclass Fake{policies} implements {policies} {{\n  denied = new Set<string>();\n"
+        ));
+        for pre in &op.preconditions {
+            s.push_str(&format!(
+                "\n  async {method}(_command: {in_ty}): Promise<boolean> {{\n    return !this.denied.has({policy});\n  }}\n",
+                method = camel(&pre.method),
+                policy = ts_str_lit(&pre.policy_name)
+            ));
+        }
+        s.push_str("}\n");
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!(
+            "\nclass Fake{store} implements {store} {{\n  entries = new Map<string, {{ fingerprint: string; output: {out_ty} }}>();\n\n  async lookup(key: string): Promise<{{ fingerprint: string; output: {out_ty} }} | null> {{\n    return this.entries.get(key) ?? null;\n  }}\n\n  async remember(key: string, fingerprint: string, output: {out_ty}): Promise<void> {{\n    this.entries.set(key, {{ fingerprint, output }});\n  }}\n}}\n"
+        ));
+    }
+    let mut args = vec!["this.port".to_string()];
+    s.push_str(&format!(
+        "\nclass {handler}Fixture {{\n  readonly port = new Fake{port}();\n",
+        handler = op.handler,
+        port = op.port
+    ));
+    if let Some(policies) = &op.policies_port {
+        s.push_str(&format!("  readonly policies = new Fake{policies}();\n"));
+        args.push("this.policies".to_string());
+    }
+    if let Some(store) = &op.idempotency_port {
+        s.push_str(&format!("  readonly store = new Fake{store}();\n"));
+        args.push("this.store".to_string());
+    }
+    s.push_str(&format!(
+        "  readonly handler = new {}({});\n}}\n",
+        op.handler,
+        args.join(", ")
+    ));
+    s
+}
+
+fn ts_expect(raise: &RaiseIr, command: &str) -> String {
+    format!(
+        "    const error = await failure(() => f.handler.handle({command}));\n    assert(error instanceof {cls} && error.kind === {kind});\n",
+        cls = raise.class_name,
+        kind = ts_kind(&raise.kind),
+    )
+}
+
+fn ts_checks(op: &OperationIr) -> String {
+    let upper = op.name.to_uppercase();
+    let fixture = format!("    const f = new {}Fixture();\n", op.handler);
+    let mut s = format!(
+        "  {{\n    // {name}: succeeds\n{fixture}    assert((await f.handler.handle({upper}_INPUT)) === {upper}_OUTPUT);\n    assert(f.port.calls === 1);\n  }}\n",
+        name = op.name
+    );
+    let mut case = |title: &str, setup: String, expect: String, untouched: bool| {
+        s.push_str(&format!(
+            "  {{\n    // {name}: {title}\n{fixture}{setup}{expect}{tail}  }}\n",
+            name = op.name,
+            tail = if untouched {
+                "    assert(f.port.calls === 0);\n"
+            } else {
+                ""
+            }
+        ));
+    };
+    if let (Some(raise), Some((field, value))) = (&op.on_invalid_input, &op.invalid_input) {
+        case(
+            "rejects invalid input",
+            format!(
+                "    const bad: {} = {{ ...{upper}_INPUT, {field}: {} }};\n",
+                op.input,
+                ts_sample(value)
+            ),
+            ts_expect(raise, "bad"),
+            true,
+        );
+    }
+    for pre in &op.preconditions {
+        case(
+            &format!("denied by {}", pre.policy_name),
+            format!(
+                "    f.policies.denied.add({});\n",
+                ts_str_lit(&pre.policy_name)
+            ),
+            ts_expect(&pre.raise, &format!("{upper}_INPUT")),
+            true,
+        );
+    }
+    if let (IdempotencyIr::KeyedBy { field }, Some(raise)) =
+        (&op.idempotency, &op.on_idempotency_conflict)
+    {
+        case(
+            "replays idempotently",
+            format!("    const first = await f.handler.handle({upper}_INPUT);\n"),
+            format!(
+                "    assert((await f.handler.handle({upper}_INPUT)) === first);\n    assert(f.port.calls === 1);\n"
+            ),
+            false,
+        );
+        case(
+            "rejects conflicting replay",
+            format!(
+                "    await f.store.remember(String({upper}_INPUT.{field}), \"other\", {upper}_OUTPUT);\n"
+            ),
+            ts_expect(raise, &format!("{upper}_INPUT")),
+            true,
+        );
+    }
+    if let Some(raise) = &op.on_concurrency_conflict {
+        let setup = match &op.concurrency {
+            ConcurrencyIr::OptimisticVersion { field } if op.effect == EffectIr::Mutates => {
+                format!("    f.port.version = {upper}_INPUT.{field} + 1;\n")
+            }
+            _ => "    f.port.output = null;\n".to_string(),
+        };
+        case(
+            "concurrency conflict",
+            setup,
+            ts_expect(raise, &format!("{upper}_INPUT")),
+            false,
+        );
+    }
+    if let Some(raise) = &op.on_missing_state {
+        let setup = match (&op.effect, &op.concurrency) {
+            (EffectIr::Reads, _) => "    f.port.output = null;\n",
+            (_, ConcurrencyIr::OptimisticVersion { .. }) => "    f.port.version = null;\n",
+            _ => "    f.port.present = false;\n",
+        };
+        case(
+            "missing state",
+            setup.to_string(),
+            ts_expect(raise, &format!("{upper}_INPUT")),
+            false,
+        );
+    }
+    if let Some(fingerprint) = &op.sample_fingerprint {
+        s.push_str(&format!(
+            "  {{\n    // {name}: fingerprints canonical input\n    assert((await fingerprint{input}({upper}_INPUT)) === {});\n  }}\n",
+            ts_str_lit(fingerprint),
+            name = op.name,
+            input = op.input,
+        ));
+    }
+    s
+}
+
 // ---------- helpers ----------
 
 /// Convert a snake_case identifier to camelCase (TS method convention).
@@ -485,7 +1220,7 @@ mod tests {
         )
         .expect("fixture");
         let graph = parse_to_graph(&src).expect("parses");
-        project_domain_typescript_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()))
+        project_domain_typescript_in_memory(&graph, "test.sea", Some(FIXED_TS.to_string()), None)
             .expect("projects")
     }
 
@@ -536,4 +1271,36 @@ mod tests {
             "must declare no runtime dependencies"
         );
     }
+
+    #[test]
+    fn contract_operations_emit_handlers_ports_and_smoke() {
+        let (graph, contract) = crate::projection::domain::ir::flagship_fixture();
+        let files = project_domain_typescript_in_memory(
+            &graph,
+            "command-write.sea",
+            Some(FIXED_TS.to_string()),
+            Some(&contract),
+        )
+        .expect("projects");
+        let ops = &files["src/application/operations.ts"];
+        assert!(
+            ops.contains("export class PlaceOrderIdempotencyConflictError extends PlaceOrderError")
+        );
+        assert!(ops.contains("create(command: PlaceOrderInput): Promise<PlaceOrderOutput | null>;"));
+        assert!(ops
+            .contains("allowsOrderTotalWithinLimit(command: PlaceOrderInput): Promise<boolean>;"));
+        assert!(ops.contains("async handle(command: PlaceOrderInput): Promise<PlaceOrderOutput>"));
+        assert!(ops.contains("const fingerprint = await fingerprintPlaceOrderInput(command);"));
+        let records = &files["src/application/records.ts"];
+        assert!(records
+            .contains("export function validatePlaceOrderInput(value: PlaceOrderInput): string[]"));
+        assert!(records
+            .contains("if (!(Number(value.total) > 0)) errors.push(\"total: exclusive_min 0\");"));
+        assert!(records.contains(
+            "export async function fingerprintPlaceOrderInput(value: PlaceOrderInput): Promise<string>"
+        ));
+        let smoke = &files["src/operationsSmoke.ts"];
+        assert!(smoke.contains("// place_order: rejects conflicting replay"));
+        assert!(smoke.contains("// place_order: fingerprints canonical input"));
+    }
 }
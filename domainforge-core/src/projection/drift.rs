@@ -0,0 +1,396 @@
+//! Drift between a projection rendered in memory and the artifacts on disk.
+//!
+//! `domainforge project --check` (and `build --check`) render into an
+//! [`ArtifactSink::Memory`](super::sink::ArtifactSink) map and compare it here
+//! instead of writing: each changed file becomes a unified diff, and files
+//! only on one side are listed as missing or extra.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Lines of unchanged context around each hunk.
+const CONTEXT: usize = 3;
+
+/// Above this many line pairs (after trimming the common prefix and suffix)
+/// the diff is reported as one replacement hunk rather than aligned.
+const MAX_ALIGNED_CELLS: usize = 4_000_000;
+
+/// How the on-disk output differs from the projection.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Drift {
+    /// Relative path and unified diff (on disk → projected) per changed file.
+    pub changed: Vec<(String, String)>,
+    /// Projected files absent from disk.
+    pub missing: Vec<String>,
+    /// Files on disk the projection does not produce.
+    pub extra: Vec<String>,
+}
+
+impl Drift {
+    pub fn is_clean(&self) -> bool {
+        self.changed.is_empty() && self.missing.is_empty() && self.extra.is_empty()
+    }
+
+    /// One-line count, e.g. `2 changed, 1 missing, 0 extra file(s)`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} changed, {} missing, {} extra file(s)",
+            self.changed.len(),
+            self.missing.len(),
+            self.extra.len()
+        )
+    }
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (_, diff) in &self.changed {
+            write!(f, "{diff}")?;
+        }
+        if !self.missing.is_empty() {
+            writeln!(f, "Missing files (projected, not on disk):")?;
+            for path in &self.missing {
+                writeln!(f, "  {path}")?;
+            }
+        }
+        if !self.extra.is_empty() {
+            writeln!(f, "Extra files (on disk, not projected):")?;
+            for path in &self.extra {
+                writeln!(f, "  {path}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare a directory projection (`rendered` keyed by `/`-separated paths
+/// relative to `root`) against the files under `root`.
+pub fn compare_dir(root: &Path, rendered: &BTreeMap<String, String>) -> std::io::Result<Drift> {
+    let mut on_disk = BTreeSet::new();
+    if root.is_dir() {
+        collect_files(root, "", &mut on_disk)?;
+    }
+    let mut drift = Drift::default();
+    for (rel, projected) in rendered {
+        if !on_disk.contains(rel) {
+            drift.missing.push(rel.clone());
+            continue;
+        }
+        let current = read_lossy(&root.join(rel))?;
+        if current != *projected {
+            drift
+                .changed
+                .push((rel.clone(), unified_diff(rel, &current, projected)));
+        }
+    }
+    drift.extra = on_disk
+        .into_iter()
+        .filter(|rel| !rendered.contains_key(rel))
+        .collect();
+    Ok(drift)
+}
+
+/// Compare a single-file projection against `path`.
+pub fn compare_file(path: &Path, projected: &str) -> std::io::Result<Drift> {
+    let label = path.display().to_string();
+    let mut drift = Drift::default();
+    if !path.is_file() {
+        drift.missing.push(label);
+        return Ok(drift);
+    }
+    let current = read_lossy(path)?;
+    if current != projected {
+        let diff = unified_diff(&label, &current, projected);
+        drift.changed.push((label, diff));
+    }
+    Ok(drift)
+}
+
+/// Label of the header line that records when a projection ran,
+/// `generated-at: <RFC 3339>`, written in each artifact's comment syntax.
+pub const GENERATED_AT: &str = "generated-at:";
+
+/// The `generated-at:` marker for `created_at`, for a projection header.
+pub fn generated_at(created_at: &str) -> String {
+    format!("{GENERATED_AT} {created_at}")
+}
+
+/// The timestamp the first `generated-at:` marker in the artifacts at `path`
+/// records (a file, or the files under a directory in path order): the time a
+/// projection run without `--created-at` embedded, which `--check` renders
+/// with again. Timestamps elsewhere in the artifacts are ignored.
+pub fn recorded_timestamp(path: &Path) -> Option<String> {
+    let pattern = regex::Regex::new(&format!(
+        r"{GENERATED_AT} (\d{{4}}-\d{{2}}-\d{{2}}T\d{{2}}:\d{{2}}:\d{{2}}(?:\.\d+)?(?:Z|[+-]\d{{2}}:\d{{2}}))"
+    ))
+    .ok()?;
+    artifact_files(path)
+        .iter()
+        .filter_map(|file| read_lossy(file).ok())
+        .find_map(|text| {
+            pattern
+                .captures_iter(&text)
+                .map(|c| c[1].to_string())
+                .find(|ts| chrono::DateTime::parse_from_rfc3339(ts).is_ok())
+        })
+}
+
+/// Whether any artifact exists at `path` (a file, or a file under a directory).
+pub fn has_artifacts(path: &Path) -> bool {
+    !artifact_files(path).is_empty()
+}
+
+fn artifact_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_dir() {
+        let mut rels = BTreeSet::new();
+        if collect_files(path, "", &mut rels).is_err() {
+            return Vec::new();
+        }
+        rels.iter().map(|rel| path.join(rel)).collect()
+    } else if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Unified diff of `old` (on disk) against `new` (projected), with `a/` and
+/// `b/` headers in the style of `git diff`. Empty when the texts are equal.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let edits = edit_script(&old_lines, &new_lines);
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| edit.tag != ' ')
+        .map(|(i, _)| i)
+        .collect();
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for &i in &changes {
+        match groups.last_mut() {
+            Some((_, last)) if i - *last <= 2 * CONTEXT + 1 => *last = i,
+            _ => groups.push((i, i)),
+        }
+    }
+    for (first, last) in groups {
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(edits.len());
+        let hunk = &edits[start..end];
+        let old_len = hunk.iter().filter(|e| e.tag != '+').count();
+        let new_len = hunk.iter().filter(|e| e.tag != '-').count();
+        let old_start = edits[start].old + usize::from(old_len > 0);
+        let new_start = edits[start].new + usize::from(new_len > 0);
+        out.push_str(&format!(
+            "@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"
+        ));
+        for edit in hunk {
+            out.push(edit.tag);
+            out.push_str(edit.line);
+            if !edit.line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    out
+}
+
+/// One line of the edit script; `old`/`new` are the 0-based line indices in
+/// each text *before* this edit.
+struct Edit<'a> {
+    tag: char,
+    line: &'a str,
+    old: usize,
+    new: usize,
+}
+
+/// Longest-common-subsequence edit script, after trimming the common prefix
+/// and suffix.
+fn edit_script<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut edits = Vec::with_capacity(old.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    let mut push = |tag: char, line: &'a str| {
+        edits.push(Edit {
+            tag,
+            line,
+            old: i,
+            new: j,
+        });
+        if tag != '+' {
+            i += 1;
+        }
+        if tag != '-' {
+            j += 1;
+        }
+    };
+    for line in &old[..prefix] {
+        push(' ', line);
+    }
+
+    if a.len().saturating_mul(b.len()) > MAX_ALIGNED_CELLS {
+        for line in a {
+            push('-', line);
+        }
+        for line in b {
+            push('+', line);
+        }
+    } else {
+        // lcs[x][y] = LCS length of a[x..] and b[y..].
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for x in (0..a.len()).rev() {
+            for y in (0..b.len()).rev() {
+                lcs[x * width + y] = if a[x] == b[y] {
+                    lcs[(x + 1) * width + y + 1] + 1
+                } else {
+                    lcs[(x + 1) * width + y].max(lcs[x * width + y + 1])
+                };
+            }
+        }
+        let (mut x, mut y) = (0, 0);
+        while x < a.len() || y < b.len() {
+            if x < a.len() && y < b.len() && a[x] == b[y] {
+                push(' ', a[x]);
+                x += 1;
+                y += 1;
+            } else if y < b.len()
+                && (x == a.len() || lcs[x * width + y + 1] > lcs[(x + 1) * width + y])
+            {
+                push('+', b[y]);
+                y += 1;
+            } else {
+                push('-', a[x]);
+                x += 1;
+            }
+        }
+    }
+
+    for line in &old[old.len() - suffix..] {
+        push(' ', line);
+    }
+    edits
+}
+
+fn collect_files(dir: &Path, prefix: &str, out: &mut BTreeSet<String>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let rel = format!("{prefix}{name}");
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &format!("{rel}/"), out)?;
+        } else {
+            out.insert(rel);
+        }
+    }
+    Ok(())
+}
+
+fn read_lossy(path: &Path) -> std::io::Result<String> {
+    Ok(String::from_utf8_lossy(&std::fs::read(path)?).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+        assert_eq!(
+            unified_diff("x.txt", old, new),
+            "--- a/x.txt\n+++ b/x.txt\n\
+             @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+             @@ -10,3 +10,4 @@\n j\n k\n l\n+m\n"
+        );
+        assert_eq!(unified_diff("x.txt", old, old), "");
+    }
+
+    #[test]
+    fn test_unified_diff_marks_missing_trailing_newline() {
+        assert_eq!(
+            unified_diff("x", "a", "a\n"),
+            "--- a/x\n+++ b/x\n@@ -1,1 +1,1 @@\n-a\n\\ No newline at end of file\n+a\n"
+        );
+        assert_eq!(
+            unified_diff("x", "", "a\n"),
+            "--- a/x\n+++ b/x\n@@ -0,0 +1,1 @@\n+a\n"
+        );
+    }
+
+    #[test]
+    fn test_recorded_timestamp_reads_only_the_generated_at_marker() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("a.txt"),
+            "Entity \"Audit\" recorded at 2001-01-01T00:00:00Z\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("b.md"),
+            "# generated-at: 2026-10-19T07:12:30.140991087+00:00\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("c.xml"),
+            "<!-- generated-at: 2020-01-01T00:00:00Z -->\n",
+        )
+        .unwrap();
+        assert_eq!(
+            recorded_timestamp(temp.path()).as_deref(),
+            Some("2026-10-19T07:12:30.140991087+00:00")
+        );
+        assert_eq!(
+            recorded_timestamp(&temp.path().join("c.xml")).as_deref(),
+            Some("2020-01-01T00:00:00Z")
+        );
+        assert_eq!(recorded_timestamp(&temp.path().join("a.txt")), None);
+        assert!(has_artifacts(&temp.path().join("a.txt")));
+        assert!(!has_artifacts(&temp.path().join("missing")));
+    }
+
+    #[test]
+    fn test_compare_dir_reports_changed_missing_and_extra() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("nested")).unwrap();
+        std::fs::write(temp.path().join("same.txt"), "same\n").unwrap();
+        std::fs::write(temp.path().join("nested/changed.txt"), "old\n").unwrap();
+        std::fs::write(temp.path().join("stale.txt"), "stale\n").unwrap();
+        let rendered = BTreeMap::from([
+            ("same.txt".to_string(), "same\n".to_string()),
+            ("nested/changed.txt".to_string(), "new\n".to_string()),
+            ("added.txt".to_string(), "added\n".to_string()),
+        ]);
+
+        let drift = compare_dir(temp.path(), &rendered).unwrap();
+        assert_eq!(drift.missing, ["added.txt"]);
+        assert_eq!(drift.extra, ["stale.txt"]);
+        assert_eq!(drift.changed.len(), 1);
+        assert_eq!(drift.changed[0].0, "nested/changed.txt");
+        assert!(drift.changed[0].1.contains("-old\n+new\n"));
+        assert_eq!(drift.summary(), "1 changed, 1 missing, 1 extra file(s)");
+
+        let missing = compare_dir(&temp.path().join("absent"), &rendered).unwrap();
+        assert_eq!(missing.missing.len(), 3);
+        assert!(compare_dir(temp.path(), &BTreeMap::new())
+            .unwrap()
+            .changed
+            .is_empty());
+    }
+}
@@ -12,6 +12,7 @@
 //! regression-threshold gate live — never inside DomainForge.
 
 use super::ir::{AIOptimizationIR, DSPY_TARGET_VERSION};
+use crate::projection::drift::generated_at;
 
 /// Collapse embedded newlines (a docstring stays readable line-by-line) and
 /// neutralize the two sequences that could break out of a `"""` docstring: a
@@ -31,10 +32,11 @@ fn header(ir: &AIOptimizationIR, what: &str) -> String {
     format!(
         "# Generated by DomainForge (`--format dspy`) from {}.\n\
          # {}\n\
-         # Created: {}. Target DSPy API: {}. Do not edit by hand.\n",
+         # {}\n\
+         # Target DSPy API: {}. Do not edit by hand.\n",
         comment_safe(&ir.model_ref),
         comment_safe(what),
-        comment_safe(&ir.created_at),
+        generated_at(&comment_safe(&ir.created_at)),
         DSPY_TARGET_VERSION,
     )
 }
@@ -15,6 +15,7 @@
 //! projected authority/verification artifact must not look complete.
 
 use crate::graph::Graph;
+use crate::primitives::Flow;
 use std::collections::BTreeMap;
 
 /// A flow with display names resolved for its resource and endpoints.
@@ -24,6 +25,8 @@ pub struct ResolvedFlow {
     pub from: String,
     pub to: String,
     pub quantity: String,
+    /// Unit symbol of the flowing resource.
+    pub unit: String,
     pub namespace: String,
     /// Flow annotations (e.g. `@cqrs { "kind": "command" }`) copied from the
     /// graph flow's attribute map. `BTreeMap` gives deterministic ordering.
@@ -78,41 +81,11 @@ impl From<FlowError> for String {
 /// flow references an unknown entity or resource (loud dangling-reference
 /// policy — M4).
 pub fn collect_flows(graph: &Graph) -> Result<Vec<ResolvedFlow>, FlowError> {
-    let entity_name: BTreeMap<String, String> = graph
-        .all_entities()
-        .iter()
-        .map(|e| (e.id().to_string(), e.name().to_string()))
-        .collect();
-    let resource_name: BTreeMap<String, String> = graph
-        .all_resources()
-        .iter()
-        .map(|r| (r.id().to_string(), r.name().to_string()))
-        .collect();
-
-    let mut flows: Vec<ResolvedFlow> = Vec::new();
-    for f in graph.all_flows() {
-        let (Some(from), Some(to), Some(resource)) = (
-            entity_name.get(&f.from_id().to_string()),
-            entity_name.get(&f.to_id().to_string()),
-            resource_name.get(&f.resource_id().to_string()),
-        ) else {
-            return Err(FlowError::DanglingReference {
-                flow_id: f.id().to_string(),
-            });
-        };
-        flows.push(ResolvedFlow {
-            resource: resource.clone(),
-            from: from.clone(),
-            to: to.clone(),
-            quantity: f.quantity().to_string(),
-            namespace: f.namespace().to_string(),
-            annotations: f
-                .attributes()
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect(),
-        });
-    }
+    let mut flows = graph
+        .all_flows()
+        .into_iter()
+        .map(|f| resolve_flow(graph, f))
+        .collect::<Result<Vec<_>, _>>()?;
     flows.sort_by(|a, b| {
         a.resource
             .cmp(&b.resource)
@@ -123,6 +96,34 @@ pub fn collect_flows(graph: &Graph) -> Result<Vec<ResolvedFlow>, FlowError> {
     Ok(flows)
 }
 
+/// Resolve one flow to display names — for callers that select flows
+/// themselves (e.g. via `Graph::flows_from` / `flows_to`). Same
+/// dangling-reference policy as [`collect_flows`].
+pub fn resolve_flow(graph: &Graph, f: &Flow) -> Result<ResolvedFlow, FlowError> {
+    let (Some(from), Some(to), Some(resource)) = (
+        graph.get_entity(f.from_id()),
+        graph.get_entity(f.to_id()),
+        graph.get_resource(f.resource_id()),
+    ) else {
+        return Err(FlowError::DanglingReference {
+            flow_id: f.id().to_string(),
+        });
+    };
+    Ok(ResolvedFlow {
+        resource: resource.name().to_string(),
+        from: from.name().to_string(),
+        to: to.name().to_string(),
+        quantity: f.quantity().to_string(),
+        unit: resource.unit_symbol().to_string(),
+        namespace: f.namespace().to_string(),
+        annotations: f
+            .attributes()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    })
+}
+
 /// Derive the model's namespace. Returns an error if the model spans more
 /// than one namespace (multi-namespace models previously collapsed silently
 /// to the alphabetically-first entity's namespace — M5). The namespace is
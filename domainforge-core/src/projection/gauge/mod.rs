@@ -15,6 +15,7 @@
 //! [Gauge]: https://docs.gauge.org/writing-specifications/
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::ids::{sanitize_filename, NameRegistrar};
 use crate::projection::sink::ArtifactSink;
@@ -60,9 +61,11 @@ fn build_spec(
     let flows = collect_flows(graph)?; // M4: loud dangling-ref policy
 
     let mut s = String::new();
+    let marker = generated_at(created_at);
     s.push_str(&format!(
         "# {ns} flows\n\n\
-         // Gauge spec projected by DomainForge from {model_ref} at {created_at}.\n\
+         // Gauge spec projected by DomainForge from {model_ref}.\n\
+         // {marker}\n\
          // One scenario per SEA Flow. Wire step implementations to these step\n\
          // texts; parameters are quoted.\n\n"
     ));
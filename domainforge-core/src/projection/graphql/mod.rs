@@ -0,0 +1,605 @@
+//! GraphQL projection: the application contract becomes one SDL schema
+//! (`schema.graphql`).
+//!
+//! Mapping:
+//! - `enum` → GraphQL `enum` whose values are the members' wire values,
+//!   verbatim; a wire value that is not a GraphQL name is a projection error
+//! - `entity` with a body → object `type`; `ref<E>` fields link to `E`
+//! - `record` → object `type`; records used as an operation input become an
+//!   `input` type instead (suffixed `Input` when the record is also an
+//!   output), where `ref<E>` fields carry `E`'s key scalar
+//! - operation with `effect reads` → `Query` field; `creates` / `mutates` →
+//!   `Mutation` field; both take `input: <Input>!` and return the output
+//!   record, with the intent and failure codes as the description
+//! - non-`optional` fields are non-null (`!`); `list<T>` → `[T!]`
+//! - field constraints → the `@min`, `@max`, `@length`, `@items`, and
+//!   `@pattern` directives declared at the top of the schema
+//! - `uuid` → `UUID`, `timestamp` → `DateTime`, `decimal` and quantities →
+//!   `Decimal` (custom scalars); `int` → `Int`
+//!
+//! A schema without read operations still declares `Query` (GraphQL requires
+//! it) with a single nullable `_empty` field. Type names go through
+//! [`NameRegistrar`] and every section is sorted, so output is byte-identical
+//! run-to-run for a fixed `created_at`.
+
+use crate::application::{
+    ApplicationContract, EffectKind, EntityContract, FieldConstraint, FieldContract, FieldType,
+    OperationContract, RecordContract, ScalarType,
+};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::projection::drift::generated_at;
+use crate::projection::ids::{ident, pascal, NameRegistrar};
+use crate::projection::sink::ArtifactSink;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+
+/// Custom scalars and constraint directives every schema declares.
+const PRELUDE: &str = r#""""A UUID in its canonical hyphenated string form."""
+scalar UUID
+
+"""An RFC 3339 timestamp."""
+scalar DateTime
+
+"""An arbitrary-precision decimal, serialized as a string."""
+scalar Decimal
+
+"""Lower bound of a numeric field (exclusive when `exclusive` is true)."""
+directive @min(value: Decimal!, exclusive: Boolean = false) on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+
+"""Upper bound of a numeric field (exclusive when `exclusive` is true)."""
+directive @max(value: Decimal!, exclusive: Boolean = false) on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+
+"""Character-length bounds of a string field."""
+directive @length(min: Int, max: Int) on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+
+"""Item-count bounds of a list field."""
+directive @items(min: Int, max: Int) on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+
+"""Regular expression a string field must match."""
+directive @pattern(regex: String!) on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+"#;
+
+/// Emit the GraphQL schema into `sink`; returns the emitted relative path.
+pub fn emit(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    model_ref: &str,
+    created_at: Option<String>,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let body = Schema::new(graph, contract).render(model_ref, &created_at)?;
+    let file = "schema.graphql".to_string();
+    sink.write(&file, &body)?;
+    Ok(vec![file])
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_graphql_in_memory(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    model_ref: &str,
+    created_at: Option<String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, contract, model_ref, created_at, &mut sink)?;
+    Ok(map)
+}
+
+/// Whether a field is rendered in an object type or an input type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Output,
+    Input,
+}
+
+struct Schema<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+    /// enum symbol → type name.
+    enums: HashMap<String, String>,
+    /// entity → type name (entities with a body only).
+    entities: HashMap<ConceptId, String>,
+    /// record symbol → object type name.
+    outputs: HashMap<String, String>,
+    /// record symbol → input type name.
+    inputs: HashMap<String, String>,
+}
+
+impl<'a> Schema<'a> {
+    fn new(graph: &'a Graph, contract: &'a ApplicationContract) -> Self {
+        let mut reg = NameRegistrar::new();
+        for builtin in [
+            "UUID", "DateTime", "Decimal", "Int", "Float", "String", "Boolean", "ID", "Query",
+            "Mutation",
+        ] {
+            reg.register("ident", builtin);
+        }
+        let input_symbols: HashSet<&str> = contract
+            .operations
+            .iter()
+            .map(|op| op.input.0.as_str())
+            .collect();
+        let output_symbols: HashSet<&str> = contract
+            .operations
+            .iter()
+            .map(|op| op.output.0.as_str())
+            .collect();
+
+        let mut enums = HashMap::new();
+        for e in sorted_by_name(&contract.enums, |e| &e.name) {
+            enums.insert(e.id.0.clone(), reg.register("ident", &pascal(&e.name)));
+        }
+        let mut entities = HashMap::new();
+        for e in sorted_by_name(&contract.entities, |e| &e.name) {
+            entities.insert(
+                e.concept_id.clone(),
+                reg.register("ident", &pascal(&e.name)),
+            );
+        }
+        let mut outputs = HashMap::new();
+        let mut inputs = HashMap::new();
+        for r in sorted_by_name(&contract.records, |r| &r.name) {
+            let symbol = r.id.0.as_str();
+            let is_input = input_symbols.contains(symbol);
+            if !is_input || output_symbols.contains(symbol) {
+                outputs.insert(symbol.to_string(), reg.register("ident", &pascal(&r.name)));
+            }
+            if is_input {
+                let name = if outputs.contains_key(symbol) {
+                    format!("{}Input", pascal(&r.name))
+                } else {
+                    pascal(&r.name)
+                };
+                inputs.insert(symbol.to_string(), reg.register("ident", &name));
+            }
+        }
+        Schema {
+            graph,
+            contract,
+            enums,
+            entities,
+            outputs,
+            inputs,
+        }
+    }
+
+    fn render(&self, model_ref: &str, created_at: &str) -> Result<String, String> {
+        let mut s = format!(
+            "# GraphQL schema projected by DomainForge from {model_ref}.\n# {}\n\n{PRELUDE}",
+            generated_at(created_at)
+        );
+
+        for e in sorted_by_name(&self.contract.enums, |e| &e.name) {
+            let _ = write!(s, "\nenum {} {{\n", self.enums[&e.id.0]);
+            for member in &e.members {
+                if !is_enum_value_name(&member.wire) {
+                    return Err(format!(
+                        "enum '{}' member '{}' has wire value '{}', which is not a GraphQL enum value name",
+                        e.name, member.name, member.wire
+                    ));
+                }
+                let _ = writeln!(s, "  {}", member.wire);
+            }
+            s.push_str("}\n");
+        }
+
+        let mut types: Vec<(&str, String)> = Vec::new();
+        for e in &self.contract.entities {
+            types.push((&self.entities[&e.concept_id], self.entity_type(e)?));
+        }
+        for r in &self.contract.records {
+            if let Some(name) = self.outputs.get(&r.id.0) {
+                types.push((name, self.record_type("type", name, r, Position::Output)?));
+            }
+            if let Some(name) = self.inputs.get(&r.id.0) {
+                types.push((name, self.record_type("input", name, r, Position::Input)?));
+            }
+        }
+        types.sort();
+        for (_, body) in types {
+            let _ = write!(s, "\n{body}");
+        }
+
+        let mut operations: Vec<&OperationContract> = self.contract.operations.iter().collect();
+        operations.sort_by(|a, b| a.name.cmp(&b.name));
+        let (queries, mutations): (Vec<_>, Vec<_>) = operations
+            .into_iter()
+            .partition(|op| op.effect == EffectKind::Reads);
+        s.push_str("\ntype Query {\n");
+        if queries.is_empty() {
+            s.push_str("  \"\"\"No read operations are declared.\"\"\"\n  _empty: Boolean\n");
+        }
+        for op in &queries {
+            s.push_str(&self.operation_field(op)?);
+        }
+        s.push_str("}\n");
+        if !mutations.is_empty() {
+            s.push_str("\ntype Mutation {\n");
+            for op in &mutations {
+                s.push_str(&self.operation_field(op)?);
+            }
+            s.push_str("}\n");
+        }
+        Ok(s)
+    }
+
+    fn entity_type(&self, entity: &EntityContract) -> Result<String, String> {
+        let mut s = format!("type {} {{\n", self.entities[&entity.concept_id]);
+        for field in &entity.fields {
+            s.push_str(&self.field(field, Position::Output)?);
+        }
+        s.push_str("}\n");
+        Ok(s)
+    }
+
+    fn record_type(
+        &self,
+        keyword: &str,
+        name: &str,
+        record: &RecordContract,
+        position: Position,
+    ) -> Result<String, String> {
+        let mut s = format!("{keyword} {name} {{\n");
+        for field in &record.fields {
+            s.push_str(&self.field(field, position)?);
+        }
+        s.push_str("}\n");
+        Ok(s)
+    }
+
+    fn field(&self, field: &FieldContract, position: Position) -> Result<String, String> {
+        let mut ty = self.field_type(&field.field_type, position)?;
+        if !field.optional {
+            ty.push('!');
+        }
+        Ok(format!(
+            "  {}: {ty}{}\n",
+            ident(&field.name),
+            directives(self.graph, &field.constraints)
+        ))
+    }
+
+    fn field_type(&self, field_type: &FieldType, position: Position) -> Result<String, String> {
+        Ok(match field_type {
+            FieldType::Scalar { scalar } => scalar_name(*scalar).to_string(),
+            FieldType::Quantity { .. } => "Decimal".to_string(),
+            FieldType::EntityRef { entity } => match (position, self.entities.get(entity)) {
+                (Position::Output, Some(name)) => name.clone(),
+                _ => self.key_scalar(entity)?,
+            },
+            FieldType::Enum { symbol } => self
+                .enums
+                .get(&symbol.0)
+                .cloned()
+                .ok_or_else(|| format!("field references unknown enum '{}'", symbol.0))?,
+            FieldType::List { element } => {
+                format!("[{}!]", self.field_type(element, position)?)
+            }
+        })
+    }
+
+    /// Scalar of an entity's key field, for references in input position.
+    fn key_scalar(&self, entity: &ConceptId) -> Result<String, String> {
+        let target = self
+            .contract
+            .entities
+            .iter()
+            .find(|e| &e.concept_id == entity)
+            .or_else(|| self.graph.entity_contract(entity))
+            .ok_or_else(|| format!("entity reference '{entity}' has no entity contract"))?;
+        let key = target
+            .fields
+            .iter()
+            .find(|f| f.name == target.key_field)
+            .ok_or_else(|| {
+                format!(
+                    "entity '{}' key field '{}' is not declared",
+                    target.name, target.key_field
+                )
+            })?;
+        self.field_type(&key.field_type, Position::Input)
+    }
+
+    fn operation_field(&self, op: &OperationContract) -> Result<String, String> {
+        let input = self.inputs.get(&op.input.0).ok_or_else(|| {
+            format!(
+                "operation '{}' references unknown record '{}'",
+                op.name, op.input.0
+            )
+        })?;
+        let output = self.outputs.get(&op.output.0).ok_or_else(|| {
+            format!(
+                "operation '{}' references unknown record '{}'",
+                op.name, op.output.0
+            )
+        })?;
+        let mut doc = op.intent.clone();
+        if !op.failures.is_empty() {
+            doc.push_str("\n\nFailures:");
+            for failure in &op.failures {
+                let _ = write!(doc, "\n- `{}`: {}", failure.code, failure.meaning);
+            }
+        }
+        let name = pascal(&op.name);
+        let name = format!("{}{}", name[..1].to_ascii_lowercase(), &name[1..]);
+        Ok(format!(
+            "  {}\n  {name}(input: {input}!): {output}!\n",
+            block_string(&doc, "  ")
+        ))
+    }
+}
+
+fn sorted_by_name<T>(items: &[T], name: impl Fn(&T) -> &String) -> Vec<&T> {
+    let mut sorted: Vec<&T> = items.iter().collect();
+    sorted.sort_by(|a, b| name(a).cmp(name(b)));
+    sorted
+}
+
+fn scalar_name(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::String => "String",
+        ScalarType::Int => "Int",
+        ScalarType::Decimal => "Decimal",
+        ScalarType::Bool => "Boolean",
+        ScalarType::Timestamp => "DateTime",
+        ScalarType::Uuid => "UUID",
+    }
+}
+
+/// Constraint directives in a fixed order: bounds, lengths, items, pattern.
+fn directives(graph: &Graph, constraints: &[FieldConstraint]) -> String {
+    let mut min = None;
+    let mut max = None;
+    let (mut min_length, mut max_length) = (None, None);
+    let (mut min_items, mut max_items) = (None, None);
+    let mut pattern = None;
+    for constraint in constraints {
+        match constraint {
+            FieldConstraint::Min { value } => min = Some((value.normalize(), false)),
+            FieldConstraint::ExclusiveMin { value } => min = Some((value.normalize(), true)),
+            FieldConstraint::Max { value } => max = Some((value.normalize(), false)),
+            FieldConstraint::ExclusiveMax { value } => max = Some((value.normalize(), true)),
+            FieldConstraint::MinLength { value } => min_length = Some(*value),
+            FieldConstraint::MaxLength { value } => max_length = Some(*value),
+            FieldConstraint::MinItems { value } => min_items = Some(*value),
+            FieldConstraint::MaxItems { value } => max_items = Some(*value),
+            FieldConstraint::Pattern { pattern: id } => {
+                pattern = graph
+                    .all_patterns()
+                    .into_iter()
+                    .find(|p| p.id() == id)
+                    .map(|p| p.regex().to_string());
+            }
+        }
+    }
+    let bound = |name: &str, (value, exclusive): (rust_decimal::Decimal, bool)| {
+        if exclusive {
+            format!(" @{name}(value: \"{value}\", exclusive: true)")
+        } else {
+            format!(" @{name}(value: \"{value}\")")
+        }
+    };
+    let range = |name: &str, min: Option<u32>, max: Option<u32>| {
+        let args: Vec<String> = [("min", min), ("max", max)]
+            .into_iter()
+            .filter_map(|(k, v)| v.map(|v| format!("{k}: {v}")))
+            .collect();
+        if args.is_empty() {
+            String::new()
+        } else {
+            format!(" @{name}({})", args.join(", "))
+        }
+    };
+    let mut s = String::new();
+    if let Some(min) = min {
+        s.push_str(&bound("min", min));
+    }
+    if let Some(max) = max {
+        s.push_str(&bound("max", max));
+    }
+    s.push_str(&range("length", min_length, max_length));
+    s.push_str(&range("items", min_items, max_items));
+    if let Some(regex) = pattern {
+        let _ = write!(s, " @pattern(regex: {})", string_value(&regex));
+    }
+    s
+}
+
+/// GraphQL string literal.
+fn string_value(text: &str) -> String {
+    let mut out = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Whether `wire` is a GraphQL `Name` usable as an enum value
+/// (`true`, `false`, and `null` are reserved).
+fn is_enum_value_name(wire: &str) -> bool {
+    let mut chars = wire.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(wire, "true" | "false" | "null")
+}
+
+/// GraphQL block-string description, indented by `indent`.
+fn block_string(text: &str, indent: &str) -> String {
+    let escaped = text.replace("\"\"\"", "\\\"\"\"");
+    if !escaped.contains('\n') {
+        return format!("\"\"\"{escaped}\"\"\"");
+    }
+    let mut s = String::from("\"\"\"\n");
+    for line in escaped.lines() {
+        if line.is_empty() {
+            s.push('\n');
+        } else {
+            let _ = writeln!(s, "{indent}{line}");
+        }
+    }
+    let _ = write!(s, "{indent}\"\"\"");
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{resolve_application_contract, resolve_application_graph};
+
+    const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+    fn flagship() -> String {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../fixtures/application_generation/flagship");
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+        let sources = serde_json::json!({
+            "flagship/command-write.sea": read("command-write.sea"),
+            "flagship/query-read.sea": read("query-read.sea"),
+        })
+        .to_string();
+        let entry = "flagship/query-read.sea";
+        let graph = resolve_application_graph(entry, &sources).expect("graph");
+        let contract = resolve_application_contract(entry, &sources)
+            .expect("contract")
+            .contract;
+        let files = project_graphql_in_memory(&graph, &contract, entry, Some(FIXED_TS.to_string()))
+            .unwrap();
+        files["schema.graphql"].clone()
+    }
+
+    #[test]
+    fn entities_and_records_become_types_with_directives() {
+        let sdl = flagship();
+        assert!(sdl
+            .starts_with("# GraphQL schema projected by DomainForge from flagship/query-read.sea"));
+        assert!(sdl.contains("\nenum OrderStatus {\n  placed\n}\n"));
+        assert!(sdl.contains(
+            "\ntype Order {\n  order_id: UUID!\n  client_order_id: String! @length(min: 1, max: 64)\n  total: Decimal! @min(value: \"0\", exclusive: true)\n  item_count: Int! @min(value: \"1\")\n  status: OrderStatus!\n}\n"
+        ));
+        assert!(sdl.contains("\ninput GetOrderStatusInput {\n  order_id: UUID!\n}\n"));
+        assert!(sdl.contains(
+            "\ntype GetOrderStatusOutput {\n  order_id: UUID!\n  status: OrderStatus!\n}\n"
+        ));
+        // Input-only records are not also object types.
+        assert!(!sdl.contains("type GetOrderStatusInput"));
+    }
+
+    #[test]
+    fn operations_split_into_query_and_mutation() {
+        let sdl = flagship();
+        assert!(sdl.contains(
+            "\ntype Query {\n  \"\"\"\n  return the current non-sensitive status for one opaque ID\n\n  Failures:\n  - `order_not_found`: no order exists for the given order_id\n  \"\"\"\n  getOrderStatus(input: GetOrderStatusInput!): GetOrderStatusOutput!\n}\n"
+        ));
+        assert!(sdl.contains("  placeOrder(input: PlaceOrderInput!): PlaceOrderOutput!\n"));
+        assert!(sdl.find("type Mutation").unwrap() > sdl.find("type Query").unwrap());
+    }
+
+    #[test]
+    fn refs_link_objects_and_carry_keys_in_inputs() {
+        let source = r#"@namespace "shop"
+role "Clerk"
+pattern "Sku" matches "^[A-Z]{3}-[0-9]+$"
+entity "Customer" {
+    key customer_id: uuid
+}
+entity "Basket" {
+    key basket_id: uuid
+    customer: ref<Customer>
+    skus: list<string> optional (max_items 3)
+}
+record OpenBasket {
+    basket_id: uuid
+    customer: ref<Customer>
+    lead: string (pattern Sku)
+}
+record BasketOpened {
+    basket_id: uuid
+}
+operation open_basket {
+    intent "open a basket"
+    direction inbound
+    actor Clerk
+    access public
+    input OpenBasket
+    output BasketOpened
+    state Basket
+    effect creates Basket
+    transaction single_aggregate
+    failure invalid_basket for input_validation "basket failed record validation"
+    failure duplicate for idempotency_conflict, concurrency_conflict "basket exists"
+    idempotency keyed_by basket_id
+    concurrency unique_key basket_id
+    evidence operation_trace
+    lifecycle synchronous_request_response
+}
+"#;
+        let sources = serde_json::json!({ "main.sea": source }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).expect("graph");
+        let contract = resolve_application_contract("main.sea", &sources)
+            .expect("contract")
+            .contract;
+        let run = || {
+            project_graphql_in_memory(&graph, &contract, "main.sea", Some(FIXED_TS.to_string()))
+                .unwrap()
+        };
+        let files = run();
+        assert_eq!(files, run());
+        let sdl = &files["schema.graphql"];
+        assert!(sdl.contains(
+            "\ntype Basket {\n  basket_id: UUID!\n  customer: Customer!\n  skus: [String!] @items(max: 3)\n}\n"
+        ));
+        assert!(sdl.contains(
+            "\ninput OpenBasket {\n  basket_id: UUID!\n  customer: UUID!\n  lead: String! @pattern(regex: \"^[A-Z]{3}-[0-9]+$\")\n}\n"
+        ));
+        assert!(sdl.contains(
+            "\ntype Query {\n  \"\"\"No read operations are declared.\"\"\"\n  _empty: Boolean\n}\n"
+        ));
+        assert!(sdl.contains("  openBasket(input: OpenBasket!): BasketOpened!\n"));
+    }
+
+    #[test]
+    fn non_name_enum_wire_values_are_errors() {
+        let source = r#"@namespace "shop"
+enum Channel { web = "web", in_store = "in-store" }
+record Sale {
+    channel: Channel
+}
+"#;
+        let sources = serde_json::json!({ "main.sea": source }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).expect("graph");
+        let contract = resolve_application_contract("main.sea", &sources)
+            .expect("contract")
+            .contract;
+        let err =
+            project_graphql_in_memory(&graph, &contract, "main.sea", Some(FIXED_TS.to_string()))
+                .unwrap_err();
+        assert!(err.contains("member 'in_store'"), "{err}");
+        assert!(err.contains("'in-store'"), "{err}");
+        assert!(is_enum_value_name("_placed2"));
+        assert!(!is_enum_value_name("2nd"));
+        assert!(!is_enum_value_name("null"));
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(string_value(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(
+            block_string("say \"\"\"hi\"\"\"", ""),
+            "\"\"\"say \\\"\"\"hi\\\"\"\"\"\"\""
+        );
+    }
+}
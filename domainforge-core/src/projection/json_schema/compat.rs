@@ -0,0 +1,361 @@
+//! JSON Schema compatibility, for the closed-content subset this projection
+//! emits: a reader schema accepts a writer schema when every instance valid
+//! under the writer is also valid under the reader.
+//!
+//! Rules applied, writer → reader:
+//! - every writer `type` must be a reader `type` (`integer` reads as
+//!   `number`); a reader `enum` must contain every writer value
+//! - `format`, `pattern`, and `const` must be unchanged where the reader sets
+//!   them
+//! - lower bounds (`minimum`, `exclusiveMinimum`, `minLength`, `minItems`)
+//!   may only decrease and upper bounds may only increase
+//! - a property required by the reader must be required by the writer
+//! - a closed reader (`additionalProperties: false`) must declare every
+//!   writer property, and the writer must be closed too
+//! - shared properties and array `items` are compared recursively; `$ref`
+//!   is resolved against each document's root
+
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// Every reason instances valid under `writer` may be invalid under `reader`.
+pub(crate) fn reader_accepts(reader: &Value, writer: &Value) -> Vec<String> {
+    let mut issues = Vec::new();
+    accepts(reader, reader, writer, writer, "$", &mut issues, 0);
+    issues
+}
+
+/// `$ref` chains deeper than this are reported instead of followed.
+const MAX_DEPTH: usize = 32;
+
+const LOWER_BOUNDS: [&str; 4] = ["minimum", "exclusiveMinimum", "minLength", "minItems"];
+const UPPER_BOUNDS: [&str; 4] = ["maximum", "exclusiveMaximum", "maxLength", "maxItems"];
+
+fn accepts(
+    reader_root: &Value,
+    reader: &Value,
+    writer_root: &Value,
+    writer: &Value,
+    path: &str,
+    issues: &mut Vec<String>,
+    depth: usize,
+) {
+    if depth > MAX_DEPTH {
+        issues.push(format!("{path}: `$ref` nesting is too deep to compare"));
+        return;
+    }
+    let (Some(r), Some(w)) = (
+        resolve(reader_root, reader, path, issues),
+        resolve(writer_root, writer, path, issues),
+    ) else {
+        return;
+    };
+
+    check_types(r, w, path, issues);
+    check_enum(r, w, path, issues);
+    for keyword in ["format", "pattern", "const"] {
+        if let Some(expected) = r.get(keyword) {
+            if w.get(keyword) != Some(expected) {
+                issues.push(format!(
+                    "{path}: `{keyword}` changed from {} to {expected}",
+                    w.get(keyword).map_or("none".to_string(), Value::to_string)
+                ));
+            }
+        }
+    }
+    for keyword in LOWER_BOUNDS {
+        check_bound(r, w, keyword, path, issues, |reader, writer| {
+            reader <= writer
+        });
+    }
+    for keyword in UPPER_BOUNDS {
+        check_bound(r, w, keyword, path, issues, |reader, writer| {
+            reader >= writer
+        });
+    }
+    check_object(reader_root, r, writer_root, w, path, issues, depth);
+
+    if let Some(items) = r.get("items") {
+        match w.get("items") {
+            Some(written) => accepts(
+                reader_root,
+                items,
+                writer_root,
+                written,
+                &format!("{path}[]"),
+                issues,
+                depth + 1,
+            ),
+            None => issues.push(format!("{path}: reader constrains array `items`")),
+        }
+    }
+}
+
+/// Follow local `$ref`s to the schema object they name.
+fn resolve<'v>(
+    root: &'v Value,
+    schema: &'v Value,
+    path: &str,
+    issues: &mut Vec<String>,
+) -> Option<&'v Map<String, Value>> {
+    let mut current = schema;
+    for _ in 0..MAX_DEPTH {
+        match current.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let Some(target) = reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| root.pointer(pointer))
+                else {
+                    issues.push(format!("{path}: cannot resolve `$ref` {reference}"));
+                    return None;
+                };
+                current = target;
+            }
+            None => return current.as_object(),
+        }
+    }
+    issues.push(format!("{path}: `$ref` nesting is too deep to compare"));
+    None
+}
+
+fn types(schema: &Map<String, Value>) -> Option<BTreeSet<&str>> {
+    match schema.get("type")? {
+        Value::String(t) => Some(BTreeSet::from([t.as_str()])),
+        Value::Array(ts) => Some(ts.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn check_types(
+    r: &Map<String, Value>,
+    w: &Map<String, Value>,
+    path: &str,
+    issues: &mut Vec<String>,
+) {
+    let Some(reader_types) = types(r) else {
+        return;
+    };
+    let Some(writer_types) = types(w) else {
+        issues.push(format!(
+            "{path}: reader restricts `type` to {} but the writer allows any type",
+            join(&reader_types)
+        ));
+        return;
+    };
+    for t in writer_types {
+        let readable =
+            reader_types.contains(t) || (t == "integer" && reader_types.contains("number"));
+        if !readable {
+            issues.push(format!(
+                "{path}: writer type `{t}` is not accepted by reader type {}",
+                join(&reader_types)
+            ));
+        }
+    }
+}
+
+fn check_enum(
+    r: &Map<String, Value>,
+    w: &Map<String, Value>,
+    path: &str,
+    issues: &mut Vec<String>,
+) {
+    let Some(reader_values) = r.get("enum").and_then(Value::as_array) else {
+        return;
+    };
+    let Some(writer_values) = w.get("enum").and_then(Value::as_array) else {
+        issues.push(format!("{path}: reader restricts values to an `enum`"));
+        return;
+    };
+    for value in writer_values {
+        if !reader_values.contains(value) {
+            issues.push(format!(
+                "{path}: writer value {value} is not in the reader `enum`"
+            ));
+        }
+    }
+}
+
+fn check_bound(
+    r: &Map<String, Value>,
+    w: &Map<String, Value>,
+    keyword: &str,
+    path: &str,
+    issues: &mut Vec<String>,
+    looser: fn(f64, f64) -> bool,
+) {
+    let Some(reader) = r.get(keyword).and_then(Value::as_f64) else {
+        return;
+    };
+    match w.get(keyword).and_then(Value::as_f64) {
+        Some(writer) if looser(reader, writer) => {}
+        Some(_) => issues.push(format!(
+            "{path}: `{keyword}` tightened from {} to {}",
+            w[keyword], r[keyword]
+        )),
+        None => issues.push(format!("{path}: `{keyword}` {} added", r[keyword])),
+    }
+}
+
+fn check_object(
+    reader_root: &Value,
+    r: &Map<String, Value>,
+    writer_root: &Value,
+    w: &Map<String, Value>,
+    path: &str,
+    issues: &mut Vec<String>,
+    depth: usize,
+) {
+    let empty = Map::new();
+    let reader_props = r
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let writer_props = w
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let required = |s: &Map<String, Value>| -> BTreeSet<String> {
+        s.get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    let writer_required = required(w);
+    for name in required(r) {
+        if !writer_required.contains(&name) {
+            issues.push(format!("{}: property became required", child(path, &name)));
+        }
+    }
+    let closed =
+        |s: &Map<String, Value>| s.get("additionalProperties") == Some(&Value::Bool(false));
+    if closed(r) {
+        if !closed(w) {
+            issues.push(format!(
+                "{path}: reader no longer allows additional properties"
+            ));
+        }
+        for name in writer_props.keys() {
+            if !reader_props.contains_key(name) {
+                issues.push(format!(
+                    "{}: property is no longer allowed",
+                    child(path, name)
+                ));
+            }
+        }
+    }
+    for (name, reader_prop) in reader_props {
+        if let Some(writer_prop) = writer_props.get(name) {
+            accepts(
+                reader_root,
+                reader_prop,
+                writer_root,
+                writer_prop,
+                &child(path, name),
+                issues,
+                depth + 1,
+            );
+        }
+    }
+}
+
+fn child(path: &str, name: &str) -> String {
+    format!("{path}.{name}")
+}
+
+fn join(types: &BTreeSet<&str>) -> String {
+    types
+        .iter()
+        .map(|t| format!("`{t}`"))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(properties: Value, required: Value) -> Value {
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    #[test]
+    fn required_and_removed_properties() {
+        let old = object(json!({ "id": { "type": "string" } }), json!(["id"]));
+        let optional_added = object(
+            json!({ "id": { "type": "string" }, "note": { "type": "string" } }),
+            json!(["id"]),
+        );
+        let required_added = object(
+            json!({ "id": { "type": "string" }, "note": { "type": "string" } }),
+            json!(["id", "note"]),
+        );
+        assert!(reader_accepts(&optional_added, &old).is_empty());
+        assert_eq!(
+            reader_accepts(&required_added, &old),
+            ["$.note: property became required"]
+        );
+        // The old, closed schema rejects the new property.
+        assert_eq!(
+            reader_accepts(&old, &optional_added),
+            ["$.note: property is no longer allowed"]
+        );
+    }
+
+    #[test]
+    fn types_widen_and_enums_grow() {
+        let int = object(json!({ "n": { "type": "integer" } }), json!([]));
+        let number = object(json!({ "n": { "type": "number" } }), json!([]));
+        assert!(reader_accepts(&number, &int).is_empty());
+        assert_eq!(
+            reader_accepts(&int, &number),
+            ["$.n: writer type `number` is not accepted by reader type `integer`"]
+        );
+
+        let status = |values: Value| {
+            json!({
+                "type": "object",
+                "properties": { "s": { "$ref": "#/$defs/S" } },
+                "additionalProperties": false,
+                "$defs": { "S": { "type": "string", "enum": values } },
+            })
+        };
+        let narrow = status(json!(["a"]));
+        let wide = status(json!(["a", "b"]));
+        assert!(reader_accepts(&wide, &narrow).is_empty());
+        assert_eq!(
+            reader_accepts(&narrow, &wide),
+            ["$.s: writer value \"b\" is not in the reader `enum`"]
+        );
+    }
+
+    #[test]
+    fn bounds_and_patterns() {
+        let schema = |extra: Value| {
+            let mut prop = json!({ "type": "string" });
+            prop.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            object(json!({ "s": prop }), json!([]))
+        };
+        let loose = schema(json!({ "maxLength": 10 }));
+        let tight = schema(json!({ "maxLength": 5, "pattern": "^a" }));
+        assert!(reader_accepts(&loose, &tight).is_empty());
+        assert_eq!(
+            reader_accepts(&tight, &loose),
+            [
+                "$.s: `pattern` changed from none to \"^a\"",
+                "$.s: `maxLength` tightened from 10 to 5"
+            ]
+        );
+    }
+}
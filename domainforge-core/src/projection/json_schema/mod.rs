@@ -0,0 +1,310 @@
+//! JSON Schema projection: one JSON Schema 2020-12 document
+//! (`<namespace>.<Name>.schema.json`) per resource flow and application
+//! record, built from the shared
+//! [`schema_registry`](crate::projection::schema_registry) payload schemas.
+//!
+//! Mapping:
+//! - payload schema → closed `object` (`additionalProperties: false`) with
+//!   `$id` `urn:domainforge:<namespace>.<Name>`, `title`, and `description`
+//! - non-`optional` fields → `required`
+//! - `string` → `string`; `int` → `integer`; `decimal` and quantities →
+//!   `number`; `bool` → `boolean`; `uuid` → `string` with format `uuid`;
+//!   `timestamp` → `string` with format `date-time`
+//! - `enum` → a `$defs` entry with `enum` over the members' wire values,
+//!   referenced through `$ref`
+//! - `list<T>` → `array` of `T`
+//! - `min`/`max`/`exclusive_*` → `minimum`/`maximum`/`exclusiveMinimum`/
+//!   `exclusiveMaximum`; `min_length`/`max_length` → `minLength`/`maxLength`;
+//!   `min_items`/`max_items` → `minItems`/`maxItems`; `pattern` → `pattern`
+//!
+//! Provenance goes in the root `$comment`; everything else is stable, so
+//! output is byte-identical run-to-run for a fixed `created_at`.
+
+pub mod compat;
+
+use crate::application::{ApplicationContract, TypedValue};
+use crate::graph::Graph;
+use crate::projection::drift::generated_at;
+use crate::projection::schema_registry::{
+    check_published, payload_schemas, PayloadConstraint, PayloadField, PayloadSchema, PayloadType,
+    RegistryCheckResult, RegistryCompatibility,
+};
+use crate::projection::sink::ArtifactSink;
+use rust_decimal::Decimal;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// JSON Schema dialect of every emitted document.
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Emit one `.schema.json` per payload schema into `sink`; returns the
+/// emitted relative paths.
+pub fn emit(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    model_ref: &str,
+    created_at: Option<String>,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let comment = format!(
+        "Projected by DomainForge from {model_ref}. {}",
+        generated_at(&created_at)
+    );
+    let mut files = Vec::new();
+    for schema in payload_schemas(graph, contract)? {
+        let file = format!("{}.schema.json", schema.full_name());
+        let body = serde_json::to_string_pretty(&json_schema(&schema, &comment))
+            .map_err(|e| format!("failed to serialize JSON Schema: {e}"))?;
+        sink.write(&file, &format!("{body}\n"))?;
+        files.push(file);
+    }
+    Ok(files)
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_json_schema_in_memory(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    model_ref: &str,
+    created_at: Option<String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, contract, model_ref, created_at, &mut sink)?;
+    Ok(map)
+}
+
+/// Check projected `.schema.json` files against the published ones in
+/// `published`: a reader schema accepts a writer schema when every instance
+/// valid under the writer is valid under the reader.
+pub fn check_compatibility(
+    published: &Path,
+    files: &BTreeMap<String, String>,
+    mode: RegistryCompatibility,
+) -> Result<RegistryCheckResult, String> {
+    check_published(published, files, mode, compat::reader_accepts)
+}
+
+fn json_schema(schema: &PayloadSchema, comment: &str) -> Value {
+    let mut defs = Map::new();
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in &schema.fields {
+        properties.insert(field.name.clone(), property(field, &mut defs));
+        if !field.optional {
+            required.push(Value::String(field.name.clone()));
+        }
+    }
+    let mut out = Map::new();
+    out.insert("$schema".into(), json!(DIALECT));
+    out.insert(
+        "$id".into(),
+        json!(format!("urn:domainforge:{}", schema.full_name())),
+    );
+    out.insert("$comment".into(), json!(comment));
+    out.insert("title".into(), json!(schema.name));
+    out.insert("description".into(), json!(schema.doc));
+    out.insert("type".into(), json!("object"));
+    out.insert("properties".into(), Value::Object(properties));
+    out.insert("required".into(), Value::Array(required));
+    out.insert("additionalProperties".into(), json!(false));
+    if !defs.is_empty() {
+        out.insert("$defs".into(), Value::Object(defs));
+    }
+    Value::Object(out)
+}
+
+fn property(field: &PayloadField, defs: &mut Map<String, Value>) -> Value {
+    let mut out = match type_schema(&field.ty, defs) {
+        Value::Object(map) => map,
+        _ => unreachable!("type schemas are objects"),
+    };
+    if let Some(doc) = &field.doc {
+        out.insert("description".into(), json!(doc));
+    }
+    if let Some(default) = &field.default {
+        out.insert("default".into(), default_value(default));
+    }
+    for constraint in &field.constraints {
+        let (keyword, value) = match constraint {
+            PayloadConstraint::Minimum { value, exclusive } => (
+                if *exclusive {
+                    "exclusiveMinimum"
+                } else {
+                    "minimum"
+                },
+                number(value),
+            ),
+            PayloadConstraint::Maximum { value, exclusive } => (
+                if *exclusive {
+                    "exclusiveMaximum"
+                } else {
+                    "maximum"
+                },
+                number(value),
+            ),
+            PayloadConstraint::MinLength(n) => ("minLength", json!(n)),
+            PayloadConstraint::MaxLength(n) => ("maxLength", json!(n)),
+            PayloadConstraint::MinItems(n) => ("minItems", json!(n)),
+            PayloadConstraint::MaxItems(n) => ("maxItems", json!(n)),
+            PayloadConstraint::Pattern(regex) => ("pattern", json!(regex)),
+        };
+        out.insert(keyword.into(), value);
+    }
+    Value::Object(out)
+}
+
+fn type_schema(ty: &PayloadType, defs: &mut Map<String, Value>) -> Value {
+    match ty {
+        PayloadType::String => json!({ "type": "string" }),
+        PayloadType::Long => json!({ "type": "integer" }),
+        PayloadType::Decimal => json!({ "type": "number" }),
+        PayloadType::Boolean => json!({ "type": "boolean" }),
+        PayloadType::Timestamp => json!({ "type": "string", "format": "date-time" }),
+        PayloadType::Uuid => json!({ "type": "string", "format": "uuid" }),
+        PayloadType::Enum(e) => {
+            defs.entry(e.name.clone())
+                .or_insert_with(|| json!({ "type": "string", "enum": e.symbols }));
+            json!({ "$ref": format!("#/$defs/{}", e.name) })
+        }
+        PayloadType::List(element) => {
+            json!({ "type": "array", "items": type_schema(element, defs) })
+        }
+    }
+}
+
+/// A decimal as a JSON number, integral when it has no fraction.
+fn number(value: &Decimal) -> Value {
+    serde_json::from_str(&value.normalize().to_string()).unwrap_or(Value::Null)
+}
+
+fn default_value(value: &TypedValue) -> Value {
+    match value {
+        TypedValue::String(s) => json!(s),
+        TypedValue::Int(i) => json!(i),
+        TypedValue::Decimal(d) => number(d),
+        TypedValue::Bool(b) => json!(b),
+        TypedValue::Timestamp(t) => json!(t.to_rfc3339()),
+        TypedValue::Uuid(u) => json!(u.to_string()),
+        TypedValue::Quantity { base_value, .. } => number(base_value),
+        TypedValue::EntityRef { key, .. } => default_value(key),
+        TypedValue::Enum { wire, .. } => json!(wire),
+        TypedValue::List(items) => Value::Array(items.iter().map(default_value).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{resolve_application_contract, resolve_application_graph};
+
+    const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+    const SOURCE: &str = r#"@namespace "shop"
+Resource "Pallet" units in shop
+pattern "Sku" matches "^[A-Z]{3}$"
+enum Tier { gold = "gold", silver = "silver-plus" }
+record Signup {
+    email: string (min_length 3, max_length 254)
+    tier: Tier
+    previous: Tier optional
+    skus: list<string> optional (max_items 3)
+    code: string (pattern Sku)
+    seats: int (min 1, max 50)
+    budget: decimal (exclusive_min 0.5)
+}
+"#;
+
+    fn project(source: &str) -> BTreeMap<String, String> {
+        let sources = serde_json::json!({ "main.sea": source }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).expect("graph");
+        let contract = resolve_application_contract("main.sea", &sources)
+            .expect("contract")
+            .contract;
+        project_json_schema_in_memory(&graph, &contract, "main.sea", Some(FIXED_TS.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn records_become_closed_objects_with_constraints() {
+        let files = project(SOURCE);
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            ["shop.PalletFlow.schema.json", "shop.Signup.schema.json"]
+        );
+        let signup: Value = serde_json::from_str(&files["shop.Signup.schema.json"]).unwrap();
+        assert_eq!(signup["$schema"], DIALECT);
+        assert_eq!(signup["$id"], "urn:domainforge:shop.Signup");
+        assert_eq!(
+            signup["$comment"],
+            format!("Projected by DomainForge from main.sea. generated-at: {FIXED_TS}")
+        );
+        assert_eq!(signup["additionalProperties"], false);
+        assert_eq!(
+            signup["required"],
+            json!(["email", "tier", "code", "seats", "budget"])
+        );
+        let props = &signup["properties"];
+        assert_eq!(
+            props["email"],
+            json!({ "type": "string", "minLength": 3, "maxLength": 254 })
+        );
+        assert_eq!(props["tier"], json!({ "$ref": "#/$defs/Tier" }));
+        assert_eq!(props["previous"], props["tier"]);
+        assert_eq!(
+            signup["$defs"]["Tier"],
+            json!({ "type": "string", "enum": ["gold", "silver-plus"] })
+        );
+        assert_eq!(
+            props["skus"],
+            json!({ "type": "array", "items": { "type": "string" }, "maxItems": 3 })
+        );
+        assert_eq!(props["code"]["pattern"], "^[A-Z]{3}$");
+        assert_eq!(
+            props["seats"],
+            json!({ "type": "integer", "minimum": 1, "maximum": 50 })
+        );
+        assert_eq!(
+            props["budget"],
+            json!({ "type": "number", "exclusiveMinimum": 0.5 })
+        );
+        assert_eq!(files, project(SOURCE));
+    }
+
+    #[test]
+    fn tightened_constraints_fail_backward_but_pass_forward() {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, content) in project(SOURCE) {
+            std::fs::write(dir.path().join(file), content).unwrap();
+        }
+        let tightened = project(&SOURCE.replace("(min 1, max 50)", "(min 1, max 10)"));
+        let backward =
+            check_compatibility(dir.path(), &tightened, RegistryCompatibility::Backward).unwrap();
+        assert_eq!(backward.checked.len(), 2);
+        assert_eq!(
+            backward.incompatibilities.len(),
+            1,
+            "{}",
+            backward.to_report()
+        );
+        assert_eq!(
+            backward.incompatibilities[0].description,
+            "$.seats: `maximum` tightened from 50 to 10"
+        );
+        assert!(
+            check_compatibility(dir.path(), &tightened, RegistryCompatibility::Forward)
+                .unwrap()
+                .is_compatible()
+        );
+        assert!(
+            !check_compatibility(dir.path(), &tightened, RegistryCompatibility::Full)
+                .unwrap()
+                .is_compatible()
+        );
+    }
+}
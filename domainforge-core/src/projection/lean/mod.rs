@@ -14,6 +14,7 @@ pub mod expr;
 
 use crate::graph::Graph;
 use crate::policy::Policy;
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
 use expr::{lower_policy, scaled, unique_idents, GroundCtx, Lowered};
 use std::collections::BTreeMap;
@@ -279,9 +280,9 @@ fn obligation_doc(p: &Policy, base_doc: &str, reason: &str) -> String {
 
 fn header(model_ref: &str, created_at: &str) -> String {
     format!(
-        "/-\nGenerated by DomainForge (`--format lean`) from {}.\nCreated: {}. Do not edit by hand.\n-/\n",
+        "/-\nGenerated by DomainForge (`--format lean`) from {}.\n{}\nDo not edit by hand.\n-/\n",
         doc_safe(model_ref),
-        doc_safe(created_at)
+        generated_at(&doc_safe(created_at))
     )
 }
 
@@ -293,9 +294,11 @@ fn lakefile() -> String {
 }
 
 fn readme(model_ref: &str, created_at: &str) -> String {
+    let marker = generated_at(created_at);
     format!(
         "# DomainForge Lean 4 package\n\n\
-         Generated from `{model_ref}` at {created_at}.\n\n\
+         Generated from `{model_ref}`.\n\n\
+         {marker}\n\n\
          - `DomainForge/` — types, model facts, and machine-checked policy theorems.\n  \
          Compiles **sorry-free**: every proof is discharged by `decide` against the\n  \
          declared model. `lake build` re-verifies everything.\n\
@@ -2,6 +2,7 @@ pub mod ai_learning;
 pub mod alloy;
 pub mod archimate;
 pub mod asyncapi;
+pub mod avro;
 pub mod baml;
 pub mod bpmn;
 pub mod buf;
@@ -12,18 +13,30 @@ pub mod cmmn;
 pub mod contracts;
 pub mod dagger;
 pub mod devbox;
+pub mod diagram;
+pub mod docs;
 pub mod domain;
+pub mod drift;
 pub mod dspy;
 pub mod engine;
 pub mod flows;
 pub mod gauge;
+pub mod graphql;
 pub mod ids;
+pub mod json_schema;
 pub mod lean;
 pub mod otel;
+pub mod plugin;
 pub mod protobuf;
+pub mod protobuf_contract;
 pub mod rdf;
 pub mod registry;
+pub mod schema_registry;
 pub mod sink;
+pub mod sql_postgres;
+pub mod structurizr;
+#[cfg(feature = "cli")]
+pub mod template;
 pub mod tla;
 pub mod zenml;
 
@@ -36,4 +49,8 @@ pub use protobuf::{
     ProtoType, ProtobufEngine, ScalarType, SchemaHistory, StreamingMode, ViolationType,
     WellKnownType,
 };
+pub use protobuf_contract::{
+    contract_to_proto, merge_application_contract, project_application_contract, ContractProto,
+    APPLICATION_CONTRACT_PROTO,
+};
 pub use registry::ProjectionRegistry;
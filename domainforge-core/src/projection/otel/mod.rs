@@ -27,6 +27,7 @@ pub mod ir;
 pub mod yaml;
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::sink::ArtifactSink;
 use ir::TelemetryIR;
 use std::collections::BTreeMap;
@@ -88,9 +89,10 @@ pub fn project_otel_semconv_in_memory(
 fn header(marker: char, model_ref: &str, created_at: &str) -> String {
     format!(
         "{marker} Generated by DomainForge (--format otel-semconv) from {}.\n\
-         {marker} Created: {}. Do not edit by hand.\n\n",
+         {marker} {}\n\
+         {marker} Do not edit by hand.\n\n",
         model_ref.replace(['\n', '\r'], " "),
-        created_at.replace(['\n', '\r'], " ")
+        generated_at(&created_at.replace(['\n', '\r'], " "))
     )
 }
 
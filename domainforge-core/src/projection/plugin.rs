@@ -0,0 +1,213 @@
+//! External projection plugins (`project --format plugin:<name>`).
+//!
+//! A plugin is a local executable in any language. DomainForge writes one
+//! [`PluginRequest`] as JSON to its stdin — the resolved graph, the
+//! application contract and the canonical semantic envelope — and reads one
+//! [`PluginResponse`] from its stdout: a map of relative path to file content.
+//! Artifacts are written through the shared [`ArtifactSink`], so a plugin gets
+//! the same path-traversal checks (and `--check` support) as a built-in
+//! projection. The protocol is specified in
+//! `docs/reference/projection-plugins.md`.
+//!
+//! Plugins are located by name: a `[plugins]` entry in the nearest
+//! `domainforge.toml`, else a `domainforge-projection-<name>` executable on
+//! `PATH`.
+
+use crate::application::envelope::CanonicalSemanticEnvelopeDocument;
+use crate::application::{ApplicationContract, ProducerIdentity};
+use crate::graph::Graph;
+use crate::package::{find_workspace_root, PackageManifest, MANIFEST_FILE_NAME};
+use crate::projection::sink::ArtifactSink;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Version tag carried by every request and required on every response.
+pub const PLUGIN_PROTOCOL_VERSION: &str = "domainforge-projection-plugin/v1";
+
+/// `PATH` lookup name of a plugin: this prefix followed by the plugin name.
+pub const PLUGIN_EXECUTABLE_PREFIX: &str = "domainforge-projection-";
+
+/// The document sent to a plugin on stdin.
+#[derive(Debug, Serialize)]
+pub struct PluginRequest<'a> {
+    pub protocol: &'static str,
+    pub plugin: &'a str,
+    pub producer: ProducerIdentity,
+    /// The model entry file, as given on the command line.
+    pub input: String,
+    /// `--plugin-option KEY=VALUE` pairs, passed through uninterpreted.
+    pub options: BTreeMap<String, String>,
+    pub namespace: Option<&'a str>,
+    pub created_at: Option<&'a str>,
+    pub graph: &'a Graph,
+    pub application_contract: &'a ApplicationContract,
+    pub semantic_envelope: &'a CanonicalSemanticEnvelopeDocument,
+}
+
+/// The document a plugin writes to stdout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginResponse {
+    pub protocol: String,
+    /// `/`-separated path relative to the output directory → file content.
+    pub artifacts: BTreeMap<String, String>,
+    /// Non-fatal notes, shown to the user on stderr.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Plugin names follow package names: lowercase ASCII letters, digits, `-`
+/// and `_`, starting with a letter.
+pub fn validate_plugin_name(name: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid plugin name '{name}': use lowercase letters, digits, '-' and '_', starting with a letter"
+        ))
+    }
+}
+
+/// Find the executable for plugin `name`, looking first in the `[plugins]`
+/// table of the nearest `domainforge.toml` at or above `start`, then on
+/// `PATH`.
+pub fn locate_plugin(name: &str, start: &Path) -> Result<PathBuf, String> {
+    validate_plugin_name(name)?;
+    if let Some(root) = find_workspace_root(start).map_err(|e| e.to_string())? {
+        let manifest =
+            PackageManifest::from_file(root.join(MANIFEST_FILE_NAME)).map_err(|e| e.to_string())?;
+        if let Some(path) = manifest.plugins.get(name) {
+            let path = root.join(path);
+            if !path.is_file() {
+                return Err(format!(
+                    "plugin '{name}' is declared in {} as {}, which does not exist",
+                    root.join(MANIFEST_FILE_NAME).display(),
+                    path.display()
+                ));
+            }
+            return Ok(path);
+        }
+    }
+    let executable = format!(
+        "{PLUGIN_EXECUTABLE_PREFIX}{name}{}",
+        std::env::consts::EXE_SUFFIX
+    );
+    std::env::var_os("PATH")
+        .iter()
+        .flat_map(std::env::split_paths)
+        .map(|dir| dir.join(&executable))
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            format!(
+                "no projection plugin '{name}': declare it under [plugins] in {MANIFEST_FILE_NAME} or put {executable} on PATH"
+            )
+        })
+}
+
+/// Run the plugin at `executable` with `request` on stdin and parse its
+/// response. The plugin's stderr is passed through.
+pub fn run_plugin(executable: &Path, request: &PluginRequest) -> Result<PluginResponse, String> {
+    let name = request.plugin;
+    let payload = serde_json::to_vec(request)
+        .map_err(|e| format!("failed to serialize the request for plugin '{name}': {e}"))?;
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| {
+            format!(
+                "failed to start plugin '{name}' ({}): {e}",
+                executable.display()
+            )
+        })?;
+
+    // Feed stdin from a thread so a plugin that writes before it has read
+    // the whole request cannot deadlock on a full pipe.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = std::thread::spawn(move || stdin.write_all(&payload));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("plugin '{name}' failed: {e}"))?;
+    let written = writer.join().expect("stdin writer does not panic");
+    if !output.status.success() {
+        return Err(format!("plugin '{name}' exited with {}", output.status));
+    }
+    written.map_err(|e| format!("failed to send the request to plugin '{name}': {e}"))?;
+
+    let response: PluginResponse = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("plugin '{name}' returned an invalid response: {e}"))?;
+    if response.protocol != PLUGIN_PROTOCOL_VERSION {
+        return Err(format!(
+            "plugin '{name}' answered with protocol '{}', expected '{PLUGIN_PROTOCOL_VERSION}'",
+            response.protocol
+        ));
+    }
+    Ok(response)
+}
+
+/// Write a plugin's artifacts and return their paths.
+pub fn emit(response: &PluginResponse, sink: &mut ArtifactSink) -> Result<Vec<String>, String> {
+    for (path, content) in &response.artifacts {
+        sink.write(path, content)?;
+    }
+    Ok(response.artifacts.keys().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_plugin_name() {
+        for ok in ["docs", "team-erd", "x_2"] {
+            assert!(validate_plugin_name(ok).is_ok(), "{ok}");
+        }
+        for bad in ["", "Docs", "2fa", "../evil", "a/b", "a b"] {
+            assert!(validate_plugin_name(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_emit_rejects_escaping_paths() {
+        let response = PluginResponse {
+            protocol: PLUGIN_PROTOCOL_VERSION.to_string(),
+            artifacts: BTreeMap::from([
+                ("ok.txt".to_string(), "fine".to_string()),
+                ("../escape.txt".to_string(), "nope".to_string()),
+            ]),
+            warnings: Vec::new(),
+        };
+        let mut map = BTreeMap::new();
+        let mut sink = ArtifactSink::Memory {
+            prefix: String::new(),
+            map: &mut map,
+        };
+        let err = emit(&response, &mut sink).unwrap_err();
+        assert!(err.contains("escapes output directory"), "{err}");
+        assert!(!map.contains_key("../escape.txt"));
+    }
+
+    #[test]
+    fn test_locate_plugin_prefers_the_workspace_manifest() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("tools")).unwrap();
+        std::fs::write(temp.path().join("tools/erd.sh"), "").unwrap();
+        std::fs::write(
+            temp.path().join(MANIFEST_FILE_NAME),
+            "[plugins]\nerd = \"tools/erd.sh\"\nmissing = \"tools/missing.sh\"\n",
+        )
+        .unwrap();
+        let found = locate_plugin("erd", temp.path()).unwrap();
+        assert!(found.ends_with("tools/erd.sh"));
+        let err = locate_plugin("missing", temp.path()).unwrap_err();
+        assert!(err.contains("does not exist"), "{err}");
+        assert!(locate_plugin("Bad", temp.path()).is_err());
+    }
+}
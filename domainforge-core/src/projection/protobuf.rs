@@ -335,6 +335,12 @@ impl ProtoCustomOption {
     pub fn to_proto_string(&self) -> String {
         format!("option {} = {};", self.name, self.value.to_proto_string())
     }
+
+    /// Serialize as an entry of a bracketed field option list
+    /// (`name = value`, no `option` keyword or terminator).
+    pub fn to_inline_string(&self) -> String {
+        format!("{} = {}", self.name, self.value.to_proto_string())
+    }
 }
 
 /// Value for a proto option.
@@ -435,7 +441,12 @@ impl ProtoService {
         out.push_str(&format!("service {} {{\n", self.name));
 
         for method in &self.methods {
-            out.push_str(&format!("  {}\n", method.to_proto_string()));
+            for comment in &method.comments {
+                out.push_str(&format!("  // {}\n", comment));
+            }
+            for line in method.to_proto_string().lines() {
+                out.push_str(&format!("  {}\n", line));
+            }
         }
 
         out.push_str("}\n");
@@ -456,6 +467,9 @@ pub struct ProtoRpcMethod {
     pub streaming: StreamingMode,
     /// Documentation comments
     pub comments: Vec<String>,
+    /// Method options (e.g. `idempotency_level`), rendered in the method body
+    #[serde(default)]
+    pub options: Vec<ProtoCustomOption>,
 }
 
 impl ProtoRpcMethod {
@@ -471,6 +485,7 @@ impl ProtoRpcMethod {
             response_type: response_type.into(),
             streaming: StreamingMode::Unary,
             comments: Vec::new(),
+            options: Vec::new(),
         }
     }
 
@@ -490,7 +505,16 @@ impl ProtoRpcMethod {
             _ => self.response_type.clone(),
         };
 
-        format!("rpc {}({}) returns ({});", self.name, request, response)
+        let signature = format!("rpc {}({}) returns ({})", self.name, request, response);
+        if self.options.is_empty() {
+            return format!("{};", signature);
+        }
+        let mut out = format!("{} {{\n", signature);
+        for option in &self.options {
+            out.push_str(&format!("  {}\n", option.to_proto_string()));
+        }
+        out.push('}');
+        out
     }
 }
 
@@ -632,6 +656,11 @@ pub struct ProtoField {
     pub optional: bool,
     /// Documentation comments
     pub comments: Vec<String>,
+    /// Field options, rendered as `[name = value, ...]` (e.g. protovalidate
+    /// rules). Defaulted so schema history written before field options
+    /// existed still loads.
+    #[serde(default)]
+    pub options: Vec<ProtoCustomOption>,
 }
 
 impl ProtoField {
@@ -654,7 +683,16 @@ impl ProtoField {
         parts.push(self.proto_type.to_proto_string());
         parts.push(self.name.clone());
 
-        let mut line = format!("{} = {};", parts.join(" "), self.number);
+        let mut line = format!("{} = {}", parts.join(" "), self.number);
+        if !self.options.is_empty() {
+            let options: Vec<String> = self
+                .options
+                .iter()
+                .map(ProtoCustomOption::to_inline_string)
+                .collect();
+            line.push_str(&format!(" [{}]", options.join(", ")));
+        }
+        line.push(';');
 
         if !self.comments.is_empty() {
             line.push_str(&format!(" // {}", self.comments.join("; ")));
@@ -1407,6 +1445,7 @@ impl ProtobufEngine {
                         repeated: false,
                         optional: false,
                         comments: vec![],
+                        options: Vec::new(),
                     });
                     msg.fields.push(ProtoField {
                         name: "message".to_string(),
@@ -1415,6 +1454,7 @@ impl ProtobufEngine {
                         repeated: false,
                         optional: true,
                         comments: vec![],
+                        options: Vec::new(),
                     });
                     messages.push(msg);
                 }
@@ -1440,6 +1480,7 @@ impl ProtobufEngine {
             repeated: false,
             optional: false,
             comments: vec!["Unique identifier".to_string()],
+            options: Vec::new(),
         });
         field_number += 1;
 
@@ -1451,6 +1492,7 @@ impl ProtobufEngine {
             repeated: false,
             optional: false,
             comments: vec!["Entity name".to_string()],
+            options: Vec::new(),
         });
         field_number += 1;
 
@@ -1468,6 +1510,7 @@ impl ProtobufEngine {
                 repeated: matches!(value, Value::Array(_)),
                 optional: true,
                 comments: vec![],
+                options: Vec::new(),
             });
             field_number += 1;
         }
@@ -1493,6 +1536,7 @@ impl ProtobufEngine {
             repeated: false,
             optional: false,
             comments: vec!["Unique identifier".to_string()],
+            options: Vec::new(),
         });
         field_number += 1;
 
@@ -1504,6 +1548,7 @@ impl ProtobufEngine {
             repeated: false,
             optional: false,
             comments: vec!["Resource name".to_string()],
+            options: Vec::new(),
         });
         field_number += 1;
 
@@ -1515,6 +1560,7 @@ impl ProtobufEngine {
             repeated: false,
             optional: true,
             comments: vec![format!("Quantity in {}", resource.unit().symbol())],
+            options: Vec::new(),
         });
         field_number += 1;
 
@@ -1526,6 +1572,7 @@ impl ProtobufEngine {
             repeated: false,
             optional: false,
             comments: vec!["Unit of measurement".to_string()],
+            options: Vec::new(),
         });
         field_number += 1;
 
@@ -1543,6 +1590,7 @@ impl ProtobufEngine {
                 repeated: matches!(value, Value::Array(_)),
                 optional: true,
                 comments: vec![],
+                options: Vec::new(),
             });
             field_number += 1;
         }
@@ -1567,6 +1615,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["Name of the violated policy".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "entity_id".to_string(),
@@ -1575,6 +1624,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["ID of the entity that violated the policy".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "severity".to_string(),
@@ -1583,6 +1633,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["Severity level (error, warn, info)".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "message".to_string(),
@@ -1591,6 +1642,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["Human-readable violation message".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "timestamp".to_string(),
@@ -1599,6 +1651,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["When the violation occurred".to_string()],
+                options: Vec::new(),
             },
         ];
         messages.push(violation);
@@ -1616,6 +1669,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["Name of the metric".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "value".to_string(),
@@ -1624,6 +1678,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["Measured value".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "unit".to_string(),
@@ -1632,6 +1687,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: true,
                 comments: vec!["Unit of measurement".to_string()],
+                options: Vec::new(),
             },
             ProtoField {
                 name: "timestamp".to_string(),
@@ -1640,6 +1696,7 @@ impl ProtobufEngine {
                 repeated: false,
                 optional: false,
                 comments: vec!["When the measurement was taken".to_string()],
+                options: Vec::new(),
             },
         ];
         messages.push(metric);
@@ -1669,7 +1726,7 @@ fn to_pascal_case(s: &str) -> String {
         .collect()
 }
 
-fn sanitize_proto_ident(raw: &str) -> String {
+pub(crate) fn sanitize_proto_ident(raw: &str) -> String {
     let pascal = to_pascal_case(raw);
     if pascal.is_empty() {
         return "SeaUnnamed".to_string();
@@ -1698,7 +1755,7 @@ fn sanitize_proto_ident(raw: &str) -> String {
 }
 
 /// Convert a string to snake_case.
-fn to_snake_case(s: &str) -> String {
+pub(crate) fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     let mut prev_is_uppercase = false;
 
@@ -1724,7 +1781,7 @@ fn to_snake_case(s: &str) -> String {
 }
 
 /// Convert a string to SCREAMING_SNAKE_CASE.
-fn to_screaming_snake_case(s: &str) -> String {
+pub(crate) fn to_screaming_snake_case(s: &str) -> String {
     to_snake_case(s).to_uppercase()
 }
 
@@ -2243,8 +2300,8 @@ impl SchemaHistory {
         Ok(())
     }
 
-    /// Check compatibility and optionally apply fixes.
-    pub fn check_and_update(
+    /// Check compatibility and optionally apply fixes, without saving.
+    pub fn check(
         &self,
         new: &mut ProtoFile,
         mode: CompatibilityMode,
@@ -2252,7 +2309,7 @@ impl SchemaHistory {
     ) -> Result<CompatibilityResult, String> {
         let old = self.load(&new.package)?;
 
-        let result = match old {
+        Ok(match old {
             Some(ref old_proto) => {
                 if apply_fixes && mode == CompatibilityMode::Backward {
                     CompatibilityChecker::apply_backward_compatibility(old_proto, new);
@@ -2260,7 +2317,17 @@ impl SchemaHistory {
                 CompatibilityChecker::check(old_proto, new, mode)
             }
             None => CompatibilityResult::compatible(mode),
-        };
+        })
+    }
+
+    /// Check compatibility and optionally apply fixes.
+    pub fn check_and_update(
+        &self,
+        new: &mut ProtoFile,
+        mode: CompatibilityMode,
+        apply_fixes: bool,
+    ) -> Result<CompatibilityResult, String> {
+        let result = self.check(new, mode, apply_fixes)?;
 
         // Save the new schema if compatible (or in breaking mode)
         if result.is_compatible || mode == CompatibilityMode::Breaking {
@@ -2373,6 +2440,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         };
         assert_eq!(field.to_proto_string(), "string my_field = 1;");
 
@@ -2383,6 +2451,7 @@ mod tests {
             repeated: false,
             optional: true,
             comments: vec![],
+            options: Vec::new(),
         };
         assert_eq!(
             optional_field.to_proto_string(),
@@ -2396,6 +2465,7 @@ mod tests {
             repeated: true,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         };
         assert_eq!(
             repeated_field.to_proto_string(),
@@ -2426,6 +2496,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
         msg.fields.push(ProtoField {
             name: "age".to_string(),
@@ -2434,6 +2505,7 @@ mod tests {
             repeated: false,
             optional: true,
             comments: vec![],
+            options: Vec::new(),
         });
 
         let output = msg.to_proto_string();
@@ -2456,6 +2528,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
         proto.messages.push(msg);
 
@@ -2641,6 +2714,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
         msg.fields.push(ProtoField {
             name: "duration".to_string(),
@@ -2649,6 +2723,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
         proto.messages.push(msg);
 
@@ -2679,6 +2754,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
 
         let mut imports = HashSet::new();
@@ -2715,6 +2791,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
         msg.nested_messages.push(inner);
 
@@ -2736,6 +2813,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
         proto.messages.push(msg);
         proto.add_wkt_imports();
@@ -2757,6 +2835,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
 
         let mut msg2 = ProtoMessage::new("Event2");
@@ -2767,6 +2846,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         });
 
         proto.messages.push(msg1);
@@ -3085,6 +3165,7 @@ mod tests {
             repeated: false,
             optional: false,
             comments: vec![],
+            options: Vec::new(),
         }
     }
 
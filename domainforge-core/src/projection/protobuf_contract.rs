@@ -0,0 +1,755 @@
+//! Application-contract lowering for the Protobuf projection (ADR-013).
+//!
+//! [`super::protobuf::ProtobufEngine`] derives messages from entities and
+//! resources and services from flows; this module adds the typed surface the
+//! application contract declares, so the same `.proto` (and the same
+//! [`super::protobuf::CompatibilityChecker`] / `SchemaHistory` gate) covers
+//! operation DTOs.
+//!
+//! ## Mapping
+//!
+//! | Contract                  | Protobuf                                             |
+//! |---------------------------|------------------------------------------------------|
+//! | `enum`                    | `enum` with `<ENUM>_UNSPECIFIED = 0`, members 1..n in declared order |
+//! | `record`                  | `message`, fields numbered 1..n in declared order    |
+//! | `string` / `int` / `bool` | `string` / `int64` / `bool`                          |
+//! | `decimal`, `quantity<U>`  | `string` carrying the canonical decimal (base unit for quantities) |
+//! | `timestamp`               | `google.protobuf.Timestamp`                          |
+//! | `uuid`                    | `string` + `string.uuid`                             |
+//! | entity reference          | the referenced entity's key type                     |
+//! | `list<T>`                 | `repeated T`                                         |
+//! | `optional` field          | proto3 `optional` (lists are never `optional`)       |
+//! | `operation`               | `rpc` on `<StateEntity>OperationsService`            |
+//! | operation failures        | `<Op>FailureCode` enum + `<Op>Error` detail message  |
+//!
+//! Field constraints become [protovalidate](https://github.com/bufbuild/protovalidate)
+//! `(buf.validate.field)` rules: `min`/`max`/`exclusive_*` → `int64.gte` /
+//! `lte` / `gt` / `lt`, `min_length`/`max_length` → `string.min_len` /
+//! `max_len`, `min_items`/`max_items` → `repeated.min_items` / `max_items`,
+//! `pattern` → `string.pattern` with the SEA Pattern's regex. Element rules
+//! on a list go under `repeated.items`. Non-optional enum and message fields
+//! are `required`; enum fields are `defined_only`. A numeric bound on a
+//! decimal or quantity has no protovalidate rule over a string and is kept
+//! as a field comment instead of being dropped.
+//!
+//! ## Operations and failures
+//!
+//! Each operation is a unary RPC from its input record to its output record.
+//! `reads` operations are `NO_SIDE_EFFECTS`; operations with `keyed_by` or
+//! `inherent` idempotency are `IDEMPOTENT`; anything else keeps the default
+//! level. Declared failures travel as `google.rpc.Status` details: the
+//! `<Op>Error` message carries the failure code, the canonical
+//! [`FailureKind`] that fired, and a message. The RPC comments record the
+//! gRPC status each failure kind maps to.
+//!
+//! Field numbers follow declaration order, so reordering or removing a
+//! record field is a numbering change the compatibility checker reports —
+//! contract-derived messages get no special treatment.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rust_decimal::prelude::ToPrimitive;
+
+use super::protobuf::{
+    sanitize_proto_ident, to_screaming_snake_case, to_snake_case, ProtoCustomOption, ProtoEnum,
+    ProtoField, ProtoFile, ProtoMessage, ProtoOptionValue, ProtoRpcMethod, ProtoService, ProtoType,
+    ScalarType,
+};
+use crate::application::{
+    ApplicationContract, ApplicationSymbolId, ConcurrencyStrategy, EffectKind, FailureKind,
+    FieldConstraint, FieldContract, FieldType, IdempotencyStrategy, OperationContract,
+    RecordContract, ScalarType as ContractScalar, TransactionBoundary,
+};
+use crate::graph::Graph;
+
+/// Import required by `(buf.validate.field)` rules.
+pub const PROTOVALIDATE_IMPORT: &str = "buf/validate/validate.proto";
+/// Path of the contract file added by multi-file projection.
+pub const APPLICATION_CONTRACT_PROTO: &str = "application_contract.proto";
+/// Shared enum of the canonical failure taxonomy.
+pub const FAILURE_KIND_ENUM: &str = "FailureKind";
+
+const FIELD_RULES: &str = "(buf.validate.field)";
+
+/// The Protobuf declarations derived from an application contract.
+#[derive(Debug, Clone, Default)]
+pub struct ContractProto {
+    pub enums: Vec<ProtoEnum>,
+    pub messages: Vec<ProtoMessage>,
+    pub services: Vec<ProtoService>,
+}
+
+impl ContractProto {
+    pub fn is_empty(&self) -> bool {
+        self.enums.is_empty() && self.messages.is_empty() && self.services.is_empty()
+    }
+
+    fn uses_protovalidate(&self) -> bool {
+        self.messages
+            .iter()
+            .flat_map(|m| m.fields.iter())
+            .flat_map(|f| f.options.iter())
+            .any(|o| o.name.starts_with(FIELD_RULES))
+    }
+}
+
+/// Lower the contract's enums, records and operations. With a non-empty
+/// `namespace`, only declarations of that namespace are kept, plus every
+/// record and enum they reference (so the output always type-checks).
+pub fn contract_to_proto(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    namespace: &str,
+) -> Result<ContractProto, String> {
+    let lowering = Lowering::new(graph, contract);
+    let (operations, records, enums) = lowering.select(namespace);
+
+    let mut out = ContractProto::default();
+    // Proto name → the contract symbols lowered to it; more than one is a
+    // duplicate definition in the generated file.
+    let mut origins: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for e in contract.enums.iter().filter(|e| enums.contains(&e.id.0)) {
+        let name = lowering.type_name(&e.id)?;
+        origins.entry(name.clone()).or_default().push(&e.id.0);
+        let mut proto = ProtoEnum::new(&name);
+        proto.comments.push(format!("Application enum {}", e.id.0));
+        let prefix = to_screaming_snake_case(&name);
+        for member in &e.members {
+            let value = format!("{}_{}", prefix, to_screaming_snake_case(&member.name));
+            proto
+                .comments
+                .push(format!("{}: wire \"{}\"", value, member.wire));
+            proto.add_value(value);
+        }
+        out.enums.push(proto);
+    }
+
+    for record in contract
+        .records
+        .iter()
+        .filter(|r| records.contains(&r.id.0))
+    {
+        let message = lowering.record_to_message(record)?;
+        origins
+            .entry(message.name.clone())
+            .or_default()
+            .push(&record.id.0);
+        out.messages.push(message);
+    }
+
+    if !operations.is_empty() {
+        out.enums.push(failure_kind_enum());
+    }
+
+    let mut services: BTreeMap<String, ProtoService> = BTreeMap::new();
+    for op in &operations {
+        let (code_enum, error) = lowering.failure_detail(op);
+        for name in [&code_enum.name, &error.name] {
+            origins.entry(name.clone()).or_default().push(&op.id.0);
+        }
+        out.enums.push(code_enum);
+        out.messages.push(error);
+
+        let state = lowering.state_name(op);
+        let service_name = format!("{}OperationsService", state);
+        let rpc = lowering.operation_to_rpc(op)?;
+        origins
+            .entry(format!("{}.{}", service_name, rpc.name))
+            .or_default()
+            .push(&op.id.0);
+        services
+            .entry(service_name.clone())
+            .or_insert_with(|| {
+                let mut svc = ProtoService::new(&service_name);
+                svc.comments
+                    .push(format!("Application operations on {}", state));
+                svc
+            })
+            .methods
+            .push(rpc);
+    }
+    let duplicates: Vec<String> = origins
+        .into_iter()
+        .filter(|(_, symbols)| symbols.len() > 1)
+        .map(|(name, symbols)| format!("{} ({})", name, symbols.join(", ")))
+        .collect();
+    if !duplicates.is_empty() {
+        return Err(format!(
+            "Application contract declarations map to the same Protobuf name: {}; \
+             rename them or project one namespace at a time",
+            duplicates.join("; ")
+        ));
+    }
+    out.services = services.into_values().collect();
+
+    out.enums.sort_by(|a, b| a.name.cmp(&b.name));
+    out.messages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(out)
+}
+
+/// Merge the contract-derived declarations into an existing projection.
+/// A name already used by a graph-derived message, enum or service is an
+/// error rather than a silent overwrite.
+pub fn merge_application_contract(
+    proto: &mut ProtoFile,
+    graph: &Graph,
+    contract: &ApplicationContract,
+    namespace: &str,
+) -> Result<(), String> {
+    let derived = contract_to_proto(graph, contract, namespace)?;
+    if derived.is_empty() {
+        return Ok(());
+    }
+
+    let taken: BTreeSet<&str> = proto
+        .messages
+        .iter()
+        .map(|m| m.name.as_str())
+        .chain(proto.enums.iter().map(|e| e.name.as_str()))
+        .chain(proto.services.iter().map(|s| s.name.as_str()))
+        .collect();
+    let clashes: Vec<&str> = derived
+        .messages
+        .iter()
+        .map(|m| m.name.as_str())
+        .chain(derived.enums.iter().map(|e| e.name.as_str()))
+        .chain(derived.services.iter().map(|s| s.name.as_str()))
+        .filter(|name| taken.contains(name))
+        .collect();
+    if !clashes.is_empty() {
+        return Err(format!(
+            "Application contract declarations clash with graph-derived Protobuf names: {}",
+            clashes.join(", ")
+        ));
+    }
+
+    if derived.uses_protovalidate() && !proto.imports.iter().any(|i| i == PROTOVALIDATE_IMPORT) {
+        proto.imports.push(PROTOVALIDATE_IMPORT.to_string());
+    }
+    proto.enums.extend(derived.enums);
+    proto.messages.extend(derived.messages);
+    proto.services.extend(derived.services);
+    proto.enums.sort_by(|a, b| a.name.cmp(&b.name));
+    proto.messages.sort_by(|a, b| a.name.cmp(&b.name));
+    proto.services.sort_by(|a, b| a.name.cmp(&b.name));
+    proto.add_wkt_imports();
+    Ok(())
+}
+
+/// A standalone `.proto` holding only the contract-derived declarations, in
+/// package `<base_package>.application_contract`. Multi-file projection uses
+/// it instead of splitting the contract by namespace: contract types never
+/// reference graph-derived messages, so the file needs no cross-imports.
+pub fn project_application_contract(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    base_package: &str,
+) -> Result<ProtoFile, String> {
+    let mut proto = ProtoFile::new(format!("{}.application_contract", base_package));
+    proto.metadata.projection_name = "application-contract".to_string();
+    merge_application_contract(&mut proto, graph, contract, "")?;
+    Ok(proto)
+}
+
+struct Lowering<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+    names: BTreeMap<&'a str, String>,
+}
+
+impl<'a> Lowering<'a> {
+    fn new(graph: &'a Graph, contract: &'a ApplicationContract) -> Self {
+        let names = contract
+            .enums
+            .iter()
+            .map(|e| (e.id.0.as_str(), sanitize_proto_ident(&e.name)))
+            .chain(
+                contract
+                    .records
+                    .iter()
+                    .map(|r| (r.id.0.as_str(), sanitize_proto_ident(&r.name))),
+            )
+            .collect();
+        Self {
+            graph,
+            contract,
+            names,
+        }
+    }
+
+    fn type_name(&self, id: &ApplicationSymbolId) -> Result<String, String> {
+        self.names
+            .get(id.0.as_str())
+            .cloned()
+            .ok_or_else(|| format!("Application contract references unknown type '{}'", id.0))
+    }
+
+    /// Operations, records and enums to emit for `namespace`, closed over
+    /// type references.
+    fn select(
+        &self,
+        namespace: &str,
+    ) -> (
+        Vec<&'a OperationContract>,
+        BTreeSet<String>,
+        BTreeSet<String>,
+    ) {
+        let in_namespace = |id: &ApplicationSymbolId, slug: &str, name: &str| {
+            namespace.is_empty() || id.0 == format!("{}.{}.{}", namespace, slug, name)
+        };
+
+        let operations: Vec<&OperationContract> = self
+            .contract
+            .operations
+            .iter()
+            .filter(|op| in_namespace(&op.id, "operation", &op.name))
+            .collect();
+
+        let mut records: BTreeSet<String> = self
+            .contract
+            .records
+            .iter()
+            .filter(|r| in_namespace(&r.id, "record", &r.name))
+            .map(|r| r.id.0.clone())
+            .collect();
+        for op in &operations {
+            records.insert(op.input.0.clone());
+            records.insert(op.output.0.clone());
+        }
+
+        let mut enums: BTreeSet<String> = self
+            .contract
+            .enums
+            .iter()
+            .filter(|e| in_namespace(&e.id, "enum", &e.name))
+            .map(|e| e.id.0.clone())
+            .collect();
+        for record in self.contract.records.iter() {
+            if !records.contains(&record.id.0) {
+                continue;
+            }
+            for field in &record.fields {
+                if let Some(id) = enum_symbol(&field.field_type) {
+                    enums.insert(id.0.clone());
+                }
+            }
+        }
+
+        (operations, records, enums)
+    }
+
+    fn record_to_message(&self, record: &RecordContract) -> Result<ProtoMessage, String> {
+        let mut msg = ProtoMessage::new(self.type_name(&record.id)?);
+        msg.comments
+            .push(format!("Application record {}", record.id.0));
+        for (index, field) in record.fields.iter().enumerate() {
+            msg.fields
+                .push(self.field_to_proto(field, index as u32 + 1)?);
+        }
+        Ok(msg)
+    }
+
+    fn field_to_proto(&self, field: &FieldContract, number: u32) -> Result<ProtoField, String> {
+        let (element, repeated) = match &field.field_type {
+            FieldType::List { element } => (element.as_ref(), true),
+            other => (other, false),
+        };
+        let (proto_type, family, mut comments) = self.element_type(element)?;
+
+        let mut options = Vec::new();
+        let items = if repeated { "repeated.items." } else { "" };
+        let is_reference = matches!(proto_type, ProtoType::Message(_) | ProtoType::Enum(_));
+        if !field.optional && !repeated && is_reference {
+            options.push(rule("required", ProtoOptionValue::Bool(true)));
+        }
+        if matches!(proto_type, ProtoType::Enum(_)) {
+            options.push(rule(
+                &format!("{}enum.defined_only", items),
+                ProtoOptionValue::Bool(true),
+            ));
+        }
+        if family == RuleFamily::Uuid {
+            options.push(rule(
+                &format!("{}string.uuid", items),
+                ProtoOptionValue::Bool(true),
+            ));
+        }
+
+        for constraint in &field.constraints {
+            let numeric = |op: &str, value: &rust_decimal::Decimal| match family {
+                RuleFamily::Int => match value.trunc() == *value {
+                    true => value
+                        .to_i64()
+                        .map(|v| rule(&format!("{}int64.{}", items, op), ProtoOptionValue::Int(v))),
+                    false => None,
+                },
+                _ => None,
+            };
+            let mapped = match constraint {
+                FieldConstraint::Min { value } => numeric("gte", value),
+                FieldConstraint::Max { value } => numeric("lte", value),
+                FieldConstraint::ExclusiveMin { value } => numeric("gt", value),
+                FieldConstraint::ExclusiveMax { value } => numeric("lt", value),
+                FieldConstraint::MinLength { value } => Some(rule(
+                    &format!("{}string.min_len", items),
+                    ProtoOptionValue::Int(i64::from(*value)),
+                )),
+                FieldConstraint::MaxLength { value } => Some(rule(
+                    &format!("{}string.max_len", items),
+                    ProtoOptionValue::Int(i64::from(*value)),
+                )),
+                FieldConstraint::MinItems { value } => Some(rule(
+                    "repeated.min_items",
+                    ProtoOptionValue::Int(i64::from(*value)),
+                )),
+                FieldConstraint::MaxItems { value } => Some(rule(
+                    "repeated.max_items",
+                    ProtoOptionValue::Int(i64::from(*value)),
+                )),
+                FieldConstraint::Pattern { pattern } => self
+                    .graph
+                    .all_patterns()
+                    .into_iter()
+                    .find(|p| p.id() == pattern)
+                    .map(|p| {
+                        rule(
+                            &format!("{}string.pattern", items),
+                            ProtoOptionValue::String(p.regex().to_string()),
+                        )
+                    }),
+            };
+            match mapped {
+                Some(option) => options.push(option),
+                None => comments.push(constraint_comment(constraint)),
+            }
+        }
+
+        Ok(ProtoField {
+            name: to_snake_case(&field.name),
+            number,
+            proto_type,
+            repeated,
+            optional: field.optional && !repeated,
+            comments,
+            options,
+        })
+    }
+
+    fn element_type(
+        &self,
+        element: &FieldType,
+    ) -> Result<(ProtoType, RuleFamily, Vec<String>), String> {
+        Ok(match element {
+            FieldType::Scalar { scalar } => scalar_type(*scalar),
+            FieldType::Quantity { .. } => (
+                ProtoType::Scalar(ScalarType::String),
+                RuleFamily::Decimal,
+                vec!["quantity: canonical decimal in the base unit".to_string()],
+            ),
+            FieldType::EntityRef { entity } => {
+                let target = self.graph.entity_contract(entity).ok_or_else(|| {
+                    format!("Entity reference '{}' has no entity contract", entity)
+                })?;
+                let key = target
+                    .fields
+                    .iter()
+                    .find(|f| f.name == target.key_field)
+                    .ok_or_else(|| {
+                        format!(
+                            "Entity '{}' key field '{}' is not declared",
+                            target.name, target.key_field
+                        )
+                    })?;
+                let (proto_type, family, _) = self.element_type(&key.field_type)?;
+                (
+                    proto_type,
+                    family,
+                    vec![format!("{} key of {}", target.key_field, target.name)],
+                )
+            }
+            FieldType::Enum { symbol } => (
+                ProtoType::Enum(self.type_name(symbol)?),
+                RuleFamily::Other,
+                vec![],
+            ),
+            FieldType::List { .. } => {
+                return Err("Nested list types are not valid in an application contract".into())
+            }
+        })
+    }
+
+    fn state_name(&self, op: &OperationContract) -> String {
+        self.contract
+            .entities
+            .iter()
+            .find(|e| e.concept_id == op.state)
+            .map(|e| sanitize_proto_ident(&e.name))
+            .or_else(|| {
+                self.graph
+                    .get_entity(&op.state)
+                    .map(|e| sanitize_proto_ident(e.name()))
+            })
+            .unwrap_or_else(|| "Application".to_string())
+    }
+
+    fn operation_to_rpc(&self, op: &OperationContract) -> Result<ProtoRpcMethod, String> {
+        let name = sanitize_proto_ident(&op.name);
+        let mut method = ProtoRpcMethod::new(
+            &name,
+            self.type_name(&op.input)?,
+            self.type_name(&op.output)?,
+        );
+        method.comments.push(format!("{}: {}", op.name, op.intent));
+        method.comments.push(format!(
+            "effect: {} {} ({})",
+            effect_slug(op.effect),
+            self.state_name(op),
+            transaction_slug(op)
+        ));
+        method.comments.push(format!(
+            "idempotency: {}",
+            idempotency_slug(&op.idempotency)
+        ));
+        method.comments.push(format!(
+            "concurrency: {}",
+            concurrency_slug(&op.concurrency)
+        ));
+        method.comments.push(format!(
+            "errors: google.rpc.Status details carry {}Error",
+            name
+        ));
+        for failure in &op.failures {
+            let kinds: Vec<String> = failure
+                .kinds
+                .iter()
+                .map(|k| format!("{} -> {}", failure_kind_slug(*k), grpc_status(*k)))
+                .collect();
+            method.comments.push(format!(
+                "failure {} ({}): {}",
+                failure.code,
+                kinds.join(", "),
+                failure.meaning
+            ));
+        }
+
+        let level = if op.effect == EffectKind::Reads {
+            Some("NO_SIDE_EFFECTS")
+        } else if matches!(
+            op.idempotency,
+            IdempotencyStrategy::KeyedBy { .. } | IdempotencyStrategy::Inherent
+        ) {
+            Some("IDEMPOTENT")
+        } else {
+            None
+        };
+        if let Some(level) = level {
+            method.options.push(ProtoCustomOption::new(
+                "idempotency_level",
+                ProtoOptionValue::Identifier(level.to_string()),
+            ));
+        }
+        Ok(method)
+    }
+
+    /// `<Op>FailureCode` and the `<Op>Error` status-detail message.
+    fn failure_detail(&self, op: &OperationContract) -> (ProtoEnum, ProtoMessage) {
+        let name = sanitize_proto_ident(&op.name);
+        let code_name = format!("{}FailureCode", name);
+        let mut codes = ProtoEnum::new(&code_name);
+        codes
+            .comments
+            .push(format!("Declared failures of operation {}", op.name));
+        let prefix = to_screaming_snake_case(&code_name);
+        for failure in &op.failures {
+            let value = format!("{}_{}", prefix, to_screaming_snake_case(&failure.code));
+            codes
+                .comments
+                .push(format!("{}: {}", value, failure.meaning));
+            codes.add_value(value);
+        }
+
+        let mut error = ProtoMessage::new(format!("{}Error", name));
+        error.comments.push(format!(
+            "Typed error detail for {} (google.rpc.Status details)",
+            op.name
+        ));
+        error.fields.push(ProtoField {
+            name: "code".to_string(),
+            number: 1,
+            proto_type: ProtoType::Enum(code_name),
+            repeated: false,
+            optional: false,
+            comments: vec![],
+            options: vec![
+                rule("required", ProtoOptionValue::Bool(true)),
+                rule("enum.defined_only", ProtoOptionValue::Bool(true)),
+            ],
+        });
+        error.fields.push(ProtoField {
+            name: "kind".to_string(),
+            number: 2,
+            proto_type: ProtoType::Enum(FAILURE_KIND_ENUM.to_string()),
+            repeated: false,
+            optional: false,
+            comments: vec![],
+            options: vec![
+                rule("required", ProtoOptionValue::Bool(true)),
+                rule("enum.defined_only", ProtoOptionValue::Bool(true)),
+            ],
+        });
+        error.fields.push(ProtoField {
+            name: "message".to_string(),
+            number: 3,
+            proto_type: ProtoType::Scalar(ScalarType::String),
+            repeated: false,
+            optional: false,
+            comments: vec![],
+            options: vec![],
+        });
+        (codes, error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleFamily {
+    Int,
+    Decimal,
+    Uuid,
+    Other,
+}
+
+fn scalar_type(scalar: ContractScalar) -> (ProtoType, RuleFamily, Vec<String>) {
+    match scalar {
+        ContractScalar::String => (
+            ProtoType::Scalar(ScalarType::String),
+            RuleFamily::Other,
+            vec![],
+        ),
+        ContractScalar::Int => (
+            ProtoType::Scalar(ScalarType::Int64),
+            RuleFamily::Int,
+            vec![],
+        ),
+        ContractScalar::Decimal => (
+            ProtoType::Scalar(ScalarType::String),
+            RuleFamily::Decimal,
+            vec!["decimal: canonical decimal string".to_string()],
+        ),
+        ContractScalar::Bool => (
+            ProtoType::Scalar(ScalarType::Bool),
+            RuleFamily::Other,
+            vec![],
+        ),
+        ContractScalar::Timestamp => (
+            ProtoType::Message("google.protobuf.Timestamp".to_string()),
+            RuleFamily::Other,
+            vec![],
+        ),
+        ContractScalar::Uuid => (
+            ProtoType::Scalar(ScalarType::String),
+            RuleFamily::Uuid,
+            vec![],
+        ),
+    }
+}
+
+fn enum_symbol(field_type: &FieldType) -> Option<&ApplicationSymbolId> {
+    match field_type {
+        FieldType::Enum { symbol } => Some(symbol),
+        FieldType::List { element } => enum_symbol(element),
+        _ => None,
+    }
+}
+
+fn rule(path: &str, value: ProtoOptionValue) -> ProtoCustomOption {
+    ProtoCustomOption::new(format!("{}.{}", FIELD_RULES, path), value)
+}
+
+fn constraint_comment(constraint: &FieldConstraint) -> String {
+    match constraint {
+        FieldConstraint::Min { value } => format!("min {}", value),
+        FieldConstraint::Max { value } => format!("max {}", value),
+        FieldConstraint::ExclusiveMin { value } => format!("exclusive_min {}", value),
+        FieldConstraint::ExclusiveMax { value } => format!("exclusive_max {}", value),
+        FieldConstraint::MinLength { value } => format!("min_length {}", value),
+        FieldConstraint::MaxLength { value } => format!("max_length {}", value),
+        FieldConstraint::MinItems { value } => format!("min_items {}", value),
+        FieldConstraint::MaxItems { value } => format!("max_items {}", value),
+        FieldConstraint::Pattern { pattern } => format!("pattern {} (unresolved)", pattern),
+    }
+}
+
+fn failure_kind_enum() -> ProtoEnum {
+    let mut kinds = ProtoEnum::new(FAILURE_KIND_ENUM);
+    kinds
+        .comments
+        .push("Canonical application failure taxonomy (ADR-013)".to_string());
+    let prefix = to_screaming_snake_case(FAILURE_KIND_ENUM);
+    for kind in [
+        FailureKind::InputValidation,
+        FailureKind::Policy,
+        FailureKind::MissingState,
+        FailureKind::IdempotencyConflict,
+        FailureKind::ConcurrencyConflict,
+    ] {
+        kinds.add_value(format!(
+            "{}_{}",
+            prefix,
+            failure_kind_slug(kind).to_uppercase()
+        ));
+    }
+    kinds
+}
+
+fn failure_kind_slug(kind: FailureKind) -> &'static str {
+    match kind {
+        FailureKind::InputValidation => "input_validation",
+        FailureKind::Policy => "policy",
+        FailureKind::MissingState => "missing_state",
+        FailureKind::IdempotencyConflict => "idempotency_conflict",
+        FailureKind::ConcurrencyConflict => "concurrency_conflict",
+    }
+}
+
+fn grpc_status(kind: FailureKind) -> &'static str {
+    match kind {
+        FailureKind::InputValidation => "INVALID_ARGUMENT",
+        FailureKind::Policy => "PERMISSION_DENIED",
+        FailureKind::MissingState => "NOT_FOUND",
+        FailureKind::IdempotencyConflict => "ALREADY_EXISTS",
+        FailureKind::ConcurrencyConflict => "ABORTED",
+    }
+}
+
+fn effect_slug(effect: EffectKind) -> &'static str {
+    match effect {
+        EffectKind::Creates => "creates",
+        EffectKind::Mutates => "mutates",
+        EffectKind::Reads => "reads",
+    }
+}
+
+fn transaction_slug(op: &OperationContract) -> &'static str {
+    match op.transaction {
+        TransactionBoundary::SingleAggregate => "single_aggregate",
+        TransactionBoundary::ReadOnly => "read_only",
+    }
+}
+
+fn idempotency_slug(strategy: &IdempotencyStrategy) -> String {
+    match strategy {
+        IdempotencyStrategy::KeyedBy { field } => format!("keyed_by {}", field),
+        IdempotencyStrategy::Inherent => "inherent".to_string(),
+        IdempotencyStrategy::NotApplicable(_) => "not_applicable".to_string(),
+    }
+}
+
+fn concurrency_slug(strategy: &ConcurrencyStrategy) -> String {
+    match strategy {
+        ConcurrencyStrategy::UniqueKey { field } => format!("unique_key {}", field),
+        ConcurrencyStrategy::OptimisticVersion { field } => {
+            format!("optimistic_version {}", field)
+        }
+        ConcurrencyStrategy::ReadSnapshot => "read_snapshot".to_string(),
+    }
+}
@@ -16,6 +16,7 @@
 pub mod ontology;
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::ids::content_hash;
 use crate::projection::sink::ArtifactSink;
 use crate::KnowledgeGraph;
@@ -152,9 +153,9 @@ pub fn project_rdf_in_memory(
 
 fn header(model_ref: &str, created_at: &str) -> String {
     format!(
-        "# Generated by DomainForge (--format rdf) from {}.\n# Created: {}. Do not edit by hand.\n\n",
+        "# Generated by DomainForge (--format rdf) from {}.\n# {}\n# Do not edit by hand.\n\n",
         model_ref.replace(['\n', '\r'], " "),
-        created_at.replace(['\n', '\r'], " ")
+        generated_at(&created_at.replace(['\n', '\r'], " "))
     )
 }
 
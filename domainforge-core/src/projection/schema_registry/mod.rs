@@ -0,0 +1,637 @@
+//! Payload schemas shared by the `avro` and `json-schema` projections, plus an
+//! offline schema-registry compatibility checker.
+//!
+//! Mapping:
+//! - `Resource` → one `<Name>Flow` payload schema carrying a flow of that
+//!   resource: `quantity` (decimal), `unit` (defaulting to the resource's unit), `from`
+//!   and `to` (entity names)
+//! - `record` → one payload schema with the record's fields in order
+//! - `ref<E>` → `E`'s key type; quantities → decimal; `enum` → named enum over
+//!   the members' wire values; `list<T>` → array of `T`
+//!
+//! Every schema is named `<namespace>.<Name>` — the subject under Confluent's
+//! `RecordNameStrategy`. Type names are unique per namespace: a later
+//! declaration whose name is taken gets a numeric suffix (`Order2`), in
+//! sorted enum, resource, record order.
+//!
+//! [`check_published`] compares freshly projected schemas against a local
+//! directory of previously published ones, mirroring the schema registry's
+//! `BACKWARD` (new readers read old data), `FORWARD` (old readers read new
+//! data), and `FULL` (both) modes. The per-format reading rules live next to
+//! each projection (`avro::compat`, `json_schema::compat`); this module only
+//! pairs files and applies the mode.
+
+use crate::application::{
+    ApplicationContract, EntityContract, FieldConstraint, FieldContract, FieldType, RecordContract,
+    ScalarType, TypedValue,
+};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::projection::ids::{ident, pascal, NameRegistrar};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// One schema-registry subject.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadSchema {
+    /// Dotted namespace (each segment a valid identifier).
+    pub namespace: String,
+    pub name: String,
+    pub doc: String,
+    pub fields: Vec<PayloadField>,
+}
+
+impl PayloadSchema {
+    /// `<namespace>.<name>`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.namespace, self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadField {
+    pub name: String,
+    pub doc: Option<String>,
+    pub ty: PayloadType,
+    pub optional: bool,
+    pub default: Option<TypedValue>,
+    pub constraints: Vec<PayloadConstraint>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayloadType {
+    String,
+    Long,
+    Decimal,
+    Boolean,
+    Timestamp,
+    Uuid,
+    Enum(PayloadEnum),
+    List(Box<PayloadType>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadEnum {
+    pub namespace: String,
+    pub name: String,
+    /// Member wire values, in declaration order.
+    pub symbols: Vec<String>,
+}
+
+impl PayloadEnum {
+    /// `<namespace>.<name>`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.namespace, self.name)
+    }
+}
+
+/// A field constraint with its pattern already resolved to a regex.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayloadConstraint {
+    Minimum { value: Decimal, exclusive: bool },
+    Maximum { value: Decimal, exclusive: bool },
+    MinLength(u32),
+    MaxLength(u32),
+    MinItems(u32),
+    MaxItems(u32),
+    Pattern(String),
+}
+
+/// Payload schemas for every resource and record, sorted by full name.
+pub fn payload_schemas(
+    graph: &Graph,
+    contract: &ApplicationContract,
+) -> Result<Vec<PayloadSchema>, String> {
+    let mut builder = Builder {
+        graph,
+        contract,
+        names: HashMap::new(),
+        enums: HashMap::new(),
+    };
+    let mut enums: Vec<_> = contract.enums.iter().collect();
+    enums.sort_by(|a, b| a.id.0.cmp(&b.id.0));
+    for e in enums {
+        let namespace = builder.namespace(symbol_namespace(&e.id.0, "enum"));
+        let name = builder.local_name(&namespace, &e.name);
+        builder.enums.insert(
+            e.id.0.clone(),
+            PayloadEnum {
+                namespace,
+                name,
+                symbols: e.members.iter().map(|m| m.wire.clone()).collect(),
+            },
+        );
+    }
+
+    let mut resources: Vec<_> = graph.all_resources();
+    resources.sort_by(|a, b| (a.namespace(), a.name()).cmp(&(b.namespace(), b.name())));
+    let mut records: Vec<&RecordContract> = contract.records.iter().collect();
+    records.sort_by(|a, b| a.id.0.cmp(&b.id.0));
+
+    let mut schemas = Vec::new();
+    for resource in resources {
+        let namespace = builder.namespace(resource.namespace());
+        let name = builder.local_name(&namespace, &format!("{}Flow", pascal(resource.name())));
+        let text = |name: &str, doc: &str| PayloadField {
+            name: name.to_string(),
+            doc: Some(doc.to_string()),
+            ty: PayloadType::String,
+            optional: false,
+            default: None,
+            constraints: Vec::new(),
+        };
+        schemas.push(PayloadSchema {
+            namespace,
+            name,
+            doc: format!("A flow of the {} resource.", resource.name()),
+            fields: vec![
+                PayloadField {
+                    ty: PayloadType::Decimal,
+                    ..text("quantity", "Amount moved, in `unit`.")
+                },
+                PayloadField {
+                    default: Some(TypedValue::String(resource.unit_symbol().to_string())),
+                    ..text("unit", "Unit symbol of `quantity`.")
+                },
+                text("from", "Name of the entity the resource flows from."),
+                text("to", "Name of the entity the resource flows to."),
+            ],
+        });
+    }
+    for record in records {
+        let namespace = builder.namespace(symbol_namespace(&record.id.0, "record"));
+        let name = builder.local_name(&namespace, &record.name);
+        let mut field_names = NameRegistrar::new();
+        let fields = record
+            .fields
+            .iter()
+            .map(|field| builder.field(&mut field_names, field))
+            .collect::<Result<Vec<_>, _>>()?;
+        schemas.push(PayloadSchema {
+            namespace,
+            name,
+            doc: format!("Application record {}.", record.name),
+            fields,
+        });
+    }
+    schemas.sort_by_key(PayloadSchema::full_name);
+    Ok(schemas)
+}
+
+/// Namespace prefix of an application symbol id `<ns>.<kind>.<Name>`.
+fn symbol_namespace<'s>(id: &'s str, kind: &str) -> &'s str {
+    id.rsplit_once(&format!(".{kind}."))
+        .map_or("default", |(namespace, _)| namespace)
+}
+
+struct Builder<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+    /// namespace → type names declared in it.
+    names: HashMap<String, HashSet<String>>,
+    /// enum symbol → named enum.
+    enums: HashMap<String, PayloadEnum>,
+}
+
+impl<'a> Builder<'a> {
+    /// Dotted namespace with every segment made a valid identifier.
+    fn namespace(&self, namespace: &str) -> String {
+        namespace
+            .split('.')
+            .map(ident)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn local_name(&mut self, namespace: &str, name: &str) -> String {
+        let taken = self.names.entry(namespace.to_string()).or_default();
+        let base = ident(&pascal(name));
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while !taken.insert(candidate.clone()) {
+            candidate = format!("{base}{n}");
+            n += 1;
+        }
+        candidate
+    }
+
+    fn field(
+        &self,
+        names: &mut NameRegistrar,
+        field: &FieldContract,
+    ) -> Result<PayloadField, String> {
+        let (ty, doc) = self.payload_type(&field.field_type)?;
+        Ok(PayloadField {
+            name: names.register("ident", &field.name),
+            doc,
+            ty,
+            optional: field.optional,
+            default: field.default.clone(),
+            constraints: field
+                .constraints
+                .iter()
+                .filter_map(|c| self.constraint(c))
+                .collect(),
+        })
+    }
+
+    fn constraint(&self, constraint: &FieldConstraint) -> Option<PayloadConstraint> {
+        Some(match constraint {
+            FieldConstraint::Min { value } => PayloadConstraint::Minimum {
+                value: *value,
+                exclusive: false,
+            },
+            FieldConstraint::ExclusiveMin { value } => PayloadConstraint::Minimum {
+                value: *value,
+                exclusive: true,
+            },
+            FieldConstraint::Max { value } => PayloadConstraint::Maximum {
+                value: *value,
+                exclusive: false,
+            },
+            FieldConstraint::ExclusiveMax { value } => PayloadConstraint::Maximum {
+                value: *value,
+                exclusive: true,
+            },
+            FieldConstraint::MinLength { value } => PayloadConstraint::MinLength(*value),
+            FieldConstraint::MaxLength { value } => PayloadConstraint::MaxLength(*value),
+            FieldConstraint::MinItems { value } => PayloadConstraint::MinItems(*value),
+            FieldConstraint::MaxItems { value } => PayloadConstraint::MaxItems(*value),
+            FieldConstraint::Pattern { pattern } => PayloadConstraint::Pattern(
+                self.graph
+                    .all_patterns()
+                    .into_iter()
+                    .find(|p| p.id() == pattern)?
+                    .regex()
+                    .to_string(),
+            ),
+        })
+    }
+
+    /// The payload type of a field, with a doc note where the mapping loses
+    /// information (quantities and entity references).
+    fn payload_type(
+        &self,
+        field_type: &FieldType,
+    ) -> Result<(PayloadType, Option<String>), String> {
+        Ok(match field_type {
+            FieldType::Scalar { scalar } => (
+                match scalar {
+                    ScalarType::String => PayloadType::String,
+                    ScalarType::Int => PayloadType::Long,
+                    ScalarType::Decimal => PayloadType::Decimal,
+                    ScalarType::Bool => PayloadType::Boolean,
+                    ScalarType::Timestamp => PayloadType::Timestamp,
+                    ScalarType::Uuid => PayloadType::Uuid,
+                },
+                None,
+            ),
+            FieldType::Quantity { .. } => (
+                PayloadType::Decimal,
+                Some("Quantity in the base unit.".to_string()),
+            ),
+            FieldType::EntityRef { entity } => {
+                let target = self
+                    .entity_contract(entity)
+                    .ok_or_else(|| format!("entity reference '{entity}' has no entity contract"))?;
+                let key = target
+                    .fields
+                    .iter()
+                    .find(|f| f.name == target.key_field)
+                    .ok_or_else(|| {
+                        format!(
+                            "entity '{}' key field '{}' is not declared",
+                            target.name, target.key_field
+                        )
+                    })?;
+                let (ty, _) = self.payload_type(&key.field_type)?;
+                (
+                    ty,
+                    Some(format!("Key `{}` of {}.", target.key_field, target.name)),
+                )
+            }
+            FieldType::Enum { symbol } => (
+                PayloadType::Enum(
+                    self.enums
+                        .get(&symbol.0)
+                        .cloned()
+                        .ok_or_else(|| format!("field references unknown enum '{}'", symbol.0))?,
+                ),
+                None,
+            ),
+            FieldType::List { element } => {
+                let (ty, doc) = self.payload_type(element)?;
+                (PayloadType::List(Box::new(ty)), doc)
+            }
+        })
+    }
+
+    fn entity_contract(&self, entity: &ConceptId) -> Option<&EntityContract> {
+        self.contract
+            .entities
+            .iter()
+            .find(|e| &e.concept_id == entity)
+            .or_else(|| self.graph.entity_contract(entity))
+    }
+}
+
+// ============================================================================
+// Compatibility checking
+// ============================================================================
+
+/// Schema-registry compatibility level, as in Confluent Schema Registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegistryCompatibility {
+    /// No checking.
+    None,
+    /// Consumers using the new schema can read data written with the
+    /// published one.
+    #[default]
+    Backward,
+    /// Consumers using the published schema can read data written with the
+    /// new one.
+    Forward,
+    /// Both `Backward` and `Forward`.
+    Full,
+}
+
+impl std::fmt::Display for RegistryCompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryCompatibility::None => write!(f, "NONE"),
+            RegistryCompatibility::Backward => write!(f, "BACKWARD"),
+            RegistryCompatibility::Forward => write!(f, "FORWARD"),
+            RegistryCompatibility::Full => write!(f, "FULL"),
+        }
+    }
+}
+
+impl std::str::FromStr for RegistryCompatibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(RegistryCompatibility::None),
+            "backward" => Ok(RegistryCompatibility::Backward),
+            "forward" => Ok(RegistryCompatibility::Forward),
+            "full" => Ok(RegistryCompatibility::Full),
+            _ => Err(format!("Unknown registry compatibility level: {}", s)),
+        }
+    }
+}
+
+/// Reading rules of one schema format: every reason data written with
+/// `writer` cannot be read with `reader` (empty when it can).
+pub type ReaderAccepts = fn(reader: &Value, writer: &Value) -> Vec<String>;
+
+/// One reason a projected schema is incompatible with its published version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaIncompatibility {
+    /// Relative path of the schema file (the subject).
+    pub subject: String,
+    /// `Backward` or `Forward`: which reader failed.
+    pub direction: RegistryCompatibility,
+    pub description: String,
+}
+
+/// Result of checking projected schemas against published ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryCheckResult {
+    pub mode: RegistryCompatibility,
+    /// Subjects that had a published version and were compared.
+    pub checked: Vec<String>,
+    /// Subjects with no published version yet.
+    pub new_subjects: Vec<String>,
+    pub incompatibilities: Vec<SchemaIncompatibility>,
+}
+
+impl RegistryCheckResult {
+    pub fn is_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+
+    /// Format the result as a human-readable report.
+    pub fn to_report(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "Registry compatibility check (mode: {})\n",
+            self.mode
+        ));
+        out.push_str(&format!(
+            "Result: {}\n",
+            if self.is_compatible() { "PASS" } else { "FAIL" }
+        ));
+        out.push_str(&format!(
+            "Compared {} published schema(s); {} new\n",
+            self.checked.len(),
+            self.new_subjects.len()
+        ));
+        if !self.incompatibilities.is_empty() {
+            out.push_str(&format!(
+                "\nIncompatibilities ({}):\n",
+                self.incompatibilities.len()
+            ));
+            for i in &self.incompatibilities {
+                out.push_str(&format!(
+                    "  - [{}] {}: {}\n",
+                    i.direction, i.subject, i.description
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Compare projected schema files (relative path → content) with the files of
+/// the same relative path under `published`. Files without a published
+/// counterpart are new subjects and always pass.
+pub fn check_published(
+    published: &Path,
+    files: &BTreeMap<String, String>,
+    mode: RegistryCompatibility,
+    reader_accepts: ReaderAccepts,
+) -> Result<RegistryCheckResult, String> {
+    let mut result = RegistryCheckResult {
+        mode,
+        checked: Vec::new(),
+        new_subjects: Vec::new(),
+        incompatibilities: Vec::new(),
+    };
+    if mode == RegistryCompatibility::None {
+        return Ok(result);
+    }
+    if !published.is_dir() {
+        return Err(format!(
+            "Published schema directory {} does not exist",
+            published.display()
+        ));
+    }
+    for (subject, content) in files {
+        let path = published.join(subject);
+        if !path.exists() {
+            result.new_subjects.push(subject.clone());
+            continue;
+        }
+        let old_text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let old: Value = serde_json::from_str(&old_text)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        let new: Value = serde_json::from_str(content)
+            .map_err(|e| format!("Failed to parse projected {}: {}", subject, e))?;
+        let mut push = |direction, reasons: Vec<String>| {
+            result
+                .incompatibilities
+                .extend(
+                    reasons
+                        .into_iter()
+                        .map(|description| SchemaIncompatibility {
+                            subject: subject.clone(),
+                            direction,
+                            description,
+                        }),
+                );
+        };
+        if matches!(
+            mode,
+            RegistryCompatibility::Backward | RegistryCompatibility::Full
+        ) {
+            push(RegistryCompatibility::Backward, reader_accepts(&new, &old));
+        }
+        if matches!(
+            mode,
+            RegistryCompatibility::Forward | RegistryCompatibility::Full
+        ) {
+            push(RegistryCompatibility::Forward, reader_accepts(&old, &new));
+        }
+        result.checked.push(subject.clone());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{resolve_application_contract, resolve_application_graph};
+
+    const SOURCE: &str = r#"@namespace "shop"
+Entity "Warehouse" in shop
+Resource "Order" units in shop
+pattern "Sku" matches "^[A-Z]{3}$"
+enum Tier { gold = "gold", silver = "silver" }
+entity "Customer" {
+    key customer_id: uuid
+}
+record Order {
+    customer: ref<Customer>
+    skus: list<string> optional (max_items 3)
+    tier: Tier
+    code: string (pattern Sku)
+}
+record OrderFlow {
+    code: string
+}
+"#;
+
+    fn schemas() -> Vec<PayloadSchema> {
+        let sources = serde_json::json!({ "main.sea": SOURCE }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).expect("graph");
+        let contract = resolve_application_contract("main.sea", &sources)
+            .expect("contract")
+            .contract;
+        payload_schemas(&graph, &contract).unwrap()
+    }
+
+    #[test]
+    fn resources_and_records_get_distinct_subjects() {
+        let schemas = schemas();
+        let names: Vec<String> = schemas.iter().map(PayloadSchema::full_name).collect();
+        assert_eq!(
+            names,
+            ["shop.Order", "shop.OrderFlow", "shop.OrderFlow2"],
+            "resource flows and records share one name space"
+        );
+        assert_eq!(schemas[2].doc, "Application record OrderFlow.");
+        let resource = &schemas[1];
+        assert_eq!(resource.doc, "A flow of the Order resource.");
+        let fields: Vec<&str> = resource.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, ["quantity", "unit", "from", "to"]);
+    }
+
+    #[test]
+    fn record_fields_resolve_refs_enums_and_patterns() {
+        let schemas = schemas();
+        let record = &schemas[0];
+        let customer = &record.fields[0];
+        assert_eq!(customer.ty, PayloadType::Uuid);
+        assert_eq!(
+            customer.doc.as_deref(),
+            Some("Key `customer_id` of Customer.")
+        );
+        assert_eq!(
+            record.fields[1].ty,
+            PayloadType::List(Box::new(PayloadType::String))
+        );
+        assert!(record.fields[1].optional);
+        assert_eq!(
+            record.fields[1].constraints,
+            [PayloadConstraint::MaxItems(3)]
+        );
+        let PayloadType::Enum(tier) = &record.fields[2].ty else {
+            panic!("expected enum, got {:?}", record.fields[2].ty);
+        };
+        assert_eq!(tier.full_name(), "shop.Tier");
+        assert_eq!(tier.symbols, ["gold", "silver"]);
+        assert_eq!(
+            record.fields[3].constraints,
+            [PayloadConstraint::Pattern("^[A-Z]{3}$".to_string())]
+        );
+    }
+
+    fn field_names(reader: &Value, writer: &Value) -> Vec<String> {
+        // Toy rule: the reader must not declare fields the writer lacks.
+        let names = |v: &Value| -> Vec<String> {
+            v["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|f| f.as_str().unwrap().to_string())
+                .collect()
+        };
+        let writer = names(writer);
+        names(reader)
+            .into_iter()
+            .filter(|f| !writer.contains(f))
+            .map(|f| format!("missing {f}"))
+            .collect()
+    }
+
+    #[test]
+    fn check_published_applies_mode_directions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.json"), r#"{"fields":["x"]}"#).unwrap();
+        let files = BTreeMap::from([
+            ("a.json".to_string(), r#"{"fields":["x","y"]}"#.to_string()),
+            ("b.json".to_string(), r#"{"fields":[]}"#.to_string()),
+        ]);
+        let check = |mode| check_published(dir.path(), &files, mode, field_names).unwrap();
+
+        let backward = check(RegistryCompatibility::Backward);
+        assert_eq!(backward.checked, ["a.json"]);
+        assert_eq!(backward.new_subjects, ["b.json"]);
+        assert_eq!(backward.incompatibilities.len(), 1);
+        assert_eq!(backward.incompatibilities[0].description, "missing y");
+        assert!(backward.to_report().contains("Result: FAIL"));
+
+        assert!(check(RegistryCompatibility::Forward).is_compatible());
+        let full = check(RegistryCompatibility::Full);
+        assert_eq!(full.incompatibilities.len(), 1);
+        assert_eq!(
+            full.incompatibilities[0].direction,
+            RegistryCompatibility::Backward
+        );
+        let none = check(RegistryCompatibility::None);
+        assert!(none.is_compatible() && none.checked.is_empty());
+    }
+}
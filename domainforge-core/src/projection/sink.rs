@@ -6,7 +6,7 @@
 //! stay byte-for-byte identical.
 
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Component, Path};
 
 /// Write one artifact under the output root, path-traversal-safe.
 pub enum ArtifactSink<'a> {
@@ -22,6 +22,17 @@ impl ArtifactSink<'_> {
         match self {
             ArtifactSink::Dir(root) => write_artifact(root, rel, content),
             ArtifactSink::Memory { prefix, map } => {
+                // Same lexical traversal check a directory sink applies, so
+                // an in-memory render never keys a path outside the root.
+                if rel.is_empty()
+                    || Path::new(rel)
+                        .components()
+                        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+                {
+                    return Err(format!(
+                        "Security: output path '{rel}' escapes output directory"
+                    ));
+                }
                 map.insert(format!("{prefix}{rel}"), content.to_string());
                 Ok(())
             }
@@ -0,0 +1,537 @@
+//! Forward migrations between two [`SqlSchema`] versions, and the file-based
+//! history that remembers the last projected schema.
+//!
+//! Steps are ordered so the script runs top to bottom: drop removed foreign
+//! keys, create schemas and enum types, create tables, alter existing tables
+//! (drop checks → add columns → alter columns → primary key → add checks →
+//! drop columns), drop tables and enum types, then add foreign keys. A step
+//! is flagged breaking when it can lose data or fail against existing rows:
+//! dropped tables or columns, type changes, new `NOT NULL` columns without a
+//! default, tightened nullability, primary-key changes, new checks or foreign
+//! keys on existing columns, and removed enum values (which Postgres cannot
+//! drop, so that step is a manual note).
+
+use super::{
+    add_foreign_key, check_definition, column_definition, create_enum, create_schema, create_table,
+    primary_key_definition, quote, string_literal, SqlSchema, SqlTable,
+};
+use crate::projection::drift::generated_at;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// One statement of a migration script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationStep {
+    pub sql: String,
+    /// Why the step is breaking, if it is.
+    pub breaking: Option<String>,
+}
+
+/// Ordered forward migration from `from_version` to `to_version`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Migration {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Steps flagged as breaking, in script order.
+    pub fn breaking_steps(&self) -> impl Iterator<Item = &MigrationStep> {
+        self.steps.iter().filter(|s| s.breaking.is_some())
+    }
+
+    /// Render the migration as one transaction.
+    pub fn render(&self, model_ref: &str, created_at: &str) -> String {
+        let mut s = format!(
+            "-- PostgreSQL migration {:04} -> {:04} projected by DomainForge from {model_ref}.\n-- {}\n",
+            self.from_version,
+            self.to_version,
+            generated_at(created_at)
+        );
+        let breaking = self.breaking_steps().count();
+        if breaking > 0 {
+            let _ = writeln!(s, "-- {breaking} breaking change(s) flagged below.");
+        }
+        s.push_str("\nBEGIN;\n\n");
+        for step in &self.steps {
+            if let Some(reason) = &step.breaking {
+                let _ = writeln!(s, "-- BREAKING: {reason}");
+            }
+            s.push_str(&step.sql);
+        }
+        s.push_str("\nCOMMIT;\n");
+        s
+    }
+}
+
+/// Compute the forward migration from `old` to `new`.
+pub fn diff(old: &SqlSchema, new: &SqlSchema) -> Migration {
+    let mut steps = Vec::new();
+    let safe = |sql: String| MigrationStep {
+        sql,
+        breaking: None,
+    };
+    let breaking = |sql: String, reason: String| MigrationStep {
+        sql,
+        breaking: Some(reason),
+    };
+    let find_table = |schema: &SqlSchema, t: &SqlTable| {
+        schema
+            .tables
+            .iter()
+            .find(|o| o.schema == t.schema && o.name == t.name)
+            .cloned()
+    };
+
+    // 1. Foreign keys that disappear or change go first, so nothing below is
+    //    blocked by a dangling reference.
+    for old_table in &old.tables {
+        let new_table = find_table(new, old_table);
+        for fk in &old_table.foreign_keys {
+            let kept = new_table
+                .as_ref()
+                .is_some_and(|t| t.foreign_keys.contains(fk));
+            if !kept {
+                steps.push(safe(format!(
+                    "ALTER TABLE {} DROP CONSTRAINT {};\n",
+                    old_table.qualified(),
+                    quote(&fk.name)
+                )));
+            }
+        }
+    }
+
+    // 2. Schemas and enum types.
+    for schema in &new.schemas {
+        if !old.schemas.contains(schema) {
+            steps.push(safe(create_schema(schema)));
+        }
+    }
+    for e in &new.enums {
+        match old
+            .enums
+            .iter()
+            .find(|o| o.schema == e.schema && o.name == e.name)
+        {
+            None => steps.push(safe(create_enum(e))),
+            Some(o) => {
+                for value in e.values.iter().filter(|v| !o.values.contains(v)) {
+                    steps.push(safe(format!(
+                        "ALTER TYPE {} ADD VALUE {};\n",
+                        e.qualified(),
+                        string_literal(value)
+                    )));
+                }
+                for value in o.values.iter().filter(|v| !e.values.contains(v)) {
+                    steps.push(breaking(
+                        format!(
+                            "-- Postgres cannot drop enum values: recreate {} without {} manually.\n",
+                            e.qualified(),
+                            string_literal(value)
+                        ),
+                        format!(
+                            "removes value {} from enum {}",
+                            string_literal(value),
+                            e.qualified()
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    // 3. New tables.
+    for table in &new.tables {
+        if find_table(old, table).is_none() {
+            steps.push(safe(create_table(table)));
+        }
+    }
+
+    // 4. Existing tables.
+    for table in &new.tables {
+        let Some(old_table) = find_table(old, table) else {
+            continue;
+        };
+        alter_table(&old_table, table, &mut steps);
+    }
+
+    // 5. Dropped tables, then enum types nothing references any more.
+    for old_table in &old.tables {
+        if find_table(new, old_table).is_none() {
+            steps.push(breaking(
+                format!("DROP TABLE {};\n", old_table.qualified()),
+                format!("drops table {} and its data", old_table.qualified()),
+            ));
+        }
+    }
+    for e in &old.enums {
+        if !new
+            .enums
+            .iter()
+            .any(|n| n.schema == e.schema && n.name == e.name)
+        {
+            steps.push(safe(format!("DROP TYPE {};\n", e.qualified())));
+        }
+    }
+
+    // 6. Foreign keys that are new or changed.
+    for table in &new.tables {
+        let old_table = find_table(old, table);
+        for fk in &table.foreign_keys {
+            if old_table
+                .as_ref()
+                .is_some_and(|t| t.foreign_keys.contains(fk))
+            {
+                continue;
+            }
+            let existing_column = old_table
+                .as_ref()
+                .is_some_and(|t| t.column(&fk.column).is_some());
+            let sql = add_foreign_key(table, fk);
+            steps.push(if existing_column {
+                breaking(
+                    sql,
+                    format!(
+                        "adds foreign key {} on existing column {}; existing rows may violate it",
+                        quote(&fk.name),
+                        quote(&fk.column)
+                    ),
+                )
+            } else {
+                safe(sql)
+            });
+        }
+    }
+
+    Migration {
+        from_version: old.version,
+        to_version: new.version,
+        steps,
+    }
+}
+
+fn alter_table(old: &SqlTable, new: &SqlTable, steps: &mut Vec<MigrationStep>) {
+    let table = new.qualified();
+    let column_ref = |column: &str| format!("{table}.{}", quote(column));
+    let push = |steps: &mut Vec<MigrationStep>, sql: String, reason: Option<String>| {
+        steps.push(MigrationStep {
+            sql: format!("ALTER TABLE {table} {sql};\n"),
+            breaking: reason,
+        });
+    };
+
+    // Checks that disappear or change; a dropped column takes its checks with it.
+    for check in &old.checks {
+        if !new.checks.contains(check) && new.column(&check.column).is_some() {
+            push(
+                steps,
+                format!("DROP CONSTRAINT {}", quote(&check.name)),
+                None,
+            );
+        }
+    }
+
+    for column in &new.columns {
+        match old.column(&column.name) {
+            None => {
+                let reason = (column.not_null && column.default.is_none()).then(|| {
+                    format!(
+                        "adds NOT NULL column {} without a default; fails on non-empty tables",
+                        column_ref(&column.name)
+                    )
+                });
+                push(
+                    steps,
+                    format!("ADD COLUMN {}", column_definition(column)),
+                    reason,
+                );
+            }
+            Some(previous) => {
+                let col = quote(&column.name);
+                if previous.sql_type != column.sql_type {
+                    push(
+                        steps,
+                        format!(
+                            "ALTER COLUMN {col} TYPE {ty} USING {col}::{ty}",
+                            ty = column.sql_type
+                        ),
+                        Some(format!(
+                            "changes type of {} from {} to {}",
+                            column_ref(&column.name),
+                            previous.sql_type,
+                            column.sql_type
+                        )),
+                    );
+                }
+                if previous.default != column.default {
+                    let sql = match &column.default {
+                        Some(default) => format!("ALTER COLUMN {col} SET DEFAULT {default}"),
+                        None => format!("ALTER COLUMN {col} DROP DEFAULT"),
+                    };
+                    push(steps, sql, None);
+                }
+                match (previous.not_null, column.not_null) {
+                    (false, true) => push(
+                        steps,
+                        format!("ALTER COLUMN {col} SET NOT NULL"),
+                        Some(format!(
+                            "makes {} required; fails if any row holds NULL",
+                            column_ref(&column.name)
+                        )),
+                    ),
+                    (true, false) => push(steps, format!("ALTER COLUMN {col} DROP NOT NULL"), None),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if old.primary_key != new.primary_key {
+        push(
+            steps,
+            format!("DROP CONSTRAINT {}", quote(&old.primary_key.name)),
+            Some(format!("replaces the primary key of {table}")),
+        );
+        push(
+            steps,
+            format!("ADD {}", primary_key_definition(&new.primary_key)),
+            Some(format!(
+                "replaces the primary key of {table}; existing rows may violate it"
+            )),
+        );
+    }
+
+    for check in &new.checks {
+        if old.checks.contains(check) {
+            continue;
+        }
+        let reason = old.column(&check.column).is_some().then(|| {
+            format!(
+                "adds check {} on existing column {}; existing rows may violate it",
+                quote(&check.name),
+                column_ref(&check.column)
+            )
+        });
+        push(steps, format!("ADD {}", check_definition(check)), reason);
+    }
+
+    for column in &old.columns {
+        if new.column(&column.name).is_none() {
+            push(
+                steps,
+                format!("DROP COLUMN {}", quote(&column.name)),
+                Some(format!(
+                    "drops column {} and its data",
+                    column_ref(&column.name)
+                )),
+            );
+        }
+    }
+}
+
+/// File-based schema history: the last projected [`SqlSchema`] as JSON.
+pub struct SqlSchemaHistory {
+    /// Directory where schema history is stored
+    history_dir: PathBuf,
+}
+
+impl SqlSchemaHistory {
+    /// Create a new SqlSchemaHistory with the given directory.
+    pub fn new(history_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            history_dir: history_dir.into(),
+        }
+    }
+
+    fn schema_path(&self) -> PathBuf {
+        self.history_dir.join("postgres_schema.json")
+    }
+
+    /// Load the previously projected schema.
+    pub fn load(&self) -> Result<Option<SqlSchema>, String> {
+        let path = self.schema_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read schema history: {}", e))?;
+        let schema: SqlSchema = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse schema history: {}", e))?;
+        Ok(Some(schema))
+    }
+
+    /// Save a schema to history.
+    pub fn save(&self, schema: &SqlSchema) -> Result<(), String> {
+        std::fs::create_dir_all(&self.history_dir)
+            .map_err(|e| format!("Failed to create history directory: {}", e))?;
+        let content = serde_json::to_string_pretty(schema)
+            .map_err(|e| format!("Failed to serialize schema: {}", e))?;
+        std::fs::write(self.schema_path(), content)
+            .map_err(|e| format!("Failed to write schema history: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{resolve, schema, FIXED_TS, SOURCE};
+    use super::super::{project_sql_postgres_in_memory, qualified};
+    use super::*;
+
+    fn migrate(before: &str, after: &str) -> Migration {
+        let old = schema(before);
+        let (graph, contract) = resolve(after);
+        let new = SqlSchema::build(&graph, &contract, Some(&old)).unwrap();
+        diff(&old, &new)
+    }
+
+    fn statements(migration: &Migration) -> Vec<(&str, bool)> {
+        migration
+            .steps
+            .iter()
+            .map(|s| (s.sql.trim_end(), s.breaking.is_some()))
+            .collect()
+    }
+
+    #[test]
+    fn additive_changes_are_not_breaking() {
+        let after = SOURCE
+            .replace(
+                "enum Channel { web = \"web\", store = \"store\" }",
+                "enum Channel { web = \"web\", store = \"store\", phone = \"phone\" }",
+            )
+            .replace(
+                "    tier: int default 1\n",
+                "    tier: int default 1\n    nickname: string optional (max_length 20)\n",
+            )
+            + "entity \"Coupon\" {\n    key code: string\n    order: ref<Order>\n}\n";
+        let migration = migrate(SOURCE, &after);
+        assert_eq!((migration.from_version, migration.to_version), (1, 2));
+        assert_eq!(
+            statements(&migration),
+            vec![
+                ("ALTER TYPE \"shop\".\"channel\" ADD VALUE 'phone';", false),
+                (
+                    "CREATE TABLE \"shop\".\"coupon\" (\n    \"code\" text NOT NULL,\n    \"order\" uuid NOT NULL, -- key `order_id` of Order\n    CONSTRAINT \"coupon_pkey\" PRIMARY KEY (\"code\")\n);",
+                    false
+                ),
+                ("ALTER TABLE \"shop\".\"customer\" ADD COLUMN \"nickname\" text;", false),
+                (
+                    "ALTER TABLE \"shop\".\"customer\" ADD CONSTRAINT \"customer_nickname_max_length\" CHECK (char_length(\"nickname\") <= 20);",
+                    false
+                ),
+                (
+                    "ALTER TABLE \"shop\".\"coupon\" ADD CONSTRAINT \"coupon_order_fkey\" FOREIGN KEY (\"order\") REFERENCES \"shop\".\"order\" (\"order_id\");",
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn destructive_changes_are_flagged_in_order() {
+        let after = SOURCE
+            .replace(
+                "    tier: int default 1\n",
+                "    tier: decimal\n    email: string\n",
+            )
+            .replace(
+                "    customer: ref<Customer>\n",
+                "    customer: ref<Customer> optional\n",
+            )
+            .replace(
+                "    discount: decimal optional (min 0, max 0.5)\n",
+                "    discount: decimal (min 0, max 0.25)\n",
+            )
+            .replace("    skus: list<string> (min_items 1)\n", "");
+        let migration = migrate(SOURCE, &after);
+        assert_eq!(
+            statements(&migration),
+            vec![
+                (
+                    "ALTER TABLE \"shop\".\"customer\" ALTER COLUMN \"tier\" TYPE numeric USING \"tier\"::numeric;",
+                    true
+                ),
+                ("ALTER TABLE \"shop\".\"customer\" ALTER COLUMN \"tier\" DROP DEFAULT;", false),
+                ("ALTER TABLE \"shop\".\"customer\" ADD COLUMN \"email\" text NOT NULL;", true),
+                ("ALTER TABLE \"shop\".\"order\" DROP CONSTRAINT \"order_discount_max\";", false),
+                ("ALTER TABLE \"shop\".\"order\" ALTER COLUMN \"customer\" DROP NOT NULL;", false),
+                ("ALTER TABLE \"shop\".\"order\" ALTER COLUMN \"discount\" SET NOT NULL;", true),
+                (
+                    "ALTER TABLE \"shop\".\"order\" ADD CONSTRAINT \"order_discount_max\" CHECK (\"discount\" <= 0.25);",
+                    true
+                ),
+                ("ALTER TABLE \"shop\".\"order\" DROP COLUMN \"skus\";", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn dropped_tables_and_enum_values_are_flagged() {
+        let after = SOURCE
+            .replace(
+                "enum Channel { web = \"web\", store = \"store\" }",
+                "enum Channel { web = \"web\" }",
+            )
+            .replace("    customer: ref<Customer>\n", "");
+        let after = after[..after.find("entity \"Customer\"").unwrap()].to_string()
+            + &after[after.find("entity \"Order\"").unwrap()..];
+        let migration = migrate(SOURCE, &after);
+        let breaking: Vec<&str> = migration
+            .breaking_steps()
+            .filter_map(|s| s.breaking.as_deref())
+            .collect();
+        assert_eq!(
+            breaking,
+            vec![
+                "removes value 'store' from enum \"shop\".\"channel\"",
+                "drops column \"shop\".\"order\".\"customer\" and its data",
+                "drops table \"shop\".\"customer\" and its data",
+            ]
+        );
+        assert_eq!(
+            migration.steps.last().unwrap().sql,
+            format!("DROP TABLE {};\n", qualified("shop", "customer"))
+        );
+    }
+
+    #[test]
+    fn migration_is_written_next_to_the_schema() {
+        let old = schema(SOURCE);
+        let (graph, contract) = resolve(&SOURCE.replace("    tier: int default 1\n", ""));
+        let files = project_sql_postgres_in_memory(
+            &graph,
+            &contract,
+            "main.sea",
+            Some(FIXED_TS.into()),
+            Some(&old),
+        )
+        .unwrap();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec!["migrations/0002_migration.sql", "schema.sql"]
+        );
+        let script = &files["migrations/0002_migration.sql"];
+        assert!(script.starts_with(
+            "-- PostgreSQL migration 0001 -> 0002 projected by DomainForge from main.sea.\n-- generated-at: 2026-07-02T00:00:00+00:00\n-- 1 breaking change(s) flagged below.\n\nBEGIN;\n\n"
+        ));
+        assert!(script.ends_with(
+            "-- BREAKING: drops column \"shop\".\"customer\".\"tier\" and its data\nALTER TABLE \"shop\".\"customer\" DROP COLUMN \"tier\";\n\nCOMMIT;\n"
+        ));
+        assert!(files["schema.sql"].contains("-- Schema version 2.\n"));
+    }
+
+    #[test]
+    fn history_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = SqlSchemaHistory::new(dir.path().join("history"));
+        assert_eq!(history.load().unwrap(), None);
+        let schema = schema(SOURCE);
+        history.save(&schema).unwrap();
+        assert_eq!(history.load().unwrap(), Some(schema));
+    }
+}
@@ -0,0 +1,768 @@
+//! Relational-schema projection: the application contract's entity bodies
+//! become PostgreSQL DDL (`schema.sql`), and — given the previously projected
+//! schema from a [`SqlSchemaHistory`] — an ordered forward migration script
+//! (`migrations/<version>_migration.sql`, see [`migration`]).
+//!
+//! Mapping:
+//! - namespace → `CREATE SCHEMA`
+//! - `enum` → `CREATE TYPE … AS ENUM` over the members' wire values
+//! - `entity` with a body → `CREATE TABLE`, one column per field in authored
+//!   order; the `key` field is the primary key; non-`optional` fields are
+//!   `NOT NULL`; field defaults become column defaults
+//! - `ref<E>` → a column of `E`'s key type plus a foreign key, added by
+//!   `ALTER TABLE` after every table exists so reference cycles are legal
+//! - `list<T>` → a Postgres array (`list<ref<E>>` carries no foreign key)
+//! - `FieldConstraint` → named `CHECK` constraints; on list fields only
+//!   `min_items` / `max_items` are enforced
+//! - quantities → `numeric` in the base unit
+//!
+//! Entities without a body have no key and produce no table. Identifiers are
+//! slugs registered per Postgres schema (tables and enum types share one
+//! namespace there) and always double-quoted, so reserved words like `order`
+//! are safe. Tables, enums, and constraints are sorted, so output is
+//! byte-identical run-to-run for a fixed `created_at`.
+
+pub mod migration;
+
+pub use migration::{diff, Migration, MigrationStep, SqlSchemaHistory};
+
+use crate::application::{
+    ApplicationContract, EntityContract, FieldConstraint, FieldContract, FieldType, ScalarType,
+    TypedValue,
+};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::projection::drift::generated_at;
+use crate::projection::ids::{content_hash, NameRegistrar};
+use crate::projection::sink::ArtifactSink;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+/// Postgres truncates identifiers longer than this many bytes.
+const MAX_IDENT_LEN: usize = 63;
+
+/// The projected relational schema; also the schema-history document.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlSchema {
+    /// Monotonic version, bumped whenever the schema differs from the
+    /// previous one in history.
+    pub version: u32,
+    pub schemas: Vec<String>,
+    pub enums: Vec<SqlEnum>,
+    pub tables: Vec<SqlTable>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlEnum {
+    pub schema: String,
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlTable {
+    pub schema: String,
+    pub name: String,
+    /// Authored entity name.
+    pub entity: String,
+    pub columns: Vec<SqlColumn>,
+    pub primary_key: SqlPrimaryKey,
+    pub checks: Vec<SqlCheck>,
+    pub foreign_keys: Vec<SqlForeignKey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlColumn {
+    pub name: String,
+    pub sql_type: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+    /// Rendered as a trailing SQL comment; never affects migrations.
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlPrimaryKey {
+    pub name: String,
+    pub column: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlCheck {
+    pub name: String,
+    pub column: String,
+    pub expression: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlForeignKey {
+    pub name: String,
+    pub column: String,
+    pub ref_schema: String,
+    pub ref_table: String,
+    pub ref_column: String,
+}
+
+impl SqlTable {
+    /// `"schema"."table"`.
+    pub fn qualified(&self) -> String {
+        qualified(&self.schema, &self.name)
+    }
+
+    pub fn column(&self, name: &str) -> Option<&SqlColumn> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+impl SqlEnum {
+    pub fn qualified(&self) -> String {
+        qualified(&self.schema, &self.name)
+    }
+}
+
+impl SqlSchema {
+    /// Build the schema from the application contract. `previous` only
+    /// decides the version: unchanged schemas keep it, changed ones bump it.
+    pub fn build(
+        graph: &Graph,
+        contract: &ApplicationContract,
+        previous: Option<&SqlSchema>,
+    ) -> Result<SqlSchema, String> {
+        let mut builder = Builder {
+            graph,
+            contract,
+            schema_names: NameRegistrar::new(),
+            local_names: HashMap::new(),
+            schemas: BTreeMap::new(),
+            enum_types: HashMap::new(),
+            tables: HashMap::new(),
+        };
+
+        let mut enums = Vec::new();
+        let mut sorted_enums: Vec<_> = contract
+            .enums
+            .iter()
+            .map(|e| (enum_namespace(&e.id.0, &e.name), e))
+            .collect();
+        sorted_enums.sort_by(|a, b| (&a.0, &a.1.name).cmp(&(&b.0, &b.1.name)));
+        for (namespace, e) in sorted_enums {
+            let schema = builder.schema(&namespace);
+            let name = builder.local_name(&schema, &e.name);
+            builder
+                .enum_types
+                .insert(e.id.0.clone(), qualified(&schema, &name));
+            enums.push(SqlEnum {
+                schema,
+                name,
+                values: e.members.iter().map(|m| m.wire.clone()).collect(),
+            });
+        }
+
+        let mut entities: Vec<(String, &EntityContract)> = contract
+            .entities
+            .iter()
+            .map(|e| {
+                let namespace = graph
+                    .get_entity(&e.concept_id)
+                    .map(|entity| entity.namespace().to_string())
+                    .unwrap_or_else(|| "default".to_string());
+                (namespace, e)
+            })
+            .collect();
+        entities.sort_by(|a, b| (&a.0, &a.1.name).cmp(&(&b.0, &b.1.name)));
+        // Register every table name first so references resolve regardless
+        // of declaration order.
+        for (namespace, e) in &entities {
+            let schema = builder.schema(namespace);
+            let name = builder.local_name(&schema, &e.name);
+            builder.tables.insert(e.concept_id.clone(), (schema, name));
+        }
+        let mut tables = entities
+            .iter()
+            .map(|(_, e)| builder.table(e))
+            .collect::<Result<Vec<_>, _>>()?;
+        tables.sort_by(|a, b| (&a.schema, &a.name).cmp(&(&b.schema, &b.name)));
+
+        let mut schema = SqlSchema {
+            version: 1,
+            schemas: builder.schemas.into_values().collect(),
+            enums,
+            tables,
+        };
+        schema.schemas.sort();
+        if let Some(previous) = previous {
+            schema.version = if schema.same_shape(previous) {
+                previous.version
+            } else {
+                previous.version + 1
+            };
+        }
+        Ok(schema)
+    }
+
+    /// True when migrating from `other` would be a no-op (versions and
+    /// column comments are ignored).
+    pub fn same_shape(&self, other: &SqlSchema) -> bool {
+        diff(other, self).is_empty()
+    }
+
+    /// Render the full DDL.
+    pub fn render(&self, model_ref: &str, created_at: &str) -> String {
+        let mut s = String::new();
+        let _ = writeln!(
+            s,
+            "-- PostgreSQL schema projected by DomainForge from {model_ref}.\n-- {}\n-- Schema version {}.",
+            generated_at(created_at),
+            self.version
+        );
+        for schema in &self.schemas {
+            let _ = write!(s, "\n{}", create_schema(schema));
+        }
+        for e in &self.enums {
+            let _ = write!(s, "\n{}", create_enum(e));
+        }
+        for table in &self.tables {
+            let _ = write!(s, "\n{}", create_table(table));
+        }
+        let foreign_keys: Vec<String> = self
+            .tables
+            .iter()
+            .flat_map(|t| t.foreign_keys.iter().map(move |fk| add_foreign_key(t, fk)))
+            .collect();
+        if !foreign_keys.is_empty() {
+            s.push('\n');
+            for fk in foreign_keys {
+                s.push_str(&fk);
+            }
+        }
+        s
+    }
+}
+
+/// Emit `schema.sql`, plus `migrations/<version>_migration.sql` when
+/// `previous` differs from the current schema; returns the emitted paths.
+pub fn emit(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    model_ref: &str,
+    created_at: Option<String>,
+    previous: Option<&SqlSchema>,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let schema = SqlSchema::build(graph, contract, previous)?;
+    let mut files = vec!["schema.sql".to_string()];
+    sink.write(&files[0], &schema.render(model_ref, &created_at))?;
+    if let Some(previous) = previous {
+        let migration = diff(previous, &schema);
+        if !migration.is_empty() {
+            let file = format!("migrations/{:04}_migration.sql", migration.to_version);
+            sink.write(&file, &migration.render(model_ref, &created_at))?;
+            files.push(file);
+        }
+    }
+    Ok(files)
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_sql_postgres_in_memory(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    model_ref: &str,
+    created_at: Option<String>,
+    previous: Option<&SqlSchema>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, contract, model_ref, created_at, previous, &mut sink)?;
+    Ok(map)
+}
+
+struct Builder<'a> {
+    graph: &'a Graph,
+    contract: &'a ApplicationContract,
+    schema_names: NameRegistrar,
+    /// Postgres schema → registrar of its table and type names.
+    local_names: HashMap<String, NameRegistrar>,
+    /// namespace → Postgres schema name.
+    schemas: BTreeMap<String, String>,
+    /// enum symbol → qualified type.
+    enum_types: HashMap<String, String>,
+    /// entity → (schema, table).
+    tables: HashMap<ConceptId, (String, String)>,
+}
+
+impl Builder<'_> {
+    fn schema(&mut self, namespace: &str) -> String {
+        if let Some(schema) = self.schemas.get(namespace) {
+            return schema.clone();
+        }
+        let schema = pg_ident(&self.schema_names.register("slug", namespace));
+        self.schemas.insert(namespace.to_string(), schema.clone());
+        schema
+    }
+
+    /// Table and enum names share one registrar per schema, because a
+    /// Postgres table also defines a composite type of the same name.
+    fn local_name(&mut self, schema: &str, name: &str) -> String {
+        pg_ident(
+            &self
+                .local_names
+                .entry(schema.to_string())
+                .or_default()
+                .register("slug", name),
+        )
+    }
+
+    fn table(&self, entity: &EntityContract) -> Result<SqlTable, String> {
+        let (schema, name) = self.tables[&entity.concept_id].clone();
+        let mut columns_reg = NameRegistrar::new();
+        let mut columns = Vec::new();
+        let mut checks = Vec::new();
+        let mut foreign_keys = Vec::new();
+        let mut primary_key = None;
+        for field in &entity.fields {
+            let column = pg_ident(&columns_reg.register("slug", &field.name));
+            let ty = self.column_type(&field.field_type)?;
+            if let Some((ref_schema, ref_table, ref_column)) = ty.reference {
+                foreign_keys.push(SqlForeignKey {
+                    name: constraint_name(&[&name, &column, "fkey"]),
+                    column: column.clone(),
+                    ref_schema,
+                    ref_table,
+                    ref_column,
+                });
+            }
+            checks.extend(self.checks(&name, &column, field));
+            if field.name == entity.key_field {
+                primary_key = Some(SqlPrimaryKey {
+                    name: constraint_name(&[&name, "pkey"]),
+                    column: column.clone(),
+                });
+            }
+            columns.push(SqlColumn {
+                name: column,
+                sql_type: ty.sql_type,
+                not_null: !field.optional,
+                default: field.default.as_ref().map(default_literal),
+                comment: ty.comment,
+            });
+        }
+        let primary_key = primary_key.ok_or_else(|| {
+            format!(
+                "entity '{}' key field '{}' is not declared",
+                entity.name, entity.key_field
+            )
+        })?;
+        checks.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(SqlTable {
+            schema,
+            name,
+            entity: entity.name.clone(),
+            columns,
+            primary_key,
+            checks,
+            foreign_keys,
+        })
+    }
+
+    fn entity_contract(&self, entity: &ConceptId) -> Option<&EntityContract> {
+        self.contract
+            .entities
+            .iter()
+            .find(|e| &e.concept_id == entity)
+            .or_else(|| self.graph.entity_contract(entity))
+    }
+
+    fn column_type(&self, field_type: &FieldType) -> Result<ColumnType, String> {
+        Ok(match field_type {
+            FieldType::Scalar { scalar } => ColumnType::plain(match scalar {
+                ScalarType::String => "text",
+                ScalarType::Int => "bigint",
+                ScalarType::Decimal => "numeric",
+                ScalarType::Bool => "boolean",
+                ScalarType::Timestamp => "timestamptz",
+                ScalarType::Uuid => "uuid",
+            }),
+            FieldType::Quantity { .. } => ColumnType {
+                sql_type: "numeric".to_string(),
+                reference: None,
+                comment: Some("quantity in the base unit".to_string()),
+            },
+            FieldType::EntityRef { entity } => {
+                let target = self
+                    .entity_contract(entity)
+                    .ok_or_else(|| format!("entity reference '{entity}' has no entity contract"))?;
+                let key = target
+                    .fields
+                    .iter()
+                    .find(|f| f.name == target.key_field)
+                    .ok_or_else(|| {
+                        format!(
+                            "entity '{}' key field '{}' is not declared",
+                            target.name, target.key_field
+                        )
+                    })?;
+                let key_type = self.column_type(&key.field_type)?;
+                let reference = self.tables.get(entity).map(|(schema, table)| {
+                    (
+                        schema.clone(),
+                        table.clone(),
+                        pg_ident(&crate::projection::ids::slug(&target.key_field)),
+                    )
+                });
+                ColumnType {
+                    sql_type: key_type.sql_type,
+                    comment: Some(format!("key `{}` of {}", target.key_field, target.name)),
+                    reference,
+                }
+            }
+            FieldType::Enum { symbol } => ColumnType::plain(
+                self.enum_types
+                    .get(&symbol.0)
+                    .ok_or_else(|| format!("field references unknown enum '{}'", symbol.0))?,
+            ),
+            FieldType::List { element } => {
+                let element = self.column_type(element)?;
+                ColumnType {
+                    sql_type: format!("{}[]", element.sql_type),
+                    reference: None,
+                    comment: element.comment,
+                }
+            }
+        })
+    }
+
+    fn checks(&self, table: &str, column: &str, field: &FieldContract) -> Vec<SqlCheck> {
+        let is_list = matches!(field.field_type, FieldType::List { .. });
+        let col = quote(column);
+        field
+            .constraints
+            .iter()
+            .filter_map(|constraint| {
+                let (kind, expression) = match (constraint, is_list) {
+                    (FieldConstraint::MinItems { value }, true) => {
+                        ("min_items", format!("cardinality({col}) >= {value}"))
+                    }
+                    (FieldConstraint::MaxItems { value }, true) => {
+                        ("max_items", format!("cardinality({col}) <= {value}"))
+                    }
+                    (_, true) => return None,
+                    (FieldConstraint::Min { value }, false) => {
+                        ("min", format!("{col} >= {}", value.normalize()))
+                    }
+                    (FieldConstraint::ExclusiveMin { value }, false) => {
+                        ("exclusive_min", format!("{col} > {}", value.normalize()))
+                    }
+                    (FieldConstraint::Max { value }, false) => {
+                        ("max", format!("{col} <= {}", value.normalize()))
+                    }
+                    (FieldConstraint::ExclusiveMax { value }, false) => {
+                        ("exclusive_max", format!("{col} < {}", value.normalize()))
+                    }
+                    (FieldConstraint::MinLength { value }, false) => {
+                        ("min_length", format!("char_length({col}) >= {value}"))
+                    }
+                    (FieldConstraint::MaxLength { value }, false) => {
+                        ("max_length", format!("char_length({col}) <= {value}"))
+                    }
+                    (FieldConstraint::Pattern { pattern }, false) => {
+                        let regex = self
+                            .graph
+                            .all_patterns()
+                            .into_iter()
+                            .find(|p| p.id() == pattern)?
+                            .regex()
+                            .to_string();
+                        ("pattern", format!("{col} ~ {}", string_literal(&regex)))
+                    }
+                    (
+                        FieldConstraint::MinItems { .. } | FieldConstraint::MaxItems { .. },
+                        false,
+                    ) => return None,
+                };
+                Some(SqlCheck {
+                    name: constraint_name(&[table, column, kind]),
+                    column: column.to_string(),
+                    expression,
+                })
+            })
+            .collect()
+    }
+}
+
+struct ColumnType {
+    sql_type: String,
+    /// (schema, table, key column) of a foreign-key target.
+    reference: Option<(String, String, String)>,
+    comment: Option<String>,
+}
+
+impl ColumnType {
+    fn plain(sql_type: &str) -> Self {
+        ColumnType {
+            sql_type: sql_type.to_string(),
+            reference: None,
+            comment: None,
+        }
+    }
+}
+
+/// Namespace of an application symbol ID (`<namespace>.enum.<name>`).
+fn enum_namespace(id: &str, name: &str) -> String {
+    id.strip_suffix(&format!(".enum.{name}"))
+        .unwrap_or("default")
+        .to_string()
+}
+
+/// Cap an identifier at Postgres' limit, keeping it unique with a hash
+/// suffix instead of letting the server truncate it silently.
+fn pg_ident(raw: &str) -> String {
+    if raw.len() <= MAX_IDENT_LEN {
+        return raw.to_string();
+    }
+    format!(
+        "{}_{}",
+        &raw[..MAX_IDENT_LEN - 9],
+        &content_hash(&[raw])[..8]
+    )
+}
+
+fn constraint_name(parts: &[&str]) -> String {
+    pg_ident(&parts.join("_"))
+}
+
+/// Double-quoted SQL identifier.
+pub fn quote(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// `"schema"."name"`.
+pub fn qualified(schema: &str, name: &str) -> String {
+    format!("{}.{}", quote(schema), quote(name))
+}
+
+/// Single-quoted SQL string literal.
+pub(crate) fn string_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+fn default_literal(value: &TypedValue) -> String {
+    match value {
+        TypedValue::String(s) => string_literal(s),
+        TypedValue::Int(i) => i.to_string(),
+        TypedValue::Decimal(d) => d.normalize().to_string(),
+        TypedValue::Bool(b) => b.to_string(),
+        TypedValue::Timestamp(t) => format!("{}::timestamptz", string_literal(&t.to_rfc3339())),
+        TypedValue::Uuid(u) => format!("{}::uuid", string_literal(&u.to_string())),
+        TypedValue::Quantity { base_value, .. } => base_value.normalize().to_string(),
+        TypedValue::EntityRef { key, .. } => default_literal(key),
+        TypedValue::Enum { wire, .. } => string_literal(wire),
+        TypedValue::List(items) if items.is_empty() => "'{}'".to_string(),
+        TypedValue::List(items) => format!(
+            "ARRAY[{}]",
+            items
+                .iter()
+                .map(default_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+pub(crate) fn create_schema(schema: &str) -> String {
+    format!("CREATE SCHEMA IF NOT EXISTS {};\n", quote(schema))
+}
+
+pub(crate) fn create_enum(e: &SqlEnum) -> String {
+    format!(
+        "CREATE TYPE {} AS ENUM ({});\n",
+        e.qualified(),
+        e.values
+            .iter()
+            .map(|v| string_literal(v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+pub(crate) fn column_definition(column: &SqlColumn) -> String {
+    let mut s = format!("{} {}", quote(&column.name), column.sql_type);
+    if column.not_null {
+        s.push_str(" NOT NULL");
+    }
+    if let Some(default) = &column.default {
+        let _ = write!(s, " DEFAULT {default}");
+    }
+    s
+}
+
+pub(crate) fn primary_key_definition(pk: &SqlPrimaryKey) -> String {
+    format!(
+        "CONSTRAINT {} PRIMARY KEY ({})",
+        quote(&pk.name),
+        quote(&pk.column)
+    )
+}
+
+pub(crate) fn check_definition(check: &SqlCheck) -> String {
+    format!(
+        "CONSTRAINT {} CHECK ({})",
+        quote(&check.name),
+        check.expression
+    )
+}
+
+/// `CREATE TABLE` without foreign keys (those are added afterwards).
+pub(crate) fn create_table(table: &SqlTable) -> String {
+    let mut lines: Vec<(String, Option<&str>)> = table
+        .columns
+        .iter()
+        .map(|c| (column_definition(c), c.comment.as_deref()))
+        .collect();
+    lines.push((primary_key_definition(&table.primary_key), None));
+    lines.extend(table.checks.iter().map(|c| (check_definition(c), None)));
+    let mut s = format!("CREATE TABLE {} (\n", table.qualified());
+    let last = lines.len() - 1;
+    for (i, (line, comment)) in lines.into_iter().enumerate() {
+        let comma = if i == last { "" } else { "," };
+        match comment {
+            Some(comment) => {
+                let _ = writeln!(s, "    {line}{comma} -- {comment}");
+            }
+            None => {
+                let _ = writeln!(s, "    {line}{comma}");
+            }
+        }
+    }
+    s.push_str(");\n");
+    s
+}
+
+pub(crate) fn add_foreign_key(table: &SqlTable, fk: &SqlForeignKey) -> String {
+    format!(
+        "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({});\n",
+        table.qualified(),
+        quote(&fk.name),
+        quote(&fk.column),
+        qualified(&fk.ref_schema, &fk.ref_table),
+        quote(&fk.ref_column)
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::application::{resolve_application_contract, resolve_application_graph};
+
+    pub(crate) const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+    pub(crate) const SOURCE: &str = r#"@namespace "shop"
+pattern "Sku" matches "^[A-Z]{3}-[0-9]+$"
+enum Channel { web = "web", store = "store" }
+entity "Customer" {
+    key customer_id: uuid
+    name: string (min_length 1, max_length 80)
+    tier: int default 1
+}
+entity "Order" {
+    key order_id: uuid
+    customer: ref<Customer>
+    channel: Channel
+    skus: list<string> (min_items 1)
+    lead: string optional (pattern Sku)
+    discount: decimal optional (min 0, max 0.5)
+}
+"#;
+
+    pub(crate) fn resolve(source: &str) -> (Graph, ApplicationContract) {
+        let sources = serde_json::json!({ "main.sea": source }).to_string();
+        let graph = resolve_application_graph("main.sea", &sources).expect("graph");
+        let document = resolve_application_contract("main.sea", &sources).expect("contract");
+        (graph, document.contract)
+    }
+
+    pub(crate) fn schema(source: &str) -> SqlSchema {
+        let (graph, contract) = resolve(source);
+        SqlSchema::build(&graph, &contract, None).unwrap()
+    }
+
+    #[test]
+    fn entities_become_tables_with_keys_checks_and_defaults() {
+        let (graph, contract) = resolve(SOURCE);
+        let files = project_sql_postgres_in_memory(
+            &graph,
+            &contract,
+            "main.sea",
+            Some(FIXED_TS.into()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["schema.sql"]);
+        let sql = &files["schema.sql"];
+        assert!(sql.starts_with(
+            "-- PostgreSQL schema projected by DomainForge from main.sea.\n-- generated-at: 2026-07-02T00:00:00+00:00\n-- Schema version 1.\n"
+        ));
+        assert!(sql.contains("CREATE SCHEMA IF NOT EXISTS \"shop\";\n"));
+        assert!(sql.contains("CREATE TYPE \"shop\".\"channel\" AS ENUM ('web', 'store');\n"));
+        assert!(sql.contains(
+            "CREATE TABLE \"shop\".\"customer\" (\n    \"customer_id\" uuid NOT NULL,\n    \"name\" text NOT NULL,\n    \"tier\" bigint NOT NULL DEFAULT 1,\n    CONSTRAINT \"customer_pkey\" PRIMARY KEY (\"customer_id\"),\n    CONSTRAINT \"customer_name_max_length\" CHECK (char_length(\"name\") <= 80),\n    CONSTRAINT \"customer_name_min_length\" CHECK (char_length(\"name\") >= 1)\n);\n"
+        ));
+        assert!(sql.contains("    \"customer\" uuid NOT NULL, -- key `customer_id` of Customer\n"));
+        assert!(sql.contains("    \"channel\" \"shop\".\"channel\" NOT NULL,\n"));
+        assert!(sql.contains("    \"skus\" text[] NOT NULL,\n"));
+        assert!(sql.contains("    \"discount\" numeric,\n"));
+        assert!(sql.contains(
+            "    CONSTRAINT \"order_lead_pattern\" CHECK (\"lead\" ~ '^[A-Z]{3}-[0-9]+$'),\n"
+        ));
+        assert!(sql.contains(
+            "    CONSTRAINT \"order_skus_min_items\" CHECK (cardinality(\"skus\") >= 1)\n"
+        ));
+        assert!(sql.ends_with(
+            "\nALTER TABLE \"shop\".\"order\" ADD CONSTRAINT \"order_customer_fkey\" FOREIGN KEY (\"customer\") REFERENCES \"shop\".\"customer\" (\"customer_id\");\n"
+        ));
+    }
+
+    #[test]
+    fn version_only_bumps_on_change() {
+        let (graph, contract) = resolve(SOURCE);
+        let first = SqlSchema::build(&graph, &contract, None).unwrap();
+        assert_eq!(first.version, 1);
+        let again = SqlSchema::build(&graph, &contract, Some(&first)).unwrap();
+        assert_eq!(again, first);
+        let files = project_sql_postgres_in_memory(
+            &graph,
+            &contract,
+            "main.sea",
+            Some(FIXED_TS.into()),
+            Some(&first),
+        )
+        .unwrap();
+        assert_eq!(files.len(), 1, "unchanged schema writes no migration");
+
+        let changed = schema(&SOURCE.replace("    tier: int default 1\n", ""));
+        let bumped = SqlSchema::build(&graph, &contract, Some(&changed)).unwrap();
+        assert_eq!(bumped.version, 2);
+        let mut older = changed.clone();
+        older.version = 4;
+        assert_eq!(
+            SqlSchema::build(&graph, &contract, Some(&older))
+                .unwrap()
+                .version,
+            5
+        );
+    }
+
+    #[test]
+    fn long_identifiers_are_capped_deterministically() {
+        let long = "x".repeat(80);
+        let capped = pg_ident(&long);
+        assert_eq!(capped.len(), MAX_IDENT_LEN);
+        assert_eq!(capped, pg_ident(&long));
+        assert_ne!(capped, pg_ident(&"x".repeat(81)));
+    }
+}
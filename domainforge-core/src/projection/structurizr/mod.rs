@@ -0,0 +1,535 @@
+//! C4 / Structurizr projection: a SEA model becomes a [Structurizr DSL]
+//! workspace (`workspace.dsl`) that engineers can render as C4 diagrams.
+//!
+//! Mapping:
+//! - namespace → `softwareSystem` (the system boundary)
+//! - `Entity` → `container` inside its namespace's system, tagged `Entity`
+//! - `Service` / `Endpoint` cell declarations → `container` inside the file
+//!   namespace's system, tagged `Service` / `Endpoint`
+//! - `Role` → `person`
+//! - `Flow` (`<R> from <E1> to <E2> quantity <q>`) → relationship
+//!   `E1 -> E2 "<R>: <q> <unit>"`; flows from an entity to itself are dropped
+//!   (Structurizr rejects self-relationships)
+//! - role assignment → relationship `person -> container "Played by"`
+//! - `Relation` → relationship between the subject and object persons
+//!   described by the predicate (and `via <R>` when bound to a flow)
+//!
+//! Each system gets a `systemContext`, a `container`, and — when any flow
+//! touches it — a `dynamic` view. Dynamic steps name containers of the
+//! system itself and the software system on the far side of a cross-boundary
+//! flow, relying on Structurizr's implied relationships.
+//!
+//! Every identifier goes through [`NameRegistrar`]; systems, containers,
+//! persons, and relationships are sorted, so output is byte-identical
+//! run-to-run for a fixed `created_at`.
+//!
+//! [Structurizr DSL]: https://docs.structurizr.com/dsl
+
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::parser::ast::{Ast, AstNode};
+use crate::projection::drift::generated_at;
+use crate::projection::flows::resolve_flow;
+use crate::projection::ids::NameRegistrar;
+use crate::projection::sink::ArtifactSink;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+/// Emit the Structurizr workspace into `sink`; returns the emitted relative path.
+///
+/// `ast` supplies the `Service` / `Endpoint` declarations the graph does not
+/// carry; pass `None` to project the graph alone.
+pub fn emit(
+    graph: &Graph,
+    ast: Option<&Ast>,
+    model_ref: &str,
+    created_at: Option<String>,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let workspace = Workspace::build(graph, ast)?;
+    let file = "workspace.dsl".to_string();
+    sink.write(&file, &workspace.render(model_ref, &created_at))?;
+    Ok(vec![file])
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_structurizr_in_memory(
+    graph: &Graph,
+    ast: Option<&Ast>,
+    model_ref: &str,
+    created_at: Option<String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, ast, model_ref, created_at, &mut sink)?;
+    Ok(map)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Container {
+    id: String,
+    name: String,
+    description: &'static str,
+    technology: String,
+    tag: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct System {
+    id: String,
+    namespace: String,
+    containers: Vec<Container>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Person {
+    id: String,
+    name: String,
+    namespace: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Relationship {
+    from: String,
+    to: String,
+    description: String,
+}
+
+/// A flow between two containers, kept with the namespace of each end so the
+/// dynamic views can decide which side is inside their boundary.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct FlowStep {
+    from: String,
+    from_namespace: String,
+    to: String,
+    to_namespace: String,
+    description: String,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Workspace {
+    systems: Vec<System>,
+    persons: Vec<Person>,
+    relationships: Vec<Relationship>,
+    steps: Vec<FlowStep>,
+}
+
+impl Workspace {
+    fn build(graph: &Graph, ast: Option<&Ast>) -> Result<Self, String> {
+        let mut reg = NameRegistrar::new();
+
+        // Entities first, sorted by (namespace, name), then services and
+        // endpoints, so collision suffixes are deterministic.
+        let mut entities: Vec<(String, String, ConceptId)> = graph
+            .all_entities()
+            .iter()
+            .map(|e| {
+                (
+                    e.namespace().to_string(),
+                    e.name().to_string(),
+                    e.id().clone(),
+                )
+            })
+            .collect();
+        entities.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        let mut systems: BTreeMap<String, Vec<Container>> = BTreeMap::new();
+        let mut container_ids: HashMap<ConceptId, (String, String)> = HashMap::new();
+        for (namespace, name, concept) in entities {
+            let id = reg.register("ident", &format!("{namespace}.{name}"));
+            container_ids.insert(concept, (id.clone(), namespace.clone()));
+            systems.entry(namespace).or_default().push(Container {
+                id,
+                name,
+                description: "SEA entity",
+                technology: String::new(),
+                tag: "Entity",
+            });
+        }
+        if let Some(ast) = ast {
+            let namespace = ast
+                .metadata
+                .namespace
+                .clone()
+                .unwrap_or_else(|| "default".to_string());
+            let mut cell_containers = cell_containers(ast);
+            cell_containers.sort_by(|a, b| (a.tag, &a.name).cmp(&(b.tag, &b.name)));
+            for mut container in cell_containers {
+                container.id = reg.register("ident", &format!("{namespace}.{}", container.name));
+                systems
+                    .entry(namespace.clone())
+                    .or_default()
+                    .push(container);
+            }
+        }
+        let systems: Vec<System> = systems
+            .into_iter()
+            .map(|(namespace, containers)| System {
+                id: reg.register("ident", &format!("ns {namespace}")),
+                namespace,
+                containers,
+            })
+            .collect();
+
+        let mut roles: Vec<(String, String, ConceptId)> = graph
+            .all_roles()
+            .iter()
+            .map(|r| {
+                (
+                    r.namespace().to_string(),
+                    r.name().to_string(),
+                    r.id().clone(),
+                )
+            })
+            .collect();
+        roles.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        let mut person_ids: HashMap<ConceptId, String> = HashMap::new();
+        let mut persons = Vec::new();
+        for (namespace, name, concept) in roles {
+            let id = reg.register("ident", &format!("role {namespace}.{name}"));
+            person_ids.insert(concept, id.clone());
+            persons.push(Person {
+                id,
+                name,
+                namespace,
+            });
+        }
+
+        let mut relationships = Vec::new();
+        let mut steps = Vec::new();
+        for f in graph.all_flows() {
+            let resolved = resolve_flow(graph, f)?; // M4: loud dangling-ref policy
+            let (from, from_namespace) = &container_ids[f.from_id()];
+            let (to, to_namespace) = &container_ids[f.to_id()];
+            if from == to {
+                continue;
+            }
+            let description = format!(
+                "{}: {} {}",
+                resolved.resource,
+                f.quantity().normalize(),
+                resolved.unit
+            );
+            relationships.push(Relationship {
+                from: from.clone(),
+                to: to.clone(),
+                description: description.clone(),
+            });
+            steps.push(FlowStep {
+                from: from.clone(),
+                from_namespace: from_namespace.clone(),
+                to: to.clone(),
+                to_namespace: to_namespace.clone(),
+                description,
+            });
+        }
+        for (concept, (container, _)) in &container_ids {
+            for role in graph.roles_for_entity(concept).into_iter().flatten() {
+                if let Some(person) = person_ids.get(role) {
+                    relationships.push(Relationship {
+                        from: person.clone(),
+                        to: container.clone(),
+                        description: "Played by".to_string(),
+                    });
+                }
+            }
+        }
+        for relation in graph.all_relations() {
+            let (Some(from), Some(to)) = (
+                person_ids.get(relation.subject_role()),
+                person_ids.get(relation.object_role()),
+            ) else {
+                continue;
+            };
+            let via = relation
+                .via_flow()
+                .and_then(|id| graph.get_resource(id))
+                .map(|r| format!(" via {}", r.name()))
+                .unwrap_or_default();
+            relationships.push(Relationship {
+                from: from.clone(),
+                to: to.clone(),
+                description: format!("{}{via}", relation.predicate()),
+            });
+        }
+        // Structurizr rejects a second relationship with the same description
+        // between the same pair, so duplicates collapse.
+        relationships.sort();
+        relationships.dedup();
+        steps.sort();
+        steps.dedup();
+
+        Ok(Workspace {
+            systems,
+            persons,
+            relationships,
+            steps,
+        })
+    }
+
+    fn render(&self, model_ref: &str, created_at: &str) -> String {
+        let mut s = String::new();
+        let _ = writeln!(
+            s,
+            "// Structurizr workspace projected by DomainForge from {model_ref}.\n// {}",
+            generated_at(created_at)
+        );
+        let _ = writeln!(s, "workspace \"{}\" {{", escape(model_ref));
+        s.push_str("    model {\n");
+        for person in &self.persons {
+            let _ = writeln!(
+                s,
+                "        {} = person \"{}\" \"Role in {}\"",
+                person.id,
+                escape(&person.name),
+                escape(&person.namespace)
+            );
+        }
+        for system in &self.systems {
+            let _ = writeln!(
+                s,
+                "        {} = softwareSystem \"{}\" {{",
+                system.id,
+                escape(&system.namespace)
+            );
+            for c in &system.containers {
+                let _ = writeln!(
+                    s,
+                    "            {} = container \"{}\" \"{}\" \"{}\" \"{}\"",
+                    c.id,
+                    escape(&c.name),
+                    c.description,
+                    escape(&c.technology),
+                    c.tag
+                );
+            }
+            s.push_str("        }\n");
+        }
+        for r in &self.relationships {
+            let _ = writeln!(
+                s,
+                "        {} -> {} \"{}\"",
+                r.from,
+                r.to,
+                escape(&r.description)
+            );
+        }
+        s.push_str("    }\n    views {\n");
+        for system in &self.systems {
+            let ns = escape(&system.namespace);
+            let _ = writeln!(
+                s,
+                "        systemContext {id} \"{id}-context\" \"System context of {ns}\" {{\n            include *\n            autoLayout lr\n        }}",
+                id = system.id
+            );
+            let _ = writeln!(
+                s,
+                "        container {id} \"{id}-containers\" \"Containers of {ns}\" {{\n            include *\n            autoLayout lr\n        }}",
+                id = system.id
+            );
+            let steps = self.dynamic_steps(system);
+            if !steps.is_empty() {
+                let _ = writeln!(
+                    s,
+                    "        dynamic {id} \"{id}-dynamic\" \"Flows of {ns}\" {{",
+                    id = system.id
+                );
+                for (from, to, description) in steps {
+                    let _ = writeln!(s, "            {from} -> {to} \"{}\"", escape(description));
+                }
+                s.push_str("            autoLayout lr\n        }\n");
+            }
+        }
+        s.push_str(
+            "        styles {\n            element \"Person\" {\n                shape Person\n            }\n        }\n",
+        );
+        s.push_str("    }\n}\n");
+        s
+    }
+
+    /// Dynamic-view steps for one system: flows with at least one end inside
+    /// it, the outside end replaced by that end's software system.
+    fn dynamic_steps(&self, system: &System) -> Vec<(&str, &str, &str)> {
+        let system_id = |namespace: &str| {
+            self.systems
+                .iter()
+                .find(|s| s.namespace == namespace)
+                .map(|s| s.id.as_str())
+                .unwrap_or_default()
+        };
+        let mut steps = Vec::new();
+        for step in &self.steps {
+            let from_inside = step.from_namespace == system.namespace;
+            let to_inside = step.to_namespace == system.namespace;
+            if !from_inside && !to_inside {
+                continue;
+            }
+            let from = if from_inside {
+                step.from.as_str()
+            } else {
+                system_id(&step.from_namespace)
+            };
+            let to = if to_inside {
+                step.to.as_str()
+            } else {
+                system_id(&step.to_namespace)
+            };
+            steps.push((from, to, step.description.as_str()));
+        }
+        steps.dedup();
+        steps
+    }
+}
+
+/// `Service` and `Endpoint` declarations as (not yet identified) containers.
+fn cell_containers(ast: &Ast) -> Vec<Container> {
+    let ann = |annotations: &HashMap<String, JsonValue>, key: &str| {
+        annotations
+            .get(key)
+            .and_then(JsonValue::as_str)
+            .map(str::to_string)
+    };
+    let mut containers = Vec::new();
+    for declaration in &ast.declarations {
+        let node = match &declaration.node {
+            AstNode::Export(inner) => &inner.node,
+            other => other,
+        };
+        match node {
+            AstNode::Service { name, version, .. } => containers.push(Container {
+                id: String::new(),
+                name: name.clone(),
+                description: "SEA service",
+                technology: version.clone().unwrap_or_default(),
+                tag: "Service",
+            }),
+            AstNode::Endpoint { name, annotations } => {
+                let protocol = ann(annotations, "protocol").unwrap_or_else(|| "https".to_string());
+                let technology = match (ann(annotations, "host"), ann(annotations, "port")) {
+                    (Some(host), Some(port)) => format!("{protocol}://{host}:{port}"),
+                    (Some(host), None) => format!("{protocol}://{host}"),
+                    _ => protocol,
+                };
+                containers.push(Container {
+                    id: String::new(),
+                    name: name.clone(),
+                    description: "SEA endpoint",
+                    technology,
+                    tag: "Endpoint",
+                });
+            }
+            _ => {}
+        }
+    }
+    containers
+}
+
+/// Structurizr DSL strings are double-quoted with backslash escapes.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse, parse_to_graph};
+
+    const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+    const SOURCE: &str = r#"
+@namespace "procurement"
+Entity "Buyer" in procurement
+Entity "Supplier" in procurement
+Entity "Carrier" in logistics
+Resource "PurchaseOrder" units in procurement
+Resource "Parcel" units in logistics
+Role "Requester" in procurement
+Role "Authorizer" in procurement
+Flow "PurchaseOrder" from "Buyer" to "Supplier" quantity 1
+Flow "Parcel" from "Supplier" to "Carrier" quantity 2
+Relation "OrderFulfillment"
+  subject: "Requester"
+  predicate: "orders"
+  object: "Authorizer"
+Service "postgres" version "16"
+Endpoint "erp"
+    @host "erp.example.internal"
+    @port "443"
+"#;
+
+    fn project() -> String {
+        let mut graph = parse_to_graph(SOURCE).expect("fixture parses");
+        let role = graph.find_role_by_name("Requester").expect("role");
+        let buyer = graph.find_entity_by_name("Buyer").expect("entity");
+        graph.assign_role_to_entity(buyer, role).expect("assigns");
+        let ast = parse(SOURCE).expect("ast parses");
+        let files = project_structurizr_in_memory(
+            &graph,
+            Some(&ast),
+            "test.sea",
+            Some(FIXED_TS.to_string()),
+        )
+        .unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["workspace.dsl"]);
+        files["workspace.dsl"].clone()
+    }
+
+    #[test]
+    fn namespaces_become_systems_with_containers() {
+        let dsl = project();
+        assert!(dsl.starts_with("// Structurizr workspace projected by DomainForge from test.sea"));
+        assert!(dsl.contains(
+            "        ns_logistics = softwareSystem \"logistics\" {\n            logistics_Carrier = container \"Carrier\" \"SEA entity\" \"\" \"Entity\"\n        }\n"
+        ));
+        assert!(dsl.contains(
+            "            procurement_Supplier = container \"Supplier\" \"SEA entity\" \"\" \"Entity\"\n            procurement_erp = container \"erp\" \"SEA endpoint\" \"https://erp.example.internal:443\" \"Endpoint\"\n            procurement_postgres = container \"postgres\" \"SEA service\" \"16\" \"Service\"\n"
+        ));
+    }
+
+    #[test]
+    fn roles_become_people_and_flows_relationships() {
+        let dsl = project();
+        assert!(dsl.contains(
+            "        role_procurement_Requester = person \"Requester\" \"Role in procurement\"\n"
+        ));
+        assert!(dsl.contains(
+            "        procurement_Buyer -> procurement_Supplier \"PurchaseOrder: 1 units\"\n"
+        ));
+        assert!(
+            dsl.contains("        role_procurement_Requester -> procurement_Buyer \"Played by\"\n")
+        );
+        assert!(dsl.contains(
+            "        role_procurement_Requester -> role_procurement_Authorizer \"orders\"\n"
+        ));
+    }
+
+    #[test]
+    fn views_per_system_with_cross_boundary_dynamic_steps() {
+        let dsl = project();
+        assert!(dsl.contains("        systemContext ns_procurement \"ns_procurement-context\""));
+        assert!(dsl.contains("        container ns_logistics \"ns_logistics-containers\""));
+        assert!(dsl.contains(
+            "        dynamic ns_procurement \"ns_procurement-dynamic\" \"Flows of procurement\" {\n            procurement_Buyer -> procurement_Supplier \"PurchaseOrder: 1 units\"\n            procurement_Supplier -> ns_logistics \"Parcel: 2 units\"\n"
+        ));
+        assert!(
+            dsl.contains("            ns_procurement -> logistics_Carrier \"Parcel: 2 units\"\n")
+        );
+    }
+
+    #[test]
+    fn graph_only_projection_is_deterministic() {
+        let graph = parse_to_graph(SOURCE).expect("fixture parses");
+        let run = || {
+            project_structurizr_in_memory(&graph, None, "test.sea", Some(FIXED_TS.to_string()))
+                .unwrap()
+        };
+        let first = run();
+        assert_eq!(first, run());
+        assert!(!first["workspace.dsl"].contains("postgres"));
+    }
+}
@@ -0,0 +1,728 @@
+//! Template projection (`project --format template --templates <dir>`).
+//!
+//! Renders a directory of Jinja-style templates (MiniJinja) against the
+//! resolved model, for house formats that do not justify a compiled
+//! projection. Each file under the template directory maps to output by its
+//! relative path:
+//!
+//! - `*.j2` is rendered and written without the `.j2` suffix;
+//! - a path containing `[entity]`, `[resource]`, `[role]`, `[flow]`,
+//!   `[policy]`, `[enum]`, `[record]` or `[operation]` is rendered once per
+//!   item of that collection, with the placeholder replaced by the item's
+//!   `slug` and the item bound to the placeholder name;
+//! - any path segment starting with `_` marks a partial: available to
+//!   `include` / `import` / `extends`, never written;
+//! - every other file is copied verbatim.
+//!
+//! Templates see `model`, the collections above (plural names) and the
+//! [`crate::projection::ids`] helpers as filters (`pascal`, `slug`, `ident`,
+//! `qname`, `filename`) and functions (`element_id`, `content_hash`).
+//! Rendering is sandboxed: templates are loaded into memory up front, there
+//! is no clock or filesystem access from a template, undefined variables are
+//! errors, and every artifact goes through [`ArtifactSink`]'s path checks.
+//! Collections are sorted, so output is byte-identical for a fixed model.
+
+use crate::application::{
+    AccessMode, ActorRef, ApplicationContract, ApplicationSymbolId, FieldContract, FieldType,
+    OperationContract, ScalarType,
+};
+use crate::concept_id::ConceptId;
+use crate::graph::Graph;
+use crate::policy::{PolicyKind, PolicyModality};
+use crate::projection::ids::{
+    content_hash, element_id, ident, pascal, sanitize_filename, sanitize_qname, slug, NameRegistrar,
+};
+use crate::projection::sink::ArtifactSink;
+use minijinja::value::Rest;
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Suffix of the files that are rendered rather than copied.
+pub const TEMPLATE_SUFFIX: &str = ".j2";
+
+/// Path placeholder → context collection it fans out over.
+const COLLECTIONS: [(&str, &str); 8] = [
+    ("entity", "entities"),
+    ("resource", "resources"),
+    ("role", "roles"),
+    ("flow", "flows"),
+    ("policy", "policies"),
+    ("enum", "enums"),
+    ("record", "records"),
+    ("operation", "operations"),
+];
+
+/// Model-level values available to every template as `model`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TemplateModel {
+    /// The model entry file, as given on the command line.
+    pub input: String,
+    pub namespace: Option<String>,
+    /// Only set when pinned (`--created-at`); templates never read the clock.
+    pub created_at: Option<String>,
+}
+
+/// Load every file under `dir`, keyed by `/`-separated relative path.
+/// Symlinks are refused so a template set cannot reach outside its
+/// directory.
+pub fn load_templates(dir: &Path) -> Result<BTreeMap<String, String>, String> {
+    if !dir.is_dir() {
+        return Err(format!(
+            "template directory {} does not exist",
+            dir.display()
+        ));
+    }
+    let mut templates = BTreeMap::new();
+    collect(dir, "", &mut templates)?;
+    Ok(templates)
+}
+
+fn collect(dir: &Path, prefix: &str, out: &mut BTreeMap<String, String>) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("failed to read {}: {e}", dir.display()))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("failed to read {}: {e}", dir.display()))?;
+        let path = entry.path();
+        let rel = format!("{prefix}{}", entry.file_name().to_string_lossy());
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        if file_type.is_symlink() {
+            return Err(format!(
+                "template {rel} is a symlink; symlinks are not followed"
+            ));
+        } else if file_type.is_dir() {
+            collect(&path, &format!("{rel}/"), out)?;
+        } else {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read template {rel}: {e}"))?;
+            out.insert(rel, content);
+        }
+    }
+    Ok(())
+}
+
+/// Render `templates` (as returned by [`load_templates`]) against the model
+/// into `sink`; returns the emitted relative paths.
+pub fn emit(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    templates: &BTreeMap<String, String>,
+    model: TemplateModel,
+    sink: &mut ArtifactSink,
+) -> Result<Vec<String>, String> {
+    let env = environment(templates)?;
+    let context = context(graph, contract, model);
+    let mut files = Vec::new();
+    for (name, source) in templates {
+        if is_partial(name) {
+            continue;
+        }
+        let (path, rendered) = match name.strip_suffix(TEMPLATE_SUFFIX) {
+            Some(path) => (path, true),
+            None => (name.as_str(), false),
+        };
+        let placeholder = placeholder(path).map_err(|e| format!("template {name}: {e}"))?;
+        let Some((singular, plural)) = placeholder else {
+            let content = if rendered {
+                render(&env, name, &context)?
+            } else {
+                source.clone()
+            };
+            sink.write(path, &content)?;
+            files.push(path.to_string());
+            continue;
+        };
+        let pattern = format!("[{singular}]");
+        for item in context[plural].as_array().into_iter().flatten() {
+            let item_path = path.replace(&pattern, item["slug"].as_str().unwrap_or("_"));
+            let content = if rendered {
+                let mut item_context = context.clone();
+                item_context[singular] = item.clone();
+                render(&env, name, &item_context)?
+            } else {
+                source.clone()
+            };
+            sink.write(&item_path, &content)?;
+            files.push(item_path);
+        }
+    }
+    Ok(files)
+}
+
+/// Binding surface: string in, path→content map out, no filesystem.
+pub fn project_template_in_memory(
+    graph: &Graph,
+    contract: &ApplicationContract,
+    templates: &BTreeMap<String, String>,
+    model: TemplateModel,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    let mut sink = ArtifactSink::Memory {
+        prefix: String::new(),
+        map: &mut map,
+    };
+    emit(graph, contract, templates, model, &mut sink)?;
+    Ok(map)
+}
+
+fn environment(templates: &BTreeMap<String, String>) -> Result<Environment<'_>, String> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("pascal", |raw: &str| pascal(raw));
+    env.add_filter("slug", |raw: &str| slug(raw));
+    env.add_filter("ident", |raw: &str| ident(raw));
+    env.add_filter("qname", |raw: &str| sanitize_qname(raw));
+    env.add_filter("filename", |raw: &str| sanitize_filename(raw));
+    env.add_function("element_id", |family: &str, parts: Rest<String>| {
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+        element_id(family, &parts)
+    });
+    env.add_function("content_hash", |parts: Rest<String>| {
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+        content_hash(&parts)
+    });
+    for (name, source) in templates {
+        if name.ends_with(TEMPLATE_SUFFIX) {
+            env.add_template(name, source)
+                .map_err(|e| format!("template {name}: {e}"))?;
+        }
+    }
+    Ok(env)
+}
+
+fn render(env: &Environment, name: &str, context: &Value) -> Result<String, String> {
+    env.get_template(name)
+        .and_then(|template| template.render(context))
+        .map_err(|e| format!("template {name}: {e}"))
+}
+
+fn is_partial(name: &str) -> bool {
+    name.split('/').any(|segment| segment.starts_with('_'))
+}
+
+/// The collection a template path fans out over, if any.
+fn placeholder(path: &str) -> Result<Option<(&'static str, &'static str)>, String> {
+    let mut found = None;
+    let mut rest = path;
+    while let Some(start) = rest.find('[') {
+        let Some(len) = rest[start..].find(']') else {
+            break;
+        };
+        let key = &rest[start + 1..start + len];
+        let collection = COLLECTIONS
+            .iter()
+            .find(|(singular, _)| *singular == key)
+            .ok_or_else(|| format!("unknown path placeholder '[{key}]'"))?;
+        match found {
+            Some((singular, _)) if singular != collection.0 => {
+                return Err(format!(
+                    "path uses both '[{singular}]' and '[{key}]'; use one placeholder per path"
+                ))
+            }
+            _ => found = Some(*collection),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    Ok(found)
+}
+
+#[derive(Serialize)]
+struct Context {
+    model: TemplateModel,
+    entities: Vec<EntityView>,
+    resources: Vec<ResourceView>,
+    roles: Vec<RoleView>,
+    flows: Vec<FlowView>,
+    policies: Vec<PolicyView>,
+    enums: Vec<EnumView>,
+    records: Vec<RecordView>,
+    operations: Vec<OperationView>,
+}
+
+#[derive(Serialize)]
+struct EntityView {
+    id: String,
+    name: String,
+    namespace: String,
+    slug: String,
+    attributes: BTreeMap<String, Value>,
+    /// Key field and fields of the entity body; empty without one.
+    key_field: Option<String>,
+    fields: Vec<FieldView>,
+}
+
+#[derive(Serialize)]
+struct ResourceView {
+    id: String,
+    name: String,
+    namespace: String,
+    slug: String,
+    unit: String,
+    attributes: BTreeMap<String, Value>,
+}
+
+#[derive(Serialize)]
+struct RoleView {
+    id: String,
+    name: String,
+    namespace: String,
+    slug: String,
+}
+
+#[derive(Serialize)]
+struct FlowView {
+    /// Content-derived (flow concept ids are random per parse).
+    id: String,
+    namespace: String,
+    slug: String,
+    resource: String,
+    from: String,
+    to: String,
+    quantity: String,
+    attributes: BTreeMap<String, Value>,
+}
+
+#[derive(Serialize)]
+struct PolicyView {
+    id: String,
+    name: String,
+    namespace: String,
+    slug: String,
+    modality: &'static str,
+    kind: &'static str,
+    priority: i32,
+    rationale: Option<String>,
+    tags: Vec<String>,
+    expression: String,
+}
+
+#[derive(Serialize)]
+struct EnumView {
+    id: String,
+    name: String,
+    slug: String,
+    members: Vec<EnumMemberView>,
+}
+
+#[derive(Serialize)]
+struct EnumMemberView {
+    name: String,
+    wire: String,
+}
+
+#[derive(Serialize)]
+struct RecordView {
+    id: String,
+    name: String,
+    slug: String,
+    fields: Vec<FieldView>,
+}
+
+#[derive(Serialize)]
+struct FieldView {
+    name: String,
+    /// `string`, `int`, `decimal`, `bool`, `timestamp`, `uuid`, `quantity`,
+    /// `ref<Entity>`, `enum<Enum>` or `list<…>`.
+    #[serde(rename = "type")]
+    type_name: String,
+    optional: bool,
+    constraints: Vec<Value>,
+}
+
+#[derive(Serialize)]
+struct OperationView {
+    id: String,
+    name: String,
+    slug: String,
+    intent: String,
+    direction: Value,
+    effect: Value,
+    /// Role name, or `anonymous`.
+    actor: String,
+    input: String,
+    output: String,
+    state: String,
+    /// Names of the policies guarding the operation.
+    policies: Vec<String>,
+    failures: Vec<FailureView>,
+}
+
+#[derive(Serialize)]
+struct FailureView {
+    code: String,
+    meaning: String,
+}
+
+fn context(graph: &Graph, contract: &ApplicationContract, model: TemplateModel) -> Value {
+    let names = Names::new(graph, contract);
+    let bodies: HashMap<&ConceptId, _> = contract
+        .entities
+        .iter()
+        .map(|entity| (&entity.concept_id, entity))
+        .collect();
+
+    let mut entities = graph.all_entities();
+    entities.sort_by(|a, b| (a.namespace(), a.name()).cmp(&(b.namespace(), b.name())));
+    let mut registrar = NameRegistrar::new();
+    let entities = entities
+        .into_iter()
+        .map(|entity| {
+            let body = bodies.get(entity.id());
+            EntityView {
+                id: entity.id().to_string(),
+                name: entity.name().to_string(),
+                namespace: entity.namespace().to_string(),
+                slug: registrar.register("slug", entity.name()),
+                attributes: sorted(entity.attributes()),
+                key_field: body.map(|body| body.key_field.clone()),
+                fields: body
+                    .map(|body| fields(&body.fields, &names))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    let mut resources = graph.all_resources();
+    resources.sort_by(|a, b| (a.namespace(), a.name()).cmp(&(b.namespace(), b.name())));
+    let mut registrar = NameRegistrar::new();
+    let resources = resources
+        .into_iter()
+        .map(|resource| ResourceView {
+            id: resource.id().to_string(),
+            name: resource.name().to_string(),
+            namespace: resource.namespace().to_string(),
+            slug: registrar.register("slug", resource.name()),
+            unit: resource.unit_symbol().to_string(),
+            attributes: sorted(resource.attributes()),
+        })
+        .collect();
+
+    let mut roles = graph.all_roles();
+    roles.sort_by(|a, b| (a.namespace(), a.name()).cmp(&(b.namespace(), b.name())));
+    let mut registrar = NameRegistrar::new();
+    let roles = roles
+        .into_iter()
+        .map(|role| RoleView {
+            id: role.id().to_string(),
+            name: role.name().to_string(),
+            namespace: role.namespace().to_string(),
+            slug: registrar.register("slug", role.name()),
+        })
+        .collect();
+
+    let mut flows: Vec<FlowView> = graph
+        .all_flows()
+        .into_iter()
+        .map(|flow| FlowView {
+            id: element_id(
+                "flow",
+                &[
+                    flow.namespace(),
+                    &flow.resource_id().to_string(),
+                    &flow.from_id().to_string(),
+                    &flow.to_id().to_string(),
+                    &flow.quantity().normalize().to_string(),
+                ],
+            ),
+            namespace: flow.namespace().to_string(),
+            slug: String::new(),
+            resource: names.concept(flow.resource_id()),
+            from: names.concept(flow.from_id()),
+            to: names.concept(flow.to_id()),
+            quantity: flow.quantity().normalize().to_string(),
+            attributes: sorted(flow.attributes()),
+        })
+        .collect();
+    flows.sort_by(|a, b| {
+        (&a.resource, &a.from, &a.to, &a.id).cmp(&(&b.resource, &b.from, &b.to, &b.id))
+    });
+    let mut registrar = NameRegistrar::new();
+    for flow in &mut flows {
+        flow.slug = registrar.register(
+            "slug",
+            &format!("{} {} {}", flow.resource, flow.from, flow.to),
+        );
+    }
+
+    let mut policies = graph.all_policies();
+    policies.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    let mut registrar = NameRegistrar::new();
+    let policies = policies
+        .into_iter()
+        .map(|policy| PolicyView {
+            id: policy.id.to_string(),
+            name: policy.name.clone(),
+            namespace: policy.namespace.clone(),
+            slug: registrar.register("slug", &policy.name),
+            modality: match policy.modality {
+                PolicyModality::Obligation => "obligation",
+                PolicyModality::Prohibition => "prohibition",
+                PolicyModality::Permission => "permission",
+            },
+            kind: match policy.kind {
+                PolicyKind::Constraint => "constraint",
+                PolicyKind::Derivation => "derivation",
+                PolicyKind::Obligation => "obligation",
+            },
+            priority: policy.priority,
+            rationale: policy.rationale.clone(),
+            tags: policy.tags.clone(),
+            expression: policy.expression().to_string(),
+        })
+        .collect();
+
+    let mut registrar = NameRegistrar::new();
+    let enums = contract
+        .enums
+        .iter()
+        .map(|enumeration| EnumView {
+            id: enumeration.id.0.clone(),
+            name: enumeration.name.clone(),
+            slug: registrar.register("slug", &enumeration.name),
+            members: enumeration
+                .members
+                .iter()
+                .map(|member| EnumMemberView {
+                    name: member.name.clone(),
+                    wire: member.wire.clone(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let mut registrar = NameRegistrar::new();
+    let records = contract
+        .records
+        .iter()
+        .map(|record| RecordView {
+            id: record.id.0.clone(),
+            name: record.name.clone(),
+            slug: registrar.register("slug", &record.name),
+            fields: fields(&record.fields, &names),
+        })
+        .collect();
+
+    let mut registrar = NameRegistrar::new();
+    let operations = contract
+        .operations
+        .iter()
+        .map(|operation| operation_view(operation, &names, &mut registrar))
+        .collect();
+
+    serde_json::to_value(Context {
+        model,
+        entities,
+        resources,
+        roles,
+        flows,
+        policies,
+        enums,
+        records,
+        operations,
+    })
+    .expect("template context serializes")
+}
+
+fn operation_view(
+    operation: &OperationContract,
+    names: &Names,
+    registrar: &mut NameRegistrar,
+) -> OperationView {
+    let policies = match &operation.access {
+        AccessMode::Public => Vec::new(),
+        AccessMode::PolicyGoverned { bindings } => bindings
+            .iter()
+            .map(|binding| names.concept(&binding.policy))
+            .collect(),
+    };
+    OperationView {
+        id: operation.id.0.clone(),
+        name: operation.name.clone(),
+        slug: registrar.register("slug", &operation.name),
+        intent: operation.intent.clone(),
+        direction: serde_json::to_value(operation.direction).unwrap_or_default(),
+        effect: serde_json::to_value(operation.effect).unwrap_or_default(),
+        actor: match &operation.actor {
+            ActorRef::Anonymous => "anonymous".to_string(),
+            ActorRef::Role { role } => names.concept(role),
+        },
+        input: names.symbol(&operation.input),
+        output: names.symbol(&operation.output),
+        state: names.concept(&operation.state),
+        policies,
+        failures: operation
+            .failures
+            .iter()
+            .map(|failure| FailureView {
+                code: failure.code.clone(),
+                meaning: failure.meaning.clone(),
+            })
+            .collect(),
+    }
+}
+
+fn fields(fields: &[FieldContract], names: &Names) -> Vec<FieldView> {
+    fields
+        .iter()
+        .map(|field| FieldView {
+            name: field.name.clone(),
+            type_name: names.field_type(&field.field_type),
+            optional: field.optional,
+            constraints: field
+                .constraints
+                .iter()
+                .map(|constraint| serde_json::to_value(constraint).unwrap_or_default())
+                .collect(),
+        })
+        .collect()
+}
+
+fn sorted(attributes: &HashMap<String, Value>) -> BTreeMap<String, Value> {
+    attributes
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Display names for the ids the contract and graph cross-reference.
+struct Names {
+    concepts: HashMap<ConceptId, String>,
+    symbols: HashMap<String, String>,
+}
+
+impl Names {
+    fn new(graph: &Graph, contract: &ApplicationContract) -> Self {
+        let mut concepts = HashMap::new();
+        for entity in graph.all_entities() {
+            concepts.insert(entity.id().clone(), entity.name().to_string());
+        }
+        for resource in graph.all_resources() {
+            concepts.insert(resource.id().clone(), resource.name().to_string());
+        }
+        for role in graph.all_roles() {
+            concepts.insert(role.id().clone(), role.name().to_string());
+        }
+        for policy in graph.all_policies() {
+            concepts.insert(policy.id.clone(), policy.name.clone());
+        }
+        let symbols = contract
+            .enums
+            .iter()
+            .map(|e| (e.id.0.clone(), e.name.clone()))
+            .chain(
+                contract
+                    .records
+                    .iter()
+                    .map(|r| (r.id.0.clone(), r.name.clone())),
+            )
+            .collect();
+        Self { concepts, symbols }
+    }
+
+    fn concept(&self, id: &ConceptId) -> String {
+        self.concepts
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn symbol(&self, id: &ApplicationSymbolId) -> String {
+        self.symbols
+            .get(&id.0)
+            .cloned()
+            .unwrap_or_else(|| id.0.clone())
+    }
+
+    fn field_type(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::Scalar { scalar } => match scalar {
+                ScalarType::String => "string",
+                ScalarType::Int => "int",
+                ScalarType::Decimal => "decimal",
+                ScalarType::Bool => "bool",
+                ScalarType::Timestamp => "timestamp",
+                ScalarType::Uuid => "uuid",
+            }
+            .to_string(),
+            FieldType::Quantity { .. } => "quantity".to_string(),
+            FieldType::EntityRef { entity } => format!("ref<{}>", self.concept(entity)),
+            FieldType::Enum { symbol } => format!("enum<{}>", self.symbol(symbol)),
+            FieldType::List { element } => format!("list<{}>", self.field_type(element)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        crate::parser::parse_to_graph(
+            r#"Entity "Warehouse" in logistics
+Entity "Cold Store" in logistics
+Resource "Widget" units in logistics
+Flow "Widget" from "Cold Store" to "Warehouse" quantity 10
+"#,
+        )
+        .unwrap()
+    }
+
+    fn render_all(templates: &[(&str, &str)]) -> Result<BTreeMap<String, String>, String> {
+        let templates = templates
+            .iter()
+            .map(|(name, source)| (name.to_string(), source.to_string()))
+            .collect();
+        project_template_in_memory(
+            &graph(),
+            &ApplicationContract::default(),
+            &templates,
+            TemplateModel::default(),
+        )
+    }
+
+    #[test]
+    fn test_renders_loops_filters_and_fan_out() {
+        let out = render_all(&[
+            (
+                "index.md.j2",
+                "{% for e in entities %}- {{ e.name | pascal }} ({{ e.slug }})\n{% endfor %}",
+            ),
+            (
+                "entities/[entity].yaml.j2",
+                "{% include '_partials/header.j2' %}name: {{ entity.name | ident }}\n",
+            ),
+            ("_partials/header.j2", "# generated\n"),
+            ("static.txt", "as is\n"),
+        ])
+        .unwrap();
+        assert_eq!(
+            out["index.md"],
+            "- ColdStore (cold_store)\n- Warehouse (warehouse)\n"
+        );
+        assert_eq!(
+            out["entities/cold_store.yaml"],
+            "# generated\nname: Cold_Store\n"
+        );
+        assert_eq!(out["static.txt"], "as is\n");
+        assert_eq!(out.len(), 4);
+    }
+
+    #[test]
+    fn test_rejects_bad_templates() {
+        let err = render_all(&[("x.j2", "{{ missing }}")]).unwrap_err();
+        assert!(err.contains("template x.j2"), "{err}");
+        let err = render_all(&[("[entity]-[flow].j2", "")]).unwrap_err();
+        assert!(err.contains("one placeholder per path"), "{err}");
+        let err = render_all(&[("[thing].j2", "")]).unwrap_err();
+        assert!(err.contains("unknown path placeholder"), "{err}");
+        let err = render_all(&[("../escape.txt", "x")]).unwrap_err();
+        assert!(err.contains("escapes output directory"), "{err}");
+    }
+}
@@ -22,6 +22,7 @@
 //! [TLA+]: https://lamport.org/tla/tla.html
 
 use crate::graph::Graph;
+use crate::projection::drift::generated_at;
 use crate::projection::flows::{collect_flows, model_namespace};
 use crate::projection::ids::{sanitize_filename, NameRegistrar};
 use crate::projection::sink::ArtifactSink;
@@ -119,8 +120,10 @@ fn build_tla(
     };
 
     let mut s = String::new();
+    let marker = generated_at(created_at);
     s.push_str(&format!(
-        "(* TLA+ spec projected by DomainForge from {model_ref} at {created_at}. *)\n\
+        "(* TLA+ spec projected by DomainForge from {model_ref}. *)\n\
+         (* {marker} *)\n\
          (* Model-check with TLC: tlc {module}.tla. *)\n\
          ---------------------------- MODULE {module} ----------------------------\n\
          EXTENDS Naturals, Sequences, TLC\n\n"
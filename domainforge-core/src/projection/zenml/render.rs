@@ -12,6 +12,7 @@
 //! pipeline object without executing it.
 
 use super::ir::{LearningPipelineIR, ZENML_PIP_SPEC, ZENML_TARGET_VERSION};
+use crate::projection::drift::generated_at;
 
 /// Collapse embedded newlines (a docstring stays readable line-by-line) and
 /// neutralize the two sequences that could break out of a `"""` docstring: a
@@ -31,10 +32,11 @@ fn header(ir: &LearningPipelineIR, what: &str) -> String {
     format!(
         "# Generated by DomainForge (`--format zenml`) from {}.\n\
          # {}\n\
-         # Created: {}. Target ZenML API: {}. Do not edit by hand.\n",
+         # {}\n\
+         # Target ZenML API: {}. Do not edit by hand.\n",
         comment_safe(&ir.model_ref),
         comment_safe(what),
-        comment_safe(&ir.created_at),
+        generated_at(&comment_safe(&ir.created_at)),
         ZENML_TARGET_VERSION,
     )
 }
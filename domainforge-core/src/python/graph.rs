@@ -298,6 +298,31 @@ impl Graph {
             })
     }
 
+    /// Resolve an in-memory workspace: an entry module, its import closure
+    /// and an optional namespace registry.
+    ///
+    /// Args:
+    ///     entry_logical_path: logical path of the entry module
+    ///     sources_json: JSON object mapping logical paths to SEA source text
+    ///     registry_toml: optional `.sea-registry.toml` contents; modules
+    ///         without `@namespace` take the namespace it assigns to their path
+    ///
+    /// Returns:
+    ///     WorkspaceResolution with the graph, semantic envelope and diagnostics
+    #[staticmethod]
+    #[pyo3(signature = (entry_logical_path, sources_json, registry_toml=None))]
+    fn resolve_workspace(
+        entry_logical_path: String,
+        sources_json: String,
+        registry_toml: Option<String>,
+    ) -> super::workspace::WorkspaceResolution {
+        super::workspace::WorkspaceResolution::from_rust(crate::application::resolve_workspace(
+            &entry_logical_path,
+            &sources_json,
+            registry_toml.as_deref(),
+        ))
+    }
+
     fn export_calm(&self) -> PyResult<String> {
         crate::calm::export(&self.inner)
             .and_then(|value| {
@@ -463,14 +488,27 @@ impl Graph {
     /// Emit an ArchiMate 3.0 Model Exchange File (the `--format archimate`
     /// layout: model.xml).
     ///
+    /// Args:
+    ///     viewpoints: Optional viewpoint names (e.g. `organization`,
+    ///         `business-process-cooperation`, `motivation`), one extra view each
+    ///
     /// Returns:
     ///     JSON object mapping relative artifact paths to file contents
-    #[pyo3(signature = (model_ref = "<in-memory>", created_at = None))]
-    fn export_archimate(&self, model_ref: &str, created_at: Option<String>) -> PyResult<String> {
+    #[pyo3(signature = (model_ref = "<in-memory>", created_at = None, viewpoints = None))]
+    fn export_archimate(
+        &self,
+        model_ref: &str,
+        created_at: Option<String>,
+        viewpoints: Option<Vec<String>>,
+    ) -> PyResult<String> {
+        let viewpoints =
+            crate::projection::archimate::parse_viewpoints(&viewpoints.unwrap_or_default())
+                .map_err(PyValueError::new_err)?;
         let artifacts = crate::projection::archimate::project_archimate_in_memory(
             &self.inner,
             model_ref,
             created_at,
+            viewpoints,
         )
         .map_err(PyValueError::new_err)?;
         serde_json::to_string(&artifacts)
@@ -658,3 +696,9 @@ impl Graph {
         )
     }
 }
+
+impl Graph {
+    pub fn from_rust(inner: RustGraph) -> Self {
+        Self { inner }
+    }
+}
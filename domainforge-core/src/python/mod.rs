@@ -7,3 +7,4 @@ pub mod primitives;
 pub mod registry;
 pub mod semantic_pack;
 pub mod units;
+pub mod workspace;
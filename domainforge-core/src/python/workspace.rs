@@ -0,0 +1,66 @@
+use crate::application::WorkspaceResolution as RustWorkspaceResolution;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::graph::Graph;
+
+/// Result of `Graph.resolve_workspace`: the graph and semantic envelope of
+/// an in-memory workspace, or the diagnostics that prevented them.
+#[pyclass]
+pub struct WorkspaceResolution {
+    inner: RustWorkspaceResolution,
+}
+
+#[pymethods]
+impl WorkspaceResolution {
+    #[getter]
+    pub fn entry(&self) -> String {
+        self.inner.entry.clone()
+    }
+
+    /// True when the workspace resolved without diagnostics.
+    #[getter]
+    pub fn ok(&self) -> bool {
+        self.inner.is_ok()
+    }
+
+    /// The resolved graph, or None when resolution failed.
+    #[getter]
+    pub fn graph(&self) -> Option<Graph> {
+        self.inner.graph.clone().map(Graph::from_rust)
+    }
+
+    /// Diagnostics as a JSON array; each carries `logical_module_id`,
+    /// `line` and `column` in its `context` when the failure has a location.
+    #[getter]
+    pub fn diagnostics_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner.diagnostics)
+            .map_err(|e| PyValueError::new_err(format!("Serialization error: {}", e)))
+    }
+
+    /// Canonical semantic envelope document JSON, or None when resolution failed.
+    #[getter]
+    pub fn envelope_json(&self) -> PyResult<Option<String>> {
+        self.inner
+            .semantic_envelope
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(format!("Serialization error: {}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "WorkspaceResolution(entry='{}', ok={}, diagnostics={})",
+            self.inner.entry,
+            if self.inner.is_ok() { "True" } else { "False" },
+            self.inner.diagnostics.len()
+        )
+    }
+}
+
+impl WorkspaceResolution {
+    pub fn from_rust(inner: RustWorkspaceResolution) -> Self {
+        Self { inner }
+    }
+}
@@ -161,7 +161,18 @@ impl NamespaceRegistry {
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
         let registry_path = path.as_ref();
         let contents = fs::read_to_string(registry_path)?;
-        let raw: RawRegistry = toml::from_str(&contents)?;
+        let root = registry_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .canonicalize()?;
+        Self::from_toml_str(&contents, root)
+    }
+
+    /// Build a registry from `.sea-registry.toml` contents rooted at `root`.
+    /// The root is taken as given, so in-memory workspaces (which match
+    /// logical paths, not files) can pass any placeholder.
+    pub fn from_toml_str(contents: &str, root: PathBuf) -> Result<Self, RegistryError> {
+        let raw: RawRegistry = toml::from_str(contents)?;
 
         if raw.version != 1 {
             return Err(RegistryError::InvalidVersion(raw.version));
@@ -171,11 +182,6 @@ impl NamespaceRegistry {
             return Err(RegistryError::MissingNamespaces);
         }
 
-        let root = registry_path
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .canonicalize()?;
-
         let default_namespace = raw
             .default_namespace
             .unwrap_or_else(|| "default".to_string());
@@ -302,14 +308,36 @@ impl NamespaceRegistry {
             Err(_) => return Ok(self.default_namespace.as_str()),
         };
         let normalized = normalize_path(relative);
+        self.match_relative(&normalized, fail_on_ambiguity)
+            .map_err(|namespaces| RegistryError::Ambiguous {
+                path: path.as_ref().to_path_buf(),
+                namespaces,
+            })
+    }
+
+    /// Namespace for a root-relative logical path such as `domain/orders.sea`,
+    /// without touching the filesystem. Ambiguous matches fall back to the
+    /// alphabetically first namespace, as [`Self::namespace_for`] does.
+    pub fn namespace_for_logical_path(&self, logical_path: &str) -> &str {
+        let normalized = normalize_path(Path::new(logical_path));
+        self.match_relative(&normalized, false)
+            .unwrap_or(self.default_namespace.as_str())
+    }
 
+    /// Match a normalized root-relative path; `Err` carries the sorted
+    /// candidate namespaces of an ambiguity when `fail_on_ambiguity` is set.
+    fn match_relative(
+        &self,
+        normalized: &str,
+        fail_on_ambiguity: bool,
+    ) -> Result<&str, Vec<String>> {
         // Collect all matches and pick the best candidate(s) using longest literal
         // prefix precedence. If more than one candidate exist with equal prefix
         // length and 'fail_on_ambiguity' is true, return an error.
         let mut candidates: Vec<(&CompiledRule, usize)> = vec![];
         let mut best_len: usize = 0;
         for entry in &self.entries {
-            if entry.matcher.is_match(normalized.as_str()) {
+            if entry.matcher.is_match(normalized) {
                 let len = entry.literal_prefix_len;
                 if len > best_len {
                     candidates.clear();
@@ -336,10 +364,7 @@ impl NamespaceRegistry {
                 .map(|(e, _)| e.namespace.clone())
                 .collect();
             names.sort();
-            return Err(RegistryError::Ambiguous {
-                path: path.as_ref().to_path_buf(),
-                namespaces: names,
-            });
+            return Err(names);
         }
 
         // alphabetical fallback determination
@@ -208,3 +208,35 @@ fn test_windows_paths_normalization() {
     // So this test is tricky to run on Linux to simulate Windows.
     // We will skip explicit OS simulation but ensure standard paths work.
 }
+
+#[test]
+fn test_logical_path_matching_without_filesystem() {
+    let registry = NamespaceRegistry::from_toml_str(
+        r#"
+        version = 1
+        default_namespace = "shared"
+        [[namespaces]]
+        namespace = "orders"
+        patterns = ["domains/orders/**/*.sea"]
+
+        [[namespaces]]
+        namespace = "orders.payments"
+        patterns = ["domains/orders/payments/*.sea"]
+    "#,
+        PathBuf::from("/nonexistent-workspace"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        registry.namespace_for_logical_path("domains/orders/core.sea"),
+        "orders"
+    );
+    assert_eq!(
+        registry.namespace_for_logical_path("./domains/orders/payments/card.sea"),
+        "orders.payments"
+    );
+    assert_eq!(
+        registry.namespace_for_logical_path("lib/util.sea"),
+        "shared"
+    );
+}
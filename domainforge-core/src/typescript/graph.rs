@@ -53,6 +53,24 @@ impl Graph {
             })
     }
 
+    /// Resolve an in-memory workspace (entry module, import closure and an
+    /// optional `.sea-registry.toml`) into its graph, semantic envelope and
+    /// located diagnostics.
+    #[napi]
+    pub fn resolve_workspace(
+        entry_logical_path: String,
+        sources_json: String,
+        registry_toml: Option<String>,
+    ) -> crate::typescript::workspace::WorkspaceResolution {
+        crate::typescript::workspace::WorkspaceResolution::from_rust(
+            crate::application::resolve_workspace(
+                &entry_logical_path,
+                &sources_json,
+                registry_toml.as_deref(),
+            ),
+        )
+    }
+
     #[napi]
     pub fn add_entity(&mut self, entity: &Entity) -> Result<()> {
         self.inner
@@ -483,17 +501,24 @@ impl Graph {
     ///
     /// @param modelRef - Provenance label for the source model
     /// @param createdAt - Optional fixed RFC3339 timestamp for reproducible output
+    /// @param viewpoints - Optional viewpoint names (e.g. `organization`,
+    ///   `business-process-cooperation`, `motivation`), one extra view each
     /// @returns JSON object mapping relative artifact paths to file contents
     #[napi]
     pub fn export_archimate(
         &self,
         model_ref: Option<String>,
         created_at: Option<String>,
+        viewpoints: Option<Vec<String>>,
     ) -> Result<String> {
+        let viewpoints =
+            crate::projection::archimate::parse_viewpoints(&viewpoints.unwrap_or_default())
+                .map_err(Error::from_reason)?;
         let artifacts = crate::projection::archimate::project_archimate_in_memory(
             &self.inner,
             model_ref.as_deref().unwrap_or("<in-memory>"),
             created_at,
+            viewpoints,
         )
         .map_err(Error::from_reason)?;
         serde_json::to_string(&artifacts)
@@ -685,3 +710,9 @@ impl Graph {
         )
     }
 }
+
+impl Graph {
+    pub fn from_rust(inner: RustGraph) -> Self {
+        Self { inner }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::application::WorkspaceResolution as RustWorkspaceResolution;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use super::graph::Graph;
+
+/// Result of `Graph.resolveWorkspace`: the graph and semantic envelope of an
+/// in-memory workspace, or the diagnostics that prevented them.
+#[napi]
+pub struct WorkspaceResolution {
+    inner: RustWorkspaceResolution,
+}
+
+#[napi]
+impl WorkspaceResolution {
+    #[napi(getter)]
+    pub fn entry(&self) -> String {
+        self.inner.entry.clone()
+    }
+
+    /// True when the workspace resolved without diagnostics.
+    #[napi(getter)]
+    pub fn ok(&self) -> bool {
+        self.inner.is_ok()
+    }
+
+    /// The resolved graph, or null when resolution failed.
+    #[napi(getter)]
+    pub fn graph(&self) -> Option<Graph> {
+        self.inner.graph.clone().map(Graph::from_rust)
+    }
+
+    /// Diagnostics as a JSON array; each carries `logical_module_id`, `line`
+    /// and `column` in its `context` when the failure has a location.
+    #[napi(getter)]
+    pub fn diagnostics_json(&self) -> Result<String> {
+        serde_json::to_string(&self.inner.diagnostics)
+            .map_err(|e| Error::from_reason(format!("Serialization error: {}", e)))
+    }
+
+    /// Canonical semantic envelope document JSON, or null when resolution failed.
+    #[napi(getter)]
+    pub fn envelope_json(&self) -> Result<Option<String>> {
+        self.inner
+            .semantic_envelope
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| Error::from_reason(format!("Serialization error: {}", e)))
+    }
+}
+
+impl WorkspaceResolution {
+    pub fn from_rust(inner: RustWorkspaceResolution) -> Self {
+        Self { inner }
+    }
+}
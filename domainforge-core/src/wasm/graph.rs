@@ -60,6 +60,24 @@ impl Graph {
             })
     }
 
+    /// Resolve an in-memory workspace (entry module, import closure and an
+    /// optional `.sea-registry.toml`) into its graph, semantic envelope and
+    /// located diagnostics.
+    #[wasm_bindgen(js_name = resolveWorkspace)]
+    pub fn resolve_workspace(
+        entry_logical_path: String,
+        sources_json: String,
+        registry_toml: Option<String>,
+    ) -> crate::wasm::workspace::WorkspaceResolution {
+        crate::wasm::workspace::WorkspaceResolution::from_rust(
+            crate::application::resolve_workspace(
+                &entry_logical_path,
+                &sources_json,
+                registry_toml.as_deref(),
+            ),
+        )
+    }
+
     #[wasm_bindgen(js_name = isEmpty)]
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
@@ -543,6 +561,8 @@ impl Graph {
     ///
     /// @param modelRef - Optional provenance label for the source model
     /// @param createdAt - Optional fixed RFC3339 timestamp for reproducible output
+    /// @param viewpoints - Optional viewpoint names (e.g. `organization`,
+    ///   `business-process-cooperation`, `motivation`), one extra view each
     /// @returns JSON object mapping relative artifact paths to file contents
     #[cfg(feature = "wasm-projections")]
     #[wasm_bindgen(js_name = exportArchimate)]
@@ -550,11 +570,16 @@ impl Graph {
         &self,
         model_ref: Option<String>,
         created_at: Option<String>,
+        viewpoints: Option<Vec<String>>,
     ) -> Result<String, JsValue> {
+        let viewpoints =
+            crate::projection::archimate::parse_viewpoints(&viewpoints.unwrap_or_default())
+                .map_err(|e| JsValue::from_str(&e))?;
         let artifacts = crate::projection::archimate::project_archimate_in_memory(
             &self.inner,
             model_ref.as_deref().unwrap_or("<in-memory>"),
             created_at,
+            viewpoints,
         )
         .map_err(|e| JsValue::from_str(&e))?;
         serde_json::to_string(&artifacts)
@@ -764,3 +789,9 @@ impl Graph {
             .map_err(|e| JsValue::from_str(&format!("Serialization failed: {}", e)))
     }
 }
+
+impl Graph {
+    pub fn from_rust(inner: RustGraph) -> Self {
+        Self { inner }
+    }
+}
@@ -6,6 +6,7 @@ pub mod policy;
 pub mod primitives;
 pub mod semantic_pack;
 pub mod units;
+pub mod workspace;
 
 pub use authority::*;
 pub use formatter::*;
@@ -14,3 +15,4 @@ pub use policy::*;
 pub use primitives::*;
 pub use semantic_pack::*;
 pub use units::*;
+pub use workspace::*;
@@ -0,0 +1,55 @@
+use crate::application::WorkspaceResolution as RustWorkspaceResolution;
+use crate::wasm::graph::Graph;
+use wasm_bindgen::prelude::*;
+
+/// Result of `Graph.resolveWorkspace`: the graph and semantic envelope of an
+/// in-memory workspace, or the diagnostics that prevented them.
+#[wasm_bindgen]
+pub struct WorkspaceResolution {
+    inner: RustWorkspaceResolution,
+}
+
+#[wasm_bindgen]
+impl WorkspaceResolution {
+    #[wasm_bindgen(getter)]
+    pub fn entry(&self) -> String {
+        self.inner.entry.clone()
+    }
+
+    /// True when the workspace resolved without diagnostics.
+    #[wasm_bindgen(getter)]
+    pub fn ok(&self) -> bool {
+        self.inner.is_ok()
+    }
+
+    /// The resolved graph, or undefined when resolution failed.
+    #[wasm_bindgen(getter)]
+    pub fn graph(&self) -> Option<Graph> {
+        self.inner.graph.clone().map(Graph::from_rust)
+    }
+
+    /// Diagnostics as a JSON array; each carries `logical_module_id`, `line`
+    /// and `column` in its `context` when the failure has a location.
+    #[wasm_bindgen(getter, js_name = diagnosticsJson)]
+    pub fn diagnostics_json(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.inner.diagnostics)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Canonical semantic envelope document JSON, or undefined when resolution failed.
+    #[wasm_bindgen(getter, js_name = envelopeJson)]
+    pub fn envelope_json(&self) -> Result<Option<String>, JsValue> {
+        self.inner
+            .semantic_envelope
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+}
+
+impl WorkspaceResolution {
+    pub fn from_rust(inner: RustWorkspaceResolution) -> Self {
+        Self { inner }
+    }
+}
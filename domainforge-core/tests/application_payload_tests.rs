@@ -0,0 +1,319 @@
+//! JSON payload validation against application-contract records.
+
+use domainforge_core::application::{
+    resolve_application_contract, resolve_application_graph, validate_payload,
+    PayloadDiagnosticKind, PayloadOptions, PayloadReport, TypedValue,
+};
+use domainforge_core::ConceptId;
+use serde_json::{json, Value};
+
+const FLAGSHIP: &str = "flagship/command-write.sea";
+
+const BASKET: &str = r#"@namespace "shop"
+role "Clerk"
+role "Guest"
+pattern "Sku" matches "^[A-Z]{3}-[0-9]+$"
+policy clerk_only per Constraint Obligation priority 1 as: actor = role<Clerk>
+enum Channel { web = "web", store = "store" }
+entity "Customer" {
+    key customer_id: uuid
+}
+entity "Basket" {
+    key basket_id: uuid
+    customer: ref<Customer>
+}
+record OpenBasket {
+    basket_id: uuid
+    customer: ref<Customer>
+    channel: Channel
+    skus: list<string> (min_items 1, max_items 2)
+    lead: string (pattern Sku)
+    placed_at: timestamp
+    discount: decimal optional (min 0, max 0.5)
+}
+record BasketOpened {
+    basket_id: uuid
+}
+operation open_basket {
+    intent "open a basket"
+    direction inbound
+    actor Clerk
+    access policy_governed by clerk_only at precondition fails with not_clerk
+    input OpenBasket
+    output BasketOpened
+    state Basket
+    effect creates Basket
+    transaction single_aggregate
+    failure invalid_basket for input_validation "basket failed record validation"
+    failure not_clerk for policy "only clerks open baskets"
+    failure duplicate for idempotency_conflict, concurrency_conflict "basket exists"
+    idempotency keyed_by basket_id
+    concurrency unique_key basket_id
+    evidence operation_trace
+    lifecycle synchronous_request_response
+}
+"#;
+
+fn check(
+    entry: &str,
+    source: &str,
+    record: &str,
+    payload: Value,
+    options: &PayloadOptions,
+) -> Result<PayloadReport, String> {
+    let sources = json!({ entry: source }).to_string();
+    let graph = resolve_application_graph(entry, &sources).expect("graph");
+    let document = resolve_application_contract(entry, &sources).expect("contract");
+    validate_payload(&graph, &document.contract, record, &payload, options)
+}
+
+fn flagship(payload: Value) -> PayloadReport {
+    let source = include_str!("../../fixtures/application_generation/flagship/command-write.sea");
+    check(
+        FLAGSHIP,
+        source,
+        "PlaceOrderInput",
+        payload,
+        &PayloadOptions::default(),
+    )
+    .expect("record resolves")
+}
+
+fn basket(payload: Value, options: &PayloadOptions) -> PayloadReport {
+    check("main.sea", BASKET, "OpenBasket", payload, options).expect("record resolves")
+}
+
+fn findings(report: &PayloadReport) -> Vec<(&str, PayloadDiagnosticKind)> {
+    report
+        .diagnostics
+        .iter()
+        .map(|d| (d.path.as_str(), d.kind))
+        .collect()
+}
+
+fn order(total: Value) -> Value {
+    json!({
+        "order_id": "7f1c4b1e-0c7e-4d0a-9a53-6c5f0f0b2b11",
+        "client_order_id": "c-1",
+        "total": total,
+        "item_count": 2,
+    })
+}
+
+fn open_basket() -> Value {
+    json!({
+        "basket_id": "0b7c2f8e-5a1d-4c3b-9e6f-1a2b3c4d5e6f",
+        "customer": "7f1c4b1e-0c7e-4d0a-9a53-6c5f0f0b2b11",
+        "channel": "web",
+        "skus": ["ABC-1"],
+        "lead": "ABC-1",
+        "placed_at": "2026-07-02T00:00:00+00:00",
+    })
+}
+
+#[test]
+fn valid_payload_is_typed() {
+    let report = flagship(order(json!("250.00")));
+    assert!(report.is_valid(), "{:?}", report.diagnostics);
+    let value = report.value.expect("typed payload");
+    assert_eq!(value["item_count"], TypedValue::Int(2));
+    assert!(matches!(
+        &value["total"],
+        TypedValue::Quantity { base_value, .. } if base_value.to_string() == "250"
+    ));
+}
+
+#[test]
+fn constraint_violations_are_path_addressed() {
+    let mut payload = order(json!(0));
+    payload["client_order_id"] = json!("");
+    let report = flagship(payload);
+    assert_eq!(
+        findings(&report),
+        vec![
+            (
+                "/client_order_id",
+                PayloadDiagnosticKind::ConstraintViolation
+            ),
+            ("/total", PayloadDiagnosticKind::ConstraintViolation),
+        ]
+    );
+    assert_eq!(
+        report.diagnostics[1].constraint.as_deref(),
+        Some("exclusive_min")
+    );
+    // Constraint violations keep the typed value.
+    assert!(report.value.is_some());
+}
+
+#[test]
+fn type_errors_missing_and_unknown_fields() {
+    let payload = json!({
+        "order_id": "not-a-uuid",
+        "total": true,
+        "item_count": 1.5,
+        "coupon": "X",
+    });
+    let report = flagship(payload);
+    assert_eq!(
+        findings(&report),
+        vec![
+            ("/order_id", PayloadDiagnosticKind::InvalidType),
+            ("/client_order_id", PayloadDiagnosticKind::MissingField),
+            ("/total", PayloadDiagnosticKind::InvalidType),
+            ("/item_count", PayloadDiagnosticKind::InvalidType),
+            ("/coupon", PayloadDiagnosticKind::UnknownField),
+        ]
+    );
+    assert!(report.value.is_none());
+}
+
+#[test]
+fn precondition_policy_runs_on_well_typed_payloads() {
+    let report = flagship(order(json!(10000.01)));
+    assert_eq!(
+        findings(&report),
+        vec![("/total", PayloadDiagnosticKind::PolicyViolation)]
+    );
+    let diagnostic = &report.diagnostics[0];
+    assert_eq!(diagnostic.operation.as_deref(), Some("place_order"));
+    assert_eq!(
+        diagnostic.failure_code.as_deref(),
+        Some("order_limit_exceeded")
+    );
+
+    // Ill-typed payloads never reach the evaluator.
+    let mut payload = order(json!(10000.01));
+    payload["item_count"] = json!("two");
+    assert_eq!(
+        findings(&flagship(payload)),
+        vec![("/item_count", PayloadDiagnosticKind::InvalidType)]
+    );
+}
+
+#[test]
+fn enums_lists_patterns_refs_and_optionals() {
+    let report = basket(open_basket(), &PayloadOptions::default());
+    assert!(report.is_valid(), "{:?}", report.diagnostics);
+    assert!(matches!(
+        &report.value.as_ref().unwrap()["customer"],
+        TypedValue::EntityRef { key, .. } if matches!(key.as_ref(), TypedValue::Uuid(_))
+    ));
+
+    let mut payload = open_basket();
+    payload["channel"] = json!("fax");
+    payload["skus"] = json!(["ABC-1", 3]);
+    payload["lead"] = json!("abc");
+    payload["placed_at"] = json!("yesterday");
+    payload["discount"] = json!("0.9");
+    let report = basket(payload, &PayloadOptions::default());
+    assert_eq!(
+        findings(&report),
+        vec![
+            ("/channel", PayloadDiagnosticKind::InvalidType),
+            ("/skus/1", PayloadDiagnosticKind::InvalidType),
+            ("/lead", PayloadDiagnosticKind::ConstraintViolation),
+            ("/placed_at", PayloadDiagnosticKind::InvalidType),
+            ("/discount", PayloadDiagnosticKind::ConstraintViolation),
+        ]
+    );
+
+    let mut payload = open_basket();
+    payload["skus"] = json!(["ABC-1", "ABC-2", "ABC-3"]);
+    payload["discount"] = Value::Null;
+    let report = basket(payload, &PayloadOptions::default());
+    assert_eq!(
+        findings(&report),
+        vec![("/skus", PayloadDiagnosticKind::ConstraintViolation)]
+    );
+    assert_eq!(
+        report.diagnostics[0].constraint.as_deref(),
+        Some("max_items")
+    );
+}
+
+#[test]
+fn actor_override_resolves_role_references() {
+    let as_guest = PayloadOptions {
+        actor_role: Some("Guest".to_string()),
+        ..Default::default()
+    };
+    let report = basket(open_basket(), &as_guest);
+    assert_eq!(
+        findings(&report),
+        vec![("", PayloadDiagnosticKind::PolicyViolation)]
+    );
+    assert_eq!(
+        report.diagnostics[0].failure_code.as_deref(),
+        Some("not_clerk")
+    );
+}
+
+#[test]
+fn unresolvable_targets_are_errors() {
+    let options = PayloadOptions::default();
+    assert!(check("main.sea", BASKET, "Nope", open_basket(), &options).is_err());
+    let unknown_op = PayloadOptions {
+        operation: Some("close_basket".to_string()),
+        ..Default::default()
+    };
+    assert!(check("main.sea", BASKET, "OpenBasket", open_basket(), &unknown_op).is_err());
+    let unknown_role = PayloadOptions {
+        actor_role: Some("Manager".to_string()),
+        ..Default::default()
+    };
+    assert!(check(
+        "main.sea",
+        BASKET,
+        "OpenBasket",
+        open_basket(),
+        &unknown_role
+    )
+    .is_err());
+
+    let report = basket(json!([1, 2]), &options);
+    assert_eq!(
+        findings(&report),
+        vec![("", PayloadDiagnosticKind::InvalidType)]
+    );
+}
+
+#[test]
+fn quantities_stay_in_the_declared_unit() {
+    let source = r#"@namespace "ship"
+dimension "Mass"
+unit "kg" of "Mass" factor 1 base "kg"
+unit "g" of "Mass" factor 0.001 base "kg"
+record Parcel {
+    weight: quantity<g> (max 2000)
+}
+"#;
+    let parcel = |weight: Value| {
+        check(
+            "main.sea",
+            source,
+            "Parcel",
+            json!({ "weight": weight }),
+            &PayloadOptions::default(),
+        )
+        .expect("record resolves")
+    };
+
+    // 1500 g is typed as 1500 in grams, not 1.5 in the kilogram base unit.
+    let report = parcel(json!(1500));
+    assert!(report.is_valid(), "{:?}", report.diagnostics);
+    let value = report.value.expect("typed payload");
+    assert_eq!(
+        value["weight"],
+        TypedValue::Quantity {
+            base_value: 1500.into(),
+            unit: ConceptId::from_concept("ship", "g"),
+        }
+    );
+
+    // The bound is authored in grams too.
+    assert_eq!(
+        findings(&parcel(json!(2500))),
+        vec![("/weight", PayloadDiagnosticKind::ConstraintViolation)]
+    );
+}
@@ -0,0 +1,87 @@
+//! In-memory workspace resolution (`resolve_workspace`): source maps with
+//! imports, `std:` modules and an optional namespace registry, as the
+//! bindings expose them to editors and notebooks.
+
+use domainforge_core::application::{resolve_workspace, ApplicationDiagnosticCode};
+use serde_json::json;
+
+const REGISTRY: &str = r#"
+version = 1
+default_namespace = "shared"
+
+[[namespaces]]
+namespace = "orders"
+patterns = ["domains/orders/**/*.sea"]
+"#;
+
+#[test]
+fn registry_assigns_namespaces_and_imports_resolve() {
+    let sources = json!({
+        "app.sea": "@namespace \"app\"\nimport { Customer } from \"orders\"\nimport * as std from \"std:core\"\nEntity \"Shop\"\n",
+        "domains/orders/customer.sea": "export Entity \"Customer\"\nexport Resource \"Money\" units\nFlow \"Money\" from \"Customer\" to \"Customer\" quantity 5\n",
+    });
+    let resolution = resolve_workspace("app.sea", &sources.to_string(), Some(REGISTRY));
+    assert!(resolution.is_ok(), "{:?}", resolution.diagnostics);
+
+    let graph = resolution.graph.expect("graph");
+    let customer = graph.find_entity_by_name("Customer").expect("imported");
+    assert_eq!(graph.get_entity(&customer).unwrap().namespace(), "orders");
+    assert_eq!(graph.flow_count(), 1);
+
+    let envelope = resolution.semantic_envelope.expect("envelope");
+    assert_eq!(envelope.schema_version, "domainforge-semantic-envelope/v1");
+}
+
+#[test]
+fn parse_errors_report_module_and_line_of_the_given_source() {
+    let sources = json!({
+        "app.sea": "@namespace \"app\"\nimport { Customer } from \"orders\"\n",
+        "domains/orders/customer.sea": "export Entity \"Customer\"\n\nEntity Broken\n",
+    });
+    let resolution = resolve_workspace("app.sea", &sources.to_string(), Some(REGISTRY));
+    assert!(resolution.graph.is_none());
+    assert!(resolution.semantic_envelope.is_none());
+
+    let d = &resolution.diagnostics[0];
+    assert_eq!(d.code, ApplicationDiagnosticCode::App015);
+    assert_eq!(
+        d.context.logical_module_id.as_deref(),
+        Some("domains/orders/customer.sea")
+    );
+    assert_eq!(d.context.line, Some(3));
+}
+
+#[test]
+fn contract_diagnostics_are_not_shifted_by_registry_namespaces() {
+    // The registry gives `domains/orders/records.sea` its namespace; the
+    // record is still reported on line 3 of the source as written.
+    let sources = json!({
+        "domains/orders/records.sea": "\n\nrecord Order {\n    id: string\n    id: int\n}\n",
+    });
+    let resolution = resolve_workspace(
+        "domains/orders/records.sea",
+        &sources.to_string(),
+        Some(REGISTRY),
+    );
+    let d = &resolution.diagnostics[0];
+    assert_eq!(d.code, ApplicationDiagnosticCode::App004);
+    assert_eq!(d.context.line, Some(3));
+}
+
+#[test]
+fn invalid_registry_and_missing_entry_are_diagnostics() {
+    let sources = json!({ "app.sea": "Entity \"Shop\"\n" }).to_string();
+
+    let bad_registry = resolve_workspace("app.sea", &sources, Some("version = 2\n"));
+    assert_eq!(
+        bad_registry.diagnostics[0].code,
+        ApplicationDiagnosticCode::App014
+    );
+
+    let missing = resolve_workspace("other.sea", &sources, None);
+    assert!(missing.graph.is_none());
+    assert_eq!(
+        missing.diagnostics[0].context.reason.as_deref(),
+        Some("unresolved_specifier")
+    );
+}
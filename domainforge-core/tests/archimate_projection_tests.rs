@@ -108,3 +108,60 @@ fn output_must_be_a_directory() {
     std::fs::write(&file, "occupied").expect("write");
     project(&file).failure();
 }
+
+#[test]
+fn viewpoint_flag_adds_a_view_per_viewpoint() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.arg("project")
+        .arg("--format")
+        .arg("archimate")
+        .arg("--created-at")
+        .arg(FIXED_TS)
+        .arg("--viewpoint")
+        .arg("motivation")
+        .arg("--viewpoint")
+        .arg("organization")
+        .arg(fixture_model())
+        .arg(tmp.path());
+    cmd.assert().success();
+    let xml = read_tree(tmp.path())["model.xml"].clone();
+    assert!(xml.contains("viewpoint=\"Motivation\""));
+    assert!(xml.contains("viewpoint=\"Organization\""));
+    // The business-layer view draws its relationships as connections.
+    assert!(xml.contains("<connection "));
+}
+
+#[test]
+fn unknown_viewpoint_is_rejected() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.arg("project")
+        .arg("--format")
+        .arg("archimate")
+        .arg("--viewpoint")
+        .arg("layered")
+        .arg(fixture_model())
+        .arg(tmp.path());
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "unknown ArchiMate viewpoint `layered`",
+    ));
+}
+
+#[test]
+fn projected_model_imports_back_to_sea() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    project(tmp.path()).success();
+    let sea = tmp.path().join("model.sea");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.arg("import")
+        .arg("--format")
+        .arg("archimate")
+        .arg("--out")
+        .arg(&sea)
+        .arg(tmp.path().join("model.xml"));
+    cmd.assert().success();
+    let source = std::fs::read_to_string(&sea).unwrap();
+    assert!(source.contains("Role \"Approver\""));
+    assert!(source.contains("Flow \""));
+}
@@ -0,0 +1,264 @@
+use domainforge_core::authority::explorer::{OTHER_VALUE, UNKNOWN_VALUE};
+use domainforge_core::authority::*;
+use domainforge_core::parse_to_graph;
+use std::collections::BTreeMap;
+
+const MODEL: &str = r#"
+@namespace "procurement"
+
+Entity "Buyer" in procurement
+Entity "Warehouse" in procurement
+Resource "Order" units in procurement
+Role "Shipper" in procurement
+
+Flow "Order" from "Buyer" to "Warehouse" quantity 1
+
+Policy block_credit_hold per Constraint Prohibition priority 100
+  @tags ["fact_source:customer.credit_status=system_of_record"]
+  as: action = "ShipOrder" and resource.type = "Order" and customer.credit_status = "hold"
+
+Policy shipper_may_ship per Constraint Permission priority 10
+  as: action = "ShipOrder" and actor.role = "Shipper"
+
+Policy orders_may_ship per Constraint Permission priority 10
+  as: action = "ShipOrder" and resource.type = "Order"
+
+Policy redundant_allow per Constraint Permission priority 1
+  as: action = "ShipOrder" and actor.role = "Shipper" and resource.type = "Order"
+
+Policy needs_review per Constraint Obligation priority 5
+  as: action = "ReturnOrder" and region != "domestic"
+"#;
+
+fn environment() -> AuthorityEnvironment {
+    environment_for(MODEL)
+}
+
+fn environment_for(model: &str) -> AuthorityEnvironment {
+    let graph = parse_to_graph(model).expect("model parses");
+    let compilation = SeaPolicyCompiler::new("1.0.0".to_string(), "1.0.0".to_string())
+        .compile(&graph)
+        .expect("compiles");
+    let config = AuthorityEnvironmentConfig {
+        resolver_semantics_version: "1.0.0".to_string(),
+        specificity_profile: SpecificityProfile::default_profile(),
+        unknown_handling: UnknownHandlingConfig::defaults(),
+        fact_sources: vec![],
+        fact_transforms: vec![],
+        authority_packs: compilation
+            .packs
+            .iter()
+            .map(|p| serde_json::to_value(p).unwrap())
+            .collect(),
+        strict_mode: true,
+        compatibility_lowering_version: "1.0.0".to_string(),
+        resolver_version: "1.0.0".to_string(),
+    };
+    let mut env = AuthorityEnvironment::new(config).expect("environment");
+    env.validate().expect("valid");
+    env
+}
+
+fn explore() -> DecisionSpaceReport {
+    explore_model(MODEL)
+}
+
+fn explore_model(model: &str) -> DecisionSpaceReport {
+    let env = environment_for(model);
+    DecisionSpaceExplorer::new(env.config())
+        .explore(env.packs())
+        .expect("explores")
+}
+
+fn assignment(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn row<'a>(
+    report: &'a DecisionSpaceReport,
+    request: &[(&str, &str)],
+    facts: &[(&str, &str)],
+) -> &'a DecisionRow {
+    let request = assignment(request);
+    let facts = assignment(facts);
+    report
+        .matrix
+        .iter()
+        .find(|r| r.request == request && r.facts == facts)
+        .expect("scenario in matrix")
+}
+
+fn policies_of(report: &DecisionSpaceReport, kind: CoverageFindingKind) -> Vec<Vec<String>> {
+    report
+        .findings_of(kind)
+        .into_iter()
+        .map(|f| f.policies.clone())
+        .collect()
+}
+
+#[test]
+fn enumerates_every_referenced_dimension() {
+    let report = explore();
+    assert_eq!(
+        report.dimensions["action"],
+        vec!["ReturnOrder", "ShipOrder", OTHER_VALUE]
+    );
+    assert_eq!(
+        report.dimensions["actor.role"],
+        vec!["Shipper", OTHER_VALUE]
+    );
+    assert_eq!(
+        report.dimensions["fact:region"],
+        vec!["domestic", OTHER_VALUE, UNKNOWN_VALUE]
+    );
+    // 3 * 2 * 2 structural combinations, 3 * 3 fact assignments.
+    assert_eq!(report.matrix.len(), 108);
+}
+
+#[test]
+fn matrix_records_decisions_including_unknown_facts() {
+    let report = explore();
+    let request = [
+        ("action", "ShipOrder"),
+        ("actor.role", "Shipper"),
+        ("resource.type", "Order"),
+    ];
+
+    let hold = row(
+        &report,
+        &request,
+        &[("customer.credit_status", "hold"), ("region", "domestic")],
+    );
+    assert_eq!(hold.final_decision, FinalDecision::Deny);
+    assert_eq!(hold.deciding_policy.as_deref(), Some("block_credit_hold"));
+
+    let unknown = row(
+        &report,
+        &request,
+        &[
+            ("customer.credit_status", UNKNOWN_VALUE),
+            ("region", "domestic"),
+        ],
+    );
+    assert_eq!(unknown.final_decision, FinalDecision::Deny);
+
+    let unmatched = row(
+        &report,
+        &[
+            ("action", OTHER_VALUE),
+            ("actor.role", OTHER_VALUE),
+            ("resource.type", OTHER_VALUE),
+        ],
+        &[("customer.credit_status", "hold"), ("region", "domestic")],
+    );
+    assert_eq!(unmatched.final_decision, FinalDecision::NotApplicable);
+    assert_eq!(unmatched.deciding_policy, None);
+}
+
+#[test]
+fn fail_closed_fact_requirements_make_a_policy_unreachable() {
+    let report = explore();
+    assert_eq!(
+        policies_of(&report, CoverageFindingKind::UnreachablePolicy),
+        vec![vec!["needs_review".to_string()]]
+    );
+}
+
+#[test]
+fn each_policy_sees_facts_from_a_source_it_trusts() {
+    // Both policies read `customer.tier`, each trusting a different class;
+    // neither is unreachable.
+    let report = explore_model(
+        r#"
+@namespace "procurement"
+
+Policy gold_may_ship per Constraint Permission priority 10
+  @tags ["fact_source:customer.tier=system_of_record"]
+  as: action = "ShipOrder" and customer.tier = "gold"
+
+Policy attested_gold_may_return per Constraint Permission priority 10
+  @tags ["fact_source:customer.tier=attested"]
+  as: action = "ReturnOrder" and customer.tier = "gold"
+"#,
+    );
+    assert_eq!(
+        report.dimensions["fact:customer.tier"],
+        vec![
+            "gold@attested".to_string(),
+            "gold@system_of_record".to_string(),
+            format!("{OTHER_VALUE}@attested"),
+            format!("{OTHER_VALUE}@system_of_record"),
+            UNKNOWN_VALUE.to_string(),
+        ]
+    );
+    assert!(policies_of(&report, CoverageFindingKind::UnreachablePolicy).is_empty());
+    assert_eq!(
+        row(
+            &report,
+            &[("action", "ReturnOrder")],
+            &[("customer.tier", "gold@attested")]
+        )
+        .deciding_policy
+        .as_deref(),
+        Some("attested_gold_may_return")
+    );
+}
+
+#[test]
+fn lower_priority_duplicates_are_shadowed() {
+    let report = explore();
+    assert_eq!(
+        policies_of(&report, CoverageFindingKind::ShadowedPolicy),
+        vec![vec!["redundant_allow".to_string()]]
+    );
+}
+
+#[test]
+fn incomparable_specificity_is_reported_as_a_tie() {
+    let report = explore();
+    let ties = report.findings_of(CoverageFindingKind::SpecificityTie);
+    assert_eq!(ties.len(), 1);
+    assert_eq!(
+        ties[0].policies,
+        vec!["orders_may_ship", "shipper_may_ship"]
+    );
+    assert_eq!(ties[0].request["actor.role"], "Shipper");
+    assert_eq!(ties[0].request["resource.type"], "Order");
+}
+
+#[test]
+fn partially_matched_requests_are_gaps() {
+    let report = explore();
+    let gaps = report.findings_of(CoverageFindingKind::NotApplicableGap);
+    let ship_unscoped = assignment(&[
+        ("action", "ShipOrder"),
+        ("actor.role", OTHER_VALUE),
+        ("resource.type", OTHER_VALUE),
+    ]);
+    assert!(gaps.iter().any(|g| g.request == ship_unscoped));
+    assert!(gaps
+        .iter()
+        .all(|g| g.request.values().any(|v| v != OTHER_VALUE)));
+}
+
+#[test]
+fn exploration_is_deterministic() {
+    let first = explore();
+    let second = explore();
+    assert_eq!(first.matrix, second.matrix);
+    assert_eq!(first.findings, second.findings);
+    assert_eq!(first.to_report(), second.to_report());
+}
+
+#[test]
+fn oversized_decision_spaces_are_refused() {
+    let env = environment();
+    let err = DecisionSpaceExplorer::new(env.config())
+        .with_max_scenarios(10)
+        .explore(env.packs())
+        .expect_err("108 scenarios exceed the limit");
+    assert_eq!(err.code, AuthorityErrorCode::InvalidAuthorityEnvironment);
+}
@@ -0,0 +1,210 @@
+use chrono::Utc;
+use domainforge_core::authority::*;
+use domainforge_core::parse_to_graph;
+
+const MODEL: &str = r#"
+@namespace "procurement"
+
+Entity "Buyer" in procurement
+Entity "Warehouse" in procurement
+Resource "Order" units in procurement
+Role "Shipper" in procurement
+
+Flow "Order" from "Buyer" to "Warehouse" quantity 1
+
+Policy block_credit_hold per Constraint Prohibition priority 100
+  @rationale "Orders on credit hold never ship"
+  @tags ["fact_source:customer.credit_status=system_of_record"]
+  as: action = "ShipOrder" and resource.type = "Order" and customer.credit_status = "hold"
+
+Policy shipper_may_ship per Constraint Permission priority 10
+  as: action = "ShipOrder" and actor.role = "Shipper" and resource.type = "Order"
+
+Policy needs_review per Constraint Obligation priority 5
+  as: action = "ShipOrder" and resource.type = "Order" and region != "domestic"
+
+Policy total_cap per Constraint Obligation priority 1
+  as: forall f in flows: (f.quantity <= 1000)
+"#;
+
+fn compiler() -> SeaPolicyCompiler {
+    SeaPolicyCompiler::new("1.0.0".to_string(), "1.0.0".to_string())
+}
+
+fn compile(source: &str) -> SeaCompilation {
+    let graph = parse_to_graph(source).expect("model parses");
+    compiler().compile(&graph).expect("compiles")
+}
+
+fn policy<'a>(compilation: &'a SeaCompilation, id: &str) -> &'a AuthorityPolicy {
+    compilation
+        .packs
+        .iter()
+        .flat_map(|p| p.policies.iter())
+        .find(|p| p.policy_id == id)
+        .unwrap_or_else(|| panic!("policy {id} compiled"))
+}
+
+#[test]
+fn emits_one_hashed_pack_per_namespace() {
+    let compilation = compile(MODEL);
+    assert_eq!(compilation.packs.len(), 1);
+    let pack = &compilation.packs[0];
+    assert_eq!(pack.id, "procurement");
+    pack.validate_hash()
+        .expect("hash matches compute_pack_hash");
+    assert_eq!(
+        compilation.report.compiled,
+        vec!["block_credit_hold", "needs_review", "shipper_may_ship"]
+    );
+}
+
+#[test]
+fn modality_priority_and_predicates_come_from_the_declaration() {
+    let compilation = compile(MODEL);
+    let p = policy(&compilation, "block_credit_hold");
+    assert_eq!(p.modality, PolicyModality::Prohibition);
+    assert_eq!(p.priority, 100);
+    assert_eq!(p.applies_to.predicates["action"], "ShipOrder");
+    assert_eq!(p.applies_to.predicates["resource.type"], "Order");
+    let when = p.when.as_ref().expect("when clause");
+    assert_eq!(when.conditions["customer.credit_status"], "hold");
+    assert_eq!(p.requires_fact.len(), 1);
+    assert_eq!(
+        p.requires_fact[0].allowed_source_classes,
+        vec![SourceClass::SystemOfRecord]
+    );
+    assert_eq!(
+        p.description.as_deref(),
+        Some("Orders on credit hold never ship")
+    );
+}
+
+#[test]
+fn not_equal_lowers_to_neq_condition() {
+    let compilation = compile(MODEL);
+    let p = policy(&compilation, "needs_review");
+    let when = p.when.as_ref().expect("when clause");
+    assert_eq!(
+        when.conditions["region"],
+        serde_json::json!({ "__neq": "domestic" })
+    );
+}
+
+#[test]
+fn unannotated_facts_compile_fail_closed_and_are_reported() {
+    let compilation = compile(MODEL);
+    let p = policy(&compilation, "needs_review");
+    assert!(p.requires_fact[0].allowed_source_classes.is_empty());
+    let missing = compilation.report.missing_fact_sources();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].policy_id, "needs_review");
+    assert_eq!(missing[0].expression, "region");
+}
+
+#[test]
+fn compiler_level_fact_sources_satisfy_the_annotation() {
+    let graph = parse_to_graph(MODEL).expect("model parses");
+    let compilation = compiler()
+        .with_fact_source("region", [SourceClass::RuntimeObserved])
+        .compile(&graph)
+        .expect("compiles");
+    assert!(compilation.report.missing_fact_sources().is_empty());
+}
+
+#[test]
+fn quantified_policies_are_skipped_not_partially_compiled() {
+    let compilation = compile(MODEL);
+    assert_eq!(compilation.report.skipped, vec!["total_cap"]);
+    assert!(compilation.report.findings.iter().any(|f| {
+        f.policy_id == "total_cap" && f.kind == SeaCompileFindingKind::UnsupportedExpression
+    }));
+}
+
+#[test]
+fn unknown_roles_and_resources_are_flagged() {
+    let compilation = compile(
+        r#"
+@namespace "p"
+Entity "A" in p
+Resource "Order" units in p
+Policy typo per Constraint Permission priority 1
+  as: actor.role = "Shiper" and resource.type = "Invoice"
+"#,
+    );
+    let kinds: Vec<_> = compilation.report.findings.iter().map(|f| f.kind).collect();
+    assert!(kinds.contains(&SeaCompileFindingKind::UnknownRole));
+    assert!(kinds.contains(&SeaCompileFindingKind::UnknownResource));
+}
+
+#[test]
+fn invalid_fact_source_tag_is_an_error() {
+    let graph = parse_to_graph(
+        r#"
+Policy bad per Constraint Prohibition priority 1
+  @tags ["fact_source:x=nobody"]
+  as: action = "Go" and x = 1
+"#,
+    )
+    .expect("model parses");
+    let err = compiler().compile(&graph).expect_err("bad class rejected");
+    assert_eq!(err.code, AuthorityErrorCode::PolicyParseError);
+}
+
+#[test]
+fn compiled_pack_loads_and_denies_through_the_environment() {
+    let compilation = compile(MODEL);
+    let config = AuthorityEnvironmentConfig {
+        resolver_semantics_version: "1.0.0".to_string(),
+        specificity_profile: SpecificityProfile::default_profile(),
+        unknown_handling: UnknownHandlingConfig::defaults(),
+        fact_sources: vec![],
+        fact_transforms: vec![],
+        authority_packs: compilation
+            .packs
+            .iter()
+            .map(|p| serde_json::to_value(p).unwrap())
+            .collect(),
+        strict_mode: false,
+        compatibility_lowering_version: "1.0.0".to_string(),
+        resolver_version: "1.0.0".to_string(),
+    };
+    let mut env = AuthorityEnvironment::new(config).expect("environment");
+    env.validate().expect("valid");
+
+    let request = AuthorityRequest {
+        request_id: "r1".to_string(),
+        actor: ActorContext {
+            id: "u1".to_string(),
+            role: Some("Shipper".to_string()),
+            groups: vec![],
+            service_account: None,
+            agent_identity: None,
+        },
+        operation: "ShipOrder".to_string(),
+        resource: ResourceRef {
+            id: None,
+            type_: Some("Order".to_string()),
+            extra: Default::default(),
+        },
+        context: serde_json::json!({}),
+        requested_at: Utc::now(),
+        correlation_id: None,
+        risk_class: None,
+        metadata: Default::default(),
+    };
+    let fact = FactEnvelope {
+        path: "customer.credit_status".to_string(),
+        value: serde_json::json!("hold"),
+        source_class: SourceClass::SystemOfRecord,
+        source_id: "crm".to_string(),
+        observed_at: Utc::now(),
+        expires_at: None,
+        evidence_ref: None,
+        signature: None,
+        confidence: None,
+        lineage: None,
+    };
+    let (_, decision) = env.evaluate(&request, &[fact]).expect("evaluates");
+    assert_eq!(decision.final_decision, FinalDecision::Deny);
+}
@@ -112,3 +112,69 @@ fn output_must_be_a_directory() {
     std::fs::write(&file, "occupied").expect("write");
     project(&file).failure();
 }
+
+/// Every `attr="…"` value in `xml`, in document order.
+fn attr_values<'a>(xml: &'a str, attr: &str) -> Vec<&'a str> {
+    let re = regex::Regex::new(&format!(r#"\s{attr}="([^"]*)""#)).expect("regex");
+    re.captures_iter(xml)
+        .map(|c| c.get(1).expect("group").as_str())
+        .collect()
+}
+
+#[test]
+fn diagram_elements_resolve_to_semantic_elements() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    project(tmp.path()).success();
+    let bpmn = &read_tree(tmp.path())["model.bpmn"];
+    let (semantic, diagram) = bpmn
+        .split_once("<bpmndi:BPMNDiagram")
+        .expect("diagram section");
+    let ids: std::collections::BTreeSet<&str> = attr_values(semantic, "id").into_iter().collect();
+    let referenced = attr_values(diagram, "bpmnElement");
+    for element in &referenced {
+        assert!(
+            ids.contains(element),
+            "bpmnElement {element} has no semantic element"
+        );
+    }
+    // Every lane, flow node, and sequence flow is drawn.
+    let drawn: std::collections::BTreeSet<&str> = referenced.into_iter().collect();
+    for line in semantic.lines().map(str::trim_start) {
+        let drawable = [
+            "<lane ",
+            "<task ",
+            "<startEvent ",
+            "<endEvent ",
+            "<parallelGateway ",
+            "<sequenceFlow ",
+        ]
+        .iter()
+        .any(|tag| line.starts_with(tag));
+        if drawable {
+            let id = attr_values(line, "id")[0];
+            assert!(drawn.contains(id), "{id} has no diagram element");
+        }
+    }
+}
+
+/// Schema check: requires `xmllint` (libxml2) on PATH.
+/// Run explicitly with `cargo test --features cli -- --ignored validates_against`.
+#[test]
+#[ignore = "requires xmllint on PATH"]
+fn validates_against_the_vendored_xsd() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    project(tmp.path()).success();
+    let schema = Path::new(env!("CARGO_MANIFEST_DIR")).join("../schemas/bpmn/BPMN20.xsd");
+    let output = std::process::Command::new("xmllint")
+        .arg("--noout")
+        .arg("--schema")
+        .arg(&schema)
+        .arg(tmp.path().join("model.bpmn"))
+        .output()
+        .expect("xmllint on PATH");
+    assert!(
+        output.status.success(),
+        "xmllint rejected the projection:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
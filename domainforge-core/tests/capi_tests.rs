@@ -0,0 +1,485 @@
+#![cfg(feature = "capi")]
+
+//! C ABI (`--features capi`): round trips through the exported functions, and
+//! generation of `include/domainforge.h` from `src/capi/mod.rs`.
+//!
+//! The header is checked against the committed copy; after changing the ABI
+//! regenerate it with
+//! `DOMAINFORGE_BLESS_HEADER=1 cargo test --features capi --test capi_tests`.
+
+use domainforge_core::authority::*;
+use domainforge_core::capi::*;
+use serde_json::{json, Value};
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+use std::ptr;
+
+const MODEL: &str = r#"@namespace "procurement"
+Entity "Buyer"
+Entity "Supplier"
+Resource "Payment" USD
+Role "Payer"
+Flow "Payment" from "Buyer" to "Supplier" quantity 100
+
+Policy payments_are_bounded as: forall f in flows: (f.quantity <= 1000)
+
+Policy payers_may_pay per Constraint Permission priority 10
+  as: action = "Pay" and actor.role = "Payer"
+"#;
+
+fn c(value: &str) -> CString {
+    CString::new(value).unwrap()
+}
+
+/// Parse and free a string returned by the library.
+fn take(value: *mut c_char) -> Value {
+    assert!(!value.is_null(), "expected a returned string");
+    let text = unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    unsafe { df_string_free(value) };
+    serde_json::from_str(&text).unwrap()
+}
+
+fn resolve(sources: &Value) -> *mut DfWorkspace {
+    let entry = c("model.sea");
+    let sources = c(&sources.to_string());
+    let mut workspace = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let status = unsafe {
+        df_workspace_resolve(
+            entry.as_ptr(),
+            sources.as_ptr(),
+            ptr::null(),
+            &mut workspace,
+            &mut error,
+        )
+    };
+    assert_eq!(
+        status,
+        DF_OK,
+        "{:?}",
+        (!error.is_null()).then(|| take(error))
+    );
+    workspace
+}
+
+/// Call a `(workspace, out_json, out_error)` function: its status and output.
+fn call(
+    f: unsafe extern "C" fn(*const DfWorkspace, *mut *mut c_char, *mut *mut c_char) -> i32,
+    workspace: *const DfWorkspace,
+) -> (i32, Value) {
+    let mut out = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let status = unsafe { f(workspace, &mut out, &mut error) };
+    let result = if status == DF_OK {
+        take(out)
+    } else {
+        take(error)
+    };
+    (status, result)
+}
+
+#[test]
+fn versions_are_exported() {
+    assert_eq!(df_abi_version(), DF_ABI_VERSION);
+    let version = unsafe { CStr::from_ptr(df_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn parse_returns_ast_or_a_located_validation_error() {
+    let mut out = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let source = c(MODEL);
+    let status = unsafe { df_parse(source.as_ptr(), &mut out, &mut error) };
+    assert_eq!(status, DF_OK);
+    assert!(error.is_null());
+    let ast = take(out);
+    assert_eq!(ast["metadata"]["namespace"], "procurement");
+    assert!(ast["declarations"].as_array().unwrap().len() >= 6);
+
+    let broken = c("Entity \"Buyer\"\n\nEntity Broken\n");
+    let mut out = ptr::null_mut();
+    let status = unsafe { df_parse(broken.as_ptr(), &mut out, &mut error) };
+    assert_eq!(status, DF_ERR_PARSE);
+    assert!(out.is_null());
+    let error = take(error);
+    assert_eq!(error["code"], "E005");
+    assert_eq!(error["line"], 3);
+}
+
+#[test]
+fn workspace_validates_evaluates_and_projects() {
+    let workspace = resolve(&json!({ "model.sea": MODEL }));
+
+    let (status, envelope) = call(df_workspace_envelope, workspace);
+    assert_eq!(status, DF_OK);
+    assert_eq!(
+        envelope["schema_version"],
+        "domainforge-semantic-envelope/v1"
+    );
+
+    let (status, validation) = call(df_workspace_validate, workspace);
+    assert_eq!(status, DF_OK);
+    assert_eq!(validation["total_policies"], 2);
+    assert_eq!(
+        validation["valid"],
+        validation["error_count"].as_u64() == Some(0)
+    );
+
+    let policy = c("payments_are_bounded");
+    let mut out = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let status =
+        unsafe { df_workspace_evaluate_policies(workspace, policy.as_ptr(), &mut out, &mut error) };
+    assert_eq!(status, DF_OK);
+    let evaluation = take(out);
+    assert_eq!(evaluation["policies"].as_array().unwrap().len(), 1);
+    assert_eq!(evaluation["policies"][0]["satisfied"], true);
+    assert_eq!(evaluation["violation_count"], 0);
+
+    let unknown = c("no_such_policy");
+    let status = unsafe {
+        df_workspace_evaluate_policies(workspace, unknown.as_ptr(), &mut out, &mut error)
+    };
+    assert_eq!(status, DF_ERR_INVALID_ARGUMENT);
+    assert_eq!(take(error)["code"], "unknown_policy");
+
+    let format = c("mermaid");
+    let options = c(r#"{"model_ref": "model.sea", "created_at": "2026-01-01T00:00:00Z"}"#);
+    let status = unsafe {
+        df_workspace_project(
+            workspace,
+            format.as_ptr(),
+            options.as_ptr(),
+            &mut out,
+            &mut error,
+        )
+    };
+    assert_eq!(status, DF_OK);
+    let artifacts = take(out);
+    let artifacts = artifacts.as_object().unwrap();
+    assert!(!artifacts.is_empty());
+    assert!(artifacts
+        .values()
+        .any(|content| content.as_str().unwrap().contains("Buyer")));
+
+    let format = c("calm");
+    let status = unsafe {
+        df_workspace_project(
+            workspace,
+            format.as_ptr(),
+            ptr::null(),
+            &mut out,
+            &mut error,
+        )
+    };
+    assert_eq!(status, DF_ERR_INVALID_ARGUMENT);
+    assert_eq!(take(error)["code"], "unknown_format");
+
+    unsafe { df_workspace_free(workspace) };
+}
+
+#[test]
+fn resolution_failures_carry_application_diagnostics() {
+    let entry = c("model.sea");
+    let sources = c(&json!({ "model.sea": "import { Missing } from \"elsewhere\"\n" }).to_string());
+    let mut workspace = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let status = unsafe {
+        df_workspace_resolve(
+            entry.as_ptr(),
+            sources.as_ptr(),
+            ptr::null(),
+            &mut workspace,
+            &mut error,
+        )
+    };
+    assert_eq!(status, DF_ERR_RESOLUTION);
+    assert!(workspace.is_null());
+    let error = take(error);
+    assert!(error["code"].as_str().unwrap().starts_with("APP"));
+    assert_eq!(error["code"], error["diagnostics"][0]["code"]);
+}
+
+#[test]
+fn authority_decisions_round_trip_and_map_error_codes() {
+    let graph = domainforge_core::parse_to_graph(MODEL).expect("model parses");
+    let compilation = SeaPolicyCompiler::new("1.0.0".to_string(), "1.0.0".to_string())
+        .compile(&graph)
+        .expect("compiles");
+    let config = AuthorityEnvironmentConfig {
+        resolver_semantics_version: "1.0.0".to_string(),
+        specificity_profile: SpecificityProfile::default_profile(),
+        unknown_handling: UnknownHandlingConfig::defaults(),
+        fact_sources: vec![],
+        fact_transforms: vec![],
+        authority_packs: compilation
+            .packs
+            .iter()
+            .map(|p| serde_json::to_value(p).unwrap())
+            .collect(),
+        strict_mode: true,
+        compatibility_lowering_version: "1.0.0".to_string(),
+        resolver_version: "1.0.0".to_string(),
+    };
+    let request = json!({
+        "request_id": "req-1",
+        "actor": { "id": "alice", "role": "Payer", "groups": [] },
+        "operation": "Pay",
+        "resource": { "id": "payment-1", "type": "Payment" },
+        "context": {},
+        "requested_at": "2026-01-01T00:00:00Z",
+    });
+    let config_json = c(&serde_json::to_string(&config).unwrap());
+    let request_json = c(&request.to_string());
+
+    let mut out = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let status = unsafe {
+        df_authority_decide(
+            config_json.as_ptr(),
+            request_json.as_ptr(),
+            ptr::null(),
+            &mut out,
+            &mut error,
+        )
+    };
+    assert_eq!(
+        status,
+        DF_OK,
+        "{:?}",
+        (!error.is_null()).then(|| take(error))
+    );
+    let output = take(out);
+    assert_eq!(output["decision"]["request_id"], "req-1");
+    assert!(output["decision"]["final_decision"].is_string());
+    assert!(output["trace"].is_object());
+
+    let mut bad_config = serde_json::to_value(&config).unwrap();
+    bad_config["authority_packs"] = json!([{ "not": "a pack" }]);
+    let bad_config = c(&bad_config.to_string());
+    let status = unsafe {
+        df_authority_decide(
+            bad_config.as_ptr(),
+            request_json.as_ptr(),
+            ptr::null(),
+            &mut out,
+            &mut error,
+        )
+    };
+    assert_eq!(status, DF_ERR_AUTHORITY);
+    let error_doc = take(error);
+    assert!(error_doc["code"].is_string());
+    assert!(error_doc["message"].is_string());
+
+    let not_json = c("{");
+    let status = unsafe {
+        df_authority_decide(
+            not_json.as_ptr(),
+            request_json.as_ptr(),
+            ptr::null(),
+            &mut out,
+            &mut error,
+        )
+    };
+    assert_eq!(status, DF_ERR_INVALID_JSON);
+    assert_eq!(take(error)["code"], "invalid_json");
+}
+
+#[test]
+fn null_arguments_are_reported_not_dereferenced() {
+    let mut out = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    assert_eq!(
+        unsafe { df_parse(ptr::null(), &mut out, &mut error) },
+        DF_ERR_NULL_ARGUMENT
+    );
+    assert_eq!(take(error)["code"], "null_argument");
+
+    let (status, _) = call(df_workspace_validate, ptr::null());
+    assert_eq!(status, DF_ERR_NULL_ARGUMENT);
+
+    // Releasing NULL is a no-op.
+    unsafe {
+        df_string_free(ptr::null_mut());
+        df_workspace_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn header_matches_the_exported_abi() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source = std::fs::read_to_string(root.join("src/capi/mod.rs")).unwrap();
+    let header = render_header(&source);
+    let path = root.join("include/domainforge.h");
+    if std::env::var_os("DOMAINFORGE_BLESS_HEADER").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &header).unwrap();
+        return;
+    }
+    let committed = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        committed == header,
+        "include/domainforge.h is stale; regenerate it with \
+         DOMAINFORGE_BLESS_HEADER=1 cargo test --features capi --test capi_tests"
+    );
+}
+
+/// Render the C header for the `pub const DF_*`, `pub struct Df*` and
+/// `extern "C"` items of `src/capi/mod.rs`, carrying their doc comments.
+fn render_header(source: &str) -> String {
+    let mut out = String::from(
+        "/* domainforge.h: C ABI of domainforge-core (cargo feature `capi`).\n \
+         * Generated from src/capi/mod.rs by tests/capi_tests.rs; do not edit. */\n\n\
+         #ifndef DOMAINFORGE_H\n#define DOMAINFORGE_H\n\n#include <stdint.h>\n\n\
+         #ifdef __cplusplus\nextern \"C\" {\n#endif\n",
+    );
+    let mut docs: Vec<String> = Vec::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.strip_prefix(' ').unwrap_or(doc).to_string());
+            continue;
+        }
+        if line.starts_with("#[") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("pub const DF_") {
+            let (name, rest) = rest.split_once(':').unwrap();
+            let (ty, value) = rest.split_once('=').unwrap();
+            let value = value.trim().trim_end_matches(';');
+            let suffix = if ty.trim() == "u32" { "u" } else { "" };
+            out.push_str(&format!(
+                "\n{}#define DF_{name} {value}{suffix}\n",
+                comment(&docs)
+            ));
+        } else if let Some(rest) = line.strip_prefix("pub struct ") {
+            let name = rest.split([' ', '{']).next().unwrap();
+            out.push_str(&format!(
+                "\n{}typedef struct {name} {name};\n",
+                comment(&docs)
+            ));
+        } else if line.starts_with("pub extern \"C\" fn")
+            || line.starts_with("pub unsafe extern \"C\" fn")
+        {
+            let mut signature = line.to_string();
+            while !signature.ends_with('{') {
+                signature.push(' ');
+                signature.push_str(lines.next().unwrap().trim());
+            }
+            out.push_str(&format!(
+                "\n{}{}\n",
+                comment(&docs),
+                c_declaration(&signature)
+            ));
+        }
+        docs.clear();
+    }
+    out.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* DOMAINFORGE_H */\n");
+    out
+}
+
+fn comment(docs: &[String]) -> String {
+    let text: Vec<String> = docs
+        .iter()
+        .map(|line| {
+            let line = line.replace("[`", "`").replace("`]", "`");
+            match line.strip_prefix("# ") {
+                Some(heading) => format!("{heading}:"),
+                None => line,
+            }
+        })
+        .collect();
+    match text.as_slice() {
+        [] => String::new(),
+        [line] => format!("/** {line} */\n"),
+        lines => {
+            let body: String = lines
+                .iter()
+                .map(|line| {
+                    if line.is_empty() {
+                        " *\n".to_string()
+                    } else {
+                        format!(" * {line}\n")
+                    }
+                })
+                .collect();
+            format!("/**\n{body} */\n")
+        }
+    }
+}
+
+/// `pub unsafe extern "C" fn name(a: T, ...) -> R {` as a C prototype.
+fn c_declaration(signature: &str) -> String {
+    let rest = signature.split_once(" fn ").unwrap().1;
+    let (name, rest) = rest.split_once('(').unwrap();
+    let (params, rest) = rest.rsplit_once(')').unwrap();
+    let ret = rest
+        .trim()
+        .trim_end_matches('{')
+        .trim()
+        .strip_prefix("->")
+        .map(|ty| c_type(ty.trim()))
+        .unwrap_or_else(|| "void".to_string());
+    let params: Vec<String> = params
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (name, ty) = param.split_once(':').unwrap();
+            let ty = c_type(ty.trim());
+            format!(
+                "{ty}{}{}",
+                if ty.ends_with('*') { "" } else { " " },
+                name.trim()
+            )
+        })
+        .collect();
+    let params = if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.join(", ")
+    };
+    let separator = if ret.ends_with('*') { "" } else { " " };
+    format!("{ret}{separator}{name}({params});")
+}
+
+fn c_type(ty: &str) -> String {
+    let mut rest = ty;
+    let mut pointers = Vec::new();
+    loop {
+        if let Some(inner) = rest.strip_prefix("*const ") {
+            pointers.push("const");
+            rest = inner;
+        } else if let Some(inner) = rest.strip_prefix("*mut ") {
+            pointers.push("mut");
+            rest = inner;
+        } else {
+            break;
+        }
+    }
+    let base = match rest {
+        "c_char" => "char",
+        "i32" => "int32_t",
+        "u32" => "uint32_t",
+        other if other.starts_with("Df") => other,
+        other => panic!("no C mapping for Rust type `{other}`"),
+    };
+    let constness = match pointers.as_slice() {
+        ["const"] => "const ",
+        [_, ..] if pointers[1..].contains(&"const") => {
+            panic!("const behind a pointer is not mapped: `{ty}`")
+        }
+        _ => "",
+    };
+    let stars = "*".repeat(pointers.len());
+    if stars.is_empty() {
+        base.to_string()
+    } else {
+        format!("{constness}{base} {stars}")
+    }
+}
@@ -0,0 +1,167 @@
+#![cfg(feature = "cli")]
+
+//! Integration tests for `domainforge build` (workspace targets declared in
+//! `domainforge.toml`).
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+const MANIFEST: &str = r#"[build]
+input = "domain/model.sea"
+out_dir = "generated"
+
+[[target]]
+format = "archimate"
+created_at = "2024-01-01T00:00:00Z"
+
+[[target]]
+name = "proto"
+format = "protobuf"
+package = "orders.v1"
+"#;
+
+fn domainforge() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+}
+
+fn workspace(dir: &Path, manifest: &str) {
+    fs::create_dir_all(dir.join("domain")).unwrap();
+    fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures/archimate/basic/domain/model.sea"),
+        dir.join("domain/model.sea"),
+    )
+    .unwrap();
+    fs::write(dir.join("domainforge.toml"), manifest).unwrap();
+}
+
+fn build(dir: &Path) -> assert_cmd::assert::Assert {
+    domainforge().arg("build").arg(dir).assert()
+}
+
+#[test]
+fn builds_every_target_and_records_artifact_hashes() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(tmp.path(), MANIFEST);
+
+    build(tmp.path())
+        .success()
+        .stdout(predicate::str::contains("Built 2 target(s), skipped 0"));
+    assert!(tmp.path().join("generated/archimate/model.xml").is_file());
+    let proto = fs::read_to_string(tmp.path().join("generated/proto.proto")).unwrap();
+    assert!(proto.contains("package orders.v1;"));
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(tmp.path().join(".domainforge/build-manifest.json")).unwrap(),
+    )
+    .unwrap();
+    let proto = &manifest["targets"]["proto"];
+    assert_eq!(proto["format"], "protobuf");
+    assert_eq!(proto["output"], "generated/proto.proto");
+    assert!(proto["artifacts"]["generated/proto.proto"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+    assert!(manifest["targets"]["archimate"]["artifacts"]
+        .get("generated/archimate/model.xml")
+        .is_some());
+}
+
+#[test]
+fn skips_targets_whose_inputs_are_unchanged() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(tmp.path(), MANIFEST);
+    build(tmp.path()).success();
+
+    build(tmp.path())
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipping target 'proto' (protobuf): inputs unchanged",
+        ))
+        .stdout(predicate::str::contains("Built 0 target(s), skipped 2"));
+
+    // A deleted artifact rebuilds its target only.
+    fs::remove_file(tmp.path().join("generated/proto.proto")).unwrap();
+    build(tmp.path())
+        .success()
+        .stdout(predicate::str::contains("Building target 'proto'"))
+        .stdout(predicate::str::contains("Built 1 target(s), skipped 1"));
+
+    // A model edit rebuilds everything that reads the model.
+    let model = tmp.path().join("domain/model.sea");
+    let source = fs::read_to_string(&model).unwrap();
+    fs::write(&model, source + "\nEntity \"Auditor\"\n").unwrap();
+    build(tmp.path())
+        .success()
+        .stdout(predicate::str::contains("Built 2 target(s), skipped 0"));
+
+    // --force rebuilds regardless.
+    domainforge()
+        .arg("build")
+        .arg("--force")
+        .arg("--target")
+        .arg("archimate")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Built 1 target(s), skipped 0"));
+}
+
+#[test]
+fn rejects_unknown_formats_and_options() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(
+        tmp.path(),
+        "[build]\ninput = \"domain/model.sea\"\n\n[[target]]\nformat = \"visio\"\n",
+    );
+    build(tmp.path())
+        .failure()
+        .stderr(predicate::str::contains("Unknown format 'visio'"));
+
+    fs::write(
+        tmp.path().join("domainforge.toml"),
+        "[build]\ninput = \"domain/model.sea\"\n\n[[target]]\nformat = \"rdf\"\nbase_uri = \"x\"\n",
+    )
+    .unwrap();
+    build(tmp.path())
+        .failure()
+        .stderr(predicate::str::contains("unknown field `base_uri`"));
+}
+
+#[test]
+fn check_reports_drift_without_writing() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(tmp.path(), MANIFEST);
+    build(tmp.path()).success();
+    let manifest_path = tmp.path().join(".domainforge/build-manifest.json");
+    let manifest = fs::read_to_string(&manifest_path).unwrap();
+
+    domainforge()
+        .args(["build", "--check"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Checked 2 target(s): all up to date",
+        ));
+
+    let proto_path = tmp.path().join("generated/proto.proto");
+    let proto = fs::read_to_string(&proto_path).unwrap();
+    fs::write(&proto_path, proto.replace("orders.v1", "orders.v0")).unwrap();
+    fs::remove_file(tmp.path().join("generated/archimate/model.xml")).unwrap();
+
+    domainforge()
+        .args(["build", "--check"])
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("-package orders.v0;"))
+        .stdout(predicate::str::contains("+package orders.v1;"))
+        .stdout(
+            predicate::str::contains("Missing files").and(predicate::str::contains("model.xml")),
+        )
+        .stderr(predicate::str::contains("2 of 2 target(s) drifted"));
+    assert!(!tmp.path().join("generated/archimate/model.xml").exists());
+    assert_eq!(fs::read_to_string(&manifest_path).unwrap(), manifest);
+}
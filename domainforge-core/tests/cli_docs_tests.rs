@@ -0,0 +1,172 @@
+#![cfg(feature = "cli")]
+
+//! Integration tests for the documentation site projection (`project --format docs`).
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CREATED_AT: &str = "2026-07-02T00:00:00+00:00";
+
+fn domainforge() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+}
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../fixtures/application_generation/flagship/command-write.sea")
+}
+
+fn concept(id: &str, name: &str, kind: &str, text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "canonical_name": name,
+        "kind": kind,
+        "status": "active",
+        "definition": { "text": text, "definition_hash": "", "decision_ref": "ADR-7" },
+        "owner": "orders-team@example.com",
+        "examples": ["A web checkout for two books"],
+        "counterexamples": ["A quote that was never confirmed"]
+    })
+}
+
+fn write_pack(path: &Path) {
+    let pack = serde_json::json!({
+        "schema_version": "0.3",
+        "pack_id": "acme/orders/1.0.0",
+        "org_id": "acme",
+        "domain_id": "orders",
+        "pack_version": "1.0.0",
+        "meaning_version": "1.0.0",
+        "meaning_fingerprint": "",
+        "source_graph_hash": "sha256:test",
+        "build_config_hash": "sha256:cfg",
+        "review_manifest_hash": "sha256:rev",
+        "created_at": CREATED_AT,
+        "generator": { "name": "domainforge-core", "version": "0.3" },
+        "trust": { "approval_state": "approved", "signature_state": "unsigned" },
+        "concepts": [
+            concept("orders.order", "order", "entity", "A confirmed request to buy goods. It is immutable once placed."),
+            concept("orders.customer", "Customer", "role", "Someone who places orders."),
+        ],
+        "relations": [],
+        "metrics": [],
+        "dimensions": [],
+        "units": [],
+        "aliases": [{
+            "alias": "Purchase",
+            "normalized_alias": "purchase",
+            "target_concept_id": "orders.order",
+            "status": "approved",
+            "decision_ref": "ADR-7",
+            "source_ref": {
+                "uri": "domainforge://workspace-root",
+                "start_byte": 0, "end_byte": 0, "start_line": 0,
+                "start_col": 0, "end_line": 0, "end_col": 0
+            }
+        }],
+        "mapping_rules": [],
+        "compatibility": { "domainforge_min_version": "0.3" }
+    });
+    fs::write(path, serde_json::to_string_pretty(&pack).unwrap()).unwrap();
+}
+
+fn project_docs(pack: &Path, out: &Path) -> Command {
+    let mut cmd = domainforge();
+    cmd.args(["project", "--format", "docs", "--created-at", CREATED_AT])
+        .arg("--semantic-pack")
+        .arg(pack)
+        .arg(fixture())
+        .arg(out);
+    cmd
+}
+
+#[test]
+fn generates_an_offline_site_with_pack_definitions() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let pack = tmp.path().join("pack.json");
+    write_pack(&pack);
+    let out = tmp.path().join("site");
+
+    project_docs(&pack, &out)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Projected documentation site"));
+
+    for page in [
+        "index",
+        "glossary",
+        "policies",
+        "entities/order",
+        "roles/customer",
+        "records/placeorderinput",
+        "operations/place_order",
+    ] {
+        assert!(out.join(format!("{page}.html")).is_file(), "{page}.html");
+        assert!(out.join(format!("{page}.md")).is_file(), "{page}.md");
+    }
+    assert!(out.join("assets/style.css").is_file());
+
+    // Definitions, examples and aliases come from the pack; the policy bound
+    // to the operation on Order governs the entity.
+    let order = fs::read_to_string(out.join("entities/order.html")).unwrap();
+    assert!(order.contains("A confirmed request to buy goods."));
+    assert!(order.contains("A web checkout for two books"));
+    assert!(order.contains("Also known as: Purchase"));
+    assert!(order.contains(r#"<a href="../policies.html#order_total_within_limit">"#));
+    assert!(order.contains("It is obligatory that"));
+    assert!(order.contains(r#"<a href="../operations/place_order.html">place_order</a>"#));
+    assert!(order.contains("Generated by DomainForge from"));
+    assert!(order.contains(CREATED_AT));
+
+    let glossary = fs::read_to_string(out.join("glossary.html")).unwrap();
+    assert!(glossary.contains(r#"<table class="searchable">"#));
+    assert!(glossary.contains("<td>Purchase</td><td>Alias</td>"));
+    assert!(!glossary.contains("http://") && !glossary.contains("https://"));
+
+    let operation = fs::read_to_string(out.join("operations/place_order.md")).unwrap();
+    assert!(operation.contains("- Actor: [Customer](../roles/customer.md)"));
+    assert!(operation.contains("| `order_limit_exceeded` | order total exceeds 10000 USD |"));
+
+    // Byte-identical for a fixed created_at: `--check` finds no drift.
+    project_docs(&pack, &out).arg("--check").assert().success();
+}
+
+#[test]
+fn entity_pages_embed_diagrams_and_flows() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let model = tmp.path().join("model.sea");
+    fs::write(
+        &model,
+        r#"@namespace "procurement"
+Entity "Buyer" in procurement
+Entity "Supplier" in procurement
+Resource "Payment" USD in procurement
+Flow "Payment" from "Buyer" to "Supplier" quantity 100
+Policy payments_are_bounded as: Payment.quantity <= 1000
+"#,
+    )
+    .unwrap();
+    let out = tmp.path().join("site");
+
+    domainforge()
+        .args(["project", "--format", "docs", "--created-at", CREATED_AT])
+        .arg(&model)
+        .arg(&out)
+        .assert()
+        .success();
+
+    let buyer = fs::read_to_string(out.join("entities/buyer.html")).unwrap();
+    assert!(buyer.contains("<h2>Outgoing flows</h2>"));
+    assert!(buyer.contains(r#"<a href="../entities/supplier.html">Supplier</a>"#));
+    assert!(buyer.contains("<svg"));
+    let buyer_md = fs::read_to_string(out.join("entities/buyer.md")).unwrap();
+    assert!(buyer_md.contains("```mermaid"));
+
+    let payment = fs::read_to_string(out.join("resources/payment.md")).unwrap();
+    assert!(payment.contains(
+        "| [Buyer](../entities/buyer.md) | [Supplier](../entities/supplier.md) | 100 USD |"
+    ));
+    assert!(payment.contains("[payments_are_bounded](../policies.md#payments_are_bounded)"));
+}
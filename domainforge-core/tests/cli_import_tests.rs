@@ -115,8 +115,225 @@ fn test_cli_import_kg_turtle_minimal() {
         .stdout(predicate::str::contains("Imported KG (Turtle) to Graph"));
 }
 
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_import_bpmn_writes_sea_and_report() {
+    let bpmn = r#"<?xml version="1.0" encoding="UTF-8"?>
+<definitions xmlns="http://www.omg.org/spec/BPMN/20100524/MODEL" id="D" name="Returns">
+  <process id="P">
+    <startEvent id="S"/>
+    <task id="T1" name="Receive"/>
+    <exclusiveGateway id="G"/>
+    <task id="T2" name="Refund"/>
+    <sequenceFlow id="F1" sourceRef="S" targetRef="T1"/>
+    <sequenceFlow id="F2" sourceRef="T1" targetRef="G"/>
+    <sequenceFlow id="F3" sourceRef="G" targetRef="T2"/>
+  </process>
+</definitions>
+"#;
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("returns.bpmn");
+    let out = dir.path().join("returns.sea");
+    let report = dir.path().join("report.txt");
+    write(&file, bpmn).unwrap();
+
+    Command::new(get_sea_binary())
+        .arg("import")
+        .arg("--format")
+        .arg("bpmn")
+        .arg("--out")
+        .arg(&out)
+        .arg("--report")
+        .arg(&report)
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported BPMN 2.0 to"));
+
+    let sea = std::fs::read_to_string(&out).unwrap();
+    assert!(sea.contains("Flow \"Handoff\" from \"Receive\" to \"Refund\""));
+    let graph = domainforge_core::parse_to_graph(&sea).unwrap();
+    assert_eq!(graph.flow_count(), 1);
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert!(report.contains("2 element(s) have no SEA counterpart"));
+    assert!(report.contains("exclusiveGateway (G)"));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_import_openapi_writes_contract_and_report() {
+    let spec = r#"openapi: 3.0.3
+info: {title: Catalog, version: "1"}
+paths:
+  /items/{itemId}:
+    get:
+      operationId: getItem
+      parameters:
+        - {name: itemId, in: path, required: true, schema: {type: string}}
+      responses:
+        "200":
+          description: The item
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [title]
+                properties:
+                  title: {type: string}
+                  price: {oneOf: [{type: number}, {type: string}]}
+        "404": {description: Unknown item}
+"#;
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("catalog.yaml");
+    let out = dir.path().join("catalog.sea");
+    let report = dir.path().join("report.txt");
+    write(&file, spec).unwrap();
+
+    Command::new(get_sea_binary())
+        .arg("import")
+        .arg("--format")
+        .arg("openapi")
+        .arg("--out")
+        .arg(&out)
+        .arg("--report")
+        .arg(&report)
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported OpenAPI 3.0.3 to"));
+
+    let sea = std::fs::read_to_string(&out).unwrap();
+    assert!(sea.contains("operation get_item {"));
+    assert!(sea.contains("failure get_item_not_found for missing_state \"Unknown item\""));
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert!(report.contains("records=2 enums=0 operations=1"));
+    assert!(report.contains("oneOf (#/paths/~1items~1{itemId}/get/responses/200/content/application~1json/schema/properties/price)"));
+    assert!(report.contains("need review"));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_import_protobuf_verifies_round_trip() {
+    let proto = r#"syntax = "proto3";
+package shop.v1;
+
+enum Size {
+  SIZE_UNSPECIFIED = 0;
+  SIZE_SMALL = 1;
+  SIZE_LARGE = 2;
+}
+
+message Shirt {
+  string sku = 1;
+  Size size = 2;
+  double price = 3;
+}
+"#;
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("shop.proto");
+    let out = dir.path().join("shop.sea");
+    write(&file, proto).unwrap();
+
+    Command::new(get_sea_binary())
+        .arg("import")
+        .arg("--format")
+        .arg("protobuf")
+        .arg("--out")
+        .arg(&out)
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported Protobuf (proto3) to"))
+        .stderr(predicate::str::contains("Round trip: 1 difference(s):"));
+
+    let sea = std::fs::read_to_string(&out).unwrap();
+    assert!(sea.starts_with("@namespace \"shop_v1\""));
+    assert!(sea.contains("    size: Size optional\n"));
+
+    Command::new(get_sea_binary())
+        .arg("import")
+        .arg("--format")
+        .arg("protobuf")
+        .arg("--verify-round-trip")
+        .arg("--out")
+        .arg(&out)
+        .arg(&file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "message Shirt differs: `Size size = 2;` becomes `optional Size size = 2",
+        ))
+        .stderr(predicate::str::contains(
+            "Round trip failed: the projection differs from",
+        ));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_import_protobuf_round_trips_the_flagship_projection() {
+    let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../fixtures/application_generation/flagship/command-write.sea");
+    let dir = tempdir().unwrap();
+    let proto = dir.path().join("command-write.proto");
+    Command::new(get_sea_binary())
+        .arg("project")
+        .arg("--format")
+        .arg("protobuf")
+        .arg(&fixture)
+        .arg(&proto)
+        .assert()
+        .success();
+
+    let out = dir.path().join("command-write.sea");
+    let report = dir.path().join("command-write.report.txt");
+    Command::new(get_sea_binary())
+        .arg("import")
+        .arg("--format")
+        .arg("protobuf")
+        .arg("--verify-round-trip")
+        .arg("--out")
+        .arg(&out)
+        .arg("--report")
+        .arg(&report)
+        .arg(&proto)
+        .assert()
+        .success();
+
+    let sea = std::fs::read_to_string(&out).unwrap();
+    assert!(sea.contains("    key order_id: uuid\n"));
+    assert!(sea.contains("    status: OrderStatus default \"placed\"\n"));
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert!(report.contains("Round trip: no differences."));
+    assert!(report.contains("field PlaceOrderInput.total carries a quantity"));
+    assert!(report.contains("actor anonymous and access public are assumed"));
+    assert!(report.contains(
+        "entity Order is inferred from service OrderOperationsService and keyed by order_id"
+    ));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_import_out_is_sea_importers_only() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("test.ttl");
+    write(&file, "").unwrap();
+    Command::new(get_sea_binary())
+        .arg("import")
+        .arg("--format")
+        .arg("kg")
+        .arg("--out")
+        .arg(dir.path().join("x.sea"))
+        .arg(&file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "apply to --format bpmn, archimate, openapi, asyncapi and protobuf only",
+        ));
+}
+
 #[cfg(not(feature = "cli"))]
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn cli_import_tests_skipped_without_feature() {
     assert!(true);
 }
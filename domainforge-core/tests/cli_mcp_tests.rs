@@ -0,0 +1,336 @@
+#![cfg(feature = "cli")]
+
+//! Integration tests for the MCP server (`domainforge mcp`).
+
+use assert_cmd::Command;
+use domainforge_core::authority::*;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+const MODEL: &str = r#"@namespace "procurement"
+Entity "Buyer" in procurement
+Entity "Supplier" in procurement
+Entity "Bank" in procurement
+Resource "Payment" USD in procurement
+Role "Payer" in procurement
+Flow "Payment" from "Buyer" to "Supplier" quantity 100
+Flow "Payment" from "Bank" to "Buyer" quantity 500
+
+Policy block_credit_hold per Constraint Prohibition priority 100
+  as: action = "Pay" and resource.type = "Payment" and customer.credit_status = "hold"
+
+Policy payers_may_pay per Constraint Permission priority 10
+  as: action = "Pay" and actor.role = "Payer"
+"#;
+
+fn write_model(dir: &Path) -> std::path::PathBuf {
+    let model = dir.join("model.sea");
+    fs::write(&model, MODEL).unwrap();
+    model
+}
+
+/// Send `requests` (one JSON-RPC message each) and return the responses by id.
+fn session(args: &[&Path], requests: &[Value]) -> Vec<Value> {
+    let input: String = requests
+        .iter()
+        .map(|request| format!("{request}\n"))
+        .collect();
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+        .arg("mcp")
+        .args(args)
+        .write_stdin(input)
+        .output()
+        .expect("runs");
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON-RPC message per line"))
+        .collect()
+}
+
+fn call(id: u64, tool: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": tool, "arguments": arguments },
+    })
+}
+
+/// The JSON payload of a successful tool result.
+fn tool_output(response: &Value) -> Value {
+    let result = &response["result"];
+    assert_eq!(result["isError"], false, "{response}");
+    serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn handshake_lists_tools_and_serves_resources() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let model = write_model(tmp.path());
+
+    let responses = session(
+        &[&model],
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05", "capabilities": {},
+                            "clientInfo": { "name": "test", "version": "1" } },
+            }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "resources/read",
+                    "params": { "uri": "sea://modules/model.sea" } }),
+            json!({ "jsonrpc": "2.0", "id": 5, "method": "resources/read",
+                    "params": { "uri": "sea://envelope" } }),
+            json!({ "jsonrpc": "2.0", "id": 6, "method": "prompts/list" }),
+        ],
+    );
+    // The notification gets no response.
+    assert_eq!(responses.len(), 6);
+
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2024-11-05");
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "domainforge");
+
+    let tools: Vec<&str> = responses[1]["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        tools,
+        [
+            "lookup_concept",
+            "graph_neighbourhood",
+            "evaluate_policies",
+            "authority_decision",
+            "validate_snippet"
+        ]
+    );
+
+    let uris: Vec<&str> = responses[2]["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|resource| resource["uri"].as_str().unwrap())
+        .collect();
+    assert_eq!(uris, ["sea://modules/model.sea", "sea://envelope"]);
+
+    assert_eq!(responses[3]["result"]["contents"][0]["text"], MODEL);
+    let envelope: Value = serde_json::from_str(
+        responses[4]["result"]["contents"][0]["text"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        envelope["schema_version"],
+        "domainforge-semantic-envelope/v1"
+    );
+
+    assert_eq!(responses[5]["error"]["code"], -32601);
+}
+
+#[test]
+fn model_tools_answer_from_the_current_files() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let model = write_model(tmp.path());
+
+    let responses = session(
+        &[&model],
+        &[
+            call(
+                1,
+                "graph_neighbourhood",
+                json!({ "concept": "Supplier", "depth": 2 }),
+            ),
+            call(2, "graph_neighbourhood", json!({ "concept": "Payment" })),
+            call(
+                3,
+                "evaluate_policies",
+                json!({ "policy": "payers_may_pay" }),
+            ),
+            call(
+                4,
+                "validate_snippet",
+                json!({ "source": "Flow \"Payment\" from \"Buyer\" to \"Nobody\" quantity 5" }),
+            ),
+            call(
+                5,
+                "validate_snippet",
+                json!({ "source": "Entity \"Auditor\" in procurement" }),
+            ),
+            call(6, "lookup_concept", json!({ "term": "Payment" })),
+        ],
+    );
+
+    let supplier = tool_output(&responses[0]);
+    assert_eq!(supplier["kind"], "entity");
+    let entities: Vec<(&str, u64)> = supplier["entities"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| (e["name"].as_str().unwrap(), e["hops"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(entities, [("Buyer", 1), ("Bank", 2)]);
+    assert_eq!(supplier["flows"].as_array().unwrap().len(), 2);
+
+    let payment = tool_output(&responses[1]);
+    assert_eq!(payment["kind"], "resource");
+    assert_eq!(payment["unit"], "USD");
+    assert_eq!(payment["policies"][0]["name"], "block_credit_hold");
+
+    let policies = tool_output(&responses[2]);
+    assert_eq!(policies["policies"].as_array().unwrap().len(), 1);
+    assert!(policies["policies"][0]["english"]
+        .as_str()
+        .unwrap()
+        .starts_with("It is permitted that"));
+
+    let invalid = tool_output(&responses[3]);
+    assert_eq!(invalid["valid"], false);
+    assert_eq!(invalid["snippet_line"], 15);
+    assert!(invalid["diagnostics"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Nobody"));
+
+    let valid = tool_output(&responses[4]);
+    assert_eq!(valid["diagnostics"], json!([]));
+    // Validation never writes the snippet to disk.
+    assert_eq!(fs::read_to_string(&model).unwrap(), MODEL);
+
+    // Without --semantic-pack, lookups fail as a readable tool error.
+    assert_eq!(responses[5]["result"]["isError"], true);
+}
+
+#[test]
+fn authority_decisions_use_the_configured_environment() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let model = write_model(tmp.path());
+
+    let graph = domainforge_core::parse_to_graph(MODEL).expect("model parses");
+    let compilation = SeaPolicyCompiler::new("1.0.0".to_string(), "1.0.0".to_string())
+        .compile(&graph)
+        .expect("compiles");
+    let config = AuthorityEnvironmentConfig {
+        resolver_semantics_version: "1.0.0".to_string(),
+        specificity_profile: SpecificityProfile::default_profile(),
+        unknown_handling: UnknownHandlingConfig::defaults(),
+        fact_sources: vec![],
+        fact_transforms: vec![],
+        authority_packs: compilation
+            .packs
+            .iter()
+            .map(|p| serde_json::to_value(p).unwrap())
+            .collect(),
+        strict_mode: true,
+        compatibility_lowering_version: "1.0.0".to_string(),
+        resolver_version: "1.0.0".to_string(),
+    };
+    let config_path = tmp.path().join("authority.json");
+    fs::write(&config_path, serde_json::to_string(&config).unwrap()).unwrap();
+
+    let request = AuthorityRequest {
+        request_id: "req-1".to_string(),
+        actor: ActorContext {
+            id: "alice".to_string(),
+            role: Some("Payer".to_string()),
+            groups: vec![],
+            service_account: None,
+            agent_identity: None,
+        },
+        operation: "Pay".to_string(),
+        resource: ResourceRef {
+            id: Some("payment-1".to_string()),
+            type_: Some("Payment".to_string()),
+            extra: Default::default(),
+        },
+        context: json!({}),
+        requested_at: chrono::Utc::now(),
+        correlation_id: None,
+        risk_class: None,
+        metadata: Default::default(),
+    };
+
+    let responses = session(
+        &[&model, Path::new("--authority-config"), &config_path],
+        &[call(
+            1,
+            "authority_decision",
+            json!({ "request": serde_json::to_value(&request).unwrap() }),
+        )],
+    );
+    let output = tool_output(&responses[0]);
+    assert_eq!(output["decision"]["request_id"], "req-1");
+    assert!(output["decision"]["final_decision"].is_string());
+    assert!(output["trace"].is_object());
+}
+
+#[test]
+fn concept_lookup_resolves_aliases_through_the_semantic_pack() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let model = write_model(tmp.path());
+    let source_ref = json!({
+        "uri": "domainforge://workspace-root",
+        "start_byte": 0, "end_byte": 0, "start_line": 0,
+        "start_col": 0, "end_line": 0, "end_col": 0
+    });
+    let pack = json!({
+        "schema_version": "0.3",
+        "pack_id": "acme/procurement/1.0.0",
+        "org_id": "acme",
+        "domain_id": "procurement",
+        "pack_version": "1.0.0",
+        "meaning_version": "1.0.0",
+        "meaning_fingerprint": "",
+        "source_graph_hash": "sha256:test",
+        "build_config_hash": "sha256:cfg",
+        "review_manifest_hash": "sha256:rev",
+        "created_at": "2026-07-02T00:00:00+00:00",
+        "generator": { "name": "domainforge-core", "version": "0.3" },
+        "trust": { "approval_state": "approved", "signature_state": "unsigned" },
+        "concepts": [{
+            "id": "procurement.payment",
+            "canonical_name": "Payment",
+            "kind": "resource",
+            "status": "active",
+            "owner": "finance@example.com",
+            "definition": { "text": "Money owed for delivered goods.", "definition_hash": "", "decision_ref": "ADR-3" },
+        }],
+        "relations": [],
+        "metrics": [],
+        "dimensions": [],
+        "units": [],
+        "aliases": [{
+            "alias": "Remittance",
+            "normalized_alias": "remittance",
+            "target_concept_id": "procurement.payment",
+            "status": "approved",
+            "decision_ref": "ADR-3",
+            "source_ref": source_ref,
+        }],
+        "mapping_rules": [],
+        "compatibility": { "domainforge_min_version": "0.3" }
+    });
+    let pack_path = tmp.path().join("pack.json");
+    fs::write(&pack_path, pack.to_string()).unwrap();
+
+    let responses = session(
+        &[&model, Path::new("--semantic-pack"), &pack_path],
+        &[call(1, "lookup_concept", json!({ "term": "remittance" }))],
+    );
+    let output = tool_output(&responses[0]);
+    assert_eq!(output["resolved_concept_id"], "procurement.payment");
+    assert_eq!(
+        output["concept"]["definition"]["text"],
+        "Money owed for delivered goods."
+    );
+    assert_eq!(output["aliases"][0]["alias"], "Remittance");
+}
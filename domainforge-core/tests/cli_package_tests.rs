@@ -0,0 +1,118 @@
+#![cfg(feature = "cli")]
+
+//! Integration tests for `domainforge package` and `pkg:` imports.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+fn domainforge() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+}
+
+fn write(dir: &Path, path: &str, content: &str) {
+    let full_path = dir.join(path);
+    fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+    fs::write(full_path, content).unwrap();
+}
+
+/// An `app` workspace depending on a sibling `payments` package.
+fn workspace(dir: &Path) {
+    write(
+        dir,
+        "payments/domainforge.toml",
+        "[package]\nname = \"payments\"\nversion = \"0.3.0\"\nsource = \"domain\"\n",
+    );
+    write(
+        dir,
+        "payments/domain/core.sea",
+        "@namespace \"payments\"\nexport Entity \"Payment\"\n",
+    );
+    write(
+        dir,
+        "app/domainforge.toml",
+        "[package]\nname = \"app\"\nversion = \"1.0.0\"\n\n[dependencies]\npayments = { path = \"../payments\" }\n",
+    );
+    write(
+        dir,
+        "app/model.sea",
+        "@namespace \"app\"\nimport { Payment } from \"pkg:payments/core\"\n\nEntity \"Checkout\"\n",
+    );
+}
+
+#[test]
+fn locked_package_imports_resolve_and_survive_vendoring() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(tmp.path());
+    let app = tmp.path().join("app");
+
+    domainforge()
+        .arg("validate")
+        .arg(app.join("model.sea"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("run `domainforge package lock`"));
+
+    domainforge()
+        .arg("package")
+        .arg("lock")
+        .arg(&app)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Locked payments 0.3.0 (path+../payments)",
+        ));
+    let lock = fs::read_to_string(app.join("domainforge.lock")).unwrap();
+    assert!(lock.contains("path = \"../payments/domain\""));
+    assert!(lock.contains("checksum = \"sha256:"));
+
+    domainforge()
+        .arg("validate")
+        .arg(app.join("model.sea"))
+        .assert()
+        .success();
+
+    domainforge()
+        .arg("package")
+        .arg("vendor")
+        .arg(&app)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Vendored 1 package(s)"));
+    assert!(app.join("vendor/payments/core.sea").is_file());
+    fs::remove_dir_all(tmp.path().join("payments")).unwrap();
+
+    domainforge()
+        .arg("validate")
+        .arg(app.join("model.sea"))
+        .assert()
+        .success();
+}
+
+#[test]
+fn edited_package_fails_its_locked_checksum() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(tmp.path());
+    let app = tmp.path().join("app");
+    domainforge()
+        .arg("package")
+        .arg("lock")
+        .arg(&app)
+        .assert()
+        .success();
+
+    write(
+        tmp.path(),
+        "payments/domain/core.sea",
+        "@namespace \"payments\"\nexport Entity \"Payment\"\nexport Entity \"Refund\"\n",
+    );
+    domainforge()
+        .arg("validate")
+        .arg(app.join("model.sea"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Package 'payments' does not match its locked checksum",
+        ));
+}
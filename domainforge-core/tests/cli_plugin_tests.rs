@@ -0,0 +1,167 @@
+#![cfg(all(feature = "cli", unix))]
+
+//! Integration tests for external projection plugins
+//! (`project --format plugin:<name>`).
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+const MODEL: &str = r#"Entity "Warehouse" in logistics
+Entity "Factory" in logistics
+Resource "Widget" units in logistics
+Flow "Widget" from "Factory" to "Warehouse" quantity 10
+"#;
+
+fn domainforge() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+}
+
+/// A workspace whose `erd` plugin saves the request it receives next to the
+/// model and answers with `response`.
+fn workspace(dir: &Path, response: &str) {
+    fs::write(dir.join("model.sea"), MODEL).unwrap();
+    fs::create_dir_all(dir.join("tools")).unwrap();
+    let script = dir.join("tools/erd.sh");
+    fs::write(
+        &script,
+        format!("#!/bin/sh\ncat > \"$(dirname \"$0\")/../request.json\"\ncat <<'JSON'\n{response}\nJSON\n"),
+    )
+    .unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::write(
+        dir.join("domainforge.toml"),
+        "[plugins]\nerd = \"tools/erd.sh\"\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn plugin_receives_the_ir_and_its_artifacts_are_written() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(
+        tmp.path(),
+        r#"{"protocol":"domainforge-projection-plugin/v1","artifacts":{"erd/model.txt":"Factory -> Warehouse\n"},"warnings":["draft layout"]}"#,
+    );
+
+    domainforge()
+        .current_dir(tmp.path())
+        .args([
+            "project",
+            "--format",
+            "plugin:erd",
+            "--plugin-option",
+            "title=Logistics",
+        ])
+        .args(["model.sea", "out"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Projected with plugin 'erd' to out (1 files)",
+        ))
+        .stderr(predicate::str::contains("Warning (erd): draft layout"));
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("out/erd/model.txt")).unwrap(),
+        "Factory -> Warehouse\n"
+    );
+
+    let request: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(tmp.path().join("request.json")).unwrap())
+            .unwrap();
+    assert_eq!(request["protocol"], "domainforge-projection-plugin/v1");
+    assert_eq!(request["plugin"], "erd");
+    assert_eq!(request["options"]["title"], "Logistics");
+    assert_eq!(request["graph"]["entities"].as_object().unwrap().len(), 2);
+    assert!(request["application_contract"].is_object());
+    assert_eq!(
+        request["semantic_envelope"]["schema_version"],
+        "domainforge-semantic-envelope/v1"
+    );
+
+    domainforge()
+        .current_dir(tmp.path())
+        .args([
+            "project",
+            "--check",
+            "--format",
+            "plugin:erd",
+            "--plugin-option",
+            "title=Logistics",
+        ])
+        .args(["model.sea", "out"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("out is up to date"));
+}
+
+#[test]
+fn plugin_artifacts_cannot_escape_the_output_directory() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(
+        tmp.path(),
+        r#"{"protocol":"domainforge-projection-plugin/v1","artifacts":{"../escaped.txt":"x"}}"#,
+    );
+
+    domainforge()
+        .current_dir(tmp.path())
+        .args(["project", "--format", "plugin:erd", "model.sea", "out"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("escapes output directory"));
+    assert!(!tmp.path().join("escaped.txt").exists());
+}
+
+#[test]
+fn plugin_responses_must_speak_the_protocol() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(
+        tmp.path(),
+        r#"{"protocol":"domainforge-projection-plugin/v0","artifacts":{}}"#,
+    );
+
+    domainforge()
+        .current_dir(tmp.path())
+        .args(["project", "--format", "plugin:erd", "model.sea", "out"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "expected 'domainforge-projection-plugin/v1'",
+        ));
+
+    domainforge()
+        .current_dir(tmp.path())
+        .args(["project", "--format", "plugin:missing", "model.sea", "out"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no projection plugin 'missing'"));
+}
+
+#[test]
+fn build_targets_can_run_plugins() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    workspace(
+        tmp.path(),
+        r#"{"protocol":"domainforge-projection-plugin/v1","artifacts":{"erd.txt":"ok\n"}}"#,
+    );
+    fs::write(
+        tmp.path().join("domainforge.toml"),
+        "[plugins]\nerd = \"tools/erd.sh\"\n\n[build]\ninput = \"model.sea\"\n\n[[target]]\nformat = \"plugin:erd\"\nplugin_options = { title = \"Logistics\" }\n",
+    )
+    .unwrap();
+
+    domainforge()
+        .arg("build")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Building target 'erd' (plugin:erd)",
+        ));
+    assert!(tmp.path().join("erd/erd.txt").is_file());
+    let request: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(tmp.path().join("request.json")).unwrap())
+            .unwrap();
+    assert_eq!(request["options"]["title"], "Logistics");
+}
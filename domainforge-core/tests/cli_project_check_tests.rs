@@ -0,0 +1,139 @@
+#![cfg(feature = "cli")]
+
+//! Integration tests for `domainforge project --check`: render in memory,
+//! compare against the output on disk, never write.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+const MODEL: &str = r#"Entity "Warehouse" in logistics
+Entity "Factory" in logistics
+Resource "Widget" units in logistics
+Flow "Widget" from "Factory" to "Warehouse" quantity 10
+"#;
+
+fn project(dir: &Path, format: &str, output: &str, check: bool) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.current_dir(dir).args(["project", "--format", format]);
+    if format == "mermaid" {
+        cmd.args(["--created-at", "2024-01-01T00:00:00Z"]);
+    }
+    if check {
+        cmd.arg("--check");
+    }
+    cmd.args(["model.sea", output]).assert()
+}
+
+#[test]
+fn directory_projection_reports_changed_missing_and_extra_files() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("model.sea"), MODEL).unwrap();
+
+    // Nothing projected yet: every file is missing and nothing is created.
+    project(tmp.path(), "mermaid", "out", true)
+        .failure()
+        .stdout(
+            predicate::str::contains("Missing files").and(predicate::str::contains("model.mmd")),
+        );
+    assert!(!tmp.path().join("out").exists());
+
+    project(tmp.path(), "mermaid", "out", false).success();
+    project(tmp.path(), "mermaid", "out", true)
+        .success()
+        .stdout(predicate::str::contains("out is up to date"));
+
+    let diagram = tmp.path().join("out/model.mmd");
+    let original = fs::read_to_string(&diagram).unwrap();
+    let edited = original.replace("Warehouse", "Depot");
+    fs::write(&diagram, &edited).unwrap();
+    fs::write(tmp.path().join("out/notes.txt"), "hand-written\n").unwrap();
+
+    project(tmp.path(), "mermaid", "out", true)
+        .failure()
+        .stdout(predicate::str::contains(
+            "--- a/model.mmd\n+++ b/model.mmd\n@@ ",
+        ))
+        .stdout(predicate::str::contains("Depot").and(predicate::str::contains("Warehouse")))
+        .stdout(predicate::str::contains("Extra files").and(predicate::str::contains("notes.txt")))
+        .stderr(predicate::str::contains(
+            "1 changed, 0 missing, 1 extra file(s)",
+        ));
+    assert_eq!(fs::read_to_string(&diagram).unwrap(), edited);
+}
+
+#[test]
+fn single_file_projection_is_compared_in_place() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("model.sea"), MODEL).unwrap();
+    project(tmp.path(), "protobuf", "model.proto", false).success();
+    project(tmp.path(), "protobuf", "model.proto", true)
+        .success()
+        .stdout(predicate::str::contains("model.proto is up to date"));
+
+    fs::write(tmp.path().join("model.proto"), "syntax = \"proto3\";\n").unwrap();
+    project(tmp.path(), "protobuf", "model.proto", true)
+        .failure()
+        .stdout(predicate::str::contains("@@ -1,1 +1,"))
+        .stderr(predicate::str::contains("model.proto has drifted"));
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("model.proto")).unwrap(),
+        "syntax = \"proto3\";\n"
+    );
+}
+
+#[test]
+fn output_projected_without_created_at_checks_clean() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let flagship = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../fixtures/application_generation/flagship/command-write.sea");
+    fs::copy(flagship, tmp.path().join("model.sea")).unwrap();
+
+    // Each embeds the time of the run; --check renders with the recorded one.
+    for format in ["domain-rust", "sql-postgres", "graphql", "docs"] {
+        project(tmp.path(), format, format, false).success();
+        project(tmp.path(), format, format, true)
+            .success()
+            .stdout(predicate::str::contains(format!("{format} is up to date")));
+    }
+
+    let readme = tmp.path().join("domain-rust/README.md");
+    let edited = fs::read_to_string(&readme).unwrap() + "hand-written\n";
+    fs::write(&readme, edited).unwrap();
+    project(tmp.path(), "domain-rust", "domain-rust", true)
+        .failure()
+        .stderr(predicate::str::contains("domain-rust has drifted"));
+}
+
+#[test]
+fn output_without_a_generated_at_marker_needs_created_at() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("model.sea"), MODEL).unwrap();
+
+    // CloudEvents are JSON lines: the run time is in each event, with no
+    // header to carry the marker.
+    project(tmp.path(), "cloudevents", "events", false).success();
+    project(tmp.path(), "cloudevents", "events", true)
+        .failure()
+        .stderr(predicate::str::contains(
+            "records no `generated-at:` timestamp; pass --created-at",
+        ));
+
+    let events = fs::read_to_string(tmp.path().join("events/events.jsonl")).unwrap();
+    let first: serde_json::Value = serde_json::from_str(events.lines().next().unwrap()).unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.current_dir(tmp.path())
+        .args([
+            "project",
+            "--format",
+            "cloudevents",
+            "--check",
+            "--created-at",
+        ])
+        .arg(first["time"].as_str().unwrap())
+        .args(["model.sea", "events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("events is up to date"));
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "cli")]
+
+//! Integration tests for template-driven projections (`project --format template`).
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn domainforge() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+}
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../fixtures/application_generation/flagship/command-write.sea")
+}
+
+fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn templates(root: &Path) {
+    write(
+        root,
+        "README.md.j2",
+        "# {{ model.namespace }}\n\n\
+         {% for entity in entities %}\n\
+         - [{{ entity.name }}](entities/{{ entity.slug }}.yaml)\n\
+         {% endfor %}\n",
+    );
+    write(
+        root,
+        "entities/[entity].yaml.j2",
+        "{% import '_macros.j2' as m %}\n\
+         kind: {{ entity.name | pascal }}\n\
+         key: {{ entity.key_field }}\n\
+         fields:\n\
+         {% for field in entity.fields %}\n\
+         {{ m.field(field) }}\n\
+         {% endfor %}\n",
+    );
+    write(
+        root,
+        "_macros.j2",
+        "{% macro field(f) %}  {{ f.name | ident }}: {{ f.type }}{% if f.optional %}?{% endif %}{% endmacro %}",
+    );
+    write(
+        root,
+        "operations/[operation].md.j2",
+        "## {{ operation.name }}\n\n\
+         {{ operation.intent }} ({{ operation.actor }}: {{ operation.input }} -> {{ operation.output }})\n\
+         {% for policy in operation.policies %}\n\
+         - guarded by `{{ policy | slug }}`\n\
+         {% endfor %}\n",
+    );
+    write(root, "static/LICENSE", "Apache-2.0\n");
+}
+
+#[test]
+fn renders_a_template_directory_against_the_model() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    templates(&tmp.path().join("templates"));
+    let out = tmp.path().join("out");
+
+    for _ in 0..2 {
+        domainforge()
+            .args([
+                "project",
+                "--format",
+                "template",
+                "--namespace",
+                "flagship.orders",
+            ])
+            .arg("--templates")
+            .arg(tmp.path().join("templates"))
+            .arg(fixture())
+            .arg(&out)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("(4 files)"));
+    }
+
+    assert_eq!(
+        fs::read_to_string(out.join("README.md")).unwrap(),
+        "# flagship.orders\n\n- [Order](entities/order.yaml)\n"
+    );
+    assert_eq!(
+        fs::read_to_string(out.join("entities/order.yaml")).unwrap(),
+        "kind: Order\nkey: order_id\nfields:\n  order_id: uuid\n  client_order_id: string\n  \
+         total: quantity\n  item_count: int\n  status: enum<OrderStatus>\n"
+    );
+    assert_eq!(
+        fs::read_to_string(out.join("operations/place_order.md")).unwrap(),
+        "## place_order\n\npersist one valid order exactly once \
+         (Customer: PlaceOrderInput -> PlaceOrderOutput)\n\
+         - guarded by `order_total_within_limit`\n"
+    );
+    assert_eq!(
+        fs::read_to_string(out.join("static/LICENSE")).unwrap(),
+        "Apache-2.0\n"
+    );
+    assert!(!out.join("_macros").exists() && !out.join("_macros.j2").exists());
+
+    domainforge()
+        .args([
+            "project",
+            "--check",
+            "--format",
+            "template",
+            "--namespace",
+            "flagship.orders",
+        ])
+        .arg("--templates")
+        .arg(tmp.path().join("templates"))
+        .arg(fixture())
+        .arg(&out)
+        .assert()
+        .success();
+}
+
+#[test]
+fn template_errors_name_the_template() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    write(tmp.path(), "templates/broken.txt.j2", "{{ entity.nmae }}\n");
+
+    domainforge()
+        .args(["project", "--format", "template"])
+        .arg(fixture())
+        .arg(tmp.path().join("out"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--templates <DIR> is required"));
+
+    domainforge()
+        .args(["project", "--format", "template", "--templates"])
+        .arg(tmp.path().join("templates"))
+        .arg(fixture())
+        .arg(tmp.path().join("out"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("template broken.txt.j2"));
+}
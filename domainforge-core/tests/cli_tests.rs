@@ -93,3 +93,39 @@ fn test_project_cell_format_via_cli() {
     assert!(out_dir.path().join("cell.lock").exists());
     assert!(out_dir.path().join("semantic/cell-ir.json").exists());
 }
+
+#[test]
+fn test_check_payload_via_cli() {
+    let model = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../fixtures/application_generation/flagship/command-write.sea"
+    );
+    let payload = |total: &str| {
+        format!(
+            "{{\"order_id\":\"7f1c4b1e-0c7e-4d0a-9a53-6c5f0f0b2b11\",\"client_order_id\":\"c-1\",\"total\":{total},\"item_count\":2}}"
+        )
+    };
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.args(["check-payload", model, "--record", "PlaceOrderInput"])
+        .write_stdin(payload("250"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Payload is valid for record 'PlaceOrderInput'",
+        ));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("domainforge"));
+    cmd.args([
+        "check-payload",
+        model,
+        "--record",
+        "PlaceOrderInput",
+        "--json",
+    ])
+    .write_stdin(payload("20000"))
+    .assert()
+    .failure()
+    .stdout(predicate::str::contains("\"path\": \"/total\""))
+    .stdout(predicate::str::contains("\"kind\": \"policy_violation\""));
+}
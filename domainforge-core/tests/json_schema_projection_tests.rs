@@ -0,0 +1,61 @@
+//! The projected JSON Schema documents compile as Draft 2020-12 and accept
+//! exactly the payloads the flagship application records describe.
+
+use domainforge_core::application::{resolve_application_contract, resolve_application_graph};
+use domainforge_core::projection::json_schema::project_json_schema_in_memory;
+use jsonschema::{Draft, JSONSchema};
+use serde_json::{json, Value};
+
+const FIXED_TS: &str = "2026-07-02T00:00:00+00:00";
+
+fn flagship_schema(file: &str) -> JSONSchema {
+    let sources = json!({
+        "flagship/command-write.sea": include_str!(
+            "../../fixtures/application_generation/flagship/command-write.sea"),
+        "flagship/query-read.sea": include_str!(
+            "../../fixtures/application_generation/flagship/query-read.sea"),
+    })
+    .to_string();
+    let entry = "flagship/query-read.sea";
+    let graph = resolve_application_graph(entry, &sources).expect("graph");
+    let contract = resolve_application_contract(entry, &sources)
+        .expect("contract")
+        .contract;
+    let files = project_json_schema_in_memory(&graph, &contract, entry, Some(FIXED_TS.into()))
+        .expect("projects");
+    let schema: Value = serde_json::from_str(&files[file]).expect("valid JSON");
+    JSONSchema::options()
+        .with_draft(Draft::Draft202012)
+        .compile(&schema)
+        .expect("schema compiles")
+}
+
+#[test]
+fn command_input_accepts_valid_payloads_only() {
+    let schema = flagship_schema("flagship.orders.PlaceOrderInput.schema.json");
+    let valid = json!({
+        "order_id": "0f8fad5b-d9cb-469f-a165-70867728950e",
+        "client_order_id": "c-1",
+        "total": 12.5,
+        "item_count": 2,
+    });
+    assert!(schema.is_valid(&valid));
+
+    for invalid in [
+        json!({ "order_id": "0f8fad5b-d9cb-469f-a165-70867728950e", "client_order_id": "", "total": 1, "item_count": 1 }),
+        json!({ "order_id": "0f8fad5b-d9cb-469f-a165-70867728950e", "client_order_id": "c", "total": 0, "item_count": 1 }),
+        json!({ "order_id": "0f8fad5b-d9cb-469f-a165-70867728950e", "client_order_id": "c", "total": 1, "item_count": 0 }),
+        json!({ "client_order_id": "c", "total": 1, "item_count": 1 }),
+        json!({ "order_id": "0f8fad5b-d9cb-469f-a165-70867728950e", "client_order_id": "c", "total": 1, "item_count": 1, "extra": true }),
+    ] {
+        assert!(!schema.is_valid(&invalid), "{invalid} should be rejected");
+    }
+}
+
+#[test]
+fn query_output_enum_is_resolved_through_defs() {
+    let schema = flagship_schema("flagship.orders.GetOrderStatusOutput.schema.json");
+    let id = "0f8fad5b-d9cb-469f-a165-70867728950e";
+    assert!(schema.is_valid(&json!({ "order_id": id, "status": "placed" })));
+    assert!(!schema.is_valid(&json!({ "order_id": id, "status": "shipped" })));
+}
@@ -59,6 +59,40 @@ Flow "Widget" from "Warehouse" to "Factory" quantity 100
     assert_eq!(output, expected);
 }
 
+#[test]
+fn test_pretty_print_flow_annotations_round_trip() {
+    let mut annotations = HashMap::new();
+    annotations.insert("streaming".to_string(), json!("server_streaming"));
+    annotations.insert("cqrs".to_string(), json!({ "kind": "command" }));
+    let ast = Ast {
+        metadata: FileMetadata {
+            namespace: Some("test_ns".to_string()),
+            ..Default::default()
+        },
+        declarations: vec![spanned(AstNode::Flow {
+            resource_name: "Widget".to_string(),
+            annotations,
+            from_entity: "Warehouse".to_string(),
+            to_entity: "Factory".to_string(),
+            quantity: None,
+        })],
+    };
+
+    let output = PrettyPrinter::new().print(&ast);
+    assert_eq!(
+        output,
+        r#"@namespace "test_ns"
+
+Flow "Widget"
+    @cqrs {"kind":"command"}
+    @streaming "server_streaming"
+    from "Warehouse" to "Factory"
+"#
+    );
+    let reparsed = domainforge_core::parser::parse(&output).unwrap();
+    assert_eq!(reparsed.declarations[0].node, ast.declarations[0].node);
+}
+
 #[test]
 fn test_pretty_print_policy_header() {
     let metadata = PolicyMetadata {
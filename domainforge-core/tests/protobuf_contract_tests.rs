@@ -0,0 +1,208 @@
+//! Application-contract lowering into the Protobuf projection (ADR-013).
+
+use domainforge_core::application::{resolve_application_contract, resolve_application_graph};
+use domainforge_core::graph::Graph;
+use domainforge_core::projection::protobuf::{
+    CompatibilityChecker, CompatibilityMode, ProtoMessage, ProtobufEngine, ViolationType,
+};
+use domainforge_core::projection::protobuf_contract::PROTOVALIDATE_IMPORT;
+use domainforge_core::projection::{merge_application_contract, project_application_contract};
+use domainforge_core::projection::{ProtoFile, ProtoType};
+use serde_json::json;
+
+const ENTRY: &str = "flagship/command-write.sea";
+
+fn sources(source: &str) -> String {
+    json!({ ENTRY: source }).to_string()
+}
+
+fn flagship() -> String {
+    include_str!("../../fixtures/application_generation/flagship/command-write.sea").to_string()
+}
+
+fn project(source: &str) -> ProtoFile {
+    let sources = sources(source);
+    let graph: Graph = resolve_application_graph(ENTRY, &sources).expect("graph");
+    let document = resolve_application_contract(ENTRY, &sources).expect("contract");
+    let mut proto = ProtobufEngine::project(&graph, "", "sea.orders");
+    merge_application_contract(&mut proto, &graph, &document.contract, "").expect("merges");
+    proto
+}
+
+fn message<'a>(proto: &'a ProtoFile, name: &str) -> &'a ProtoMessage {
+    proto
+        .messages
+        .iter()
+        .find(|m| m.name == name)
+        .unwrap_or_else(|| panic!("message {name}"))
+}
+
+#[test]
+fn records_become_messages_with_protovalidate_rules() {
+    let proto = project(&flagship());
+    assert!(proto.imports.iter().any(|i| i == PROTOVALIDATE_IMPORT));
+
+    let input = message(&proto, "PlaceOrderInput");
+    let names: Vec<_> = input.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["order_id", "client_order_id", "total", "item_count"]
+    );
+    let text = input.to_proto_string();
+    assert!(text.contains("string order_id = 1 [(buf.validate.field).string.uuid = true];"));
+    assert!(text.contains(
+        "string client_order_id = 2 [(buf.validate.field).string.min_len = 1, (buf.validate.field).string.max_len = 64];"
+    ));
+    assert!(text.contains("int64 item_count = 4 [(buf.validate.field).int64.gte = 1];"));
+    // Decimal bounds have no string rule and survive as a comment.
+    assert!(text.contains("string total = 3; //"));
+    assert!(text.contains("exclusive_min 0"));
+
+    let output = message(&proto, "PlaceOrderOutput");
+    assert_eq!(
+        output.fields[1].proto_type,
+        ProtoType::Enum("OrderStatus".to_string())
+    );
+    assert!(output
+        .to_proto_string()
+        .contains("(buf.validate.field).enum.defined_only = true"));
+
+    let status = proto
+        .enums
+        .iter()
+        .find(|e| e.name == "OrderStatus")
+        .expect("enum");
+    let values: Vec<_> = status.values.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(
+        values,
+        vec!["ORDER_STATUS_UNSPECIFIED", "ORDER_STATUS_PLACED"]
+    );
+}
+
+#[test]
+fn operations_become_rpcs_with_typed_failure_details() {
+    let proto = project(&flagship());
+    let service = proto
+        .services
+        .iter()
+        .find(|s| s.name == "OrderOperationsService")
+        .expect("operations service");
+    let rpc = &service.methods[0];
+    assert_eq!(rpc.name, "PlaceOrder");
+    assert_eq!(rpc.request_type, "PlaceOrderInput");
+    assert_eq!(rpc.response_type, "PlaceOrderOutput");
+    assert!(rpc
+        .to_proto_string()
+        .contains("option idempotency_level = IDEMPOTENT;"));
+    assert!(rpc
+        .comments
+        .iter()
+        .any(|c| c.contains("order_limit_exceeded (policy -> PERMISSION_DENIED)")));
+
+    let codes = proto
+        .enums
+        .iter()
+        .find(|e| e.name == "PlaceOrderFailureCode")
+        .expect("failure codes");
+    assert_eq!(codes.values.len(), 4);
+    let error = message(&proto, "PlaceOrderError");
+    assert_eq!(
+        error.fields[0].proto_type,
+        ProtoType::Enum("PlaceOrderFailureCode".to_string())
+    );
+    assert_eq!(
+        error.fields[1].proto_type,
+        ProtoType::Enum("FailureKind".to_string())
+    );
+}
+
+#[test]
+fn compatibility_checker_covers_contract_messages() {
+    let old = project(&flagship());
+    let new = project(&flagship().replace("    status: OrderStatus\n}", "}"));
+    let result = CompatibilityChecker::check(&old, &new, CompatibilityMode::Additive);
+    assert!(!result.is_compatible);
+    assert!(result.violations.iter().any(|v| {
+        v.violation_type == ViolationType::FieldRemoved && v.message_name == "PlaceOrderOutput"
+    }));
+}
+
+#[test]
+fn name_clashes_with_graph_messages_are_errors() {
+    let sources = sources(&flagship());
+    let graph = resolve_application_graph(ENTRY, &sources).unwrap();
+    let document = resolve_application_contract(ENTRY, &sources).unwrap();
+    let mut proto = ProtobufEngine::project(&graph, "", "sea.orders");
+    proto.messages.push(ProtoMessage::new("PlaceOrderInput"));
+    let err =
+        merge_application_contract(&mut proto, &graph, &document.contract, "").expect_err("clash");
+    assert!(err.contains("PlaceOrderInput"));
+}
+
+#[test]
+fn namespace_filter_and_standalone_file() {
+    let sources = sources(&flagship());
+    let graph = resolve_application_graph(ENTRY, &sources).unwrap();
+    let document = resolve_application_contract(ENTRY, &sources).unwrap();
+
+    let mut other = ProtoFile::new("sea.other");
+    merge_application_contract(&mut other, &graph, &document.contract, "billing").unwrap();
+    assert!(other.messages.is_empty() && other.services.is_empty());
+
+    let standalone = project_application_contract(&graph, &document.contract, "sea").unwrap();
+    assert_eq!(standalone.package, "sea.application_contract");
+    assert!(standalone.messages.iter().all(|m| m.name != "Order"));
+    assert_eq!(standalone.services.len(), 1);
+}
+
+#[test]
+fn same_named_declarations_across_namespaces_are_errors() {
+    let sources = json!({
+        "shipping.sea": "@namespace \"shipping\"\nimport { Customer } from \"billing\"\nrecord Address {\n    line: string\n}\n",
+        "billing.sea": "@namespace \"billing\"\nexport Entity \"Customer\"\nrecord Address {\n    line: string\n}\n",
+    })
+    .to_string();
+    let graph = resolve_application_graph("shipping.sea", &sources).unwrap();
+    let document = resolve_application_contract("shipping.sea", &sources).unwrap();
+
+    let err = project_application_contract(&graph, &document.contract, "sea")
+        .expect_err("two `message Address` definitions");
+    assert!(err.contains("Address"), "{err}");
+    assert!(err.contains("billing.record.Address"), "{err}");
+    assert!(err.contains("shipping.record.Address"), "{err}");
+
+    // One namespace at a time is unambiguous.
+    let mut shipping = ProtoFile::new("sea.shipping");
+    merge_application_contract(&mut shipping, &graph, &document.contract, "shipping").unwrap();
+    assert_eq!(
+        shipping
+            .messages
+            .iter()
+            .filter(|m| m.name == "Address")
+            .count(),
+        1
+    );
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn graph_only_model_projects_without_a_contract() {
+    let tmp = tempfile::tempdir().unwrap();
+    let model = tmp.path().join("model.sea");
+    std::fs::write(
+        &model,
+        "@namespace \"logistics\"\n\nEntity \"Factory\"\nEntity \"Warehouse\"\nResource \"Widget\" units\nFlow \"Widget\" from \"Factory\" to \"Warehouse\" quantity 10\n",
+    )
+    .unwrap();
+    let output = tmp.path().join("model.proto");
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("domainforge"))
+        .args(["project", "--format", "protobuf"])
+        .arg(&model)
+        .arg(&output)
+        .assert()
+        .success();
+    let proto = std::fs::read_to_string(&output).unwrap();
+    assert!(proto.contains("message Widget"));
+    assert!(!proto.contains(domainforge_core::projection::protobuf_contract::FAILURE_KIND_ENUM));
+    assert!(!proto.contains(PROTOVALIDATE_IMPORT));
+}
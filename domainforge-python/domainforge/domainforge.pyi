@@ -169,6 +169,22 @@ class Graph:
     @staticmethod
     def import_calm(calm_json: str) -> Graph: ...
 
+    @staticmethod
+    def resolve_workspace(
+        entry_logical_path: str,
+        sources_json: str,
+        registry_toml: Optional[str] = ...,
+    ) -> WorkspaceResolution: ...
+
+    def __repr__(self) -> str: ...
+
+class WorkspaceResolution:
+    entry: str
+    ok: bool
+    graph: Optional[Graph]
+    diagnostics_json: str
+    envelope_json: Optional[str]
+
     def __repr__(self) -> str: ...
 
 # =============================================================================
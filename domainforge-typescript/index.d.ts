@@ -219,6 +219,12 @@ export declare class Graph {
    * document JSON (ADR-013 Milestone 0).
    */
   static resolveApplicationContractJson(entryLogicalPath: string, sourcesJson: string): string
+  /**
+   * Resolve an in-memory workspace (entry module, import closure and an
+   * optional `.sea-registry.toml`) into its graph, semantic envelope and
+   * located diagnostics.
+   */
+  static resolveWorkspace(entryLogicalPath: string, sourcesJson: string, registryToml?: string | undefined | null): WorkspaceResolution
   addEntity(entity: Entity): void
   addResource(resource: Resource): void
   addFlow(flow: Flow): void
@@ -322,7 +328,7 @@ export declare class Graph {
    * @param createdAt - Optional fixed RFC3339 timestamp for reproducible output
    * @returns JSON object mapping relative artifact paths to file contents
    */
-  exportArchimate(modelRef?: string | undefined | null, createdAt?: string | undefined | null): string
+  exportArchimate(modelRef?: string | undefined | null, createdAt?: string | undefined | null, viewpoints?: Array<string> | undefined | null): string
   /**
    * Emit an OpenTelemetry SemConv projection (the `--format otel-semconv`
    * layout: registry/telemetry.yaml + constants/attributes.{rs,py,ts}).
@@ -555,6 +561,24 @@ export declare class NamespaceRegistry {
   get root(): string
   get defaultNamespace(): string
 }
+/**
+ * Result of `Graph.resolveWorkspace`: the graph and semantic envelope of an
+ * in-memory workspace, or the diagnostics that prevented them.
+ */
+export declare class WorkspaceResolution {
+  get entry(): string
+  /** True when the workspace resolved without diagnostics. */
+  get ok(): boolean
+  /** The resolved graph, or null when resolution failed. */
+  get graph(): Graph | null
+  /**
+   * Diagnostics as a JSON array; each carries `logical_module_id`, `line`
+   * and `column` in its `context` when the failure has a location.
+   */
+  get diagnosticsJson(): string
+  /** Canonical semantic envelope document JSON, or null when resolution failed. */
+  get envelopeJson(): string | null
+}
 export declare class Dimension {
   static parse(name: string): Dimension
   get name(): string
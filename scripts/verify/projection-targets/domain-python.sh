@@ -1,8 +1,8 @@
 #!/usr/bin/env bash
 # domain-python projection gate.
 #
-# Projects the projection-cell fixture to a complete Python DDD/CQRS package
-# and validates:
+# Projects the projection-cell fixture and the flagship application-contract
+# fixture to complete Python DDD/CQRS packages and validates each with:
 #   1. Structural checks (file set, key classes/methods).
 #   2. python -m compileall — every emitted .py byte-compiles.
 #   3. mypy --strict — the package typechecks with zero edits (if mypy on PATH;
@@ -16,8 +16,10 @@ REPO_ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/../../.." && pwd)"
 cd "$REPO_ROOT"
 
 FIXTURE="fixtures/projection_cell/basic/model.sea"
+CONTRACT_FIXTURE="fixtures/application_generation/flagship/command-write.sea"
 OUT="$(mktemp -d)"
-trap 'rm -rf "$OUT"' EXIT
+CONTRACT_OUT="$(mktemp -d)"
+trap 'rm -rf "$OUT" "$CONTRACT_OUT"' EXIT
 
 echo "==> project --format domain-python"
 cargo run -q -p domainforge-core --features cli -- project --format domain-python \
@@ -36,17 +38,31 @@ grep -q "class RequireApprovalViolation(DomainError)" "$PKG/domain/errors.py"
 grep -q "dependencies = \[\]" "$OUT/pyproject.toml"
 echo "  structural OK"
 
-echo "==> python -m compileall"
-python3 -m compileall -q "$OUT/src" "$OUT/tests"
+echo "==> project --format domain-python (application contract)"
+cargo run -q -p domainforge-core --features cli -- project --format domain-python \
+  --created-at '2026-07-02T00:00:00+00:00' "$CONTRACT_FIXTURE" "$CONTRACT_OUT"
+
+echo "==> validate generated contract package (structural)"
+CONTRACT_PKG="$CONTRACT_OUT/src/flagship_orders_domain"
+test -s "$CONTRACT_PKG/application/records.py"
+test -s "$CONTRACT_PKG/application/operations.py"
+test -s "$CONTRACT_OUT/tests/test_operations.py"
+grep -q "class PlaceOrderHandler" "$CONTRACT_PKG/application/operations.py"
+echo "  structural OK"
+
+for dir in "$OUT" "$CONTRACT_OUT"; do
+    echo "==> python -m compileall ($dir)"
+    python3 -m compileall -q "$dir/src" "$dir/tests"
 
-if command -v mypy >/dev/null 2>&1; then
-    echo "==> mypy --strict"
-    (cd "$OUT" && mypy --strict --explicit-package-bases src)
-else
-    echo "==> WARNING: mypy not found — skipping strict typecheck (CI installs it)"
-fi
+    if command -v mypy >/dev/null 2>&1; then
+        echo "==> mypy --strict"
+        (cd "$dir" && mypy --strict --explicit-package-bases src)
+    else
+        echo "==> WARNING: mypy not found — skipping strict typecheck (CI installs it)"
+    fi
 
-echo "==> python -m unittest (smoke test)"
-(cd "$OUT" && PYTHONPATH=src python3 -m unittest discover -s tests -p 'test_*.py')
+    echo "==> python -m unittest"
+    (cd "$dir" && PYTHONPATH=src python3 -m unittest discover -s tests -p 'test_*.py')
+done
 
 echo "==> domain-python gate OK"
@@ -1,8 +1,8 @@
 #!/usr/bin/env bash
 # domain-rust projection gate.
 #
-# Projects the projection-cell fixture to a complete zero-dependency Rust
-# DDD/CQRS crate and validates:
+# Projects the projection-cell fixture and the flagship application-contract
+# fixture to complete zero-dependency Rust DDD/CQRS crates and validates:
 #   1. Structural checks (file set, key traits/variants).
 #   2. cargo check --offline (zero-dep crate builds offline).
 #   3. cargo test (runs the smoke test: constructs commands/events/VOs,
@@ -22,8 +22,10 @@ TOOLCHAIN_BIN="$(dirname "$CARGO_BIN")"
 export PATH="$TOOLCHAIN_BIN:$PATH"
 
 FIXTURE="fixtures/projection_cell/basic/model.sea"
+CONTRACT_FIXTURE="fixtures/application_generation/flagship/command-write.sea"
 OUT="$(mktemp -d)"
-trap 'rm -rf "$OUT"' EXIT
+CONTRACT_OUT="$(mktemp -d)"
+trap 'rm -rf "$OUT" "$CONTRACT_OUT"' EXIT
 
 echo "==> project --format domain-rust"
 cargo run -q -p domainforge-core --features cli -- project --format domain-rust \
@@ -46,4 +48,19 @@ echo "==> cargo check --offline (zero-dep crate)"
 echo "==> cargo test (smoke test)"
 (cd "$OUT" && cargo test --offline)
 
+echo "==> project --format domain-rust (application contract)"
+cargo run -q -p domainforge-core --features cli -- project --format domain-rust \
+  --created-at '2026-07-02T00:00:00+00:00' "$CONTRACT_FIXTURE" "$CONTRACT_OUT"
+
+echo "==> validate generated contract crate (structural)"
+test -s "$CONTRACT_OUT/src/records.rs"
+test -s "$CONTRACT_OUT/src/operations.rs"
+test -s "$CONTRACT_OUT/tests/operations.rs"
+grep -q "pub struct PlaceOrderInput" "$CONTRACT_OUT/src/records.rs"
+grep -q "pub fn fingerprint" "$CONTRACT_OUT/src/records.rs"
+echo "  structural OK"
+
+echo "==> cargo test (application contract)"
+(cd "$CONTRACT_OUT" && cargo test --offline)
+
 echo "==> domain-rust gate OK"
@@ -1,8 +1,8 @@
 #!/usr/bin/env bash
 # domain-typescript projection gate.
 #
-# Projects the projection-cell fixture to a complete TypeScript DDD/CQRS package
-# and validates:
+# Projects the projection-cell fixture and the flagship application-contract
+# fixture to complete TypeScript DDD/CQRS packages and validates each with:
 #   1. Structural checks (file set, key interfaces/classes).
 #   2. tsc --noEmit (strict) — the package typechecks with zero edits. Uses the
 #      local typescript install if present, else `npx -y -p typescript tsc`; if
@@ -15,8 +15,10 @@ REPO_ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/../../.." && pwd)"
 cd "$REPO_ROOT"
 
 FIXTURE="fixtures/projection_cell/basic/model.sea"
+CONTRACT_FIXTURE="fixtures/application_generation/flagship/command-write.sea"
 OUT="$(mktemp -d)"
-trap 'rm -rf "$OUT"' EXIT
+CONTRACT_OUT="$(mktemp -d)"
+trap 'rm -rf "$OUT" "$CONTRACT_OUT"' EXIT
 
 echo "==> project --format domain-typescript"
 cargo run -q -p domainforge-core --features cli -- project --format domain-typescript \
@@ -34,6 +36,17 @@ grep -q "class RequireApprovalViolation extends DomainError" "$OUT/src/domain/er
 ! grep -q '"dependencies"' "$OUT/package.json"
 echo "  structural OK"
 
+echo "==> project --format domain-typescript (application contract)"
+cargo run -q -p domainforge-core --features cli -- project --format domain-typescript \
+  --created-at '2026-07-02T00:00:00+00:00' "$CONTRACT_FIXTURE" "$CONTRACT_OUT"
+
+echo "==> validate generated contract package (structural)"
+test -s "$CONTRACT_OUT/src/application/records.ts"
+test -s "$CONTRACT_OUT/src/application/operations.ts"
+test -s "$CONTRACT_OUT/src/operationsSmoke.ts"
+grep -q "interface PlaceOrderPort" "$CONTRACT_OUT/src/application/operations.ts"
+echo "  structural OK"
+
 # Pick a TypeScript compiler: local install > npx > skip.
 TSC_BIN=""
 if [ -x "$(command -v tsc)" ]; then
@@ -43,8 +56,10 @@ elif command -v npx >/dev/null 2>&1; then
 fi
 
 if [ -n "$TSC_BIN" ]; then
-    echo "==> tsc --noEmit (strict)"
-    (cd "$OUT" && $TSC_BIN --noEmit -p .)
+    for dir in "$OUT" "$CONTRACT_OUT"; do
+        echo "==> tsc --noEmit (strict) ($dir)"
+        (cd "$dir" && $TSC_BIN --noEmit -p .)
+    done
     echo "  tsc OK"
 else
     echo "==> WARNING: tsc/npx not found — skipping strict typecheck (CI installs typescript)"
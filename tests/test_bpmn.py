@@ -35,6 +35,7 @@ def test_export_bpmn_single_file_manifest():
     assert 'gatewayDirection="Converging"' in bpmn
     assert 'name="CameraUnits"' in bpmn  # data object
     assert 'name="Operator"' in bpmn  # lane
+    assert "<bpmndi:BPMNDiagram" in bpmn  # layout
 
 
 def test_export_bpmn_is_deterministic():
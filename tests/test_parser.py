@@ -126,3 +126,44 @@ def test_resolve_application_contract_json_rejects_bad_source_map():
     with pytest.raises(ValueError) as err:
         domainforge.Graph.resolve_application_contract_json("a.sea", "[]")
     assert "APP" in str(err.value)
+
+
+_REGISTRY_TOML = """
+version = 1
+
+[[namespaces]]
+namespace = "orders"
+patterns = ["domains/orders/*.sea"]
+"""
+
+
+def test_resolve_workspace_resolves_imports_through_registry():
+    import json
+
+    sources = json.dumps(
+        {
+            "app.sea": '@namespace "app"\nimport { Customer } from "orders"\nEntity "Shop"\n',
+            "domains/orders/customer.sea": 'export Entity "Customer"\n',
+        }
+    )
+    resolution = domainforge.Graph.resolve_workspace(
+        "app.sea", sources, registry_toml=_REGISTRY_TOML
+    )
+    assert resolution.ok
+    assert json.loads(resolution.diagnostics_json) == []
+    assert resolution.graph.find_entity_by_name("Customer") is not None
+    envelope = json.loads(resolution.envelope_json)
+    assert envelope["schema_version"] == "domainforge-semantic-envelope/v1"
+
+
+def test_resolve_workspace_reports_located_diagnostics():
+    import json
+
+    sources = json.dumps({"app.sea": 'Entity "Shop"\n\nEntity Broken\n'})
+    resolution = domainforge.Graph.resolve_workspace("app.sea", sources)
+    assert not resolution.ok
+    assert resolution.graph is None
+    assert resolution.envelope_json is None
+    context = json.loads(resolution.diagnostics_json)[0]["context"]
+    assert context["logical_module_id"] == "app.sea"
+    assert context["line"] == 3
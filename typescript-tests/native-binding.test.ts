@@ -43,4 +43,27 @@ describe('application contract binding (ADR-013 Milestone 0)', () => {
     it('reports diagnostics for a malformed source map', () => {
         expect(() => Graph.resolveApplicationContractJson('a.sea', '[]')).toThrowError(/APP/);
     });
+
+    it('resolves an in-memory workspace with a namespace registry', () => {
+        const registryToml = 'version = 1\n[[namespaces]]\nnamespace = "orders"\npatterns = ["domains/orders/*.sea"]\n';
+        const resolution = Graph.resolveWorkspace(
+            'app.sea',
+            JSON.stringify({
+                'app.sea': '@namespace "app"\nimport { Customer } from "orders"\nEntity "Shop"\n',
+                'domains/orders/customer.sea': 'export Entity "Customer"\n',
+            }),
+            registryToml,
+        );
+        expect(resolution.ok).toBe(true);
+        expect(resolution.graph?.findEntityByName('Customer')).toBeTruthy();
+        expect(JSON.parse(resolution.envelopeJson!).schema_version).toBe('domainforge-semantic-envelope/v1');
+    });
+
+    it('locates workspace diagnostics by module and line', () => {
+        const resolution = Graph.resolveWorkspace('app.sea', JSON.stringify({ 'app.sea': 'Entity "Shop"\n\nEntity Broken\n' }));
+        expect(resolution.ok).toBe(false);
+        expect(resolution.graph).toBeNull();
+        const [diagnostic] = JSON.parse(resolution.diagnosticsJson);
+        expect(diagnostic.context).toMatchObject({ logical_module_id: 'app.sea', line: 3 });
+    });
 });